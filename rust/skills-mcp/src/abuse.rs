@@ -0,0 +1,236 @@
+//! Detection of anomalous skill access patterns.
+//!
+//! Tracks recent reads per caller in a sliding window and flags two
+//! patterns: one caller hammering a single skill, and one caller
+//! enumerating many distinct skills. Each pattern raises an alert once per
+//! window, and callers can optionally be throttled while flagged.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::notify::{Alert, Notifiers, Severity};
+
+/// Thresholds for abuse detection.
+#[derive(Debug, Clone, Serialize)]
+pub struct AbuseConfig {
+    /// Sliding window length in seconds.
+    pub window_secs: u64,
+
+    /// Reads of one skill by one caller within the window before flagging.
+    pub max_reads_per_skill: usize,
+
+    /// Distinct skills read by one caller within the window before flagging.
+    pub max_distinct_skills: usize,
+
+    /// Reject reads from flagged callers until the window passes.
+    pub throttle: bool,
+}
+
+impl Default for AbuseConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 60,
+            max_reads_per_skill: 60,
+            max_distinct_skills: 50,
+            throttle: false,
+        }
+    }
+}
+
+/// Outcome of checking a read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessVerdict {
+    /// The read may proceed.
+    Allow,
+    /// The caller is throttled; retry after the given duration.
+    Throttle(Duration),
+}
+
+#[derive(Default)]
+struct CallerActivity {
+    reads: VecDeque<(Instant, String)>,
+    flagged_until: Option<Instant>,
+    alerted: HashSet<&'static str>,
+}
+
+/// Number of tracked callers above which idle callers are pruned.
+const MAX_TRACKED_CALLERS: usize = 10_000;
+
+/// Sliding-window access monitor.
+pub struct AbuseDetector {
+    config: Mutex<AbuseConfig>,
+    callers: Mutex<HashMap<String, CallerActivity>>,
+    notifiers: Arc<Notifiers>,
+}
+
+impl AbuseDetector {
+    /// Create a detector that alerts through `notifiers`.
+    pub fn new(config: AbuseConfig, notifiers: Arc<Notifiers>) -> Self {
+        Self {
+            config: Mutex::new(config),
+            callers: Mutex::new(HashMap::new()),
+            notifiers,
+        }
+    }
+
+    /// Replace the detection thresholds.
+    pub fn configure(&self, config: AbuseConfig) {
+        *self.config.lock() = config;
+    }
+
+    /// Get the current thresholds.
+    pub fn config(&self) -> AbuseConfig {
+        self.config.lock().clone()
+    }
+
+    /// Record a read of `skill` by `caller` and decide whether to allow it.
+    pub fn check(&self, caller: &str, skill: &str) -> AccessVerdict {
+        self.check_at(caller, skill, Instant::now())
+    }
+
+    fn check_at(&self, caller: &str, skill: &str, now: Instant) -> AccessVerdict {
+        let config = self.config();
+        let window = Duration::from_secs(config.window_secs);
+
+        let mut callers = self.callers.lock();
+        if callers.len() >= MAX_TRACKED_CALLERS {
+            callers.retain(|_, a| {
+                a.flagged_until.is_some_and(|until| now < until)
+                    || a.reads.back().is_some_and(|(at, _)| now.duration_since(*at) <= window)
+            });
+        }
+        let activity = callers.entry(caller.to_string()).or_default();
+
+        if let Some(until) = activity.flagged_until {
+            if now < until {
+                if config.throttle {
+                    return AccessVerdict::Throttle(until - now);
+                }
+            } else {
+                activity.flagged_until = None;
+                activity.alerted.clear();
+            }
+        }
+
+        // Drop reads that have left the window
+        while let Some((at, _)) = activity.reads.front() {
+            if now.duration_since(*at) <= window {
+                break;
+            }
+            activity.reads.pop_front();
+        }
+        activity.reads.push_back((now, skill.to_string()));
+
+        let same_skill = activity.reads.iter().filter(|(_, s)| s == skill).count();
+        let distinct: HashSet<&str> = activity.reads.iter().map(|(_, s)| s.as_str()).collect();
+
+        let mut alerts = Vec::new();
+        if same_skill > config.max_reads_per_skill && activity.alerted.insert("skill_hammering") {
+            alerts.push(Alert::new(
+                "abuse.skill_hammering",
+                Severity::Warning,
+                format!(
+                    "Caller '{}' read skill '{}' {} times in {}s",
+                    caller, skill, same_skill, config.window_secs
+                ),
+            ));
+        }
+        if distinct.len() > config.max_distinct_skills && activity.alerted.insert("enumeration") {
+            alerts.push(Alert::new(
+                "abuse.enumeration",
+                Severity::Warning,
+                format!(
+                    "Caller '{}' read {} distinct skills in {}s",
+                    caller,
+                    distinct.len(),
+                    config.window_secs
+                ),
+            ));
+        }
+
+        if alerts.is_empty() {
+            return AccessVerdict::Allow;
+        }
+
+        activity.flagged_until = Some(now + window);
+        drop(callers);
+
+        for alert in &alerts {
+            self.notifiers.send(alert);
+        }
+
+        if config.throttle {
+            AccessVerdict::Throttle(window)
+        } else {
+            AccessVerdict::Allow
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::Notifier;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Notifier for Recorder {
+        fn notify(&self, alert: &Alert) {
+            self.0.lock().push(alert.kind.clone());
+        }
+    }
+
+    fn detector(throttle: bool) -> (AbuseDetector, Arc<Recorder>) {
+        let recorder = Arc::new(Recorder::default());
+        let notifiers = Arc::new(Notifiers::new());
+        notifiers.register(recorder.clone());
+
+        let config = AbuseConfig {
+            window_secs: 10,
+            max_reads_per_skill: 3,
+            max_distinct_skills: 3,
+            throttle,
+        };
+        (AbuseDetector::new(config, notifiers), recorder)
+    }
+
+    #[test]
+    fn test_hammering_alerts_once_and_throttles() {
+        let (detector, recorder) = detector(true);
+        let start = Instant::now();
+
+        for i in 0..3 {
+            let at = start + Duration::from_secs(i);
+            assert_eq!(detector.check_at("key:a", "forms", at), AccessVerdict::Allow);
+        }
+        let verdict = detector.check_at("key:a", "forms", start + Duration::from_secs(3));
+        assert!(matches!(verdict, AccessVerdict::Throttle(_)));
+
+        // Still throttled within the window, with no duplicate alert
+        let verdict = detector.check_at("key:a", "forms", start + Duration::from_secs(5));
+        assert!(matches!(verdict, AccessVerdict::Throttle(_)));
+        assert_eq!(*recorder.0.lock(), vec!["abuse.skill_hammering"]);
+
+        // Other callers are unaffected, and the flag expires
+        assert_eq!(detector.check_at("key:b", "forms", start), AccessVerdict::Allow);
+        let later = start + Duration::from_secs(30);
+        assert_eq!(detector.check_at("key:a", "forms", later), AccessVerdict::Allow);
+    }
+
+    #[test]
+    fn test_enumeration_alerts_without_throttling() {
+        let (detector, recorder) = detector(false);
+        let now = Instant::now();
+
+        for skill in ["a", "b", "c", "d"] {
+            assert_eq!(detector.check_at("session:x", skill, now), AccessVerdict::Allow);
+        }
+
+        assert_eq!(*recorder.0.lock(), vec!["abuse.enumeration"]);
+    }
+}
@@ -0,0 +1,384 @@
+//! Skill quality scores, for prioritizing cleanup work.
+//!
+//! Each skill is scored out of 100 on three things:
+//!
+//! - **Completeness** (60): it has a description, tags, code examples,
+//!   reference files, and, if it has sub-skills, triggers on every one.
+//! - **Freshness** (20): its files changed within `stale_days`.
+//! - **Size** (20): its files total between `min_tokens` and `max_tokens`;
+//!   outside that the points shrink in proportion.
+//!
+//! Thresholds come from the `[quality]` section of `skills-mcp.toml`:
+//!
+//! ```toml
+//! [quality]
+//! stale_days = 180
+//! min_tokens = 50
+//! max_tokens = 8000
+//! ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::index::SkillIndexer;
+use crate::markdown::estimate_tokens;
+use crate::models::SkillMeta;
+
+/// Points for completeness, shared evenly by the checks that apply.
+const COMPLETENESS_POINTS: f64 = 60.0;
+
+/// Points for having changed within `stale_days`.
+const FRESHNESS_POINTS: f64 = 20.0;
+
+/// Points for a size within bounds.
+const SIZE_POINTS: f64 = 20.0;
+
+/// Quality settings from the `[quality]` configuration section.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct QualityConfig {
+    /// Days without changes after which a skill is stale.
+    pub stale_days: u64,
+
+    /// Tokens below which a skill is too thin.
+    pub min_tokens: u64,
+
+    /// Tokens above which a skill is oversized.
+    pub max_tokens: u64,
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            stale_days: 180,
+            min_tokens: 50,
+            max_tokens: 8000,
+        }
+    }
+}
+
+/// Which completeness checks a skill passes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Completeness {
+    /// Non-empty description.
+    pub description: bool,
+    /// At least one tag.
+    pub tags: bool,
+    /// At least one fenced code block.
+    pub examples: bool,
+    /// At least one file under `references/`.
+    pub references: bool,
+    /// Every sub-skill declares triggers; None without sub-skills.
+    pub triggers: Option<bool>,
+}
+
+impl Completeness {
+    /// Share of the applicable checks passed, from 0 to 1.
+    pub fn ratio(&self) -> f64 {
+        let checks = [
+            Some(self.description),
+            Some(self.tags),
+            Some(self.examples),
+            Some(self.references),
+            self.triggers,
+        ];
+        let applicable = checks.iter().flatten().count();
+        let passed = checks.iter().flatten().filter(|c| **c).count();
+        passed as f64 / applicable as f64
+    }
+}
+
+/// Quality of one skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillQuality {
+    /// Skill name.
+    pub skill: String,
+
+    /// Overall score out of 100.
+    pub score: u32,
+
+    /// Completeness checks.
+    pub completeness: Completeness,
+
+    /// Latest file modification.
+    #[serde(with = "crate::models::timestamp::option", skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+
+    /// Whether it hasn't changed within `stale_days`.
+    pub stale: bool,
+
+    /// Estimated tokens across SKILL.md and its other indexed files.
+    pub tokens: u64,
+
+    /// Whether it's over `max_tokens`.
+    pub oversized: bool,
+
+    /// Whether it's under `min_tokens`.
+    pub thin: bool,
+
+    /// What would raise the score, most valuable first.
+    pub issues: Vec<String>,
+}
+
+/// How many skills pass each completeness check.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Coverage {
+    /// Skills with a description.
+    pub description: usize,
+    /// Skills with tags.
+    pub tags: usize,
+    /// Skills with code examples.
+    pub examples: usize,
+    /// Skills with reference files.
+    pub references: usize,
+    /// Skills with sub-skills that all declare triggers.
+    pub triggers: usize,
+    /// Skills with sub-skills.
+    pub with_sub_skills: usize,
+}
+
+/// Quality of the whole library.
+#[derive(Debug, Clone, Serialize)]
+pub struct QualityReport {
+    /// When the report was built.
+    #[serde(with = "crate::models::timestamp")]
+    pub generated_at: DateTime<Utc>,
+
+    /// Thresholds the skills were scored against.
+    pub config: QualityReportConfig,
+
+    /// Skills scored.
+    pub skills_checked: usize,
+
+    /// Mean score, 0 without skills.
+    pub average_score: f64,
+
+    /// Stale skills.
+    pub stale: usize,
+
+    /// Oversized skills.
+    pub oversized: usize,
+
+    /// Thin skills.
+    pub thin: usize,
+
+    /// Skills passing each completeness check.
+    pub coverage: Coverage,
+
+    /// Skills, lowest score first.
+    pub skills: Vec<SkillQuality>,
+}
+
+/// Thresholds in a report.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct QualityReportConfig {
+    /// Days without changes after which a skill is stale.
+    pub stale_days: u64,
+    /// Tokens below which a skill is too thin.
+    pub min_tokens: u64,
+    /// Tokens above which a skill is oversized.
+    pub max_tokens: u64,
+}
+
+/// Score every indexed skill as of `now`.
+pub fn quality_report(indexer: &SkillIndexer, config: &QualityConfig, now: DateTime<Utc>) -> QualityReport {
+    let index = indexer.get_skill_index();
+    let content_index = indexer.get_content_index();
+
+    let mut skills: Vec<SkillQuality> = index
+        .skills
+        .iter()
+        .map(|meta| {
+            let entries = content_index.get_domain_entries(&meta.name);
+            let examples = entries.iter().any(|e| !e.code_blocks.is_empty());
+            let tokens = entries.iter().map(|e| estimate_tokens(&e.content)).sum();
+            let references = !indexer.list_references(&meta.name).is_empty();
+            score_skill(meta, examples, references, tokens, config, now)
+        })
+        .collect();
+    skills.sort_by(|a, b| a.score.cmp(&b.score).then_with(|| a.skill.cmp(&b.skill)));
+
+    let mut coverage = Coverage::default();
+    for skill in &skills {
+        let c = &skill.completeness;
+        coverage.description += usize::from(c.description);
+        coverage.tags += usize::from(c.tags);
+        coverage.examples += usize::from(c.examples);
+        coverage.references += usize::from(c.references);
+        coverage.triggers += usize::from(c.triggers == Some(true));
+        coverage.with_sub_skills += usize::from(c.triggers.is_some());
+    }
+
+    let average_score = if skills.is_empty() {
+        0.0
+    } else {
+        skills.iter().map(|s| f64::from(s.score)).sum::<f64>() / skills.len() as f64
+    };
+
+    QualityReport {
+        generated_at: now,
+        config: QualityReportConfig {
+            stale_days: config.stale_days,
+            min_tokens: config.min_tokens,
+            max_tokens: config.max_tokens,
+        },
+        skills_checked: skills.len(),
+        average_score,
+        stale: skills.iter().filter(|s| s.stale).count(),
+        oversized: skills.iter().filter(|s| s.oversized).count(),
+        thin: skills.iter().filter(|s| s.thin).count(),
+        coverage,
+        skills,
+    }
+}
+
+fn score_skill(
+    meta: &SkillMeta,
+    examples: bool,
+    references: bool,
+    tokens: u64,
+    config: &QualityConfig,
+    now: DateTime<Utc>,
+) -> SkillQuality {
+    let completeness = Completeness {
+        description: !meta.description.trim().is_empty(),
+        tags: !meta.tags.is_empty(),
+        examples,
+        references,
+        triggers: meta
+            .sub_skills
+            .as_ref()
+            .filter(|subs| !subs.is_empty())
+            .map(|subs| subs.iter().all(|s| !s.triggers.is_empty())),
+    };
+
+    // Skills without file times can't be judged stale
+    let stale = meta
+        .updated_at
+        .is_some_and(|updated| (now - updated).num_days() >= config.stale_days as i64);
+    let oversized = tokens > config.max_tokens;
+    let thin = tokens < config.min_tokens;
+    let size_ratio = if oversized {
+        config.max_tokens as f64 / tokens as f64
+    } else if thin {
+        tokens as f64 / config.min_tokens as f64
+    } else {
+        1.0
+    };
+
+    let score = COMPLETENESS_POINTS * completeness.ratio()
+        + if stale { 0.0 } else { FRESHNESS_POINTS }
+        + SIZE_POINTS * size_ratio;
+
+    let mut issues = Vec::new();
+    if !completeness.description {
+        issues.push("add a description".to_string());
+    }
+    if !completeness.tags {
+        issues.push("add tags".to_string());
+    }
+    if !completeness.examples {
+        issues.push("add code examples".to_string());
+    }
+    if !completeness.references {
+        issues.push("add reference files".to_string());
+    }
+    if completeness.triggers == Some(false) {
+        issues.push("add triggers to every sub-skill".to_string());
+    }
+    if stale {
+        issues.push(format!("review: unchanged for over {} days", config.stale_days));
+    }
+    if oversized {
+        issues.push(format!("split: {} tokens, over {}", tokens, config.max_tokens));
+    }
+    if thin {
+        issues.push(format!("expand: {} tokens, under {}", tokens, config.min_tokens));
+    }
+
+    SkillQuality {
+        skill: meta.name.clone(),
+        score: score.round() as u32,
+        completeness,
+        updated_at: meta.updated_at,
+        stale,
+        tokens,
+        oversized,
+        thin,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_skill;
+    use chrono::Duration;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_quality_report() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "complete",
+            r#"{"name": "complete", "description": "Everything", "tags": ["x"],
+                "sub_skills": [{"name": "a", "file": "a.md", "triggers": ["a"]}]}"#,
+            &format!("# Complete\n\n{}\n\n```rust\nfn main() {{}}\n```\n", "Words here. ".repeat(30)),
+        );
+        let complete = temp_dir.path().join("complete");
+        fs::create_dir_all(complete.join("references")).unwrap();
+        fs::write(complete.join("a.md"), "# A\n").unwrap();
+        fs::write(complete.join("references/guide.md"), "# Guide\n").unwrap();
+
+        write_skill(
+            temp_dir.path(),
+            "bare",
+            r#"{"name": "bare", "description": "Bare", "sub_skills": [{"name": "b", "file": "b.md"}]}"#,
+            "# Bare\n",
+        );
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+
+        let report = quality_report(&indexer, &QualityConfig::default(), Utc::now());
+        assert_eq!(report.skills_checked, 2);
+        let [bare, complete] = [&report.skills[0], &report.skills[1]];
+        assert_eq!(complete.skill, "complete");
+        assert_eq!(complete.score, 100);
+        assert!(complete.issues.is_empty());
+
+        // 1 of 5 checks, fresh, and a fraction of the size points
+        assert_eq!(bare.skill, "bare");
+        assert_eq!(bare.completeness.triggers, Some(false));
+        assert!(bare.thin);
+        let size = (SIZE_POINTS * bare.tokens as f64 / 50.0).round() as u32;
+        assert_eq!(bare.score, 12 + 20 + size);
+        assert_eq!(bare.issues[0], "add tags");
+
+        assert_eq!(
+            report.coverage,
+            Coverage {
+                description: 2,
+                tags: 1,
+                examples: 1,
+                references: 1,
+                triggers: 1,
+                with_sub_skills: 2,
+            }
+        );
+
+        let later = Utc::now() + Duration::days(200);
+        let config = QualityConfig {
+            max_tokens: 100,
+            ..Default::default()
+        };
+        let report = quality_report(&indexer, &config, later);
+        assert_eq!(report.stale, 2);
+        assert_eq!(report.oversized, 1);
+        let complete = report.skills.iter().find(|s| s.skill == "complete").unwrap();
+        assert!(complete.score < 80);
+        assert!(complete.issues[0].starts_with("review"));
+    }
+}
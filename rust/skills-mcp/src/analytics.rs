@@ -0,0 +1,389 @@
+//! Persistent usage analytics: tool calls, skill reads, and search queries.
+//!
+//! Unlike [`UsageStats`](crate::models::UsageStats), which covers the
+//! current process only, analytics can be kept in a small JSON store so
+//! counts survive restarts. The store is rewritten every
+//! [`Analytics::FLUSH_EVERY`] recorded events and on [`Analytics::flush`].
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::models::SkillIndex;
+
+/// Default store file name, kept at the root of the skills directory.
+pub const STORE_FILE: &str = ".skills-analytics.json";
+
+/// Read counters for one skill.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ReadCounter {
+    count: u64,
+    last_read: Option<DateTime<Utc>>,
+}
+
+/// Counters for one normalized search query.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct QueryCounter {
+    count: u64,
+    zero_results: u64,
+    last_searched: Option<DateTime<Utc>>,
+}
+
+/// Everything persisted in the store.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AnalyticsData {
+    since: DateTime<Utc>,
+    #[serde(default)]
+    tool_calls: HashMap<String, u64>,
+    #[serde(default)]
+    skill_reads: HashMap<String, ReadCounter>,
+    #[serde(default)]
+    queries: HashMap<String, QueryCounter>,
+}
+
+impl Default for AnalyticsData {
+    fn default() -> Self {
+        Self {
+            since: Utc::now(),
+            tool_calls: HashMap::new(),
+            skill_reads: HashMap::new(),
+            queries: HashMap::new(),
+        }
+    }
+}
+
+/// Read count for one skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillReadCount {
+    /// Skill name.
+    pub skill: String,
+
+    /// Number of reads.
+    pub reads: u64,
+
+    /// When the skill was last read.
+    #[serde(skip_serializing_if = "Option::is_none", with = "crate::models::timestamp::option")]
+    pub last_read: Option<DateTime<Utc>>,
+}
+
+/// Counts for one search query.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryCount {
+    /// Normalized query (lowercased, whitespace collapsed).
+    pub query: String,
+
+    /// Times the query was searched.
+    pub count: u64,
+
+    /// Times the query returned no results.
+    pub zero_results: u64,
+}
+
+/// Usage report served at `/api/stats`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticsReport {
+    /// When recording started.
+    #[serde(with = "crate::models::timestamp")]
+    pub since: DateTime<Utc>,
+
+    /// Total skill reads.
+    pub total_reads: u64,
+
+    /// Total searches.
+    pub total_searches: u64,
+
+    /// Calls per tool.
+    pub tool_calls: HashMap<String, u64>,
+
+    /// Read counts per skill, most read first.
+    pub skills: Vec<SkillReadCount>,
+
+    /// Most frequent queries.
+    pub top_queries: Vec<QueryCount>,
+
+    /// Most frequent queries that returned nothing.
+    pub zero_result_queries: Vec<QueryCount>,
+
+    /// Indexed skills that have never been read.
+    pub never_read: Vec<String>,
+}
+
+/// Usage analytics recorder with an optional JSON store.
+#[derive(Debug, Default)]
+pub struct Analytics {
+    data: Mutex<AnalyticsData>,
+    store: Mutex<Option<PathBuf>>,
+    pending: Mutex<usize>,
+}
+
+impl Analytics {
+    /// Recorded events between automatic store writes.
+    pub const FLUSH_EVERY: usize = 20;
+
+    /// Distinct queries retained; the least searched are dropped beyond this.
+    const MAX_QUERIES: usize = 10_000;
+
+    /// Create an in-memory recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Persist to `path`, loading any counts already stored there.
+    pub fn persist_to(&self, path: impl Into<PathBuf>) -> Result<(), AnalyticsError> {
+        let path = path.into();
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            let stored: AnalyticsData = serde_json::from_str(&content)?;
+            self.data.lock().merge(stored);
+        }
+        *self.store.lock() = Some(path);
+        Ok(())
+    }
+
+    /// Path of the store, if persistent.
+    pub fn store_path(&self) -> Option<PathBuf> {
+        self.store.lock().clone()
+    }
+
+    /// Record a tool invocation.
+    pub fn record_tool_call(&self, tool: &str) {
+        *self.data.lock().tool_calls.entry(tool.to_string()).or_default() += 1;
+        self.recorded();
+    }
+
+    /// Record a read of a skill.
+    pub fn record_read(&self, skill: &str) {
+        {
+            let mut data = self.data.lock();
+            let counter = data.skill_reads.entry(skill.to_string()).or_default();
+            counter.count += 1;
+            counter.last_read = Some(Utc::now());
+        }
+        self.recorded();
+    }
+
+    /// Record a search and how many results it returned.
+    pub fn record_search(&self, query: &str, result_count: usize) {
+        let query = normalize_query(query);
+        if query.is_empty() {
+            return;
+        }
+
+        {
+            let mut data = self.data.lock();
+            let counter = data.queries.entry(query).or_default();
+            counter.count += 1;
+            if result_count == 0 {
+                counter.zero_results += 1;
+            }
+            counter.last_searched = Some(Utc::now());
+
+            if data.queries.len() > Self::MAX_QUERIES {
+                if let Some(rarest) = data
+                    .queries
+                    .iter()
+                    .min_by_key(|(_, c)| (c.count, c.last_searched))
+                    .map(|(q, _)| q.clone())
+                {
+                    data.queries.remove(&rarest);
+                }
+            }
+        }
+        self.recorded();
+    }
+
+    /// Build a report, listing at most `limit` queries in each query list.
+    pub fn report(&self, index: &SkillIndex, limit: usize) -> AnalyticsReport {
+        let data = self.data.lock();
+
+        let mut skills: Vec<SkillReadCount> = data
+            .skill_reads
+            .iter()
+            .map(|(skill, counter)| SkillReadCount {
+                skill: skill.clone(),
+                reads: counter.count,
+                last_read: counter.last_read,
+            })
+            .collect();
+        skills.sort_by(|a, b| b.reads.cmp(&a.reads).then_with(|| a.skill.cmp(&b.skill)));
+
+        let mut queries: Vec<QueryCount> = data
+            .queries
+            .iter()
+            .map(|(query, counter)| QueryCount {
+                query: query.clone(),
+                count: counter.count,
+                zero_results: counter.zero_results,
+            })
+            .collect();
+        queries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.query.cmp(&b.query)));
+
+        let mut zero_result_queries: Vec<QueryCount> =
+            queries.iter().filter(|q| q.zero_results > 0).cloned().collect();
+        zero_result_queries.sort_by(|a, b| {
+            b.zero_results
+                .cmp(&a.zero_results)
+                .then_with(|| a.query.cmp(&b.query))
+        });
+        zero_result_queries.truncate(limit);
+        queries.truncate(limit);
+
+        let never_read = index
+            .skills
+            .iter()
+            .filter(|s| !data.skill_reads.contains_key(&s.name))
+            .map(|s| s.name.clone())
+            .collect();
+
+        AnalyticsReport {
+            since: data.since,
+            total_reads: data.skill_reads.values().map(|c| c.count).sum(),
+            total_searches: data.queries.values().map(|c| c.count).sum(),
+            tool_calls: data.tool_calls.clone(),
+            skills,
+            top_queries: queries,
+            zero_result_queries,
+            never_read,
+        }
+    }
+
+    /// Write the store now. Does nothing when running in memory.
+    pub fn flush(&self) -> Result<(), AnalyticsError> {
+        let Some(path) = self.store_path() else {
+            return Ok(());
+        };
+        let json = serde_json::to_string(&*self.data.lock())?;
+        write_atomic(&path, &json)?;
+        *self.pending.lock() = 0;
+        Ok(())
+    }
+
+    /// Count an event and write the store when enough have accumulated.
+    fn recorded(&self) {
+        let due = {
+            let mut pending = self.pending.lock();
+            *pending += 1;
+            *pending >= Self::FLUSH_EVERY
+        };
+        if due {
+            if let Err(e) = self.flush() {
+                tracing::warn!("Failed to write analytics store: {}", e);
+            }
+        }
+    }
+}
+
+impl AnalyticsData {
+    /// Add stored counts to the in-memory ones.
+    fn merge(&mut self, stored: AnalyticsData) {
+        self.since = self.since.min(stored.since);
+        for (tool, count) in stored.tool_calls {
+            *self.tool_calls.entry(tool).or_default() += count;
+        }
+        for (skill, counter) in stored.skill_reads {
+            let entry = self.skill_reads.entry(skill).or_default();
+            entry.count += counter.count;
+            entry.last_read = entry.last_read.max(counter.last_read);
+        }
+        for (query, counter) in stored.queries {
+            let entry = self.queries.entry(query).or_default();
+            entry.count += counter.count;
+            entry.zero_results += counter.zero_results;
+            entry.last_searched = entry.last_searched.max(counter.last_searched);
+        }
+    }
+}
+
+/// Lowercase a query and collapse its whitespace.
+fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Write via a temporary file so a crash never leaves a truncated store.
+fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)
+}
+
+/// Analytics store errors.
+#[derive(Debug, thiserror::Error)]
+pub enum AnalyticsError {
+    /// The store couldn't be read or written.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The store isn't valid JSON.
+    #[error("Invalid analytics store: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SkillMeta;
+    use tempfile::TempDir;
+
+    fn index(names: &[&str]) -> SkillIndex {
+        let skills = names
+            .iter()
+            .map(|name| SkillMeta {
+                name: name.to_string(),
+                description: "Skill".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        SkillIndex::with_skills(skills, vec![])
+    }
+
+    #[test]
+    fn test_report() {
+        let analytics = Analytics::new();
+        analytics.record_read("forms");
+        analytics.record_read("forms");
+        analytics.record_tool_call("get_skill");
+        analytics.record_search("Form  Validation", 2);
+        analytics.record_search("form validation", 0);
+        analytics.record_search("kubernetes", 0);
+
+        let report = analytics.report(&index(&["forms", "testing"]), 10);
+        assert_eq!(report.total_reads, 2);
+        assert_eq!(report.total_searches, 3);
+        assert_eq!(report.skills[0].skill, "forms");
+        assert_eq!(report.top_queries[0].query, "form validation");
+        assert_eq!(report.top_queries[0].count, 2);
+        assert_eq!(report.zero_result_queries.len(), 2);
+        assert_eq!(report.never_read, vec!["testing"]);
+        assert_eq!(report.tool_calls["get_skill"], 1);
+    }
+
+    #[test]
+    fn test_store_survives_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(STORE_FILE);
+
+        let analytics = Analytics::new();
+        analytics.persist_to(&path).unwrap();
+        analytics.record_read("forms");
+        analytics.flush().unwrap();
+
+        let reopened = Analytics::new();
+        reopened.persist_to(&path).unwrap();
+        reopened.record_read("forms");
+        assert_eq!(reopened.report(&index(&["forms"]), 10).skills[0].reads, 2);
+
+        fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            Analytics::new().persist_to(&path),
+            Err(AnalyticsError::Parse(_))
+        ));
+    }
+}
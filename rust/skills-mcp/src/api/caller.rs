@@ -0,0 +1,145 @@
+//! Caller identification for API requests.
+//!
+//! Callers are identified by an `X-Api-Key` header (stored only as a
+//! fingerprint, never in plain text) or an `X-Session-Id` header.
+//!
+//! Both headers are chosen by the client, so abuse detection doesn't count
+//! reads under them: it uses the peer address of the connection, or the
+//! key when a tenant library proves it is one the server knows.
+
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+
+use crate::models::{key_fingerprint, short_fingerprint};
+
+/// Header carrying an API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Header carrying a client-chosen session identifier.
+pub const SESSION_HEADER: &str = "x-session-id";
+
+/// Request extension marking the API key as one the server knows, set when
+/// the key selects an existing tenant library.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct VerifiedKey;
+
+/// Identity of the client making a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Caller {
+    /// Stable identifier: `key:<short fingerprint>`, `session:<id>`, or
+    /// `anonymous`.
+    pub id: String,
+
+    /// Full fingerprint of the caller's API key, which visibility checks use.
+    pub key: Option<String>,
+
+    /// Address of the connection, when the server records it.
+    pub peer: Option<IpAddr>,
+
+    /// Whether the API key is known to the server rather than only claimed.
+    pub key_verified: bool,
+}
+
+impl Caller {
+    /// The identity used when no credentials are supplied.
+    pub fn anonymous() -> Self {
+        Self {
+            id: "anonymous".to_string(),
+            key: None,
+            peer: None,
+            key_verified: false,
+        }
+    }
+
+    /// Build the identity for an API key.
+    pub fn from_api_key(key: &str) -> Self {
+        let fingerprint = key_fingerprint(key);
+        Self {
+            id: format!("key:{}", short_fingerprint(&fingerprint)),
+            key: Some(fingerprint),
+            peer: None,
+            key_verified: false,
+        }
+    }
+
+    /// Fingerprint of the caller's API key, if they sent one.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Identity that abuse detection counts reads under: a verified key, or
+    /// else the peer address, so new keys or sessions don't reset it.
+    pub fn abuse_id(&self) -> String {
+        match (self.key().filter(|_| self.key_verified), self.peer) {
+            (Some(key), _) => format!("key:{}", key),
+            (None, Some(peer)) => format!("peer:{}", peer),
+            (None, None) => "peer:unknown".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Caller {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+
+        let mut caller = if let Some(key) = header(API_KEY_HEADER) {
+            Self::from_api_key(key)
+        } else if let Some(session) = header(SESSION_HEADER) {
+            let session: String = session.chars().take(64).collect();
+            Self {
+                id: format!("session:{}", session),
+                ..Self::anonymous()
+            }
+        } else {
+            Self::anonymous()
+        };
+        caller.peer = ConnectInfo::<SocketAddr>::from_request_parts(parts, state)
+            .await
+            .ok()
+            .map(|info| info.0.ip());
+        caller.key_verified = caller.key().is_some() && parts.extensions.get::<VerifiedKey>().is_some();
+        Ok(caller)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_fingerprint_hides_key() {
+        let caller = Caller::from_api_key("secret-key");
+
+        assert!(caller.id.starts_with("key:"));
+        assert!(!caller.id.contains("secret"));
+        assert_eq!(caller, Caller::from_api_key("secret-key"));
+        assert_eq!(caller.key(), Some(key_fingerprint("secret-key").as_str()));
+        assert_eq!(caller.id, format!("key:{}", short_fingerprint(caller.key().unwrap())));
+        assert_eq!(Caller::anonymous().key(), None);
+    }
+
+    #[test]
+    fn test_abuse_id() {
+        let mut caller = Caller::from_api_key("secret-key");
+        assert_eq!(caller.abuse_id(), "peer:unknown");
+        caller.peer = Some(IpAddr::from([10, 0, 0, 7]));
+        assert_eq!(caller.abuse_id(), "peer:10.0.0.7");
+        caller.key_verified = true;
+        assert_eq!(caller.abuse_id(), format!("key:{}", key_fingerprint("secret-key")));
+    }
+}
@@ -0,0 +1,156 @@
+//! Conditional GET support for content routes.
+//!
+//! Skill, sub-skill, and reference reads carry an `ETag` and a
+//! `Last-Modified` header. A request whose `If-None-Match` lists the current
+//! ETag, or, without `If-None-Match`, whose `If-Modified-Since` is no older
+//! than the last modification, is answered `304 Not Modified` with no body.
+//!
+//! ETags of rendered responses are hashes of the body, so they change with
+//! anything in it (metadata, related skills, `minify`); ETags of raw
+//! reference files are weak ones built from the file's size and
+//! modification time, so serving them never reads the whole file.
+
+use std::time::SystemTime;
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// `Last-Modified` format (IMF-fixdate, RFC 9110).
+const HTTP_DATE: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// Validators of a response's current representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Validators {
+    /// Quoted entity tag, `W/`-prefixed when weak.
+    pub etag: String,
+    /// Last modification, if known.
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+impl Validators {
+    /// Strong validators for a rendered body.
+    pub fn for_body(body: &[u8], last_modified: Option<DateTime<Utc>>) -> Self {
+        let digest = Sha256::digest(body);
+        let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+        Self {
+            etag: format!("\"{}\"", hex),
+            last_modified,
+        }
+    }
+
+    /// Weak validators for a file from its size and modification time.
+    pub fn for_file(metadata: &std::fs::Metadata) -> Self {
+        let modified = metadata.modified().ok();
+        let nanos = modified
+            .and_then(|m| m.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos());
+        Self {
+            etag: format!("W/\"{:x}-{:x}\"", metadata.len(), nanos),
+            last_modified: modified.map(DateTime::<Utc>::from),
+        }
+    }
+
+    /// Whether the request's conditional headers match, so the client's
+    /// copy is current.
+    pub fn not_modified(&self, headers: &HeaderMap) -> bool {
+        if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH) {
+            let Ok(tags) = if_none_match.to_str() else {
+                return false;
+            };
+            // Weak comparison: W/"x" matches "x"
+            let ours = self.etag.trim_start_matches("W/");
+            return tags
+                .split(',')
+                .map(str::trim)
+                .any(|tag| tag == "*" || tag.trim_start_matches("W/") == ours);
+        }
+
+        let since = headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| DateTime::parse_from_rfc2822(v).ok());
+        match (since, self.last_modified) {
+            // HTTP dates have whole seconds
+            (Some(since), Some(modified)) => modified.timestamp() <= since.timestamp(),
+            _ => false,
+        }
+    }
+
+    /// Add the `ETag` and `Last-Modified` headers to a response.
+    pub fn apply(&self, response: &mut Response) {
+        let headers = response.headers_mut();
+        if let Ok(etag) = HeaderValue::from_str(&self.etag) {
+            headers.insert(header::ETAG, etag);
+        }
+        if let Some(modified) = self.last_modified {
+            if let Ok(value) = HeaderValue::from_str(&modified.format(HTTP_DATE).to_string()) {
+                headers.insert(header::LAST_MODIFIED, value);
+            }
+        }
+    }
+
+    /// An empty `304 Not Modified` response carrying the validators.
+    pub fn not_modified_response(&self) -> Response {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        self.apply(&mut response);
+        response
+    }
+}
+
+/// Respond with `body` and its validators, or with 304 if the client's copy
+/// is current.
+pub(super) fn respond(
+    headers: &HeaderMap,
+    content_type: &'static str,
+    body: Vec<u8>,
+    last_modified: Option<DateTime<Utc>>,
+) -> Response {
+    let validators = Validators::for_body(&body, last_modified);
+    if validators.not_modified(headers) {
+        return validators.not_modified_response();
+    }
+    let mut response = ([(header::CONTENT_TYPE, content_type)], Body::from(body)).into_response();
+    validators.apply(&mut response);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(name: header::HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_not_modified() {
+        let modified = DateTime::parse_from_rfc3339("2026-10-15T09:30:00.500Z").unwrap().with_timezone(&Utc);
+        let validators = Validators::for_body(b"body", Some(modified));
+
+        assert!(!validators.not_modified(&HeaderMap::new()));
+        assert!(validators.not_modified(&request(header::IF_NONE_MATCH, &validators.etag)));
+        assert!(validators.not_modified(&request(header::IF_NONE_MATCH, &format!("\"x\", W/{}", validators.etag))));
+        assert!(validators.not_modified(&request(header::IF_NONE_MATCH, "*")));
+        assert!(!validators.not_modified(&request(header::IF_NONE_MATCH, "\"other\"")));
+
+        assert!(validators.not_modified(&request(header::IF_MODIFIED_SINCE, "Thu, 15 Oct 2026 09:30:00 GMT")));
+        assert!(!validators.not_modified(&request(header::IF_MODIFIED_SINCE, "Thu, 15 Oct 2026 09:29:59 GMT")));
+        assert!(!validators.not_modified(&request(header::IF_MODIFIED_SINCE, "yesterday")));
+
+        // If-None-Match wins over If-Modified-Since
+        let mut headers = request(header::IF_NONE_MATCH, "\"other\"");
+        headers.insert(header::IF_MODIFIED_SINCE, HeaderValue::from_static("Thu, 15 Oct 2026 09:30:00 GMT"));
+        assert!(!validators.not_modified(&headers));
+
+        let mut response = validators.not_modified_response();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers_mut()[header::LAST_MODIFIED], "Thu, 15 Oct 2026 09:30:00 GMT");
+    }
+}
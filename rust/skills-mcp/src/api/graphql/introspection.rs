@@ -0,0 +1,105 @@
+//! The schema as data, for introspection (`__schema`, `__type`) and for
+//! checking the type conditions of fragments.
+//!
+//! Types are parsed once from `schema.graphql` and `introspection.graphql`;
+//! the built-in scalars the schema uses are added to them. The schema has
+//! no interfaces, unions, input objects, or directives, so introspection
+//! reports none.
+
+use std::sync::OnceLock;
+
+use super::parser::{self, TypeDefinition, TypeRef};
+use super::SCHEMA;
+
+/// The introspection types, in SDL.
+const INTROSPECTION: &str = include_str!("introspection.graphql");
+
+/// Built-in scalars, in the order `__schema { types }` lists them.
+const SCALARS: &[&str] = &["Boolean", "Float", "Int", "String"];
+
+/// Object types and enums of the endpoint.
+#[derive(Debug)]
+pub struct Schema {
+    types: Vec<TypeDefinition>,
+}
+
+/// The parsed schema.
+pub fn schema() -> &'static Schema {
+    static PARSED: OnceLock<Schema> = OnceLock::new();
+    PARSED.get_or_init(|| {
+        let mut types = parser::parse_schema(SCHEMA).expect("schema.graphql parses");
+        types.extend(parser::parse_schema(INTROSPECTION).expect("introspection.graphql parses"));
+        Schema { types }
+    })
+}
+
+impl Schema {
+    /// An object type or enum by name.
+    pub fn get(&self, name: &str) -> Option<&TypeDefinition> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    /// Every object type and enum.
+    pub fn types(&self) -> impl Iterator<Item = &TypeDefinition> {
+        self.types.iter()
+    }
+
+    /// Whether `name` is an object type, which fragments can apply to.
+    pub fn is_object(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|t| !t.is_enum())
+    }
+
+    /// Whether `name` is any type of the schema.
+    pub fn has_type(&self, name: &str) -> bool {
+        SCALARS.contains(&name) || self.get(name).is_some()
+    }
+
+    /// Names of every type: scalars first, then the rest in definition
+    /// order.
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        SCALARS.iter().copied().chain(self.types.iter().map(|t| t.name.as_str()))
+    }
+
+    /// `__TypeKind` of a type reference.
+    pub fn kind(&self, type_ref: &TypeRef) -> &'static str {
+        match type_ref {
+            TypeRef::List(_) => "LIST",
+            TypeRef::NonNull(_) => "NON_NULL",
+            TypeRef::Named(name) => match self.get(name) {
+                None => "SCALAR",
+                Some(t) if t.is_enum() => "ENUM",
+                Some(_) => "OBJECT",
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema() {
+        let schema = schema();
+        assert!(schema.is_object("Skill"));
+        assert!(schema.is_object("__Type"));
+        assert!(!schema.is_object("__TypeKind"));
+        assert!(schema.has_type("String"));
+        assert_eq!(schema.kind(&TypeRef::Named("__TypeKind".to_string())), "ENUM");
+
+        // Every named type a field or argument uses is defined
+        for definition in &schema.types {
+            for field in &definition.fields {
+                let mut refs = vec![&field.field_type];
+                refs.extend(field.arguments.iter().map(|a| &a.value_type));
+                for mut type_ref in refs {
+                    while let TypeRef::List(inner) | TypeRef::NonNull(inner) = type_ref {
+                        type_ref = inner;
+                    }
+                    let TypeRef::Named(name) = type_ref else { unreachable!() };
+                    assert!(schema.has_type(name), "{}.{}: {}", definition.name, field.name, name);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1223 @@
+//! GraphQL endpoint (`graphql` feature).
+//!
+//! `POST /api/graphql` takes `{"query", "variables", "operationName"}`;
+//! `GET /api/graphql?query=...&variables=...` runs a query from the URL,
+//! and without `query` returns the schema in SDL. Queries are resolved
+//! against the same [`ServiceContext`](crate::mcp::ServiceContext) as the
+//! REST routes, with the same visibility rules, read tracking, and abuse
+//! checks, so a client can fetch a skill with its sub-skills, references,
+//! and dependencies in one request.
+//!
+//! This is a small executor written for this schema, not a general
+//! GraphQL server. It supports:
+//!
+//! - one query operation per document, with variables and defaults;
+//! - aliases, named fragments, and inline fragments;
+//! - `__typename`, and introspection through `__schema` and `__type`, so
+//!   tools such as GraphiQL can load the schema.
+//!
+//! It doesn't support mutations, subscriptions, directives (including
+//! `@skip` and `@include`), block strings, or custom scalars, and it only
+//! validates what it needs to run a document: unknown fields and
+//! arguments, argument types, and fragments are checked as they're
+//! resolved, not up front. See [`parser`] for the accepted syntax.
+//!
+//! List fields such as `related` nest, so before anything is resolved a
+//! document's cost is estimated, counting every field once per item of
+//! the lists above it (assuming [`LIST_SIZE`] items each, or the schema's
+//! own sizes for introspection) with fragments expanded, and searches
+//! weighing most. Documents nested deeper than [`MAX_DEPTH`] objects or costing
+//! more than [`MAX_COST`] are rejected.
+//!
+//! Errors use the standard `{"data", "errors"}` shape: a field that fails
+//! resolves to null and adds an error with its path. Documents that can't
+//! run at all (syntax errors, missing variables, unknown or cyclic
+//! fragments, too deep or costly) are rejected with 400 and no `data`.
+//!
+//! The endpoint isn't part of the OpenAPI document; the schema is served
+//! by the endpoint itself.
+
+mod introspection;
+mod parser;
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::abuse::AccessVerdict;
+use crate::index::{read_lossy, ReferenceFile};
+use crate::mcp::tools::{skill_stats, SkillStats};
+use crate::models::{timestamp, SearchOptions, SearchResult, SkillMeta, SubSkillMeta, UsageStats};
+
+use self::introspection::schema;
+use self::parser::{
+    EnumValueDefinition, Field, FieldDefinition, InputValueDefinition, Operation, Selection, TypeDefinition, TypeRef,
+    Value,
+};
+use super::routes::{AppState, MAX_SEARCH_LIMIT, MAX_SEARCH_QUERY_LENGTH, RELATED_LIMIT};
+use super::Caller;
+
+/// The schema, in SDL.
+pub const SCHEMA: &str = include_str!("schema.graphql");
+
+/// Largest accepted query document.
+const MAX_DOCUMENT_BYTES: usize = 64 * 1024;
+
+/// Deepest nesting of objects a document may select, counting through
+/// fragments. GraphiQL's introspection query nests 12 deep.
+const MAX_DEPTH: usize = 15;
+
+/// Most a document may cost to run; see [`estimate_cost`].
+const MAX_COST: u64 = 100_000;
+
+/// Items assumed per list when estimating cost.
+const LIST_SIZE: u64 = 10;
+
+/// Cost of resolving a field of a skill or of usage data.
+const FIELD_COST: u64 = 10;
+
+/// Cost of a field that searches the library (`search`, `related`).
+const SEARCH_COST: u64 = 200;
+
+/// Cost of an introspection field, which reads the static schema.
+const SCHEMA_FIELD_COST: u64 = 1;
+
+/// Body of `POST /api/graphql`.
+#[derive(Debug, Deserialize)]
+pub struct GraphQLRequest {
+    /// Query document.
+    pub query: String,
+    /// Variable values.
+    #[serde(default)]
+    pub variables: Option<Map<String, JsonValue>>,
+    /// Operation to run; must match the document's operation if given.
+    #[serde(default, rename = "operationName")]
+    pub operation_name: Option<String>,
+}
+
+/// Query string of `GET /api/graphql`.
+#[derive(Debug, Deserialize)]
+pub struct GraphQLQuery {
+    /// Query document; the schema is returned when omitted.
+    pub query: Option<String>,
+    /// Variable values, as a JSON object.
+    pub variables: Option<String>,
+    /// Operation to run.
+    #[serde(rename = "operationName")]
+    pub operation_name: Option<String>,
+}
+
+/// `GET /api/graphql`.
+pub async fn graphql_get(
+    State(state): State<AppState>,
+    caller: Caller,
+    axum::extract::Query(query): axum::extract::Query<GraphQLQuery>,
+) -> Response {
+    let Some(document) = query.query else {
+        return ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], SCHEMA).into_response();
+    };
+    let variables = match query.variables.as_deref().map(serde_json::from_str::<Map<String, JsonValue>>) {
+        None => None,
+        Some(Ok(variables)) => Some(variables),
+        Some(Err(e)) => return request_error(format!("Invalid variables: {}", e), None),
+    };
+    run(
+        &state,
+        &caller,
+        GraphQLRequest {
+            query: document,
+            variables,
+            operation_name: query.operation_name,
+        },
+    )
+}
+
+/// `POST /api/graphql`.
+pub async fn graphql_post(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(request): Json<GraphQLRequest>,
+) -> Response {
+    run(&state, &caller, request)
+}
+
+fn run(state: &AppState, caller: &Caller, request: GraphQLRequest) -> Response {
+    if request.query.len() > MAX_DOCUMENT_BYTES {
+        return request_error(
+            format!("Query too long (max {} bytes)", MAX_DOCUMENT_BYTES),
+            None,
+        );
+    }
+    let operation = match parser::parse(&request.query) {
+        Ok(operation) => operation,
+        Err(e) => {
+            let location = json!([{"line": e.line, "column": e.column}]);
+            return request_error(format!("Syntax error: {}", e.message), Some(location));
+        }
+    };
+    if let Some(name) = &request.operation_name {
+        if operation.name.as_ref() != Some(name) {
+            return request_error(format!("Unknown operation '{}'", name), None);
+        }
+    }
+    match execute(state, caller, &operation, request.variables.unwrap_or_default()) {
+        Ok(response) => Json(response).into_response(),
+        Err(message) => request_error(message, None),
+    }
+}
+
+fn request_error(message: String, locations: Option<JsonValue>) -> Response {
+    let mut error = json!({ "message": message });
+    if let Some(locations) = locations {
+        error["locations"] = locations;
+    }
+    (StatusCode::BAD_REQUEST, Json(json!({ "errors": [error] }))).into_response()
+}
+
+/// Run an operation, returning the `{"data", "errors"}` response, or the
+/// message of a request error.
+pub(crate) fn execute(
+    state: &AppState,
+    caller: &Caller,
+    operation: &Operation,
+    mut provided: Map<String, JsonValue>,
+) -> Result<JsonValue, String> {
+    check_fragments(operation)?;
+    estimate_cost(operation)?;
+
+    let mut variables = Map::new();
+    let mut declared = HashSet::new();
+    for definition in &operation.variables {
+        declared.insert(definition.name.clone());
+        let value = match provided.remove(&definition.name) {
+            Some(value) => value,
+            None => match &definition.default {
+                Some(default) => constant(default),
+                None => JsonValue::Null,
+            },
+        };
+        if definition.required && value.is_null() {
+            return Err(format!("Variable '${}' is required", definition.name));
+        }
+        variables.insert(definition.name.clone(), value);
+    }
+
+    let mut executor = Executor {
+        state,
+        caller,
+        operation,
+        variables,
+        declared,
+        errors: Vec::new(),
+        path: Vec::new(),
+        depth: 0,
+    };
+    let data = executor.select(&Node::Query, &[&operation.selection]);
+
+    let mut response = json!({ "data": data });
+    if !executor.errors.is_empty() {
+        response["errors"] = JsonValue::Array(executor.errors);
+    }
+    Ok(response)
+}
+
+/// Check that every fragment spread names a fragment, without cycles, and
+/// that every fragment is used and applies to an object type.
+fn check_fragments(operation: &Operation) -> Result<(), String> {
+    // Each fragment is visited once; `used` holds the ones finished
+    fn visit<'o>(
+        operation: &'o Operation,
+        selection: &'o [Selection],
+        stack: &mut Vec<&'o str>,
+        used: &mut HashSet<&'o str>,
+    ) -> Result<(), String> {
+        for item in selection {
+            match item {
+                Selection::Field(field) => visit(operation, &field.selection, stack, used)?,
+                Selection::InlineFragment {
+                    type_condition,
+                    selection,
+                } => {
+                    if let Some(type_name) = type_condition.as_deref().filter(|t| !schema().is_object(t)) {
+                        return Err(format!("Inline fragment on unknown type '{}'", type_name));
+                    }
+                    visit(operation, selection, stack, used)?;
+                }
+                Selection::FragmentSpread(name) => {
+                    let (name, fragment) = operation
+                        .fragments
+                        .get_key_value(name)
+                        .ok_or_else(|| format!("Unknown fragment '{}'", name))?;
+                    if stack.contains(&name.as_str()) {
+                        return Err(format!("Fragment '{}' spreads itself", name));
+                    }
+                    if !used.contains(name.as_str()) {
+                        stack.push(name);
+                        visit(operation, &fragment.selection, stack, used)?;
+                        stack.pop();
+                        used.insert(name);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    for (name, fragment) in &operation.fragments {
+        if !schema().is_object(&fragment.type_condition) {
+            return Err(format!("Fragment '{}' is on unknown type '{}'", name, fragment.type_condition));
+        }
+    }
+    let mut used = HashSet::new();
+    visit(operation, &operation.selection, &mut Vec::new(), &mut used)?;
+    let mut unused: Vec<&String> = operation.fragments.keys().filter(|name| !used.contains(name.as_str())).collect();
+    unused.sort();
+    match unused.first() {
+        Some(name) => Err(format!("Fragment '{}' is never used", name)),
+        None => Ok(()),
+    }
+}
+
+/// Reject a document nested more than [`MAX_DEPTH`] objects deep or
+/// estimated to cost more than [`MAX_COST`]. Every field costs its weight
+/// once per item of the lists above it; fragments count where they're
+/// spread.
+/// Fragments must already be checked for cycles. Returns the cost.
+fn estimate_cost(operation: &Operation) -> Result<u64, String> {
+    fn visit(
+        operation: &Operation,
+        type_name: &str,
+        selection: &[Selection],
+        depth: usize,
+        multiplier: u64,
+        remaining: &mut u64,
+    ) -> Result<(), String> {
+        for item in selection {
+            let (selection, type_condition) = match item {
+                Selection::Field(field) => {
+                    let weight = match (type_name, field.name.as_str()) {
+                        ("Query", "search") | ("Skill", "related") => SEARCH_COST,
+                        (_, "__schema" | "__type" | "__typename") => SCHEMA_FIELD_COST,
+                        (parent, _) if parent.starts_with("__") => SCHEMA_FIELD_COST,
+                        _ => FIELD_COST,
+                    };
+                    let cost = multiplier.saturating_mul(weight);
+                    *remaining = remaining
+                        .checked_sub(cost)
+                        .ok_or_else(|| format!("Query is too costly to run (max {})", MAX_COST))?;
+                    let Some((child, list)) = field_type(type_name, &field.name) else {
+                        continue;
+                    };
+                    if field.selection.is_empty() {
+                        continue;
+                    }
+                    if depth >= MAX_DEPTH {
+                        return Err(format!("Query is nested too deeply (max {} levels)", MAX_DEPTH));
+                    }
+                    let multiplier = if list { multiplier.saturating_mul(list_size(type_name, &field.name)) } else { multiplier };
+                    visit(operation, &child, &field.selection, depth + 1, multiplier, remaining)?;
+                    continue;
+                }
+                Selection::InlineFragment {
+                    type_condition,
+                    selection,
+                } => (selection, type_condition.as_deref()),
+                Selection::FragmentSpread(name) => match operation.fragments.get(name) {
+                    Some(fragment) => (&fragment.selection, Some(fragment.type_condition.as_str())),
+                    None => continue,
+                },
+            };
+            if type_condition.is_none_or(|t| t == type_name) {
+                visit(operation, type_name, selection, depth, multiplier, remaining)?;
+            }
+        }
+        Ok(())
+    }
+
+    let mut remaining = MAX_COST;
+    visit(operation, "Query", &operation.selection, 0, 1, &mut remaining)?;
+    Ok(MAX_COST - remaining)
+}
+
+/// Items assumed for a list field: the most there can be for the schema's
+/// own types, and [`LIST_SIZE`] for everything else.
+fn list_size(type_name: &str, field: &str) -> u64 {
+    let schema = schema();
+    let most = |count: &dyn Fn(&TypeDefinition) -> usize| schema.types().map(count).max().unwrap_or(0) as u64;
+    match (type_name, field) {
+        ("__Schema", "types") => schema.type_names().count() as u64,
+        ("__Type", "fields") => most(&|t| t.fields.len()),
+        ("__Type", "enumValues") => most(&|t| t.enum_values.len()),
+        ("__Field", "args") => most(&|t| t.fields.iter().map(|f| f.arguments.len()).max().unwrap_or(0)),
+        // Always empty: the schema has no directives, interfaces, unions,
+        // or input objects
+        ("__Schema", "directives") | ("__Type", "interfaces" | "possibleTypes" | "inputFields") => 0,
+        _ => LIST_SIZE,
+    }
+}
+
+/// The named type of a field, and whether it's a list; None for unknown
+/// fields and `__typename`.
+fn field_type(type_name: &str, field: &str) -> Option<(String, bool)> {
+    let mut type_ref = match (type_name, field) {
+        ("Query", "__schema") => return Some(("__Schema".to_string(), false)),
+        ("Query", "__type") => return Some(("__Type".to_string(), false)),
+        _ => &schema().get(type_name)?.fields.iter().find(|f| f.name == field)?.field_type,
+    };
+    let mut list = false;
+    loop {
+        match type_ref {
+            TypeRef::Named(name) => return Some((name.clone(), list)),
+            TypeRef::List(inner) => {
+                list = true;
+                type_ref = inner;
+            }
+            TypeRef::NonNull(inner) => type_ref = inner,
+        }
+    }
+}
+
+/// Convert a value without variables to JSON.
+fn constant(value: &Value) -> JsonValue {
+    match value {
+        Value::Variable(_) | Value::Null => JsonValue::Null,
+        Value::Boolean(b) => json!(b),
+        Value::Int(n) => json!(n),
+        Value::Float(n) => json!(n),
+        Value::String(s) | Value::Enum(s) => json!(s),
+        Value::List(items) => JsonValue::Array(items.iter().map(constant).collect()),
+        Value::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), constant(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// An object being resolved.
+enum Node {
+    Query,
+    Skill(SkillMeta),
+    SubSkill(String, SubSkillMeta),
+    Reference(String, ReferenceFile),
+    SkillStats(SkillStats),
+    SearchResult(SearchResult),
+    UsageStats(UsageStats),
+    Count(String, u64),
+    Schema,
+    Type(TypeRef),
+    Field(&'static FieldDefinition),
+    InputValue(&'static InputValueDefinition),
+    EnumValue(&'static EnumValueDefinition),
+}
+
+impl Node {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Node::Schema => "__Schema",
+            Node::Type(_) => "__Type",
+            Node::Field(_) => "__Field",
+            Node::InputValue(_) => "__InputValue",
+            Node::EnumValue(_) => "__EnumValue",
+            Node::Query => "Query",
+            Node::Skill(_) => "Skill",
+            Node::SubSkill(..) => "SubSkill",
+            Node::Reference(..) => "Reference",
+            Node::SkillStats(_) => "SkillStats",
+            Node::SearchResult(_) => "SearchResult",
+            Node::UsageStats(_) => "UsageStats",
+            Node::Count(..) => "Count",
+        }
+    }
+}
+
+/// A resolved field, before its sub-selection is applied.
+enum Output {
+    Value(JsonValue),
+    Object(Box<Node>),
+    List(Vec<Output>),
+}
+
+impl Output {
+    fn value(value: impl serde::Serialize) -> Self {
+        Output::Value(serde_json::to_value(value).unwrap_or(JsonValue::Null))
+    }
+
+    fn optional(node: Option<Node>) -> Self {
+        node.map_or(Output::Value(JsonValue::Null), |node| Output::Object(Box::new(node)))
+    }
+
+    fn list(nodes: impl IntoIterator<Item = Node>) -> Self {
+        Output::List(nodes.into_iter().map(|node| Output::Object(Box::new(node))).collect())
+    }
+}
+
+/// Arguments of one field, with variables substituted.
+struct Arguments<'f> {
+    field: &'f Field,
+    values: HashMap<&'f str, JsonValue>,
+}
+
+impl Arguments<'_> {
+    fn string(&self, name: &str) -> Result<Option<String>, String> {
+        match self.values.get(name) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(self.invalid(name, "String")),
+        }
+    }
+
+    fn required_string(&self, name: &str) -> Result<String, String> {
+        self.string(name)?
+            .ok_or_else(|| format!("Argument '{}' of field '{}' is required", name, self.field.name))
+    }
+
+    fn int(&self, name: &str) -> Result<Option<i64>, String> {
+        match self.values.get(name) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(value) => value.as_i64().map(Some).ok_or_else(|| self.invalid(name, "Int")),
+        }
+    }
+
+    fn boolean(&self, name: &str) -> Result<bool, String> {
+        match self.values.get(name) {
+            None | Some(JsonValue::Null) => Ok(false),
+            Some(value) => value.as_bool().ok_or_else(|| self.invalid(name, "Boolean")),
+        }
+    }
+
+    fn invalid(&self, name: &str, expected: &str) -> String {
+        format!(
+            "Argument '{}' of field '{}' must be a {}",
+            name, self.field.name, expected
+        )
+    }
+}
+
+struct Executor<'a> {
+    state: &'a AppState,
+    caller: &'a Caller,
+    operation: &'a Operation,
+    variables: Map<String, JsonValue>,
+    declared: HashSet<String>,
+    errors: Vec<JsonValue>,
+    path: Vec<JsonValue>,
+    /// Objects selected into, to stop at [`MAX_DEPTH`].
+    depth: usize,
+}
+
+impl<'a> Executor<'a> {
+    /// Resolve selection sets on a node, merged as one.
+    fn select(&mut self, node: &Node, selections: &[&'a [Selection]]) -> JsonValue {
+        // Checked up front too; this guards the executor itself
+        if self.depth > MAX_DEPTH {
+            return self.error(format!("Query is nested too deeply (max {} levels)", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let value = self.select_fields(node, selections);
+        self.depth -= 1;
+        value
+    }
+
+    fn select_fields(&mut self, node: &Node, selections: &[&'a [Selection]]) -> JsonValue {
+        let mut fields = Vec::new();
+        for selection in selections {
+            self.collect(node.type_name(), selection, &mut fields);
+        }
+
+        let mut object = Map::new();
+        for (key, fields) in fields {
+            // Fields with the same response key merge their sub-selections
+            let field = fields[0];
+            let sub: Vec<&'a [Selection]> = fields.iter().map(|f| f.selection.as_slice()).collect();
+            self.path.push(json!(key));
+            let value = if field.name == "__typename" {
+                json!(node.type_name())
+            } else {
+                match self.field(node, field) {
+                    Ok(output) => match selection_error(&output, field, sub.iter().any(|s| !s.is_empty())) {
+                        Some(message) => self.error(message),
+                        None => self.complete(output, &sub),
+                    },
+                    Err(message) => self.error(message),
+                }
+            };
+            self.path.pop();
+            object.insert(key.to_string(), value);
+        }
+        JsonValue::Object(object)
+    }
+
+    /// The fields of a selection that apply to `type_name`, with fragments
+    /// expanded, grouped by response key in document order.
+    fn collect(&self, type_name: &str, selection: &'a [Selection], fields: &mut Vec<(&'a str, Vec<&'a Field>)>) {
+        for item in selection {
+            match item {
+                Selection::Field(field) => match fields.iter_mut().find(|(key, _)| *key == field.response_key()) {
+                    Some((_, group)) => group.push(field),
+                    None => fields.push((field.response_key(), vec![field])),
+                },
+                Selection::InlineFragment {
+                    type_condition,
+                    selection,
+                } => {
+                    if type_condition.as_deref().is_none_or(|t| t == type_name) {
+                        self.collect(type_name, selection, fields);
+                    }
+                }
+                Selection::FragmentSpread(name) => {
+                    // Checked to exist, without cycles, before running
+                    if let Some(fragment) = self.operation.fragments.get(name) {
+                        if fragment.type_condition == type_name {
+                            self.collect(type_name, &fragment.selection, fields);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Apply a field's sub-selections to its resolved output.
+    fn complete(&mut self, output: Output, selections: &[&'a [Selection]]) -> JsonValue {
+        match output {
+            Output::Value(value) => value,
+            Output::Object(node) => self.select(&node, selections),
+            Output::List(items) => JsonValue::Array(
+                items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        self.path.push(json!(i));
+                        let value = self.complete(item, selections);
+                        self.path.pop();
+                        value
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn error(&mut self, message: String) -> JsonValue {
+        self.errors.push(json!({ "message": message, "path": self.path }));
+        JsonValue::Null
+    }
+
+    /// Check a field's arguments against the ones it accepts and substitute
+    /// variables.
+    fn arguments<'f>(&self, field: &'f Field, accepted: &[&str]) -> Result<Arguments<'f>, String> {
+        let mut values = HashMap::new();
+        for (name, value) in &field.arguments {
+            if !accepted.contains(&name.as_str()) {
+                return Err(format!("Unknown argument '{}' on field '{}'", name, field.name));
+            }
+            values.insert(name.as_str(), self.value(value)?);
+        }
+        Ok(Arguments { field, values })
+    }
+
+    fn value(&self, value: &Value) -> Result<JsonValue, String> {
+        Ok(match value {
+            Value::Variable(name) if !self.declared.contains(name) => {
+                return Err(format!("Variable '${}' is not declared", name));
+            }
+            Value::Variable(name) => self.variables.get(name).cloned().unwrap_or(JsonValue::Null),
+            Value::List(items) => JsonValue::Array(items.iter().map(|v| self.value(v)).collect::<Result<_, _>>()?),
+            Value::Object(fields) => JsonValue::Object(
+                fields
+                    .iter()
+                    .map(|(name, v)| Ok((name.clone(), self.value(v)?)))
+                    .collect::<Result<_, String>>()?,
+            ),
+            _ => constant(value),
+        })
+    }
+
+    /// A skill by name or alias, if the caller can see it.
+    fn skill(&self, name: &str) -> Option<Node> {
+        self.state
+            .indexer
+            .get_skill_meta(name)
+            .filter(|meta| meta.is_visible_to(self.caller.key()))
+            .map(Node::Skill)
+    }
+
+    /// Count a read of a skill's content, refusing throttled callers.
+    fn track_read(&self, skill: &str, content: &str) -> Result<(), String> {
+        if let AccessVerdict::Throttle(retry_after) = self.state.abuse.check(&self.caller.abuse_id(), skill) {
+            return Err(format!(
+                "Unusual access pattern detected; retry in {}s",
+                retry_after.as_secs().max(1)
+            ));
+        }
+        self.state.track_skill_read(skill, &self.caller.id);
+        self.state.track_tokens_served(skill, &self.caller.id, content);
+        Ok(())
+    }
+
+    fn field(&mut self, node: &Node, field: &Field) -> Result<Output, String> {
+        let unknown = || format!("Unknown field '{}' on type '{}'", field.name, node.type_name());
+        match (node, field.name.as_str()) {
+            (Node::Query | Node::Skill(_), _) => {}
+            // Nothing in the schema is deprecated, so the argument changes nothing
+            (Node::Type(_), "fields" | "enumValues" | "inputFields") | (Node::Field(_), "args") => {
+                self.arguments(field, &["includeDeprecated"])?.boolean("includeDeprecated")?;
+            }
+            _ => {
+                self.arguments(field, &[])?;
+            }
+        }
+
+        Ok(match node {
+            Node::Query => return self.query_field(field),
+            Node::Skill(meta) => return self.skill_field(meta, field),
+            Node::Type(type_ref) => return type_field(type_ref, field).ok_or_else(unknown),
+            Node::Schema => match field.name.as_str() {
+                "description" | "mutationType" | "subscriptionType" => Output::Value(JsonValue::Null),
+                "types" => Output::list(schema().type_names().map(|name| Node::Type(TypeRef::Named(name.to_string())))),
+                "queryType" => Output::Object(Box::new(Node::Type(TypeRef::Named("Query".to_string())))),
+                "directives" => Output::List(Vec::new()),
+                _ => return Err(unknown()),
+            },
+            Node::Field(definition) => match field.name.as_str() {
+                "name" => Output::value(&definition.name),
+                "description" => Output::value(&definition.description),
+                "args" => Output::list(definition.arguments.iter().map(Node::InputValue)),
+                "type" => Output::Object(Box::new(Node::Type(definition.field_type.clone()))),
+                "isDeprecated" => Output::value(false),
+                "deprecationReason" => Output::Value(JsonValue::Null),
+                _ => return Err(unknown()),
+            },
+            Node::InputValue(definition) => match field.name.as_str() {
+                "name" => Output::value(&definition.name),
+                "description" => Output::value(&definition.description),
+                "type" => Output::Object(Box::new(Node::Type(definition.value_type.clone()))),
+                "defaultValue" => Output::value(definition.default.as_ref().map(Value::to_string)),
+                "isDeprecated" => Output::value(false),
+                "deprecationReason" => Output::Value(JsonValue::Null),
+                _ => return Err(unknown()),
+            },
+            Node::EnumValue(definition) => match field.name.as_str() {
+                "name" => Output::value(&definition.name),
+                "description" => Output::value(&definition.description),
+                "isDeprecated" => Output::value(false),
+                "deprecationReason" => Output::Value(JsonValue::Null),
+                _ => return Err(unknown()),
+            },
+            Node::SubSkill(skill, sub) => match field.name.as_str() {
+                "name" => Output::value(&sub.name),
+                "file" => Output::value(&sub.file),
+                "triggers" => Output::value(&sub.triggers),
+                "language" => Output::value(&sub.language),
+                "content" => {
+                    let content = self
+                        .state
+                        .indexer
+                        .read_sub_skill_content(skill, &sub.name)
+                        .map_err(|e| e.to_string())?
+                        .content;
+                    self.track_read(skill, &content)?;
+                    Output::value(content)
+                }
+                _ => return Err(unknown()),
+            },
+            Node::Reference(skill, file) => match field.name.as_str() {
+                "path" => Output::value(&file.path),
+                "mimeType" => Output::value(&file.mime_type),
+                "size" => Output::value(file.size),
+                "indexed" => Output::value(file.indexed),
+                "content" => {
+                    let path = self.state.indexer.skill_dir(skill).join("references").join(&file.path);
+                    Output::value(read_lossy(&path).map_err(|e| format!("Failed to read reference: {}", e))?)
+                }
+                _ => return Err(unknown()),
+            },
+            Node::SkillStats(stats) => match field.name.as_str() {
+                "words" => Output::value(stats.total.words),
+                "headings" => Output::value(stats.total.headings),
+                "codeBlocks" => Output::value(stats.total.code_blocks),
+                "tokens" => Output::value(stats.total.tokens),
+                "references" => Output::value(stats.references),
+                _ => return Err(unknown()),
+            },
+            Node::SearchResult(result) => match field.name.as_str() {
+                "skill" => Output::optional(self.skill(&result.domain)),
+                "subSkill" => Output::value(&result.sub_skill),
+                "score" => Output::value(result.score),
+                "matchType" => Output::value(result.match_type),
+                "snippet" => Output::value(&result.snippet),
+                "file" => Output::value(&result.file),
+                "line" => Output::value(result.line),
+                _ => return Err(unknown()),
+            },
+            Node::UsageStats(stats) => match field.name.as_str() {
+                "startTime" => Output::value(timestamp::format(&stats.start_time)),
+                "toolCalls" => Output::list(counts(&stats.tool_calls)),
+                "skillLoads" => Output::list(counts(&stats.skill_loads)),
+                "searches" => Output::value(stats.searches.len()),
+                _ => return Err(unknown()),
+            },
+            Node::Count(name, count) => match field.name.as_str() {
+                "name" => Output::value(name),
+                "count" => Output::value(count),
+                _ => return Err(unknown()),
+            },
+        })
+    }
+
+    fn query_field(&mut self, field: &Field) -> Result<Output, String> {
+        Ok(match field.name.as_str() {
+            "skills" => {
+                let args = self.arguments(field, &["includeArchived", "collection"])?;
+                let include_archived = args.boolean("includeArchived")?;
+                let collection = args.string("collection")?;
+                let index = self.state.indexer.get_skill_index();
+                Output::list(
+                    index
+                        .skills
+                        .iter()
+                        .filter(|s| collection.is_none() || s.collection() == collection.as_deref())
+                        .filter(|s| include_archived || !s.archived)
+                        .filter(|s| s.is_visible_to(self.caller.key()))
+                        .cloned()
+                        .map(Node::Skill),
+                )
+            }
+            "skill" => {
+                let name = self.arguments(field, &["name"])?.required_string("name")?;
+                Output::optional(self.skill(&name))
+            }
+            "search" => {
+                let args = self.arguments(field, &["query", "limit", "content"])?;
+                let query = args.required_string("query")?;
+                if query.is_empty() {
+                    return Err("Search query cannot be empty".to_string());
+                }
+                if query.len() > MAX_SEARCH_QUERY_LENGTH {
+                    return Err(format!(
+                        "Search query too long (max {} characters)",
+                        MAX_SEARCH_QUERY_LENGTH
+                    ));
+                }
+                let limit = args.int("limit")?.unwrap_or(10).clamp(1, MAX_SEARCH_LIMIT as i64);
+                let options = SearchOptions {
+                    limit: Some(limit as usize),
+                    key: self.caller.key().map(str::to_string),
+                    ..Default::default()
+                };
+                let results = if args.boolean("content")? {
+                    self.state.search.search_content(&query, options)
+                } else {
+                    self.state.search.search_skills(&query, options)
+                };
+                self.state.track_search(&query, results.total_matches);
+                Output::list(results.results.into_iter().map(Node::SearchResult))
+            }
+            "stats" => {
+                self.arguments(field, &[])?;
+                Output::Object(Box::new(Node::UsageStats(self.state.stats.read().clone())))
+            }
+            "__schema" => {
+                self.arguments(field, &[])?;
+                Output::Object(Box::new(Node::Schema))
+            }
+            "__type" => {
+                let name = self.arguments(field, &["name"])?.required_string("name")?;
+                Output::optional(schema().has_type(&name).then_some(Node::Type(TypeRef::Named(name))))
+            }
+            _ => return Err(format!("Unknown field '{}' on type 'Query'", field.name)),
+        })
+    }
+
+    fn skill_field(&mut self, meta: &SkillMeta, field: &Field) -> Result<Output, String> {
+        let name = meta.name.as_str();
+        let args = self.arguments(field, if field.name == "subSkill" { &["name"] } else { &[] })?;
+
+        Ok(match field.name.as_str() {
+            "name" => Output::value(name),
+            "description" => Output::value(&meta.description),
+            "tags" => Output::value(&meta.tags),
+            "aliases" => Output::value(&meta.aliases),
+            "collection" => Output::value(meta.collection()),
+            "language" => Output::value(&meta.language),
+            "quickAnswer" => Output::value(&meta.quick_answer),
+            "archived" => Output::value(meta.archived),
+            "createdAt" => Output::value(meta.created_at.as_ref().map(timestamp::format)),
+            "updatedAt" => Output::value(meta.updated_at.as_ref().map(timestamp::format)),
+            "content" => {
+                let content = self
+                    .state
+                    .indexer
+                    .read_skill_content(name)
+                    .map_err(|e| e.to_string())?
+                    .content;
+                self.track_read(name, &content)?;
+                Output::value(content)
+            }
+            "subSkills" => Output::list(
+                meta.sub_skills
+                    .iter()
+                    .flatten()
+                    .map(|sub| Node::SubSkill(name.to_string(), sub.clone())),
+            ),
+            "subSkill" => {
+                let sub_name = args.required_string("name")?;
+                Output::optional(
+                    meta.find_sub_skill(&sub_name)
+                        .map(|sub| Node::SubSkill(name.to_string(), sub.clone())),
+                )
+            }
+            "references" => Output::list(
+                self.state
+                    .indexer
+                    .list_references(name)
+                    .into_iter()
+                    .map(|file| Node::Reference(name.to_string(), file)),
+            ),
+            "dependsOn" => Output::list(meta.depends_on.iter().filter_map(|dep| self.skill(dep))),
+            "related" => {
+                let index = self.state.indexer.get_skill_index();
+                Output::list(
+                    index
+                        .related(name, RELATED_LIMIT)
+                        .into_iter()
+                        .filter_map(|related| self.skill(&related.name)),
+                )
+            }
+            "stats" => Output::optional(skill_stats(self.state, name).map(Node::SkillStats)),
+            "reads" => Output::value(self.state.receipts.read().readers(name).total_reads),
+            _ => return Err(format!("Unknown field '{}' on type 'Skill'", field.name)),
+        })
+    }
+}
+
+/// A field of `__Type` for a type reference; None for unknown fields.
+fn type_field(type_ref: &TypeRef, field: &Field) -> Option<Output> {
+    let schema = schema();
+    let null = || Output::Value(JsonValue::Null);
+    let name = field.name.as_str();
+    if name == "kind" {
+        return Some(Output::value(schema.kind(type_ref)));
+    }
+    let known = [
+        "name", "description", "fields", "interfaces", "possibleTypes", "enumValues", "inputFields", "ofType",
+        "specifiedByURL",
+    ];
+    if !known.contains(&name) {
+        return None;
+    }
+
+    let TypeRef::Named(type_name) = type_ref else {
+        return Some(match (type_ref, name) {
+            (TypeRef::List(inner) | TypeRef::NonNull(inner), "ofType") => {
+                Output::Object(Box::new(Node::Type(inner.as_ref().clone())))
+            }
+            _ => null(),
+        });
+    };
+    let definition = schema.get(type_name);
+    let object = definition.filter(|d| !d.is_enum());
+    let enumeration = definition.filter(|d| d.is_enum());
+    Some(match name {
+        "name" => Output::value(type_name),
+        "description" => Output::value(definition.and_then(|d| d.description.as_ref())),
+        "fields" => object.map_or_else(null, |d| Output::list(d.fields.iter().map(Node::Field))),
+        "interfaces" => object.map_or_else(null, |_| Output::List(Vec::new())),
+        "enumValues" => enumeration.map_or_else(null, |d| Output::list(d.enum_values.iter().map(Node::EnumValue))),
+        _ => null(),
+    })
+}
+
+/// Why a field's selection doesn't fit its output, if it doesn't.
+fn selection_error(output: &Output, field: &Field, has_selection: bool) -> Option<String> {
+    match output {
+        Output::Value(JsonValue::Null) => None,
+        Output::Value(_) if has_selection => Some(format!("Field '{}' has no subfields to select", field.name)),
+        Output::Object(_) if !has_selection => {
+            Some(format!("Field '{}' must have a selection of subfields", field.name))
+        }
+        Output::List(items) => items.first().and_then(|item| selection_error(item, field, has_selection)),
+        _ => None,
+    }
+}
+
+/// Counts as nodes, highest first.
+fn counts(counts: &HashMap<String, u64>) -> Vec<Node> {
+    let mut counts: Vec<(&String, &u64)> = counts.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    counts
+        .into_iter()
+        .map(|(name, count)| Node::Count(name.clone(), *count))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::Arc;
+
+    use tempfile::TempDir;
+
+    use crate::index::SkillIndexer;
+    use crate::mcp::tools::ServiceContext;
+    use crate::test_support::write_skill;
+
+    fn run_query(state: &AppState, caller: &Caller, query: &str, variables: JsonValue) -> JsonValue {
+        let operation = parser::parse(query).unwrap();
+        let variables = variables.as_object().cloned().unwrap_or_default();
+        execute(state, caller, &operation, variables).unwrap()
+    }
+
+    #[test]
+    fn test_execute() {
+        let temp_dir = TempDir::new().unwrap();
+        let write = |path: &str, text: &str| {
+            let path = temp_dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        };
+        write(
+            "forms/_meta.json",
+            r#"{"name": "forms", "description": "Forms", "tags": ["ui"], "depends_on": ["tables", "secret"],
+                "sub_skills": [{"name": "validation", "file": "validation.md", "triggers": ["validate"]}]}"#,
+        );
+        write("forms/SKILL.md", "# Forms\n\nBuild forms.");
+        write("forms/validation.md", "# Validation\n");
+        write("forms/references/api.md", "API notes");
+        write("tables/_meta.json", r#"{"name": "tables", "description": "Tables"}"#);
+        write("tables/SKILL.md", "# Tables\n");
+        write(
+            "secret/_meta.json",
+            r#"{"name": "secret", "description": "Secret", "visibility": "private"}"#,
+        );
+        write("secret/SKILL.md", "# Secret\n");
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let state: AppState = Arc::new(ServiceContext::new(indexer));
+        let caller = Caller::anonymous();
+
+        let response = run_query(
+            &state,
+            &caller,
+            r#"query Form($name: String!) {
+                skill(name: $name) {
+                    __typename name tags content
+                    subSkills { name triggers content }
+                    references { path content }
+                    dependsOn { name }
+                    stats { headings }
+                }
+                hidden: skill(name: "secret") { name }
+                all: skills { name }
+            }"#,
+            json!({"name": "forms"}),
+        );
+        assert!(response.get("errors").is_none(), "{}", response);
+        let skill = &response["data"]["skill"];
+        assert_eq!(skill["__typename"], "Skill");
+        assert_eq!(skill["content"], "# Forms\n\nBuild forms.");
+        assert_eq!(skill["subSkills"], json!([{"name": "validation", "triggers": ["validate"], "content": "# Validation\n"}]));
+        assert_eq!(skill["references"], json!([{"path": "api.md", "content": "API notes"}]));
+        // The private dependency is left out
+        assert_eq!(skill["dependsOn"], json!([{"name": "tables"}]));
+        assert_eq!(skill["stats"]["headings"], 2);
+        assert!(response["data"]["hidden"].is_null());
+        assert_eq!(response["data"]["all"], json!([{"name": "forms"}, {"name": "tables"}]));
+        // Both content fields count as reads
+        assert_eq!(state.receipts.read().readers("forms").total_reads, 2);
+
+        // Failed fields are null, with errors at their paths
+        let response = run_query(
+            &state,
+            &caller,
+            "{ skill(name: \"forms\") { name nope subSkill(name: 1) { name } } search(query: \"forms\") }",
+            json!({}),
+        );
+        assert_eq!(response["data"]["skill"]["name"], "forms");
+        assert!(response["data"]["skill"]["nope"].is_null());
+        let errors: Vec<(&str, &JsonValue)> = response["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| (e["message"].as_str().unwrap(), &e["path"]))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("Unknown field 'nope' on type 'Skill'", &json!(["skill", "nope"])),
+                ("Argument 'name' of field 'subSkill' must be a String", &json!(["skill", "subSkill"])),
+                ("Field 'search' must have a selection of subfields", &json!(["search"])),
+            ]
+        );
+
+        // Missing required variables reject the whole request
+        let operation = parser::parse("query ($name: String!) { skill(name: $name) { name } }").unwrap();
+        assert!(execute(&state, &caller, &operation, Map::new()).is_err());
+    }
+
+    fn test_state() -> (TempDir, AppState) {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Forms", "tags": ["ui"]}"#,
+            "# Forms\n",
+        );
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        (temp_dir, Arc::new(ServiceContext::new(indexer)))
+    }
+
+    #[test]
+    fn test_fragments() {
+        let (_temp_dir, state) = test_state();
+        let caller = Caller::anonymous();
+
+        let response = run_query(
+            &state,
+            &caller,
+            r#"{
+                skill(name: "forms") { ...Summary tags ... on Skill { name description } ... on Query { nope } }
+            }
+            fragment Summary on Skill { name ...Tags }
+            fragment Tags on Skill { tags }"#,
+            json!({}),
+        );
+        assert!(response.get("errors").is_none(), "{}", response);
+        // Fields from fragments merge in document order; other types are skipped
+        assert_eq!(
+            response["data"]["skill"],
+            json!({"name": "forms", "tags": ["ui"], "description": "Forms"})
+        );
+
+        let rejected = |query: &str| {
+            let operation = parser::parse(query).unwrap();
+            execute(&state, &caller, &operation, Map::new()).unwrap_err()
+        };
+        assert_eq!(rejected("{ skill(name: \"forms\") { ...Missing } }"), "Unknown fragment 'Missing'");
+        assert_eq!(
+            rejected("{ skill(name: \"forms\") { ...A } } fragment A on Skill { ...B } fragment B on Skill { ...A }"),
+            "Fragment 'A' spreads itself"
+        );
+        assert_eq!(
+            rejected("{ skills { name } } fragment Unused on Skill { name }"),
+            "Fragment 'Unused' is never used"
+        );
+        assert_eq!(
+            rejected("{ skills { ...Other } } fragment Other on Nothing { name }"),
+            "Fragment 'Other' is on unknown type 'Nothing'"
+        );
+    }
+
+    #[test]
+    fn test_introspection() {
+        let (_temp_dir, state) = test_state();
+        let caller = Caller::anonymous();
+
+        let response = run_query(
+            &state,
+            &caller,
+            r#"{
+                __schema { queryType { name } mutationType { name } types { name kind } directives { name } }
+                skill: __type(name: "Skill") {
+                    kind name
+                    fields(includeDeprecated: true) { name args { name defaultValue } type { ...TypeRef } }
+                }
+                missing: __type(name: "Nothing") { name }
+            }
+            fragment TypeRef on __Type { kind name ofType { kind name ofType { kind name } } }"#,
+            json!({}),
+        );
+        assert!(response.get("errors").is_none(), "{}", response);
+        let data = &response["data"];
+        assert_eq!(data["__schema"]["queryType"]["name"], "Query");
+        assert!(data["__schema"]["mutationType"].is_null());
+        let types = data["__schema"]["types"].as_array().unwrap();
+        for (name, kind) in [("Skill", "OBJECT"), ("String", "SCALAR"), ("__Type", "OBJECT"), ("__TypeKind", "ENUM")] {
+            assert!(types.contains(&json!({"name": name, "kind": kind})), "{}", name);
+        }
+        assert!(data["missing"].is_null());
+
+        let skill = &data["skill"];
+        assert_eq!(skill["kind"], "OBJECT");
+        let fields = skill["fields"].as_array().unwrap();
+        let field = |name: &str| fields.iter().find(|f| f["name"] == name).unwrap();
+        assert_eq!(
+            field("name")["type"],
+            json!({"kind": "NON_NULL", "name": null, "ofType": {"kind": "SCALAR", "name": "String", "ofType": null}})
+        );
+        assert_eq!(field("subSkill")["args"], json!([{"name": "name", "defaultValue": null}]));
+    }
+
+    #[test]
+    fn test_cost_limits() {
+        let (_temp_dir, state) = test_state();
+        let caller = Caller::anonymous();
+        let run = |query: &str| execute(&state, &caller, &parser::parse(query).unwrap(), Map::new());
+
+        // Lists of lists multiply
+        assert!(run("{ skills { related { related { name } } } }").is_ok());
+        assert_eq!(
+            run("{ skills { related { related { related { name } } } } }").unwrap_err(),
+            format!("Query is too costly to run (max {})", MAX_COST)
+        );
+
+        // Aliases and fragments count each time they're used
+        let searches: String = (0..400).map(|i| format!("s{}: search(query: \"x\") {{ name }} ", i)).collect();
+        assert!(run(&format!("{{ {} }}", searches)).is_err());
+        let fragments = "{ skills { ...A ...A } } fragment A on Skill { ...B ...B } fragment B on Skill { ...C ...C } \
+                         fragment C on Skill { related { ...D } } fragment D on Skill { related { related { name } } }";
+        assert!(run(fragments).is_err());
+
+        // Depth counts through fragments, which the parser doesn't see
+        let chain: String = (0..MAX_DEPTH)
+            .map(|i| format!("fragment F{} on __Type {{ ofType {{ ...F{} }} }} ", i, i + 1))
+            .collect();
+        let query = format!(
+            "{{ __type(name: \"Skill\") {{ ...F0 }} }} {} fragment F{} on __Type {{ name }}",
+            chain, MAX_DEPTH
+        );
+        assert_eq!(
+            run(&query).unwrap_err(),
+            format!("Query is nested too deeply (max {} levels)", MAX_DEPTH)
+        );
+
+        assert!(run(INTROSPECTION_QUERY).is_ok());
+    }
+
+    /// The query GraphiQL loads the schema with.
+    const INTROSPECTION_QUERY: &str = r#"
+        query IntrospectionQuery {
+          __schema {
+            queryType { name } mutationType { name } subscriptionType { name }
+            types { ...FullType }
+            directives { name description locations args { ...InputValue } }
+          }
+        }
+        fragment FullType on __Type {
+          kind name description
+          fields(includeDeprecated: true) {
+            name description args { ...InputValue } type { ...TypeRef } isDeprecated deprecationReason
+          }
+          inputFields { ...InputValue }
+          interfaces { ...TypeRef }
+          enumValues(includeDeprecated: true) { name description isDeprecated deprecationReason }
+          possibleTypes { ...TypeRef }
+        }
+        fragment InputValue on __InputValue { name description type { ...TypeRef } defaultValue }
+        fragment TypeRef on __Type {
+          kind name
+          ofType { kind name ofType { kind name ofType { kind name ofType { kind name
+            ofType { kind name ofType { kind name ofType { kind name } } } } } } }
+        }
+    "#;
+}
@@ -0,0 +1,834 @@
+//! Parser for the supported subset of GraphQL query documents, and for
+//! the schema they run against.
+//!
+//! A document holds one query operation, either a bare selection set or
+//! `query Name($var: Type = default) { ... }`, and any number of
+//! `fragment Name on Type { ... }` definitions. Selections are fields with
+//! optional aliases and arguments, fragment spreads (`...Name`), and
+//! inline fragments (`... on Type { ... }`). Values may be variables,
+//! strings, numbers, booleans, `null`, enums, lists, and objects. Commas
+//! and `#` comments are ignored, as in GraphQL.
+//!
+//! Mutations, subscriptions, directives, and block strings are rejected
+//! with an error saying so, rather than misread.
+//!
+//! Schemas are parsed from the SDL subset `schema.graphql` uses: object
+//! types and enums, with descriptions as plain strings.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// Deepest nesting of selection sets and list or object values.
+pub const MAX_DEPTH: usize = 16;
+
+/// A parsed query operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation {
+    /// Operation name, if given.
+    pub name: Option<String>,
+    /// Declared variables and their defaults.
+    pub variables: Vec<VariableDefinition>,
+    /// Top-level selections.
+    pub selection: Vec<Selection>,
+    /// Fragments defined in the document, by name.
+    pub fragments: HashMap<String, Fragment>,
+}
+
+/// A named fragment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fragment {
+    /// Type the fragment applies to.
+    pub type_condition: String,
+    /// The fragment's selections.
+    pub selection: Vec<Selection>,
+}
+
+/// One entry of a selection set.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selection {
+    /// A field.
+    Field(Field),
+    /// `...Name`.
+    FragmentSpread(String),
+    /// `... on Type { ... }`, or `... { ... }` without a type condition.
+    InlineFragment {
+        /// Type the selections apply to; all types when None.
+        type_condition: Option<String>,
+        /// The fragment's selections.
+        selection: Vec<Selection>,
+    },
+}
+
+/// A declared variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableDefinition {
+    /// Name without the `$`.
+    pub name: String,
+    /// Whether the type is non-null (`String!`).
+    pub required: bool,
+    /// Default value.
+    pub default: Option<Value>,
+}
+
+/// A selected field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// Response key, if different from the name.
+    pub alias: Option<String>,
+    /// Field name.
+    pub name: String,
+    /// Arguments, in document order.
+    pub arguments: Vec<(String, Value)>,
+    /// Sub-selections; empty for leaf fields.
+    pub selection: Vec<Selection>,
+}
+
+impl Field {
+    /// Key of the field in the response.
+    pub fn response_key(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// An input value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// `$name`.
+    Variable(String),
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Boolean(bool),
+    /// An integer.
+    Int(i64),
+    /// A float.
+    Float(f64),
+    /// A string.
+    String(String),
+    /// An enum value.
+    Enum(String),
+    /// `[...]`.
+    List(Vec<Value>),
+    /// `{...}`.
+    Object(Vec<(String, Value)>),
+}
+
+/// Values in GraphQL syntax, as introspection reports defaults.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Variable(name) => write!(f, "${}", name),
+            Value::Null => f.write_str("null"),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Float(n) => write!(f, "{:?}", n),
+            Value::String(s) => write!(f, "{}", serde_json::Value::from(s.as_str())),
+            Value::Enum(name) => f.write_str(name),
+            Value::List(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", name, value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// A syntax error, with its 1-based position.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{message} at line {line}, column {column}")]
+pub struct SyntaxError {
+    /// What went wrong.
+    pub message: String,
+    /// Line of the error.
+    pub line: usize,
+    /// Column of the error.
+    pub column: usize,
+}
+
+/// Parse a query document.
+pub fn parse(document: &str) -> Result<Operation, SyntaxError> {
+    let mut parser = Parser::new(document)?;
+    let mut operation: Option<Operation> = None;
+    let mut fragments = HashMap::new();
+    while parser.peek().is_some() {
+        if parser.peek_keyword("fragment") {
+            let start = parser.pos;
+            let (name, fragment) = parser.fragment()?;
+            if fragments.insert(name.clone(), fragment).is_some() {
+                parser.pos = start;
+                return Err(parser.error(&format!("fragment '{}' is defined more than once", name)));
+            }
+        } else if operation.is_some() {
+            return Err(parser.error("only one operation per document is supported"));
+        } else {
+            operation = Some(parser.operation()?);
+        }
+    }
+    let mut operation = operation.ok_or_else(|| parser.error("expected an operation"))?;
+    operation.fragments = fragments;
+    Ok(operation)
+}
+
+/// A type in a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeDefinition {
+    /// Type name.
+    pub name: String,
+    /// Description, if given.
+    pub description: Option<String>,
+    /// Fields of an object type; empty for enums.
+    pub fields: Vec<FieldDefinition>,
+    /// Values of an enum; empty for object types.
+    pub enum_values: Vec<EnumValueDefinition>,
+}
+
+impl TypeDefinition {
+    /// Whether this is an enum rather than an object type.
+    pub fn is_enum(&self) -> bool {
+        !self.enum_values.is_empty()
+    }
+}
+
+/// A field of an object type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDefinition {
+    /// Field name.
+    pub name: String,
+    /// Description, if given.
+    pub description: Option<String>,
+    /// Accepted arguments.
+    pub arguments: Vec<InputValueDefinition>,
+    /// Type of the field's value.
+    pub field_type: TypeRef,
+}
+
+/// An argument of a field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputValueDefinition {
+    /// Argument name.
+    pub name: String,
+    /// Description, if given.
+    pub description: Option<String>,
+    /// Type of the argument.
+    pub value_type: TypeRef,
+    /// Default value.
+    pub default: Option<Value>,
+}
+
+/// A value of an enum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValueDefinition {
+    /// Value name.
+    pub name: String,
+    /// Description, if given.
+    pub description: Option<String>,
+}
+
+/// A reference to a type, with its list and non-null wrappers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeRef {
+    /// A named type.
+    Named(String),
+    /// `[Type]`.
+    List(Box<TypeRef>),
+    /// `Type!`.
+    NonNull(Box<TypeRef>),
+}
+
+/// Parse a schema in SDL.
+pub fn parse_schema(sdl: &str) -> Result<Vec<TypeDefinition>, SyntaxError> {
+    let mut parser = Parser::new(sdl)?;
+    let mut types = Vec::new();
+    while parser.peek().is_some() {
+        types.push(parser.type_definition()?);
+    }
+    Ok(types)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Punct(char),
+    Spread,
+    Name(String),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+struct Parser<'a> {
+    document: &'a str,
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(document: &'a str) -> Result<Self, SyntaxError> {
+        Ok(Parser {
+            document,
+            tokens: tokenize(document)?,
+            pos: 0,
+            depth: 0,
+        })
+    }
+
+    fn operation(&mut self) -> Result<Operation, SyntaxError> {
+        let mut operation = Operation {
+            name: None,
+            variables: Vec::new(),
+            selection: Vec::new(),
+            fragments: HashMap::new(),
+        };
+        match self.peek() {
+            Some(Token::Name(keyword)) if keyword == "query" => {
+                self.pos += 1;
+                if let Some(Token::Name(_)) = self.peek() {
+                    operation.name = Some(self.name()?);
+                }
+                if self.eat('(') {
+                    while !self.eat(')') {
+                        operation.variables.push(self.variable_definition()?);
+                    }
+                }
+            }
+            Some(Token::Name(keyword)) if keyword == "mutation" || keyword == "subscription" => {
+                let message = format!("{}s are not supported", keyword);
+                return Err(self.error(&message));
+            }
+            _ => {}
+        }
+        operation.selection = self.selection_set()?;
+        Ok(operation)
+    }
+
+    /// `fragment Name on Type { ... }`.
+    fn fragment(&mut self) -> Result<(String, Fragment), SyntaxError> {
+        self.pos += 1;
+        if self.peek_keyword("on") {
+            return Err(self.error("expected a fragment name"));
+        }
+        let name = self.name()?;
+        if !self.peek_keyword("on") {
+            return Err(self.error("expected 'on'"));
+        }
+        self.pos += 1;
+        let type_condition = self.name()?;
+        self.no_directives()?;
+        let selection = self.selection_set()?;
+        Ok((name, Fragment {
+            type_condition,
+            selection,
+        }))
+    }
+
+    fn variable_definition(&mut self) -> Result<VariableDefinition, SyntaxError> {
+        self.expect('$')?;
+        let name = self.name()?;
+        self.expect(':')?;
+        let required = self.type_reference()?;
+        let default = if self.eat('=') { Some(self.value(true)?) } else { None };
+        Ok(VariableDefinition {
+            name,
+            required,
+            default,
+        })
+    }
+
+    /// Skip a type like `[String!]!`, returning whether it's non-null.
+    fn type_reference(&mut self) -> Result<bool, SyntaxError> {
+        Ok(matches!(self.type_ref()?, TypeRef::NonNull(_)))
+    }
+
+    /// A type like `[String!]!`.
+    fn type_ref(&mut self) -> Result<TypeRef, SyntaxError> {
+        let inner = if self.eat('[') {
+            self.enter()?;
+            let item = self.type_ref()?;
+            self.expect(']')?;
+            self.depth -= 1;
+            TypeRef::List(Box::new(item))
+        } else {
+            TypeRef::Named(self.name()?)
+        };
+        Ok(if self.eat('!') {
+            TypeRef::NonNull(Box::new(inner))
+        } else {
+            inner
+        })
+    }
+
+    fn selection_set(&mut self) -> Result<Vec<Selection>, SyntaxError> {
+        self.enter()?;
+        let start = self.pos;
+        self.expect('{')?;
+        let mut selections = Vec::new();
+        while !self.eat('}') {
+            selections.push(self.selection()?);
+        }
+        if selections.is_empty() {
+            self.pos = start;
+            return Err(self.error("selection set is empty"));
+        }
+        self.depth -= 1;
+        Ok(selections)
+    }
+
+    fn selection(&mut self) -> Result<Selection, SyntaxError> {
+        if self.peek() != Some(&Token::Spread) {
+            return Ok(Selection::Field(self.field()?));
+        }
+        self.pos += 1;
+        if self.peek_keyword("on") {
+            self.pos += 1;
+            let type_condition = Some(self.name()?);
+            self.no_directives()?;
+            let selection = self.selection_set()?;
+            return Ok(Selection::InlineFragment {
+                type_condition,
+                selection,
+            });
+        }
+        if self.peek() == Some(&Token::Punct('{')) || self.peek() == Some(&Token::Punct('@')) {
+            self.no_directives()?;
+            let selection = self.selection_set()?;
+            return Ok(Selection::InlineFragment {
+                type_condition: None,
+                selection,
+            });
+        }
+        let name = self.name()?;
+        self.no_directives()?;
+        Ok(Selection::FragmentSpread(name))
+    }
+
+    fn no_directives(&self) -> Result<(), SyntaxError> {
+        if self.peek() == Some(&Token::Punct('@')) {
+            return Err(self.error("directives are not supported"));
+        }
+        Ok(())
+    }
+
+    fn field(&mut self) -> Result<Field, SyntaxError> {
+        let mut name = self.name()?;
+        let mut alias = None;
+        if self.eat(':') {
+            alias = Some(name);
+            name = self.name()?;
+        }
+
+        let mut arguments = Vec::new();
+        if self.eat('(') {
+            while !self.eat(')') {
+                let argument = self.name()?;
+                self.expect(':')?;
+                arguments.push((argument, self.value(false)?));
+            }
+        }
+        self.no_directives()?;
+
+        let selection = if self.peek() == Some(&Token::Punct('{')) {
+            self.selection_set()?
+        } else {
+            Vec::new()
+        };
+        Ok(Field {
+            alias,
+            name,
+            arguments,
+            selection,
+        })
+    }
+
+    /// Parse a value; constants (variable defaults) can't use variables.
+    fn value(&mut self, constant: bool) -> Result<Value, SyntaxError> {
+        let Some((token, _)) = self.tokens.get(self.pos).cloned() else {
+            return Err(self.error("expected a value"));
+        };
+        self.pos += 1;
+        Ok(match token {
+            Token::Punct('$') if !constant => Value::Variable(self.name()?),
+            Token::Int(n) => Value::Int(n),
+            Token::Float(n) => Value::Float(n),
+            Token::String(s) => Value::String(s),
+            Token::Name(name) => match name.as_str() {
+                "true" => Value::Boolean(true),
+                "false" => Value::Boolean(false),
+                "null" => Value::Null,
+                _ => Value::Enum(name),
+            },
+            Token::Punct('[') => {
+                self.enter()?;
+                let mut items = Vec::new();
+                while !self.eat(']') {
+                    items.push(self.value(constant)?);
+                }
+                self.depth -= 1;
+                Value::List(items)
+            }
+            Token::Punct('{') => {
+                self.enter()?;
+                let mut fields = Vec::new();
+                while !self.eat('}') {
+                    let name = self.name()?;
+                    self.expect(':')?;
+                    fields.push((name, self.value(constant)?));
+                }
+                self.depth -= 1;
+                Value::Object(fields)
+            }
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("expected a value"));
+            }
+        })
+    }
+
+    /// `type Name { ... }` or `enum Name { ... }`, with an optional
+    /// description.
+    fn type_definition(&mut self) -> Result<TypeDefinition, SyntaxError> {
+        let description = self.description();
+        let kind = self.name()?;
+        if kind != "type" && kind != "enum" {
+            self.pos -= 1;
+            return Err(self.error("expected 'type' or 'enum'"));
+        }
+        let mut definition = TypeDefinition {
+            name: self.name()?,
+            description,
+            fields: Vec::new(),
+            enum_values: Vec::new(),
+        };
+        self.expect('{')?;
+        while !self.eat('}') {
+            let description = self.description();
+            let name = self.name()?;
+            if kind == "enum" {
+                definition.enum_values.push(EnumValueDefinition { name, description });
+                continue;
+            }
+            let mut arguments = Vec::new();
+            if self.eat('(') {
+                while !self.eat(')') {
+                    let description = self.description();
+                    let name = self.name()?;
+                    self.expect(':')?;
+                    let value_type = self.type_ref()?;
+                    let default = if self.eat('=') { Some(self.value(true)?) } else { None };
+                    arguments.push(InputValueDefinition {
+                        name,
+                        description,
+                        value_type,
+                        default,
+                    });
+                }
+            }
+            self.expect(':')?;
+            definition.fields.push(FieldDefinition {
+                name,
+                description,
+                arguments,
+                field_type: self.type_ref()?,
+            });
+        }
+        Ok(definition)
+    }
+
+    /// A string before a definition, if there is one.
+    fn description(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(Token::String(text)) => {
+                let text = text.clone();
+                self.pos += 1;
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+
+    /// Go one level deeper, up to `MAX_DEPTH`.
+    fn enter(&mut self) -> Result<(), SyntaxError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error(&format!("nested too deeply (max {} levels)", MAX_DEPTH)));
+        }
+        Ok(())
+    }
+
+    fn name(&mut self) -> Result<String, SyntaxError> {
+        match self.peek() {
+            Some(Token::Name(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => Err(self.error("expected a name")),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Name(name)) if name == keyword)
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        let found = self.peek() == Some(&Token::Punct(punct));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, punct: char) -> Result<(), SyntaxError> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", punct)))
+        }
+    }
+
+    /// An error at the current token, or the end of the document.
+    fn error(&self, message: &str) -> SyntaxError {
+        let offset = self
+            .tokens
+            .get(self.pos)
+            .map_or(self.document.len(), |(_, offset)| *offset);
+        error_at(self.document, offset, message)
+    }
+}
+
+fn error_at(document: &str, offset: usize, message: &str) -> SyntaxError {
+    let before = &document[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    SyntaxError {
+        message: message.to_string(),
+        line,
+        column,
+    }
+}
+
+fn tokenize(document: &str) -> Result<Vec<(Token, usize)>, SyntaxError> {
+    let mut tokens = Vec::new();
+    let mut chars = document.char_indices().peekable();
+
+    while let Some(&(offset, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() || c == ',' || c == '\u{feff}' => {
+                chars.next();
+            }
+            '#' => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '{' | '}' | '(' | ')' | '[' | ']' | ':' | '!' | '$' | '=' | '@' | '|' | '&' => {
+                chars.next();
+                tokens.push((Token::Punct(c), offset));
+            }
+            '.' => {
+                if !document[offset..].starts_with("...") {
+                    return Err(error_at(document, offset, "unexpected '.'"));
+                }
+                chars.nth(2);
+                tokens.push((Token::Spread, offset));
+            }
+            '"' => {
+                chars.next();
+                tokens.push((Token::String(read_string(document, offset, &mut chars)?), offset));
+            }
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let mut name = String::new();
+                while let Some((_, c)) = chars.next_if(|&(_, c)| c == '_' || c.is_ascii_alphanumeric()) {
+                    name.push(c);
+                }
+                tokens.push((Token::Name(name), offset));
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                let mut number = String::new();
+                while let Some((_, c)) = chars.next_if(|&(_, c)| {
+                    c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')
+                }) {
+                    number.push(c);
+                }
+                let token = if number.contains(['.', 'e', 'E']) {
+                    number.parse().ok().map(Token::Float)
+                } else {
+                    number.parse().ok().map(Token::Int)
+                };
+                let token = token.ok_or_else(|| error_at(document, offset, "invalid number"))?;
+                tokens.push((token, offset));
+            }
+            _ => return Err(error_at(document, offset, &format!("unexpected '{}'", c))),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Read a string after its opening quote. Block strings aren't supported.
+fn read_string(
+    document: &str,
+    start: usize,
+    chars: &mut Peekable<CharIndices>,
+) -> Result<String, SyntaxError> {
+    if document[start..].starts_with("\"\"\"") {
+        return Err(error_at(document, start, "block strings are not supported"));
+    }
+    let unterminated = || error_at(document, start, "unterminated string");
+    let mut text = String::new();
+    loop {
+        let (offset, c) = chars.next().ok_or_else(unterminated)?;
+        match c {
+            '"' => return Ok(text),
+            '\n' => return Err(unterminated()),
+            '\\' => {
+                let (_, escaped) = chars.next().ok_or_else(unterminated)?;
+                text.push(match escaped {
+                    '"' => '"',
+                    '\\' => '\\',
+                    '/' => '/',
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'u' => {
+                        let hex: String = (0..4).filter_map(|_| chars.next().map(|(_, c)| c)).collect();
+                        u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| error_at(document, offset, "invalid unicode escape"))?
+                    }
+                    _ => return Err(error_at(document, offset, "invalid escape")),
+                });
+            }
+            c => text.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let operation = parse(
+            r#"query SkillPage($name: String!, $limit: Int = 5) {
+                # The page's skill
+                page: skill(name: $name) { name tags subSkills { name } }
+                search(query: "form \"state\"", limit: $limit, filter: {tags: [a, "b"]}) { score }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(operation.name.as_deref(), Some("SkillPage"));
+        assert_eq!(operation.variables.len(), 2);
+        assert!(operation.variables[0].required);
+        assert_eq!(operation.variables[1].default, Some(Value::Int(5)));
+
+        let page = field(&operation.selection[0]);
+        assert_eq!((page.response_key(), page.name.as_str()), ("page", "skill"));
+        assert_eq!(page.arguments, vec![("name".to_string(), Value::Variable("name".to_string()))]);
+        assert_eq!(field(&field(&page.selection[2]).selection[0]).name, "name");
+
+        let search = field(&operation.selection[1]);
+        assert_eq!(search.arguments[0].1, Value::String("form \"state\"".to_string()));
+        assert_eq!(
+            search.arguments[2].1,
+            Value::Object(vec![(
+                "tags".to_string(),
+                Value::List(vec![Value::Enum("a".to_string()), Value::String("b".to_string())])
+            )])
+        );
+
+        assert_eq!(field(&parse("{ stats { searches } }").unwrap().selection[0]).name, "stats");
+    }
+
+    fn field(selection: &Selection) -> &Field {
+        match selection {
+            Selection::Field(field) => field,
+            other => panic!("not a field: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_fragments() {
+        let operation = parse(
+            r#"fragment Names on Skill { name aliases }
+            { skill(name: "forms") { ...Names ... on Skill { tags } ... { description } } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(operation.fragments["Names"].type_condition, "Skill");
+        assert_eq!(operation.fragments["Names"].selection.len(), 2);
+        let skill = field(&operation.selection[0]);
+        assert_eq!(skill.selection[0], Selection::FragmentSpread("Names".to_string()));
+        assert!(matches!(
+            &skill.selection[1],
+            Selection::InlineFragment { type_condition: Some(t), .. } if t == "Skill"
+        ));
+        assert!(matches!(&skill.selection[2], Selection::InlineFragment { type_condition: None, .. }));
+    }
+
+    #[test]
+    fn test_parse_schema() {
+        let types = parse_schema(
+            r#""A skill." type Skill { name: String! "Sub-skill by name." subSkill(name: String!, limit: Int = 5): [Sub!] }
+            enum Kind { A "Second." B }"#,
+        )
+        .unwrap();
+
+        assert_eq!(types[0].description.as_deref(), Some("A skill."));
+        let sub = &types[0].fields[1];
+        assert_eq!(sub.description.as_deref(), Some("Sub-skill by name."));
+        assert_eq!(sub.arguments[1].default, Some(Value::Int(5)));
+        assert_eq!(
+            sub.field_type,
+            TypeRef::List(Box::new(TypeRef::NonNull(Box::new(TypeRef::Named("Sub".to_string())))))
+        );
+        assert!(types[1].is_enum());
+        assert_eq!(types[1].enum_values[1].description.as_deref(), Some("Second."));
+
+        assert_eq!(Value::List(vec![Value::String("a\"b".to_string()), Value::Null]).to_string(), r#"["a\"b", null]"#);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let message = |document: &str| parse(document).unwrap_err().to_string();
+
+        assert_eq!(message("mutation { x }"), "mutations are not supported at line 1, column 1");
+        assert_eq!(message("{ skill @include(if: true) }"), "directives are not supported at line 1, column 9");
+        assert_eq!(message("{ ...Page @skip(if: true) }"), "directives are not supported at line 1, column 11");
+        assert_eq!(message("fragment on on Skill { name }"), "expected a fragment name at line 1, column 10");
+        assert_eq!(message("fragment A Skill { name }"), "expected 'on' at line 1, column 12");
+        assert_eq!(
+            message("fragment A on Skill { name } fragment A on Skill { tags } { a }"),
+            "fragment 'A' is defined more than once at line 1, column 30"
+        );
+        assert_eq!(message("fragment A on Skill { name }"), "expected an operation at line 1, column 29");
+        assert_eq!(message("{\n  skill(name: ) }"), "expected a value at line 2, column 15");
+        assert_eq!(message("{ skill"), "expected a name at line 1, column 8");
+        assert_eq!(message("{ }"), "selection set is empty at line 1, column 1");
+        assert!(message("{ a(b: \"open) }").starts_with("unterminated string"));
+        assert!(message("{ a } { b }").starts_with("only one operation"));
+        assert!(message(&"{ a ".repeat(MAX_DEPTH + 1)).starts_with("nested too deeply"));
+    }
+}
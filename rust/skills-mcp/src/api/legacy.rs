@@ -0,0 +1,238 @@
+//! Version 1 of the API: request bodies as the Flask API took them.
+//!
+//! `/api/v1` serves the same routes as `/api/v2` and the unversioned
+//! `/api`, except that these accept the payloads scripts written against
+//! skills_manager_api.py post:
+//!
+//! - `POST /skills`: `description` and `content` default to empty, `tags`
+//!   may be a comma-separated string, `sub_skills` may list bare names
+//!   (`"react"` for `react.md`), and `overwrite: true` updates an existing
+//!   skill in place instead of failing with 409
+//! - `PUT /skills/:name`: the same `tags` shapes, with `null` or a missing
+//!   field leaving a field as it is
+//! - `POST /import/folder`: `{path, name}`, where an empty `name` keeps the
+//!   imported skill's own; `path` must be inside the `[import]` root, as
+//!   for the current import route
+//!
+//! Each body converts into the current request and goes to the current
+//! handler, so responses are the current ones. The Flask API's
+//! `/import/json` upload has no counterpart.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::models::{ErrorResponse, SubSkillMeta};
+
+use super::routes::{self, AppState, CreateSkillRequest, ImportClaudeSkillRequest, UpdateSkillRequest};
+use super::Caller;
+
+type LegacyResult = Result<Response, (StatusCode, Json<ErrorResponse>)>;
+
+/// Tags as a list or a comma-separated string.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LegacyTags {
+    List(Vec<String>),
+    Joined(String),
+}
+
+impl From<LegacyTags> for Vec<String> {
+    fn from(tags: LegacyTags) -> Self {
+        match tags {
+            LegacyTags::List(tags) => tags,
+            LegacyTags::Joined(tags) => tags
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+/// A sub-skill as an object or a bare name.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LegacySubSkill {
+    Name(String),
+    Meta(SubSkillMeta),
+}
+
+impl From<LegacySubSkill> for SubSkillMeta {
+    fn from(sub_skill: LegacySubSkill) -> Self {
+        match sub_skill {
+            LegacySubSkill::Name(name) => SubSkillMeta {
+                file: format!("{}.md", name),
+                name,
+                triggers: Vec::new(),
+                language: None,
+            },
+            LegacySubSkill::Meta(meta) => meta,
+        }
+    }
+}
+
+// ============================================================================
+// POST /api/v1/skills - Create skill
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSkillRequestV1 {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    tags: Option<LegacyTags>,
+    #[serde(default)]
+    sub_skills: Vec<LegacySubSkill>,
+    #[serde(default)]
+    overwrite: bool,
+}
+
+impl From<CreateSkillRequestV1> for CreateSkillRequest {
+    fn from(req: CreateSkillRequestV1) -> Self {
+        Self {
+            name: req.name,
+            description: req.description,
+            content: req.content,
+            tags: req.tags.map(Vec::from).unwrap_or_default(),
+            sub_skills: req.sub_skills.into_iter().map(SubSkillMeta::from).collect(),
+        }
+    }
+}
+
+pub async fn create_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(req): Json<CreateSkillRequestV1>,
+) -> LegacyResult {
+    // Overwriting updates in place, keeping the skill's other files
+    if req.overwrite && state.indexer.skill_exists(&req.name) {
+        let update = UpdateSkillRequest {
+            description: Some(req.description),
+            content: Some(req.content),
+            tags: req.tags.map(Vec::from),
+            base_sha256: None,
+        };
+        return routes::update_skill(State(state), caller, Path(req.name), Json(update))
+            .await
+            .map(IntoResponse::into_response);
+    }
+
+    routes::create_skill(State(state), caller, Json(req.into()))
+        .await
+        .map(IntoResponse::into_response)
+}
+
+// ============================================================================
+// PUT /api/v1/skills/:name - Update skill
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSkillRequestV1 {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tags: Option<LegacyTags>,
+}
+
+impl From<UpdateSkillRequestV1> for UpdateSkillRequest {
+    fn from(req: UpdateSkillRequestV1) -> Self {
+        Self {
+            description: req.description,
+            content: req.content,
+            tags: req.tags.map(Vec::from),
+            base_sha256: None,
+        }
+    }
+}
+
+pub async fn update_skill(
+    state: State<AppState>,
+    caller: Caller,
+    name: Path<String>,
+    Json(req): Json<UpdateSkillRequestV1>,
+) -> LegacyResult {
+    routes::update_skill(state, caller, name, Json(req.into()))
+        .await
+        .map(IntoResponse::into_response)
+}
+
+// ============================================================================
+// POST /api/v1/import/folder - Import a skill folder
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ImportFolderRequestV1 {
+    path: String,
+    #[serde(default)]
+    name: String,
+}
+
+impl From<ImportFolderRequestV1> for ImportClaudeSkillRequest {
+    fn from(req: ImportFolderRequestV1) -> Self {
+        Self {
+            path: req.path,
+            name: Some(req.name).filter(|name| !name.is_empty()),
+            merge: false,
+        }
+    }
+}
+
+pub async fn import_folder(
+    state: State<AppState>,
+    caller: Caller,
+    Json(req): Json<ImportFolderRequestV1>,
+) -> LegacyResult {
+    routes::import_claude_skill(state, caller, Json(req.into()))
+        .await
+        .map(IntoResponse::into_response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_create_request_shims() {
+        let req: CreateSkillRequestV1 = serde_json::from_value(json!({
+            "name": "forms",
+            "tags": "frontend, validation,",
+            "sub_skills": ["react", {"name": "vue", "file": "guides/vue.md"}],
+        }))
+        .unwrap();
+        let req = CreateSkillRequest::from(req);
+        assert_eq!(req.description, "");
+        assert_eq!(req.content, "");
+        assert_eq!(req.tags, vec!["frontend", "validation"]);
+        let files: Vec<&str> = req.sub_skills.iter().map(|s| s.file.as_str()).collect();
+        assert_eq!(files, vec!["react.md", "guides/vue.md"]);
+
+        let req: CreateSkillRequestV1 =
+            serde_json::from_value(json!({"name": "forms", "tags": null, "overwrite": true})).unwrap();
+        assert!(req.overwrite);
+        assert!(CreateSkillRequest::from(req).tags.is_empty());
+    }
+
+    #[test]
+    fn test_update_and_import_request_shims() {
+        let req: UpdateSkillRequestV1 =
+            serde_json::from_value(json!({"content": "# Forms", "tags": ["a", "b"]})).unwrap();
+        let req = UpdateSkillRequest::from(req);
+        assert_eq!(req.description, None);
+        assert_eq!(req.tags, Some(vec!["a".to_string(), "b".to_string()]));
+
+        let req: ImportFolderRequestV1 = serde_json::from_value(json!({"path": "/tmp/forms", "name": ""})).unwrap();
+        assert_eq!(ImportClaudeSkillRequest::from(req).name, None);
+    }
+}
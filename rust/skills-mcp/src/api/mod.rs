@@ -2,8 +2,26 @@
 //!
 //! Provides REST endpoints for skill management, matching the Flask API
 //! in skills_manager_api.py.
+//!
+//! # Timestamps
+//!
+//! Every timestamp in a response (skill `created_at`/`updated_at`, audit
+//! entries, events, analytics, reports) is an RFC 3339 string in UTC with
+//! millisecond precision and a `Z` suffix, e.g. `2024-05-01T09:30:00.000Z`.
+//! Query parameters accept any RFC 3339 offset. See
+//! [`models::timestamp`](crate::models::timestamp).
 
+mod caller;
+mod conditional;
+#[cfg(feature = "graphql")]
+mod graphql;
+mod legacy;
+mod openapi;
+mod request_id;
 mod routes;
 mod server;
+mod tenants;
 
+pub use caller::Caller;
+pub use request_id::{RequestId, REQUEST_ID_HEADER};
 pub use server::ApiServer;
@@ -0,0 +1,767 @@
+//! OpenAPI 3.0 description of the REST API.
+//!
+//! Served at `GET /api/openapi.json`, with Swagger UI at `GET /api/docs`,
+//! for generating typed clients. Every route in
+//! [`ApiServer::router`](super::ApiServer::router) has an entry in
+//! [`OPERATIONS`]; the tests fail when a route is added without one, or
+//! when a documented response no longer matches what the handler returns.
+
+use serde_json::{json, Map, Value};
+
+/// A query string parameter.
+struct QueryParam {
+    name: &'static str,
+    kind: &'static str,
+    required: bool,
+    description: &'static str,
+}
+
+const fn param(name: &'static str, kind: &'static str, description: &'static str) -> QueryParam {
+    QueryParam {
+        name,
+        kind,
+        required: false,
+        description,
+    }
+}
+
+/// What a successful response carries.
+enum Payload {
+    /// A JSON object described by a component schema.
+    Schema(&'static str),
+    /// A JSON array of a component schema.
+    List(&'static str),
+    /// No body.
+    Empty,
+    /// A body of another media type.
+    Content(&'static str),
+}
+
+/// One documented route.
+struct Operation {
+    method: &'static str,
+    /// Path in router syntax (`/skills/:name`), relative to `/api`.
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    query: &'static [QueryParam],
+    request: Option<&'static str>,
+    status: u16,
+    response: Payload,
+}
+
+const fn op(
+    method: &'static str,
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    response: Payload,
+) -> Operation {
+    Operation {
+        method,
+        path,
+        tag,
+        summary,
+        query: &[],
+        request: None,
+        status: 200,
+        response,
+    }
+}
+
+impl Operation {
+    const fn query(mut self, query: &'static [QueryParam]) -> Self {
+        self.query = query;
+        self
+    }
+
+    const fn body(mut self, schema: &'static str) -> Self {
+        self.request = Some(schema);
+        self
+    }
+
+    const fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+use Payload::{Content, Empty, List, Schema};
+
+const LIMIT: QueryParam = param("limit", "integer", "Maximum number of results");
+const INCLUDE_ARCHIVED: QueryParam = param("include_archived", "boolean", "Include archived skills");
+
+/// Every API route, in router order.
+const OPERATIONS: &[Operation] = &[
+    op("get", "/skills", "skills", "List skills", List("SkillListItem"))
+        .query(&[param("collection", "string", "Only skills in this collection"), INCLUDE_ARCHIVED]),
+    op("post", "/skills", "skills", "Create a skill", Schema("SkillDetails"))
+        .body("CreateSkillRequest")
+        .status(201),
+    op("post", "/skills/import-claude-skill", "skills", "Import a Claude-format skill directory from inside the [import] root, or merge it into an existing skill", Schema("Report"))
+        .body("ImportClaudeSkillRequest")
+        .status(201),
+    op("post", "/skills/import-from-url", "skills", "Download a GitHub repository archive and import the skills in it, or merge them into existing skills", Schema("Report"))
+        .body("ImportFromUrlRequest")
+        .status(201),
+    op("get", "/import/conflicts", "skills", "Files left unmerged by imports, by skill", List("Report")),
+    op("post", "/import/conflicts/:name/resolve", "skills", "Resolve a conflicting file with the local, incoming, or given content", Schema("Report"))
+        .body("ResolveConflictRequest"),
+    op("get", "/skills/:name", "skills", "Get a skill and its SKILL.md content", Schema("SkillDetails"))
+        .query(&[
+            param("minify", "boolean", "Strip badges, HTML comments, and navigation lines"),
+            param("lang", "string", "Serve the SKILL.<lang>.md translation, falling back to SKILL.md"),
+        ]),
+    op("put", "/skills/:name", "skills", "Update a skill's description, content, or tags", Schema("SkillDetails"))
+        .body("UpdateSkillRequest"),
+    op("patch", "/skills/:name", "skills", "Patch SKILL.md with a unified diff or section operations", Schema("PatchSkillResponse"))
+        .body("PatchSkillRequest"),
+    op("delete", "/skills/:name", "skills", "Delete a skill", Empty).status(204),
+    op("post", "/skills/:name/rename", "skills", "Rename a skill, rewriting links to it", Schema("Report"))
+        .body("RenameSkillRequest"),
+    op("post", "/skills/:name/duplicate", "skills", "Copy a skill under a new name", Schema("Report"))
+        .body("DuplicateSkillRequest")
+        .status(201),
+    op("get", "/skills/:name/readers", "skills", "Who has read a skill, and when", Schema("Report")),
+    op("get", "/skills/:name/dependencies", "skills", "Dependencies and dependents of a skill", Schema("Report")),
+    op("get", "/skills/:name/html", "skills", "SKILL.md or a sub-skill rendered to an HTML fragment", Content("text/html"))
+        .query(&[param("sub_skill", "string", "Render this sub-skill instead of SKILL.md")]),
+    op("get", "/skills/:name/tables", "skills", "Markdown tables in a skill, parsed", Schema("Report"))
+        .query(&[param("sub_skill", "string", "Only tables from this sub-skill")]),
+    op("get", "/skills/:name/outline", "skills", "Heading hierarchy with token counts per section", Schema("Report"))
+        .query(&[param("sub_skill", "string", "Outline this sub-skill instead of SKILL.md")]),
+    op("get", "/skills/:name/examples", "skills", "Fenced code blocks with their language and nearest heading", Schema("Report"))
+        .query(&[param("lang", "string", "Only blocks in this code language (aliases like py allowed)")]),
+    op("post", "/skills/:name/test-triggers", "search", "Which sub-skills sample phrases select, and why", Schema("Report"))
+        .body("TestTriggersRequest"),
+    op("get", "/skills/:name/stats", "skills", "Word, heading, code block, and token counts per file", Schema("Report"))
+        .query(&[param("max_tokens", "integer", "Token budget to check the total against")]),
+    op("get", "/skills/:name/scripts", "skills", "Scripts in the skill's scripts/ directory and whether each can run", Schema("Report")),
+    op("get", "/skills/:name/links", "links", "Curated and computed related skills", Schema("SkillLinksResponse")),
+    op("put", "/skills/:name/links", "links", "Replace a skill's curated see-also links", Schema("SkillLinksResponse"))
+        .body("UpdateLinksRequest"),
+    op("get", "/skills/:name/backlinks", "links", "Links to a skill from other skills", Schema("Report"))
+        .query(&[param("file", "string", "Only links to this file within the skill")]),
+    op("get", "/skills/:name/references", "references", "List reference files with MIME type and size", Schema("ReferencesResponse")),
+    op("get", "/skills/:name/references/*path", "references", "Download a reference file; supports Range requests", Content("application/octet-stream"))
+        .query(&[param("format", "string", "html renders a markdown reference to an HTML fragment")]),
+    op("post", "/skills/:name/freeze", "cold storage", "Move a skill to cold storage", Schema("Report")),
+    op("post", "/skills/:name/archive", "skills", "Hide a skill from listings and search", Schema("Report")),
+    op("post", "/skills/:name/unarchive", "skills", "Make an archived skill visible again", Schema("Report")),
+    op("get", "/collections", "skills", "List collections and their skills", List("Collection")),
+    op("get", "/tags", "skills", "List tags with the skills using them, most used first", List("TagCount")),
+    op("post", "/tags/rename", "skills", "Rename a tag in every skill's _meta.json", Schema("Report"))
+        .body("RenameTagRequest"),
+    op("get", "/bundles", "bundles", "List skill bundles", List("Report")),
+    op("get", "/bundles/:name", "bundles", "Load a bundle's skills, dependencies first, within its token budget", Schema("Report"))
+        .query(&[param("max_tokens", "integer", "Token budget overriding the bundle's own")]),
+    op("get", "/cold", "cold storage", "List skills in cold storage", List("Report")),
+    op("post", "/cold/sweep", "cold storage", "Freeze skills unused for a number of days", Schema("Report"))
+        .body("ColdSweepRequest"),
+    op("post", "/cold/:name/restore", "cold storage", "Restore a skill from cold storage", Schema("Report")),
+    op("post", "/backup", "backups", "Archive the skills directory and index to the backup directory", Schema("Report"))
+        .status(201),
+    op("get", "/backups", "backups", "List backups, newest first", List("Report")),
+    op("post", "/backups/:id/restore", "backups", "Preview a restore, or restore a backup when confirmed", Schema("Report"))
+        .body("RestoreBackupRequest"),
+    op("post", "/reload", "index", "Start a background reload of the index", Schema("ReloadStatus"))
+        .query(&[param("wait", "boolean", "Respond when the reload finishes")])
+        .status(202),
+    op("get", "/reload/:job_id", "index", "Progress of a reload job", Schema("ReloadStatus")),
+    op("get", "/watch/status", "index", "State of the file watcher", Schema("WatchStatus")),
+    op("get", "/events", "index", "Server-sent events for skill changes", Content("text/event-stream")),
+    op("get", "/search", "search", "Search skills", Schema("SearchResults")).query(&[
+        QueryParam {
+            name: "q",
+            kind: "string",
+            required: true,
+            description: "Query: words, \"phrases\", tag:x, domain:x, -excluded, AND/OR",
+        },
+        LIMIT,
+        param("include_cold", "boolean", "Also search skills in cold storage"),
+        param("collection", "string", "Only skills in this collection"),
+        param("lang", "string", "Only content in this language"),
+        INCLUDE_ARCHIVED,
+        param("boost", "string", "Field boosts for this search, e.g. tags:4,description:2"),
+    ]),
+    op("post", "/search/fusion", "search", "Search with query variants fused by rank", Schema("FusedSearchResults"))
+        .body("FusionSearchRequest"),
+    op("post", "/match/explain", "search", "Explain why a skill does or doesn't match a task", Schema("Report"))
+        .body("ExplainMatchRequest"),
+    op("get", "/diff", "skills", "Compare two skills' metadata field by field and files by path", Schema("Report")).query(&[
+        QueryParam {
+            name: "left",
+            kind: "string",
+            required: true,
+            description: "Skill to compare from, e.g. the origin",
+        },
+        QueryParam {
+            name: "right",
+            kind: "string",
+            required: true,
+            description: "Skill to compare to, e.g. a fork",
+        },
+    ]),
+    op("post", "/replace", "skills", "Preview, or apply when dry_run is false, a search and replace across skills", Schema("Report"))
+        .body("ReplaceRequest"),
+    op("get", "/integrity", "admin", "Result of the last integrity verification", Schema("Report")),
+    op("get", "/stats", "analytics", "Usage analytics, content cache, and search cache counters", Schema("Report")).query(&[LIMIT]),
+    op("get", "/validate", "admin", "Validate and lint every skill", Schema("Report")),
+    op("get", "/analyze/storage", "admin", "Identical files in the content index and what deduplicating them saves", Schema("Report")),
+    op("get", "/audit", "admin", "Log of skill mutations, newest first", List("Report")).query(&[
+        param("since", "string", "Only entries at or after this RFC 3339 time"),
+        param("until", "string", "Only entries at or before this RFC 3339 time"),
+        param("skill", "string", "Only entries for this skill"),
+        param("actor", "string", "Only entries by this actor"),
+        LIMIT,
+    ]),
+    op("get", "/analytics/tokens", "analytics", "Tokens of skill content served", Schema("Report")).query(&[LIMIT]),
+    op("get", "/analytics/tokens/sessions", "analytics", "Tokens served per session", List("Report")).query(&[LIMIT]),
+    op("get", "/report/quality", "admin", "Skill quality scores, lowest first, with coverage of metadata and examples", Schema("Report"))
+        .query(&[LIMIT]),
+    op("get", "/report/deprecations", "admin", "Deprecated skills with the skills still depending on or linking to them", Schema("Report")),
+    op("get", "/reports/weekly", "analytics", "Weekly usage digest", Content("text/markdown"))
+        .query(&[param("format", "string", "markdown (default), html, or json")]),
+    op("post", "/reports/weekly/send", "analytics", "Deliver the weekly digest to notifiers now", Schema("Report")),
+    op("get", "/admin/maintenance", "admin", "Maintenance mode status", Schema("MaintenanceStatus")),
+    op("post", "/admin/maintenance", "admin", "Enable or disable maintenance mode", Schema("MaintenanceStatus"))
+        .body("MaintenanceRequest"),
+    op("get", "/schema/meta", "meta", "JSON Schema for _meta.json files", Content("application/json")),
+    op("get", "/openapi.json", "meta", "This document", Content("application/json")),
+    op("get", "/docs", "meta", "Swagger UI for this document", Content("text/html")),
+];
+
+/// The OpenAPI document.
+pub fn spec() -> Value {
+    let mut paths = Map::new();
+    for operation in OPERATIONS {
+        let (path, path_params) = openapi_path(operation.path);
+        let mut parameters: Vec<Value> = path_params
+            .iter()
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                })
+            })
+            .collect();
+        parameters.extend(operation.query.iter().map(|q| {
+            json!({
+                "name": q.name,
+                "in": "query",
+                "required": q.required,
+                "description": q.description,
+                "schema": { "type": q.kind },
+            })
+        }));
+
+        let content = |schema: Value| json!({ "application/json": { "schema": schema } });
+        let success = match operation.response {
+            Schema(name) => json!({ "description": "Success", "content": content(schema_ref(name)) }),
+            List(name) => json!({
+                "description": "Success",
+                "content": content(json!({ "type": "array", "items": schema_ref(name) })),
+            }),
+            Empty => json!({ "description": "Success" }),
+            Content(media_type) => {
+                let kind = if media_type.ends_with("json") { "object" } else { "string" };
+                json!({
+                    "description": "Success",
+                    "content": { media_type: { "schema": { "type": kind } } },
+                })
+            }
+        };
+        let error = json!({ "description": "Error", "content": content(schema_ref("ErrorResponse")) });
+
+        let mut entry = json!({
+            "tags": [operation.tag],
+            "summary": operation.summary,
+            "operationId": operation_id(operation),
+            "parameters": parameters,
+            "responses": {
+                operation.status.to_string(): success,
+                "4XX": error,
+            },
+        });
+        if let Some(request) = operation.request {
+            entry["requestBody"] = json!({ "required": true, "content": content(schema_ref(request)) });
+        }
+
+        paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .expect("path item is an object")
+            .insert(operation.method.to_string(), entry);
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Skills MCP API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "REST API for browsing, searching, and editing skills. Timestamps are RFC 3339 strings in UTC.",
+        },
+        "servers": [{ "url": "/api" }, { "url": "/api/v2" }],
+        "paths": paths,
+        // The key is optional; it identifies the caller and unlocks private
+        // skills that allow it
+        "security": [{}, { "apiKey": [] }],
+        "components": {
+            "schemas": schemas(),
+            "securitySchemes": {
+                "apiKey": {
+                    "type": "apiKey",
+                    "in": "header",
+                    "name": "X-Api-Key",
+                    "description": "Private skills are only listed, searched, and served for the keys in their allowed_keys.",
+                },
+            },
+        },
+    })
+}
+
+/// Swagger UI page for the document at `/api/openapi.json`.
+pub const DOCS_HTML: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Skills MCP API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+  <script>
+    SwaggerUIBundle({ url: "/api/openapi.json", dom_id: "#swagger-ui" });
+  </script>
+</body>
+</html>
+"##;
+
+/// Convert a router path to OpenAPI syntax, returning its path parameters.
+fn openapi_path(path: &str) -> (String, Vec<&str>) {
+    let mut params = Vec::new();
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| match segment.strip_prefix([':', '*']) {
+            Some(name) => {
+                params.push(name);
+                format!("{{{}}}", name)
+            }
+            None => segment.to_string(),
+        })
+        .collect();
+    (segments.join("/"), params)
+}
+
+/// camelCase operation ID from the method and path, e.g.
+/// `getSkillsByNameReferences` for `GET /skills/:name/references`.
+fn operation_id(operation: &Operation) -> String {
+    let mut id = operation.method.to_string();
+    for segment in operation.path.split('/').filter(|s| !s.is_empty()) {
+        let segment = match segment.strip_prefix([':', '*']) {
+            Some(param) => {
+                id.push_str("By");
+                param
+            }
+            None => segment,
+        };
+        for word in segment.split(['-', '.', '_']) {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                id.push(first.to_ascii_uppercase());
+                id.extend(chars);
+            }
+        }
+    }
+    id
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn strings() -> Value {
+    json!({ "type": "array", "items": { "type": "string" } })
+}
+
+fn flag(default: bool) -> Value {
+    json!({ "type": "boolean", "default": default })
+}
+
+fn timestamp() -> Value {
+    json!({ "type": "string", "format": "date-time" })
+}
+
+fn object(properties: Value, required: &[&str]) -> Value {
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// Component schemas for response bodies.
+fn schemas() -> Value {
+    let mut schemas = json!({
+        "ErrorResponse": object(
+            json!({
+                "code": {
+                    "type": "string",
+                    "enum": [
+                        "SKILL_NOT_FOUND", "NOT_FOUND", "INVALID_NAME", "INVALID_PATH",
+                        "VALIDATION_FAILED", "INVALID_REQUEST", "META_PARSE_ERROR",
+                        "ALREADY_EXISTS", "CONFLICT", "READ_ONLY", "MAINTENANCE", "THROTTLED",
+                        "DISABLED", "IO_ERROR", "INTERNAL_ERROR",
+                    ],
+                },
+                "error": string(),
+                "details": {
+                    "type": "array",
+                    "items": object(json!({ "field": string(), "message": string() }), &["field", "message"]),
+                },
+                "suggestions": strings(),
+                "request_id": string(),
+            }),
+            &["code", "error"],
+        ),
+        "Report": {
+            "type": "object",
+            "description": "A JSON report; see the endpoint's documentation for its fields.",
+            "additionalProperties": true,
+        },
+        "SkillListItem": object(
+            json!({
+                "name": string(),
+                "collection": string(),
+                "description": string(),
+                "tags": strings(),
+                "sub_skills": strings(),
+                "file_count": { "type": "integer" },
+                "archived": { "type": "boolean" },
+                "deprecated": { "type": "boolean" },
+                "created_at": timestamp(),
+                "updated_at": timestamp(),
+            }),
+            &["name", "description", "tags", "sub_skills", "file_count"],
+        ),
+        "SubSkillInfo": object(
+            json!({ "name": string(), "file": string(), "triggers": strings(), "language": string() }),
+            &["name", "file", "triggers"],
+        ),
+        "RelatedSkill": object(
+            json!({
+                "name": string(),
+                "reason": { "type": "string", "enum": ["see_also", "dependency", "shared_tags"] },
+                "shared_tags": strings(),
+            }),
+            &["name", "reason"],
+        ),
+        "SkillDetails": object(
+            json!({
+                "name": string(),
+                "description": string(),
+                "content": string(),
+                "tags": strings(),
+                "sub_skills": { "type": "array", "items": schema_ref("SubSkillInfo") },
+                "has_references": { "type": "boolean" },
+                "quick_answer": string(),
+                "language": string(),
+                "translations": strings(),
+                "archived": { "type": "boolean" },
+                "deprecated": { "type": "boolean" },
+                "superseded_by": string(),
+                "created_at": timestamp(),
+                "updated_at": timestamp(),
+                "related": { "type": "array", "items": schema_ref("RelatedSkill") },
+            }),
+            &["name", "description", "content", "tags", "sub_skills", "has_references"],
+        ),
+        "PatchSkillResponse": {
+            "allOf": [
+                schema_ref("SkillDetails"),
+                object(json!({ "content_sha256": string() }), &["content_sha256"]),
+            ],
+        },
+        "Collection": object(json!({ "name": string(), "skills": strings() }), &["name", "skills"]),
+        "TagCount": object(
+            json!({ "tag": string(), "count": { "type": "integer" }, "skills": strings() }),
+            &["tag", "count", "skills"],
+        ),
+        "SkillLinksResponse": object(
+            json!({
+                "skill": string(),
+                "see_also": strings(),
+                "related": { "type": "array", "items": schema_ref("RelatedSkill") },
+            }),
+            &["skill", "see_also", "related"],
+        ),
+        "ReferenceFile": object(
+            json!({
+                "path": string(),
+                "mime_type": string(),
+                "size": { "type": "integer" },
+                "indexed": { "type": "boolean" },
+                "truncated": { "type": "boolean" },
+                "skipped": { "type": "string", "enum": ["extension", "binary"] },
+            }),
+            &["path", "mime_type", "size", "indexed"],
+        ),
+        "ReferencesResponse": object(
+            json!({
+                "skill": string(),
+                "references": { "type": "array", "items": schema_ref("ReferenceFile") },
+            }),
+            &["skill", "references"],
+        ),
+        "ReloadStatus": object(
+            json!({
+                "job_id": string(),
+                "state": { "type": "string", "enum": ["running", "completed", "failed"] },
+                "phase": { "type": "string", "enum": ["pending", "scanning", "indexing", "done"] },
+                "started_at": timestamp(),
+                "finished_at": timestamp(),
+                "skills_total": { "type": "integer" },
+                "skills_scanned": { "type": "integer" },
+                "errors": strings(),
+                "skill_count": { "type": "integer" },
+                "error": string(),
+            }),
+            &["job_id", "state", "phase", "started_at", "skills_total", "skills_scanned", "errors"],
+        ),
+        "WatchStatus": object(
+            json!({
+                "enabled": { "type": "boolean" },
+                "mode": { "type": "string", "enum": ["events", "polling"] },
+                "debounce_ms": { "type": "integer" },
+                "last_event_at": { "type": "string", "format": "date-time", "nullable": true },
+                "pending": { "type": "integer" },
+                "batches": { "type": "integer" },
+                "last_batch_at": { "type": "string", "format": "date-time", "nullable": true },
+                "last_batch_skills": strings(),
+            }),
+            &["enabled", "debounce_ms", "last_event_at", "pending", "batches", "last_batch_at", "last_batch_skills"],
+        ),
+        "Highlight": object(
+            json!({
+                "start": { "type": "integer" },
+                "end": { "type": "integer" },
+                "char_start": { "type": "integer" },
+                "char_end": { "type": "integer" },
+            }),
+            &["start", "end", "char_start", "char_end"],
+        ),
+        "SearchResult": object(
+            json!({
+                "domain": string(),
+                "sub_skill": string(),
+                "score": { "type": "number" },
+                "match_type": {
+                    "type": "string",
+                    "enum": ["name", "description", "tags", "triggers", "headings", "content"],
+                },
+                "snippet": string(),
+                "highlights": { "type": "array", "items": schema_ref("Highlight") },
+                "line": { "type": "integer" },
+                "file": string(),
+                "quick_answer": string(),
+                "cold": { "type": "boolean" },
+                "duplicates": strings(),
+            }),
+            &["domain", "score", "match_type"],
+        ),
+        "SearchResults": object(
+            json!({
+                "results": { "type": "array", "items": schema_ref("SearchResult") },
+                "query": string(),
+                "total_matches": { "type": "integer" },
+                "truncated": { "type": "boolean" },
+                "fallback": schema_ref("Report"),
+            }),
+            &["results", "query", "total_matches", "truncated"],
+        ),
+        "FusedSearchResults": {
+            "allOf": [
+                schema_ref("SearchResults"),
+                object(json!({ "variants": strings() }), &["variants"]),
+            ],
+        },
+        "MaintenanceStatus": object(
+            json!({
+                "enabled": { "type": "boolean" },
+                "banner": object(json!({ "message": string(), "since": timestamp() }), &["message", "since"]),
+            }),
+            &["enabled"],
+        ),
+    });
+    if let (Some(schemas), Value::Object(requests)) = (schemas.as_object_mut(), request_schemas()) {
+        schemas.extend(requests);
+    }
+    schemas
+}
+
+/// Component schemas for request bodies.
+fn request_schemas() -> Value {
+    json!({
+        "CreateSkillRequest": object(
+            json!({
+                "name": string(),
+                "description": string(),
+                "content": string(),
+                "tags": strings(),
+                "sub_skills": { "type": "array", "items": schema_ref("SubSkillInfo") },
+            }),
+            &["name", "description", "content"],
+        ),
+        "UpdateSkillRequest": object(
+            json!({
+                "description": string(),
+                "content": string(),
+                "tags": strings(),
+                "base_sha256": string(),
+            }),
+            &[],
+        ),
+        "SectionOp": object(
+            json!({
+                "op": { "type": "string", "enum": ["replace", "append", "insert"] },
+                "heading": string(),
+                "content": string(),
+            }),
+            &["op", "heading", "content"],
+        ),
+        "PatchSkillRequest": object(
+            json!({
+                "diff": string(),
+                "operations": { "type": "array", "items": schema_ref("SectionOp") },
+                "base_sha256": string(),
+            }),
+            &[],
+        ),
+        "RenameSkillRequest": object(
+            json!({ "new_name": string(), "rewrite_links": { "type": "boolean", "default": true } }),
+            &["new_name"],
+        ),
+        "DuplicateSkillRequest": object(json!({ "new_name": string() }), &["new_name"]),
+        "RenameTagRequest": object(json!({ "from": string(), "to": string() }), &["from", "to"]),
+        "ImportClaudeSkillRequest": object(
+            json!({ "path": string(), "name": string(), "merge": flag(false) }),
+            &["path"],
+        ),
+        "ImportFromUrlRequest": object(json!({ "url": string(), "merge": flag(false) }), &["url"]),
+        "ResolveConflictRequest": object(
+            json!({
+                "file": string(),
+                "resolution": { "type": "string", "enum": ["local", "incoming", "content"] },
+                "content": string(),
+            }),
+            &["file", "resolution"],
+        ),
+        "UpdateLinksRequest": object(json!({ "see_also": strings() }), &["see_also"]),
+        "FusionSearchRequest": object(
+            json!({ "task": string(), "limit": { "type": "integer", "default": 10 } }),
+            &["task"],
+        ),
+        "ExplainMatchRequest": object(json!({ "task": string(), "skill": string() }), &["task", "skill"]),
+        "TestTriggersRequest": object(
+            json!({
+                "phrases": strings(),
+                "cases": {
+                    "type": "array",
+                    "items": object(json!({ "phrase": string(), "expect": strings() }), &["phrase"]),
+                },
+                "file": string(),
+                "min_score": { "type": "number", "default": 0.5 },
+            }),
+            &[],
+        ),
+        "MaintenanceRequest": object(
+            json!({ "enabled": { "type": "boolean" }, "message": string() }),
+            &["enabled"],
+        ),
+        "ColdSweepRequest": object(
+            json!({
+                "unused_days": { "type": "integer", "default": 90 },
+                "dry_run": { "type": "boolean", "default": true },
+            }),
+            &[],
+        ),
+        "RestoreBackupRequest": object(json!({ "confirm": { "type": "boolean", "default": false } }), &[]),
+        "ReplaceRequest": object(
+            json!({
+                "find": string(),
+                "replace": string(),
+                "regex": flag(false),
+                "case_insensitive": flag(false),
+                "skills": strings(),
+                "tags": strings(),
+                "files": string(),
+                "dry_run": flag(true),
+            }),
+            &["find"],
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(method, path)` of every route registered in the router.
+    fn router_routes() -> Vec<(String, String)> {
+        let source = include_str!("server.rs");
+        let router = &source[source.find("let api_routes").unwrap()..source.find(".nest(\"/api\"").unwrap()];
+        let mut routes = Vec::new();
+        for call in router.split(".route(").skip(1) {
+            let path = call.split('"').nth(1).unwrap().to_string();
+            for method in ["get", "post", "put", "patch", "delete"] {
+                if call.contains(&format!("{}(routes::", method)) {
+                    routes.push((method.to_string(), path.clone()));
+                }
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn test_operations_match_router() {
+        let mut documented: Vec<(String, String)> = OPERATIONS
+            .iter()
+            .map(|o| (o.method.to_string(), o.path.to_string()))
+            .collect();
+        let mut routes = router_routes();
+        documented.sort();
+        routes.sort();
+        assert_eq!(documented, routes);
+
+        let mut ids: Vec<String> = OPERATIONS.iter().map(operation_id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), OPERATIONS.len(), "operation IDs must be unique");
+    }
+
+    #[test]
+    fn test_spec() {
+        let spec = spec();
+        let operation = &spec["paths"]["/skills/{name}/references/{path}"]["get"];
+        assert_eq!(operation["parameters"][1]["name"], "path");
+        assert_eq!(operation["operationId"], "getSkillsByNameReferencesByPath");
+        assert_eq!(spec["paths"]["/search"]["get"]["parameters"][0]["required"], true);
+
+        // Every schema reference resolves
+        let text = spec.to_string();
+        for reference in text.split("#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(spec["components"]["schemas"].get(name).is_some(), "missing schema {}", name);
+        }
+
+        // The documented error codes are the ones ErrorCode serializes to
+        let codes = &spec["components"]["schemas"]["ErrorResponse"]["properties"]["code"]["enum"];
+        for code in codes.as_array().unwrap() {
+            serde_json::from_value::<crate::models::ErrorCode>(code.clone()).unwrap();
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Request IDs for correlating logs and responses.
+//!
+//! Every request gets an ID: the client's `X-Request-Id` when it sends a
+//! usable one, otherwise a random one. The ID is echoed in the response
+//! header, added as `request_id` to JSON error bodies, and recorded on the
+//! request's tracing span, so log lines from the indexer and search
+//! (including MCP tool calls over HTTP) can be tied to the call that caused
+//! them.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Span;
+
+/// Header carrying the request ID, in both directions.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied ID that is kept.
+const MAX_ID_LENGTH: usize = 128;
+
+/// ID of the request being handled, available as a request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// A new random ID: 32 hex digits.
+    pub fn generate() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        // Each RandomState is seeded randomly, so hashing a counter with two
+        // of them gives unpredictable, unique-enough IDs without a uuid crate
+        let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let half = || {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u64(count);
+            hasher.finish()
+        };
+        Self(format!("{:016x}{:016x}", half(), half()))
+    }
+
+    /// The client's ID, if it's short printable ASCII without spaces.
+    fn from_header(value: &HeaderValue) -> Option<Self> {
+        let id = value.to_str().ok()?.trim();
+        let usable = !id.is_empty()
+            && id.len() <= MAX_ID_LENGTH
+            && id.bytes().all(|b| b.is_ascii_graphic());
+        usable.then(|| Self(id.to_string()))
+    }
+
+    /// The ID as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Span for one HTTP request, recording its ID.
+pub(crate) fn request_span(request: &Request<Body>) -> Span {
+    let id = request
+        .extensions()
+        .get::<RequestId>()
+        .map_or("", RequestId::as_str);
+    tracing::info_span!(
+        "request",
+        request_id = %id,
+        method = %request.method(),
+        uri = %request.uri(),
+    )
+}
+
+/// Assign the request's ID, echo it in the response, and add it to JSON
+/// error bodies.
+pub(crate) async fn assign_request_id(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(RequestId::from_header)
+        .unwrap_or_else(RequestId::generate);
+    request.extensions_mut().insert(id.clone());
+
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(id.as_str()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+    if response.status().is_success() || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(serde_json::Value::Object(mut error)) = serde_json::from_slice(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    error.insert("request_id".to_string(), id.0.into());
+    let body = serde_json::to_vec(&error).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id() {
+        let a = RequestId::generate();
+        let b = RequestId::generate();
+        assert_eq!(a.as_str().len(), 32);
+        assert_ne!(a, b);
+
+        let header = |value: &str| RequestId::from_header(&HeaderValue::from_str(value).unwrap());
+        assert_eq!(header(" abc-123 "), Some(RequestId("abc-123".to_string())));
+        assert_eq!(header("two words"), None);
+        assert_eq!(header(""), None);
+        assert_eq!(header(&"x".repeat(MAX_ID_LENGTH + 1)), None);
+    }
+}
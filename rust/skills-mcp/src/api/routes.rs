@@ -2,20 +2,69 @@
 //!
 //! These handlers correspond to the Flask routes in skills_manager_api.py.
 
+use std::collections::HashSet;
 use std::path::Path as StdPath;
 use std::sync::Arc;
 
+use std::convert::Infallible;
+
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
+    body::Body,
+    extract::{Path, Request, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Json,
 };
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::fs as async_fs;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+
+use crate::abuse::AccessVerdict;
+use crate::analysis::{self, QualityReport};
+use crate::analytics::AnalyticsReport;
+use crate::audit::{self, AuditAction, AuditEntry, AuditQuery};
+use crate::backup::{Backup, BackupError, Backups, RestorePlan};
+use crate::bundles::{Bundle, BundleContent, BundleError, Bundles};
+use crate::cold::{ColdCandidate, ColdError, ColdSkill, ColdStorage};
+use crate::deprecation::{self, DeprecationReport};
+use crate::diff;
+use crate::events::{SkillEvent, SkillEventKind};
+use crate::github::{self, GitHubSource, UrlImportReport};
+use crate::import::{self, ImportError, ImportMode, ImportReport};
+use crate::integrity::IntegrityReport;
+use crate::lifecycle::{self, DuplicateReport, LifecycleError, RenameReport};
+use crate::lint::{self, LintReport};
+use crate::maintenance::MaintenanceStatus;
+use crate::merge::{self, Imports, MergeError, Resolution, SkillConflicts};
+use crate::patch::{self, PatchError, SectionOp};
+use crate::replace::{self, ReplaceError};
+use crate::scripts::{self, SkillScript};
+use crate::search::SearchCacheStats;
+use crate::report::Digest;
+use crate::markdown;
+use crate::index::{
+    file_type, reserved_segment, resolve_skill_link, ContentCacheStats, DependencyGraph, IndexError, LinkGraph,
+    ReferenceFile, ReloadStatus, SkillDependencies, SkillLink, WatchStatus, MARKDOWN,
+};
+use crate::mcp::tools::{
+    skill_examples, skill_outline, skill_stats, skill_tables, ServiceContext, SkillExamples, SkillOutline,
+    SkillStats, SkillTables,
+};
+use crate::models::{
+    Collection, ErrorCode, ErrorResponse, FieldError, RelatedSkill, SessionTokens, SkillMeta, SkillReaders, StorageReport,
+    SubSkillMeta, TagCount, TokenSummary, ValidationResult,
+};
+use crate::validation::{meta_schema, validate_meta, validate_skills};
 
-use crate::mcp::tools::ServiceContext;
-use crate::models::{ErrorResponse, SkillMeta};
+use super::conditional::{self, Validators};
+use super::{openapi, Caller};
 
 // ============================================================================
 // Path Traversal Protection
@@ -37,17 +86,25 @@ const MAX_TAGS_COUNT: usize = 20;
 const MAX_TAG_LENGTH: usize = 50;
 
 /// Characters that are not allowed in skill names
-const FORBIDDEN_CHARS: &[char] = &['/', '\\', '\0', ':', '*', '?', '"', '<', '>', '|'];
+const FORBIDDEN_CHARS: &[char] = &['\\', '\0', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Not-found response for a skill, suggesting close names.
+fn skill_not_found(state: &AppState, name: &str) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::NOT_FOUND, Json(state.skill_not_found(name)))
+}
 
 /// Validates that a skill name is safe and doesn't contain path traversal sequences.
 ///
+/// Names may have one collection prefix (`frontend/forms`); in URLs the
+/// separator is percent-encoded (`frontend%2Fforms`).
+///
 /// Returns `Ok(())` if the name is valid, or an error response if not.
-fn validate_skill_name(name: &str) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+fn validate_skill_name(state: &AppState, name: &str) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
     // Check for empty name
     if name.is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new("Skill name cannot be empty".to_string())),
+            Json(ErrorResponse::new(ErrorCode::InvalidName, "Skill name cannot be empty".to_string())),
         ));
     }
 
@@ -55,7 +112,7 @@ fn validate_skill_name(name: &str) -> Result<(), (StatusCode, Json<ErrorResponse
     if name.len() > MAX_SKILL_NAME_LENGTH {
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new(format!(
+            Json(ErrorResponse::new(ErrorCode::InvalidName, format!(
                 "Skill name too long (max {} characters)",
                 MAX_SKILL_NAME_LENGTH
             ))),
@@ -67,6 +124,7 @@ fn validate_skill_name(name: &str) -> Result<(), (StatusCode, Json<ErrorResponse
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse::new(
+                ErrorCode::InvalidName,
                 "Skill name cannot contain '..'".to_string(),
             )),
         ));
@@ -77,21 +135,48 @@ fn validate_skill_name(name: &str) -> Result<(), (StatusCode, Json<ErrorResponse
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse::new(
+                ErrorCode::InvalidName,
                 "Skill name contains invalid characters".to_string(),
             )),
         ));
     }
 
-    // Check name doesn't start with a dot (hidden files)
-    if name.starts_with('.') {
+    // Allow at most one collection segment, with no empty segments
+    let segments: Vec<&str> = name.split('/').collect();
+    if segments.len() > 2 || segments.iter().any(|s| s.is_empty()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                ErrorCode::InvalidName,
+                "Skill name must be 'name' or 'collection/name'".to_string(),
+            )),
+        ));
+    }
+
+    // Check no segment starts with a dot (hidden files)
+    if segments.iter().any(|s| s.starts_with('.')) {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse::new(
+                ErrorCode::InvalidName,
                 "Skill name cannot start with '.'".to_string(),
             )),
         ));
     }
 
+    // Check no segment is a name Windows can't create, unless the
+    // `[index.names]` policy allows them
+    let reserved = reserved_segment(name).filter(|_| state.indexer.index_config().names.reject_reserved);
+    if let Some(segment) = reserved {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                ErrorCode::InvalidName,
+                format!("Skill name cannot use '{}', a reserved name on Windows", segment),
+            )),
+        ));
+    }
+
     Ok(())
 }
 
@@ -112,13 +197,13 @@ fn validate_skill_path(
     };
 
     // For skill_path, it may not exist yet (for create operations)
-    // So we canonicalize the parent (skills_dir) and check the name component
-    let skill_name = match skill_path.file_name() {
-        Some(name) => name,
-        None => {
+    // So we canonicalize the parent (skills_dir) and check the name components
+    let skill_name = match skill_path.strip_prefix(skills_dir) {
+        Ok(name) if name.file_name().is_some() => name,
+        _ => {
             return Err((
                 StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new("Invalid skill path".to_string())),
+                Json(ErrorResponse::new(ErrorCode::InvalidPath, "Invalid skill path".to_string())),
             ));
         }
     };
@@ -133,7 +218,7 @@ fn validate_skill_path(
             Err(e) => {
                 return Err((
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ErrorResponse::new(format!(
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!(
                         "Failed to resolve skill path: {}",
                         e
                     ))),
@@ -146,6 +231,7 @@ fn validate_skill_path(
             return Err((
                 StatusCode::BAD_REQUEST,
                 Json(ErrorResponse::new(
+                    ErrorCode::InvalidPath,
                     "Skill path is outside skills directory".to_string(),
                 )),
             ));
@@ -156,6 +242,7 @@ fn validate_skill_path(
             return Err((
                 StatusCode::BAD_REQUEST,
                 Json(ErrorResponse::new(
+                    ErrorCode::InvalidPath,
                     "Invalid skill path construction".to_string(),
                 )),
             ));
@@ -168,6 +255,75 @@ fn validate_skill_path(
 /// Application state shared across routes.
 pub type AppState = Arc<ServiceContext>;
 
+/// Reject writes during maintenance.
+fn ensure_not_maintenance(state: &AppState) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    match state.maintenance.banner() {
+        Some(banner) => Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(ErrorCode::Maintenance, banner.message)),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Reject writes during maintenance and to skills served from a read-only
+/// skills root.
+fn ensure_writable(state: &AppState, name: &str) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    ensure_not_maintenance(state)?;
+    if state.indexer.is_read_only(name) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(ErrorCode::ReadOnly, format!(
+                "Skill '{}' is in a read-only skills root",
+                name
+            ))),
+        ));
+    }
+    Ok(())
+}
+
+/// Report a private skill the caller's key isn't allowed as not found, so
+/// its existence doesn't leak. Unknown names pass through.
+fn ensure_visible(
+    state: &AppState,
+    caller: &Caller,
+    name: &str,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if can_see(state, caller, name) {
+        Ok(())
+    } else {
+        Err(skill_not_found(state, name))
+    }
+}
+
+/// Whether the caller may see a skill. Unknown names are visible.
+fn can_see(state: &AppState, caller: &Caller, name: &str) -> bool {
+    state
+        .indexer
+        .get_skill_meta(name)
+        .is_none_or(|meta| meta.is_visible_to(caller.key()))
+}
+
+/// The actor of an audit entry as shown to the caller: other callers' key
+/// fingerprints are short enough to brute-force, so they're masked.
+fn shown_actor(caller: &Caller, actor: String) -> String {
+    if actor.starts_with("key:") && actor != caller.id {
+        "key".to_string()
+    } else {
+        actor
+    }
+}
+
+/// Related skills of `name` that the caller can see.
+fn visible_related(state: &AppState, caller: &Caller, name: &str) -> Vec<RelatedSkill> {
+    let index = state.indexer.get_skill_index();
+    index
+        .related(name, RELATED_LIMIT)
+        .into_iter()
+        .filter(|r| index.find(&r.name).is_some_and(|s| s.is_visible_to(caller.key())))
+        .collect()
+}
+
 // ============================================================================
 // GET /api/skills - List all skills
 // ============================================================================
@@ -175,18 +331,43 @@ pub type AppState = Arc<ServiceContext>;
 #[derive(Debug, Serialize)]
 pub struct SkillListItem {
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
     pub description: String,
     pub tags: Vec<String>,
     pub sub_skills: Vec<String>,
     pub file_count: usize,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    #[serde(skip_serializing_if = "Option::is_none", with = "crate::models::timestamp::option")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "crate::models::timestamp::option")]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-pub async fn list_skills(State(state): State<AppState>) -> impl IntoResponse {
+#[derive(Debug, Default, Deserialize)]
+pub struct ListSkillsQuery {
+    #[serde(default)]
+    pub collection: Option<String>,
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+pub async fn list_skills(
+    State(state): State<AppState>,
+    caller: Caller,
+    axum::extract::Query(query): axum::extract::Query<ListSkillsQuery>,
+) -> impl IntoResponse {
     let index = state.indexer.get_skill_index();
 
     let skills: Vec<SkillListItem> = index
         .skills
         .iter()
+        .filter(|s| query.collection.is_none() || s.collection() == query.collection.as_deref())
+        .filter(|s| query.include_archived || !s.archived)
+        .filter(|s| s.is_visible_to(caller.key()))
         .map(|s| {
             let file_count = if s.has_sub_skills() {
                 s.sub_skills.as_ref().map(|ss| ss.len()).unwrap_or(0) + 1
@@ -196,10 +377,15 @@ pub async fn list_skills(State(state): State<AppState>) -> impl IntoResponse {
 
             SkillListItem {
                 name: s.name.clone(),
+                collection: s.collection().map(str::to_string),
                 description: s.description.clone(),
                 tags: s.tags.clone(),
                 sub_skills: s.sub_skill_names().iter().map(|n| n.to_string()).collect(),
                 file_count,
+                archived: s.archived,
+                deprecated: s.is_deprecated(),
+                created_at: s.created_at,
+                updated_at: s.updated_at,
             }
         })
         .collect();
@@ -207,6 +393,179 @@ pub async fn list_skills(State(state): State<AppState>) -> impl IntoResponse {
     Json(skills)
 }
 
+// ============================================================================
+// GET /api/collections - List skill namespaces
+// ============================================================================
+
+pub async fn list_collections(State(state): State<AppState>, caller: Caller) -> Json<Vec<Collection>> {
+    let index = state.indexer.get_skill_index();
+    let mut collections = index.collections();
+    for collection in &mut collections {
+        collection
+            .skills
+            .retain(|name| index.find(name).is_some_and(|s| s.is_visible_to(caller.key())));
+    }
+    collections.retain(|c| !c.skills.is_empty());
+    Json(collections)
+}
+
+// ============================================================================
+// GET /api/tags - Tags with usage counts
+// ============================================================================
+
+pub async fn list_tags(State(state): State<AppState>, caller: Caller) -> Json<Vec<TagCount>> {
+    let index = state.indexer.get_skill_index();
+    let mut tags = index.tags();
+    for tag in &mut tags {
+        tag.skills
+            .retain(|name| index.find(name).is_some_and(|s| s.is_visible_to(caller.key())));
+        tag.count = tag.skills.len();
+    }
+    tags.retain(|t| t.count > 0);
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    Json(tags)
+}
+
+// ============================================================================
+// POST /api/tags/rename - Rename a tag across skills
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct RenameTagRequest {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RenameTagResponse {
+    pub from: String,
+    pub to: String,
+    /// Skills whose `_meta.json` was rewritten.
+    pub skills: Vec<String>,
+}
+
+pub async fn rename_tag(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(req): Json<RenameTagRequest>,
+) -> Result<Json<RenameTagResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let from = req.from.trim();
+    let to = req.to.trim().to_string();
+    if from.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(ErrorCode::InvalidRequest, "Tag to rename is empty".to_string())),
+        ));
+    }
+    check_fields(None, None, Some(std::slice::from_ref(&to)))?;
+    ensure_not_maintenance(&state)?;
+
+    // Skills are found by their indexed tags, which are already normalized
+    let rules = state.indexer.index_config().tags;
+    let target = rules.normalize(from);
+    let skills = run_blocking(&state, {
+        let (from, to) = (from.to_string(), to.clone());
+        move |state| {
+            let _guard = state.edits.lock();
+            let mut skills = Vec::new();
+            for meta in &state.indexer.get_skill_index().skills {
+                if !meta.tags.iter().any(|t| *t == from || *t == target)
+                    || !meta.is_visible_to(caller.key())
+                    || state.indexer.is_read_only(&meta.name)
+                {
+                    continue;
+                }
+                let entry = AuditEntry::new(&caller.id, AuditAction::Update, &meta.name)
+                    .with_summary(format!("tag '{}' renamed to '{}'", from, to));
+                state.begin_mutation(&entry);
+                match lifecycle::rename_tag(&state.indexer.skill_dir(&meta.name), &from, &to, &rules) {
+                    Ok(true) => {
+                        state.record_mutation(entry);
+                        skills.push(meta.name.clone());
+                    }
+                    // Tags from SKILL.md frontmatter alone have no file to rewrite
+                    Ok(false) | Err(LifecycleError::NotFound(_)) => {}
+                    Err(e) => return Err(lifecycle_error(e)),
+                }
+            }
+            Ok(skills)
+        }
+    })
+    .await?;
+
+    for name in &skills {
+        let _ = state.indexer.update_skill_async(name).await;
+    }
+
+    Ok(Json(RenameTagResponse {
+        from: from.to_string(),
+        to,
+        skills,
+    }))
+}
+
+// ============================================================================
+// GET /api/bundles - List skill bundles
+// ============================================================================
+
+fn bundle_error(e: BundleError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        BundleError::NotFound(_) => StatusCode::NOT_FOUND,
+        BundleError::Parse(_) | BundleError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ErrorResponse::new(e.code(), e.to_string())))
+}
+
+pub async fn list_bundles(
+    State(state): State<AppState>,
+    caller: Caller,
+) -> Result<Json<Vec<Bundle>>, (StatusCode, Json<ErrorResponse>)> {
+    let index = state.indexer.get_skill_index();
+    let mut bundles =
+        run_blocking(&state, |state| Bundles::new(state.indexer.skills_dir()).list().map_err(bundle_error)).await?;
+    // Items naming skills hidden from the caller aren't listed
+    for bundle in &mut bundles {
+        bundle.items.retain(|item| {
+            let skill = item.split_once(':').map_or(item.as_str(), |(skill, _)| skill);
+            index.find(skill).is_none_or(|s| s.is_visible_to(caller.key()))
+        });
+    }
+    Ok(Json(bundles))
+}
+
+// ============================================================================
+// GET /api/bundles/:name - Load a bundle's content
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct BundleQuery {
+    /// Token budget overriding the bundle's own.
+    pub max_tokens: Option<u64>,
+}
+
+pub async fn get_bundle(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<BundleQuery>,
+) -> Result<Json<BundleContent>, (StatusCode, Json<ErrorResponse>)> {
+    // Bundles are defined in a file and load their skills' content
+    let content = run_blocking(&state, {
+        let key = caller.key().map(str::to_string);
+        move |state| {
+            let bundle = Bundles::new(state.indexer.skills_dir()).get(&name).map_err(bundle_error)?;
+            let index = state.indexer.get_skill_index();
+            Ok(bundle.load(&state.indexer, query.max_tokens, |skill| {
+                index.find(skill).is_some_and(|s| s.is_visible_to(key.as_deref()))
+            }))
+        }
+    })
+    .await?;
+
+    state.track_bundle_served(&content, &caller.id);
+    Ok(Json(content))
+}
+
 // ============================================================================
 // GET /api/skills/:name - Get skill details
 // ============================================================================
@@ -219,39 +578,110 @@ pub struct SkillDetails {
     pub tags: Vec<String>,
     pub sub_skills: Vec<SubSkillInfo>,
     pub has_references: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quick_answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub translations: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "crate::models::timestamp::option")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none", with = "crate::models::timestamp::option")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedSkill>,
 }
 
+/// Computed related-skill suggestions included alongside curated links.
+pub(super) const RELATED_LIMIT: usize = 5;
+
 #[derive(Debug, Serialize)]
 pub struct SubSkillInfo {
     pub name: String,
     pub file: String,
     pub triggers: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetSkillQuery {
+    /// Strip badges, HTML comments, navigation lines, and extra blank lines.
+    #[serde(default)]
+    pub minify: bool,
+    /// Serve the translation in this language, if the skill has one.
+    #[serde(default)]
+    pub lang: Option<String>,
+}
+
+/// Look up a skill for reading its content: the name must be valid, the
+/// skill visible to the caller, and the caller not throttled.
+async fn readable_skill(
+    state: &AppState,
+    caller: &Caller,
+    name: &str,
+) -> Result<SkillMeta, (StatusCode, Json<ErrorResponse>)> {
+    // Validate skill name to prevent path traversal
+    validate_skill_name(state, name)?;
+    ensure_visible(state, caller, name)?;
+
+    if let AccessVerdict::Throttle(retry_after) = state.abuse.check(&caller.abuse_id(), name) {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse::new(ErrorCode::Throttled, format!(
+                "Unusual access pattern detected; retry in {}s",
+                retry_after.as_secs().max(1)
+            ))),
+        ));
+    }
+
+    if let Some(meta) = state.indexer.get_skill_meta(name) {
+        return Ok(meta);
+    }
+    let cold = run_blocking(state, {
+        let name = name.to_string();
+        move |state| Ok(state.indexer.roots().iter().any(|root| ColdStorage::new(&root.path).contains(&name)))
+    })
+    .await?;
+    if !cold {
+        return Err(skill_not_found(state, name));
+    }
+    Err((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new(ErrorCode::NotFound, format!(
+            "Skill '{}' is in cold storage; restore it via POST /api/cold/{}/restore",
+            name, name
+        ))),
+    ))
 }
 
+/// Skill details, answering 304 when the caller's copy is current (see
+/// [`conditional`](super::conditional)).
 pub async fn get_skill(
     State(state): State<AppState>,
+    caller: Caller,
     Path(name): Path<String>,
-) -> Result<Json<SkillDetails>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate skill name to prevent path traversal
-    validate_skill_name(&name)?;
-
-    let meta = state
-        .indexer
-        .get_skill_meta(&name)
-        .ok_or_else(|| {
-            (
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse::new(format!("Skill '{}' not found", name))),
-            )
-        })?;
+    axum::extract::Query(query): axum::extract::Query<GetSkillQuery>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let meta = readable_skill(&state, &caller, &name).await?;
+    // Aliases are served under the skill's real name
+    let name = meta.name.clone();
 
     let content = state
         .indexer
-        .read_skill_content(&name)
+        .read_skill_content_in_async(&name, query.lang.as_deref())
+        .await
         .map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(e.to_string())),
+                Json(ErrorResponse::new(e.code(), e.to_string())),
             )
         })?;
 
@@ -264,19 +694,172 @@ pub async fn get_skill(
                     name: s.name.clone(),
                     file: s.file.clone(),
                     triggers: s.triggers.clone(),
+                    language: s.language.clone(),
                 })
                 .collect()
         })
         .unwrap_or_default();
 
-    Ok(Json(SkillDetails {
+    let mut content_text = if query.minify {
+        markdown::minify(&content.content)
+    } else {
+        content.content
+    };
+    meta.append_deprecation_notice(&mut content_text);
+
+    let details = SkillDetails {
+        deprecated: meta.is_deprecated(),
         name: meta.name,
         description: meta.description,
-        content: content.content,
+        content: content_text,
         tags: meta.tags,
         sub_skills,
         has_references: content.has_references,
-    }))
+        quick_answer: meta.quick_answer,
+        language: content.language,
+        translations: meta.translations,
+        archived: meta.archived,
+        superseded_by: meta.superseded_by,
+        created_at: meta.created_at,
+        updated_at: meta.updated_at,
+        related: visible_related(&state, &caller, &name),
+    };
+    let body = serde_json::to_vec(&details).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(ErrorCode::InternalError, e.to_string())),
+        )
+    })?;
+    let response = conditional::respond(&headers, "application/json", body, details.updated_at);
+
+    // A 304 serves nothing, so it isn't counted as a read
+    if response.status() == StatusCode::OK {
+        state.track_skill_read(&name, &caller.id);
+        state.track_tokens_served(&name, &caller.id, &details.content);
+    }
+    Ok(response)
+}
+
+// ============================================================================
+// GET /api/skills/:name/html - Skill content rendered to HTML
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SkillHtmlQuery {
+    /// Render this sub-skill instead of SKILL.md.
+    #[serde(default)]
+    pub sub_skill: Option<String>,
+}
+
+/// Map an index error to an HTTP error response.
+fn index_error(e: IndexError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        IndexError::NotFound(_) => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ErrorResponse::new(e.code(), e.to_string())))
+}
+
+/// Run a handler's file work through [`SkillIndexer::run_blocking`], so
+/// `std::fs` calls and the edit lock never hold up the runtime.
+async fn run_blocking<T, F>(state: &AppState, f: F) -> Result<T, (StatusCode, Json<ErrorResponse>)>
+where
+    F: FnOnce(&AppState) -> Result<T, (StatusCode, Json<ErrorResponse>)> + Send + 'static,
+    T: Send + 'static,
+{
+    let ctx = Arc::clone(state);
+    state.indexer.run_blocking(move |_| f(&ctx)).await.map_err(index_error)?
+}
+
+/// SKILL.md or a sub-skill as an HTML fragment, answering 304 when the
+/// caller's copy is current.
+pub async fn get_skill_html(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<SkillHtmlQuery>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let meta = readable_skill(&state, &caller, &name).await?;
+    let name = meta.name.clone();
+
+    let (file, mut content) = match &query.sub_skill {
+        Some(sub_skill) => {
+            let content = state
+                .indexer
+                .read_sub_skill_content_async(&name, sub_skill)
+                .await
+                .map_err(index_error)?;
+            let file = meta.find_sub_skill(sub_skill).map(|s| s.file.clone()).unwrap_or_default();
+            (file, content.content)
+        }
+        None => {
+            let content = state.indexer.read_skill_content_async(&name).await.map_err(index_error)?;
+            ("SKILL.md".to_string(), content.content)
+        }
+    };
+    meta.append_deprecation_notice(&mut content);
+
+    let links = html_links(&state, &caller, &name, &file);
+    let html = markdown::render_html(markdown::strip_frontmatter(&content), links);
+    let modified = modified_time(&state.indexer.skill_dir(&name).join(&file)).await;
+    let response = conditional::respond(&headers, "text/html; charset=utf-8", html.into_bytes(), modified);
+
+    if response.status() == StatusCode::OK {
+        state.track_skill_read(&name, &caller.id);
+        state.track_tokens_served(&name, &caller.id, &content);
+    }
+    Ok(response)
+}
+
+/// Modification time of a file, if it can be read.
+async fn modified_time(path: &std::path::Path) -> Option<DateTime<Utc>> {
+    let metadata = async_fs::metadata(path).await.ok()?;
+    metadata.modified().ok().map(DateTime::<Utc>::from)
+}
+
+/// Link rewriter for HTML rendered from `file` of skill `name`.
+///
+/// Relative links to SKILL.md files, sub-skills, and reference files of
+/// skills the caller can see point at their API URLs, rendered as HTML
+/// where they're markdown. Other links are left as written.
+fn html_links(state: &AppState, caller: &Caller, name: &str, file: &str) -> impl Fn(&str) -> Option<String> {
+    let index = state.indexer.get_skill_index();
+    let key = caller.key().map(str::to_string);
+    let (name, file) = (name.to_string(), file.to_string());
+
+    move |target| {
+        let (path, fragment) = target.split_at(target.find('#').unwrap_or(target.len()));
+        let (skill, target_file) = resolve_skill_link(&index, &name, &file, path)?;
+        let meta = index.find(&skill).filter(|m| m.is_visible_to(key.as_deref()))?;
+        // Collection separators are part of the name segment
+        let base = format!("/api/skills/{}", encode_path(&meta.name).replace('/', "%2F"));
+
+        let url = if target_file.is_empty() || target_file == "SKILL.md" {
+            format!("{}/html", base)
+        } else if let Some(sub) = meta.sub_skills.iter().flatten().find(|s| s.file == target_file) {
+            format!("{}/html?sub_skill={}", base, encode_path(&sub.name))
+        } else if let Some(reference) = target_file.strip_prefix("references/") {
+            let format = if file_type(reference) == MARKDOWN { "?format=html" } else { "" };
+            format!("{}/references/{}{}", base, encode_path(reference), format)
+        } else {
+            return None;
+        };
+        Some(format!("{}{}", url, fragment))
+    }
+}
+
+/// Percent-encode a path for a URL, keeping `/` separators.
+fn encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
 }
 
 // ============================================================================
@@ -290,141 +873,165 @@ pub struct CreateSkillRequest {
     pub content: String,
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub sub_skills: Vec<SubSkillMeta>,
 }
 
 impl CreateSkillRequest {
     /// Validate the request fields.
     fn validate(&self) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
-        // Validate description length
-        if self.description.len() > MAX_DESCRIPTION_LENGTH {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(format!(
-                    "Description too long (max {} characters)",
-                    MAX_DESCRIPTION_LENGTH
-                ))),
-            ));
-        }
-
-        // Validate content length
-        if self.content.len() > MAX_CONTENT_LENGTH {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(format!(
-                    "Content too long (max {} bytes)",
-                    MAX_CONTENT_LENGTH
-                ))),
-            ));
-        }
+        check_fields(Some(&self.description), Some(&self.content), Some(&self.tags))
+    }
+}
 
-        // Validate tags count
-        if self.tags.len() > MAX_TAGS_COUNT {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(format!(
-                    "Too many tags (max {})",
-                    MAX_TAGS_COUNT
-                ))),
-            ));
+/// Check the editable fields of a skill, reporting every invalid one.
+fn check_fields(
+    description: Option<&str>,
+    content: Option<&str>,
+    tags: Option<&[String]>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let mut details = Vec::new();
+    if description.is_some_and(|d| d.len() > MAX_DESCRIPTION_LENGTH) {
+        details.push(FieldError::new(
+            "description",
+            format!("Description too long (max {} characters)", MAX_DESCRIPTION_LENGTH),
+        ));
+    }
+    if content.is_some_and(|c| c.len() > MAX_CONTENT_LENGTH) {
+        details.push(FieldError::new(
+            "content",
+            format!("Content too long (max {} bytes)", MAX_CONTENT_LENGTH),
+        ));
+    }
+    if let Some(tags) = tags {
+        if tags.len() > MAX_TAGS_COUNT {
+            details.push(FieldError::new("tags", format!("Too many tags (max {})", MAX_TAGS_COUNT)));
         }
-
-        // Validate individual tag lengths
-        for tag in &self.tags {
+        for (i, tag) in tags.iter().enumerate() {
+            let field = format!("tags[{}]", i);
             if tag.len() > MAX_TAG_LENGTH {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(ErrorResponse::new(format!(
-                        "Tag '{}' too long (max {} characters)",
-                        tag, MAX_TAG_LENGTH
-                    ))),
-                ));
-            }
-            if tag.is_empty() {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(ErrorResponse::new("Tags cannot be empty".to_string())),
+                details.push(FieldError::new(
+                    field,
+                    format!("Tag '{}' too long (max {} characters)", tag, MAX_TAG_LENGTH),
                 ));
+            } else if tag.is_empty() {
+                details.push(FieldError::new(field, "Tags cannot be empty"));
             }
         }
+    }
 
+    if details.is_empty() {
         Ok(())
+    } else {
+        Err((StatusCode::BAD_REQUEST, Json(ErrorResponse::validation(details))))
     }
 }
 
+/// Field errors from `validate_meta` messages, which read `field: problem`.
+fn meta_field_errors(errors: Vec<String>) -> Vec<FieldError> {
+    errors
+        .into_iter()
+        .map(|e| {
+            let field = e.split_once(": ").map_or("", |(field, _)| field);
+            FieldError::new(field, &e)
+        })
+        .collect()
+}
+
 pub async fn create_skill(
     State(state): State<AppState>,
+    caller: Caller,
     Json(req): Json<CreateSkillRequest>,
 ) -> Result<(StatusCode, Json<SkillDetails>), (StatusCode, Json<ErrorResponse>)> {
     // Validate skill name to prevent path traversal
-    validate_skill_name(&req.name)?;
+    validate_skill_name(&state, &req.name)?;
 
     // Validate request fields
     req.validate()?;
 
+    ensure_writable(&state, &req.name)?;
+
     // Check if skill already exists
     if state.indexer.skill_exists(&req.name) {
         return Err((
             StatusCode::CONFLICT,
-            Json(ErrorResponse::new(format!(
+            Json(ErrorResponse::new(ErrorCode::AlreadyExists, format!(
                 "Skill '{}' already exists",
                 req.name
             ))),
         ));
     }
 
-    // Create skill directory and files
-    let skills_dir = state.indexer.skills_dir();
-    let skill_dir = skills_dir.join(&req.name);
-
-    // Validate the constructed path is within skills directory
-    validate_skill_path(&skill_dir, skills_dir)?;
-
-    async_fs::create_dir_all(&skill_dir).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to create directory: {}", e))),
-        )
-    })?;
-
     // Create _meta.json
     let meta = SkillMeta {
         name: req.name.clone(),
         description: req.description.clone(),
         tags: req.tags.clone(),
-        sub_skills: None,
+        sub_skills: (!req.sub_skills.is_empty()).then(|| req.sub_skills.clone()),
         source: None,
+        ..Default::default()
     };
 
     let meta_json = serde_json::to_string_pretty(&meta).map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to serialize meta: {}", e))),
+            Json(ErrorResponse::new(ErrorCode::InternalError, format!("Failed to serialize meta: {}", e))),
         )
     })?;
 
-    async_fs::write(skill_dir.join("_meta.json"), meta_json).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to write _meta.json: {}", e))),
-        )
-    })?;
+    run_blocking(&state, {
+        let (name, content) = (req.name.clone(), req.content.clone());
+        move |state| {
+            // Create skill directory and files
+            let skills_dir = state.indexer.skills_dir();
+            let skill_dir = skills_dir.join(&name);
 
-    // Create SKILL.md
-    async_fs::write(skill_dir.join("SKILL.md"), &req.content).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to write SKILL.md: {}", e))),
-        )
-    })?;
+            // Validate the constructed path is within skills directory
+            validate_skill_path(&skill_dir, skills_dir)?;
+
+            // Hold the lock through the journal entry, so it records these writes alone
+            let _guard = state.edits.lock();
+            let entry = AuditEntry::new(&caller.id, AuditAction::Create, &name);
+            state.begin_mutation(&entry);
+
+            std::fs::create_dir_all(&skill_dir).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to create directory: {}", e))),
+                )
+            })?;
+
+            std::fs::write(skill_dir.join("_meta.json"), meta_json).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to write _meta.json: {}", e))),
+                )
+            })?;
+
+            // Create SKILL.md
+            std::fs::write(skill_dir.join("SKILL.md"), &content).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to write SKILL.md: {}", e))),
+                )
+            })?;
+            state.record_mutation(entry.with_summary(format!("SKILL.md {} lines", content.lines().count())));
+            Ok(())
+        }
+    })
+    .await?;
 
     // Reload index
-    state.indexer.reload().map_err(|e| {
+    state.indexer.reload_async().await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to reload index: {}", e))),
+            Json(ErrorResponse::new(e.code(), format!("Failed to reload index: {}", e))),
         )
     })?;
 
+    let indexed = state.indexer.get_skill_meta(&req.name);
+    let related = state.indexer.get_skill_index().related(&req.name, RELATED_LIMIT);
+
     Ok((
         StatusCode::CREATED,
         Json(SkillDetails {
@@ -432,8 +1039,26 @@ pub async fn create_skill(
             description: req.description,
             content: req.content,
             tags: req.tags,
-            sub_skills: vec![],
+            sub_skills: req
+                .sub_skills
+                .into_iter()
+                .map(|s| SubSkillInfo {
+                    name: s.name,
+                    file: s.file,
+                    triggers: s.triggers,
+                    language: s.language,
+                })
+                .collect(),
             has_references: false,
+            quick_answer: indexed.as_ref().and_then(|m| m.quick_answer.clone()),
+            language: indexed.as_ref().and_then(|m| m.language.clone()),
+            translations: indexed.as_ref().map(|m| m.translations.clone()).unwrap_or_default(),
+            archived: false,
+            deprecated: false,
+            superseded_by: None,
+            created_at: indexed.as_ref().and_then(|m| m.created_at),
+            updated_at: indexed.and_then(|m| m.updated_at),
+            related,
         }),
     ))
 }
@@ -450,166 +1075,278 @@ pub struct UpdateSkillRequest {
     pub content: Option<String>,
     #[serde(default)]
     pub tags: Option<Vec<String>>,
+    /// SHA-256 of the SKILL.md the update was made against.
+    #[serde(default)]
+    pub base_sha256: Option<String>,
 }
 
 impl UpdateSkillRequest {
-    /// Validate the request fields.
+    /// Validate the request fields that are provided.
     fn validate(&self) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
-        // Validate description length if provided
-        if let Some(ref desc) = self.description {
-            if desc.len() > MAX_DESCRIPTION_LENGTH {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(ErrorResponse::new(format!(
-                        "Description too long (max {} characters)",
-                        MAX_DESCRIPTION_LENGTH
-                    ))),
-                ));
-            }
-        }
-
-        // Validate content length if provided
-        if let Some(ref content) = self.content {
-            if content.len() > MAX_CONTENT_LENGTH {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(ErrorResponse::new(format!(
-                        "Content too long (max {} bytes)",
-                        MAX_CONTENT_LENGTH
-                    ))),
-                ));
-            }
-        }
-
-        // Validate tags if provided
-        if let Some(ref tags) = self.tags {
-            if tags.len() > MAX_TAGS_COUNT {
-                return Err((
-                    StatusCode::BAD_REQUEST,
-                    Json(ErrorResponse::new(format!(
-                        "Too many tags (max {})",
-                        MAX_TAGS_COUNT
-                    ))),
-                ));
-            }
-
-            for tag in tags {
-                if tag.len() > MAX_TAG_LENGTH {
-                    return Err((
-                        StatusCode::BAD_REQUEST,
-                        Json(ErrorResponse::new(format!(
-                            "Tag '{}' too long (max {} characters)",
-                            tag, MAX_TAG_LENGTH
-                        ))),
-                    ));
-                }
-                if tag.is_empty() {
-                    return Err((
-                        StatusCode::BAD_REQUEST,
-                        Json(ErrorResponse::new("Tags cannot be empty".to_string())),
-                    ));
-                }
-            }
-        }
-
-        Ok(())
+        check_fields(self.description.as_deref(), self.content.as_deref(), self.tags.as_deref())
     }
 }
 
 pub async fn update_skill(
     State(state): State<AppState>,
+    caller: Caller,
     Path(name): Path<String>,
     Json(req): Json<UpdateSkillRequest>,
 ) -> Result<Json<SkillDetails>, (StatusCode, Json<ErrorResponse>)> {
     // Validate skill name to prevent path traversal
-    validate_skill_name(&name)?;
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+    let name = state.canonical_name(&name);
 
     // Validate request fields
     req.validate()?;
+    ensure_writable(&state, &name)?;
 
-    let skills_dir = state.indexer.skills_dir();
-    let skill_dir = skills_dir.join(&name);
+    let skill_dir = state.indexer.skill_dir(&name);
+    let skills_dir = state.indexer.skill_root_dir(&name);
 
-    // Validate the constructed path is within skills directory
-    validate_skill_path(&skill_dir, skills_dir)?;
+    let (meta, content) = run_blocking(&state, {
+        let (name, actor) = (name.clone(), caller.id.clone());
+        move |state| {
+            // Validate the constructed path is within skills directory
+            validate_skill_path(&skill_dir, &skills_dir)?;
 
-    if !skill_dir.exists() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse::new(format!("Skill '{}' not found", name))),
-        ));
-    }
+            if !skill_dir.exists() {
+                return Err(skill_not_found(state, &name));
+            }
 
-    // Load existing meta
-    let meta_path = skill_dir.join("_meta.json");
-    let meta_content = async_fs::read_to_string(&meta_path).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to read _meta.json: {}", e))),
-        )
-    })?;
+            // Hold the lock from the precondition check to the last write, as
+            // PATCH does, so a concurrent edit can't slip in between
+            let _guard = state.edits.lock();
 
-    let mut meta: SkillMeta = serde_json::from_str(&meta_content).map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to parse _meta.json: {}", e))),
-        )
-    })?;
+            // Load existing meta
+            let meta_path = skill_dir.join("_meta.json");
+            let meta_content = std::fs::read_to_string(&meta_path).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to read _meta.json: {}", e))),
+                )
+            })?;
 
-    // Update fields
-    if let Some(description) = req.description {
-        meta.description = description;
-    }
-    if let Some(tags) = req.tags {
-        meta.tags = tags;
-    }
+            let mut meta: SkillMeta = serde_json::from_str(&meta_content).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::MetaParseError, format!("Failed to parse _meta.json: {}", e))),
+                )
+            })?;
+
+            let before = meta.clone();
+            let old_content = std::fs::read_to_string(skill_dir.join("SKILL.md")).unwrap_or_default();
+            if let Some(base) = &req.base_sha256 {
+                if !base.eq_ignore_ascii_case(&patch::content_hash(&old_content)) {
+                    return Err((
+                        StatusCode::CONFLICT,
+                        Json(ErrorResponse::new(ErrorCode::Conflict, "SKILL.md changed since base_sha256".to_string())),
+                    ));
+                }
+            }
+            let entry = AuditEntry::new(&actor, AuditAction::Update, &name);
+            state.begin_mutation(&entry);
 
-    // Save updated meta
-    let meta_json = serde_json::to_string_pretty(&meta).unwrap();
-    async_fs::write(&meta_path, meta_json).await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to write _meta.json: {}", e))),
-        )
-    })?;
+            // Update fields
+            if let Some(description) = req.description {
+                meta.description = description;
+            }
+            if let Some(tags) = req.tags {
+                meta.tags = tags;
+            }
 
-    // Update content if provided
-    let content = if let Some(new_content) = req.content {
-        async_fs::write(skill_dir.join("SKILL.md"), &new_content).await.map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Failed to write SKILL.md: {}", e))),
-            )
-        })?;
-        new_content
-    } else {
-        async_fs::read_to_string(skill_dir.join("SKILL.md")).await.unwrap_or_default()
-    };
+            // Save updated meta
+            let meta_json = serde_json::to_string_pretty(&meta).unwrap();
+            std::fs::write(&meta_path, meta_json).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to write _meta.json: {}", e))),
+                )
+            })?;
+
+            // Update content if provided
+            let content = if let Some(new_content) = req.content {
+                std::fs::write(skill_dir.join("SKILL.md"), &new_content).map_err(|e| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to write SKILL.md: {}", e))),
+                    )
+                })?;
+                new_content
+            } else {
+                old_content.clone()
+            };
+            state.record_mutation(entry.with_summary(audit::change_summary(&before, &meta, &old_content, &content)));
+            Ok((meta, content))
+        }
+    })
+    .await?;
 
     // Reload index
-    let _ = state.indexer.reload();
+    let _ = state.indexer.reload_async().await;
+    let meta = state.indexer.get_skill_meta(&name).unwrap_or(meta);
 
-    let sub_skills = meta
-        .sub_skills
-        .as_ref()
-        .map(|subs| {
-            subs.iter()
-                .map(|s| SubSkillInfo {
-                    name: s.name.clone(),
-                    file: s.file.clone(),
-                    triggers: s.triggers.clone(),
-                })
-                .collect()
+    Ok(Json(written_details(&state, caller, meta, content).await?))
+}
+
+/// Details of a skill just written, from its reindexed metadata.
+async fn written_details(
+    state: &AppState,
+    caller: Caller,
+    meta: SkillMeta,
+    content: String,
+) -> Result<SkillDetails, (StatusCode, Json<ErrorResponse>)> {
+    // Whether it has references is checked on disk
+    run_blocking(state, move |state| {
+        let sub_skills = meta
+            .sub_skills
+            .as_ref()
+            .map(|subs| {
+                subs.iter()
+                    .map(|s| SubSkillInfo {
+                        name: s.name.clone(),
+                        file: s.file.clone(),
+                        triggers: s.triggers.clone(),
+                        language: s.language.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(SkillDetails {
+            has_references: state.indexer.has_references(&meta.name),
+            deprecated: meta.is_deprecated(),
+            related: visible_related(state, &caller, &meta.name),
+            name: meta.name,
+            description: meta.description,
+            content,
+            tags: meta.tags,
+            sub_skills,
+            quick_answer: meta.quick_answer,
+            language: meta.language,
+            translations: meta.translations,
+            archived: meta.archived,
+            superseded_by: meta.superseded_by,
+            created_at: meta.created_at,
+            updated_at: meta.updated_at,
         })
-        .unwrap_or_default();
+    })
+    .await
+}
 
-    Ok(Json(SkillDetails {
-        name: meta.name,
-        description: meta.description,
-        content,
-        tags: meta.tags,
-        sub_skills,
-        has_references: state.indexer.has_references(&name),
+// ============================================================================
+// PATCH /api/skills/:name - Partially update SKILL.md
+// ============================================================================
+
+/// Either `diff` or `operations`, optionally guarded by the hash of the
+/// content the patch was made against.
+#[derive(Debug, Deserialize)]
+pub struct PatchSkillRequest {
+    #[serde(default)]
+    pub diff: Option<String>,
+    #[serde(default)]
+    pub operations: Option<Vec<SectionOp>>,
+    /// SHA-256 of the SKILL.md the patch was made against.
+    #[serde(default)]
+    pub base_sha256: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PatchSkillResponse {
+    #[serde(flatten)]
+    pub skill: SkillDetails,
+    /// SHA-256 of the patched SKILL.md, to send as the next `base_sha256`.
+    pub content_sha256: String,
+}
+
+pub async fn patch_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    Json(req): Json<PatchSkillRequest>,
+) -> Result<Json<PatchSkillResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+    let name = state.canonical_name(&name);
+    ensure_writable(&state, &name)?;
+
+    let skill_dir = state.indexer.skill_dir(&name);
+    let skills_dir = state.indexer.skill_root_dir(&name);
+    let content = run_blocking(&state, {
+        let (name, actor) = (name.clone(), caller.id.clone());
+        move |state| {
+            validate_skill_path(&skill_dir, &skills_dir)?;
+
+            let skill_md = skill_dir.join("SKILL.md");
+            if !skill_md.exists() {
+                return Err(skill_not_found(state, &name));
+            }
+
+            let conflict = |message: String| (StatusCode::CONFLICT, Json(ErrorResponse::new(ErrorCode::Conflict, message)));
+            // Hold the lock from read to rename so concurrent patches can't interleave
+            let _guard = state.edits.lock();
+            let old_content = std::fs::read_to_string(&skill_md).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to read SKILL.md: {}", e))),
+                )
+            })?;
+            if let Some(base) = &req.base_sha256 {
+                if !base.eq_ignore_ascii_case(&patch::content_hash(&old_content)) {
+                    return Err(conflict("SKILL.md changed since base_sha256".to_string()));
+                }
+            }
+
+            let patched = match (&req.diff, &req.operations) {
+                (Some(diff), None) => patch::apply_unified_diff(&old_content, diff),
+                (None, Some(ops)) => patch::apply_section_ops(&old_content, ops),
+                _ => {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        Json(ErrorResponse::new(ErrorCode::InvalidRequest, "Provide either diff or operations".to_string())),
+                    ))
+                }
+            };
+            let content = patched.map_err(|e| match e {
+                PatchError::Malformed(_) => (StatusCode::BAD_REQUEST, Json(ErrorResponse::new(ErrorCode::InvalidRequest, e.to_string()))),
+                _ => conflict(e.to_string()),
+            })?;
+            if content.len() > MAX_CONTENT_LENGTH {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::new(ErrorCode::InvalidRequest, format!(
+                        "Content too long (max {} bytes)",
+                        MAX_CONTENT_LENGTH
+                    ))),
+                ));
+            }
+
+            let entry = AuditEntry::new(&actor, AuditAction::Update, &name);
+            state.begin_mutation(&entry);
+            let tmp = skill_dir.join(".SKILL.md.tmp");
+            std::fs::write(&tmp, &content)
+                .and_then(|_| std::fs::rename(&tmp, &skill_md))
+                .map_err(|e| {
+                    let _ = std::fs::remove_file(&tmp);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to write SKILL.md: {}", e))),
+                    )
+                })?;
+            let (added, removed) = diff::line_changes(&old_content, &content);
+            state.record_mutation(entry.with_summary(format!("SKILL.md +{} -{} lines", added, removed)));
+            Ok(content)
+        }
+    })
+    .await?;
+
+    let _ = state.indexer.reload_async().await;
+    let meta = state.indexer.get_skill_meta(&name).ok_or_else(|| skill_not_found(&state, &name))?;
+
+    Ok(Json(PatchSkillResponse {
+        content_sha256: patch::content_hash(&content),
+        skill: written_details(&state, caller, meta, content).await?,
     }))
 }
 
@@ -619,113 +1356,2256 @@ pub async fn update_skill(
 
 pub async fn delete_skill(
     State(state): State<AppState>,
+    caller: Caller,
     Path(name): Path<String>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
     // Validate skill name to prevent path traversal
-    validate_skill_name(&name)?;
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+    let name = state.canonical_name(&name);
+    ensure_writable(&state, &name)?;
+
+    let skill_dir = state.indexer.skill_dir(&name);
+    let skills_dir = state.indexer.skill_root_dir(&name);
+
+    run_blocking(&state, {
+        let name = name.clone();
+        move |state| {
+            // Validate the constructed path is within skills directory
+            validate_skill_path(&skill_dir, &skills_dir)?;
+
+            if !skill_dir.exists() {
+                return Err(skill_not_found(state, &name));
+            }
 
-    let skills_dir = state.indexer.skills_dir();
-    let skill_dir = skills_dir.join(&name);
+            let _guard = state.edits.lock();
+            let entry = AuditEntry::new(&caller.id, AuditAction::Delete, &name);
+            state.begin_mutation(&entry);
+            std::fs::remove_dir_all(&skill_dir).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to delete skill: {}", e))),
+                )
+            })?;
+            state.record_mutation(entry);
+            Ok(())
+        }
+    })
+    .await?;
+
+    // Reload index
+    let _ = state.indexer.reload_async().await;
+    state.receipts.write().forget(&name);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// POST /api/skills/:name/rename - Rename skill
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct RenameSkillRequest {
+    pub new_name: String,
+    #[serde(default = "default_true")]
+    pub rewrite_links: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Map a lifecycle error to an HTTP error response.
+fn lifecycle_error(e: LifecycleError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        LifecycleError::NotFound(_) => StatusCode::NOT_FOUND,
+        LifecycleError::AlreadyExists(_) => StatusCode::CONFLICT,
+        LifecycleError::Invalid(_) => StatusCode::BAD_REQUEST,
+        LifecycleError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ErrorResponse::new(e.code(), e.to_string())))
+}
+
+pub async fn rename_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    Json(req): Json<RenameSkillRequest>,
+) -> Result<Json<RenameReport>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    validate_skill_name(&state, &req.new_name)?;
+    ensure_visible(&state, &caller, &name)?;
+    ensure_writable(&state, &name)?;
+
+    // Renames rewrite references across the root, so it's journaled whole
+    let root = state.indexer.skill_root_dir(&name);
+    let report = run_blocking(&state, {
+        let name = name.clone();
+        move |state| {
+            let _guard = state.edits.lock();
+            let entry = AuditEntry::new(&caller.id, AuditAction::Rename, &name);
+            state.begin_mutation(&entry);
+            let report = lifecycle::rename_skill(&root, &name, &req.new_name, req.rewrite_links).map_err(lifecycle_error)?;
+            state.record_mutation_in(
+                entry.with_summary(format!(
+                    "renamed to {}; {} links rewritten",
+                    report.new_name, report.links_rewritten
+                )),
+                &[root],
+            );
+            Ok(report)
+        }
+    })
+    .await?;
+
+    // Reload index
+    let _ = state.indexer.reload_async().await;
+    state.receipts.write().forget(&name);
+
+    Ok(Json(report))
+}
+
+// ============================================================================
+// POST /api/skills/:name/duplicate - Fork skill under a new name
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateSkillRequest {
+    pub new_name: String,
+}
 
-    // Validate the constructed path is within skills directory
-    validate_skill_path(&skill_dir, skills_dir)?;
+pub async fn duplicate_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    Json(req): Json<DuplicateSkillRequest>,
+) -> Result<(StatusCode, Json<DuplicateReport>), (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    validate_skill_name(&state, &req.new_name)?;
+    ensure_visible(&state, &caller, &name)?;
+    ensure_writable(&state, &req.new_name)?;
+
+    let root = state.indexer.skill_root_dir(&name);
+    let report = run_blocking(&state, move |state| {
+        let _guard = state.edits.lock();
+        let entry = AuditEntry::new(&caller.id, AuditAction::Duplicate, &req.new_name)
+            .with_summary(format!("copied from {}", name));
+        state.begin_mutation(&entry);
+        let report = lifecycle::duplicate_skill(&root, &name, &req.new_name).map_err(lifecycle_error)?;
+        state.record_mutation_in(entry, &[root.join(&report.new_name)]);
+        Ok(report)
+    })
+    .await?;
+
+    // Reload index
+    let _ = state.indexer.reload_async().await;
+
+    Ok((StatusCode::CREATED, Json(report)))
+}
+
+// ============================================================================
+// POST /api/skills/import-claude-skill - Import a Claude-format skill
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ImportClaudeSkillRequest {
+    /// Path to the skill directory on the server, inside the `[import]`
+    /// root.
+    pub path: String,
+    /// Name override; defaults to the frontmatter name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Merge into the skill if it already exists, instead of failing.
+    #[serde(default)]
+    pub merge: bool,
+}
+
+fn import_error(e: ImportError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        ImportError::NotFound(_) => StatusCode::NOT_FOUND,
+        ImportError::AlreadyExists(_) => StatusCode::CONFLICT,
+        ImportError::Invalid(_) => StatusCode::BAD_REQUEST,
+        ImportError::Download(_) => StatusCode::BAD_GATEWAY,
+        ImportError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ErrorResponse::new(e.code(), e.to_string())))
+}
 
-    if !skill_dir.exists() {
+/// Resolve a folder to import, which must be inside the configured
+/// `[import]` root.
+fn import_folder_path(state: &AppState, path: &str) -> Result<std::path::PathBuf, (StatusCode, Json<ErrorResponse>)> {
+    let config = state.import.read();
+    if config.root.is_none() {
         return Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse::new(format!("Skill '{}' not found", name))),
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                ErrorCode::Disabled,
+                "Folder imports are disabled on this server; set [import] root to allow them".to_string(),
+            )),
         ));
     }
-
-    async_fs::remove_dir_all(&skill_dir).await.map_err(|e| {
+    config.resolve(state.indexer.skills_dir(), StdPath::new(path)).ok_or_else(|| {
         (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to delete skill: {}", e))),
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                ErrorCode::InvalidPath,
+                format!("'{}' is not a folder inside the import root", path),
+            )),
         )
-    })?;
+    })
+}
+
+pub async fn import_claude_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(req): Json<ImportClaudeSkillRequest>,
+) -> Result<(StatusCode, Json<ImportReport>), (StatusCode, Json<ErrorResponse>)> {
+    if let Some(name) = &req.name {
+        validate_skill_name(&state, name)?;
+        ensure_visible(&state, &caller, name)?;
+    }
+    ensure_writable(&state, req.name.as_deref().unwrap_or_default())?;
+
+    let mode = if req.merge { ImportMode::Merge } else { ImportMode::Create };
+    let report = run_blocking(&state, move |state| {
+        // Resolving the folder follows links on disk
+        let source = import_folder_path(state, &req.path)?;
+        let _guard = state.edits.lock();
+        // The name may only be known from the frontmatter once imported
+        state.begin_mutation(&AuditEntry::new(&caller.id, AuditAction::Import, req.name.as_deref().unwrap_or_default()));
+        let report =
+            import::import_claude_skill(state.indexer.skills_dir(), &source, req.name.as_deref(), mode)
+                .map_err(import_error)?;
+        let summary = match &report.merge {
+            Some(merge) => format!(
+                "merged {} files from {}, {} conflicts",
+                report.files.len() + merge.removed.len(),
+                req.path,
+                merge.conflicts.len()
+            ),
+            None => format!("{} files from {}", report.files.len(), req.path),
+        };
+        state.record_mutation(AuditEntry::new(&caller.id, AuditAction::Import, &report.name).with_summary(summary));
+        Ok(report)
+    })
+    .await?;
 
     // Reload index
-    let _ = state.indexer.reload();
+    let _ = state.indexer.reload_async().await;
 
-    Ok(StatusCode::NO_CONTENT)
+    let status = if report.merge.is_some() { StatusCode::OK } else { StatusCode::CREATED };
+    Ok((status, Json(report)))
 }
 
 // ============================================================================
-// POST /api/reload - Reload index
+// POST /api/skills/import-from-url - Import skills from a GitHub repository
 // ============================================================================
 
-#[derive(Debug, Serialize)]
-pub struct ReloadResponse {
-    pub success: bool,
-    pub skill_count: usize,
-}
-
-pub async fn reload_index(State(state): State<AppState>) -> impl IntoResponse {
-    match state.indexer.reload() {
-        Ok(()) => {
-            let count = state.indexer.get_skill_index().len();
-            Json(ReloadResponse {
-                success: true,
-                skill_count: count,
-            })
+#[derive(Debug, Deserialize)]
+pub struct ImportFromUrlRequest {
+    /// Repository URL, optionally with `/tree/<ref>/<path>`.
+    pub url: String,
+    /// Merge into skills that already exist, instead of skipping them.
+    #[serde(default)]
+    pub merge: bool,
+}
+
+pub async fn import_from_url(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(req): Json<ImportFromUrlRequest>,
+) -> Result<(StatusCode, Json<UrlImportReport>), (StatusCode, Json<ErrorResponse>)> {
+    let source: GitHubSource = req.url.parse().map_err(import_error)?;
+    ensure_writable(&state, "")?;
+
+    let mode = if req.merge { ImportMode::Merge } else { ImportMode::Create };
+    let task_state = state.clone();
+    let report = tokio::task::spawn_blocking(move || {
+        let state = task_state;
+        // Downloading and extracting can take a while, so only installing
+        // holds the edit lock
+        let archive = github::download(&source.archive_url())?;
+        let staged = github::stage_archive(&archive, &source)?;
+
+        let _guard = state.edits.lock();
+        state.begin_mutation(&AuditEntry::new(&caller.id, AuditAction::Import, ""));
+        let report = staged.install(state.indexer.skills_dir(), mode, |name| {
+            // Merging into a skill the caller can't see would reveal it
+            if mode == ImportMode::Merge && !can_see(&state, &caller, name) {
+                return Err(ImportError::NotFound(state.skill_not_found(name).error));
+            }
+            Ok(())
+        })?;
+        for skill in &report.skills {
+            let summary = match &skill.merge {
+                Some(merge) => format!("merged from {}, {} conflicts", req.url, merge.conflicts.len()),
+                None => format!("{} files from {}", skill.files.len(), req.url),
+            };
+            state.record_mutation(AuditEntry::new(&caller.id, AuditAction::Import, &skill.name).with_summary(summary));
         }
-        Err(_) => Json(ReloadResponse {
-            success: false,
-            skill_count: 0,
-        }),
-    }
+        Ok(report)
+    })
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(ErrorCode::InternalError, format!("Import task failed: {}", e))),
+        )
+    })?
+    .map_err(import_error)?;
+
+    // Reload index
+    let _ = state.indexer.reload_async().await;
+
+    let created = report.skills.iter().any(|s| s.merge.is_none());
+    let status = if created { StatusCode::CREATED } else { StatusCode::OK };
+    Ok((status, Json(report)))
 }
 
 // ============================================================================
-// GET /api/search - Search skills
+// GET /api/import/conflicts - Files left unmerged by imports
 // ============================================================================
 
-/// Maximum allowed search query length
-const MAX_SEARCH_QUERY_LENGTH: usize = 1000;
+fn merge_error(e: MergeError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        MergeError::NoConflict(_) => StatusCode::NOT_FOUND,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ErrorResponse::new(e.code(), e.to_string())))
+}
 
-/// Maximum allowed search limit
-const MAX_SEARCH_LIMIT: usize = 100;
+pub async fn list_import_conflicts(
+    State(state): State<AppState>,
+    caller: Caller,
+) -> Result<Json<Vec<SkillConflicts>>, (StatusCode, Json<ErrorResponse>)> {
+    let conflicts = run_blocking(&state, |state| {
+        Imports::new(state.indexer.skills_dir()).all_conflicts().map_err(merge_error)
+    })
+    .await?;
+    let index = state.indexer.get_skill_index();
+    Ok(Json(
+        conflicts
+            .into_iter()
+            .filter(|c| index.find(&c.skill).is_none_or(|s| s.is_visible_to(caller.key())))
+            .collect(),
+    ))
+}
+
+// ============================================================================
+// POST /api/import/conflicts/:name/resolve - Resolve one conflicting file
+// ============================================================================
 
 #[derive(Debug, Deserialize)]
-pub struct SearchQuery {
-    pub q: String,
-    #[serde(default = "default_limit")]
-    pub limit: usize,
+pub struct ResolveConflictRequest {
+    /// Conflicting file, relative to the skill directory.
+    pub file: String,
+    /// `local`, `incoming`, or `content`.
+    pub resolution: String,
+    /// Merged content, for the `content` resolution.
+    #[serde(default)]
+    pub content: Option<String>,
 }
 
-fn default_limit() -> usize {
-    10
+pub async fn resolve_import_conflict(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    Json(req): Json<ResolveConflictRequest>,
+) -> Result<Json<SkillConflicts>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+    ensure_writable(&state, &name)?;
+    let invalid = |message: String| (StatusCode::BAD_REQUEST, Json(ErrorResponse::new(ErrorCode::InvalidRequest, message)));
+    if !merge::is_skill_file(&req.file) {
+        return Err(invalid(format!("Invalid file path '{}'", req.file)));
+    }
+    let resolution = match (req.resolution.as_str(), req.content) {
+        ("local", _) => Resolution::Local,
+        ("incoming", _) => Resolution::Incoming,
+        ("content", Some(content)) => Resolution::Content(content),
+        ("content", None) => return Err(invalid("The content resolution needs content".to_string())),
+        (other, _) => {
+            return Err(invalid(format!(
+                "Unknown resolution '{}' (expected local, incoming, or content)",
+                other
+            )))
+        }
+    };
+
+    let conflicts = run_blocking(&state, {
+        let name = name.clone();
+        move |state| {
+            let _guard = state.edits.lock();
+            let entry = AuditEntry::new(&caller.id, AuditAction::Update, &name)
+                .with_summary(format!("resolved import conflict on {} ({})", req.file, req.resolution));
+            state.begin_mutation(&entry);
+            let conflicts = Imports::new(state.indexer.skills_dir())
+                .resolve(&name, &req.file, resolution)
+                .map_err(merge_error)?;
+            state.record_mutation(entry);
+            Ok(conflicts)
+        }
+    })
+    .await?;
+    let _ = state.indexer.update_skill_async(&name).await;
+
+    Ok(Json(SkillConflicts { skill: name, conflicts }))
 }
 
-pub async fn search_skills(
+// ============================================================================
+// GET /api/skills/:name/readers - Recent readers of a skill
+// ============================================================================
+
+pub async fn get_skill_readers(
     State(state): State<AppState>,
-    axum::extract::Query(query): axum::extract::Query<SearchQuery>,
-) -> Result<Json<crate::models::SearchResults>, (StatusCode, Json<ErrorResponse>)> {
-    use crate::models::SearchOptions;
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<SkillReaders>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
 
-    // Validate query length
-    if query.q.is_empty() {
+    let receipts = state.receipts.read();
+
+    // Reader identities are only exposed when explicitly enabled
+    if !receipts.is_enabled() {
         return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new("Search query cannot be empty".to_string())),
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                ErrorCode::Disabled,
+                "Read receipts are disabled on this server".to_string(),
+            )),
         ));
     }
 
-    if query.q.len() > MAX_SEARCH_QUERY_LENGTH {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new(format!(
+    if state.indexer.get_skill_meta(&name).is_none() {
+        return Err(skill_not_found(&state, &name));
+    }
+
+    Ok(Json(receipts.readers(&state.canonical_name(&name))))
+}
+
+// ============================================================================
+// GET /api/skills/:name/tables - Markdown tables as structured data
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct TablesQuery {
+    /// Only return tables from this sub-skill.
+    #[serde(default)]
+    pub sub_skill: Option<String>,
+}
+
+pub async fn get_skill_tables(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<TablesQuery>,
+) -> Result<Json<SkillTables>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+
+    let tables = skill_tables(&state, &name, query.sub_skill.as_deref()).ok_or_else(|| skill_not_found(&state, &name))?;
+    state.track_skill_read(&tables.skill, &caller.id);
+
+    Ok(Json(tables))
+}
+
+// ============================================================================
+// GET /api/skills/:name/outline - Heading hierarchy with token counts
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct OutlineQuery {
+    /// Outline this sub-skill instead of SKILL.md.
+    #[serde(default)]
+    pub sub_skill: Option<String>,
+}
+
+pub async fn get_skill_outline(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<OutlineQuery>,
+) -> Result<Json<SkillOutline>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+    if !state.indexer.skill_exists(&name) {
+        return Err(skill_not_found(&state, &name));
+    }
+
+    let outline = skill_outline(&state, &name, query.sub_skill.as_deref()).map_err(index_error)?;
+    state.track_skill_read(&outline.skill, &caller.id);
+
+    Ok(Json(outline))
+}
+
+// ============================================================================
+// GET /api/skills/:name/examples - Fenced code examples
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ExamplesQuery {
+    /// Only return examples in this code language (`python`, `ts`).
+    #[serde(default)]
+    pub lang: Option<String>,
+}
+
+pub async fn get_skill_examples(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<ExamplesQuery>,
+) -> Result<Json<SkillExamples>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+
+    let examples = skill_examples(&state, &name, query.lang.as_deref()).ok_or_else(|| skill_not_found(&state, &name))?;
+    state.track_skill_read(&examples.skill, &caller.id);
+
+    Ok(Json(examples))
+}
+
+// ============================================================================
+// GET /api/skills/:name/stats - Content statistics
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SkillStatsQuery {
+    /// Token budget to check the skill's total against.
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillStatsResponse {
+    #[serde(flatten)]
+    pub stats: SkillStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+    /// Whether the total token count fits `max_tokens`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub within_budget: Option<bool>,
+}
+
+pub async fn get_skill_stats(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<SkillStatsQuery>,
+) -> Result<Json<SkillStatsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+
+    let stats = skill_stats(&state, &name).ok_or_else(|| skill_not_found(&state, &name))?;
+
+    Ok(Json(SkillStatsResponse {
+        within_budget: query.max_tokens.map(|max| stats.total.tokens <= max),
+        max_tokens: query.max_tokens,
+        stats,
+    }))
+}
+
+// ============================================================================
+// GET /api/skills/:name/scripts - Scripts bundled with a skill
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct SkillScriptsResponse {
+    pub name: String,
+    /// Whether running scripts is enabled on this server.
+    pub enabled: bool,
+    pub scripts: Vec<SkillScript>,
+}
+
+pub async fn list_skill_scripts(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<SkillScriptsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+    let name = state
+        .indexer
+        .get_skill_meta(&name)
+        .map(|meta| meta.name)
+        .ok_or_else(|| skill_not_found(&state, &name))?;
+
+    let config = state.scripts.read().clone();
+    let skill_dir = state.indexer.skill_dir(&name);
+    let scripts = tokio::task::spawn_blocking({
+        let name = name.clone();
+        let config = config.clone();
+        move || scripts::list_scripts(&config, &name, &skill_dir)
+    })
+    .await
+    .unwrap_or_default();
+
+    Ok(Json(SkillScriptsResponse {
+        name,
+        enabled: config.enabled,
+        scripts,
+    }))
+}
+
+// ============================================================================
+// GET /api/skills/:name/dependencies - Transitive dependencies
+// ============================================================================
+
+pub async fn get_skill_dependencies(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<SkillDependencies>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+
+    let index = state.indexer.get_skill_index();
+    DependencyGraph::new(&index)
+        .dependencies(&state.canonical_name(&name))
+        .map(Json)
+        .ok_or_else(|| skill_not_found(&state, &name))
+}
+
+// ============================================================================
+// GET/PUT /api/skills/:name/links - Curated related-skill links
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateLinksRequest {
+    pub see_also: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillLinksResponse {
+    pub skill: String,
+    pub see_also: Vec<String>,
+    pub related: Vec<RelatedSkill>,
+}
+
+pub async fn get_skill_links(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<SkillLinksResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+
+    let skill_index = state.indexer.get_skill_index();
+    let meta = skill_index.find(&name).ok_or_else(|| skill_not_found(&state, &name))?;
+
+    Ok(Json(SkillLinksResponse {
+        see_also: meta.see_also.clone(),
+        related: visible_related(&state, &caller, &name),
+        skill: meta.name.clone(),
+    }))
+}
+
+pub async fn update_skill_links(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    Json(req): Json<UpdateLinksRequest>,
+) -> Result<Json<SkillLinksResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+    let name = state.canonical_name(&name);
+    ensure_writable(&state, &name)?;
+
+    let skill_index = state.indexer.get_skill_index();
+    if skill_index.find(&name).is_none() {
+        return Err(skill_not_found(&state, &name));
+    }
+    let unknown = |link: &String| skill_index.find(link).is_none_or(|s| !s.is_visible_to(caller.key()));
+    if let Some(unknown) = req.see_also.iter().find(|l| unknown(l)) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::validation(vec![FieldError::new(
+                "see_also",
+                format!("Cannot link to unknown skill '{}'", unknown),
+            )])),
+        ));
+    }
+
+    let meta_path = state.indexer.skill_dir(&name).join("_meta.json");
+    let meta = run_blocking(&state, {
+        let name = name.clone();
+        move |state| {
+            let _guard = state.edits.lock();
+            let meta_content = std::fs::read_to_string(&meta_path).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to read _meta.json: {}", e))),
+                )
+            })?;
+            let mut meta: SkillMeta = serde_json::from_str(&meta_content).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::MetaParseError, format!("Failed to parse _meta.json: {}", e))),
+                )
+            })?;
+
+            let before = meta.clone();
+            meta.see_also = req.see_also;
+            if let Err(errors) = validate_meta(&meta) {
+                let errors: Vec<String> = errors.into_iter().filter(|e| e.starts_with("see_also")).collect();
+                if !errors.is_empty() {
+                    return Err((
+                        StatusCode::BAD_REQUEST,
+                        Json(ErrorResponse::validation(meta_field_errors(errors))),
+                    ));
+                }
+            }
+
+            let entry = AuditEntry::new(&caller.id, AuditAction::Update, &name)
+                .with_summary(audit::change_summary(&before, &meta, "", ""));
+            state.begin_mutation(&entry);
+            let meta_json = serde_json::to_string_pretty(&meta).unwrap();
+            std::fs::write(&meta_path, meta_json).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to write _meta.json: {}", e))),
+                )
+            })?;
+            state.record_mutation(entry);
+            Ok(meta)
+        }
+    })
+    .await?;
+
+    state.indexer.update_skill_async(&name).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(e.code(), format!("Failed to reindex skill: {}", e))),
+        )
+    })?;
+
+    Ok(Json(SkillLinksResponse {
+        see_also: meta.see_also,
+        related: state.indexer.get_skill_index().related(&name, RELATED_LIMIT),
+        skill: name,
+    }))
+}
+
+// ============================================================================
+// GET /api/skills/:name/backlinks - Links into a skill from other skills
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct BacklinksQuery {
+    /// Only return links to this file within the skill.
+    #[serde(default)]
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BacklinksResponse {
+    pub skill: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    pub backlinks: Vec<SkillLink>,
+}
+
+pub async fn get_skill_backlinks(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    axum::extract::Query(query): axum::extract::Query<BacklinksQuery>,
+) -> Result<Json<BacklinksResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+
+    let skill_index = state.indexer.get_skill_index();
+    if skill_index.find(&name).is_none() {
+        return Err(skill_not_found(&state, &name));
+    }
+    let name = state.canonical_name(&name);
+
+    let graph = LinkGraph::new(
+        state.indexer.skills_dir(),
+        &skill_index,
+        &state.indexer.get_content_index(),
+    );
+    let backlinks = graph
+        .backlinks(&name, query.file.as_deref())
+        .into_iter()
+        .cloned()
+        .collect();
+
+    Ok(Json(BacklinksResponse {
+        skill: name,
+        file: query.file,
+        backlinks,
+    }))
+}
+
+// ============================================================================
+// GET /api/skills/:name/references - Files in the references directory
+// GET /api/skills/:name/references/*path - Download a reference file
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct ReferencesResponse {
+    pub skill: String,
+    pub references: Vec<ReferenceFile>,
+}
+
+pub async fn list_skill_references(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<ReferencesResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+
+    let meta = state
+        .indexer
+        .get_skill_meta(&name)
+        .ok_or_else(|| skill_not_found(&state, &name))?;
+    Ok(Json(ReferencesResponse {
+        references: state.indexer.list_references_async(&meta.name).await,
+        skill: meta.name,
+    }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReferenceQuery {
+    /// `html` renders a markdown reference instead of sending the file.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// Stream a reference file of any type, honoring `Range` and conditional
+/// request headers, or render a markdown reference to HTML.
+pub async fn get_skill_reference(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path((name, path)): Path<(String, String)>,
+    axum::extract::Query(query): axum::extract::Query<ReferenceQuery>,
+    request: Request,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+
+    let meta = state
+        .indexer
+        .get_skill_meta(&name)
+        .ok_or_else(|| skill_not_found(&state, &name))?;
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(ErrorCode::NotFound, format!(
+                "Reference '{}' not found in '{}'",
+                path, meta.name
+            ))),
+        )
+    };
+
+    // Only listed files are served, so `..` and symlinks the symlink policy
+    // doesn't follow are rejected the same way as missing files
+    if !state.indexer.list_references_async(&meta.name).await.iter().any(|f| f.path == path) {
+        return Err(not_found());
+    }
+    let file = state.indexer.skill_dir(&meta.name).join("references").join(&path);
+
+    match query.format.as_deref() {
+        None => {}
+        Some("html") if file_type(&path) == MARKDOWN => {
+            let content = async_fs::read_to_string(&file).await.map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to read reference: {}", e))),
+                )
+            })?;
+            let links = html_links(&state, &caller, &meta.name, &format!("references/{}", path));
+            let html = markdown::render_html(markdown::strip_frontmatter(&content), links);
+            let modified = modified_time(&file).await;
+            return Ok(conditional::respond(request.headers(), "text/html; charset=utf-8", html.into_bytes(), modified));
+        }
+        Some(format) => {
+            let message = if format == "html" {
+                "Only markdown references can be rendered as HTML".to_string()
+            } else {
+                format!("Unknown format '{}' (expected html)", format)
+            };
+            return Err((StatusCode::BAD_REQUEST, Json(ErrorResponse::new(ErrorCode::InvalidRequest, message))));
+        }
+    }
+
+    // ServeFile handles If-Modified-Since and Range; ETags are added here
+    let validators = async_fs::metadata(&file).await.ok().map(|m| Validators::for_file(&m));
+    if let Some(validators) = validators.as_ref().filter(|v| v.not_modified(request.headers())) {
+        return Ok(validators.not_modified_response());
+    }
+    let response = ServeFile::new(file).oneshot(request).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to read reference: {}", e))),
+        )
+    })?;
+    let mut response = response.map(Body::new);
+    if let Some(validators) = validators.filter(|_| response.status().is_success()) {
+        validators.apply(&mut response);
+    }
+    Ok(response)
+}
+
+// ============================================================================
+// POST /api/reload - Start a background reload
+// GET /api/reload/:job_id - Reload progress
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReloadQuery {
+    /// Respond once the reload finishes instead of right away.
+    #[serde(default)]
+    pub wait: bool,
+}
+
+/// Start a reload, answering `202 Accepted` with the job to poll. The old
+/// index is served until the reload completes.
+pub async fn reload_index(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<ReloadQuery>,
+) -> (StatusCode, Json<ReloadStatus>) {
+    let job = state.reloads.start(&state.indexer);
+    if query.wait {
+        job.wait().await;
+        return (StatusCode::OK, Json(job.status()));
+    }
+    (StatusCode::ACCEPTED, Json(job.status()))
+}
+
+pub async fn get_reload_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+) -> Result<Json<ReloadStatus>, (StatusCode, Json<ErrorResponse>)> {
+    let job = state.reloads.get(&job_id).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(ErrorCode::NotFound, format!("Reload job '{}' not found", job_id))),
+        )
+    })?;
+    Ok(Json(job.status()))
+}
+
+// ============================================================================
+// GET /api/watch/status - File watcher state
+// ============================================================================
+
+pub async fn get_watch_status(State(state): State<AppState>) -> Json<WatchStatus> {
+    Json(state.indexer.watch_state().status())
+}
+
+// ============================================================================
+// GET /healthz - Liveness probe
+// GET /readyz - Readiness probe
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+}
+
+/// The process is up and serving requests.
+pub async fn healthz() -> Json<HealthResponse> {
+    Json(HealthResponse { status: "ok" })
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillsDirCheck {
+    pub path: String,
+    pub accessible: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IndexCheck {
+    /// Whether a full build has completed.
+    pub built: bool,
+    #[serde(with = "crate::models::timestamp::option", skip_serializing_if = "Option::is_none")]
+    pub built_at: Option<DateTime<Utc>>,
+    /// Seconds since the last full build.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_secs: Option<i64>,
+    /// Skills held in memory.
+    pub skills: usize,
+    /// Files held in the in-memory content index.
+    pub content_entries: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadinessResponse {
+    pub ready: bool,
+    pub skills_dir: SkillsDirCheck,
+    pub index: IndexCheck,
+    pub watcher: WatchStatus,
+}
+
+/// Ready once the first index build has completed and the skills directory
+/// is readable; 503 otherwise, with the same detail.
+pub async fn readyz(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let skills_dir = state.indexer.skills_dir();
+    let read_error = async_fs::read_dir(skills_dir).await.err();
+    let skills_dir = SkillsDirCheck {
+        path: skills_dir.display().to_string(),
+        accessible: read_error.is_none(),
+        error: read_error.map(|e| e.to_string()),
+    };
+
+    let built_at = state.indexer.built_at();
+    let index = IndexCheck {
+        built: built_at.is_some(),
+        built_at,
+        age_secs: built_at.map(|at| (Utc::now() - at).num_seconds().max(0)),
+        skills: state.indexer.get_skill_index().len(),
+        content_entries: state.indexer.get_content_index().len(),
+    };
+
+    let ready = index.built && skills_dir.accessible;
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    let response = ReadinessResponse {
+        ready,
+        skills_dir,
+        index,
+        watcher: state.indexer.watch_state().status(),
+    };
+    (status, Json(response))
+}
+
+// ============================================================================
+// GET /api/events - Stream of skill change events
+// ============================================================================
+
+pub async fn stream_events(
+    State(state): State<AppState>,
+    caller: Caller,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    // Skills hidden from the caller, remembered so their deletion stays
+    // hidden once they are gone from the index
+    let mut hidden: HashSet<String> = state
+        .indexer
+        .get_skill_index()
+        .skills
+        .iter()
+        .filter(|s| !s.is_visible_to(caller.key()))
+        .map(|s| s.name.clone())
+        .collect();
+    let receiver = state.indexer.events().subscribe();
+    let events = BroadcastStream::new(receiver).filter_map(move |event| {
+        let event = match event {
+            Ok(event) => {
+                let event = visible_event(&state, &caller, &mut hidden, event)?;
+                Event::default().event(event.kind.name()).json_data(&event)
+            }
+            // Tell slow subscribers to resync rather than silently skipping
+            Err(BroadcastStreamRecvError::Lagged(missed)) => Event::default()
+                .event("lagged")
+                .json_data(serde_json::json!({ "missed": missed })),
+        };
+        event.ok().map(Ok)
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// An event as the caller may see it, or None if it is only about skills
+/// hidden from them.
+fn visible_event(
+    state: &AppState,
+    caller: &Caller,
+    hidden: &mut HashSet<String>,
+    mut event: SkillEvent,
+) -> Option<SkillEvent> {
+    let mut visible = |name: &str| {
+        if !can_see(state, caller, name) {
+            hidden.insert(name.to_string());
+            false
+        } else {
+            state.indexer.get_skill_meta(name).is_some() || !hidden.contains(name)
+        }
+    };
+    match &mut event.kind {
+        SkillEventKind::Created { name } | SkillEventKind::Updated { name } | SkillEventKind::Deleted { name } => {
+            if !visible(name) {
+                return None;
+            }
+        }
+        SkillEventKind::IndexUpdated { skills } => {
+            skills.retain(|name| visible(name));
+            if skills.is_empty() {
+                return None;
+            }
+        }
+        SkillEventKind::IndexReloaded { skills } => {
+            let index = state.indexer.get_skill_index();
+            *skills = index.skills.iter().filter(|s| s.is_visible_to(caller.key())).count();
+        }
+    }
+    Some(event)
+}
+
+// ============================================================================
+// GET /api/search - Search skills
+// ============================================================================
+
+/// Maximum allowed search query length
+pub(super) const MAX_SEARCH_QUERY_LENGTH: usize = 1000;
+
+/// Maximum allowed search limit
+pub(super) const MAX_SEARCH_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: String,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub include_cold: bool,
+    #[serde(default)]
+    pub collection: Option<String>,
+    #[serde(default)]
+    pub lang: Option<String>,
+    #[serde(default)]
+    pub include_archived: bool,
+    #[serde(default)]
+    pub boost: Option<String>,
+}
+
+fn default_limit() -> usize {
+    10
+}
+
+pub async fn search_skills(
+    State(state): State<AppState>,
+    caller: Caller,
+    axum::extract::Query(query): axum::extract::Query<SearchQuery>,
+) -> Result<Json<crate::models::SearchResults>, (StatusCode, Json<ErrorResponse>)> {
+    use crate::models::SearchOptions;
+
+    // Validate query length
+    if query.q.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(ErrorCode::InvalidRequest, "Search query cannot be empty".to_string())),
+        ));
+    }
+
+    if query.q.len() > MAX_SEARCH_QUERY_LENGTH {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(ErrorCode::InvalidRequest, format!(
                 "Search query too long (max {} characters)",
                 MAX_SEARCH_QUERY_LENGTH
             ))),
-        ));
+        ));
+    }
+
+    // Clamp limit to valid range
+    let limit = query.limit.clamp(1, MAX_SEARCH_LIMIT);
+
+    let boosts = match &query.boost {
+        Some(boost) => crate::models::FieldBoosts::parse_overrides(boost).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(ErrorCode::InvalidRequest, format!("Invalid boost: {}", e))),
+            )
+        })?,
+        None => Vec::new(),
+    };
+
+    let filter = SearchOptions {
+        collection: query.collection.clone(),
+        language: query.lang.clone(),
+        include_archived: query.include_archived,
+        key: caller.key().map(str::to_string),
+        boosts,
+        ..Default::default()
+    };
+
+    if !query.include_cold {
+        let options = SearchOptions {
+            limit: Some(limit),
+            ..filter
+        };
+        let mut results = state.search.search_skills(&query.q, options);
+        state.track_search(&query.q, results.total_matches);
+        state.search.apply_fallback(&mut results);
+        return Ok(Json(results));
+    }
+
+    // Merge hot and cold matches before applying the limit
+    let cold_skills: Vec<SkillMeta> = run_blocking(&state, |state| {
+        ColdStorage::new(state.indexer.skills_dir()).list().map_err(cold_error)
+    })
+    .await?
+    .into_iter()
+    .map(|c| c.meta)
+    .collect();
+
+    let mut results = state
+        .search
+        .search_skills(&query.q, filter.clone())
+        .results;
+    results.extend(
+        state
+            .search
+            .search_skill_list(&cold_skills, &query.q, filter.clone())
+            .results
+            .into_iter()
+            .map(|r| r.into_cold()),
+    );
+
+    let mut results = crate::models::SearchResults::new(query.q, results, Some(limit));
+    state.track_search(&results.query, results.total_matches);
+    state.search.apply_fallback(&mut results);
+    Ok(Json(results))
+}
+
+// ============================================================================
+// POST /api/search/fusion - Multi-query fusion search
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct FusionSearchRequest {
+    pub task: String,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+pub async fn search_fusion(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(req): Json<FusionSearchRequest>,
+) -> Result<Json<crate::models::FusedSearchResults>, (StatusCode, Json<ErrorResponse>)> {
+    use crate::models::SearchOptions;
+
+    if req.task.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(ErrorCode::InvalidRequest, "Task description cannot be empty".to_string())),
+        ));
+    }
+
+    if req.task.len() > MAX_SEARCH_QUERY_LENGTH {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(ErrorCode::InvalidRequest, format!(
+                "Task description too long (max {} characters)",
+                MAX_SEARCH_QUERY_LENGTH
+            ))),
+        ));
+    }
+
+    let limit = req.limit.clamp(1, MAX_SEARCH_LIMIT);
+    let mut options = SearchOptions::with_limit(limit);
+    options.key = caller.key().map(str::to_string);
+
+    let mut fused = state.search.search_fused(&req.task, options);
+    state.track_search(&req.task, fused.results.total_matches);
+    state.search.apply_fallback_if_empty(&mut fused.results);
+    Ok(Json(fused))
+}
+
+// ============================================================================
+// POST /api/match/explain - Explain trigger matching for a skill
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct ExplainMatchRequest {
+    pub task: String,
+    pub skill: String,
+}
+
+pub async fn explain_match(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(req): Json<ExplainMatchRequest>,
+) -> Result<Json<crate::models::MatchExplanation>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &req.skill)?;
+    ensure_visible(&state, &caller, &req.skill)?;
+
+    if req.task.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(ErrorCode::InvalidRequest, "Task description cannot be empty".to_string())),
+        ));
+    }
+
+    if req.task.len() > MAX_SEARCH_QUERY_LENGTH {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(ErrorCode::InvalidRequest, format!(
+                "Task description too long (max {} characters)",
+                MAX_SEARCH_QUERY_LENGTH
+            ))),
+        ));
+    }
+
+    state
+        .search
+        .explain_match(
+            &state.canonical_name(&req.skill),
+            &req.task,
+            crate::models::SearchOptions {
+                key: caller.key().map(str::to_string),
+                ..Default::default()
+            },
+        )
+        .map(Json)
+        .ok_or_else(|| skill_not_found(&state, &req.skill))
+}
+
+// ============================================================================
+// POST /api/skills/:name/test-triggers - Sub-skill selection for sample phrases
+// ============================================================================
+
+/// Most phrases tested in one request.
+const MAX_TRIGGER_CASES: usize = 500;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TestTriggersRequest {
+    /// Phrases to report on, without expectations.
+    #[serde(default)]
+    pub phrases: Vec<String>,
+    /// Phrases with the sub-skills they should select.
+    #[serde(default)]
+    pub cases: Vec<crate::models::TriggerCase>,
+    /// A prompts file, one phrase per line with `=> expected` (batch mode).
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub min_score: Option<f64>,
+}
+
+pub async fn test_skill_triggers(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+    Json(req): Json<TestTriggersRequest>,
+) -> Result<Json<crate::models::TriggerTestReport>, (StatusCode, Json<ErrorResponse>)> {
+    use crate::models::TriggerCase;
+    use crate::search::{parse_trigger_cases, test_triggers, DEFAULT_TRIGGER_MIN_SCORE};
+
+    validate_skill_name(&state, &name)?;
+    ensure_visible(&state, &caller, &name)?;
+    let invalid = |message: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(ErrorCode::InvalidRequest, message)),
+        )
+    };
+
+    let min_score = req.min_score.unwrap_or(DEFAULT_TRIGGER_MIN_SCORE);
+    if !(0.0..=1.0).contains(&min_score) {
+        return Err(invalid("min_score must be between 0 and 1".to_string()));
+    }
+
+    let mut cases: Vec<TriggerCase> = req
+        .phrases
+        .into_iter()
+        .map(|phrase| TriggerCase { phrase, expect: None })
+        .collect();
+    cases.extend(req.cases);
+    if let Some(file) = &req.file {
+        cases.extend(parse_trigger_cases(file));
+    }
+
+    if cases.is_empty() {
+        return Err(invalid("No phrases to test".to_string()));
+    }
+    if cases.len() > MAX_TRIGGER_CASES {
+        return Err(invalid(format!("Too many phrases (max {})", MAX_TRIGGER_CASES)));
+    }
+    if let Some(case) = cases
+        .iter()
+        .find(|c| c.phrase.trim().is_empty() || c.phrase.len() > MAX_SEARCH_QUERY_LENGTH)
+    {
+        return Err(invalid(format!(
+            "Phrases must be non-empty and at most {} characters: {:?}",
+            MAX_SEARCH_QUERY_LENGTH,
+            case.phrase.chars().take(50).collect::<String>()
+        )));
+    }
+
+    let meta = state
+        .indexer
+        .get_skill_meta(&name)
+        .ok_or_else(|| skill_not_found(&state, &name))?;
+    Ok(Json(test_triggers(&meta, &cases, min_score)))
+}
+
+// ============================================================================
+// GET /api/diff - Compare two skills
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct DiffQuery {
+    pub left: String,
+    pub right: String,
+}
+
+pub async fn diff_skills(
+    State(state): State<AppState>,
+    caller: Caller,
+    axum::extract::Query(query): axum::extract::Query<DiffQuery>,
+) -> Result<Json<diff::SkillDiff>, (StatusCode, Json<ErrorResponse>)> {
+    let mut sides = Vec::new();
+    for name in [&query.left, &query.right] {
+        validate_skill_name(&state, name)?;
+        ensure_visible(&state, &caller, name)?;
+        let meta = state
+            .indexer
+            .get_skill_meta(name)
+            .ok_or_else(|| skill_not_found(&state, name))?;
+        sides.push((state.indexer.skill_dir(&meta.name), meta));
+    }
+
+    let read = |dir: std::path::PathBuf| {
+        tokio::task::spawn_blocking(move || diff::read_skill_files(&dir))
+    };
+    let files = tokio::try_join!(read(sides[0].0.clone()), read(sides[1].0.clone()));
+    let (left, right) = match files {
+        Ok((Ok(left), Ok(right))) => (left, right),
+        Ok((Err(e), _) | (_, Err(e))) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to read skill files: {}", e))),
+            ));
+        }
+        Err(e) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(ErrorCode::InternalError, format!("Diff task failed: {}", e))),
+            ));
+        }
+    };
+
+    Ok(Json(diff::diff_skills((&sides[0].1, &left), (&sides[1].1, &right))))
+}
+
+// ============================================================================
+// POST /api/replace - Search and replace across skills
+// ============================================================================
+
+/// A search and replace, previewed unless `dry_run` is false.
+#[derive(Debug, Deserialize)]
+pub struct ReplaceRequest {
+    pub find: String,
+    #[serde(default)]
+    pub replace: String,
+    /// Treat `find` as a regex, with `$1` for groups in `replace`.
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// Skills to search; every writable skill when empty.
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Only search skills with one of these tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Glob of files to search, relative to each skill directory.
+    #[serde(default = "default_replace_files")]
+    pub files: String,
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+fn default_replace_files() -> String {
+    replace::DEFAULT_FILES.to_string()
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplacedFile {
+    pub skill: String,
+    pub path: String,
+    pub replacements: usize,
+    pub diff: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplaceResponse {
+    pub dry_run: bool,
+    /// Skills with at least one change.
+    pub skills: Vec<String>,
+    pub files: Vec<ReplacedFile>,
+    pub replacements: usize,
+}
+
+fn replace_error(e: ReplaceError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        ReplaceError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    (status, Json(ErrorResponse::new(e.code(), e.to_string())))
+}
+
+pub async fn replace_in_skills(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(req): Json<ReplaceRequest>,
+) -> Result<Json<ReplaceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let replacement = if req.regex {
+        replace::Replacement::regex(&req.find, &req.replace, req.case_insensitive)
+    } else {
+        replace::Replacement::literal(&req.find, &req.replace, req.case_insensitive)
+    }
+    .map_err(replace_error)?;
+
+    // Named skills must exist and be writable; otherwise read-only ones are left out
+    let mut selected = Vec::new();
+    if req.skills.is_empty() {
+        for meta in &state.indexer.get_skill_index().skills {
+            if meta.is_visible_to(caller.key()) && !state.indexer.is_read_only(&meta.name) {
+                selected.push(meta.clone());
+            }
+        }
+    } else {
+        for name in &req.skills {
+            validate_skill_name(&state, name)?;
+            ensure_visible(&state, &caller, name)?;
+            let meta = state
+                .indexer
+                .get_skill_meta(name)
+                .ok_or_else(|| skill_not_found(&state, name))?;
+            if !req.dry_run {
+                ensure_writable(&state, &meta.name)?;
+            }
+            if !selected.iter().any(|s: &SkillMeta| s.name == meta.name) {
+                selected.push(meta);
+            }
+        }
+    }
+    if !req.dry_run {
+        ensure_not_maintenance(&state)?;
+    }
+    let skills: Vec<(String, std::path::PathBuf)> = selected
+        .into_iter()
+        .filter(|meta| req.tags.is_empty() || meta.tags.iter().any(|t| req.tags.contains(t)))
+        .map(|meta| (meta.name.clone(), state.indexer.skill_dir(&meta.name)))
+        .collect();
+
+    let (edits, changed) = run_blocking(&state, move |state| {
+        // Hold the lock from read to rename so concurrent edits can't interleave
+        let _guard = state.edits.lock();
+        let edits = replace::plan(&skills, &replacement, &req.files).map_err(replace_error)?;
+        if let Some(edit) = edits.iter().find(|e| e.path == "SKILL.md" && e.new.len() > MAX_CONTENT_LENGTH) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(ErrorCode::InvalidRequest, format!(
+                    "{}: Content too long (max {} bytes)",
+                    edit.skill, MAX_CONTENT_LENGTH
+                ))),
+            ));
+        }
+        let mut changed: Vec<String> = Vec::new();
+        for edit in &edits {
+            if !changed.contains(&edit.skill) {
+                changed.push(edit.skill.clone());
+            }
+        }
+        if !req.dry_run {
+            // One intent for the batch, which is applied all or nothing
+            state.begin_mutation(&AuditEntry::new(&caller.id, AuditAction::Update, ""));
+            replace::apply(&edits).map_err(replace_error)?;
+            for skill in &changed {
+                let skill_edits: Vec<_> = edits.iter().filter(|e| &e.skill == skill).collect();
+                state.record_mutation(
+                    AuditEntry::new(&caller.id, AuditAction::Update, skill).with_summary(format!(
+                        "replace: {} matches in {} files",
+                        skill_edits.iter().map(|e| e.replacements).sum::<usize>(),
+                        skill_edits.len()
+                    )),
+                );
+            }
+        }
+        Ok((edits, changed))
+    })
+    .await?;
+
+    if !req.dry_run {
+        for skill in &changed {
+            let _ = state.indexer.update_skill_async(skill).await;
+        }
+    }
+
+    Ok(Json(ReplaceResponse {
+        dry_run: req.dry_run,
+        skills: changed,
+        replacements: edits.iter().map(|e| e.replacements).sum(),
+        files: edits
+            .into_iter()
+            .map(|e| ReplacedFile {
+                diff: diff::unified_diff(&e.old, &e.new),
+                skill: e.skill,
+                path: e.path,
+                replacements: e.replacements,
+            })
+            .collect(),
+    }))
+}
+
+// ============================================================================
+// GET /api/integrity - Startup integrity verification report
+// ============================================================================
+
+pub async fn get_integrity(
+    State(state): State<AppState>,
+) -> Result<Json<IntegrityReport>, (StatusCode, Json<ErrorResponse>)> {
+    state.integrity.read().clone().map(Json).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                ErrorCode::NotFound,
+                "No integrity manifest was verified".to_string(),
+            )),
+        )
+    })
+}
+
+// ============================================================================
+// GET /api/analytics/tokens - Tokens of skill content served
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct TokenAnalyticsQuery {
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+pub async fn get_token_analytics(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<TokenAnalyticsQuery>,
+) -> Json<TokenSummary> {
+    Json(state.tokens.read().summary(query.limit.clamp(1, MAX_SEARCH_LIMIT)))
+}
+
+pub async fn get_token_sessions(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<TokenAnalyticsQuery>,
+) -> Json<Vec<SessionTokens>> {
+    Json(state.tokens.read().sessions(query.limit.clamp(1, MAX_SEARCH_LIMIT)))
+}
+
+// ============================================================================
+// GET /api/audit - Audit log of skill mutations
+// ============================================================================
+
+pub async fn get_audit_log(
+    State(state): State<AppState>,
+    caller: Caller,
+    axum::extract::Query(mut query): axum::extract::Query<AuditQuery>,
+) -> Result<Json<Vec<AuditEntry>>, (StatusCode, Json<ErrorResponse>)> {
+    query.limit = Some(query.limit.unwrap_or(100).clamp(1, 1000));
+    // Other callers' keys can't be looked up, masked or not
+    if query.actor.as_deref().is_some_and(|actor| shown_actor(&caller, actor.to_string()) != actor) {
+        return Ok(Json(Vec::new()));
+    }
+    let entries = state
+        .audit
+        .query_where(&query, |entry| can_see(&state, &caller, &entry.skill))
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(ErrorCode::IoError, format!("Failed to read audit log: {}", e))),
+            )
+        })?;
+    Ok(Json(
+        entries
+            .into_iter()
+            .map(|mut entry| {
+                entry.actor = shown_actor(&caller, entry.actor);
+                entry
+            })
+            .collect(),
+    ))
+}
+
+// ============================================================================
+// GET /api/stats - Usage analytics
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct StatsResponse {
+    #[serde(flatten)]
+    pub analytics: AnalyticsReport,
+    pub content_cache: ContentCacheStats,
+    pub search_cache: SearchCacheStats,
+}
+
+pub async fn get_stats(
+    State(state): State<AppState>,
+    caller: Caller,
+    axum::extract::Query(query): axum::extract::Query<TokenAnalyticsQuery>,
+) -> Json<StatsResponse> {
+    let index = state.indexer.get_skill_index();
+    let mut analytics = state
+        .analytics
+        .report(&index, query.limit.clamp(1, MAX_SEARCH_LIMIT));
+    analytics.skills.retain(|s| can_see(&state, &caller, &s.skill));
+    analytics.never_read.retain(|name| can_see(&state, &caller, name));
+    Json(StatsResponse {
+        analytics,
+        content_cache: state.indexer.content_cache().stats(),
+        search_cache: state.search.cache_stats(),
+    })
+}
+
+// ============================================================================
+// GET /api/validate - Validation and lint results
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+pub struct ValidateResponse {
+    #[serde(flatten)]
+    pub validation: ValidationResult,
+    pub lint: LintReport,
+}
+
+pub async fn validate(State(state): State<AppState>) -> Json<ValidateResponse> {
+    let lint = lint::lint_skills(&state.indexer, &state.lint.read());
+    Json(ValidateResponse {
+        validation: validate_skills(Arc::clone(&state.indexer)),
+        lint,
+    })
+}
+
+// ============================================================================
+// GET /api/analyze/storage - Duplicate files in the content index
+// ============================================================================
+
+pub async fn get_storage_analysis(State(state): State<AppState>) -> Json<StorageReport> {
+    Json(state.indexer.get_content_index().storage_report())
+}
+
+// ============================================================================
+// GET /api/report/quality - Skill quality scores
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct QualityReportQuery {
+    /// Only list this many of the lowest-scoring skills.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+pub async fn get_quality_report(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<QualityReportQuery>,
+) -> Result<Json<QualityReport>, (StatusCode, Json<ErrorResponse>)> {
+    let config = state.quality.read().clone();
+    let indexer = Arc::clone(&state.indexer);
+    // Lists every skill's references, so keep it off the async workers
+    let mut report = tokio::task::spawn_blocking(move || analysis::quality_report(&indexer, &config, Utc::now()))
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(ErrorCode::InternalError, format!("Quality report failed: {}", e))),
+            )
+        })?;
+    if let Some(limit) = query.limit {
+        report.skills.truncate(limit);
+    }
+    Ok(Json(report))
+}
+
+// ============================================================================
+// GET /api/report/deprecations - Deprecated skills and references to them
+// ============================================================================
+
+pub async fn get_deprecation_report(
+    State(state): State<AppState>,
+    caller: Caller,
+) -> Result<Json<DeprecationReport>, (StatusCode, Json<ErrorResponse>)> {
+    let indexer = Arc::clone(&state.indexer);
+    let key = caller.key().map(str::to_string);
+    // Builds the link graph from every skill's content
+    let report = tokio::task::spawn_blocking(move || {
+        let index = indexer.get_skill_index();
+        deprecation::deprecation_report(&indexer, |name| {
+            index.find(name).is_some_and(|s| s.is_visible_to(key.as_deref()))
+        })
+    })
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(ErrorCode::InternalError, format!("Deprecation report failed: {}", e))),
+        )
+    })?;
+    Ok(Json(report))
+}
+
+// ============================================================================
+// GET /api/reports/weekly - Weekly digest
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReportQuery {
+    /// Output format: markdown (default), html, or json.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+pub async fn get_weekly_report(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<ReportQuery>,
+) -> Result<axum::response::Response, (StatusCode, Json<ErrorResponse>)> {
+    let digest = Digest::weekly(&state);
+
+    let (content_type, body) = match query.format.as_deref().unwrap_or("markdown") {
+        "markdown" | "md" => ("text/markdown; charset=utf-8", digest.to_markdown()),
+        "html" => ("text/html; charset=utf-8", digest.to_html()),
+        "json" => return Ok(Json(digest).into_response()),
+        other => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(ErrorCode::InvalidRequest, format!(
+                    "Unknown report format '{}' (expected markdown, html, or json)",
+                    other
+                ))),
+            ));
+        }
+    };
+
+    Ok(([(axum::http::header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
+pub async fn send_weekly_report(State(state): State<AppState>) -> Json<Digest> {
+    let digest = Digest::weekly(&state);
+    digest.deliver(&state);
+    Json(digest)
+}
+
+// ============================================================================
+// Maintenance mode
+// ============================================================================
+
+/// Request body for POST /api/admin/maintenance.
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceRequest {
+    pub enabled: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+pub async fn get_maintenance(State(state): State<AppState>) -> Json<MaintenanceStatus> {
+    Json(state.maintenance.status())
+}
+
+pub async fn set_maintenance(
+    State(state): State<AppState>,
+    Json(req): Json<MaintenanceRequest>,
+) -> Json<MaintenanceStatus> {
+    if req.enabled {
+        let banner = state.maintenance.enable(req.message);
+        tracing::warn!("Maintenance mode enabled: {}", banner.message);
+    } else if state.maintenance.is_enabled() {
+        state.maintenance.disable();
+        tracing::info!("Maintenance mode disabled");
+    }
+
+    Json(state.maintenance.status())
+}
+
+// ============================================================================
+// Cold storage
+// ============================================================================
+
+/// Map a cold-storage error to an HTTP error response.
+fn cold_error(e: ColdError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        ColdError::NotFound(_) => StatusCode::NOT_FOUND,
+        ColdError::AlreadyCold(_) | ColdError::Conflict(_) => StatusCode::CONFLICT,
+        ColdError::Archive(_) | ColdError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ErrorResponse::new(e.code(), e.to_string())))
+}
+
+/// GET /api/cold - List skills in cold storage
+pub async fn list_cold_skills(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ColdSkill>>, (StatusCode, Json<ErrorResponse>)> {
+    let skills = run_blocking(&state, |state| {
+        let mut skills = Vec::new();
+        for root in state.indexer.roots() {
+            skills.extend(ColdStorage::new(&root.path).list().map_err(cold_error)?);
+        }
+        Ok(skills)
+    })
+    .await?;
+
+    Ok(Json(skills))
+}
+
+/// POST /api/skills/:name/freeze - Move a skill into cold storage
+pub async fn freeze_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<ColdSkill>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_writable(&state, &name)?;
+
+    let frozen = run_blocking(&state, {
+        let name = name.clone();
+        move |state| {
+            let _guard = state.edits.lock();
+            let entry = AuditEntry::new(&caller.id, AuditAction::Freeze, &name);
+            state.begin_mutation(&entry);
+            let frozen = ColdStorage::new(state.indexer.skill_root_dir(&name))
+                .freeze(&name)
+                .map_err(cold_error)?;
+            state.record_mutation(entry);
+            Ok(frozen)
+        }
+    })
+    .await?;
+
+    let _ = state.indexer.update_skill_async(&name).await;
+
+    Ok(Json(frozen))
+}
+
+/// POST /api/cold/:name/restore - Restore a skill from cold storage
+pub async fn restore_cold_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<SkillMeta>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(&state, &name)?;
+    ensure_not_maintenance(&state)?;
+
+    run_blocking(&state, {
+        let name = name.clone();
+        move |state| {
+            // Restore into the root whose cold storage holds the skill
+            let root = state
+                .indexer
+                .roots()
+                .into_iter()
+                .find(|root| ColdStorage::new(&root.path).contains(&name));
+            if root.as_ref().is_some_and(|root| root.read_only) {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    Json(ErrorResponse::new(ErrorCode::ReadOnly, format!(
+                        "Skill '{}' is in a read-only skills root",
+                        name
+                    ))),
+                ));
+            }
+            let skills_dir = root.map_or_else(|| state.indexer.skills_dir().to_path_buf(), |root| root.path);
+            let _guard = state.edits.lock();
+            let entry = AuditEntry::new(&caller.id, AuditAction::Restore, &name);
+            state.begin_mutation(&entry);
+            ColdStorage::new(&skills_dir).restore(&name).map_err(cold_error)?;
+            state.record_mutation_in(entry, &[skills_dir.join(&name)]);
+            Ok(())
+        }
+    })
+    .await?;
+
+    state.indexer.update_skill_async(&name).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(e.code(), format!("Failed to index restored skill: {}", e))),
+        )
+    })?;
+
+    state.indexer.get_skill_meta(&name).map(Json).ok_or_else(|| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(ErrorCode::InternalError, format!(
+                "Restored skill '{}' could not be indexed",
+                name
+            ))),
+        )
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ColdSweepRequest {
+    /// Days without reads or edits before a skill goes cold.
+    #[serde(default = "default_unused_days")]
+    pub unused_days: i64,
+    /// Only report candidates without moving anything.
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+fn default_unused_days() -> i64 {
+    ColdStorage::DEFAULT_UNUSED_DAYS
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColdSweepResponse {
+    pub dry_run: bool,
+    pub candidates: Vec<ColdCandidate>,
+    pub frozen: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// POST /api/cold/sweep - Apply the cold-storage policy
+pub async fn sweep_cold_skills(
+    State(state): State<AppState>,
+    caller: Caller,
+    Json(req): Json<ColdSweepRequest>,
+) -> Result<Json<ColdSweepResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if !req.dry_run {
+        ensure_not_maintenance(&state)?;
     }
 
-    // Clamp limit to valid range
-    let limit = query.limit.clamp(1, MAX_SEARCH_LIMIT);
+    let (candidates, frozen, errors) = run_blocking(&state, move |state| {
+        let cold = ColdStorage::new(state.indexer.skills_dir());
+        let candidates = cold.candidates(
+            &state.indexer.get_skill_index(),
+            &state.receipts.read(),
+            req.unused_days,
+        );
+
+        let mut frozen = Vec::new();
+        let mut errors = Vec::new();
+
+        if !req.dry_run {
+            let _guard = state.edits.lock();
+            for candidate in &candidates {
+                let entry =
+                    AuditEntry::new(&caller.id, AuditAction::Freeze, &candidate.name).with_summary("cold storage sweep");
+                state.begin_mutation(&entry);
+                match cold.freeze(&candidate.name) {
+                    Ok(_) => {
+                        state.record_mutation(entry);
+                        frozen.push(candidate.name.clone());
+                    }
+                    Err(e) => errors.push(format!("{}: {}", candidate.name, e)),
+                }
+            }
+        }
+        Ok((candidates, frozen, errors))
+    })
+    .await?;
+    if !frozen.is_empty() {
+        let _ = state.indexer.reload_async().await;
+    }
 
-    let options = SearchOptions::with_limit(limit);
-    let results = state.search.search_skills(&query.q, options);
+    Ok(Json(ColdSweepResponse {
+        dry_run: req.dry_run,
+        candidates,
+        frozen,
+        errors,
+    }))
+}
 
-    Ok(Json(results))
+// ============================================================================
+// POST /api/backup, GET /api/backups - Backups of the skills directory
+// ============================================================================
+
+fn backup_error(e: BackupError) -> (StatusCode, Json<ErrorResponse>) {
+    let status = match e {
+        BackupError::NotConfigured => StatusCode::FORBIDDEN,
+        BackupError::NotFound(_) => StatusCode::NOT_FOUND,
+        BackupError::InvalidId(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, Json(ErrorResponse::new(e.code(), e.to_string())))
+}
+
+/// Run backup work off the async runtime; archives can be large.
+async fn run_backups<T: Send + 'static>(
+    state: &AppState,
+    work: impl FnOnce(Backups, &ServiceContext) -> Result<T, BackupError> + Send + 'static,
+) -> Result<T, (StatusCode, Json<ErrorResponse>)> {
+    let state = Arc::clone(state);
+    tokio::task::spawn_blocking(move || {
+        let backups = Backups::from_config(state.indexer.skills_dir(), &state.backup.read())?;
+        work(backups, &state)
+    })
+    .await
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(ErrorCode::InternalError, format!("Backup task failed: {}", e))),
+        )
+    })?
+    .map_err(backup_error)
+}
+
+/// POST /api/backup - Archive the skills directory and index
+pub async fn create_backup(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<Backup>), (StatusCode, Json<ErrorResponse>)> {
+    let backup = run_backups(&state, |backups, state| backups.create(&state.indexer.get_skill_index())).await?;
+    Ok((StatusCode::CREATED, Json(backup)))
+}
+
+/// GET /api/backups - List backups, newest first
+pub async fn list_backups(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Backup>>, (StatusCode, Json<ErrorResponse>)> {
+    run_backups(&state, |backups, _| backups.list()).await.map(Json)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RestoreBackupRequest {
+    /// Restore for real; otherwise only report what would change.
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoreBackupResponse {
+    pub backup: String,
+    pub restored: bool,
+    #[serde(flatten)]
+    pub plan: RestorePlan,
+    /// Backup of the state before the restore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safety_backup: Option<Backup>,
+}
+
+/// POST /api/backups/:id/restore - Restore a backup once confirmed
+pub async fn restore_backup(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(id): Path<String>,
+    Json(req): Json<RestoreBackupRequest>,
+) -> Result<Json<RestoreBackupResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if !req.confirm {
+        let backup = id.clone();
+        let plan = run_backups(&state, move |backups, _| backups.plan_restore(&backup)).await?;
+        return Ok(Json(RestoreBackupResponse {
+            backup: id,
+            restored: false,
+            plan,
+            safety_backup: None,
+        }));
+    }
+    ensure_not_maintenance(&state)?;
+
+    let backup = id.clone();
+    let (plan, safety) = run_backups(&state, move |backups, state| {
+        let _edit = state.edits.lock();
+        let summary = format!("backup {}", backup);
+        state.begin_mutation(&AuditEntry::new(&caller.id, AuditAction::Restore, "").with_summary(&summary));
+        let (plan, safety) = backups.restore(&backup, &state.indexer.get_skill_index())?;
+        for name in plan.added.iter().chain(&plan.replaced).chain(&plan.removed) {
+            state.record_mutation(AuditEntry::new(&caller.id, AuditAction::Restore, name).with_summary(&summary));
+        }
+        Ok((plan, safety))
+    })
+    .await?;
+
+    let _ = state.indexer.reload_async().await;
+
+    Ok(Json(RestoreBackupResponse {
+        backup: id,
+        restored: true,
+        plan,
+        safety_backup: Some(safety),
+    }))
+}
+
+// ============================================================================
+// POST /api/skills/:name/archive, /unarchive - Hide skills without deleting
+// ============================================================================
+
+/// POST /api/skills/:name/archive - Hide a skill from listings and search
+pub async fn archive_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<SkillMeta>, (StatusCode, Json<ErrorResponse>)> {
+    set_archived(&state, &caller, &name, true).await
+}
+
+/// POST /api/skills/:name/unarchive - Make an archived skill visible again
+pub async fn unarchive_skill(
+    State(state): State<AppState>,
+    caller: Caller,
+    Path(name): Path<String>,
+) -> Result<Json<SkillMeta>, (StatusCode, Json<ErrorResponse>)> {
+    set_archived(&state, &caller, &name, false).await
+}
+
+async fn set_archived(
+    state: &AppState,
+    caller: &Caller,
+    name: &str,
+    archived: bool,
+) -> Result<Json<SkillMeta>, (StatusCode, Json<ErrorResponse>)> {
+    validate_skill_name(state, name)?;
+    // Aliases act on the skill's real name
+    let name = state
+        .indexer
+        .get_skill_meta(name)
+        .map(|meta| meta.name)
+        .ok_or_else(|| skill_not_found(state, name))?;
+    ensure_writable(state, &name)?;
+
+    let action = if archived {
+        AuditAction::Archive
+    } else {
+        AuditAction::Unarchive
+    };
+    let changed = run_blocking(state, {
+        let (name, actor) = (name.clone(), caller.id.clone());
+        move |state| {
+            let _guard = state.edits.lock();
+            let entry = AuditEntry::new(&actor, action, &name);
+            state.begin_mutation(&entry);
+            let changed = lifecycle::set_archived(&state.indexer.skill_dir(&name), archived).map_err(lifecycle_error)?;
+            if changed {
+                state.record_mutation(entry);
+            }
+            Ok(changed)
+        }
+    })
+    .await?;
+    if changed {
+        state.indexer.update_skill_async(&name).await.map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(e.code(), format!("Failed to reindex skill: {}", e))),
+            )
+        })?;
+    }
+
+    state
+        .indexer
+        .get_skill_meta(&name)
+        .map(Json)
+        .ok_or_else(|| skill_not_found(state, &name))
+}
+
+// ============================================================================
+// GET /api/schema/meta - JSON Schema for _meta.json
+// ============================================================================
+
+/// JSON Schema that `_meta.json` files are validated against.
+pub async fn get_meta_schema() -> Json<serde_json::Value> {
+    Json(meta_schema().clone())
+}
+
+// ============================================================================
+// GET /api/openapi.json, GET /api/docs - API description
+// ============================================================================
+
+/// OpenAPI description of this API.
+pub async fn get_openapi() -> Json<serde_json::Value> {
+    Json(openapi::spec())
+}
+
+/// Swagger UI for the OpenAPI description.
+pub async fn get_docs() -> axum::response::Html<&'static str> {
+    axum::response::Html(openapi::DOCS_HTML)
 }
@@ -8,33 +8,82 @@
 //!
 //! For development or single-instance deployments, the Python API server
 //! includes built-in rate limiting (100 req/s per IP with burst of 200).
+//!
+//! Separately, skill reads are monitored per peer address (or verified
+//! tenant key) for hammering and enumeration patterns (see
+//! [`crate::abuse`]); behind a reverse proxy every client shares the
+//! proxy's address. Alerts go to the registered
+//! notifiers; throttling is opt-in via [`ApiServer::with_abuse_config`].
+//!
+//! # Versions
+//!
+//! Every route is served under `/api/v2` and, unversioned, under `/api`.
+//! `/api/v1` serves the same routes with the Flask API's request bodies
+//! (see `legacy.rs`), for automation written against it.
+//!
+//! # Probes
+//!
+//! `GET /healthz` answers whenever the process is serving requests.
+//! `GET /readyz` returns 503 until the first index build has completed and
+//! while the skills directory can't be read, and reports the index age,
+//! in-memory index sizes, and watcher state either way.
+//!
+//! # Tenants
+//!
+//! With a `[tenants]` root configured, requests with an API key that has a
+//! tenant directory are served from that tenant's own library (see
+//! `tenants.rs`); everything else uses the primary skills directory.
 
+use std::future::IntoFuture;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
-    routing::{delete, get, post, put},
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderName, HeaderValue, Method},
+    middleware::{self, Next},
+    response::Response,
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
-use tracing::info;
+use tokio::time::Instant;
+use tracing::{info, warn};
 
-use crate::index::SkillIndexer;
-use crate::mcp::tools::ServiceContext;
+use crate::abuse::AbuseConfig;
+use crate::backup::Backups;
+use crate::config::Config;
+use crate::index::{MetaPrecedence, SkillIndexer, SkillRoot};
+use crate::models::FallbackConfig;
+use crate::integrity::{IntegrityError, IntegrityReport, IntegrityStatus, Manifest};
+use crate::notify::{Alert, Severity};
+use crate::report::Digest;
+use crate::mcp::{self, http::McpSessions, tools::ServiceContext};
 
+use super::legacy;
+use super::request_id::{assign_request_id, request_span, REQUEST_ID_HEADER};
 use super::routes::{self, AppState};
+use super::tenants::{route_tenant, Tenants};
 
 /// HTTP API Server.
 pub struct ApiServer {
     state: AppState,
+    mcp_sessions: Arc<McpSessions>,
+    tenants: Option<Arc<Tenants>>,
     port: u16,
+    drain_timeout: Duration,
 }
 
 impl ApiServer {
     /// Default port for the API server.
     pub const DEFAULT_PORT: u16 = 5050;
 
+    /// Default time in-flight requests get to finish after a shutdown signal.
+    pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
     /// Create a new API server.
     pub fn new(skills_dir: impl AsRef<std::path::Path>) -> Self {
         Self::with_port(skills_dir, Self::DEFAULT_PORT)
@@ -52,7 +101,180 @@ impl ApiServer {
         let ctx = ServiceContext::new(indexer);
         let state = Arc::new(ctx);
 
-        Self { state, port }
+        Self {
+            state,
+            mcp_sessions: Arc::new(McpSessions::default()),
+            tenants: None,
+            port,
+            drain_timeout: Self::DEFAULT_DRAIN_TIMEOUT,
+        }
+    }
+
+    /// Set how long in-flight requests and a running reload get to finish
+    /// after a shutdown signal before the server stops anyway.
+    pub fn with_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    /// Enable or disable exposing read receipts via `/api/skills/:name/readers`.
+    pub fn with_read_receipts(self, enabled: bool) -> Self {
+        self.state.receipts.write().set_enabled(enabled);
+        self
+    }
+
+    /// Choose whether `_meta.json` or SKILL.md frontmatter wins when a skill
+    /// has both, and reload the index with that setting.
+    pub fn with_meta_precedence(self, precedence: MetaPrecedence) -> Self {
+        if self.state.indexer.meta_precedence() != precedence {
+            self.state.indexer.set_meta_precedence(precedence);
+            if let Err(e) = self.state.indexer.reload() {
+                tracing::error!("Failed to reload index: {}", e);
+            }
+        }
+        self
+    }
+
+    /// Add lower-priority skills roots after the primary skills directory,
+    /// and reload the index to include them.
+    pub fn with_extra_roots(self, roots: Vec<SkillRoot>) -> Self {
+        if roots.is_empty() {
+            return self;
+        }
+        for root in roots {
+            self.state.indexer.add_root(root);
+        }
+        if let Err(e) = self.state.indexer.reload() {
+            tracing::error!("Failed to reload index: {}", e);
+        }
+        self
+    }
+
+    /// Start in maintenance mode with the given message.
+    pub fn with_maintenance(self, message: Option<String>) -> Self {
+        self.state.maintenance.enable(message);
+        self
+    }
+
+    /// Serve `config.skill` with zero-result diagnostics when a search has
+    /// no match scoring at least `config.min_score`.
+    pub fn with_fallback_skill(self, config: FallbackConfig) -> Self {
+        self.state.search.set_fallback(Some(config));
+        self
+    }
+
+    /// Apply settings from a `skills-mcp.toml` configuration, reloading the
+    /// index if the indexing settings changed, and serve tenant libraries if
+    /// a tenants root is set.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.tenants = Tenants::new(&config).map(Arc::new);
+        if self.state.apply_config(config) {
+            if let Err(e) = self.state.indexer.reload() {
+                tracing::error!("Failed to reload index: {}", e);
+            }
+        }
+        self
+    }
+
+    /// Append the audit log of skill mutations to the JSON Lines file at
+    /// `path` instead of keeping recent entries in memory.
+    pub fn with_audit_log(self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.state.audit.write_to(path);
+        self
+    }
+
+    /// Journal the files changed by every mutation to the JSON Lines file at
+    /// `path`, for replay with `skills-mcp replay`.
+    pub fn with_journal(self, path: impl Into<std::path::PathBuf>) -> Self {
+        if let Err(e) = self.state.journal.write_to(path, self.state.indexer.skills_dir()) {
+            tracing::error!("Failed to start journal: {}", e);
+        }
+        self
+    }
+
+    /// Keep usage analytics in a JSON store at `path` so they survive
+    /// restarts. Counts already in the store are loaded.
+    pub fn with_analytics_store(self, path: impl Into<std::path::PathBuf>) -> Self {
+        if let Err(e) = self.state.analytics.persist_to(path) {
+            tracing::warn!("Failed to load analytics store: {}", e);
+        }
+        self
+    }
+
+    /// Set abuse-detection thresholds and whether flagged callers are throttled.
+    pub fn with_abuse_config(self, config: AbuseConfig) -> Self {
+        self.state.abuse.configure(config);
+        self
+    }
+
+    /// Verify the skills directory against its integrity manifest.
+    ///
+    /// Returns None when no manifest is present. The report is kept in the
+    /// application state, served at `/api/integrity`, and a tampered result
+    /// adds an `X-Skills-Integrity: tampered` header to every response.
+    pub fn verify_integrity(&self) -> Result<Option<IntegrityReport>, IntegrityError> {
+        let skills_dir = self.state.indexer.skills_dir();
+        let Some(manifest) = Manifest::load(skills_dir)? else {
+            return Ok(None);
+        };
+
+        let report = manifest.verify(skills_dir)?;
+        if !report.is_verified() {
+            self.state.notifiers.send(&Alert::new(
+                "integrity.tampered",
+                Severity::Critical,
+                format!(
+                    "Skills directory differs from manifest: {} modified, {} missing, {} unexpected",
+                    report.modified.len(),
+                    report.missing.len(),
+                    report.unexpected.len()
+                ),
+            ));
+        }
+
+        *self.state.integrity.write() = Some(report.clone());
+        Ok(Some(report))
+    }
+
+    /// Deliver the weekly digest through the notification adapters every
+    /// seven days, starting one week from now.
+    pub fn spawn_weekly_digest(&self) -> tokio::task::JoinHandle<()> {
+        let state = Arc::clone(&self.state);
+        tokio::spawn(async move {
+            let period = std::time::Duration::from_secs(7 * 24 * 60 * 60);
+            let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                interval.tick().await;
+                Digest::weekly(&state).deliver(&state);
+            }
+        })
+    }
+
+    /// Take backups on the `[backup]` schedule, or return None when no
+    /// schedule is configured.
+    pub fn spawn_scheduled_backups(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let schedule = self.state.backup.read().schedule.clone()?;
+        let state = Arc::clone(&self.state);
+        info!("Backups scheduled at '{}' (UTC)", schedule);
+        Some(tokio::spawn(async move {
+            while let Some(next) = schedule.next_after(chrono::Utc::now()) {
+                let wait = (next - chrono::Utc::now()).to_std().unwrap_or_default();
+                tokio::time::sleep(wait).await;
+
+                let state = Arc::clone(&state);
+                let result = tokio::task::spawn_blocking(move || {
+                    let config = state.backup.read().clone();
+                    Backups::from_config(state.indexer.skills_dir(), &config)?
+                        .create(&state.indexer.get_skill_index())
+                })
+                .await;
+                match result {
+                    Ok(Ok(backup)) => info!("Scheduled backup {} written", backup.id),
+                    Ok(Err(e)) => warn!("Scheduled backup failed: {}", e),
+                    Err(e) => warn!("Scheduled backup panicked: {}", e),
+                }
+            }
+        }))
     }
 
     /// Get the application state.
@@ -66,23 +288,17 @@ impl ApiServer {
         let cors = CorsLayer::new()
             .allow_origin(Any)
             .allow_methods(Any)
-            .allow_headers(Any);
-
-        // API routes
-        let api_routes = Router::new()
-            .route("/skills", get(routes::list_skills))
-            .route("/skills", post(routes::create_skill))
-            .route("/skills/:name", get(routes::get_skill))
-            .route("/skills/:name", put(routes::update_skill))
-            .route("/skills/:name", delete(routes::delete_skill))
-            .route("/reload", post(routes::reload_index))
-            .route("/search", get(routes::search_skills));
-
-        Router::new()
-            .nest("/api", api_routes)
+            .allow_headers(Any)
+            .expose_headers([HeaderName::from_static(REQUEST_ID_HEADER)]);
+
+        let mut router = routes(&self.state, &self.mcp_sessions);
+        if let Some(tenants) = &self.tenants {
+            router = router.layer(middleware::from_fn_with_state(Arc::clone(tenants), route_tenant));
+        }
+        router
             .layer(cors)
-            .layer(TraceLayer::new_for_http())
-            .with_state(Arc::clone(&self.state))
+            .layer(TraceLayer::new_for_http().make_span_with(request_span))
+            .layer(middleware::from_fn(assign_request_id))
     }
 
     /// Start the server.
@@ -96,7 +312,7 @@ impl ApiServer {
             .await
             .map_err(|e| ApiError::Bind(e.to_string()))?;
 
-        axum::serve(listener, app)
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
             .await
             .map_err(|e| ApiError::Serve(e.to_string()))?;
 
@@ -104,8 +320,11 @@ impl ApiServer {
     }
 
     /// Start the server with graceful shutdown.
+    ///
+    /// Once `shutdown` completes the server stops accepting connections and
+    /// waits up to the drain timeout for in-flight requests and a running
+    /// reload, then flushes the audit log and analytics store.
     pub async fn run_with_shutdown(&self, shutdown: impl std::future::Future<Output = ()> + Send + 'static) -> Result<(), ApiError> {
-        let app = self.router();
         let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
 
         info!("Starting API server on http://{}", addr);
@@ -113,20 +332,223 @@ impl ApiServer {
         let listener = tokio::net::TcpListener::bind(addr)
             .await
             .map_err(|e| ApiError::Bind(e.to_string()))?;
+        self.serve_until(listener, shutdown).await
+    }
 
-        // Run server with graceful shutdown using tokio::select
-        tokio::select! {
-            result = axum::serve(listener, app) => {
+    async fn serve_until(
+        &self,
+        listener: tokio::net::TcpListener,
+        shutdown: impl std::future::Future<Output = ()> + Send + 'static,
+    ) -> Result<(), ApiError> {
+        let (signalled_tx, signalled_rx) = tokio::sync::oneshot::channel();
+        let app = self.router().into_make_service_with_connect_info::<SocketAddr>();
+        let serve = axum::serve(listener, app).with_graceful_shutdown(async move {
+            shutdown.await;
+            info!("Shutdown signal received, draining in-flight requests");
+            let _ = signalled_tx.send(Instant::now());
+        });
+
+        let drain_timeout = self.drain_timeout;
+        let deadline = async move {
+            match signalled_rx.await {
+                Ok(signalled) => tokio::time::sleep_until(signalled + drain_timeout).await,
+                Err(_) => std::future::pending().await,
+            }
+        };
+        let deadline = tokio::select! {
+            result = serve.into_future() => {
                 result.map_err(|e| ApiError::Serve(e.to_string()))?;
+                Instant::now() + drain_timeout
             }
-            _ = shutdown => {
-                info!("Shutdown signal received");
+            _ = deadline => {
+                warn!("Requests still in flight after {:?}; shutting down anyway", drain_timeout);
+                Instant::now()
+            }
+        };
+
+        // A reload only swaps the index at the end, but let it finish
+        // rather than abandon it halfway
+        if let Some(job) = self.state.reloads.running() {
+            info!("Waiting for reload {} to finish", job.id());
+            if tokio::time::timeout_at(deadline, job.wait()).await.is_err() {
+                warn!("Reload {} still running at shutdown", job.id());
             }
         }
+        self.flush();
 
         info!("API server shut down");
         Ok(())
     }
+
+    /// Write state kept in memory or OS buffers to disk.
+    fn flush(&self) {
+        if let Err(e) = self.state.audit.sync() {
+            warn!("Failed to flush audit log: {}", e);
+        }
+        if let Err(e) = self.state.analytics.flush() {
+            warn!("Failed to write analytics store: {}", e);
+        }
+        if let Some(tenants) = &self.tenants {
+            tenants.flush();
+        }
+    }
+}
+
+/// The routes of one skills library, with its MCP endpoint and response
+/// headers.
+pub(super) fn routes(state: &AppState, mcp_sessions: &Arc<McpSessions>) -> Router {
+    // API routes
+    let api_routes = Router::new()
+        .route("/skills", get(routes::list_skills))
+        .route(
+            "/skills/import-claude-skill",
+            post(routes::import_claude_skill),
+        )
+        .route("/skills/import-from-url", post(routes::import_from_url))
+        .route("/import/conflicts", get(routes::list_import_conflicts))
+        .route("/import/conflicts/:name/resolve", post(routes::resolve_import_conflict))
+        .route("/skills/:name", get(routes::get_skill))
+        .route("/skills/:name", patch(routes::patch_skill))
+        .route("/skills/:name", delete(routes::delete_skill))
+        .route("/skills/:name/rename", post(routes::rename_skill))
+        .route("/skills/:name/duplicate", post(routes::duplicate_skill))
+        .route("/skills/:name/readers", get(routes::get_skill_readers))
+        .route(
+            "/skills/:name/dependencies",
+            get(routes::get_skill_dependencies),
+        )
+        .route("/skills/:name/html", get(routes::get_skill_html))
+        .route("/skills/:name/tables", get(routes::get_skill_tables))
+        .route("/skills/:name/outline", get(routes::get_skill_outline))
+        .route("/skills/:name/examples", get(routes::get_skill_examples))
+        .route("/skills/:name/test-triggers", post(routes::test_skill_triggers))
+        .route("/skills/:name/stats", get(routes::get_skill_stats))
+        .route("/skills/:name/scripts", get(routes::list_skill_scripts))
+        .route("/skills/:name/links", get(routes::get_skill_links))
+        .route("/skills/:name/links", put(routes::update_skill_links))
+        .route("/skills/:name/backlinks", get(routes::get_skill_backlinks))
+        .route("/skills/:name/references", get(routes::list_skill_references))
+        .route("/skills/:name/references/*path", get(routes::get_skill_reference))
+        .route("/skills/:name/freeze", post(routes::freeze_skill))
+        .route("/skills/:name/archive", post(routes::archive_skill))
+        .route("/skills/:name/unarchive", post(routes::unarchive_skill))
+        .route("/collections", get(routes::list_collections))
+        .route("/tags", get(routes::list_tags))
+        .route("/tags/rename", post(routes::rename_tag))
+        .route("/bundles", get(routes::list_bundles))
+        .route("/bundles/:name", get(routes::get_bundle))
+        .route("/cold", get(routes::list_cold_skills))
+        .route("/cold/sweep", post(routes::sweep_cold_skills))
+        .route("/cold/:name/restore", post(routes::restore_cold_skill))
+        .route("/backup", post(routes::create_backup))
+        .route("/backups", get(routes::list_backups))
+        .route("/backups/:id/restore", post(routes::restore_backup))
+        .route("/reload", post(routes::reload_index))
+        .route("/reload/:job_id", get(routes::get_reload_job))
+        .route("/watch/status", get(routes::get_watch_status))
+        .route("/events", get(routes::stream_events))
+        .route("/search", get(routes::search_skills))
+        .route("/search/fusion", post(routes::search_fusion))
+        .route("/match/explain", post(routes::explain_match))
+        .route("/diff", get(routes::diff_skills))
+        .route("/replace", post(routes::replace_in_skills))
+        .route("/integrity", get(routes::get_integrity))
+        .route("/stats", get(routes::get_stats))
+        .route("/validate", get(routes::validate))
+        .route("/analyze/storage", get(routes::get_storage_analysis))
+        .route("/audit", get(routes::get_audit_log))
+        .route("/analytics/tokens", get(routes::get_token_analytics))
+        .route("/analytics/tokens/sessions", get(routes::get_token_sessions))
+        .route("/report/quality", get(routes::get_quality_report))
+        .route("/report/deprecations", get(routes::get_deprecation_report))
+        .route("/reports/weekly", get(routes::get_weekly_report))
+        .route("/reports/weekly/send", post(routes::send_weekly_report))
+        .route("/admin/maintenance", get(routes::get_maintenance))
+        .route("/admin/maintenance", post(routes::set_maintenance))
+        .route("/schema/meta", get(routes::get_meta_schema))
+        .route("/openapi.json", get(routes::get_openapi))
+        .route("/docs", get(routes::get_docs));
+    // GraphQL serves its own schema, so it isn't in the OpenAPI document
+    #[cfg(feature = "graphql")]
+    let api_routes = api_routes.route(
+        "/graphql",
+        get(super::graphql::graphql_get).post(super::graphql::graphql_post),
+    );
+
+    // Versions differ only in request bodies; v1 takes the Flask API's
+    let v1_routes = api_routes
+        .clone()
+        .route("/skills", post(legacy::create_skill))
+        .route("/skills/:name", put(legacy::update_skill))
+        .route("/import/folder", post(legacy::import_folder));
+    let api_routes = api_routes
+        .route("/skills", post(routes::create_skill))
+        .route("/skills/:name", put(routes::update_skill));
+
+    Router::new()
+        .nest("/api", api_routes.clone())
+        .nest("/api/v2", api_routes)
+        .nest("/api/v1", v1_routes)
+        .route("/healthz", get(routes::healthz))
+        .route("/readyz", get(routes::readyz))
+        .with_state(Arc::clone(state))
+        .merge(mcp::http::router(
+            Arc::clone(state),
+            Arc::clone(mcp_sessions),
+        ))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(state),
+            maintenance_banner,
+        ))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(state),
+            integrity_header,
+        ))
+}
+
+/// Flag every response while the skills directory is known to be tampered.
+async fn integrity_header(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let mut response = next.run(request).await;
+
+    let tampered = state
+        .integrity
+        .read()
+        .as_ref()
+        .is_some_and(|r| r.status == IntegrityStatus::Tampered);
+    if tampered {
+        response
+            .headers_mut()
+            .insert("x-skills-integrity", HeaderValue::from_static("tampered"));
+    }
+
+    response
+}
+
+/// Add the maintenance banner to successful JSON read responses.
+async fn maintenance_banner(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let is_read = request.method() == Method::GET;
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(b"application/json"));
+    if !is_read || !is_json || !response.status().is_success() || !state.maintenance.is_enabled() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(mut value) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    state.maintenance.annotate(&mut value);
+    let body = serde_json::to_vec(&value).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(body))
 }
 
 /// API server errors.
@@ -142,6 +564,8 @@ pub enum ApiError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::index::{PollConfig, PollingWatcher};
+    use crate::test_support::{meta, write_skill};
     use axum::body::Body;
     use axum::http::{Request, StatusCode};
     use std::fs;
@@ -152,14 +576,12 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         // Create a test skill
-        let skill_dir = temp_dir.path().join("test-skill");
-        fs::create_dir_all(&skill_dir).unwrap();
-        fs::write(
-            skill_dir.join("_meta.json"),
+        write_skill(
+            temp_dir.path(),
+            "test-skill",
             r#"{"name": "test-skill", "description": "A test skill", "tags": ["test"]}"#,
-        )
-        .unwrap();
-        fs::write(skill_dir.join("SKILL.md"), "# Test Skill\n\nContent.").unwrap();
+            "# Test Skill\n\nContent.",
+        );
 
         let server = ApiServer::new(temp_dir.path());
         let router = server.router();
@@ -167,6 +589,13 @@ mod tests {
         (temp_dir, router)
     }
 
+    async fn body_json(response: axum::response::Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
     #[tokio::test]
     async fn test_list_skills() {
         let (_temp, app) = create_test_server().await;
@@ -216,5 +645,2892 @@ mod tests {
             .unwrap();
 
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body_json(response).await["code"], "SKILL_NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_skill_readers_privacy_gated() {
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill/readers")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_skill_readers_records_caller() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "test-skill",
+            r#"{"name": "test-skill", "description": "A test skill"}"#,
+            "# Test Skill",
+        );
+
+        let app = ApiServer::new(temp_dir.path())
+            .with_read_receipts(true)
+            .router();
+
+        app.clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill")
+                    .header("x-session-id", "dashboard")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill/readers")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let readers = body_json(response).await;
+        assert_eq!(readers["total_reads"], 1);
+        assert_eq!(readers["readers"][0]["reader"], "session:dashboard");
+    }
+
+    #[tokio::test]
+    async fn test_freeze_and_search_cold() {
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills/test-skill/freeze")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/search?q=test")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["total_matches"], 0);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/search?q=test&include_cold=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let results = body_json(response).await;
+        assert_eq!(results["results"][0]["domain"], "test-skill");
+        assert_eq!(results["results"][0]["cold"], true);
+    }
+
+    #[tokio::test]
+    async fn test_search_fusion() {
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/search/fusion")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"task": "I want to write a test for my parser"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let results = body_json(response).await;
+        assert_eq!(results["results"][0]["domain"], "test-skill");
+        assert!(results["variants"].as_array().unwrap().len() > 1);
+    }
+
+    #[tokio::test]
+    async fn test_skill_dependencies() {
+        let (temp, app) = create_test_server().await;
+
+        write_skill(
+            temp.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Forms", "depends_on": ["test-skill"]}"#,
+            "# Forms",
+        );
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/reload?wait=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/forms/dependencies")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let deps = body_json(response).await;
+        assert_eq!(deps["direct"][0], "test-skill");
+        assert_eq!(deps["transitive"][0], "test-skill");
+    }
+
+    #[tokio::test]
+    async fn test_skill_backlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, content) in [
+            ("test-skill", "# Test Skill"),
+            ("forms", "See [the test skill](../test-skill/SKILL.md)."),
+        ] {
+            write_skill(temp_dir.path(), name, &meta(name, name), content);
+        }
+
+        let app = ApiServer::new(temp_dir.path()).router();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill/backlinks?file=SKILL.md")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = body_json(response).await;
+        assert_eq!(body["backlinks"][0]["source_skill"], "forms");
+        assert_eq!(body["backlinks"][0]["target_file"], "SKILL.md");
+    }
+
+    #[tokio::test]
+    async fn test_explain_match() {
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/match/explain")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"task": "write a test for checkout", "skill": "test-skill"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let explanation = body_json(response).await;
+        assert_eq!(explanation["matched"], true);
+        assert_eq!(explanation["hits"][0]["field"], "name");
+        assert!(explanation["missed_keywords"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("checkout")));
+    }
+
+    #[tokio::test]
+    async fn test_abuse_throttling() {
+        let (temp, _) = create_test_server().await;
+        let server = ApiServer::new(temp.path()).with_abuse_config(AbuseConfig {
+            max_reads_per_skill: 2,
+            throttle: true,
+            ..Default::default()
+        });
+        let peer = |ip: [u8; 4]| {
+            server
+                .router()
+                .layer(axum::extract::connect_info::MockConnectInfo(SocketAddr::from((ip, 4000))))
+        };
+        let read = |app: Router, key: String| {
+            app.oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill")
+                    .header("x-api-key", key)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        // A new key per request doesn't escape the peer's count
+        let scraper = peer([10, 0, 0, 1]);
+        let mut statuses = Vec::new();
+        for i in 0..3 {
+            let response = read(scraper.clone(), format!("scraper-{}", i)).await.unwrap();
+            statuses.push(response.status());
+        }
+        assert_eq!(
+            statuses,
+            vec![StatusCode::OK, StatusCode::OK, StatusCode::TOO_MANY_REQUESTS]
+        );
+
+        // Other peers are counted separately
+        let response = read(peer([10, 0, 0, 2]), "scraper-0".to_string()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_integrity_tampered_header() {
+        let (temp, _) = create_test_server().await;
+        Manifest::generate(temp.path())
+            .unwrap()
+            .save(temp.path())
+            .unwrap();
+        fs::write(temp.path().join("test-skill/SKILL.md"), "# Edited").unwrap();
+
+        let server = ApiServer::new(temp.path());
+        let report = server.verify_integrity().unwrap().unwrap();
+        assert_eq!(report.modified, vec!["test-skill/SKILL.md"]);
+
+        let response = server
+            .router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/integrity")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-skills-integrity"], "tampered");
+        assert_eq!(body_json(response).await["status"], "tampered");
+    }
+
+    #[tokio::test]
+    async fn test_rename_skill() {
+        let (temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills/test-skill/rename")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"new_name": "renamed-skill"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(temp.path().join("renamed-skill/SKILL.md").exists());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/renamed-skill")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_skill() {
+        let (_temp, app) = create_test_server().await;
+
+        let duplicate = |new_name: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/skills/test-skill/duplicate")
+                .header("content-type", "application/json")
+                .body(Body::from(format!(r#"{{"new_name": "{}"}}"#, new_name)))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(duplicate("test-copy")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = app.oneshot(duplicate("test-copy")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_get_skill_minified() {
+        let (temp, app) = create_test_server().await;
+        fs::write(
+            temp.path().join("test-skill/SKILL.md"),
+            "# Test Skill\n\n<!-- draft -->\n\n\nBody\n\n[Back to top](#test-skill)\n",
+        )
+        .unwrap();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill?minify=true")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["content"], "# Test Skill\n\nBody");
+    }
+
+    #[tokio::test]
+    async fn test_import_claude_skill() {
+        let (temp, app) = create_test_server().await;
+
+        let imports = TempDir::new().unwrap();
+        let source = imports.path().join("pdf");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(
+            source.join("SKILL.md"),
+            "---\nname: pdf\ndescription: PDF tools\n---\n# PDF\n",
+        )
+        .unwrap();
+        let import = |app: Router, path: &std::path::Path| {
+            let body = serde_json::json!({ "path": path }).to_string();
+            app.oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills/import-claude-skill")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+        };
+
+        // Folder imports need an import root
+        let response = import(app, &source).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let server = ApiServer::new(temp.path());
+        server.state().import.write().root = Some(imports.path().to_path_buf());
+        let app = server.router();
+        for outside in [temp.path().join("test-skill"), source.join("../../")] {
+            let response = import(app.clone(), &outside).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", outside.display());
+        }
+        let response = import(app.clone(), &source).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/pdf")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_read_only_root() {
+        let primary = TempDir::new().unwrap();
+        let team = TempDir::new().unwrap();
+        let skill_dir = team.path().join("team-skill");
+        write_skill(
+            team.path(),
+            "team-skill",
+            r#"{"name": "team-skill", "description": "Shared skill"}"#,
+            "# Team Skill",
+        );
+
+        let app = ApiServer::new(primary.path())
+            .with_extra_roots(vec![SkillRoot::read_only(team.path())])
+            .router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/team-skill")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/api/skills/team-skill")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(skill_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_in_extra_root() {
+        let primary = TempDir::new().unwrap();
+        let team = TempDir::new().unwrap();
+        write_skill(
+            team.path(),
+            "team-skill",
+            r#"{"name": "team-skill", "description": "Shared skill"}"#,
+            "# Team Skill",
+        );
+
+        let app = ApiServer::new(primary.path())
+            .with_extra_roots(vec![SkillRoot::new(team.path())])
+            .router();
+        let post = |uri: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(post("/api/skills/team-skill/duplicate", r#"{"new_name": "team-copy"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(team.path().join("team-copy/SKILL.md").exists());
+
+        let response = app
+            .clone()
+            .oneshot(post("/api/skills/team-skill/rename", r#"{"new_name": "team-renamed"}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(team.path().join("team-renamed/SKILL.md").exists());
+
+        let response = app.clone().oneshot(post("/api/skills/team-renamed/freeze", "")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!team.path().join("team-renamed").exists());
+        let cold = body_json(app.clone().oneshot(Request::builder().uri("/api/cold").body(Body::empty()).unwrap()).await.unwrap()).await;
+        assert_eq!(cold[0]["meta"]["name"], "team-renamed");
+
+        let response = app.oneshot(post("/api/cold/team-renamed/restore", "")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(team.path().join("team-renamed/SKILL.md").exists());
+        assert!(fs::read_dir(primary.path()).unwrap().next().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_mode() {
+        let (temp, app) = create_test_server().await;
+
+        let toggle = |body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/admin/maintenance")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(toggle(r#"{"enabled": true, "message": "Migrating skills"}"#))
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["enabled"], true);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/api/skills/test-skill")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body_json(response).await["error"], "Migrating skills");
+        assert!(temp.path().join("test-skill").exists());
+
+        let read = || {
+            Request::builder()
+                .uri("/api/skills/test-skill")
+                .body(Body::empty())
+                .unwrap()
+        };
+        let response = app.clone().oneshot(read()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["maintenance"]["message"], "Migrating skills");
+        assert_eq!(json["name"], "test-skill");
+
+        app.clone().oneshot(toggle(r#"{"enabled": false}"#)).await.unwrap();
+        let json = body_json(app.oneshot(read()).await.unwrap()).await;
+        assert!(json.get("maintenance").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_collections() {
+        let (temp, app) = create_test_server().await;
+        write_skill(temp.path(), "frontend/forms", r#"{"name": "forms", "description": "Frontend forms"}"#, "# Forms");
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        app.clone()
+            .oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let json = body_json(app.clone().oneshot(get("/api/collections")).await.unwrap()).await;
+        assert_eq!(json, serde_json::json!([{ "name": "frontend", "skills": ["frontend/forms"] }]));
+
+        let json = body_json(
+            app.clone()
+                .oneshot(get("/api/skills?collection=frontend"))
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(json.as_array().unwrap().len(), 1);
+        assert_eq!(json[0]["collection"], "frontend");
+
+        let response = app.oneshot(get("/api/skills/frontend%2Fforms")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["name"], "frontend/forms");
+    }
+
+    #[tokio::test]
+    async fn test_token_analytics() {
+        let (_temp, app) = create_test_server().await;
+
+        app.clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill")
+                    .header("x-session-id", "agent-1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let json = body_json(app.clone().oneshot(get("/api/analytics/tokens")).await.unwrap()).await;
+        // "# Test Skill\n\nContent." is 23 characters
+        assert_eq!(json["total_tokens"], 6);
+        assert_eq!(json["top_skills"][0]["skill"], "test-skill");
+        assert_eq!(json["days"][0]["reads"], 1);
+
+        let json = body_json(app.oneshot(get("/api/analytics/tokens/sessions")).await.unwrap()).await;
+        assert_eq!(json[0]["session"], "session:agent-1");
+        assert_eq!(json[0]["tokens"], 6);
+    }
+
+    #[tokio::test]
+    async fn test_weekly_report() {
+        let (_temp, app) = create_test_server().await;
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(get("/api/reports/weekly")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/markdown"));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("- test-skill"));
+
+        let response = app.clone().oneshot(get("/api/reports/weekly?format=json")).await.unwrap();
+        assert_eq!(body_json(response).await["new_skills"][0], "test-skill");
+
+        let response = app.oneshot(get("/api/reports/weekly?format=pdf")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_search_fallback_skill() {
+        let (temp, _app) = create_test_server().await;
+        let app = ApiServer::new(temp.path())
+            .with_fallback_skill(FallbackConfig::new("test-skill"))
+            .router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/search?q=kubernetes")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let results = body_json(response).await;
+        assert_eq!(results["results"].as_array().unwrap().len(), 0);
+        assert_eq!(results["fallback"]["skill"], "test-skill");
+        assert_eq!(results["fallback"]["diagnostics"]["unknown_keywords"][0], "kubernetes");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/search?q=test")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(body_json(response).await.get("fallback").is_none());
+    }
+
+    fn mcp_post(body: &str, session: Option<&str>) -> Request<Body> {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .header("accept", "application/json, text/event-stream");
+        if let Some(session) = session {
+            request = request.header(mcp::http::SESSION_HEADER, session);
+        }
+        request.body(Body::from(body.to_string())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mcp_streamable_http() {
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(mcp_post(
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"protocolVersion": "2025-03-26"}}"#,
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let session = response.headers()[mcp::http::SESSION_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let result = body_json(response).await;
+        assert_eq!(result["result"]["protocolVersion"], "2025-03-26");
+
+        // Notifications are accepted without a body
+        let response = app
+            .clone()
+            .oneshot(mcp_post(
+                r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#,
+                Some(&session),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let response = app
+            .clone()
+            .oneshot(mcp_post(
+                r#"[{"jsonrpc": "2.0", "id": 2, "method": "resources/list"}, {"jsonrpc": "2.0", "id": 3, "method": "ping"}]"#,
+                Some(&session),
+            ))
+            .await
+            .unwrap();
+        let results = body_json(response).await;
+        assert_eq!(results[0]["result"]["resources"][0]["uri"], "skill://test-skill");
+        assert_eq!(results[1]["id"], 3);
+
+        // Requests need a known session
+        let response = app
+            .clone()
+            .oneshot(mcp_post(r#"{"jsonrpc": "2.0", "id": 4, "method": "ping"}"#, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/mcp")
+                    .header(mcp::http::SESSION_HEADER, &session)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app
+            .oneshot(mcp_post(r#"{"jsonrpc": "2.0", "id": 5, "method": "ping"}"#, Some(&session)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_tools_over_http() {
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(mcp_post(r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#, None))
+            .await
+            .unwrap();
+        let session = response.headers()[mcp::http::SESSION_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(body_json(response).await["result"]["capabilities"]["tools"].is_object());
+
+        let response = app
+            .clone()
+            .oneshot(mcp_post(r#"{"jsonrpc": "2.0", "id": 2, "method": "tools/list"}"#, Some(&session)))
+            .await
+            .unwrap();
+        let tools = body_json(response).await["result"]["tools"].clone();
+        assert!(tools.as_array().unwrap().iter().any(|t| t["name"] == "get_skill"));
+
+        let response = app
+            .oneshot(mcp_post(
+                r#"{"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "get_quick_answer", "arguments": {"name": "test-skill"}}}"#,
+                Some(&session),
+            ))
+            .await
+            .unwrap();
+        let result = body_json(response).await["result"].clone();
+        assert_eq!(result["isError"], false);
+        let answer: serde_json::Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(answer["name"], "test-skill");
+    }
+
+    #[tokio::test]
+    async fn test_mcp_sse_fallback() {
+        use tokio_stream::StreamExt;
+
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/sse").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+
+        let mut events = response.into_body().into_data_stream();
+        let endpoint = String::from_utf8(events.next().await.unwrap().unwrap().to_vec()).unwrap();
+        assert!(endpoint.starts_with("event: endpoint\ndata: /messages?sessionId="));
+        let path = endpoint.lines().nth(1).unwrap().trim_start_matches("data: ").to_string();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(path)
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"jsonrpc": "2.0", "id": 1, "method": "ping"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let message = String::from_utf8(events.next().await.unwrap().unwrap().to_vec()).unwrap();
+        assert!(message.starts_with("event: message\ndata: "));
+        assert!(message.contains(r#""id":1"#));
+    }
+
+    #[tokio::test]
+    async fn test_skill_links() {
+        let (temp, _app) = create_test_server().await;
+
+        write_skill(temp.path(), "forms", r#"{"name": "forms", "description": "Forms"}"#, "# Forms");
+        let app = ApiServer::new(temp.path()).router();
+
+        let put_links = |body: &'static str| {
+            Request::builder()
+                .method("PUT")
+                .uri("/api/skills/forms/links")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(put_links(r#"{"see_also": ["test-skill"]}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let links = body_json(response).await;
+        assert_eq!(links["see_also"][0], "test-skill");
+        assert_eq!(links["related"][0]["reason"], "see_also");
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/skills/forms").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let skill = body_json(response).await;
+        assert_eq!(skill["related"][0]["name"], "test-skill");
+
+        for body in [r#"{"see_also": ["ghost"]}"#, r#"{"see_also": ["forms"]}"#] {
+            let response = app.clone().oneshot(put_links(body)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_stream() {
+        use tokio_stream::StreamExt;
+
+        let (temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/events").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/event-stream");
+        let mut events = response.into_body().into_data_stream();
+
+        write_skill(temp.path(), "forms", r#"{"name": "forms", "description": "Forms"}"#, "# Forms");
+        fs::remove_dir_all(temp.path().join("test-skill")).unwrap();
+
+        app.oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let mut received = Vec::new();
+        while received.len() < 3 {
+            let chunk = events.next().await.unwrap().unwrap();
+            received.push(String::from_utf8(chunk.to_vec()).unwrap());
+        }
+        assert!(received[0].starts_with("event: created\n"));
+        assert!(received[0].contains(r#""name":"forms""#));
+        assert!(received[1].starts_with("event: deleted\n"));
+        assert!(received[2].contains(r#""type":"index_reloaded","skills":1"#));
+    }
+
+    #[tokio::test]
+    async fn test_skill_tables() {
+        let (temp, _app) = create_test_server().await;
+        fs::write(
+            temp.path().join("test-skill/SKILL.md"),
+            "# Test Skill\n\n## Choosing\n\n| Case | Use |\n|---|---|\n| small | a |\n| large | b |\n",
+        )
+        .unwrap();
+        let app = ApiServer::new(temp.path()).router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill/tables")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let tables = body_json(response).await;
+        assert_eq!(tables["tables"][0]["file"], "SKILL.md");
+        assert_eq!(tables["tables"][0]["heading"], "Choosing");
+        assert_eq!(tables["tables"][0]["headers"], serde_json::json!(["Case", "Use"]));
+        assert_eq!(tables["tables"][0]["rows"][1], serde_json::json!(["large", "b"]));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/missing/tables")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_usage_stats() {
+        let (temp, _app) = create_test_server().await;
+        let store = temp.path().join(crate::analytics::STORE_FILE);
+        let server = ApiServer::new(temp.path()).with_analytics_store(&store);
+        let app = server.router();
+
+        for uri in [
+            "/api/skills/test-skill",
+            "/api/search?q=test",
+            "/api/search?q=Kubernetes",
+            "/api/search?q=kubernetes",
+        ] {
+            app.clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/stats")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let stats = body_json(response).await;
+        assert_eq!(stats["skills"][0]["skill"], "test-skill");
+        assert_eq!(stats["skills"][0]["reads"], 1);
+        assert_eq!(stats["total_searches"], 3);
+        assert_eq!(stats["top_queries"][0]["query"], "kubernetes");
+        assert_eq!(stats["zero_result_queries"][0]["zero_results"], 2);
+        assert_eq!(stats["never_read"], serde_json::json!([]));
+        // The skill read went to disk; nothing was read twice yet
+        assert_eq!(stats["content_cache"]["misses"], 1);
+        assert_eq!(stats["content_cache"]["hits"], 0);
+        assert_eq!(stats["search_cache"]["capacity"], 256);
+        assert!(stats["search_cache"]["misses"].as_u64().unwrap() > 0);
+
+        server.state().analytics.flush().unwrap();
+        assert!(store.exists());
+    }
+
+    #[tokio::test]
+    async fn test_search_language_filter() {
+        let (temp, _app) = create_test_server().await;
+        fs::write(
+            temp.path().join("test-skill/SKILL.md"),
+            "# Test Skill\n\nUse this test skill when you need to check that the server is working.",
+        )
+        .unwrap();
+        let app = ApiServer::new(temp.path()).router();
+
+        for (lang, expected) in [("en", 1), ("de", 0)] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/api/search?q=test&lang={}", lang))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(body_json(response).await["total_matches"], expected);
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["language"], "en");
+    }
+
+    #[tokio::test]
+    async fn test_audit_log() {
+        let (temp, _app) = create_test_server().await;
+        let app = ApiServer::new(temp.path())
+            .with_audit_log(temp.path().join(crate::audit::LOG_FILE))
+            .router();
+
+        let request = |method: &str, uri: &str, body: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header("x-api-key", "editor-key")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+        for req in [
+            request(
+                "POST",
+                "/api/skills",
+                r##"{"name": "forms", "description": "Forms", "content": "# Forms\n"}"##,
+            ),
+            request(
+                "PUT",
+                "/api/skills/forms",
+                r##"{"tags": ["input"], "content": "# Forms\n\nMore.\n"}"##,
+            ),
+            request("DELETE", "/api/skills/test-skill", ""),
+        ] {
+            let response = app.clone().oneshot(req).await.unwrap();
+            assert!(response.status().is_success());
+        }
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/audit?skill=forms")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let entries = body_json(response).await;
+        assert_eq!(entries.as_array().unwrap().len(), 2);
+        assert_eq!(entries[0]["action"], "update");
+        assert_eq!(entries[0]["summary"], "tags; SKILL.md +2 -0 lines");
+        // Written with a key, read without one: the fingerprint is masked
+        assert_eq!(entries[0]["actor"], "key");
+        assert_eq!(entries[1]["action"], "create");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/audit?since=2999-01-01T00:00:00Z")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await, serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_skill_timestamps() {
+        let (_temp, app) = create_test_server().await;
+        let is_utc_millis = |value: &serde_json::Value| {
+            let text = value.as_str().unwrap();
+            let (_, fraction) = text.rsplit_once('.').unwrap();
+            fraction.len() == 4 && fraction.ends_with('Z') && crate::models::timestamp::parse(text).is_ok()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/skills").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let skills = body_json(response).await;
+        assert!(is_utc_millis(&skills[0]["created_at"]));
+        assert!(is_utc_millis(&skills[0]["updated_at"]));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let skill = body_json(response).await;
+        assert!(is_utc_millis(&skill["created_at"]));
+        assert!(skill["created_at"].as_str() <= skill["updated_at"].as_str());
+    }
+
+    #[tokio::test]
+    async fn test_update_skill_base_hash() {
+        let (temp, app) = create_test_server().await;
+        let put = |body: serde_json::Value| {
+            Request::builder()
+                .method("PUT")
+                .uri("/api/skills/test-skill")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let stale = crate::patch::content_hash("# Older");
+        let response = app
+            .clone()
+            .oneshot(put(serde_json::json!({ "content": "# New", "base_sha256": stale })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(fs::read_to_string(temp.path().join("test-skill/SKILL.md")).unwrap(), "# Test Skill\n\nContent.");
+
+        let current = crate::patch::content_hash("# Test Skill\n\nContent.");
+        let response = app
+            .oneshot(put(serde_json::json!({ "content": "# New", "base_sha256": current })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(fs::read_to_string(temp.path().join("test-skill/SKILL.md")).unwrap(), "# New");
+    }
+
+    #[tokio::test]
+    async fn test_patch_skill() {
+        let (temp, app) = create_test_server().await;
+        fs::write(
+            temp.path().join("test-skill/SKILL.md"),
+            "# Test Skill\n\n## Usage\n\nContent.\n\n## Notes\n\nOld.\n",
+        )
+        .unwrap();
+        let patch = |body: String| {
+            Request::builder()
+                .method("PATCH")
+                .uri("/api/skills/test-skill")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(patch(
+                r#"{"operations": [{"op": "append", "heading": "Usage", "content": "More."}]}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let patched = body_json(response).await;
+        assert_eq!(
+            patched["content"],
+            "# Test Skill\n\n## Usage\n\nContent.\n\nMore.\n\n## Notes\n\nOld.\n"
+        );
+        let hash = patched["content_sha256"].as_str().unwrap().to_string();
+
+        let diff = crate::diff::unified_diff(
+            patched["content"].as_str().unwrap(),
+            "# Test Skill\n\n## Usage\n\nContent.\n\nMore.\n\n## Notes\n\nNew.\n",
+        );
+        let body = serde_json::json!({"diff": diff, "base_sha256": hash}).to_string();
+        let response = app.clone().oneshot(patch(body.clone())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(fs::read_to_string(temp.path().join("test-skill/SKILL.md"))
+            .unwrap()
+            .ends_with("New.\n"));
+
+        // Same patch again: the base hash is stale
+        let response = app.clone().oneshot(patch(body)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app
+            .clone()
+            .oneshot(patch(serde_json::json!({"diff": diff}).to_string()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app.oneshot(patch("{}".to_string())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_validate_with_lint() {
+        let (temp, _app) = create_test_server().await;
+        fs::write(
+            temp.path().join("test-skill/SKILL.md"),
+            "# Test Skill\n\nTODO: finish.\n",
+        )
+        .unwrap();
+        let config = Config::parse("[lint.rules]\nno-todo = \"error\"\n").unwrap();
+        let app = ApiServer::new(temp.path()).with_config(config).router();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/validate")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let result = body_json(response).await;
+        assert_eq!(result["valid"], true);
+        assert_eq!(result["skills_checked"], 1);
+        assert_eq!(result["lint"]["errors"], 1);
+        assert_eq!(result["lint"]["findings"][0]["rule"], "no-todo");
+        assert_eq!(result["lint"]["findings"][0]["severity"], "error");
+        assert_eq!(result["lint"]["findings"][0]["line"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_skill_stats() {
+        let (temp, _app) = create_test_server().await;
+        let skill_dir = temp.path().join("test-skill");
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "# Test Skill\n\n## Usage\n\n```bash\nrun it\n```\n",
+        )
+        .unwrap();
+        fs::create_dir_all(skill_dir.join("references")).unwrap();
+        fs::write(skill_dir.join("references/api.md"), "# API\n\nLong reference text here.\n").unwrap();
+        fs::write(skill_dir.join("references/data.bin"), vec![0u8; 4096]).unwrap();
+        let app = ApiServer::new(temp.path()).router();
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app
+            .clone()
+            .oneshot(get("/api/skills/test-skill/stats?max_tokens=10"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let stats = body_json(response).await;
+        assert_eq!(stats["files"][0]["file"], "SKILL.md");
+        assert_eq!(stats["files"][0]["headings"], 2);
+        assert_eq!(stats["files"][0]["code_blocks"], 1);
+        assert_eq!(stats["files"][1]["file"], "references/api.md");
+        assert_eq!(stats["total"]["headings"], 3);
+        assert_eq!(stats["references"], 2);
+        assert_eq!(stats["largest_files"][0]["file"], "references/data.bin");
+        assert_eq!(stats["largest_files"][0]["bytes"], 4096);
+        assert_eq!(stats["within_budget"], false);
+
+        let response = app.oneshot(get("/api/skills/missing/stats")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_skill_aliases_and_suggestions() {
+        let (temp, _app) = create_test_server().await;
+        fs::write(
+            temp.path().join("test-skill/_meta.json"),
+            r#"{"name": "test-skill", "description": "A test skill", "aliases": ["testing"]}"#,
+        )
+        .unwrap();
+        let app = ApiServer::new(temp.path()).router();
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(get("/api/skills/testing")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["name"], "test-skill");
+
+        let response = app.clone().oneshot(get("/api/skills/testing/stats")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["skill"], "test-skill");
+
+        let response = app.oneshot(get("/api/skills/test-skil")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let error = body_json(response).await;
+        assert_eq!(error["suggestions"], serde_json::json!(["test-skill"]));
+        assert!(error["error"].as_str().unwrap().contains("did you mean test-skill?"));
+    }
+
+    #[tokio::test]
+    async fn test_skill_references() {
+        let (temp, _app) = create_test_server().await;
+        let refs = temp.path().join("test-skill/references");
+        fs::create_dir_all(&refs).unwrap();
+        fs::write(refs.join("api.md"), "# API\n").unwrap();
+        fs::write(refs.join("logo.png"), b"\x89PNG\r\n\x1a\n\0\0").unwrap();
+        let app = ApiServer::new(temp.path()).router();
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app
+            .clone()
+            .oneshot(get("/api/skills/test-skill/references"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["references"][0]["path"], "api.md");
+        assert_eq!(json["references"][0]["indexed"], true);
+        assert_eq!(json["references"][1]["mime_type"], "image/png");
+        assert_eq!(json["references"][1]["size"], 10);
+        assert_eq!(json["references"][1]["skipped"], "extension");
+
+        let request = Request::builder()
+            .uri("/api/skills/test-skill/references/logo.png")
+            .header("range", "bytes=0-3")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()["content-range"], "bytes 0-3/10");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"\x89PNG");
+
+        let response = app
+            .oneshot(get("/api/skills/test-skill/references/..%2FSKILL.md"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_background_reload() {
+        let (_temp, app) = create_test_server().await;
+        let post = || Request::builder().method("POST").uri("/api/reload").body(Body::empty()).unwrap();
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(post()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let job_id = body_json(response).await["job_id"].as_str().unwrap().to_string();
+
+        let mut status = serde_json::Value::Null;
+        for _ in 0..200 {
+            let response = app.clone().oneshot(get(&format!("/api/reload/{}", job_id))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            status = body_json(response).await;
+            if status["state"] != "running" {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(status["state"], "completed");
+        assert_eq!(status["phase"], "done");
+        assert_eq!(status["skills_scanned"], 1);
+        assert_eq!(status["skill_count"], 1);
+
+        let response = app.oneshot(get("/api/reload/reload-404")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_openapi() {
+        let (_temp, app) = create_test_server().await;
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(get("/api/openapi.json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["openapi"], "3.0.3");
+        assert_eq!(
+            json["paths"]["/skills/{name}"]["delete"]["responses"]["204"]["description"],
+            "Success"
+        );
+
+        let response = app.oneshot(get("/api/docs")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/html"));
+    }
+
+    #[tokio::test]
+    async fn test_meta_schema() {
+        let (_temp, app) = create_test_server().await;
+
+        let request = Request::builder()
+            .uri("/api/schema/meta")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["required"], serde_json::json!(["name", "description"]));
+        assert_eq!(json["properties"]["quick_answer"]["maxLength"], 500);
+    }
+
+    #[tokio::test]
+    async fn test_watch_status() {
+        let (temp, app) = create_test_server().await;
+
+        let get = || Request::builder().uri("/api/watch/status").body(Body::empty()).unwrap();
+        let json = body_json(app.clone().oneshot(get()).await.unwrap()).await;
+        assert_eq!(json["enabled"], false);
+        assert_eq!(json["pending"], 0);
+
+        let server = ApiServer::new(temp.path());
+        let _poller = PollingWatcher::new(Arc::clone(&server.state().indexer), PollConfig::default())
+            .start()
+            .unwrap();
+        let json = body_json(server.router().oneshot(get()).await.unwrap()).await;
+        assert_eq!(json["enabled"], true);
+        assert_eq!(json["mode"], "polling");
+        assert_eq!(json["debounce_ms"], 5000);
+    }
+
+    #[tokio::test]
+    async fn test_archive_skill() {
+        let (_temp, app) = create_test_server().await;
+        let get = |uri: &str| {
+            app.clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        };
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills/test-skill/archive")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["archived"], true);
+
+        let skills = body_json(get("/api/skills").await.unwrap()).await;
+        assert_eq!(skills.as_array().unwrap().len(), 0);
+        let skills = body_json(get("/api/skills?include_archived=true").await.unwrap()).await;
+        assert_eq!(skills[0]["archived"], true);
+
+        let results = body_json(get("/api/search?q=test").await.unwrap()).await;
+        assert_eq!(results["total_matches"], 0);
+        let results = body_json(get("/api/search?q=test&include_archived=true").await.unwrap()).await;
+        assert_eq!(results["results"][0]["domain"], "test-skill");
+
+        // Archived skills stay readable by name
+        let response = get("/api/skills/test-skill").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["archived"], true);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills/test-skill/unarchive")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let skills = body_json(get("/api/skills").await.unwrap()).await;
+        assert_eq!(skills[0]["name"], "test-skill");
+        assert!(skills[0].get("archived").is_none());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills/missing/archive")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_private_skill_visibility() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "billing",
+            &format!(
+                r#"{{"name": "billing", "description": "Billing runbook", "visibility": "private",
+                    "allowed_keys": ["{}"]}}"#,
+                crate::models::key_fingerprint("team-a-key")
+            ),
+            "# Billing\n\nRefund steps.",
+        );
+        let app = ApiServer::new(temp_dir.path()).router();
+
+        let request = |uri: &str, key: Option<&str>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some(key) = key {
+                builder = builder.header("x-api-key", key);
+            }
+            app.clone().oneshot(builder.body(Body::empty()).unwrap())
+        };
+
+        for key in [None, Some("team-b-key")] {
+            let skills = body_json(request("/api/skills", key).await.unwrap()).await;
+            assert_eq!(skills.as_array().unwrap().len(), 0);
+            let results = body_json(request("/api/search?q=billing", key).await.unwrap()).await;
+            assert_eq!(results["total_matches"], 0);
+            let response = request("/api/skills/billing", key).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let response = request("/api/skills/billing/references", key).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+
+        let key = Some("team-a-key");
+        let skills = body_json(request("/api/skills", key).await.unwrap()).await;
+        assert_eq!(skills[0]["name"], "billing");
+        let results = body_json(request("/api/search?q=billing", key).await.unwrap()).await;
+        assert_eq!(results["results"][0]["domain"], "billing");
+        let response = request("/api/skills/billing", key).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// A test server with a `billing` skill only `team-a-key` may see.
+    async fn create_private_skill_server() -> (TempDir, Router) {
+        let (temp, _) = create_test_server().await;
+        write_skill(
+            temp.path(),
+            "billing",
+            &format!(
+                r#"{{"name": "billing", "description": "Billing runbook", "visibility": "private",
+                    "allowed_keys": ["{}"]}}"#,
+                crate::models::key_fingerprint("team-a-key")
+            ),
+            "# Billing\n\nRefund steps.",
+        );
+        let app = ApiServer::new(temp.path()).router();
+        (temp, app)
+    }
+
+    fn keyed_request(method: &str, uri: &str, key: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-api-key", key)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_private_skill_writes() {
+        let (temp, app) = create_private_skill_server().await;
+        let other = "team-b-key";
+        let status = |request: Request<Body>| {
+            let app = app.clone();
+            async move { app.oneshot(request).await.unwrap().status() }
+        };
+        use serde_json::json;
+
+        let update = keyed_request("PUT", "/api/skills/billing", other, json!({ "content": "# Gone" }));
+        assert_eq!(status(update).await, StatusCode::NOT_FOUND);
+
+        let ops = json!({ "operations": [{ "op": "append", "heading": "Billing", "content": "More." }] });
+        let patch = keyed_request("PATCH", "/api/skills/billing", other, ops);
+        assert_eq!(status(patch).await, StatusCode::NOT_FOUND);
+
+        let delete = keyed_request("DELETE", "/api/skills/billing", other, json!({}));
+        assert_eq!(status(delete).await, StatusCode::NOT_FOUND);
+
+        let rename = keyed_request("POST", "/api/skills/billing/rename", other, json!({ "new_name": "mine" }));
+        assert_eq!(status(rename).await, StatusCode::NOT_FOUND);
+
+        let duplicate = keyed_request("POST", "/api/skills/billing/duplicate", other, json!({ "new_name": "copy" }));
+        assert_eq!(status(duplicate).await, StatusCode::NOT_FOUND);
+        assert!(!temp.path().join("copy").exists());
+
+        let links = keyed_request("PUT", "/api/skills/billing/links", other, json!({ "see_also": [] }));
+        assert_eq!(status(links).await, StatusCode::NOT_FOUND);
+
+        // Nor can a visible skill link to it
+        let links = keyed_request("PUT", "/api/skills/test-skill/links", other, json!({ "see_also": ["billing"] }));
+        assert_eq!(status(links).await, StatusCode::BAD_REQUEST);
+
+        assert_eq!(fs::read_to_string(temp.path().join("billing/SKILL.md")).unwrap(), "# Billing\n\nRefund steps.");
+
+        // The allowed key can still write, and doesn't see hidden related skills
+        let update = keyed_request("PUT", "/api/skills/billing", "team-a-key", json!({ "tags": ["payments"] }));
+        assert_eq!(status(update).await, StatusCode::OK);
+        let related = |key: &str| {
+            let update = keyed_request("PUT", "/api/skills/test-skill", key, json!({ "tags": ["payments"] }));
+            let app = app.clone();
+            async move {
+                let details = body_json(app.oneshot(update).await.unwrap()).await;
+                details["related"].as_array().cloned().unwrap_or_default()
+            }
+        };
+        assert!(related(other).await.iter().all(|r| r["name"] != "billing"));
+        assert!(related("team-a-key").await.iter().any(|r| r["name"] == "billing"));
+    }
+
+    #[tokio::test]
+    async fn test_private_skill_activity() {
+        use tokio_stream::StreamExt;
+
+        let (temp, app) = create_private_skill_server().await;
+        let get = |uri: &str, key: &str| keyed_request("GET", uri, key, serde_json::json!({}));
+
+        let response = app.clone().oneshot(get("/api/events", "team-b-key")).await.unwrap();
+        let mut events = response.into_body().into_data_stream();
+
+        for key in ["team-a-key", "team-b-key"] {
+            let skill = if key == "team-a-key" { "billing" } else { "test-skill" };
+            let update = keyed_request("PUT", &format!("/api/skills/{}", skill), key, serde_json::json!({ "description": "Edited" }));
+            assert_eq!(app.clone().oneshot(update).await.unwrap().status(), StatusCode::OK);
+        }
+
+        // Other callers see neither the private skill's entries nor other keys
+        let entries = body_json(app.clone().oneshot(get("/api/audit", "team-b-key")).await.unwrap()).await;
+        let entries = entries.as_array().unwrap();
+        assert!(entries.iter().all(|e| e["skill"] != "billing"));
+        assert_eq!(entries[0]["actor"], crate::api::Caller::from_api_key("team-b-key").id);
+        let entries = body_json(app.clone().oneshot(get("/api/audit", "team-a-key")).await.unwrap()).await;
+        let actors: Vec<&str> = entries.as_array().unwrap().iter().map(|e| e["actor"].as_str().unwrap()).collect();
+        assert!(actors.contains(&"key"));
+        let uri = format!("/api/audit?actor={}", crate::api::Caller::from_api_key("team-a-key").id);
+        let entries = body_json(app.clone().oneshot(get(&uri, "team-b-key")).await.unwrap()).await;
+        assert!(entries.as_array().unwrap().is_empty());
+
+        let stats = body_json(app.clone().oneshot(get("/api/stats", "team-b-key")).await.unwrap()).await;
+        assert!(!stats.to_string().contains("billing"));
+
+        // Reload so both edits reach the stream, then add a public skill
+        write_skill(temp.path(), "forms", r#"{"name": "forms", "description": "Forms"}"#, "# Forms");
+        app.oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let mut received = String::new();
+        while !received.contains("forms") {
+            let chunk = events.next().await.unwrap().unwrap();
+            received.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+        assert!(!received.contains("billing"));
+    }
+
+    #[tokio::test]
+    async fn test_storage_analysis() {
+        let (temp, _) = create_test_server().await;
+        for name in ["test-skill", "other-skill"] {
+            let refs = temp.path().join(name).join("references");
+            fs::create_dir_all(&refs).unwrap();
+            fs::write(refs.join("http.md"), "# HTTP\n\nStatus codes.\n").unwrap();
+        }
+        write_skill(
+            temp.path(),
+            "other-skill",
+            r#"{"name": "other-skill", "description": "Another skill"}"#,
+            "# Other",
+        );
+        let app = ApiServer::new(temp.path()).router();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/analyze/storage")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let report = body_json(response).await;
+        assert_eq!(report["files"], 4);
+        assert_eq!(report["documents"], 3);
+        assert_eq!(report["savings_bytes"], report["duplicates"][0]["bytes"]);
+        assert_eq!(
+            report["duplicates"][0]["copies"],
+            serde_json::json!(["other-skill/references/http.md", "test-skill/references/http.md"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_boost_param() {
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/search?q=test&boost=name:0").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        // With names ignored the skill matches on its tag
+        assert_eq!(json["results"][0]["match_type"], "tags");
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/search?q=test&boost=title:2").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_bundles() {
+        let (temp, app) = create_test_server().await;
+        std::fs::write(
+            temp.path().join(crate::bundles::BUNDLES_FILE),
+            r#"{"basics": {"description": "Start here", "items": ["test-skill"], "max_tokens": 1000}}"#,
+        )
+        .unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/bundles").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json[0]["name"], "basics");
+        assert_eq!(json[0]["items"], serde_json::json!(["test-skill"]));
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/bundles/basics?max_tokens=1").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["max_tokens"], 1);
+        assert_eq!(json["omitted"], serde_json::json!(["test-skill"]));
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/bundles/nope").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[cfg(feature = "graphql")]
+    #[tokio::test]
+    async fn test_graphql() {
+        let (_temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/graphql")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"query": "query ($q: String!) { search(query: $q) { score skill { name tags } } }", "variables": {"q": "test"}}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["data"]["search"][0]["skill"]["name"], "test-skill");
+        assert_eq!(json["data"]["search"][0]["skill"]["tags"][0], "test");
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/graphql?query=%7B%20skill(name%3A%20%22test-skill%22)%20%7B%20content%20%7D%20%7D")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let json = body_json(response).await;
+        assert_eq!(json["data"]["skill"]["content"], "# Test Skill\n\nContent.");
+
+        // Syntax errors reject the request, with the error's location
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/graphql?query=%7B%20skill(")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let json = body_json(response).await;
+        assert!(json.get("data").is_none());
+        assert_eq!(json["errors"][0]["locations"][0]["column"], 9);
+
+        // Without a query, the schema
+        let response = app
+            .oneshot(Request::builder().uri("/api/graphql").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("type Query {"));
+    }
+
+    #[tokio::test]
+    async fn test_probes() {
+        let (temp, app) = create_test_server().await;
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/healthz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(Request::builder().uri("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["ready"], true);
+        assert_eq!(json["skills_dir"]["accessible"], true);
+        assert_eq!(json["index"]["skills"], 1);
+        assert!(json["index"]["built_at"].is_string());
+        assert_eq!(json["watcher"]["enabled"], false);
+        drop(temp);
+
+        // No index was ever built from a missing directory
+        let app = ApiServer::new("/nonexistent/skills").router();
+        let response = app
+            .oneshot(Request::builder().uri("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let json = body_json(response).await;
+        assert_eq!(json["ready"], false);
+        assert_eq!(json["index"]["built"], false);
+        assert_eq!(json["skills_dir"]["accessible"], false);
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown() {
+        use tokio::io::AsyncWriteExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let store = temp_dir.path().join("analytics.json");
+        let server = ApiServer::new(temp_dir.path())
+            .with_analytics_store(&store)
+            .with_drain_timeout(Duration::from_millis(100));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // A client stuck halfway through a request holds its connection open
+        let mut client = tokio::net::TcpStream::connect(addr).await.unwrap();
+        client.write_all(b"GET /healthz HTTP/1.1\r\n").await.unwrap();
+
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let shutdown = async move {
+            let _ = stop_rx.await;
+        };
+        let run = tokio::spawn(async move { server.serve_until(listener, shutdown).await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        stop_tx.send(()).unwrap();
+
+        // The drain timeout ends the wait, and the analytics store is written
+        let result = tokio::time::timeout(Duration::from_secs(5), run).await;
+        assert!(result.unwrap().unwrap().is_ok());
+        assert!(store.exists());
+        drop(client);
+    }
+
+    #[tokio::test]
+    async fn test_request_id() {
+        let (_temp, app) = create_test_server().await;
+
+        // The client's ID is echoed, and added to error bodies
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/missing")
+                    .header("x-request-id", "trace-42")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()["x-request-id"], "trace-42");
+        let json = body_json(response).await;
+        assert_eq!(json["request_id"], "trace-42");
+        assert!(json["error"].is_string());
+
+        // Otherwise one is generated; successful bodies are left alone
+        let response = app
+            .oneshot(Request::builder().uri("/api/skills").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-request-id"].len(), 32);
+        assert!(body_json(response).await.is_array());
+    }
+
+    #[tokio::test]
+    async fn test_error_codes() {
+        let (_temp, app) = create_test_server().await;
+        let post = |body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/skills")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(post(serde_json::json!({ "name": "..", "description": "d", "content": "c" })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_json(response).await["code"], "INVALID_NAME");
+
+        let response = app
+            .clone()
+            .oneshot(post(serde_json::json!({ "name": "test-skill", "description": "d", "content": "c" })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(body_json(response).await["code"], "ALREADY_EXISTS");
+
+        // Every invalid field is reported
+        let response = app
+            .oneshot(post(serde_json::json!({
+                "name": "new-skill",
+                "description": "d".repeat(1001),
+                "content": "c",
+                "tags": ["ok", ""],
+            })))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error = body_json(response).await;
+        assert_eq!(error["code"], "VALIDATION_FAILED");
+        let fields: Vec<&str> = error["details"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| d["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, ["description", "tags[1]"]);
+    }
+
+    #[tokio::test]
+    async fn test_skill_html() {
+        let (temp, _app) = create_test_server().await;
+        let skill = temp.path().join("test-skill");
+        fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: test-skill\n---\n# Test Skill\n\nSee [forms](references/forms.md#setup), \
+             [api](references/api.md), [logo](references/logo.png), and [other](../missing/SKILL.md).\n\n\
+             ```rust\nfn main() {}\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            skill.join("_meta.json"),
+            r#"{"name": "test-skill", "description": "A test skill", "tags": ["test"],
+                "sub_skills": [{"name": "forms", "file": "references/forms.md"}]}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(skill.join("references")).unwrap();
+        fs::write(skill.join("references/forms.md"), "# Forms\n\n[Back](../SKILL.md)\n").unwrap();
+        fs::write(skill.join("references/api.md"), "# API\n").unwrap();
+        fs::write(skill.join("references/logo.png"), b"\x89PNG").unwrap();
+        let app = ApiServer::new(temp.path()).router();
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let body_text = |response: axum::response::Response| async move {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            String::from_utf8(bytes.to_vec()).unwrap()
+        };
+
+        let response = app.clone().oneshot(get("/api/skills/test-skill/html")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/html; charset=utf-8");
+        let html = body_text(response).await;
+        assert!(html.starts_with("<h1 id=\"test-skill\">Test Skill</h1>"), "{}", html);
+        assert!(html.contains("<a href=\"/api/skills/test-skill/html?sub_skill=forms#setup\">forms</a>"));
+        assert!(html.contains("<a href=\"/api/skills/test-skill/references/api.md?format=html\">api</a>"));
+        assert!(html.contains("<a href=\"/api/skills/test-skill/references/logo.png\">logo</a>"));
+        assert!(html.contains("<a href=\"../missing/SKILL.md\">other</a>"));
+        assert!(html.contains("<span class=\"hl-keyword\">fn</span> main"));
+
+        let response = app
+            .clone()
+            .oneshot(get("/api/skills/test-skill/html?sub_skill=forms"))
+            .await
+            .unwrap();
+        let html = body_text(response).await;
+        assert!(html.contains("<a href=\"/api/skills/test-skill/html\">Back</a>"), "{}", html);
+
+        let response = app
+            .clone()
+            .oneshot(get("/api/skills/test-skill/html?sub_skill=missing"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app
+            .clone()
+            .oneshot(get("/api/skills/test-skill/references/api.md?format=html"))
+            .await
+            .unwrap();
+        assert_eq!(body_text(response).await, "<h1 id=\"api\">API</h1>\n");
+
+        let response = app
+            .oneshot(get("/api/skills/test-skill/references/logo.png?format=html"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_skill_outline() {
+        let (temp, _app) = create_test_server().await;
+        fs::write(
+            temp.path().join("test-skill/SKILL.md"),
+            "# Test Skill\n\n## Setup\n\nInstall it.\n\n### Config\n\nSet flags.\n\n## Usage\n\nRun it.\n",
+        )
+        .unwrap();
+        let app = ApiServer::new(temp.path()).router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill/outline")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let outline = body_json(response).await;
+        assert_eq!(outline["skill"], "test-skill");
+        let top = &outline["sections"][0];
+        assert_eq!(top["title"], "Test Skill");
+        assert_eq!(top["children"][0]["anchor"], "setup");
+        assert_eq!(top["children"][0]["children"][0]["title"], "Config");
+        assert_eq!(top["children"][1]["line"], 11);
+        assert_eq!(top["total_tokens"], outline["total_tokens"]);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill/outline?sub_skill=missing")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_skill_examples() {
+        let (temp, _app) = create_test_server().await;
+        fs::write(
+            temp.path().join("test-skill/SKILL.md"),
+            "# Test Skill\n\n## Python\n\n```python\nprint(1)\n```\n\n## Shell\n\n```sh\necho 1\n```\n",
+        )
+        .unwrap();
+        let app = ApiServer::new(temp.path()).router();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/test-skill/examples?lang=py")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let examples = body_json(response).await;
+        assert_eq!(examples["examples"].as_array().unwrap().len(), 1);
+        assert_eq!(examples["examples"][0]["language"], "python");
+        assert_eq!(examples["examples"][0]["heading"], "Python");
+        assert_eq!(examples["examples"][0]["file"], "SKILL.md");
+        assert_eq!(examples["examples"][0]["code"], "print(1)\n");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/skills/missing/examples")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_backups() {
+        let (temp, app) = create_test_server().await;
+        let post = |uri: &str, body: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        // Backups need a configured directory
+        let response = app.clone().oneshot(post("/api/backup", "")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(body_json(response).await["code"], "DISABLED");
+
+        let server = ApiServer::new(temp.path())
+            .with_config(Config::parse("[backup]\npath = \"_backups\"\n").unwrap());
+        let app = server.router();
+
+        let response = app.clone().oneshot(post("/api/backup", "")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let backup = body_json(response).await;
+        let id = backup["id"].as_str().unwrap().to_string();
+        assert_eq!(backup["skills"], 1);
+
+        fs::remove_dir_all(temp.path().join("test-skill")).unwrap();
+        server.state().indexer.reload().unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/backups").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await[0]["id"], id.as_str());
+
+        // Without confirmation the restore is only previewed
+        let uri = format!("/api/backups/{}/restore", id);
+        let response = app.clone().oneshot(post(&uri, "{}")).await.unwrap();
+        let preview = body_json(response).await;
+        assert_eq!(preview["restored"], false);
+        assert_eq!(preview["added"], serde_json::json!(["test-skill"]));
+        assert!(!temp.path().join("test-skill").exists());
+
+        let response = app.clone().oneshot(post(&uri, r#"{"confirm": true}"#)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let restored = body_json(response).await;
+        assert_eq!(restored["restored"], true);
+        assert!(restored["safety_backup"]["id"].is_string());
+        assert!(server.state().indexer.skill_exists("test-skill"));
+
+        let response = app
+            .oneshot(post("/api/backups/skills-19700101T000000Z/restore", "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_tenants() {
+        let (temp, _) = create_test_server().await;
+        let tenants = TempDir::new().unwrap();
+        let skill_dir = tenants
+            .path()
+            .join(crate::models::key_fingerprint("tenant-key"))
+            .join("tenant-skill");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: tenant-skill\ndescription: A tenant's skill\n---\n# Tenant Skill\n",
+        )
+        .unwrap();
+
+        let config = Config::parse(&format!("[tenants]\nroot = \"{}\"\n", tenants.path().display())).unwrap();
+        let app = ApiServer::new(temp.path()).with_config(config).router();
+
+        let list = |key: Option<&str>| {
+            let mut request = Request::builder().uri("/api/skills");
+            if let Some(key) = key {
+                request = request.header("x-api-key", key);
+            }
+            app.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+        let names = |json: serde_json::Value| -> Vec<String> {
+            json.as_array()
+                .unwrap()
+                .iter()
+                .map(|s| s["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let tenant = names(body_json(list(Some("tenant-key")).await.unwrap()).await);
+        assert_eq!(tenant, vec!["tenant-skill"]);
+
+        // No key, or a key without a tenant, gets the primary library
+        for key in [None, Some("other-key")] {
+            let primary = names(body_json(list(key).await.unwrap()).await);
+            assert_eq!(primary, vec!["test-skill"]);
+        }
+
+        // Writes go to the tenant's directory
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills")
+                    .header("x-api-key", "tenant-key")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({"name": "second", "description": "Second tenant skill", "content": "# Second"}).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert!(skill_dir.parent().unwrap().join("second").is_dir());
+        assert!(!temp.path().join("second").exists());
+    }
+
+    #[tokio::test]
+    async fn test_journal() {
+        let (temp, _) = create_test_server().await;
+        let path = temp.path().join(crate::journal::JOURNAL_FILE);
+        let app = ApiServer::new(temp.path()).with_journal(&path).router();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({"name": "journaled", "description": "A journaled skill", "content": "# Journaled"})
+                            .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // The intent is written ahead of the entry recording the changes
+        let entries = crate::journal::read(&path).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].action, None);
+        assert!(entries[1].intent && entries[1].changes.is_empty());
+        assert_eq!(entries[2].action, Some(crate::audit::AuditAction::Create));
+        assert_eq!(entries[2].skill.as_deref(), Some("journaled"));
+        assert!(entries[2].changes.iter().all(|c| c.path().starts_with("journaled/")));
+    }
+
+    #[tokio::test]
+    async fn test_conditional_get() {
+        let (temp, app) = create_test_server().await;
+        let refs = temp.path().join("test-skill/references");
+        fs::create_dir_all(&refs).unwrap();
+        fs::write(refs.join("guide.md"), "# Guide\n").unwrap();
+        fs::write(temp.path().join("test-skill/SKILL.md"), "# Test Skill\n\n<!-- draft -->\nContent.").unwrap();
+
+        let get = |uri: &str, header: Option<(&str, String)>| {
+            let mut request = Request::builder().uri(uri);
+            if let Some((name, value)) = header {
+                request = request.header(name, value);
+            }
+            app.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        for uri in [
+            "/api/skills/test-skill",
+            "/api/skills/test-skill/html",
+            "/api/skills/test-skill/references/guide.md",
+            "/api/skills/test-skill/references/guide.md?format=html",
+        ] {
+            let response = get(uri, None).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+            let etag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+            let modified = response.headers()[header::LAST_MODIFIED].to_str().unwrap().to_string();
+
+            let response = get(uri, Some(("if-none-match", etag.clone()))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{}", uri);
+            assert_eq!(response.headers()[header::ETAG], etag.as_str());
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert!(body.is_empty());
+
+            let response = get(uri, Some(("if-modified-since", modified))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{}", uri);
+
+            let response = get(uri, Some(("if-none-match", "\"stale\"".to_string()))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+        }
+
+        // Different representations have different tags
+        let full = get("/api/skills/test-skill", None).await.unwrap();
+        let minified = get("/api/skills/test-skill?minify=true", None).await.unwrap();
+        assert_eq!(full.headers()[header::CONTENT_TYPE], "application/json");
+        assert_ne!(full.headers()[header::ETAG], minified.headers()[header::ETAG]);
+    }
+
+    #[tokio::test]
+    async fn test_test_triggers() {
+        let (temp, app) = create_test_server().await;
+
+        write_skill(
+            temp.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Forms",
+                "sub_skills": [{"name": "validation", "file": "validation.md", "triggers": ["zod"]},
+                               {"name": "react", "file": "react.md", "triggers": ["react-hook-form"]}]}"#,
+            "# Forms",
+        );
+        app.clone()
+            .oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let test = |body: &'static str| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills/forms/test-triggers")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+        };
+
+        let response = test(
+            r#"{"phrases": ["parse with zod"], "file": "use React Hook Form => react\nzod schema => react\n"}"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let report = body_json(response).await;
+        assert_eq!(report["results"][0]["selected"][0], "validation");
+        assert_eq!(report["results"][0]["sub_skills"][0]["matches"][0]["trigger"], "zod");
+        assert!(report["results"][0]["passed"].is_null());
+        assert_eq!(report["results"][1]["passed"], true);
+        assert_eq!(report["results"][2]["passed"], false);
+        assert_eq!((report["passed"].as_u64(), report["failed"].as_u64()), (Some(1), Some(1)));
+
+        assert_eq!(test(r#"{}"#).await.unwrap().status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            test(r#"{"phrases": ["zod"], "min_score": 2}"#).await.unwrap().status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quality_report() {
+        let (temp, _) = create_test_server().await;
+        let config = Config::parse("[quality]\nmin_tokens = 1\n").unwrap();
+        let app = ApiServer::new(temp.path()).with_config(config).router();
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/report/quality?limit=1").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let report = body_json(response).await;
+        assert_eq!(report["config"]["min_tokens"], 1);
+        assert_eq!(report["skills"].as_array().unwrap().len(), 1);
+        assert_eq!(report["skills"][0]["skill"], "test-skill");
+        assert_eq!(report["skills"][0]["thin"], false);
+        assert_eq!(report["coverage"]["description"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_diff_skills() {
+        let (temp, app) = create_test_server().await;
+        write_skill(
+            temp.path(),
+            "test-fork",
+            r#"{"name": "test-fork", "description": "A forked test skill", "tags": ["test"]}"#,
+            "# Test Skill\n\nBetter content.",
+        );
+        app.clone()
+            .oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/diff?left=test-skill&right=test-fork").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let diff = body_json(response).await;
+        assert_eq!(diff["metadata"][0]["field"], "description");
+        assert_eq!(diff["metadata"][0]["right"], "A forked test skill");
+        assert_eq!(diff["files"][0]["path"], "SKILL.md");
+        assert!(diff["files"][0]["diff"].as_str().unwrap().contains("+Better content."));
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/diff?left=test-skill&right=missing").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_skill_translation() {
+        let (temp, app) = create_test_server().await;
+        fs::write(temp.path().join("test-skill/SKILL.fr.md"), "# Compétence de test\n\nContenu.").unwrap();
+        app.clone()
+            .oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/skills/test-skill?lang=fr-CA").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let details = body_json(response).await;
+        assert_eq!(details["content"], "# Compétence de test\n\nContenu.");
+        assert_eq!(details["language"], "fr");
+        assert_eq!(details["translations"], serde_json::json!(["fr"]));
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/skills/test-skill?lang=ja").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let details = body_json(response).await;
+        assert_eq!(details["content"], "# Test Skill\n\nContent.");
+    }
+
+    #[tokio::test]
+    async fn test_validate_reports_orphaned_files() {
+        let (temp, app) = create_test_server().await;
+        fs::write(temp.path().join("test-skill/notes.md"), "# Notes").unwrap();
+        app.clone()
+            .oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/validate").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let result = body_json(response).await;
+        assert_eq!(result["valid"], true);
+        assert!(result["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|w| w.as_str().unwrap().starts_with("test-skill: notes.md: Orphaned")));
+    }
+
+    #[tokio::test]
+    async fn test_api_versions() {
+        let (temp, app) = create_test_server().await;
+        let send = |method: &str, uri: &str, body: serde_json::Value| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/v2/skills/test-skill").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // v2 requires the current shapes
+        let response = app
+            .clone()
+            .oneshot(send("POST", "/api/v2/skills", serde_json::json!({"name": "forms", "tags": "a, b"})))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = app
+            .clone()
+            .oneshot(send(
+                "POST",
+                "/api/v1/skills",
+                serde_json::json!({"name": "forms", "tags": "frontend, validation", "sub_skills": ["react"]}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let details = body_json(response).await;
+        assert_eq!(details["tags"], serde_json::json!(["frontend", "validation"]));
+        assert_eq!(details["sub_skills"][0]["file"], "react.md");
+
+        let response = app
+            .clone()
+            .oneshot(send("POST", "/api/v1/skills", serde_json::json!({"name": "forms", "content": "# Forms"})))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = app
+            .clone()
+            .oneshot(send(
+                "POST",
+                "/api/v1/skills",
+                serde_json::json!({"name": "forms", "content": "# Forms", "overwrite": true}),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(fs::read_to_string(temp.path().join("forms/SKILL.md")).unwrap(), "# Forms");
+        let meta = fs::read_to_string(temp.path().join("forms/_meta.json")).unwrap();
+        assert!(meta.contains("validation"));
+
+        let response = app
+            .clone()
+            .oneshot(send("PUT", "/api/v1/skills/forms", serde_json::json!({"tags": "forms"})))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["tags"], serde_json::json!(["forms"]));
+
+        // Folder imports are held to the import root like the current route
+        let imports = TempDir::new().unwrap();
+        fs::create_dir_all(imports.path().join("pdf")).unwrap();
+        fs::write(
+            imports.path().join("pdf/SKILL.md"),
+            "---\nname: pdf\ndescription: PDF tools\n---\n# PDF\n",
+        )
+        .unwrap();
+        let import_folder = |path: &std::path::Path| {
+            send("POST", "/api/v1/import/folder", serde_json::json!({"path": path, "name": ""}))
+        };
+        let response = app.oneshot(import_folder(&imports.path().join("pdf"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let server = ApiServer::new(temp.path());
+        server.state().import.write().root = Some(imports.path().to_path_buf());
+        let app = server.router();
+        let response = app.clone().oneshot(import_folder(&temp.path().join("forms"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = app.oneshot(import_folder(&imports.path().join("pdf"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_replace_in_skills() {
+        let (temp, app) = create_test_server().await;
+        fs::write(temp.path().join("test-skill/guide.md"), "Run oldtool here.\n").unwrap();
+        let replace = |body: serde_json::Value| {
+            Request::builder()
+                .method("POST")
+                .uri("/api/replace")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(replace(serde_json::json!({"find": "Content|oldtool", "replace": "x", "regex": true})))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let preview = body_json(response).await;
+        assert_eq!(preview["dry_run"], true);
+        assert_eq!(preview["replacements"], 2);
+        assert_eq!(preview["files"][0]["path"], "SKILL.md");
+        assert!(preview["files"][0]["diff"].as_str().unwrap().contains("-Content.\n+x.\n"));
+        assert_eq!(
+            fs::read_to_string(temp.path().join("test-skill/SKILL.md")).unwrap(),
+            "# Test Skill\n\nContent."
+        );
+
+        let response = app
+            .clone()
+            .oneshot(replace(serde_json::json!({"find": "(", "regex": true})))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .clone()
+            .oneshot(replace(serde_json::json!({"find": "Content", "replace": "Body", "dry_run": false})))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["skills"], serde_json::json!(["test-skill"]));
+        assert_eq!(fs::read_to_string(temp.path().join("test-skill/SKILL.md")).unwrap(), "# Test Skill\n\nBody.");
+
+        // The skill is reindexed
+        let response = app
+            .oneshot(Request::builder().uri("/api/skills/test-skill").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["content"], "# Test Skill\n\nBody.");
+    }
+
+    #[tokio::test]
+    async fn test_tags() {
+        let (temp, app) = create_test_server().await;
+        fs::create_dir_all(temp.path().join("jest")).unwrap();
+        fs::write(
+            temp.path().join("jest/_meta.json"),
+            r#"{"name": "jest", "description": "Jest", "tags": ["unit", "test"]}"#,
+        )
+        .unwrap();
+        fs::write(temp.path().join("jest/SKILL.md"), "# Jest").unwrap();
+        app.clone()
+            .oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/tags").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let tags = body_json(response).await;
+        assert_eq!(tags[0]["tag"], "test");
+        assert_eq!(tags[0]["count"], 2);
+        assert_eq!(tags[0]["skills"], serde_json::json!(["jest", "test-skill"]));
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/tags/rename")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"from": "test", "to": "testing"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["skills"], serde_json::json!(["jest", "test-skill"]));
+        assert!(fs::read_to_string(temp.path().join("jest/_meta.json")).unwrap().contains("\"testing\""));
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/tags").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let tags = body_json(response).await;
+        assert_eq!(tags[0]["tag"], "testing");
+        assert_eq!(tags[0]["count"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_skill_scripts() {
+        let (temp, app) = create_test_server().await;
+        fs::create_dir_all(temp.path().join("test-skill/scripts")).unwrap();
+        fs::write(temp.path().join("test-skill/scripts/check.py"), "print('ok')").unwrap();
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/skills/test-skill/scripts").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let listing = body_json(response).await;
+        assert_eq!(listing["enabled"], false);
+        assert_eq!(listing["scripts"][0]["name"], "check.py");
+        assert_eq!(listing["scripts"][0]["interpreter"], "python3");
+        assert_eq!(listing["scripts"][0]["runnable"], false);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_skill_script_over_mcp() {
+        let (temp, _) = create_test_server().await;
+        fs::create_dir_all(temp.path().join("test-skill/scripts")).unwrap();
+        fs::write(temp.path().join("test-skill/scripts/greet.sh"), "echo \"hi $1\"\n").unwrap();
+        let server = ApiServer::new(temp.path());
+        *server.state().scripts.write() = crate::scripts::ScriptsConfig {
+            enabled: true,
+            allow: vec!["test-skill/*.sh".to_string()],
+            ..Default::default()
+        };
+        let app = server.router();
+
+        let response = app
+            .clone()
+            .oneshot(mcp_post(r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#, None))
+            .await
+            .unwrap();
+        let session = response.headers()[mcp::http::SESSION_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let response = app
+            .oneshot(mcp_post(
+                r#"{"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "run_skill_script",
+                    "arguments": {"name": "test-skill", "script": "greet.sh", "args": ["there"]}}}"#,
+                Some(&session),
+            ))
+            .await
+            .unwrap();
+        let result = body_json(response).await["result"].clone();
+        assert_eq!(result["isError"], false);
+        let output: serde_json::Value = serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(output["exit_code"], 0);
+        assert_eq!(output["stdout"], "hi there\n");
+    }
+
+    #[tokio::test]
+    async fn test_deprecations() {
+        let (temp, app) = create_test_server().await;
+        for (name, meta) in [
+            ("legacy", r#"{"name": "legacy", "description": "Old", "superseded_by": "test-skill"}"#),
+            ("forms", r#"{"name": "forms", "description": "Forms", "depends_on": ["legacy"]}"#),
+        ] {
+            write_skill(temp.path(), name, meta, "# Skill\n");
+        }
+        app.clone()
+            .oneshot(Request::builder().method("POST").uri("/api/reload?wait=true").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/api/skills/legacy").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let skill = body_json(response).await;
+        assert_eq!(skill["deprecated"], true);
+        assert_eq!(skill["superseded_by"], "test-skill");
+        assert!(skill["content"].as_str().unwrap().ends_with("is superseded by `test-skill`; use that skill instead.\n"));
+
+        let response = app
+            .oneshot(Request::builder().uri("/api/report/deprecations").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let report = body_json(response).await;
+        assert_eq!(report["skills"][0]["name"], "legacy");
+        assert_eq!(
+            report["skills"][0]["referenced_by"],
+            serde_json::json!([{"skill": "forms", "kind": "depends_on"}])
+        );
+        assert_eq!(report["total_references"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_conflicts() {
+        let (temp, _) = create_test_server().await;
+        let source = TempDir::new().unwrap();
+        let server = ApiServer::new(temp.path());
+        server.state().import.write().root = Some(source.path().to_path_buf());
+        let app = server.router();
+
+        fs::write(
+            source.path().join("SKILL.md"),
+            "---\nname: pdf\ndescription: PDF tools\n---\n# PDF\n",
+        )
+        .unwrap();
+        fs::write(source.path().join("FORMS.md"), "# Forms\n").unwrap();
+        let import = || {
+            let body = serde_json::json!({ "path": source.path(), "merge": true }).to_string();
+            Request::builder()
+                .method("POST")
+                .uri("/api/skills/import-claude-skill")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(import()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        // Both sides change the same file
+        fs::write(temp.path().join("pdf/FORMS.md"), "# Forms, locally\n").unwrap();
+        fs::write(source.path().join("FORMS.md"), "# Forms v2\n").unwrap();
+        let response = app.clone().oneshot(import()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = body_json(response).await;
+        assert_eq!(json["merge"]["conflicts"][0]["file"], "FORMS.md");
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/import/conflicts")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let json = body_json(response).await;
+        assert_eq!(json[0]["skill"], "pdf");
+        assert_eq!(json[0]["conflicts"][0]["kind"], "modified");
+
+        let body = serde_json::json!({ "file": "FORMS.md", "resolution": "incoming" }).to_string();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/import/conflicts/pdf/resolve")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.clone()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_json(response).await["conflicts"].as_array().unwrap().is_empty());
+        assert_eq!(fs::read_to_string(temp.path().join("pdf/FORMS.md")).unwrap(), "# Forms v2\n");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/import/conflicts/pdf/resolve")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_reserved_skill_name() {
+        let (temp, app) = create_test_server().await;
+        let get = || Request::builder().uri("/api/skills/frontend%2FCON").body(Body::empty()).unwrap();
+
+        let response = app.oneshot(get()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_json(response).await["code"], "INVALID_NAME");
+
+        // The [index.names] policy can allow reserved names
+        let server = ApiServer::new(temp.path());
+        server.state().indexer.set_index_config(crate::index::IndexConfig {
+            names: crate::index::NameConfig {
+                reject_reserved: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let response = server.router().oneshot(get()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body_json(response).await["code"], "SKILL_NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_import_from_url_rejects_other_hosts() {
+        let (_temp, app) = create_test_server().await;
+
+        let body = serde_json::json!({ "url": "https://example.com/acme/skills" }).to_string();
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/skills/import-from-url")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_json(response).await["code"], "INVALID_REQUEST");
     }
 }
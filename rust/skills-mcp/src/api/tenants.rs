@@ -0,0 +1,331 @@
+//! Per-tenant skills libraries.
+//!
+//! With a `[tenants]` root configured, each API key gets its own skills
+//! directory, `<root>/<fingerprint>`, named by the fingerprint that
+//! `skills-mcp key-fingerprint` prints (without `key:`). Requests carrying the key are served
+//! entirely from that tenant's library: REST routes and MCP over HTTP get
+//! their own indexer, search, stats, audit log, and MCP sessions, so nothing
+//! is shared between tenants. Requests without a key, or whose key has no
+//! directory, are served from the primary skills directory. Session headers
+//! are chosen by the client, so they never select a tenant.
+//!
+//! Directories are only made by the operator, or with `create` for the
+//! fingerprints listed in `keys`, up to `max_tenants` of them; any client
+//! can send a key, so unknown keys never get one.
+//!
+//! Tenant libraries are loaded on first use and at most `max_loaded` are
+//! kept; the least recently used is dropped to make room. A library dropped
+//! while requests still use it is picked up again if its key returns, so a
+//! tenant never has two libraries editing its directory at once. Tenants
+//! share the server's other `skills-mcp.toml` settings, and each appends
+//! its audit log to its own directory.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+    Router,
+};
+use tower::ServiceExt;
+use tracing::{info, warn};
+
+use crate::audit;
+use crate::config::Config;
+use crate::index::SkillIndexer;
+use crate::mcp::http::McpSessions;
+use crate::mcp::tools::ServiceContext;
+
+use super::routes::AppState;
+use super::server::routes;
+use super::caller::VerifiedKey;
+use super::Caller;
+
+/// A loaded tenant library.
+struct Tenant {
+    state: AppState,
+    router: Router,
+    /// Tick of the last request, for LRU eviction.
+    last_used: u64,
+}
+
+/// Tenant libraries, loaded on demand.
+pub(super) struct Tenants {
+    root: PathBuf,
+    max_loaded: usize,
+    create: bool,
+    /// Fingerprints `create` makes directories for.
+    keys: HashSet<String>,
+    max_tenants: usize,
+    /// Settings applied to every tenant library.
+    config: Config,
+    loaded: parking_lot::Mutex<TenantCache>,
+}
+
+#[derive(Default)]
+struct TenantCache {
+    tenants: HashMap<String, Tenant>,
+    /// Libraries unloaded while requests may still hold them.
+    evicted: HashMap<String, Weak<ServiceContext>>,
+    tick: u64,
+}
+
+impl Tenants {
+    /// Tenancy for a configuration, or None when no tenants root is set.
+    pub(super) fn new(config: &Config) -> Option<Self> {
+        let root = config.tenants.root.clone()?;
+        info!("Serving tenant skills directories under {:?}", root);
+        Some(Self {
+            root,
+            max_loaded: config.tenants.max_loaded.max(1),
+            create: config.tenants.create,
+            keys: config
+                .tenants
+                .keys
+                .iter()
+                .map(|key| key.strip_prefix("key:").unwrap_or(key).to_string())
+                .collect(),
+            max_tenants: config.tenants.max_tenants,
+            config: config.clone(),
+            loaded: parking_lot::Mutex::new(TenantCache::default()),
+        })
+    }
+
+    /// The library and router of the tenant with an API key fingerprint,
+    /// loading it if needed, or None if the key has no tenant.
+    async fn tenant(self: &Arc<Self>, fingerprint: &str) -> Option<(AppState, Router)> {
+        if let Some(tenant) = self.touch(fingerprint) {
+            return Some(tenant);
+        }
+
+        let live = self.loaded.lock().evicted.get(fingerprint).and_then(Weak::upgrade);
+        let state = match live {
+            Some(state) => state,
+            None => {
+                let tenants = Arc::clone(self);
+                let key = fingerprint.to_string();
+                tokio::task::spawn_blocking(move || tenants.load(&key)).await.ok()??
+            }
+        };
+
+        let mut cache = self.loaded.lock();
+        cache.tick += 1;
+        let tick = cache.tick;
+        // Another request may have loaded the tenant meanwhile; keep the first
+        let live = cache.evicted.remove(fingerprint).and_then(|state| state.upgrade());
+        let tenant = match cache.tenants.entry(fingerprint.to_string()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                // Or one unloaded while this one was loading
+                let state = live.unwrap_or(state);
+                let router = routes(&state, &Arc::new(McpSessions::default()));
+                entry.insert(Tenant {
+                    state,
+                    router,
+                    last_used: tick,
+                })
+            }
+        };
+        let tenant = (Arc::clone(&tenant.state), tenant.router.clone());
+
+        while cache.tenants.len() > self.max_loaded {
+            let oldest = cache
+                .tenants
+                .iter()
+                .min_by_key(|(_, t)| t.last_used)
+                .map(|(id, _)| id.clone())?;
+            if let Some(evicted) = cache.tenants.remove(&oldest) {
+                info!("Unloading tenant {}", oldest);
+                flush(&evicted.state);
+                cache.evicted.retain(|_, state| state.strong_count() > 0);
+                cache.evicted.insert(oldest, Arc::downgrade(&evicted.state));
+            }
+        }
+        Some(tenant)
+    }
+
+    /// The library and router of a loaded tenant, marking it used.
+    fn touch(&self, fingerprint: &str) -> Option<(AppState, Router)> {
+        let mut cache = self.loaded.lock();
+        cache.tick += 1;
+        let tick = cache.tick;
+        let tenant = cache.tenants.get_mut(fingerprint)?;
+        tenant.last_used = tick;
+        Some((Arc::clone(&tenant.state), tenant.router.clone()))
+    }
+
+    /// Open and index a tenant's library.
+    fn load(&self, fingerprint: &str) -> Option<AppState> {
+        let dir = self.root.join(fingerprint);
+        if !dir.is_dir() {
+            if !self.create || !self.keys.contains(fingerprint) {
+                return None;
+            }
+            if self.count() >= self.max_tenants {
+                warn!("Not creating tenant {}: the tenants root holds {} already", fingerprint, self.max_tenants);
+                return None;
+            }
+            if let Err(e) = std::fs::create_dir_all(&dir) {
+                warn!("Failed to create tenant directory {:?}: {}", dir, e);
+                return None;
+            }
+        }
+
+        let state = Arc::new(ServiceContext::new(Arc::new(SkillIndexer::new(&dir))));
+        state.apply_config(self.config.clone());
+        if let Err(e) = state.indexer.reload() {
+            warn!("Failed to load index of tenant {}: {}", fingerprint, e);
+        }
+        // MCP tools act for the tenant's key, so its private skills show
+        *state.mcp_key.write() = Some(fingerprint.to_string());
+        state.audit.write_to(dir.join(audit::LOG_FILE));
+
+        info!("Loaded tenant {} from {:?}", fingerprint, dir);
+        Some(state)
+    }
+
+    /// Tenant directories under the root.
+    fn count(&self) -> usize {
+        std::fs::read_dir(&self.root)
+            .map(|entries| entries.flatten().filter(|e| e.path().is_dir()).count())
+            .unwrap_or(0)
+    }
+
+    /// Fingerprints of the loaded tenants, most recently used first.
+    #[cfg(test)]
+    fn loaded(&self) -> Vec<String> {
+        let cache = self.loaded.lock();
+        let mut tenants: Vec<_> = cache.tenants.iter().collect();
+        tenants.sort_by_key(|(_, t)| std::cmp::Reverse(t.last_used));
+        tenants.into_iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    /// Write every loaded tenant's buffered state to disk.
+    pub(super) fn flush(&self) {
+        for tenant in self.loaded.lock().tenants.values() {
+            flush(&tenant.state);
+        }
+    }
+}
+
+fn flush(state: &ServiceContext) {
+    if let Err(e) = state.audit.sync() {
+        warn!("Failed to flush tenant audit log: {}", e);
+    }
+}
+
+/// Serve requests with a tenant's API key from the tenant's library, and
+/// everything else from the primary one.
+pub(super) async fn route_tenant(
+    State(tenants): State<Arc<Tenants>>,
+    caller: Caller,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let tenant = match caller.key() {
+        Some(key) => tenants.tenant(key).await,
+        None => None,
+    };
+    match tenant {
+        // The library stays held until the response is built, even if
+        // it's unloaded meanwhile
+        Some((_state, router)) => {
+            // Only the operator's directories and configured keys have one
+            request.extensions_mut().insert(VerifiedKey);
+            router.oneshot(request).await.unwrap_or_else(|e| match e {})
+        }
+        None => next.run(request).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::key_fingerprint;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_tenants_lru() {
+        let root = TempDir::new().unwrap();
+        let [a, b, c] = ["a", "b", "c"].map(key_fingerprint);
+        for key in [&a, &b] {
+            std::fs::create_dir_all(root.path().join(key)).unwrap();
+        }
+        let config = Config::parse(&format!("[tenants]\nroot = \"{}\"\nmax_loaded = 2\n", root.path().display())).unwrap();
+        let tenants = Arc::new(Tenants::new(&config).unwrap());
+
+        assert!(tenants.tenant(&a).await.is_some());
+        assert!(tenants.tenant(&b).await.is_some());
+        assert!(tenants.tenant(&a).await.is_some());
+        assert_eq!(tenants.loaded(), vec![a.clone(), b.clone()]);
+
+        // Keys without a directory have no tenant unless creation is on
+        assert!(tenants.tenant(&c).await.is_none());
+        let config = Config::parse(&format!(
+            "[tenants]\nroot = \"{}\"\nmax_loaded = 2\ncreate = true\nkeys = [\"key:{}\"]\n",
+            root.path().display(),
+            c
+        ))
+        .unwrap();
+        let tenants = Arc::new(Tenants::new(&config).unwrap());
+        for key in [&a, &b, &a, &c] {
+            assert!(tenants.tenant(key).await.is_some());
+        }
+        // b was least recently used
+        assert_eq!(tenants.loaded(), vec![c.clone(), a.clone()]);
+        assert!(root.path().join(&c).is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_creation_limits() {
+        let root = TempDir::new().unwrap();
+        let [a, b, c] = ["a", "b", "c"].map(key_fingerprint);
+        std::fs::create_dir_all(root.path().join(&a)).unwrap();
+        let config = Config::parse(&format!(
+            "[tenants]\nroot = \"{}\"\ncreate = true\nkeys = [\"{}\", \"{}\"]\nmax_tenants = 2\n",
+            root.path().display(),
+            b,
+            c
+        ))
+        .unwrap();
+        let tenants = Arc::new(Tenants::new(&config).unwrap());
+
+        // Keys that aren't configured never get a directory
+        assert!(tenants.tenant(&key_fingerprint("stranger")).await.is_none());
+        assert!(!root.path().join(key_fingerprint("stranger")).exists());
+
+        // Configured keys do, until the root is full
+        assert!(tenants.tenant(&b).await.is_some());
+        assert!(tenants.tenant(&c).await.is_none());
+        assert!(!root.path().join(&c).exists());
+    }
+
+    #[tokio::test]
+    async fn test_evicted_tenant_in_use_is_reused() {
+        let root = TempDir::new().unwrap();
+        let [a, b] = ["a", "b"].map(key_fingerprint);
+        for key in [&a, &b] {
+            std::fs::create_dir_all(root.path().join(key)).unwrap();
+        }
+        let config = Config::parse(&format!("[tenants]\nroot = \"{}\"\nmax_loaded = 1\n", root.path().display())).unwrap();
+        let tenants = Arc::new(Tenants::new(&config).unwrap());
+
+        // A request still holds a's library when b pushes it out
+        let (held, _) = tenants.tenant(&a).await.unwrap();
+        assert!(tenants.tenant(&b).await.is_some());
+        assert_eq!(tenants.loaded(), vec![b.clone()]);
+
+        let (state, _) = tenants.tenant(&a).await.unwrap();
+        assert!(Arc::ptr_eq(&held, &state));
+
+        // Unloaded with nothing holding it, the library is gone
+        drop((held, state));
+        assert!(tenants.tenant(&b).await.is_some());
+        let weak = tenants.loaded.lock().evicted.get(&a).cloned().unwrap();
+        assert!(weak.upgrade().is_none());
+    }
+}
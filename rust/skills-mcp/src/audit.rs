@@ -0,0 +1,382 @@
+//! Append-only audit log of skill mutations.
+//!
+//! Every create, update, delete, rename, and other change to the library
+//! is recorded with who made it (API key fingerprint, session, or MCP),
+//! when, which skill, and a short summary of what changed. Entries are
+//! appended to a JSON Lines file that is rotated once it grows past
+//! [`AuditLog::MAX_FILE_BYTES`]; without a file the most recent entries are
+//! kept in memory.
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::models::SkillMeta;
+
+/// Default log file name, kept at the root of the skills directory.
+pub const LOG_FILE: &str = ".skills-audit.jsonl";
+
+/// Kind of mutation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// A skill was created.
+    Create,
+    /// A skill's metadata or content was changed.
+    Update,
+    /// A skill was deleted.
+    Delete,
+    /// A skill was renamed.
+    Rename,
+    /// A skill was copied under a new name.
+    Duplicate,
+    /// A skill was imported from another layout.
+    Import,
+    /// A skill was moved into cold storage.
+    Freeze,
+    /// A skill was restored from cold storage.
+    Restore,
+    /// A skill was hidden from listings and search.
+    Archive,
+    /// An archived skill was made visible again.
+    Unarchive,
+}
+
+/// One recorded mutation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// When the mutation happened.
+    #[serde(with = "crate::models::timestamp")]
+    pub at: DateTime<Utc>,
+
+    /// Who made it: `key:<fingerprint>`, `session:<id>`, `mcp`, or `anonymous`.
+    pub actor: String,
+
+    /// What was done.
+    pub action: AuditAction,
+
+    /// Skill affected.
+    pub skill: String,
+
+    /// What changed, e.g. `description, tags; SKILL.md +3 -1 lines`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+impl AuditEntry {
+    /// Create an entry stamped with the current time.
+    pub fn new(actor: impl Into<String>, action: AuditAction, skill: impl Into<String>) -> Self {
+        Self {
+            at: Utc::now(),
+            actor: actor.into(),
+            action,
+            skill: skill.into(),
+            summary: None,
+        }
+    }
+
+    /// Set the change summary.
+    pub fn with_summary(mut self, summary: impl Into<String>) -> Self {
+        let summary = summary.into();
+        self.summary = (!summary.is_empty()).then_some(summary);
+        self
+    }
+}
+
+/// Filters for querying the log.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditQuery {
+    /// Only entries at or after this time.
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only entries at or before this time.
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+
+    /// Only entries for this skill.
+    #[serde(default)]
+    pub skill: Option<String>,
+
+    /// Only entries by this actor.
+    #[serde(default)]
+    pub actor: Option<String>,
+
+    /// Maximum entries to return, newest first.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        self.since.is_none_or(|since| entry.at >= since)
+            && self.until.is_none_or(|until| entry.at <= until)
+            && self.skill.as_deref().is_none_or(|skill| entry.skill == skill)
+            && self.actor.as_deref().is_none_or(|actor| entry.actor == actor)
+    }
+}
+
+/// The audit log.
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    file: Mutex<Option<PathBuf>>,
+    recent: Mutex<VecDeque<AuditEntry>>,
+}
+
+impl AuditLog {
+    /// Size at which the log file is rotated.
+    pub const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+    /// Rotated files kept (`.1` is the newest).
+    pub const ROTATED_FILES: usize = 5;
+
+    /// Entries kept in memory when there is no log file.
+    const MEMORY_ENTRIES: usize = 1000;
+
+    /// Create a log that keeps recent entries in memory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append entries to the JSON Lines file at `path` from now on.
+    pub fn write_to(&self, path: impl Into<PathBuf>) {
+        *self.file.lock() = Some(path.into());
+    }
+
+    /// Path of the log file, if any.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.file.lock().clone()
+    }
+
+    /// Flush the log file to disk. Entries are written as they're recorded,
+    /// so this only forces the operating system's buffers out, e.g. before
+    /// shutting down.
+    pub fn sync(&self) -> Result<(), AuditError> {
+        let file = self.file.lock();
+        match file.as_deref() {
+            Some(path) if path.exists() => Ok(OpenOptions::new().append(true).open(path)?.sync_all()?),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record a mutation.
+    ///
+    /// Failures to write the file are logged, never returned: a mutation
+    /// that already happened must not fail because auditing did.
+    pub fn record(&self, entry: AuditEntry) {
+        let file = self.file.lock();
+        match file.as_deref() {
+            Some(path) => {
+                if let Err(e) = append(path, &entry) {
+                    tracing::warn!("Failed to write audit log {:?}: {}", path, e);
+                }
+            }
+            None => {
+                let mut recent = self.recent.lock();
+                recent.push_back(entry);
+                if recent.len() > Self::MEMORY_ENTRIES {
+                    recent.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Entries matching `query`, newest first.
+    pub fn query(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, AuditError> {
+        self.query_where(query, |_| true)
+    }
+
+    /// Entries matching a query that `keep` accepts too, newest first; the
+    /// limit counts kept entries only.
+    pub fn query_where(
+        &self,
+        query: &AuditQuery,
+        keep: impl Fn(&AuditEntry) -> bool,
+    ) -> Result<Vec<AuditEntry>, AuditError> {
+        let mut entries: Vec<AuditEntry> = match self.path() {
+            Some(path) => read_all(&path)?,
+            None => self.recent.lock().iter().cloned().collect(),
+        };
+        entries.retain(|e| query.matches(e) && keep(e));
+        entries.reverse();
+        if let Some(limit) = query.limit {
+            entries.truncate(limit);
+        }
+        Ok(entries)
+    }
+}
+
+/// Summarize a metadata and SKILL.md change for an audit entry.
+///
+/// Lists changed metadata fields, then SKILL.md line counts when the
+/// content changed.
+pub fn change_summary(
+    before: &SkillMeta,
+    after: &SkillMeta,
+    old_content: &str,
+    new_content: &str,
+) -> String {
+    let as_map = |meta: &SkillMeta| match serde_json::to_value(meta) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let (before, after) = (as_map(before), as_map(after));
+
+    let mut fields: Vec<&str> = before
+        .keys()
+        .chain(after.keys())
+        .filter(|k| before.get(*k) != after.get(*k))
+        .map(String::as_str)
+        .collect();
+    fields.sort_unstable();
+    fields.dedup();
+
+    let mut parts = Vec::new();
+    if !fields.is_empty() {
+        parts.push(fields.join(", "));
+    }
+    if old_content != new_content {
+        let (added, removed) = crate::diff::line_changes(old_content, new_content);
+        parts.push(format!("SKILL.md +{} -{} lines", added, removed));
+    }
+    parts.join("; ")
+}
+
+/// Append one entry, rotating the file first if it is full.
+fn append(path: &Path, entry: &AuditEntry) -> Result<(), AuditError> {
+    if fs::metadata(path).is_ok_and(|m| m.len() >= AuditLog::MAX_FILE_BYTES) {
+        rotate(path)?;
+    }
+
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Shift `log.N` to `log.N+1`, dropping the oldest, and move `log` to `log.1`.
+fn rotate(path: &Path) -> std::io::Result<()> {
+    let rotated = |n: usize| PathBuf::from(format!("{}.{}", path.display(), n));
+
+    let _ = fs::remove_file(rotated(AuditLog::ROTATED_FILES));
+    for n in (1..AuditLog::ROTATED_FILES).rev() {
+        if rotated(n).exists() {
+            fs::rename(rotated(n), rotated(n + 1))?;
+        }
+    }
+    fs::rename(path, rotated(1))
+}
+
+/// Read the rotated files and the live file, oldest entries first.
+fn read_all(path: &Path) -> Result<Vec<AuditEntry>, AuditError> {
+    let mut files: Vec<PathBuf> = (1..=AuditLog::ROTATED_FILES)
+        .rev()
+        .map(|n| PathBuf::from(format!("{}.{}", path.display(), n)))
+        .collect();
+    files.push(path.to_path_buf());
+
+    let mut entries = Vec::new();
+    for file in files.iter().filter(|f| f.is_file()) {
+        for line in BufReader::new(fs::File::open(file)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // Skip a line torn by a crash rather than failing the query
+            match serde_json::from_str(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => tracing::warn!("Skipping bad audit line in {:?}: {}", file, e),
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Audit log errors.
+#[derive(Debug, thiserror::Error)]
+pub enum AuditError {
+    /// The log couldn't be read or written.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// An entry couldn't be serialized.
+    #[error("Serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AuditLog::new();
+        log.write_to(temp_dir.path().join(LOG_FILE));
+
+        log.record(AuditEntry::new("key:abc", AuditAction::Create, "forms"));
+        log.record(AuditEntry::new("mcp", AuditAction::Update, "forms").with_summary("tags"));
+        log.record(AuditEntry::new("key:abc", AuditAction::Delete, "testing"));
+
+        let all = log.query(&AuditQuery::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].action, AuditAction::Delete);
+
+        let forms = log
+            .query(&AuditQuery {
+                skill: Some("forms".to_string()),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(forms.len(), 1);
+        assert_eq!(forms[0].summary.as_deref(), Some("tags"));
+
+        let future = log
+            .query(&AuditQuery {
+                since: Some(Utc::now() + chrono::Duration::hours(1)),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(future.is_empty());
+    }
+
+    #[test]
+    fn test_rotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(LOG_FILE);
+        fs::write(&path, "x".repeat(AuditLog::MAX_FILE_BYTES as usize)).unwrap();
+
+        append(&path, &AuditEntry::new("mcp", AuditAction::Create, "forms")).unwrap();
+
+        assert!(PathBuf::from(format!("{}.1", path.display())).is_file());
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_change_summary() {
+        let before = SkillMeta {
+            name: "forms".to_string(),
+            description: "Forms".to_string(),
+            ..Default::default()
+        };
+        let after = SkillMeta {
+            description: "Form handling".to_string(),
+            tags: vec!["input".to_string()],
+            ..before.clone()
+        };
+
+        assert_eq!(
+            change_summary(&before, &after, "a\nb\n", "a\nc\n"),
+            "description, tags; SKILL.md +1 -1 lines"
+        );
+        assert_eq!(change_summary(&before, &before, "a", "a"), "");
+    }
+}
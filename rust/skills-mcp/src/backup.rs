@@ -0,0 +1,527 @@
+//! Backups of the skills directory.
+//!
+//! A backup is a ZIP archive named `skills-<UTC timestamp>.zip` in the
+//! configured backup directory. Files of the skills directory are stored
+//! under `skills/`, and `index.json` holds the skill index as it was being
+//! served, so a backup records what was live as well as what was on disk.
+//! A backup directory inside the skills directory is left out of its own
+//! backups; an underscore-prefixed name such as `_backups` also keeps it out
+//! of the index.
+//!
+//! Restoring replaces every top-level entry of the skills directory except
+//! dot-files, which hold the server's own state (audit log, analytics,
+//! integrity manifest), and the backup directory. A backup of the current
+//! state is taken first, so a restore can itself be undone.
+//!
+//! Backups are configured in the `[backup]` section of `skills-mcp.toml`:
+//!
+//! ```toml
+//! [backup]
+//! path = "/var/backups/skills"
+//! schedule = "0 3 * * *"
+//! keep = 14
+//! ```
+
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::models::{ErrorCode, SkillIndex};
+use crate::schedule::Schedule;
+
+/// Archive entry holding the skill index.
+const INDEX_ENTRY: &str = "index.json";
+
+/// Archive directory holding the skills directory's files.
+const FILES_PREFIX: &str = "skills/";
+
+/// Prefix of backup ids and archive names.
+const ID_PREFIX: &str = "skills-";
+
+/// Timestamp format in backup ids.
+const ID_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Backup settings from the `[backup]` section.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// Directory backups are written to, relative to the skills directory
+    /// unless absolute. Backups are disabled when unset.
+    pub path: Option<PathBuf>,
+
+    /// When to take backups automatically, as a cron expression in UTC.
+    pub schedule: Option<Schedule>,
+
+    /// Number of backups to keep; older ones are deleted after each new
+    /// backup. All are kept when unset.
+    pub keep: Option<usize>,
+}
+
+/// A backup archive.
+#[derive(Debug, Clone, Serialize)]
+pub struct Backup {
+    /// Backup id, the archive name without `.zip`.
+    pub id: String,
+
+    /// When the backup was taken.
+    #[serde(with = "crate::models::timestamp")]
+    pub created_at: DateTime<Utc>,
+
+    /// Archive size in bytes.
+    pub size_bytes: u64,
+
+    /// Files backed up.
+    pub files: usize,
+
+    /// Skills in the index when the backup was taken.
+    pub skills: usize,
+}
+
+/// What restoring a backup changes, by top-level entry of the skills
+/// directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RestorePlan {
+    /// Entries the backup brings back.
+    pub added: Vec<String>,
+
+    /// Entries replaced with their backed-up version.
+    pub replaced: Vec<String>,
+
+    /// Entries not in the backup, which are removed.
+    pub removed: Vec<String>,
+}
+
+/// Backup directory of a skills directory.
+pub struct Backups {
+    skills_dir: PathBuf,
+    dir: PathBuf,
+    keep: Option<usize>,
+}
+
+impl Backups {
+    /// Create a handle writing backups of `skills_dir` to `dir`, resolved
+    /// against the skills directory when relative.
+    pub fn new(skills_dir: impl AsRef<Path>, dir: impl AsRef<Path>) -> Self {
+        let skills_dir = skills_dir.as_ref().to_path_buf();
+        let dir = skills_dir.join(dir);
+        Self {
+            skills_dir,
+            dir,
+            keep: None,
+        }
+    }
+
+    /// Keep only the `keep` newest backups, deleting older ones after each
+    /// new backup.
+    pub fn with_keep(mut self, keep: Option<usize>) -> Self {
+        self.keep = keep;
+        self
+    }
+
+    /// Create a handle from the `[backup]` settings.
+    pub fn from_config(skills_dir: impl AsRef<Path>, config: &BackupConfig) -> Result<Self, BackupError> {
+        let dir = config.path.as_ref().ok_or(BackupError::NotConfigured)?;
+        Ok(Self::new(skills_dir, dir).with_keep(config.keep))
+    }
+
+    /// Directory holding the archives.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Archive the skills directory and `index`, then prune old backups.
+    pub fn create(&self, index: &SkillIndex) -> Result<Backup, BackupError> {
+        let backup = self.write_backup(index)?;
+        if let Some(keep) = self.keep {
+            for id in self.prune(keep)? {
+                tracing::info!("Pruned backup {}", id);
+            }
+        }
+        Ok(backup)
+    }
+
+    fn write_backup(&self, index: &SkillIndex) -> Result<Backup, BackupError> {
+        fs::create_dir_all(&self.dir)?;
+        let id = self.new_id();
+        let path = self.archive_path(&id)?;
+        // Written under another name first, so listings never see half an archive
+        let partial = path.with_extension("zip.partial");
+
+        let result = self.write_archive(&partial, index);
+        if let Err(e) = result {
+            let _ = fs::remove_file(&partial);
+            return Err(e);
+        }
+        fs::rename(&partial, &path)?;
+
+        tracing::info!("Backed up {} to {:?}", self.skills_dir.display(), path);
+        self.get(&id)
+    }
+
+    fn write_archive(&self, path: &Path, index: &SkillIndex) -> Result<(), BackupError> {
+        let mut zip = ZipWriter::new(File::create(path)?);
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(true);
+
+        zip.start_file(INDEX_ENTRY, options)?;
+        serde_json::to_writer_pretty(&mut zip, index)?;
+
+        for entry in WalkDir::new(&self.skills_dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.path() != self.dir)
+            .filter_map(|e| e.ok())
+        {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(&self.skills_dir).unwrap_or(entry.path());
+            let name = format!("{}{}", FILES_PREFIX, relative.to_string_lossy().replace('\\', "/"));
+            zip.start_file(name, options)?;
+            io::copy(&mut File::open(entry.path())?, &mut zip)?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+
+    /// A fresh id for a backup taken now.
+    fn new_id(&self) -> String {
+        let base = format!("{}{}", ID_PREFIX, Utc::now().format(ID_TIME_FORMAT));
+        let mut id = base.clone();
+        let mut n = 1;
+        while self.dir.join(format!("{}.zip", id)).exists() {
+            id = format!("{}-{}", base, n);
+            n += 1;
+        }
+        id
+    }
+
+    /// Path of a backup's archive, rejecting ids that aren't plain names.
+    fn archive_path(&self, id: &str) -> Result<PathBuf, BackupError> {
+        let valid = id.starts_with(ID_PREFIX)
+            && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(BackupError::InvalidId(id.to_string()));
+        }
+        Ok(self.dir.join(format!("{}.zip", id)))
+    }
+
+    /// Look up one backup.
+    pub fn get(&self, id: &str) -> Result<Backup, BackupError> {
+        let path = self.archive_path(id)?;
+        if !path.is_file() {
+            return Err(BackupError::NotFound(id.to_string()));
+        }
+
+        let size_bytes = fs::metadata(&path)?.len();
+        let mut archive = ZipArchive::new(File::open(&path)?)?;
+        let files = archive.file_names().filter(|n| n.starts_with(FILES_PREFIX)).count();
+        let index: SkillIndex = serde_json::from_reader(archive.by_name(INDEX_ENTRY)?)?;
+
+        // The id records when the backup was taken; copied archives keep it
+        let created_at = NaiveDateTime::parse_from_str(
+            id[ID_PREFIX.len()..].split('-').next().unwrap_or_default(),
+            ID_TIME_FORMAT,
+        )
+        .map(|t| t.and_utc())
+        .unwrap_or(index.last_updated);
+
+        Ok(Backup {
+            id: id.to_string(),
+            created_at,
+            size_bytes,
+            files,
+            skills: index.skills.len(),
+        })
+    }
+
+    /// List backups, newest first. Unreadable archives are skipped.
+    pub fn list(&self) -> Result<Vec<Backup>, BackupError> {
+        if !self.dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut backups = Vec::new();
+        for entry in fs::read_dir(&self.dir)?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(id) = name.strip_suffix(".zip").filter(|id| id.starts_with(ID_PREFIX)) else {
+                continue;
+            };
+            match self.get(id) {
+                Ok(backup) => backups.push(backup),
+                Err(e) => tracing::warn!("Skipping unreadable backup {:?}: {}", entry.path(), e),
+            }
+        }
+
+        backups.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+        Ok(backups)
+    }
+
+    /// Delete all but the `keep` newest backups, returning the deleted ids.
+    pub fn prune(&self, keep: usize) -> Result<Vec<String>, BackupError> {
+        let mut pruned = Vec::new();
+        for backup in self.list()?.into_iter().skip(keep) {
+            fs::remove_file(self.archive_path(&backup.id)?)?;
+            pruned.push(backup.id);
+        }
+        Ok(pruned)
+    }
+
+    /// What restoring a backup would change, without changing anything.
+    pub fn plan_restore(&self, id: &str) -> Result<RestorePlan, BackupError> {
+        let mut archive = self.open(id)?;
+        let backed_up: BTreeSet<String> = self
+            .restorable_entries(&mut archive)?
+            .iter()
+            .filter_map(|(_, path)| top_level(path))
+            .collect();
+        let current = self.current_entries()?;
+
+        Ok(RestorePlan {
+            added: backed_up.difference(&current).cloned().collect(),
+            replaced: backed_up.intersection(&current).cloned().collect(),
+            removed: current.difference(&backed_up).cloned().collect(),
+        })
+    }
+
+    /// Restore a backup over the skills directory.
+    ///
+    /// The current state, with `index`, is backed up first; that backup is
+    /// returned with what the restore changed.
+    pub fn restore(&self, id: &str, index: &SkillIndex) -> Result<(RestorePlan, Backup), BackupError> {
+        let plan = self.plan_restore(id)?;
+        let mut archive = self.open(id)?;
+        // Read the whole entry list before touching anything
+        let files = self.restorable_entries(&mut archive)?;
+
+        // Not pruned, which could delete the backup being restored
+        let safety = self.write_backup(index)?;
+
+        for name in plan.replaced.iter().chain(&plan.removed) {
+            let path = self.skills_dir.join(name);
+            if path.is_dir() && !path.is_symlink() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+        }
+
+        for (i, relative) in files {
+            let target = self.skills_dir.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            io::copy(&mut archive.by_index(i)?, &mut File::create(target)?)?;
+        }
+
+        tracing::info!("Restored backup {} ({} taken first)", id, safety.id);
+        Ok((plan, safety))
+    }
+
+    fn open(&self, id: &str) -> Result<ZipArchive<File>, BackupError> {
+        let path = self.archive_path(id)?;
+        if !path.is_file() {
+            return Err(BackupError::NotFound(id.to_string()));
+        }
+        Ok(ZipArchive::new(File::open(path)?)?)
+    }
+
+    /// Files of an archive that a restore writes, by entry index and path
+    /// relative to the skills directory.
+    fn restorable_entries(&self, archive: &mut ZipArchive<File>) -> Result<Vec<(usize, PathBuf)>, BackupError> {
+        let mut files = Vec::new();
+
+        for i in 0..archive.len() {
+            let file = archive.by_index(i)?;
+            if file.is_dir() || file.name() == INDEX_ENTRY {
+                continue;
+            }
+            let relative = file
+                .enclosed_name()
+                .and_then(|path| path.strip_prefix(FILES_PREFIX).ok().map(Path::to_path_buf))
+                .ok_or_else(|| BackupError::Invalid(format!("unexpected entry '{}'", file.name())))?;
+
+            if top_level(&relative).is_some_and(|top| !self.is_preserved(&top)) {
+                files.push((i, relative));
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Top-level entries of the skills directory a restore may replace.
+    fn current_entries(&self) -> Result<BTreeSet<String>, BackupError> {
+        let mut entries = BTreeSet::new();
+        if self.skills_dir.is_dir() {
+            for entry in fs::read_dir(&self.skills_dir)?.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if !self.is_preserved(&name) {
+                    entries.insert(name);
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Whether a top-level entry is left alone by restores: dot-files and
+    /// the entry holding the backup directory, however deep it's nested.
+    fn is_preserved(&self, name: &str) -> bool {
+        name.starts_with('.') || self.dir.starts_with(self.skills_dir.join(name))
+    }
+}
+
+/// First component of a relative path.
+fn top_level(path: &Path) -> Option<String> {
+    path.components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+}
+
+/// Errors from taking, listing, or restoring backups.
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    /// No backup directory is configured.
+    #[error("Backups are not configured; set [backup] path in skills-mcp.toml")]
+    NotConfigured,
+
+    /// No backup has the id.
+    #[error("Backup '{0}' not found")]
+    NotFound(String),
+
+    /// The id isn't a backup id.
+    #[error("Invalid backup id '{0}'")]
+    InvalidId(String),
+
+    /// The archive has entries a backup never writes.
+    #[error("Invalid backup archive: {0}")]
+    Invalid(String),
+
+    /// Failed to read or write files.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    /// Failed to read or write the archive.
+    #[error("Archive error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    /// Failed to encode or decode the archived index.
+    #[error("Index error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl BackupError {
+    /// The machine-readable code for this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::NotConfigured => ErrorCode::Disabled,
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::InvalidId(_) => ErrorCode::InvalidRequest,
+            Self::Io(_) => ErrorCode::IoError,
+            Self::Invalid(_) | Self::Zip(_) | Self::Json(_) => ErrorCode::InternalError,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn index(skills: &[&str]) -> SkillIndex {
+        let mut index = SkillIndex::new();
+        index.skills = skills
+            .iter()
+            .map(|name| crate::models::SkillMeta {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        index
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path();
+        fs::create_dir_all(skills_dir.join("forms/references")).unwrap();
+        fs::write(skills_dir.join("forms/SKILL.md"), "# Forms").unwrap();
+        fs::write(skills_dir.join("forms/references/zod.md"), "# Zod").unwrap();
+        fs::write(skills_dir.join(".skills-audit.jsonl"), "").unwrap();
+
+        let backups = Backups::new(skills_dir, "_backups");
+        let backup = backups.create(&index(&["forms"])).unwrap();
+        assert_eq!((backup.files, backup.skills), (3, 1));
+
+        // Change the library after the backup
+        fs::write(skills_dir.join("forms/SKILL.md"), "# Forms v2").unwrap();
+        fs::create_dir_all(skills_dir.join("testing")).unwrap();
+        fs::write(skills_dir.join("testing/SKILL.md"), "# Testing").unwrap();
+        fs::write(skills_dir.join(".skills-audit.jsonl"), "kept").unwrap();
+
+        let plan = backups.plan_restore(&backup.id).unwrap();
+        assert_eq!(plan.replaced, vec!["forms"]);
+        assert_eq!(plan.removed, vec!["testing"]);
+        assert!(plan.added.is_empty());
+
+        let (_, safety) = backups.restore(&backup.id, &index(&["forms", "testing"])).unwrap();
+        assert_eq!(fs::read_to_string(skills_dir.join("forms/SKILL.md")).unwrap(), "# Forms");
+        assert!(skills_dir.join("forms/references/zod.md").is_file());
+        assert!(!skills_dir.join("testing").exists());
+        assert_eq!(fs::read_to_string(skills_dir.join(".skills-audit.jsonl")).unwrap(), "kept");
+
+        // The state before the restore was backed up and can be restored
+        let ids: Vec<_> = backups.list().unwrap().into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec![safety.id.clone(), backup.id.clone()]);
+        backups.restore(&safety.id, &index(&["forms"])).unwrap();
+        assert!(skills_dir.join("testing/SKILL.md").is_file());
+
+        let backups = backups.with_keep(Some(2));
+        let latest = backups.create(&index(&["forms", "testing"])).unwrap();
+        let ids: Vec<_> = backups.list().unwrap().into_iter().map(|b| b.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], latest.id);
+    }
+
+    #[test]
+    fn test_nested_backup_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let skills_dir = temp_dir.path();
+        fs::create_dir_all(skills_dir.join("forms")).unwrap();
+        fs::write(skills_dir.join("forms/SKILL.md"), "# Forms").unwrap();
+
+        let backups = Backups::new(skills_dir, "_state/backups");
+        let backup = backups.create(&index(&["forms"])).unwrap();
+        assert_eq!(backup.files, 1);
+
+        let plan = backups.plan_restore(&backup.id).unwrap();
+        assert!(plan.removed.is_empty());
+
+        // Restoring keeps the backups, including the safety backup
+        let (_, safety) = backups.restore(&backup.id, &index(&["forms"])).unwrap();
+        assert!(backups.get(&backup.id).is_ok());
+        assert!(backups.get(&safety.id).is_ok());
+        assert_eq!(backups.list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_backup_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let backups = Backups::new(temp_dir.path(), "_backups");
+
+        assert!(matches!(backups.get("../etc/passwd"), Err(BackupError::InvalidId(_))));
+        assert!(matches!(backups.get("skills-20260101T000000Z"), Err(BackupError::NotFound(_))));
+        assert!(matches!(
+            Backups::from_config(temp_dir.path(), &BackupConfig::default()),
+            Err(BackupError::NotConfigured)
+        ));
+    }
+}
@@ -3,12 +3,22 @@
 //! Run with: cargo run --bin skills-api-server -- [OPTIONS]
 
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 use clap::Parser;
-use tracing::info;
+use tracing::{info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use skills_mcp::abuse::AbuseConfig;
+use skills_mcp::analytics;
+use skills_mcp::audit;
 use skills_mcp::api::ApiServer;
+use skills_mcp::config::Config;
+use skills_mcp::index::{FileWatcher, MetaPrecedence, PollConfig, PollingWatcher, SkillRoot};
+use skills_mcp::integrity::IntegrityMode;
+use skills_mcp::logging::{JsonFields, JsonFormat, LogFormat};
+use skills_mcp::models::FallbackConfig;
 
 /// Skills API Server
 #[derive(Parser, Debug)]
@@ -24,9 +34,98 @@ struct Args {
     #[arg(short, long, default_value = "5050", env = "PORT")]
     port: u16,
 
+    /// Expose per-skill read receipts at /api/skills/:name/readers
+    #[arg(long, env = "SKILLS_READ_RECEIPTS")]
+    read_receipts: bool,
+
+    /// Reject reads from callers flagged by abuse detection
+    #[arg(long, env = "SKILLS_THROTTLE_ABUSE")]
+    throttle_abuse: bool,
+
+    /// Integrity check against .skills-manifest.json: off, warn, or enforce
+    #[arg(long, default_value = "warn", env = "SKILLS_INTEGRITY")]
+    integrity: IntegrityMode,
+
+    /// Poll the skills directory for changes every N seconds (for storage
+    /// without filesystem events, e.g. network mounts)
+    #[arg(long, env = "SKILLS_POLL_INTERVAL")]
+    poll_interval: Option<u64>,
+
+    /// Compare file hashes when polling, not just size and mtime
+    #[arg(long, requires = "poll_interval")]
+    poll_hash: bool,
+
+    /// Watch the skills roots for filesystem events and reindex changes
+    #[arg(long, env = "SKILLS_WATCH", conflicts_with = "poll_interval")]
+    watch: bool,
+
+    /// Quiet time, in milliseconds, before a burst of changes is reindexed
+    #[arg(long, default_value = "500", env = "SKILLS_WATCH_DEBOUNCE_MS")]
+    watch_debounce_ms: u64,
+
+    /// Metadata source that wins when a skill has both _meta.json and
+    /// SKILL.md frontmatter: meta-json or frontmatter
+    #[arg(long, default_value = "meta-json", env = "SKILLS_META_PRECEDENCE")]
+    meta_precedence: MetaPrecedence,
+
+    /// Additional skills roots, lowest priority last. Append `:ro` to serve
+    /// a root read-only (e.g. a shared team library)
+    #[arg(long = "root", value_name = "PATH[:ro]", env = "SKILLS_ROOTS", value_delimiter = ',')]
+    roots: Vec<SkillRoot>,
+
+    /// Start in maintenance mode, rejecting writes with an optional message
+    #[arg(long, value_name = "MESSAGE", num_args = 0..=1, default_missing_value = "")]
+    maintenance: Option<String>,
+
+    /// Skill served, with zero-result diagnostics, when a search finds
+    /// no confident match (e.g. a guide to requesting new skills)
+    #[arg(long, env = "SKILLS_FALLBACK_SKILL")]
+    fallback_skill: Option<String>,
+
+    /// Best-result score below which the fallback skill is also served
+    #[arg(long, default_value = "0", env = "SKILLS_FALLBACK_MIN_SCORE", requires = "fallback_skill")]
+    fallback_min_score: f64,
+
+    /// JSON file keeping usage analytics across restarts
+    /// [default: <skills-dir>/.skills-analytics.json]
+    #[arg(long, env = "SKILLS_ANALYTICS_STORE")]
+    analytics_store: Option<PathBuf>,
+
+    /// JSON Lines file recording every skill mutation
+    /// [default: <skills-dir>/.skills-audit.jsonl]
+    #[arg(long, env = "SKILLS_AUDIT_LOG")]
+    audit_log: Option<PathBuf>,
+
+    /// JSON Lines file journaling the files each mutation changes, for
+    /// `skills-mcp replay` (e.g. <skills-dir>/.skills-journal.jsonl)
+    #[arg(long, env = "SKILLS_JOURNAL")]
+    journal: Option<PathBuf>,
+
+    /// Keep analytics and the audit log in memory only, writing no state
+    /// files; both start empty on every restart
+    #[arg(long, env = "SKILLS_NO_DB", conflicts_with_all = ["analytics_store", "audit_log", "journal"])]
+    no_db: bool,
+
+    /// Configuration file
+    /// [default: <skills-dir>/skills-mcp.toml or ./skills-mcp.toml]
+    #[arg(long, env = "SKILLS_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Deliver a weekly digest through the notification adapters
+    #[arg(long, env = "SKILLS_WEEKLY_DIGEST")]
+    weekly_digest: bool,
+
+    /// Seconds in-flight requests get to finish after SIGTERM or Ctrl+C
+    #[arg(long, default_value = "30", env = "SKILLS_DRAIN_TIMEOUT")]
+    drain_timeout: u64,
+
     /// Enable debug logging
     #[arg(short, long)]
     debug: bool,
+
+    /// Log output: text, or json for one object per line
+    #[arg(long, default_value = "text", env = "SKILLS_LOG_FORMAT")]
+    log_format: LogFormat,
 }
 
 #[tokio::main]
@@ -40,9 +139,11 @@ async fn main() -> anyhow::Result<()> {
         "skills_mcp=info,tower_http=info,warn"
     };
 
+    let json = args.log_format == LogFormat::Json;
     tracing_subscriber::registry()
         .with(tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| filter.into()))
-        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .with((!json).then(|| tracing_subscriber::fmt::layer().with_target(false)))
+        .with(json.then(|| tracing_subscriber::fmt::layer().event_format(JsonFormat).fmt_fields(JsonFields)))
         .init();
 
     // Determine skills directory
@@ -63,23 +164,146 @@ async fn main() -> anyhow::Result<()> {
     });
 
     info!("Skills directory: {:?}", skills_dir);
+    if let Ok(changes) = skills_mcp::lifecycle::plan_name_normalization(&skills_dir) {
+        if !changes.is_empty() {
+            warn!(
+                "{} skill directories have invalid names; run `skills-mcp normalize-names --dry-run` to review renames",
+                changes.len()
+            );
+        }
+    }
+    let config = match args.config.or_else(|| Config::find(&skills_dir)) {
+        Some(path) => {
+            info!("Configuration: {:?}", path);
+            Config::load(&path).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?
+        }
+        None => Config::default(),
+    };
     info!(
         "Starting Skills API Server v{} on port {}",
         skills_mcp::VERSION,
         args.port
     );
 
-    let server = ApiServer::with_port(&skills_dir, args.port);
+    let server = ApiServer::with_port(&skills_dir, args.port)
+        .with_read_receipts(args.read_receipts)
+        .with_meta_precedence(args.meta_precedence)
+        .with_extra_roots(args.roots)
+        .with_config(config)
+        .with_abuse_config(AbuseConfig {
+            throttle: args.throttle_abuse,
+            ..Default::default()
+        })
+        .with_drain_timeout(Duration::from_secs(args.drain_timeout));
+    let server = if args.no_db {
+        info!("Analytics and audit log kept in memory only");
+        server
+    } else {
+        server
+            .with_analytics_store(
+                args.analytics_store
+                    .unwrap_or_else(|| skills_dir.join(analytics::STORE_FILE)),
+            )
+            .with_audit_log(
+                args.audit_log
+                    .unwrap_or_else(|| skills_dir.join(audit::LOG_FILE)),
+            )
+    };
+    let server = match args.journal {
+        Some(path) => server.with_journal(path),
+        None => server,
+    };
+    let server = match args.fallback_skill {
+        Some(skill) => server.with_fallback_skill(FallbackConfig::new(skill).min_score(args.fallback_min_score)),
+        None => server,
+    };
+    let server = match args.maintenance {
+        Some(message) => server.with_maintenance(Some(message)),
+        None => server,
+    };
+
+    // Verify the library against its manifest, if present
+    if args.integrity != IntegrityMode::Off {
+        match server.verify_integrity() {
+            Ok(Some(report)) if !report.is_verified() => {
+                if args.integrity == IntegrityMode::Enforce {
+                    anyhow::bail!(
+                        "Refusing to serve: skills directory does not match its integrity manifest"
+                    );
+                }
+                warn!("Serving a skills directory that does not match its integrity manifest");
+            }
+            Ok(Some(_)) => info!("Skills directory matches its integrity manifest"),
+            Ok(None) => {}
+            Err(e) if args.integrity == IntegrityMode::Enforce => {
+                anyhow::bail!("Integrity verification failed: {}", e);
+            }
+            Err(e) => warn!("Integrity verification failed: {}", e),
+        }
+    }
+
+    // Start polling for changes if requested; polling stops when dropped
+    let poller = match args.poll_interval {
+        Some(secs) => {
+            let config = PollConfig {
+                interval: Duration::from_secs(secs.max(1)),
+                hash_contents: args.poll_hash,
+            };
+            let indexer = Arc::clone(&server.state().indexer);
+            Some(PollingWatcher::new(indexer, config).start()?)
+        }
+        None => None,
+    };
+
+    // Or watch for filesystem events; watching stops when dropped
+    let watcher = if args.watch {
+        let indexer = Arc::clone(&server.state().indexer);
+        let debounce = Duration::from_millis(args.watch_debounce_ms);
+        let mut watcher = FileWatcher::with_debounce(Arc::clone(&indexer), debounce)?;
+        for root in indexer.roots() {
+            watcher.watch(&root.path)?;
+        }
+        Some(watcher)
+    } else {
+        None
+    };
+
+    if args.weekly_digest {
+        server.spawn_weekly_digest();
+    }
+    server.spawn_scheduled_backups();
+
+    server.run_with_shutdown(shutdown_signal()).await?;
+
+    // Stop reindexing before the process exits, so no update is cut short
+    drop(watcher);
+    if let Some(poller) = poller {
+        poller.stop();
+    }
 
-    // Set up graceful shutdown
-    let shutdown = async {
+    Ok(())
+}
+
+/// Complete on Ctrl+C or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
         tokio::signal::ctrl_c()
             .await
             .expect("Failed to install Ctrl+C handler");
-        info!("Shutdown signal received");
     };
 
-    server.run_with_shutdown(shutdown).await?;
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
 
-    Ok(())
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
@@ -0,0 +1,774 @@
+//! Command-line tool for offline skill management.
+//!
+//! Run with: cargo run --bin skills-mcp -- <COMMAND>
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand};
+
+use skills_mcp::config::Config;
+use skills_mcp::diff::{diff_libraries, LibrarySnapshot};
+use skills_mcp::doctor::{Doctor, DoctorSeverity};
+use skills_mcp::import::{ImportFormat, ImportMode};
+use skills_mcp::index::SkillIndexer;
+use skills_mcp::integrity::{Manifest, MANIFEST_FILE};
+use skills_mcp::journal::{self, JOURNAL_FILE};
+use skills_mcp::lint::{self, Severity, RULES};
+use skills_mcp::models::{key_fingerprint, timestamp, FieldBoosts, SearchOptions, SkillTemplate};
+use skills_mcp::search::{parse_trigger_cases, test_triggers, SearchService, DEFAULT_TRIGGER_MIN_SCORE};
+use skills_mcp::validation::validate_skills;
+
+/// Skills CLI
+#[derive(Parser, Debug)]
+#[command(name = "skills-mcp")]
+#[command(about = "Manage and validate skills without running a server")]
+#[command(version)]
+struct Args {
+    /// Path to the skills directory
+    #[arg(short, long, global = true, env = "SKILLS_DIR")]
+    skills_dir: Option<PathBuf>,
+
+    /// Emit machine-readable JSON output
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Validate all skills; exits non-zero if any errors are found
+    Validate,
+
+    /// Build the index and print a summary
+    Index,
+
+    /// Search skills by metadata, or by content with --content
+    Search {
+        /// Search query
+        query: String,
+
+        /// Maximum number of results
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// Search full-text content instead of metadata
+        #[arg(long)]
+        content: bool,
+
+        /// With --content, only search files of this type (repeatable)
+        #[arg(long = "file-type")]
+        file_types: Vec<String>,
+
+        /// Field boosts for this search, e.g. tags:4,body:0.5
+        #[arg(long)]
+        boost: Option<String>,
+    },
+
+    /// Scaffold a new skill from a template
+    New {
+        /// Skill name (lowercase, hyphens only)
+        name: String,
+
+        /// Skill description
+        #[arg(short, long)]
+        description: String,
+
+        /// Template: minimal, standard, or with-sub-skills
+        #[arg(short, long, default_value = "standard")]
+        template: SkillTemplate,
+
+        /// Tags for discoverability
+        #[arg(long, value_delimiter = ',')]
+        tags: Vec<String>,
+    },
+
+    /// Write an integrity manifest of file hashes for the skills directory
+    Manifest,
+
+    /// Verify the skills directory against its integrity manifest
+    Verify,
+
+    /// Check the skills directory, metadata, and server state files; exits
+    /// non-zero if any error is left unfixed
+    Doctor {
+        /// Repair what can be: rewrite recoverable `_meta.json` files (kept
+        /// as `_meta.json.bak`) and register orphaned sub-skill files
+        #[arg(long)]
+        fix: bool,
+
+        /// Journal to check [default: <skills-dir>/.skills-journal.jsonl]
+        #[arg(long)]
+        journal: Option<PathBuf>,
+
+        /// Analytics store to check
+        /// [default: <skills-dir>/.skills-analytics.json]
+        #[arg(long)]
+        analytics_store: Option<PathBuf>,
+    },
+
+    /// Compare two libraries (directories, .zip snapshots, or http(s):// server
+    /// URLs); exits non-zero if they differ
+    Diff {
+        /// First library
+        a: String,
+
+        /// Second library
+        b: String,
+
+        /// Only list changed skills and files, without content diffs
+        #[arg(long)]
+        stat: bool,
+    },
+
+    /// Import a skill authored in another layout
+    Import {
+        /// Path to the skill directory to import
+        path: PathBuf,
+
+        /// Source layout
+        #[arg(short, long, default_value = "claude")]
+        format: ImportFormat,
+
+        /// Name for the imported skill (defaults to the source's own name)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Merge into the skill if it already exists, keeping local edits
+        #[arg(long)]
+        merge: bool,
+    },
+
+    /// Export a skill as a ZIP archive
+    Export {
+        /// Skill name to export
+        name: String,
+
+        /// Output file (defaults to <name>.zip)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Exclude the scripts/ directory
+        #[arg(long)]
+        no_scripts: bool,
+    },
+
+    /// Rename skill directories that break the naming rules, updating
+    /// dependencies and links; exits non-zero if any renames remain
+    NormalizeNames {
+        /// Only report planned renames (the default)
+        #[arg(long, conflicts_with = "apply")]
+        dry_run: bool,
+
+        /// Perform the renames
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Check skills against the lint rules; exits non-zero if any finding
+    /// has error severity
+    Lint {
+        /// Configuration file
+        /// [default: <skills-dir>/skills-mcp.toml or ./skills-mcp.toml]
+        #[arg(long, env = "SKILLS_CONFIG")]
+        config: Option<PathBuf>,
+
+        /// List the rules and their configured severities instead
+        #[arg(long)]
+        list_rules: bool,
+    },
+
+    /// Test a file of sample prompts against a skill's sub-skill triggers;
+    /// exits non-zero if any prompt selects other sub-skills than expected
+    TestTriggers {
+        /// Skill whose sub-skills are tested
+        skill: String,
+
+        /// Prompts file: one phrase per line, expected sub-skills after `=>`
+        file: PathBuf,
+
+        /// Score a sub-skill needs to be selected
+        #[arg(long, default_value_t = DEFAULT_TRIGGER_MIN_SCORE)]
+        min_score: f64,
+    },
+
+    /// Reapply a mutation journal to rebuild a skills directory
+    Replay {
+        /// Directory to rebuild; must be empty unless replaying --from a
+        /// point in time onto the library as it was then
+        target: PathBuf,
+
+        /// Journal to replay [default: <skills-dir>/.skills-journal.jsonl]
+        #[arg(long)]
+        journal: Option<PathBuf>,
+
+        /// Only replay entries recorded at or after this RFC 3339 time
+        #[arg(long, value_parser = timestamp::parse)]
+        from: Option<chrono::DateTime<chrono::Utc>>,
+    },
+
+    /// Print the fingerprint of an API key, for `allowed_keys` in
+    /// `_meta.json`
+    KeyFingerprint {
+        /// The API key
+        #[arg(env = "SKILLS_API_KEY", hide_env_values = true)]
+        key: String,
+    },
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    // Determine skills directory
+    let skills_dir = args.skills_dir.clone().unwrap_or_else(|| {
+        // Try common locations
+        let candidates = [
+            PathBuf::from("./skills"),
+            PathBuf::from("../skills"),
+            dirs::home_dir()
+                .map(|h| h.join(".skills"))
+                .unwrap_or_default(),
+        ];
+
+        candidates
+            .into_iter()
+            .find(|p| p.exists())
+            .unwrap_or_else(|| PathBuf::from("./skills"))
+    });
+
+    match run(&args, skills_dir) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args, skills_dir: PathBuf) -> anyhow::Result<ExitCode> {
+    match &args.command {
+        Command::Validate => {
+            let indexer = load_indexer(skills_dir)?;
+            let result = validate_skills(indexer);
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                for error in &result.errors {
+                    println!("error: {}", error);
+                }
+                for warning in &result.warnings {
+                    println!("warning: {}", warning);
+                }
+                println!(
+                    "{} skills checked: {} errors, {} warnings",
+                    result.skills_checked,
+                    result.errors.len(),
+                    result.warnings.len()
+                );
+            }
+
+            Ok(if result.valid {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Command::Index => {
+            let indexer = load_indexer(skills_dir)?;
+            let skill_index = indexer.get_skill_index();
+            let content_index = indexer.get_content_index();
+
+            if args.json {
+                let summary = serde_json::json!({
+                    "skill_count": skill_index.len(),
+                    "content_entries": content_index.len(),
+                    "validation_errors": skill_index.validation_errors,
+                });
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                for skill in &skill_index.skills {
+                    println!("{:<30} {}", skill.name, skill.description);
+                }
+                for error in &skill_index.validation_errors {
+                    println!("error: {}", error);
+                }
+                println!(
+                    "{} skills, {} content entries",
+                    skill_index.len(),
+                    content_index.len()
+                );
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Command::Search {
+            query,
+            limit,
+            content,
+            file_types,
+            boost,
+        } => {
+            let indexer = load_indexer(skills_dir)?;
+            let search = SearchService::new(indexer);
+            let mut options = SearchOptions::with_limit(*limit);
+            if !file_types.is_empty() {
+                options = options.file_types(file_types.clone());
+            }
+            if let Some(boost) = boost {
+                options.boosts = FieldBoosts::parse_overrides(boost).map_err(|e| anyhow::anyhow!("--boost: {}", e))?;
+            }
+
+            let results = if *content {
+                search.search_content(query, options)
+            } else {
+                search.search_skills(query, options)
+            };
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&results)?);
+            } else {
+                for result in &results.results {
+                    println!(
+                        "{:<30} {:>6.2}  {:?}",
+                        result.display_id(),
+                        result.score,
+                        result.match_type
+                    );
+                    if let Some(snippet) = &result.snippet {
+                        println!("    {}", snippet);
+                    }
+                }
+                println!("{} of {} matches", results.len(), results.total_matches);
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Command::New {
+            name,
+            description,
+            template,
+            tags,
+        } => {
+            let created =
+                skills_mcp::scaffold::create_skill(&skills_dir, name, description, tags.clone(), *template)?;
+
+            if args.json {
+                let output = serde_json::json!({ "name": name, "files": created });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                println!("Created skill '{}' in {:?}", name, skills_dir.join(name));
+                for file in created {
+                    println!("  {}", file);
+                }
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Command::Manifest => {
+            let manifest = Manifest::generate(&skills_dir)?;
+            manifest.save(&skills_dir)?;
+
+            if args.json {
+                let summary = serde_json::json!({
+                    "file": skills_dir.join(MANIFEST_FILE),
+                    "files": manifest.files.len(),
+                });
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                println!(
+                    "Wrote {:?} ({} files)",
+                    skills_dir.join(MANIFEST_FILE),
+                    manifest.files.len()
+                );
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Command::Verify => {
+            let manifest = Manifest::load(&skills_dir)?
+                .ok_or_else(|| anyhow::anyhow!("No {} in {:?}", MANIFEST_FILE, skills_dir))?;
+            let report = manifest.verify(&skills_dir)?;
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                for path in &report.modified {
+                    println!("modified:   {}", path);
+                }
+                for path in &report.missing {
+                    println!("missing:    {}", path);
+                }
+                for path in &report.unexpected {
+                    println!("unexpected: {}", path);
+                }
+                println!(
+                    "{} files checked: {:?}",
+                    report.files_checked, report.status
+                );
+            }
+
+            Ok(if report.is_verified() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Command::Doctor { fix, journal, analytics_store } => {
+            let mut doctor = Doctor::new(&skills_dir).with_fix(*fix);
+            if let Some(journal) = journal {
+                doctor = doctor.with_journal(journal);
+            }
+            if let Some(store) = analytics_store {
+                doctor = doctor.with_analytics_store(store);
+            }
+            let report = doctor.run();
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                for finding in &report.findings {
+                    let status = match (finding.fixed, finding.severity) {
+                        (true, _) => "fixed",
+                        (false, DoctorSeverity::Error) => "error",
+                        (false, DoctorSeverity::Warning) => "warning",
+                    };
+                    match &finding.path {
+                        Some(path) => println!("{}: {}: {}", status, path, finding.message),
+                        None => println!("{}: {}", status, finding.message),
+                    }
+                }
+                let fixable = report.findings.iter().filter(|f| f.fixable && !f.fixed).count();
+                println!(
+                    "{} skills checked: {} errors, {} fixed{}",
+                    report.skills_checked,
+                    report.errors(),
+                    report.findings.iter().filter(|f| f.fixed).count(),
+                    if fixable > 0 {
+                        format!(" ({} fixable; pass --fix to repair)", fixable)
+                    } else {
+                        String::new()
+                    }
+                );
+            }
+
+            Ok(if report.is_healthy() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Command::Diff { a, b, stat } => {
+            let diff = diff_libraries(&LibrarySnapshot::load(a)?, &LibrarySnapshot::load(b)?);
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&diff)?);
+            } else {
+                for name in &diff.added {
+                    println!("added:   {}", name);
+                }
+                for name in &diff.removed {
+                    println!("removed: {}", name);
+                }
+                for change in &diff.changed {
+                    println!("changed: {}", change.name);
+                    for field in &change.metadata {
+                        println!("    {}", field);
+                    }
+                    for file in &change.files {
+                        println!("    {:?} {}", file.status, file.path);
+                        if !stat {
+                            for line in file.diff.lines() {
+                                println!("        {}", line);
+                            }
+                        }
+                    }
+                }
+                println!(
+                    "{} added, {} removed, {} changed, {} unchanged{}",
+                    diff.added.len(),
+                    diff.removed.len(),
+                    diff.changed.len(),
+                    diff.unchanged,
+                    if diff.skill_md_only {
+                        " (metadata and SKILL.md only)"
+                    } else {
+                        ""
+                    }
+                );
+            }
+
+            Ok(if diff.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Command::Import { path, format, name, merge } => {
+            let mode = if *merge { ImportMode::Merge } else { ImportMode::Create };
+            let report = match format {
+                ImportFormat::Claude => {
+                    skills_mcp::import::import_claude_skill(&skills_dir, path, name.as_deref(), mode)?
+                }
+            };
+            let conflicts = report.merge.as_ref().map_or(0, |m| m.conflicts.len());
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                let verb = if report.merge.is_some() { "Merged" } else { "Imported" };
+                println!("{} '{}' into {:?}", verb, report.name, skills_dir.join(&report.name));
+                for file in &report.files {
+                    println!("  {}", file);
+                }
+                if let Some(merge) = &report.merge {
+                    for file in &merge.removed {
+                        println!("  - {}", file);
+                    }
+                    for file in &merge.kept {
+                        println!("  kept local {}", file);
+                    }
+                    for conflict in &merge.conflicts {
+                        println!("  CONFLICT {} ({:?})", conflict.file, conflict.kind);
+                    }
+                }
+                if !report.sub_skills.is_empty() {
+                    println!("Sub-skills: {}", report.sub_skills.join(", "));
+                }
+            }
+
+            Ok(if conflicts == 0 {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Command::Export {
+            name,
+            output,
+            no_scripts,
+        } => {
+            let bytes = skills_mcp::export::export_skill(&skills_dir, name, !no_scripts)?;
+            let output = output
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(format!("{}.zip", name)));
+            std::fs::write(&output, &bytes)?;
+
+            if args.json {
+                let summary = serde_json::json!({
+                    "name": name,
+                    "file": output,
+                    "size": bytes.len(),
+                });
+                println!("{}", serde_json::to_string_pretty(&summary)?);
+            } else {
+                println!("Exported '{}' to {:?} ({} bytes)", name, output, bytes.len());
+            }
+
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Command::NormalizeNames { dry_run: _, apply } => {
+            let report = skills_mcp::lifecycle::normalize_names(&skills_dir, *apply)?;
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                for change in &report.changes {
+                    let target = change.new_name.as_deref().unwrap_or("?");
+                    match &change.conflict {
+                        Some(conflict) => {
+                            println!("conflict: {} -> {} ({})", change.old_name, target, conflict)
+                        }
+                        None if report.applied => println!("renamed:  {} -> {}", change.old_name, target),
+                        None => println!("rename:   {} -> {}", change.old_name, target),
+                    }
+                }
+                for rename in &report.renamed {
+                    for file in &rename.files_updated {
+                        println!("    updated {}", file);
+                    }
+                }
+                println!(
+                    "{} invalid names: {} renamed, {} conflicts{}",
+                    report.changes.len(),
+                    report.renamed.len(),
+                    report.conflicts().count(),
+                    if report.applied { "" } else { " (dry run; pass --apply to rename)" }
+                );
+            }
+
+            let remaining = report.changes.len() - report.renamed.len();
+            Ok(if remaining == 0 {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Command::Lint { config, list_rules } => {
+            let config = match config.clone().or_else(|| Config::find(&skills_dir)) {
+                Some(path) => Config::load(&path)
+                    .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?,
+                None => Config::default(),
+            };
+
+            if *list_rules {
+                if args.json {
+                    let rules: Vec<_> = RULES
+                        .iter()
+                        .map(|rule| {
+                            serde_json::json!({
+                                "id": rule.id,
+                                "severity": config.lint.severity(rule),
+                                "description": rule.description,
+                            })
+                        })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&rules)?);
+                } else {
+                    for rule in RULES {
+                        let severity = serde_json::to_value(config.lint.severity(rule))?;
+                        println!(
+                            "{:<20} {:<8} {}",
+                            rule.id,
+                            severity.as_str().unwrap_or_default(),
+                            rule.description
+                        );
+                    }
+                }
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            let indexer = load_indexer(skills_dir)?;
+            let report = lint::lint_skills(&indexer, &config.lint);
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                for finding in &report.findings {
+                    let level = match finding.severity {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                        _ => "info",
+                    };
+                    let location = match (&finding.file, finding.line) {
+                        (Some(file), Some(line)) => format!("{}/{}:{}", finding.skill, file, line),
+                        (Some(file), None) => format!("{}/{}", finding.skill, file),
+                        _ => finding.skill.clone(),
+                    };
+                    println!("{}[{}] {}: {}", level, finding.rule, location, finding.message);
+                }
+                println!(
+                    "{} skills linted: {} errors, {} warnings",
+                    report.skills_checked, report.errors, report.warnings
+                );
+            }
+
+            Ok(if report.passed() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Command::TestTriggers { skill, file, min_score } => {
+            let cases = parse_trigger_cases(&std::fs::read_to_string(file)?);
+            let indexer = load_indexer(skills_dir)?;
+            let meta = indexer
+                .get_skill_meta(skill)
+                .ok_or_else(|| anyhow::anyhow!("skill not found: {}", skill))?;
+            let report = test_triggers(&meta, &cases, *min_score);
+
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                for result in &report.results {
+                    let status = match result.passed {
+                        Some(true) => "ok",
+                        Some(false) => "FAIL",
+                        None => "-",
+                    };
+                    println!("{:<4} {}", status, result.phrase);
+                    for sub in &result.sub_skills {
+                        let triggers: Vec<&str> = sub.matches.iter().map(|m| m.trigger.as_str()).collect();
+                        println!(
+                            "       {} {:<20} {:.2}  {}",
+                            if sub.selected { "*" } else { " " },
+                            sub.sub_skill,
+                            sub.score,
+                            triggers.join(", ")
+                        );
+                    }
+                    if let (Some(false), Some(expected)) = (result.passed, &result.expected) {
+                        println!("       expected: {}", expected.join(", "));
+                    }
+                }
+                println!(
+                    "{} phrases tested: {} passed, {} failed",
+                    report.results.len(),
+                    report.passed,
+                    report.failed
+                );
+            }
+
+            Ok(if report.all_passed() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            })
+        }
+
+        Command::Replay { target, journal, from } => {
+            let journal = journal.clone().unwrap_or_else(|| skills_dir.join(JOURNAL_FILE));
+            let occupied = std::fs::read_dir(target).is_ok_and(|mut entries| entries.next().is_some());
+            if occupied && from.is_none() {
+                anyhow::bail!("{:?} is not empty; replay a whole journal into an empty directory", target);
+            }
+
+            let report = journal::replay(&journal, target, *from)?;
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!(
+                    "{} entries replayed: {} files written, {} removed",
+                    report.entries, report.written, report.removed
+                );
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+
+        Command::KeyFingerprint { key } => {
+            let fingerprint = key_fingerprint(key);
+            if args.json {
+                println!("{}", serde_json::json!({ "fingerprint": fingerprint }));
+            } else {
+                println!("key:{}", fingerprint);
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// Build an indexer and load the index from disk.
+fn load_indexer(skills_dir: PathBuf) -> anyhow::Result<Arc<SkillIndexer>> {
+    let indexer = Arc::new(SkillIndexer::new(&skills_dir));
+    if let Some(path) = Config::find(&skills_dir) {
+        let config = Config::load(&path).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+        indexer.set_index_config(config.index);
+    }
+    indexer.reload()?;
+    Ok(indexer)
+}
@@ -3,12 +3,19 @@
 //! Run with: cargo run --bin skills-mcp-server -- [OPTIONS]
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use clap::Parser;
-use tracing::info;
+use tracing::{info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use skills_mcp::analytics;
+use skills_mcp::audit;
+use skills_mcp::config::Config;
+use skills_mcp::index::FileWatcher;
+use skills_mcp::logging::{JsonFields, JsonFormat, LogFormat};
 use skills_mcp::mcp::McpServer;
+use skills_mcp::models::FallbackConfig;
 
 /// Skills MCP Server
 #[derive(Parser, Debug)]
@@ -20,9 +27,57 @@ struct Args {
     #[arg(short, long, env = "SKILLS_DIR")]
     skills_dir: Option<PathBuf>,
 
+    /// Configuration file
+    /// [default: <skills-dir>/skills-mcp.toml or ./skills-mcp.toml]
+    #[arg(long, env = "SKILLS_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Skill served, with zero-result diagnostics, when a search finds
+    /// no confident match (e.g. a guide to requesting new skills)
+    #[arg(long, env = "SKILLS_FALLBACK_SKILL")]
+    fallback_skill: Option<String>,
+
+    /// Best-result score below which the fallback skill is also served
+    #[arg(long, default_value = "0", env = "SKILLS_FALLBACK_MIN_SCORE", requires = "fallback_skill")]
+    fallback_min_score: f64,
+
+    /// JSON file keeping usage analytics across restarts
+    /// [default: <skills-dir>/.skills-analytics.json]
+    #[arg(long, env = "SKILLS_ANALYTICS_STORE")]
+    analytics_store: Option<PathBuf>,
+
+    /// JSON Lines file recording every skill mutation
+    /// [default: <skills-dir>/.skills-audit.jsonl]
+    #[arg(long, env = "SKILLS_AUDIT_LOG")]
+    audit_log: Option<PathBuf>,
+
+    /// JSON Lines file journaling the files each mutation changes, for
+    /// `skills-mcp replay` (e.g. <skills-dir>/.skills-journal.jsonl)
+    #[arg(long, env = "SKILLS_JOURNAL")]
+    journal: Option<PathBuf>,
+
+    /// Keep analytics and the audit log in memory only, writing no state
+    /// files; both start empty on every restart
+    #[arg(long, env = "SKILLS_NO_DB", conflicts_with_all = ["analytics_store", "audit_log", "journal"])]
+    no_db: bool,
+
+    /// API key the server acts for; private skills whose `allowed_keys`
+    /// include its fingerprint become visible
+    #[arg(long, env = "SKILLS_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Split tool results larger than this many bytes, returning a cursor
+    /// for the rest
+    #[arg(long, env = "SKILLS_MAX_RESPONSE_BYTES")]
+    max_response_bytes: Option<usize>,
+
     /// Enable debug logging
     #[arg(short, long)]
     debug: bool,
+
+    /// Log output: text, or json for one object per line
+    #[arg(long, default_value = "text", env = "SKILLS_LOG_FORMAT")]
+    log_format: LogFormat,
 }
 
 #[tokio::main]
@@ -36,9 +91,16 @@ async fn main() -> anyhow::Result<()> {
         "skills_mcp=info,warn"
     };
 
+    let json = args.log_format == LogFormat::Json;
     tracing_subscriber::registry()
         .with(tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| filter.into()))
-        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .with((!json).then(|| tracing_subscriber::fmt::layer().with_target(false).with_writer(std::io::stderr)))
+        .with(json.then(|| {
+            tracing_subscriber::fmt::layer()
+                .event_format(JsonFormat)
+                .fmt_fields(JsonFields)
+                .with_writer(std::io::stderr)
+        }))
         .init();
 
     // Determine skills directory
@@ -61,8 +123,59 @@ async fn main() -> anyhow::Result<()> {
     info!("Skills directory: {:?}", skills_dir);
     info!("Starting Skills MCP Server v{}", skills_mcp::VERSION);
 
-    let server = McpServer::new(&skills_dir);
+    let config = match args.config.or_else(|| Config::find(&skills_dir)) {
+        Some(path) => {
+            info!("Configuration: {:?}", path);
+            Config::load(&path).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?
+        }
+        None => Config::default(),
+    };
+
+    let server = McpServer::new(&skills_dir).with_config(config);
+    if let Some(skill) = args.fallback_skill {
+        server
+            .context()
+            .search
+            .set_fallback(Some(FallbackConfig::new(skill).min_score(args.fallback_min_score)));
+    }
+
+    if let Some(key) = &args.api_key {
+        server.context().set_mcp_key(key);
+    }
+    if let Some(bytes) = args.max_response_bytes {
+        *server.context().max_response_bytes.write() = Some(bytes);
+    }
+
+    if args.no_db {
+        info!("Analytics and audit log kept in memory only");
+    } else {
+        let store = args
+            .analytics_store
+            .unwrap_or_else(|| skills_dir.join(analytics::STORE_FILE));
+        if let Err(e) = server.context().analytics.persist_to(store) {
+            warn!("Failed to load analytics store: {}", e);
+        }
+
+        server.context().audit.write_to(
+            args.audit_log
+                .unwrap_or_else(|| skills_dir.join(audit::LOG_FILE)),
+        );
+    }
+    if let Some(path) = args.journal {
+        if let Err(e) = server.context().journal.write_to(path, &skills_dir) {
+            warn!("Failed to start journal: {}", e);
+        }
+    }
+
+    // Keep the index current so resource change notifications fire
+    let mut watcher = FileWatcher::new(Arc::clone(&server.context().indexer))?;
+    watcher.watch(&skills_dir)?;
+
     server.run().await?;
 
+    if let Err(e) = server.context().analytics.flush() {
+        warn!("Failed to write analytics store: {}", e);
+    }
+
     Ok(())
 }
@@ -0,0 +1,326 @@
+//! Skill bundles ("playbooks"): named groups of skills and sub-skills that
+//! load as one unit.
+//!
+//! Bundles are defined in `_bundles.json` at the root of the skills
+//! directory. Items name a skill (`forms`) or a sub-skill (`forms:react`):
+//!
+//! ```json
+//! {
+//!   "crud-screen": {
+//!     "description": "Everything for a form-and-table admin screen",
+//!     "items": ["tables", "forms", "forms:validation"],
+//!     "max_tokens": 8000
+//!   }
+//! }
+//! ```
+//!
+//! A loaded bundle concatenates its items with dependencies first, and
+//! stops before the item that would exceed the token budget.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::index::{DependencyGraph, SkillIndexer};
+use crate::markdown;
+use crate::models::ErrorCode;
+
+/// Bundle definitions file, in the skills directory.
+pub const BUNDLES_FILE: &str = "_bundles.json";
+
+/// Separator between items in a bundle's content.
+const SEPARATOR: &str = "\n\n---\n\n";
+
+/// A bundle definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    /// Bundle name, the key in `_bundles.json`.
+    #[serde(default, skip_deserializing)]
+    pub name: String,
+
+    /// What the bundle is for.
+    #[serde(default)]
+    pub description: String,
+
+    /// Skills (`forms`) and sub-skills (`forms:react`) in the bundle.
+    pub items: Vec<String>,
+
+    /// Token budget of the loaded content; unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+}
+
+/// One item included in a loaded bundle.
+#[derive(Debug, Clone, Serialize)]
+pub struct BundlePart {
+    /// Item as listed in the bundle.
+    pub item: String,
+
+    /// Skill name.
+    pub skill: String,
+
+    /// Sub-skill name, for sub-skill items.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+
+    /// Estimated tokens of the item's content.
+    pub tokens: u64,
+}
+
+/// A bundle's items loaded and concatenated.
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleContent {
+    /// Bundle name.
+    pub name: String,
+
+    /// What the bundle is for.
+    pub description: String,
+
+    /// Content of the included items, separated by rules.
+    pub content: String,
+
+    /// Included items, in content order.
+    pub parts: Vec<BundlePart>,
+
+    /// Estimated tokens of the included items.
+    pub total_tokens: u64,
+
+    /// Token budget applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+
+    /// Items left out because they didn't fit the budget.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub omitted: Vec<String>,
+
+    /// Items naming skills or sub-skills that don't exist.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+}
+
+/// Errors reading bundle definitions.
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    /// No bundle with this name is defined.
+    #[error("Bundle '{0}' not found")]
+    NotFound(String),
+
+    /// `_bundles.json` is not valid.
+    #[error("Invalid {}: {0}", BUNDLES_FILE)]
+    Parse(#[from] serde_json::Error),
+
+    /// Filesystem error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl BundleError {
+    /// Machine-readable code of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            BundleError::NotFound(_) => ErrorCode::NotFound,
+            BundleError::Parse(_) => ErrorCode::InternalError,
+            BundleError::Io(_) => ErrorCode::IoError,
+        }
+    }
+}
+
+/// Bundle definitions of a skills directory.
+pub struct Bundles {
+    path: PathBuf,
+}
+
+impl Bundles {
+    /// Bundles defined for the given skills directory.
+    pub fn new(skills_dir: impl AsRef<Path>) -> Self {
+        Self {
+            path: skills_dir.as_ref().join(BUNDLES_FILE),
+        }
+    }
+
+    /// All bundles, sorted by name. No definitions file means no bundles.
+    pub fn list(&self) -> Result<Vec<Bundle>, BundleError> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let bundles: BTreeMap<String, Bundle> = serde_json::from_str(&text)?;
+        Ok(bundles
+            .into_iter()
+            .map(|(name, bundle)| Bundle { name, ..bundle })
+            .collect())
+    }
+
+    /// A bundle by name.
+    pub fn get(&self, name: &str) -> Result<Bundle, BundleError> {
+        self.list()?
+            .into_iter()
+            .find(|b| b.name == name)
+            .ok_or_else(|| BundleError::NotFound(name.to_string()))
+    }
+}
+
+impl Bundle {
+    /// Load the bundle's items, dependencies first, within `max_tokens`
+    /// (or the bundle's own budget).
+    ///
+    /// Items for which `visible` is false are reported as missing, so
+    /// callers can hide skills without revealing them.
+    pub fn load(
+        &self,
+        indexer: &SkillIndexer,
+        max_tokens: Option<u64>,
+        visible: impl Fn(&str) -> bool,
+    ) -> BundleContent {
+        let max_tokens = max_tokens.or(self.max_tokens);
+        let mut bundle = BundleContent {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            content: String::new(),
+            parts: Vec::new(),
+            total_tokens: 0,
+            max_tokens,
+            omitted: Vec::new(),
+            missing: Vec::new(),
+        };
+
+        let mut items = Vec::new();
+        for item in &self.items {
+            let (skill, sub_skill) = match item.split_once(':') {
+                Some((skill, sub)) => (skill, Some(sub.to_string())),
+                None => (item.as_str(), None),
+            };
+            match indexer.get_skill_meta(skill).filter(|m| visible(&m.name)) {
+                Some(meta) => items.push((item, meta.name, sub_skill)),
+                None => bundle.missing.push(item.clone()),
+            }
+        }
+
+        let graph = DependencyGraph::new(&indexer.get_skill_index());
+        for (item, skill, sub_skill) in dependency_order(items, &graph) {
+            if !bundle.omitted.is_empty() {
+                bundle.omitted.push(item.clone());
+                continue;
+            }
+            let content = match &sub_skill {
+                Some(sub) => indexer.read_sub_skill_content(&skill, sub).map(|c| c.content),
+                None => indexer.read_skill_content(&skill).map(|c| c.content),
+            };
+            let Ok(content) = content else {
+                bundle.missing.push(item.clone());
+                continue;
+            };
+
+            let tokens = markdown::estimate_tokens(&content);
+            if max_tokens.is_some_and(|max| bundle.total_tokens + tokens > max) {
+                // Later items may build on this one, so stop here
+                bundle.omitted.push(item.clone());
+                continue;
+            }
+            if !bundle.content.is_empty() {
+                bundle.content.push_str(SEPARATOR);
+            }
+            bundle.content.push_str(content.trim_end());
+            bundle.total_tokens += tokens;
+            bundle.parts.push(BundlePart {
+                item: item.clone(),
+                skill,
+                sub_skill,
+                tokens,
+            });
+        }
+        bundle
+    }
+}
+
+/// Order items so each skill comes after the skills it depends on,
+/// otherwise keeping the listed order. Cycles fall back to listed order.
+fn dependency_order<'a>(
+    mut items: Vec<(&'a String, String, Option<String>)>,
+    graph: &DependencyGraph,
+) -> Vec<(&'a String, String, Option<String>)> {
+    let mut ordered = Vec::with_capacity(items.len());
+    let mut placed: HashSet<String> = HashSet::new();
+
+    while !items.is_empty() {
+        let listed: HashSet<&str> = items.iter().map(|(_, skill, _)| skill.as_str()).collect();
+        let ready = items.iter().position(|(_, skill, _)| {
+            graph.dependencies(skill).is_none_or(|deps| {
+                deps.transitive
+                    .iter()
+                    .all(|dep| dep == skill || placed.contains(dep) || !listed.contains(dep.as_str()))
+            })
+        });
+        let item = items.remove(ready.unwrap_or(0));
+        placed.insert(item.1.clone());
+        ordered.push(item);
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_skill;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_bundle() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Forms", "depends_on": ["tables"],
+                "sub_skills": [{"name": "validation", "file": "validation.md"}]}"#,
+            "# Forms\n",
+        );
+        fs::write(temp_dir.path().join("forms/validation.md"), "# Validation\n").unwrap();
+        write_skill(temp_dir.path(), "tables", r#"{"name": "tables", "description": "Tables"}"#, "# Tables\n");
+        write_skill(
+            temp_dir.path(),
+            "charts",
+            r#"{"name": "charts", "description": "Charts"}"#,
+            &"Plot the data. ".repeat(50),
+        );
+        fs::write(
+            temp_dir.path().join(BUNDLES_FILE),
+            r#"{
+                "crud": {"description": "CRUD screen", "items": ["forms", "forms:validation", "tables", "nope", "charts"]},
+                "empty": {"items": []}
+            }"#,
+        )
+        .unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        let bundles = Bundles::new(temp_dir.path());
+        let names: Vec<String> = bundles.list().unwrap().into_iter().map(|b| b.name).collect();
+        assert_eq!(names, vec!["crud", "empty"]);
+        assert!(matches!(bundles.get("missing"), Err(BundleError::NotFound(_))));
+
+        let bundle = bundles.get("crud").unwrap();
+        let loaded = bundle.load(&indexer, None, |_| true);
+        let items: Vec<&str> = loaded.parts.iter().map(|p| p.item.as_str()).collect();
+        // tables moves ahead of forms, which depends on it
+        assert_eq!(items, vec!["tables", "forms", "forms:validation", "charts"]);
+        assert!(loaded.content.starts_with("# Tables\n\n---\n\n# Forms"));
+        assert_eq!(loaded.missing, vec!["nope"]);
+
+        // The long item doesn't fit, and nothing after it is loaded
+        let loaded = bundle.load(&indexer, Some(20), |name| name != "tables");
+        let items: Vec<&str> = loaded.parts.iter().map(|p| p.item.as_str()).collect();
+        assert_eq!(items, vec!["forms", "forms:validation"]);
+        assert_eq!(loaded.omitted, vec!["charts"]);
+        assert_eq!(loaded.missing, vec!["tables", "nope"]);
+        assert!(loaded.total_tokens <= 20);
+    }
+
+    #[test]
+    fn test_no_bundles_file() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(Bundles::new(temp_dir.path()).list().unwrap().is_empty());
+    }
+}
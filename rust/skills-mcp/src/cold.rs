@@ -0,0 +1,315 @@
+//! Cold-storage tier for rarely used skills.
+//!
+//! Frozen skills are stored as ZIP archives under `<skills_dir>/_cold/`.
+//! The indexer skips underscore-prefixed directories, so cold skills drop
+//! out of the default index while their metadata stays searchable.
+
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+use crate::export::{export_skill, ExportError};
+use crate::models::{ErrorCode, ReadReceipts, SkillIndex, SkillMeta};
+
+/// A skill held in cold storage.
+#[derive(Debug, Clone, Serialize)]
+pub struct ColdSkill {
+    /// Metadata read from the archived `_meta.json`.
+    pub meta: SkillMeta,
+
+    /// When the skill was frozen.
+    #[serde(with = "crate::models::timestamp")]
+    pub frozen_at: DateTime<Utc>,
+
+    /// Compressed archive size in bytes.
+    pub size_bytes: u64,
+}
+
+/// A hot skill that the cold policy would freeze.
+#[derive(Debug, Clone, Serialize)]
+pub struct ColdCandidate {
+    /// Skill name.
+    pub name: String,
+
+    /// Most recent read or modification.
+    #[serde(with = "crate::models::timestamp")]
+    pub last_used: DateTime<Utc>,
+}
+
+/// Cold-storage area for a skills directory.
+pub struct ColdStorage {
+    skills_dir: PathBuf,
+}
+
+impl ColdStorage {
+    /// Directory (inside the skills directory) holding cold archives.
+    pub const DIR_NAME: &'static str = "_cold";
+
+    /// Default number of days without use before a skill goes cold.
+    pub const DEFAULT_UNUSED_DAYS: i64 = 180;
+
+    /// Create a cold-storage handle for the given skills directory.
+    pub fn new(skills_dir: impl AsRef<Path>) -> Self {
+        Self {
+            skills_dir: skills_dir.as_ref().to_path_buf(),
+        }
+    }
+
+    fn cold_dir(&self) -> PathBuf {
+        self.skills_dir.join(Self::DIR_NAME)
+    }
+
+    fn archive_path(&self, name: &str) -> PathBuf {
+        self.cold_dir().join(format!("{}.zip", name))
+    }
+
+    /// Check whether a skill is in cold storage.
+    pub fn contains(&self, name: &str) -> bool {
+        self.archive_path(name).is_file()
+    }
+
+    /// Compress a hot skill into cold storage and remove its directory.
+    pub fn freeze(&self, name: &str) -> Result<ColdSkill, ColdError> {
+        if self.contains(name) {
+            return Err(ColdError::AlreadyCold(name.to_string()));
+        }
+
+        let bytes = export_skill(&self.skills_dir, name, true).map_err(|e| match e {
+            ExportError::NotFound(n) => ColdError::NotFound(n),
+            other => ColdError::Archive(other.to_string()),
+        })?;
+
+        fs::create_dir_all(self.cold_dir())?;
+        let archive_path = self.archive_path(name);
+        fs::write(&archive_path, &bytes)?;
+        fs::remove_dir_all(self.skills_dir.join(name))?;
+
+        self.read_cold_skill(&archive_path)
+    }
+
+    /// Restore a cold skill back into the hot skills directory.
+    pub fn restore(&self, name: &str) -> Result<(), ColdError> {
+        let archive_path = self.archive_path(name);
+        if !archive_path.is_file() {
+            return Err(ColdError::NotFound(name.to_string()));
+        }
+        if self.skills_dir.join(name).exists() {
+            return Err(ColdError::Conflict(name.to_string()));
+        }
+
+        let mut archive = ZipArchive::new(Cursor::new(fs::read(&archive_path)?))
+            .map_err(|e| ColdError::Archive(e.to_string()))?;
+
+        for i in 0..archive.len() {
+            let mut file = archive
+                .by_index(i)
+                .map_err(|e| ColdError::Archive(e.to_string()))?;
+
+            // Only extract entries that stay within the skill's own directory
+            let Some(relative) = file.enclosed_name() else {
+                continue;
+            };
+            if !relative.starts_with(name) || file.is_dir() {
+                continue;
+            }
+
+            let target = self.skills_dir.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)?;
+            fs::write(target, bytes)?;
+        }
+
+        fs::remove_file(archive_path)?;
+        Ok(())
+    }
+
+    /// List all skills in cold storage.
+    pub fn list(&self) -> Result<Vec<ColdSkill>, ColdError> {
+        let cold_dir = self.cold_dir();
+        if !cold_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut skills = Vec::new();
+        for entry in fs::read_dir(cold_dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("zip") {
+                continue;
+            }
+            match self.read_cold_skill(&path) {
+                Ok(skill) => skills.push(skill),
+                Err(e) => tracing::warn!("Skipping unreadable cold archive {:?}: {}", path, e),
+            }
+        }
+
+        skills.sort_by(|a, b| a.meta.name.cmp(&b.meta.name));
+        Ok(skills)
+    }
+
+    /// Read metadata from a cold archive without extracting it.
+    fn read_cold_skill(&self, archive_path: &Path) -> Result<ColdSkill, ColdError> {
+        let name = archive_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let file_meta = fs::metadata(archive_path)?;
+        let mut archive = ZipArchive::new(Cursor::new(fs::read(archive_path)?))
+            .map_err(|e| ColdError::Archive(e.to_string()))?;
+
+        let mut meta_json = String::new();
+        archive
+            .by_name(&format!("{}/_meta.json", name))
+            .map_err(|e| ColdError::Archive(e.to_string()))?
+            .read_to_string(&mut meta_json)?;
+
+        let meta: SkillMeta = serde_json::from_str(&meta_json)
+            .map_err(|e| ColdError::Archive(format!("Invalid _meta.json: {}", e)))?;
+
+        Ok(ColdSkill {
+            meta,
+            frozen_at: file_meta
+                .modified()
+                .map(DateTime::<Utc>::from)
+                .unwrap_or_else(|_| Utc::now()),
+            size_bytes: file_meta.len(),
+        })
+    }
+
+    /// Find hot skills not read or modified within `unused_days`.
+    ///
+    /// Usage is the later of the skill's last read receipt and the newest
+    /// file modification time under its directory.
+    pub fn candidates(
+        &self,
+        index: &SkillIndex,
+        receipts: &ReadReceipts,
+        unused_days: i64,
+    ) -> Vec<ColdCandidate> {
+        let cutoff = Utc::now() - Duration::days(unused_days);
+
+        index
+            .skills
+            .iter()
+            .filter_map(|skill| {
+                let modified = latest_mtime(&self.skills_dir.join(&skill.name));
+                let last_used = receipts.last_read(&skill.name).max(modified)?;
+
+                (last_used < cutoff).then(|| ColdCandidate {
+                    name: skill.name.clone(),
+                    last_used,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Newest modification time of any file under a directory.
+fn latest_mtime(dir: &Path) -> Option<DateTime<Utc>> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok()?.modified().ok())
+        .max()
+        .map(|t: SystemTime| DateTime::<Utc>::from(t))
+}
+
+/// Errors that can occur in cold storage.
+#[derive(Debug, thiserror::Error)]
+pub enum ColdError {
+    /// The skill was not found (hot or cold, depending on the operation).
+    #[error("Skill '{0}' not found")]
+    NotFound(String),
+
+    /// The skill is already in cold storage.
+    #[error("Skill '{0}' is already in cold storage")]
+    AlreadyCold(String),
+
+    /// A hot skill with the same name already exists.
+    #[error("Skill '{0}' already exists in the hot index")]
+    Conflict(String),
+
+    /// Failed to read or write a cold archive.
+    #[error("Archive error: {0}")]
+    Archive(String),
+
+    /// Filesystem error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl ColdError {
+    /// Machine-readable code of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ColdError::NotFound(_) => ErrorCode::SkillNotFound,
+            ColdError::AlreadyCold(_) => ErrorCode::Conflict,
+            ColdError::Conflict(_) => ErrorCode::AlreadyExists,
+            ColdError::Archive(_) => ErrorCode::InternalError,
+            ColdError::Io(_) => ErrorCode::IoError,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::SkillIndexer;
+    use crate::test_support::{meta, write_skill};
+    use tempfile::TempDir;
+
+    fn create_test_skill(dir: &Path, name: &str) {
+        write_skill(dir, name, &meta(name, "Cold test"), "# Cold");
+        fs::create_dir_all(dir.join(name).join("references")).unwrap();
+        fs::write(dir.join(name).join("references/notes.md"), "notes").unwrap();
+    }
+
+    #[test]
+    fn test_freeze_and_restore_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "legacy");
+
+        let cold = ColdStorage::new(temp_dir.path());
+        let frozen = cold.freeze("legacy").unwrap();
+        assert_eq!(frozen.meta.name, "legacy");
+        assert!(!temp_dir.path().join("legacy").exists());
+
+        // Cold skills are excluded from the default index
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        assert!(indexer.get_skill_index().is_empty());
+        assert_eq!(cold.list().unwrap().len(), 1);
+
+        cold.restore("legacy").unwrap();
+        assert!(temp_dir.path().join("legacy/references/notes.md").exists());
+        assert!(!cold.contains("legacy"));
+    }
+
+    #[test]
+    fn test_candidates_respect_recent_reads() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "fresh");
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        let cold = ColdStorage::new(temp_dir.path());
+
+        // Just written, so nothing is stale at a 1-day threshold
+        let candidates = cold.candidates(&indexer.get_skill_index(), &ReadReceipts::new(), 1);
+        assert!(candidates.is_empty());
+
+        // A negative threshold puts the cutoff in the future
+        let candidates = cold.candidates(&indexer.get_skill_index(), &ReadReceipts::new(), -1);
+        assert_eq!(candidates.len(), 1);
+    }
+}
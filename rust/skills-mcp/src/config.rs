@@ -0,0 +1,469 @@
+//! Project configuration from `skills-mcp.toml`.
+//!
+//! Only the TOML needed for configuration is understood: `[table]` and
+//! `[dotted.table]` headers, `key = value` pairs with quoted or bare keys,
+//! and strings, integers, floats, booleans, and single-line arrays of
+//! those as values. Comments start with `#`.
+//!
+//! ```toml
+//! [index]
+//! extensions = ["md", "py", "json"]
+//! max_file_bytes = 1048576
+//! symlinks = "internal"
+//!
+//! [lint]
+//! max_description_chars = 160
+//!
+//! [index.tags.aliases]
+//! unit-test = "testing"
+//!
+//! [search]
+//! cache = 256
+//!
+//! [search.boosts]
+//! tags = 3.0
+//!
+//! [mcp]
+//! max_response_bytes = 65536
+//!
+//! [lint.rules]
+//! no-todo = "error"
+//! h1-title = "off"
+//!
+//! [backup]
+//! path = "/var/backups/skills"
+//! schedule = "0 3 * * *"
+//! keep = 14
+//!
+//! [tenants]
+//! root = "/srv/skills-tenants"
+//! max_loaded = 16
+//!
+//! [quality]
+//! stale_days = 180
+//!
+//! [scripts]
+//! enabled = true
+//! allow = ["pdf/*.py"]
+//!
+//! [import]
+//! root = "/srv/skill-imports"
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::analysis::QualityConfig;
+use crate::backup::BackupConfig;
+use crate::import::ImportConfig;
+use crate::index::IndexConfig;
+use crate::lint::LintConfig;
+use crate::models::SearchConfig;
+use crate::scripts::ScriptsConfig;
+
+/// Configuration file name.
+pub const CONFIG_FILE: &str = "skills-mcp.toml";
+
+/// Parsed configuration. Missing sections take their defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Indexing settings.
+    pub index: IndexConfig,
+
+    /// Lint rule settings.
+    pub lint: LintConfig,
+
+    /// Search settings.
+    pub search: SearchConfig,
+
+    /// MCP tool settings.
+    pub mcp: McpConfig,
+
+    /// Backup settings.
+    pub backup: BackupConfig,
+
+    /// Per-tenant skills directories.
+    pub tenants: TenantConfig,
+
+    /// Quality report thresholds.
+    pub quality: QualityConfig,
+
+    /// Skill script execution.
+    pub scripts: ScriptsConfig,
+
+    /// Folder import settings.
+    pub import: ImportConfig,
+}
+
+/// MCP tool settings from the `[mcp]` section.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct McpConfig {
+    /// Size in bytes that tool results are split to fit, continuing with a
+    /// cursor; unlimited when unset.
+    pub max_response_bytes: Option<usize>,
+}
+
+/// Per-tenant skills directories from the `[tenants]` section.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TenantConfig {
+    /// Directory holding a skills directory per API key, named by the key's
+    /// fingerprint. Tenancy is off when unset.
+    pub root: Option<PathBuf>,
+
+    /// Tenant libraries kept loaded at once; the least recently used is
+    /// dropped to make room.
+    pub max_loaded: usize,
+
+    /// Create an empty skills directory for a key in `keys` that has none,
+    /// rather than serving it the primary skills directory.
+    pub create: bool,
+
+    /// Fingerprints of the API keys that `create` makes directories for;
+    /// other keys without a directory get the primary skills directory.
+    pub keys: Vec<String>,
+
+    /// Tenant directories the root may hold before `create` stops making
+    /// new ones.
+    pub max_tenants: usize,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self {
+            root: None,
+            max_loaded: 16,
+            create: false,
+            keys: Vec::new(),
+            max_tenants: 1000,
+        }
+    }
+}
+
+impl Config {
+    /// Load configuration from a file.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parse configuration from TOML text.
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let value = parse_toml(text)?;
+        let config: Self =
+            serde_json::from_value(value).map_err(|e| ConfigError::Invalid(e.to_string()))?;
+
+        let unknown = config.lint.unknown_rules();
+        if !unknown.is_empty() {
+            return Err(ConfigError::Invalid(format!(
+                "unknown lint rules: {}",
+                unknown.join(", ")
+            )));
+        }
+        Ok(config)
+    }
+
+    /// Find the configuration file for a skills directory: inside it, or
+    /// failing that in the current directory.
+    pub fn find(skills_dir: &Path) -> Option<PathBuf> {
+        [skills_dir.join(CONFIG_FILE), PathBuf::from(CONFIG_FILE)]
+            .into_iter()
+            .find(|p| p.is_file())
+    }
+}
+
+/// Parse the supported TOML subset into a JSON object.
+fn parse_toml(text: &str) -> Result<Value, ConfigError> {
+    let mut root = Map::new();
+    let mut table: Vec<String> = Vec::new();
+
+    for (i, raw) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let error = |message: &str| ConfigError::Syntax {
+            line: line_no,
+            message: message.to_string(),
+        };
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header.strip_suffix(']').ok_or_else(|| error("unclosed table header"))?;
+            table = parse_key(header).ok_or_else(|| error("invalid table name"))?;
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| error("expected key = value"))?;
+        let key = parse_key(key.trim()).ok_or_else(|| error("invalid key"))?;
+        let value = parse_value(value.trim()).ok_or_else(|| error("invalid value"))?;
+
+        let mut target = &mut root;
+        for part in table.iter().chain(&key[..key.len() - 1]) {
+            target = match target
+                .entry(part.clone())
+                .or_insert_with(|| Value::Object(Map::new()))
+            {
+                Value::Object(map) => map,
+                _ => return Err(error("key is not a table")),
+            };
+        }
+        if target.insert(key[key.len() - 1].clone(), value).is_some() {
+            return Err(error("duplicate key"));
+        }
+    }
+
+    Ok(Value::Object(root))
+}
+
+/// Split a possibly dotted key into its parts, unquoting quoted parts.
+fn parse_key(key: &str) -> Option<Vec<String>> {
+    key.split('.')
+        .map(|part| {
+            let part = part.trim();
+            let unquoted = part
+                .strip_prefix('"')
+                .and_then(|p| p.strip_suffix('"'))
+                .map(str::to_string);
+            let bare = !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            unquoted.or_else(|| bare.then(|| part.to_string()))
+        })
+        .collect()
+}
+
+fn parse_value(text: &str) -> Option<Value> {
+    if let Some(inner) = text.strip_prefix('[') {
+        let inner = inner.strip_suffix(']')?.trim();
+        if inner.is_empty() {
+            return Some(Value::Array(Vec::new()));
+        }
+        return split_array(inner)
+            .into_iter()
+            .filter(|item| !item.trim().is_empty())
+            .map(|item| parse_value(item.trim()))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array);
+    }
+    if let Some(inner) = text.strip_prefix('"') {
+        return unescape(inner.strip_suffix('"')?).map(Value::String);
+    }
+    if let Some(inner) = text.strip_prefix('\'') {
+        return Some(Value::String(inner.strip_suffix('\'')?.to_string()));
+    }
+    match text {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => {}
+    }
+    let number = text.replace('_', "");
+    if let Ok(n) = number.parse::<i64>() {
+        return Some(Value::from(n));
+    }
+    number.parse::<f64>().ok().map(Value::from)
+}
+
+/// Split array items on commas outside quotes.
+fn split_array(text: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, ',') => {
+                items.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        escaped = false;
+    }
+    items.push(&text[start..]);
+    items
+}
+
+fn unescape(text: &str) -> Option<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        out.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '"' => '"',
+            '\\' => '\\',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+/// Drop a trailing `#` comment that isn't inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+/// Configuration errors.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// The file couldn't be read.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The file isn't valid TOML (of the supported subset).
+    #[error("line {line}: {message}")]
+    Syntax {
+        /// 1-based line number.
+        line: usize,
+        /// What was wrong.
+        message: String,
+    },
+
+    /// The TOML is valid but a setting isn't.
+    #[error("Invalid configuration: {0}")]
+    Invalid(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::Severity;
+
+    #[test]
+    fn test_parse_toml() {
+        let value = parse_toml(
+            "# top\ntitle = \"a # not a comment\"\n\n[server]\nport = 5_050\nratio = 0.5\ndebug = true # trailing\nroots = [\"a\", 'b,c']\n\n[server.\"tls\"]\nenabled = false\n",
+        )
+        .unwrap();
+        assert_eq!(value["title"], "a # not a comment");
+        assert_eq!(value["server"]["port"], 5050);
+        assert_eq!(value["server"]["ratio"], 0.5);
+        assert_eq!(value["server"]["debug"], true);
+        assert_eq!(value["server"]["roots"], serde_json::json!(["a", "b,c"]));
+        assert_eq!(value["server"]["tls"]["enabled"], false);
+
+        assert!(matches!(parse_toml("[lint\n"), Err(ConfigError::Syntax { line: 1, .. })));
+        assert!(matches!(parse_toml("a = 1\na = 2\n"), Err(ConfigError::Syntax { line: 2, .. })));
+        assert!(parse_toml("a = nope").is_err());
+    }
+
+    #[test]
+    fn test_config_lint_section() {
+        let config = Config::parse("[lint]\nmax_description_chars = 80\n\n[lint.rules]\nno-todo = \"error\"\n").unwrap();
+        assert_eq!(config.lint.max_description_chars, 80);
+        assert_eq!(config.lint.rules["no-todo"], Severity::Error);
+
+        assert!(matches!(
+            Config::parse("[lint.rules]\nno-todo = \"loud\"\n"),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert!(matches!(
+            Config::parse("[lint.rules]\nno-typos = \"off\"\n"),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert_eq!(Config::parse("").unwrap().lint.max_description_chars, 200);
+    }
+
+    #[test]
+    fn test_config_index_section() {
+        let config = Config::parse("[index]\nextensions = [\"md\", \"py\"]\nsymlinks = \"skip\"\n").unwrap();
+        assert_eq!(config.index.extensions, vec!["md", "py"]);
+        assert_eq!(config.index.symlinks, crate::index::SymlinkPolicy::Skip);
+        assert_eq!(Config::parse("").unwrap().index, IndexConfig::default());
+
+        let config =
+            Config::parse("[index.tags]\nlowercase = true\n\n[index.tags.aliases]\nunit-test = \"testing\"\n").unwrap();
+        assert!(config.index.tags.lowercase);
+        assert_eq!(config.index.tags.normalize("Unit-Test"), "testing");
+    }
+
+    #[test]
+    fn test_config_search_section() {
+        let config = Config::parse("[search.boosts]\ntags = 4\nbody = 0.5\n").unwrap();
+        assert_eq!(config.search.boosts.tags, 4.0);
+        assert_eq!(config.search.boosts.body, 0.5);
+        assert_eq!(config.search.boosts.name, 3.0);
+        assert_eq!(config.search.cache, 256);
+
+        let config = Config::parse("[search]\ncache = 0\n").unwrap();
+        assert_eq!(config.search.cache, 0);
+    }
+
+    #[test]
+    fn test_config_mcp_section() {
+        let config = Config::parse("[mcp]\nmax_response_bytes = 65536\n").unwrap();
+        assert_eq!(config.mcp.max_response_bytes, Some(65536));
+        assert_eq!(Config::parse("").unwrap().mcp, McpConfig::default());
+    }
+
+    #[test]
+    fn test_config_backup_section() {
+        let config = Config::parse("[backup]\npath = \"_backups\"\nschedule = \"0 3 * * *\"\nkeep = 7\n").unwrap();
+        assert_eq!(config.backup.path, Some(PathBuf::from("_backups")));
+        assert_eq!(config.backup.schedule.unwrap().to_string(), "0 3 * * *");
+        assert_eq!(config.backup.keep, Some(7));
+
+        assert!(matches!(
+            Config::parse("[backup]\nschedule = \"daily\"\n"),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_config_tenants_section() {
+        let config =
+            Config::parse("[tenants]\nroot = \"/srv/tenants\"\ncreate = true\nkeys = [\"key:0a1b\"]\n").unwrap();
+        assert_eq!(config.tenants.root, Some(PathBuf::from("/srv/tenants")));
+        assert_eq!(config.tenants.max_loaded, 16);
+        assert!(config.tenants.create);
+        assert_eq!(config.tenants.keys, vec!["key:0a1b"]);
+        assert_eq!(config.tenants.max_tenants, 1000);
+        assert_eq!(Config::parse("").unwrap().tenants, TenantConfig::default());
+    }
+
+    #[test]
+    fn test_config_quality_section() {
+        let config = Config::parse("[quality]\nstale_days = 30\n").unwrap();
+        assert_eq!(config.quality.stale_days, 30);
+        assert_eq!(config.quality.max_tokens, QualityConfig::default().max_tokens);
+    }
+
+    #[test]
+    fn test_config_import_section() {
+        let config = Config::parse("[import]\nroot = \"/srv/skill-imports\"\n").unwrap();
+        assert_eq!(config.import.root, Some(PathBuf::from("/srv/skill-imports")));
+        assert_eq!(Config::parse("").unwrap().import.root, None);
+    }
+}
@@ -0,0 +1,433 @@
+//! Context packs: the skills and sub-skills relevant to a task, loaded as
+//! one document within a token budget.
+//!
+//! Skills come from search results for the task, in rank order, scored by
+//! their relevance relative to the top result plus a bonus for each of
+//! their tags named in the task. Each skill's SKILL.md is followed by the
+//! sub-skills the task selects by trigger (see [`test_triggers`]) or that
+//! the search matched within. Deprecated skills are left out unless asked
+//! for.
+//!
+//! Items are added in order while they fit the budget; an item that
+//! doesn't is skipped, and smaller ones after it may still go in. Every
+//! part starts with a comment naming its source and why it was picked:
+//!
+//! ```text
+//! <!-- source: forms/validation.md (trigger "zod") -->
+//! ```
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::index::SkillIndexer;
+use crate::markdown;
+use crate::models::{SearchResult, SkillMeta, TriggerCase};
+use crate::search::{keywords, test_triggers, DEFAULT_TRIGGER_MIN_SCORE};
+
+/// Skills included unless told otherwise.
+pub const DEFAULT_MAX_SKILLS: usize = 5;
+
+/// Score added for each of a skill's tags named in the task.
+pub const TAG_WEIGHT: f64 = 0.25;
+
+/// Separator between parts of a pack's content.
+const SEPARATOR: &str = "\n\n---\n\n";
+
+/// One skill or sub-skill included in a pack.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextPart {
+    /// Skill name.
+    pub skill: String,
+
+    /// Sub-skill name, for sub-skill parts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+
+    /// Source file, as `skill/file`.
+    pub source: String,
+
+    /// Relevance score the part was ranked by.
+    pub score: f64,
+
+    /// Why the part was picked.
+    pub reasons: Vec<String>,
+
+    /// Estimated tokens of the part, header included.
+    pub tokens: u64,
+}
+
+/// Why a candidate was left out of a pack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExclusionReason {
+    /// It didn't fit in what was left of the budget.
+    OverBudget,
+
+    /// It ranked below the skills allowed in the pack.
+    SkillLimit,
+
+    /// The skill is deprecated.
+    Deprecated,
+
+    /// Its content couldn't be read.
+    Unreadable,
+}
+
+/// A candidate left out of a pack.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextExclusion {
+    /// Skill name.
+    pub skill: String,
+
+    /// Sub-skill name, for sub-skill candidates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+
+    /// Relevance score of the candidate.
+    pub score: f64,
+
+    /// Why it was left out.
+    pub reason: ExclusionReason,
+
+    /// Estimated tokens, when the content was read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<u64>,
+}
+
+/// Content assembled for a task.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContextPack {
+    /// Task the pack was built for.
+    pub task: String,
+
+    /// Included parts, each under a source comment, separated by rules.
+    pub content: String,
+
+    /// Included parts, in content order.
+    pub parts: Vec<ContextPart>,
+
+    /// Estimated tokens of the included parts.
+    pub total_tokens: u64,
+
+    /// Token budget applied.
+    pub max_tokens: u64,
+
+    /// Candidates left out, in rank order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub excluded: Vec<ContextExclusion>,
+}
+
+/// Limits of a pack.
+#[derive(Debug, Clone)]
+pub struct ContextOptions {
+    /// Token budget of the content.
+    pub max_tokens: u64,
+
+    /// Most skills to include.
+    pub max_skills: usize,
+
+    /// Consider deprecated skills too.
+    pub include_deprecated: bool,
+}
+
+impl ContextOptions {
+    /// Options with a token budget and the default skill limit.
+    pub fn with_max_tokens(max_tokens: u64) -> Self {
+        Self {
+            max_tokens,
+            max_skills: DEFAULT_MAX_SKILLS,
+            include_deprecated: false,
+        }
+    }
+}
+
+/// A skill or sub-skill considered for a pack.
+struct Candidate {
+    skill: String,
+    sub_skill: Option<String>,
+    file: String,
+    score: f64,
+    reasons: Vec<String>,
+}
+
+/// Build a pack for `task` from its search `results`.
+///
+/// Results for which `visible` is false are dropped without mention, so
+/// callers can hide skills without revealing them.
+pub fn build(
+    indexer: &SkillIndexer,
+    task: &str,
+    results: &[SearchResult],
+    options: &ContextOptions,
+    visible: impl Fn(&str) -> bool,
+) -> ContextPack {
+    let mut pack = ContextPack {
+        task: task.to_string(),
+        content: String::new(),
+        parts: Vec::new(),
+        total_tokens: 0,
+        max_tokens: options.max_tokens,
+        excluded: Vec::new(),
+    };
+
+    let mut skills: Vec<(SkillMeta, f64, Vec<String>)> = Vec::new();
+    let top = results.iter().map(|r| r.score).fold(0.0, f64::max);
+    let task_terms: HashSet<String> = keywords(task).into_iter().collect();
+    for (rank, result) in results.iter().enumerate() {
+        if skills.iter().any(|(meta, ..)| meta.name == result.domain) {
+            continue;
+        }
+        let Some(meta) = indexer.get_skill_meta(&result.domain).filter(|m| visible(&m.name)) else {
+            continue;
+        };
+        let mut score = if top > 0.0 { result.score / top } else { 0.0 };
+        let mut reasons = vec![format!("search rank {}", rank + 1)];
+        let tags: Vec<&str> = meta
+            .tags
+            .iter()
+            .filter(|tag| task_terms.contains(&tag.to_lowercase()))
+            .map(String::as_str)
+            .collect();
+        if !tags.is_empty() {
+            score += TAG_WEIGHT * tags.len() as f64;
+            reasons.push(format!("tags {}", tags.join(", ")));
+        }
+        skills.push((meta, score, reasons));
+    }
+    // Stable, so search rank breaks ties
+    skills.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut included = 0;
+    for (meta, score, reasons) in skills {
+        if meta.is_deprecated() && !options.include_deprecated {
+            pack.excluded.push(ContextExclusion {
+                skill: meta.name,
+                sub_skill: None,
+                score,
+                reason: ExclusionReason::Deprecated,
+                tokens: None,
+            });
+            continue;
+        }
+        let over_limit = included >= options.max_skills;
+        included += 1;
+        for candidate in candidates(&meta, score, reasons, task, results) {
+            if over_limit {
+                pack.exclude(&candidate, ExclusionReason::SkillLimit, None);
+                continue;
+            }
+            let content = match &candidate.sub_skill {
+                Some(sub) => indexer.read_sub_skill_content(&candidate.skill, sub).map(|c| c.content),
+                None => indexer.read_skill_content(&candidate.skill).map(|c| c.content),
+            };
+            let Ok(mut content) = content else {
+                pack.exclude(&candidate, ExclusionReason::Unreadable, None);
+                continue;
+            };
+            meta.append_deprecation_notice(&mut content);
+            pack.add(candidate, &content);
+        }
+    }
+    pack
+}
+
+/// A skill and its sub-skills selected for the task, skill first.
+fn candidates(
+    meta: &SkillMeta,
+    score: f64,
+    reasons: Vec<String>,
+    task: &str,
+    results: &[SearchResult],
+) -> Vec<Candidate> {
+    let mut candidates = vec![Candidate {
+        skill: meta.name.clone(),
+        sub_skill: None,
+        file: "SKILL.md".to_string(),
+        score,
+        reasons,
+    }];
+
+    let case = TriggerCase {
+        phrase: task.to_string(),
+        expect: None,
+    };
+    let report = test_triggers(meta, &[case], DEFAULT_TRIGGER_MIN_SCORE);
+    let mut selected: Vec<(String, f64, String)> = report
+        .results
+        .into_iter()
+        .flat_map(|test| test.sub_skills)
+        .filter(|sub| sub.selected)
+        .map(|sub| {
+            let trigger = sub.matches.first().map(|m| m.trigger.clone()).unwrap_or_default();
+            (sub.sub_skill, sub.score, format!("trigger \"{}\"", trigger))
+        })
+        .collect();
+    for result in results.iter().filter(|r| r.domain == meta.name) {
+        if let Some(sub) = &result.sub_skill {
+            if !selected.iter().any(|(name, ..)| name == sub) {
+                selected.push((sub.clone(), 0.0, "search match".to_string()));
+            }
+        }
+    }
+
+    for (name, trigger_score, reason) in selected {
+        let Some(sub) = meta.sub_skills.iter().flatten().find(|s| s.name == name) else {
+            continue;
+        };
+        candidates.push(Candidate {
+            skill: meta.name.clone(),
+            sub_skill: Some(name),
+            file: sub.file.clone(),
+            score: score * trigger_score.max(DEFAULT_TRIGGER_MIN_SCORE),
+            reasons: vec![reason],
+        });
+    }
+    candidates
+}
+
+impl ContextPack {
+    /// Add a candidate's content if it fits the budget.
+    fn add(&mut self, candidate: Candidate, content: &str) {
+        let source = format!("{}/{}", candidate.skill, candidate.file);
+        let part = format!(
+            "<!-- source: {} ({}) -->\n\n{}",
+            source,
+            candidate.reasons.join("; "),
+            content.trim_end()
+        );
+        let tokens = markdown::estimate_tokens(&part);
+        if self.total_tokens + tokens > self.max_tokens {
+            self.exclude(&candidate, ExclusionReason::OverBudget, Some(tokens));
+            return;
+        }
+
+        if !self.content.is_empty() {
+            self.content.push_str(SEPARATOR);
+        }
+        self.content.push_str(&part);
+        self.total_tokens += tokens;
+        self.parts.push(ContextPart {
+            skill: candidate.skill,
+            sub_skill: candidate.sub_skill,
+            source,
+            score: candidate.score,
+            reasons: candidate.reasons,
+            tokens,
+        });
+    }
+
+    fn exclude(&mut self, candidate: &Candidate, reason: ExclusionReason, tokens: Option<u64>) {
+        self.excluded.push(ContextExclusion {
+            skill: candidate.skill.clone(),
+            sub_skill: candidate.sub_skill.clone(),
+            score: candidate.score,
+            reason,
+            tokens,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MatchType;
+    use crate::test_support::write_skill;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn library() -> (TempDir, SkillIndexer) {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Forms", "tags": ["validation"],
+                "sub_skills": [{"name": "validation", "file": "validation.md", "triggers": ["zod"]},
+                               {"name": "react", "file": "react.md", "triggers": ["react-hook-form"]}]}"#,
+            "# Forms\n",
+        );
+        fs::write(temp_dir.path().join("forms/validation.md"), "# Validation\n").unwrap();
+        fs::write(temp_dir.path().join("forms/react.md"), "# React\n").unwrap();
+        write_skill(temp_dir.path(), "tables", r#"{"name": "tables", "description": "Tables"}"#, "# Tables\n");
+        write_skill(
+            temp_dir.path(),
+            "charts",
+            r#"{"name": "charts", "description": "Charts"}"#,
+            &"Plot the data. ".repeat(50),
+        );
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        (temp_dir, indexer)
+    }
+
+    fn result(domain: &str, score: f64) -> SearchResult {
+        SearchResult::new(domain.to_string(), score, MatchType::Description)
+    }
+
+    #[test]
+    fn test_build_pack() {
+        let (_temp_dir, indexer) = library();
+        let task = "add zod validation to the signup form";
+        let results = vec![result("charts", 0.03), result("tables", 0.02), result("forms", 0.02)];
+
+        let options = ContextOptions {
+            max_skills: 2,
+            ..ContextOptions::with_max_tokens(60)
+        };
+        let pack = build(&indexer, task, &results, &options, |_| true);
+        let sources: Vec<&str> = pack.parts.iter().map(|p| p.source.as_str()).collect();
+        // forms moves up for its tag, and charts is too long for the budget
+        assert_eq!(sources, vec!["forms/SKILL.md", "forms/validation.md"]);
+        assert!(pack.content.starts_with("<!-- source: forms/SKILL.md (search rank 3; tags validation) -->\n\n# Forms"));
+        assert!(pack.content.contains("<!-- source: forms/validation.md (trigger \"zod\") -->\n\n# Validation"));
+        assert!(pack.total_tokens <= 60);
+
+        let excluded: Vec<(&str, ExclusionReason)> =
+            pack.excluded.iter().map(|e| (e.skill.as_str(), e.reason)).collect();
+        assert_eq!(
+            excluded,
+            vec![("charts", ExclusionReason::OverBudget), ("tables", ExclusionReason::SkillLimit)]
+        );
+        assert!(pack.excluded[0].tokens.is_some_and(|t| t > 60));
+    }
+
+    #[test]
+    fn test_build_pack_hides_invisible_skills() {
+        let (_temp_dir, indexer) = library();
+        let results = vec![result("forms", 0.03), result("tables", 0.02), result("nope", 0.01)];
+
+        let options = ContextOptions::with_max_tokens(1000);
+        let pack = build(&indexer, "build a table", &results, &options, |name| name != "forms");
+        let sources: Vec<&str> = pack.parts.iter().map(|p| p.source.as_str()).collect();
+        assert_eq!(sources, vec!["tables/SKILL.md"]);
+        assert!(pack.excluded.is_empty());
+        assert!(!pack.content.contains("Forms"));
+    }
+
+    #[test]
+    fn test_build_pack_skips_deprecated() {
+        let (temp_dir, indexer) = library();
+        write_skill(
+            temp_dir.path(),
+            "old-tables",
+            r#"{"name": "old-tables", "description": "Tables", "superseded_by": "tables"}"#,
+            "# Old Tables\n",
+        );
+        indexer.reload().unwrap();
+        let results = vec![result("old-tables", 0.03), result("tables", 0.02)];
+
+        let pack = build(&indexer, "build a table", &results, &ContextOptions::with_max_tokens(1000), |_| true);
+        let sources: Vec<&str> = pack.parts.iter().map(|p| p.source.as_str()).collect();
+        assert_eq!(sources, vec!["tables/SKILL.md"]);
+        assert_eq!(pack.excluded[0].skill, "old-tables");
+        assert_eq!(pack.excluded[0].reason, ExclusionReason::Deprecated);
+
+        let options = ContextOptions {
+            include_deprecated: true,
+            ..ContextOptions::with_max_tokens(1000)
+        };
+        let pack = build(&indexer, "build a table", &results, &options, |_| true);
+        assert_eq!(pack.parts[0].skill, "old-tables");
+        assert!(pack.content.contains("# Old Tables\n\n> **Deprecated:** `old-tables` is superseded by `tables`"));
+    }
+}
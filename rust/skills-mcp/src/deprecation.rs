@@ -0,0 +1,171 @@
+//! Deprecated skills and what still points at them.
+//!
+//! A skill is deprecated by setting `deprecated` or `superseded_by` in its
+//! `_meta.json`. It stays readable, but every other skill that depends on
+//! it, lists it in `see_also`, or links into it from markdown should move
+//! to its successor before it is archived. The report lists those.
+
+use serde::Serialize;
+
+use crate::index::{LinkGraph, SkillIndexer};
+
+/// How a skill refers to a deprecated one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// Listed in `depends_on`.
+    DependsOn,
+    /// Listed in `see_also`.
+    SeeAlso,
+    /// A markdown link into the skill.
+    Link,
+}
+
+/// A reference to a deprecated skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecationReference {
+    /// Skill holding the reference.
+    pub skill: String,
+
+    /// How it refers to the deprecated skill.
+    pub kind: ReferenceKind,
+
+    /// File containing the link, for links.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+}
+
+/// A deprecated skill and the references to it.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecatedSkill {
+    /// Skill name.
+    pub name: String,
+
+    /// Skill replacing it, if named.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+
+    /// Whether the skill is also archived.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+
+    /// References from other skills, by skill name.
+    pub referenced_by: Vec<DeprecationReference>,
+}
+
+/// Deprecated skills of a library.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeprecationReport {
+    /// Deprecated skills, sorted by name.
+    pub skills: Vec<DeprecatedSkill>,
+
+    /// References to deprecated skills across all of them.
+    pub total_references: usize,
+}
+
+/// List deprecated skills and the references to them from other skills.
+///
+/// Skills for which `visible` is false are left out, both as deprecated
+/// skills and as sources of references.
+pub fn deprecation_report(indexer: &SkillIndexer, visible: impl Fn(&str) -> bool) -> DeprecationReport {
+    let index = indexer.get_skill_index();
+    let links = LinkGraph::new(indexer.skills_dir(), &index, &indexer.get_content_index());
+
+    let mut skills: Vec<DeprecatedSkill> = index
+        .skills
+        .iter()
+        .filter(|s| s.is_deprecated() && visible(&s.name))
+        .map(|deprecated| {
+            let mut referenced_by = Vec::new();
+            for skill in index.skills.iter().filter(|s| s.name != deprecated.name && visible(&s.name)) {
+                for (kind, names) in [
+                    (ReferenceKind::DependsOn, &skill.depends_on),
+                    (ReferenceKind::SeeAlso, &skill.see_also),
+                ] {
+                    if names.iter().any(|n| index.find(n).is_some_and(|s| s.name == deprecated.name)) {
+                        referenced_by.push(DeprecationReference {
+                            skill: skill.name.clone(),
+                            kind,
+                            file: None,
+                        });
+                    }
+                }
+            }
+            for link in links.backlinks(&deprecated.name, None) {
+                if !visible(&link.source_skill) {
+                    continue;
+                }
+                let file = Some(link.source_file.clone());
+                if !referenced_by.iter().any(|r| r.skill == link.source_skill && r.file == file) {
+                    referenced_by.push(DeprecationReference {
+                        skill: link.source_skill.clone(),
+                        kind: ReferenceKind::Link,
+                        file,
+                    });
+                }
+            }
+            referenced_by.sort_by(|a, b| a.skill.cmp(&b.skill));
+
+            DeprecatedSkill {
+                name: deprecated.name.clone(),
+                superseded_by: deprecated.superseded_by.clone(),
+                archived: deprecated.archived,
+                referenced_by,
+            }
+        })
+        .collect();
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+
+    DeprecationReport {
+        total_references: skills.iter().map(|s| s.referenced_by.len()).sum(),
+        skills,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_skill;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_deprecation_report() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Forms", "superseded_by": "forms-v2"}"#,
+            "# Forms\n",
+        );
+        write_skill(temp_dir.path(), "forms-v2", r#"{"name": "forms-v2", "description": "Forms"}"#, "# Forms\n");
+        write_skill(
+            temp_dir.path(),
+            "tables",
+            r#"{"name": "tables", "description": "Tables", "depends_on": ["forms"], "see_also": ["forms"]}"#,
+            "# Tables\n\nSee [forms](../forms/SKILL.md).\n",
+        );
+        write_skill(
+            temp_dir.path(),
+            "charts",
+            r#"{"name": "charts", "description": "Charts", "deprecated": true}"#,
+            "# Charts\n",
+        );
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+
+        let report = deprecation_report(&indexer, |_| true);
+        let names: Vec<&str> = report.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["charts", "forms"]);
+        assert!(report.skills[0].referenced_by.is_empty());
+
+        let forms = &report.skills[1];
+        assert_eq!(forms.superseded_by.as_deref(), Some("forms-v2"));
+        let kinds: Vec<ReferenceKind> = forms.referenced_by.iter().map(|r| r.kind).collect();
+        assert_eq!(kinds, vec![ReferenceKind::DependsOn, ReferenceKind::SeeAlso, ReferenceKind::Link]);
+        assert_eq!(forms.referenced_by[2].file.as_deref(), Some("SKILL.md"));
+        assert_eq!(report.total_references, 3);
+
+        let report = deprecation_report(&indexer, |name| name != "tables");
+        assert_eq!(report.total_references, 0);
+    }
+}
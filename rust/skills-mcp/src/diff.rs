@@ -0,0 +1,667 @@
+//! Comparison of two skill libraries, or of two skills.
+//!
+//! A library can be read from a skills directory, a ZIP snapshot (entries
+//! stored as `<skill>/<file>`, as produced by export), or a running API
+//! server over HTTP or HTTPS. Servers only expose SKILL.md, so comparisons
+//! involving a server cover metadata and SKILL.md only.
+//!
+//! Two skills, such as a fork and its origin, are compared with
+//! [`diff_skills`]: metadata field by field, and files by path.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+use crate::fetch;
+use crate::index::SkillIndexer;
+use crate::models::SkillMeta;
+
+/// Largest line-count product diffed line by line; bigger files are shown
+/// as a full replacement.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Lines of context around each change in unified diffs.
+const CONTEXT_LINES: usize = 3;
+
+/// Network timeout for fetching from a server.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest response read from a server.
+const MAX_RESPONSE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// One skill as seen in a snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct SkillSnapshot {
+    /// Skill description.
+    pub description: String,
+
+    /// Skill tags.
+    pub tags: Vec<String>,
+
+    /// File contents keyed by path relative to the skill directory.
+    /// Binary files are recorded as a size placeholder.
+    pub files: BTreeMap<String, String>,
+}
+
+/// A whole library, keyed by skill name.
+#[derive(Debug, Clone, Default)]
+pub struct LibrarySnapshot {
+    /// Skills by name.
+    pub skills: BTreeMap<String, SkillSnapshot>,
+
+    /// Whether only SKILL.md was captured for each skill.
+    pub skill_md_only: bool,
+}
+
+impl LibrarySnapshot {
+    /// Load a snapshot from a directory, `.zip` file, or `http(s)://` URL.
+    pub fn load(source: &str) -> Result<Self, DiffError> {
+        if source.starts_with("http://") || source.starts_with("https://") {
+            return Self::fetch(source);
+        }
+
+        let path = Path::new(source);
+        if path.is_file() {
+            Self::from_zip(&fs::read(path)?)
+        } else if path.is_dir() {
+            Self::from_dir(path)
+        } else {
+            Err(DiffError::NotFound(source.to_string()))
+        }
+    }
+
+    /// Read a skills directory, using the indexer to resolve metadata.
+    pub fn from_dir(skills_dir: &Path) -> Result<Self, DiffError> {
+        let indexer = Arc::new(SkillIndexer::new(skills_dir));
+        indexer
+            .reload()
+            .map_err(|e| DiffError::Invalid(e.to_string()))?;
+
+        let mut skills = BTreeMap::new();
+        for meta in indexer.get_skill_index().skills {
+            let files = read_skill_files(&skills_dir.join(&meta.name))?;
+            skills.insert(meta.name.clone(), skill_snapshot(&meta, files));
+        }
+
+        Ok(Self {
+            skills,
+            skill_md_only: false,
+        })
+    }
+
+    /// Read a ZIP archive whose entries are stored as `<skill>/<file>`.
+    pub fn from_zip(bytes: &[u8]) -> Result<Self, DiffError> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+        let mut all_files: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            if entry.is_dir() {
+                continue;
+            }
+            let name = entry.name().replace('\\', "/");
+            let Some((skill, file)) = name.split_once('/') else {
+                continue;
+            };
+            if skill.starts_with('.') || skill.starts_with('_') {
+                continue;
+            }
+
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            all_files
+                .entry(skill.to_string())
+                .or_default()
+                .insert(file.to_string(), file_text(data));
+        }
+
+        let mut skills = BTreeMap::new();
+        for (name, files) in all_files {
+            let Some(meta_json) = files.get("_meta.json") else {
+                continue;
+            };
+            let meta: SkillMeta = serde_json::from_str(meta_json).map_err(|e| {
+                DiffError::Invalid(format!("{}/_meta.json: {}", name, e))
+            })?;
+            skills.insert(name, skill_snapshot(&meta, files));
+        }
+
+        Ok(Self {
+            skills,
+            skill_md_only: false,
+        })
+    }
+
+    /// Fetch metadata and SKILL.md for every skill from an API server.
+    pub fn fetch(base_url: &str) -> Result<Self, DiffError> {
+        #[derive(Deserialize)]
+        struct ListItem {
+            name: String,
+        }
+        #[derive(Deserialize)]
+        struct Details {
+            description: String,
+            tags: Vec<String>,
+            content: String,
+        }
+
+        let base = base_url.trim_end_matches('/').trim_end_matches("/api");
+        let list: Vec<ListItem> = get_json(&format!("{}/api/skills", base))?;
+
+        let mut skills = BTreeMap::new();
+        for item in list {
+            let details: Details = get_json(&format!("{}/api/skills/{}", base, item.name))?;
+            let files = BTreeMap::from([("SKILL.md".to_string(), details.content)]);
+            skills.insert(
+                item.name,
+                SkillSnapshot {
+                    description: details.description,
+                    tags: details.tags,
+                    files,
+                },
+            );
+        }
+
+        Ok(Self {
+            skills,
+            skill_md_only: true,
+        })
+    }
+}
+
+fn skill_snapshot(meta: &SkillMeta, files: BTreeMap<String, String>) -> SkillSnapshot {
+    SkillSnapshot {
+        description: meta.description.clone(),
+        tags: meta.tags.clone(),
+        files,
+    }
+}
+
+/// Contents of the visible files in a skill directory, keyed by relative
+/// path.
+pub fn read_skill_files(skill_dir: &Path) -> Result<BTreeMap<String, String>, DiffError> {
+    let mut files = BTreeMap::new();
+    for entry in WalkDir::new(skill_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let relative = entry
+            .path()
+            .strip_prefix(skill_dir)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        files.insert(relative, file_text(fs::read(entry.path())?));
+    }
+    Ok(files)
+}
+
+/// Decode file contents, recording binary files by size only.
+fn file_text(data: Vec<u8>) -> String {
+    let len = data.len();
+    String::from_utf8(data).unwrap_or_else(|_| format!("<binary file, {} bytes>", len))
+}
+
+/// How a file differs between libraries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    /// Only in the second library.
+    Added,
+    /// Only in the first library.
+    Removed,
+    /// In both, with different contents.
+    Modified,
+}
+
+/// A file that differs between libraries.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChange {
+    /// Path relative to the skill directory.
+    pub path: String,
+
+    /// Kind of change.
+    pub status: FileStatus,
+
+    /// Unified diff of the contents.
+    pub diff: String,
+}
+
+/// A skill present in both libraries with differences.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillChange {
+    /// Skill name.
+    pub name: String,
+
+    /// Metadata fields that differ, as human-readable descriptions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub metadata: Vec<String>,
+
+    /// Files that differ.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<FileChange>,
+}
+
+/// Differences between two libraries.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LibraryDiff {
+    /// Skills only in the second library.
+    pub added: Vec<String>,
+
+    /// Skills only in the first library.
+    pub removed: Vec<String>,
+
+    /// Skills in both with differences.
+    pub changed: Vec<SkillChange>,
+
+    /// Number of skills that are identical.
+    pub unchanged: usize,
+
+    /// Whether only metadata and SKILL.md were compared.
+    pub skill_md_only: bool,
+}
+
+impl LibraryDiff {
+    /// Whether the libraries are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two libraries.
+///
+/// If either snapshot only captured SKILL.md, other files are ignored.
+pub fn diff_libraries(a: &LibrarySnapshot, b: &LibrarySnapshot) -> LibraryDiff {
+    let skill_md_only = a.skill_md_only || b.skill_md_only;
+    let mut diff = LibraryDiff {
+        skill_md_only,
+        ..Default::default()
+    };
+
+    let names: BTreeSet<&String> = a.skills.keys().chain(b.skills.keys()).collect();
+    for name in names {
+        let (old, new) = match (a.skills.get(name), b.skills.get(name)) {
+            (Some(old), Some(new)) => (old, new),
+            (Some(_), None) => {
+                diff.removed.push(name.clone());
+                continue;
+            }
+            (None, _) => {
+                diff.added.push(name.clone());
+                continue;
+            }
+        };
+
+        let mut metadata = Vec::new();
+        if old.description != new.description {
+            metadata.push(format!(
+                "description: '{}' -> '{}'",
+                old.description, new.description
+            ));
+        }
+        if old.tags != new.tags {
+            metadata.push(format!(
+                "tags: [{}] -> [{}]",
+                old.tags.join(", "),
+                new.tags.join(", ")
+            ));
+        }
+
+        let in_scope = |path: &str| !skill_md_only || path == "SKILL.md";
+        let (files, _) = diff_files(&old.files, &new.files, in_scope);
+
+        if metadata.is_empty() && files.is_empty() {
+            diff.unchanged += 1;
+        } else {
+            diff.changed.push(SkillChange {
+                name: name.clone(),
+                metadata,
+                files,
+            });
+        }
+    }
+
+    diff
+}
+
+/// Files that differ between two skills, among the paths `in_scope`
+/// accepts, and the number of identical ones.
+fn diff_files(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+    in_scope: impl Fn(&str) -> bool,
+) -> (Vec<FileChange>, usize) {
+    let paths: BTreeSet<&String> = old.keys().chain(new.keys()).filter(|p| in_scope(p)).collect();
+
+    let mut files = Vec::new();
+    let mut unchanged = 0;
+    for path in paths {
+        let (status, before, after) = match (old.get(path), new.get(path)) {
+            (Some(before), Some(after)) if before == after => {
+                unchanged += 1;
+                continue;
+            }
+            (Some(before), Some(after)) => (FileStatus::Modified, before.as_str(), after.as_str()),
+            (Some(before), None) => (FileStatus::Removed, before.as_str(), ""),
+            (None, Some(after)) => (FileStatus::Added, "", after.as_str()),
+            (None, None) => continue,
+        };
+        files.push(FileChange {
+            path: path.clone(),
+            status,
+            diff: unified_diff(before, after),
+        });
+    }
+    (files, unchanged)
+}
+
+/// Metadata fields left out of skill comparisons: they always differ
+/// between two skills, or describe where and when files were written.
+const IGNORED_FIELDS: &[&str] = &["name", "root", "created_at", "updated_at"];
+
+/// A metadata field that differs between two skills.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    /// Field name, as in `_meta.json`.
+    pub field: String,
+
+    /// Value in the left skill; null when unset.
+    pub left: serde_json::Value,
+
+    /// Value in the right skill; null when unset.
+    pub right: serde_json::Value,
+}
+
+/// Differences between two skills.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillDiff {
+    /// Name of the left skill.
+    pub left: String,
+
+    /// Name of the right skill.
+    pub right: String,
+
+    /// Metadata fields that differ, by field name.
+    pub metadata: Vec<FieldChange>,
+
+    /// Files that differ, by path. `_meta.json` is covered by `metadata`.
+    pub files: Vec<FileChange>,
+
+    /// Files identical in both.
+    pub unchanged_files: usize,
+}
+
+impl SkillDiff {
+    /// Whether the skills have the same metadata and files.
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty() && self.files.is_empty()
+    }
+}
+
+/// Compare two skills: `left` and `right` are their metadata and their
+/// files as read by [`read_skill_files`].
+pub fn diff_skills(
+    left: (&SkillMeta, &BTreeMap<String, String>),
+    right: (&SkillMeta, &BTreeMap<String, String>),
+) -> SkillDiff {
+    let fields = |meta: &SkillMeta| match serde_json::to_value(meta) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    };
+    let (left_fields, right_fields) = (fields(left.0), fields(right.0));
+
+    let names: BTreeSet<&String> = left_fields.keys().chain(right_fields.keys()).collect();
+    let metadata = names
+        .into_iter()
+        .filter(|name| !IGNORED_FIELDS.contains(&name.as_str()))
+        .filter_map(|name| {
+            let (l, r) = (left_fields.get(name), right_fields.get(name));
+            (l != r).then(|| FieldChange {
+                field: name.clone(),
+                left: l.cloned().unwrap_or_default(),
+                right: r.cloned().unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    let (files, unchanged_files) = diff_files(left.1, right.1, |path| path != "_meta.json");
+    SkillDiff {
+        left: left.0.name.clone(),
+        right: right.0.name.clone(),
+        metadata,
+        files,
+        unchanged_files,
+    }
+}
+
+/// Produce a unified diff (`-`/`+`/` ` lines with `@@` hunk headers).
+pub fn unified_diff(old: &str, new: &str) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    let ops = if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        a.iter().map(|l| Op::Delete(l)).chain(b.iter().map(|l| Op::Insert(l))).collect()
+    } else {
+        diff_lines(&a, &b)
+    };
+
+    // Group changes into hunks with surrounding context
+    let changed: Vec<usize> = (0..ops.len()).filter(|&i| !matches!(ops[i], Op::Equal(_))).collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < changed.len() {
+        let start = changed[i].saturating_sub(CONTEXT_LINES);
+        let mut end = changed[i];
+        while i < changed.len() && changed[i] <= end + 2 * CONTEXT_LINES {
+            end = changed[i];
+            i += 1;
+        }
+        let end = (end + CONTEXT_LINES + 1).min(ops.len());
+
+        let (old_start, new_start) = line_numbers(&ops[..start]);
+        let (old_len, new_len) = line_numbers(&ops[start..end]);
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start + 1,
+            old_len,
+            new_start + 1,
+            new_len
+        ));
+        for op in &ops[start..end] {
+            let (prefix, line) = match op {
+                Op::Equal(l) => (' ', l),
+                Op::Delete(l) => ('-', l),
+                Op::Insert(l) => ('+', l),
+            };
+            out.push(prefix);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Count lines added and removed between two texts.
+pub fn line_changes(old: &str, new: &str) -> (usize, usize) {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        return (b.len(), a.len());
+    }
+    diff_lines(&a, &b).iter().fold((0, 0), |(added, removed), op| match op {
+        Op::Equal(_) => (added, removed),
+        Op::Delete(_) => (added, removed + 1),
+        Op::Insert(_) => (added + 1, removed),
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Count old and new lines covered by a run of operations.
+fn line_numbers(ops: &[Op]) -> (usize, usize) {
+    ops.iter().fold((0, 0), |(old, new), op| match op {
+        Op::Equal(_) => (old + 1, new + 1),
+        Op::Delete(_) => (old + 1, new),
+        Op::Insert(_) => (old, new + 1),
+    })
+}
+
+/// Line diff via longest common subsequence.
+fn diff_lines<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<Op<'a>> {
+    let (n, m) = (a.len(), b.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && a[i] == b[j] {
+            ops.push(Op::Equal(a[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(Op::Delete(a[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(b[j]));
+            j += 1;
+        }
+    }
+    ops
+}
+
+/// GET a URL and parse the JSON body.
+fn get_json<T: serde::de::DeserializeOwned>(url: &str) -> Result<T, DiffError> {
+    let body = fetch::fetch(url, MAX_RESPONSE_BYTES, HTTP_TIMEOUT).map_err(|e| DiffError::Http(e.to_string()))?;
+    serde_json::from_slice(&body).map_err(|e| DiffError::Http(format!("{}: {}", url, e)))
+}
+
+/// Errors that can occur while loading or comparing libraries.
+#[derive(Debug, thiserror::Error)]
+pub enum DiffError {
+    /// The source does not exist.
+    #[error("Source not found: {0}")]
+    NotFound(String),
+
+    /// The source contents could not be interpreted.
+    #[error("Invalid source: {0}")]
+    Invalid(String),
+
+    /// Fetching from a server failed.
+    #[error("HTTP error: {0}")]
+    Http(String),
+
+    /// Filesystem error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to read a ZIP snapshot.
+    #[error("Archive error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{meta, write_skill};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unified_diff() {
+        let diff = unified_diff("a\nb\nc\n", "a\nB\nc\nd\n");
+        assert_eq!(diff, "@@ -1,3 +1,4 @@\n a\n-b\n+B\n c\n+d\n");
+        assert!(unified_diff("same", "same").is_empty());
+        assert_eq!(line_changes("a\nb\nc\n", "a\nB\nc\nd\n"), (2, 1));
+    }
+
+    #[test]
+    fn test_diff_directories_and_zip() {
+        let a = TempDir::new().unwrap();
+        let b = TempDir::new().unwrap();
+        write_skill(a.path(), "forms", &meta("forms", "Forms"), "# Forms\n");
+        write_skill(a.path(), "legacy", &meta("legacy", "Old"), "# Legacy\n");
+        write_skill(a.path(), "testing", &meta("testing", "Testing"), "# Testing\n");
+        write_skill(b.path(), "forms", &meta("forms", "Form handling"), "# Forms\n\nNew.\n");
+        write_skill(b.path(), "testing", &meta("testing", "Testing"), "# Testing\n");
+        write_skill(b.path(), "api", &meta("api", "API"), "# API\n");
+
+        let old = LibrarySnapshot::load(a.path().to_str().unwrap()).unwrap();
+        let new = LibrarySnapshot::load(b.path().to_str().unwrap()).unwrap();
+        let diff = diff_libraries(&old, &new);
+
+        assert_eq!(diff.added, vec!["api"]);
+        assert_eq!(diff.removed, vec!["legacy"]);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].metadata.len(), 1);
+        let paths: Vec<&str> = diff.changed[0].files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "_meta.json"]);
+
+        // A ZIP export of the same library compares equal
+        let bytes = crate::export::export_skill(b.path(), "testing", true).unwrap();
+        let zipped = LibrarySnapshot::from_zip(&bytes).unwrap();
+        assert!(zipped.skills.contains_key("testing"));
+        let diff = diff_libraries(&zipped, &new);
+        assert_eq!(diff.added, vec!["api", "forms"]);
+        assert_eq!(diff.unchanged, 1);
+    }
+
+    #[test]
+    fn test_diff_skills() {
+        let temp = TempDir::new().unwrap();
+        write_skill(temp.path(), "forms", &meta("forms", "Forms"), "# Forms\n");
+        write_skill(temp.path(), "forms-fork", &meta("forms-fork", "Forms"), "# Forms\n\nBetter.\n");
+        fs::write(temp.path().join("forms/notes.md"), "Notes").unwrap();
+        fs::write(temp.path().join("forms-fork/notes.md"), "Notes").unwrap();
+        fs::write(temp.path().join("forms-fork/extra.md"), "Extra").unwrap();
+
+        let indexer = SkillIndexer::new(temp.path());
+        indexer.reload().unwrap();
+        let side = |name: &str| {
+            let mut meta = indexer.get_skill_meta(name).unwrap();
+            if name == "forms-fork" {
+                meta.tags = vec!["forms".to_string()];
+            }
+            (meta, read_skill_files(&temp.path().join(name)).unwrap())
+        };
+        let (left, right) = (side("forms"), side("forms-fork"));
+
+        let diff = diff_skills((&left.0, &left.1), (&right.0, &right.1));
+        assert_eq!(
+            diff.metadata,
+            vec![FieldChange {
+                field: "tags".to_string(),
+                left: serde_json::Value::Null,
+                right: serde_json::json!(["forms"]),
+            }]
+        );
+        let files: Vec<(&str, FileStatus)> = diff.files.iter().map(|f| (f.path.as_str(), f.status)).collect();
+        assert_eq!(files, vec![("SKILL.md", FileStatus::Modified), ("extra.md", FileStatus::Added)]);
+        assert_eq!(diff.unchanged_files, 1);
+        assert!(diff_skills((&left.0, &left.1), (&left.0, &left.1)).is_empty());
+    }
+}
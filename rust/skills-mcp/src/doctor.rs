@@ -0,0 +1,610 @@
+//! Self-check and repair of a skills directory (`skills-mcp doctor`).
+//!
+//! The doctor looks for problems that stop skills from loading or leave
+//! server state out of step with the library:
+//!
+//! - **structure**: the skills directory exists, and every skill directory
+//!   has its SKILL.md
+//! - **meta**: every `_meta.json` parses; malformed files that can be
+//!   recovered (a byte order mark, comments, trailing commas, `tags` as a
+//!   comma-separated string) are rewritten when fixing
+//! - **orphaned_file**: files that aren't SKILL.md, a sub-skill, or a
+//!   reference; fixing registers markdown files next to SKILL.md as
+//!   sub-skills in `_meta.json`
+//! - **journal**: the mutation journal parses, is in sequence, and matches
+//!   the directory
+//! - **manifest**: the integrity manifest, if any, matches the directory
+//! - **analytics_store**: the analytics store, if any, parses
+//!
+//! Files are only changed when fixing, and a `_meta.json` is copied to
+//! `_meta.json.bak` before it is first rewritten.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::analytics::{self, Analytics};
+use crate::index::{orphaned_files, LocalStore, SkillIndexer};
+use crate::integrity::Manifest;
+use crate::journal::{self, JOURNAL_FILE};
+use crate::lifecycle::normalize_name;
+use crate::models::SkillMeta;
+
+/// Suffix of the copy kept of a `_meta.json` before it is rewritten.
+pub const BACKUP_SUFFIX: &str = ".bak";
+
+/// What a finding is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorCheck {
+    /// Layout of the skills directory.
+    Structure,
+    /// A `_meta.json` file.
+    Meta,
+    /// A file that isn't SKILL.md, a sub-skill, or a reference.
+    OrphanedFile,
+    /// The mutation journal.
+    Journal,
+    /// The integrity manifest.
+    Manifest,
+    /// The analytics store.
+    AnalyticsStore,
+}
+
+/// How serious a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DoctorSeverity {
+    /// Skills fail to load or server state is unusable.
+    Error,
+    /// Worth a look, but nothing is broken.
+    Warning,
+}
+
+/// One problem found.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorFinding {
+    /// Check that found it.
+    pub check: DoctorCheck,
+    /// How serious it is.
+    pub severity: DoctorSeverity,
+    /// File or directory, relative to the skills directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// What is wrong.
+    pub message: String,
+    /// Whether fixing would repair it.
+    pub fixable: bool,
+    /// Whether it was repaired.
+    pub fixed: bool,
+}
+
+/// Everything the doctor found.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    /// Directory checked.
+    pub skills_dir: String,
+    /// When the checks ran.
+    #[serde(with = "crate::models::timestamp")]
+    pub checked_at: DateTime<Utc>,
+    /// Whether fixable problems were repaired.
+    pub fix: bool,
+    /// Skill directories checked.
+    pub skills_checked: usize,
+    /// Problems, in the order found.
+    pub findings: Vec<DoctorFinding>,
+}
+
+impl DoctorReport {
+    /// Errors that weren't repaired.
+    pub fn errors(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == DoctorSeverity::Error && !f.fixed)
+            .count()
+    }
+
+    /// Whether no errors are left.
+    pub fn is_healthy(&self) -> bool {
+        self.errors() == 0
+    }
+}
+
+/// Checks a skills directory and, when fixing, repairs what it can.
+#[derive(Debug, Clone)]
+pub struct Doctor {
+    skills_dir: PathBuf,
+    journal: PathBuf,
+    analytics_store: PathBuf,
+    fix: bool,
+}
+
+impl Doctor {
+    /// Check `skills_dir`, with the journal and analytics store at their
+    /// default locations inside it.
+    pub fn new(skills_dir: impl Into<PathBuf>) -> Self {
+        let skills_dir = skills_dir.into();
+        Self {
+            journal: skills_dir.join(JOURNAL_FILE),
+            analytics_store: skills_dir.join(analytics::STORE_FILE),
+            skills_dir,
+            fix: false,
+        }
+    }
+
+    /// Check the journal at `path`.
+    pub fn with_journal(mut self, path: impl Into<PathBuf>) -> Self {
+        self.journal = path.into();
+        self
+    }
+
+    /// Check the analytics store at `path`.
+    pub fn with_analytics_store(mut self, path: impl Into<PathBuf>) -> Self {
+        self.analytics_store = path.into();
+        self
+    }
+
+    /// Repair fixable problems.
+    pub fn with_fix(mut self, fix: bool) -> Self {
+        self.fix = fix;
+        self
+    }
+
+    /// Run every check.
+    pub fn run(&self) -> DoctorReport {
+        let mut run = Run {
+            doctor: self,
+            findings: Vec::new(),
+            backed_up: HashSet::new(),
+        };
+        let skills_checked = run.check_library();
+        run.check_state();
+
+        DoctorReport {
+            skills_dir: self.skills_dir.display().to_string(),
+            checked_at: Utc::now(),
+            fix: self.fix,
+            skills_checked,
+            findings: run.findings,
+        }
+    }
+}
+
+/// State of one doctor run.
+struct Run<'a> {
+    doctor: &'a Doctor,
+    findings: Vec<DoctorFinding>,
+    /// `_meta.json` files already backed up.
+    backed_up: HashSet<PathBuf>,
+}
+
+impl Run<'_> {
+    /// Check skill directories and their metadata; returns how many there
+    /// are.
+    fn check_library(&mut self) -> usize {
+        let skills_dir = &self.doctor.skills_dir;
+        if !skills_dir.is_dir() {
+            self.error(DoctorCheck::Structure, None, format!("Skills directory {:?} not found", skills_dir));
+            return 0;
+        }
+        let dirs = match crate::index::skill_dirs(skills_dir) {
+            Ok(dirs) => dirs,
+            Err(e) => {
+                self.error(DoctorCheck::Structure, None, format!("Failed to list skills: {}", e));
+                return 0;
+            }
+        };
+
+        for (name, dir) in &dirs {
+            let has_meta = dir.join("_meta.json").is_file();
+            if !dir.join("SKILL.md").is_file() {
+                if has_meta {
+                    self.error(DoctorCheck::Structure, Some(name.clone()), "Missing SKILL.md".to_string());
+                } else {
+                    self.warning(
+                        DoctorCheck::Structure,
+                        Some(name.clone()),
+                        "Neither _meta.json nor SKILL.md; not a skill".to_string(),
+                    );
+                }
+            }
+            if has_meta {
+                self.check_meta(name, dir);
+            }
+        }
+
+        // Orphans are looked up in the metadata as the server loads it
+        let indexer = SkillIndexer::new(skills_dir);
+        if indexer.reload().is_ok() {
+            for meta in &indexer.get_skill_index().skills {
+                let dir = indexer.skill_dir(&meta.name);
+                self.check_orphans(meta, &dir);
+            }
+        }
+        dirs.len()
+    }
+
+    /// Check that a skill's `_meta.json` loads, repairing it if it can.
+    fn check_meta(&mut self, name: &str, dir: &Path) {
+        let path = dir.join("_meta.json");
+        let relative = format!("{}/_meta.json", name);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                self.error(DoctorCheck::Meta, Some(relative), format!("Unreadable: {}", e));
+                return;
+            }
+        };
+        let problem = match serde_json::from_str::<Value>(&text) {
+            Ok(value) => match serde_json::from_value::<SkillMeta>(value) {
+                Ok(_) => return,
+                Err(e) => format!("Invalid metadata: {}", e),
+            },
+            Err(e) => format!("Invalid JSON: {}", e),
+        };
+
+        match repair_meta(&text) {
+            Some(repaired) => {
+                let fixed = self.doctor.fix && self.write_meta(&path, &relative, &repaired);
+                self.findings.push(DoctorFinding {
+                    check: DoctorCheck::Meta,
+                    severity: DoctorSeverity::Error,
+                    path: Some(relative),
+                    message: problem,
+                    fixable: true,
+                    fixed,
+                });
+            }
+            None => self.error(DoctorCheck::Meta, Some(relative), problem),
+        }
+    }
+
+    /// Report markdown files next to SKILL.md that no sub-skill declares,
+    /// registering them when fixing.
+    fn check_orphans(&mut self, meta: &SkillMeta, dir: &Path) {
+        let orphans = orphaned_files(&LocalStore, meta, dir);
+        if orphans.is_empty() {
+            return;
+        }
+
+        let skill_path = relative_to(dir, &self.doctor.skills_dir);
+        let meta_path = dir.join("_meta.json");
+        let first = self.findings.len();
+        let mut taken: HashSet<String> = meta.sub_skill_names().into_iter().map(str::to_string).collect();
+        let mut register = Vec::new();
+        for file in orphans {
+            // Only markdown files next to SKILL.md can become sub-skills
+            let stem = file.strip_suffix(".md").filter(|stem| !stem.contains('/'));
+            let sub_name = stem.map(normalize_name).unwrap_or_default();
+            let fixable = meta_path.is_file() && !sub_name.is_empty() && !taken.contains(&sub_name);
+            let message = if fixable {
+                format!("Not declared as a sub-skill (registered by fixing as '{}')", sub_name)
+            } else {
+                "Not SKILL.md, a sub-skill, or under references/".to_string()
+            };
+            if fixable {
+                taken.insert(sub_name.clone());
+                register.push((sub_name, file.clone()));
+            }
+            self.findings.push(DoctorFinding {
+                check: DoctorCheck::OrphanedFile,
+                severity: DoctorSeverity::Warning,
+                path: Some(format!("{}/{}", skill_path, file)),
+                message,
+                fixable,
+                fixed: false,
+            });
+        }
+
+        if !self.doctor.fix || register.is_empty() {
+            return;
+        }
+        let relative = format!("{}/_meta.json", skill_path);
+        let Some(updated) = register_sub_skills(&meta_path, &register) else {
+            self.error(DoctorCheck::Meta, Some(relative), "Failed to register sub-skills".to_string());
+            return;
+        };
+        if self.write_meta(&meta_path, &relative, &updated) {
+            for finding in self.findings[first..].iter_mut().filter(|f| f.fixable) {
+                finding.fixed = true;
+            }
+        }
+    }
+
+    /// Check the journal, manifest, and analytics store.
+    fn check_state(&mut self) {
+        let doctor = self.doctor;
+        let journal_path = relative_to(&doctor.journal, &doctor.skills_dir);
+        if doctor.journal.is_file() {
+            match journal::check(&doctor.journal, &doctor.skills_dir) {
+                Ok(check) => {
+                    if !check.out_of_sequence.is_empty() {
+                        self.error(
+                            DoctorCheck::Journal,
+                            Some(journal_path.clone()),
+                            format!("Entries out of sequence: {:?}", check.out_of_sequence),
+                        );
+                    }
+                    if !check.unrecorded.is_empty() {
+                        self.warning(
+                            DoctorCheck::Journal,
+                            Some(journal_path),
+                            format!(
+                                "{} files changed since the last entry; they are journaled when the server next starts: {}",
+                                check.unrecorded.len(),
+                                check.unrecorded.join(", ")
+                            ),
+                        );
+                    }
+                }
+                Err(e) => self.error(DoctorCheck::Journal, Some(journal_path), e.to_string()),
+            }
+        }
+
+        match Manifest::load(&doctor.skills_dir) {
+            Ok(Some(manifest)) => match manifest.verify(&doctor.skills_dir) {
+                Ok(report) if report.is_verified() => {}
+                Ok(report) => self.warning(
+                    DoctorCheck::Manifest,
+                    Some(crate::integrity::MANIFEST_FILE.to_string()),
+                    format!(
+                        "Library differs from the manifest: {} modified, {} missing, {} unexpected",
+                        report.modified.len(),
+                        report.missing.len(),
+                        report.unexpected.len()
+                    ),
+                ),
+                Err(e) => self.error(DoctorCheck::Manifest, None, e.to_string()),
+            },
+            Ok(None) => {}
+            Err(e) => self.error(DoctorCheck::Manifest, Some(crate::integrity::MANIFEST_FILE.to_string()), e.to_string()),
+        }
+
+        if doctor.analytics_store.is_file() {
+            if let Err(e) = Analytics::new().persist_to(&doctor.analytics_store) {
+                self.error(
+                    DoctorCheck::AnalyticsStore,
+                    Some(relative_to(&doctor.analytics_store, &doctor.skills_dir)),
+                    e.to_string(),
+                );
+            }
+        }
+    }
+
+    /// Rewrite a `_meta.json`, backing up the original first; returns
+    /// whether it was written.
+    fn write_meta(&mut self, path: &Path, relative: &str, json: &str) -> bool {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(BACKUP_SUFFIX);
+        let result = if self.backed_up.insert(path.to_path_buf()) {
+            fs::copy(path, &backup).map(|_| ())
+        } else {
+            Ok(())
+        };
+        match result.and_then(|_| fs::write(path, json)) {
+            Ok(()) => true,
+            Err(e) => {
+                self.error(DoctorCheck::Meta, Some(relative.to_string()), format!("Failed to rewrite: {}", e));
+                false
+            }
+        }
+    }
+
+    fn error(&mut self, check: DoctorCheck, path: Option<String>, message: String) {
+        self.push(check, DoctorSeverity::Error, path, message);
+    }
+
+    fn warning(&mut self, check: DoctorCheck, path: Option<String>, message: String) {
+        self.push(check, DoctorSeverity::Warning, path, message);
+    }
+
+    fn push(&mut self, check: DoctorCheck, severity: DoctorSeverity, path: Option<String>, message: String) {
+        self.findings.push(DoctorFinding {
+            check,
+            severity,
+            path,
+            message,
+            fixable: false,
+            fixed: false,
+        });
+    }
+}
+
+/// `path` relative to `dir` with `/` separators, or in full outside it.
+fn relative_to(path: &Path, dir: &Path) -> String {
+    path.strip_prefix(dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// A malformed `_meta.json` rewritten as valid metadata, or None when it
+/// can't be recovered.
+pub fn repair_meta(text: &str) -> Option<String> {
+    let cleaned = strip_json_extensions(text.trim_start_matches('\u{feff}'));
+    let mut value: Value = serde_json::from_str(&cleaned).ok()?;
+    let object = value.as_object_mut()?;
+
+    if let Some(Value::String(tags)) = object.get("tags") {
+        let tags: Vec<Value> = tags
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(|t| Value::String(t.to_string()))
+            .collect();
+        object.insert("tags".to_string(), Value::Array(tags));
+    }
+
+    serde_json::from_value::<SkillMeta>(value.clone()).ok()?;
+    serde_json::to_string_pretty(&value).ok()
+}
+
+/// Remove `//` and `/* */` comments and trailing commas outside strings.
+fn strip_json_extensions(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            ('}' | ']', _) => {
+                let trimmed = out.trim_end().len();
+                if out[..trimmed].ends_with(',') {
+                    out.truncate(trimmed - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// A `_meta.json` with `(name, file)` sub-skills appended, or None when it
+/// can't be read as an object.
+fn register_sub_skills(path: &Path, sub_skills: &[(String, String)]) -> Option<String> {
+    let mut value: Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let list = value
+        .as_object_mut()?
+        .entry("sub_skills")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if list.is_null() {
+        *list = Value::Array(Vec::new());
+    }
+    let list = list.as_array_mut()?;
+    for (name, file) in sub_skills {
+        let mut entry = Map::new();
+        entry.insert("name".to_string(), Value::String(name.clone()));
+        entry.insert("file".to_string(), Value::String(file.clone()));
+        list.push(Value::Object(entry));
+    }
+    serde_json::to_string_pretty(&value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_skill;
+    use tempfile::TempDir;
+
+    fn findings(report: &DoctorReport, check: DoctorCheck) -> Vec<&DoctorFinding> {
+        report.findings.iter().filter(|f| f.check == check).collect()
+    }
+
+    #[test]
+    fn test_repair_meta() {
+        let repaired = repair_meta(
+            "\u{feff}{\n  // Forms\n  \"name\": \"forms\", /* short */\n  \"description\": \"a // b\",\n  \"tags\": \"ui, forms\",\n}\n",
+        )
+        .unwrap();
+        let value: Value = serde_json::from_str(&repaired).unwrap();
+        assert_eq!(value["description"], "a // b");
+        assert_eq!(value["tags"], serde_json::json!(["ui", "forms"]));
+
+        assert!(repair_meta("{\"name\": ").is_none());
+        assert!(repair_meta("[1, 2,]").is_none());
+    }
+
+    #[test]
+    fn test_doctor() {
+        let temp = TempDir::new().unwrap();
+        let forms = temp.path().join("forms");
+        write_skill(temp.path(), "forms", r#"{"name": "forms", "description": "Forms", "tags": ["ui"],}"#, "# Forms");
+        fs::write(forms.join("SKILL.de.md"), "# Formulare").unwrap();
+        fs::write(forms.join("Zod Schemas.md"), "# Zod").unwrap();
+        fs::create_dir_all(forms.join("assets")).unwrap();
+        fs::write(forms.join("assets/logo.svg"), "<svg/>").unwrap();
+
+        let broken = temp.path().join("broken");
+        fs::create_dir_all(&broken).unwrap();
+        fs::write(broken.join("_meta.json"), r#"{"name": "#).unwrap();
+        fs::write(temp.path().join(analytics::STORE_FILE), "not json").unwrap();
+
+        let report = Doctor::new(temp.path()).run();
+        assert_eq!(report.skills_checked, 2);
+        assert_eq!(report.errors(), 4);
+        let meta = findings(&report, DoctorCheck::Meta);
+        assert_eq!(meta.len(), 2);
+        assert!(meta.iter().any(|f| f.path.as_deref() == Some("forms/_meta.json") && f.fixable));
+        assert!(meta.iter().any(|f| f.path.as_deref() == Some("broken/_meta.json") && !f.fixable));
+        assert_eq!(findings(&report, DoctorCheck::Structure)[0].path.as_deref(), Some("broken"));
+        assert_eq!(findings(&report, DoctorCheck::AnalyticsStore).len(), 1);
+        // The malformed _meta.json doesn't load, so its orphans aren't known
+        assert!(findings(&report, DoctorCheck::OrphanedFile).is_empty());
+
+        // Repairing the metadata lets the orphan be registered too
+        let report = Doctor::new(temp.path()).with_fix(true).run();
+        assert!(findings(&report, DoctorCheck::Meta).iter().any(|f| f.fixed));
+        assert_eq!(report.errors(), 3);
+        let orphans = findings(&report, DoctorCheck::OrphanedFile);
+        // The backup is left for the user to remove
+        let paths: Vec<_> = orphans.iter().filter_map(|f| f.path.as_deref()).collect();
+        assert_eq!(paths, vec!["forms/Zod Schemas.md", "forms/_meta.json.bak", "forms/assets/logo.svg"]);
+        assert!(orphans[0].fixed);
+        assert!(!orphans[2].fixable);
+
+        let meta: SkillMeta = serde_json::from_str(&fs::read_to_string(forms.join("_meta.json")).unwrap()).unwrap();
+        assert_eq!(meta.find_sub_skill("zod-schemas").unwrap().file, "Zod Schemas.md");
+        let backup = fs::read_to_string(forms.join("_meta.json.bak")).unwrap();
+        assert!(backup.ends_with(",}"));
+
+        let report = Doctor::new(temp.path()).run();
+        assert_eq!(findings(&report, DoctorCheck::OrphanedFile).len(), 2);
+        assert!(findings(&report, DoctorCheck::Meta).iter().all(|f| f.path.as_deref() == Some("broken/_meta.json")));
+    }
+
+    #[test]
+    fn test_doctor_journal() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("forms")).unwrap();
+        fs::write(temp.path().join("forms/_meta.json"), r#"{"name": "forms", "description": "Forms"}"#).unwrap();
+        fs::write(temp.path().join("forms/SKILL.md"), "# Forms").unwrap();
+        journal::Journal::new()
+            .write_to(temp.path().join(JOURNAL_FILE), temp.path())
+            .unwrap();
+
+        let report = Doctor::new(temp.path()).run();
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+
+        fs::write(temp.path().join("forms/SKILL.md"), "# Forms\n\nEdited.").unwrap();
+        let report = Doctor::new(temp.path()).run();
+        let journal = findings(&report, DoctorCheck::Journal);
+        assert_eq!(journal[0].severity, DoctorSeverity::Warning);
+        assert!(journal[0].message.contains("forms/SKILL.md"));
+        assert!(report.is_healthy());
+    }
+}
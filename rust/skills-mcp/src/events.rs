@@ -0,0 +1,136 @@
+//! Central bus of skill change events.
+//!
+//! The indexer publishes an event whenever a skill is created, updated, or
+//! deleted and whenever the whole index is reloaded, whatever caused the
+//! change (API writes, MCP tools, or the file watcher). The HTTP API
+//! streams events at `/api/events`, and the MCP transports use them to
+//! decide when to send resource change notifications.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// What changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SkillEventKind {
+    /// A skill appeared in the index.
+    Created {
+        /// Skill name.
+        name: String,
+    },
+    /// A skill's metadata or content changed.
+    Updated {
+        /// Skill name.
+        name: String,
+    },
+    /// A skill left the index.
+    Deleted {
+        /// Skill name.
+        name: String,
+    },
+    /// The whole index was rebuilt from disk.
+    IndexReloaded {
+        /// Skills in the new index.
+        skills: usize,
+    },
+    /// A watcher finished applying a batch of file changes, after the
+    /// events for each skill in it.
+    IndexUpdated {
+        /// Skills reindexed.
+        skills: Vec<String>,
+    },
+}
+
+impl SkillEventKind {
+    /// Event name, as used for the SSE `event:` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Created { .. } => "created",
+            Self::Updated { .. } => "updated",
+            Self::Deleted { .. } => "deleted",
+            Self::IndexReloaded { .. } => "index_reloaded",
+            Self::IndexUpdated { .. } => "index_updated",
+        }
+    }
+}
+
+/// A change to the skills library.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillEvent {
+    /// What changed.
+    #[serde(flatten)]
+    pub kind: SkillEventKind,
+
+    /// When the change was indexed.
+    #[serde(with = "crate::models::timestamp")]
+    pub at: DateTime<Utc>,
+}
+
+/// Broadcast channel for skill events.
+///
+/// Publishing never blocks; subscribers that fall more than
+/// [`EventBus::CAPACITY`] events behind miss the oldest ones.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<SkillEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    /// Events buffered per subscriber.
+    pub const CAPACITY: usize = 256;
+
+    /// Create a bus with no subscribers.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(Self::CAPACITY);
+        Self { sender }
+    }
+
+    /// Publish an event to all current subscribers.
+    pub fn publish(&self, kind: SkillEventKind) {
+        // No subscribers is not an error
+        let _ = self.sender.send(SkillEvent {
+            kind,
+            at: Utc::now(),
+        });
+    }
+
+    /// Receive events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<SkillEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Number of current subscribers.
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_and_subscribe() {
+        let bus = EventBus::new();
+        bus.publish(SkillEventKind::IndexReloaded { skills: 0 });
+
+        let mut events = bus.subscribe();
+        bus.publish(SkillEventKind::Created {
+            name: "forms".to_string(),
+        });
+
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.kind.name(), "created");
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "created");
+        assert_eq!(json["name"], "forms");
+        assert!(events.try_recv().is_err());
+    }
+}
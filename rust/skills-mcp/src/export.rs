@@ -0,0 +1,99 @@
+//! Skill export as ZIP archives.
+//!
+//! Mirrors the `skills_export` tool in `skills-mcp-server/src/tools/crud.ts`.
+
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::Path;
+
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Export a skill directory as an in-memory ZIP archive.
+///
+/// Entries are stored under a `<name>/` prefix so the archive extracts
+/// into its own directory. When `include_scripts` is false, anything under
+/// a `scripts/` directory is skipped.
+pub fn export_skill(skills_dir: &Path, name: &str, include_scripts: bool) -> Result<Vec<u8>, ExportError> {
+    let skill_dir = skills_dir.join(name);
+    if !skill_dir.is_dir() {
+        return Err(ExportError::NotFound(name.to_string()));
+    }
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for entry in WalkDir::new(&skill_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let relative = path.strip_prefix(&skill_dir).unwrap_or(path);
+        if !include_scripts && relative.starts_with("scripts") {
+            continue;
+        }
+
+        let archive_path = format!(
+            "{}/{}",
+            name,
+            relative.to_string_lossy().replace('\\', "/")
+        );
+
+        zip.start_file(archive_path, options)?;
+        zip.write_all(&fs::read(path)?)?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Errors that can occur while exporting a skill.
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    /// The skill directory does not exist.
+    #[error("Skill '{0}' not found")]
+    NotFound(String),
+
+    /// Failed to read skill files.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to write the archive.
+    #[error("Archive error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_export_skill_excludes_scripts() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("forms");
+        fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Forms").unwrap();
+        fs::write(skill_dir.join("scripts/run.sh"), "echo hi").unwrap();
+
+        let bytes = export_skill(temp_dir.path(), "forms", false).unwrap();
+        let archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let names: Vec<_> = archive.file_names().collect();
+
+        assert_eq!(names, vec!["forms/SKILL.md"]);
+    }
+
+    #[test]
+    fn test_export_missing_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = export_skill(temp_dir.path(), "missing", true);
+
+        assert!(matches!(result, Err(ExportError::NotFound(_))));
+    }
+}
@@ -0,0 +1,103 @@
+//! Fetching http and https URLs.
+//!
+//! Requests go through the `curl` executable, since the server has no TLS
+//! stack of its own. Only http and https are followed, including across
+//! redirects, and URLs are passed literally rather than as curl globs. The
+//! body is read as curl streams it, so a response over budget is cut off
+//! rather than held in memory.
+
+use std::io::{self, Read};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Fetch a URL, failing once the body passes `max_bytes` or the request
+/// takes longer than `timeout`.
+///
+/// `--max-filesize` only stops responses that announce their size, so the
+/// budget is also enforced on the body as it's read.
+pub fn fetch(url: &str, max_bytes: u64, timeout: Duration) -> Result<Vec<u8>, FetchError> {
+    let mut child = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", "--globoff"])
+        .args(["--proto", "=http,https", "--proto-redir", "=http,https"])
+        .args(["--max-time", &timeout.as_secs().max(1).to_string()])
+        .args(["--max-filesize", &max_bytes.to_string()])
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => FetchError::MissingCurl,
+            _ => FetchError::Io(e),
+        })?;
+
+    let mut body = Vec::new();
+    let stdout = child.stdout.take().expect("curl's stdout is piped");
+    let read = stdout.take(max_bytes.saturating_add(1)).read_to_end(&mut body);
+    if read.is_err() || body.len() as u64 > max_bytes {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(match read {
+            Err(e) => FetchError::Io(e),
+            Ok(_) => FetchError::TooLarge(format!("{}: response is larger than {} bytes", url, max_bytes)),
+        });
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(FetchError::Failed(format!("{}: {}", url, stderr.trim())));
+    }
+    Ok(body)
+}
+
+/// Errors that can occur while fetching a URL.
+#[derive(Debug, thiserror::Error)]
+pub enum FetchError {
+    /// The `curl` executable isn't installed.
+    #[error("curl is needed to fetch URLs")]
+    MissingCurl,
+
+    /// The response passed the size budget.
+    #[error("{0}")]
+    TooLarge(String),
+
+    /// The request failed, as curl reported it.
+    #[error("{0}")]
+    Failed(String),
+
+    /// Running curl failed.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_fetch_size_limit() {
+        // Sent without a Content-Length, so only reading can enforce the limit
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/archive.zip", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let _ = stream.read(&mut [0; 4096]);
+                let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n");
+                let _ = stream.write_all(&[b'x'; 8192]);
+            }
+        });
+
+        let timeout = Duration::from_secs(10);
+        assert_eq!(fetch(&url, 8192, timeout).unwrap().len(), 8192);
+        let error = fetch(&url, 1024, timeout).unwrap_err();
+        assert!(matches!(error, FetchError::TooLarge(_)), "{}", error);
+    }
+
+    #[test]
+    fn test_fetch_rejects_other_protocols() {
+        assert!(matches!(fetch("file:///etc/passwd", 1024, Duration::from_secs(10)), Err(FetchError::Failed(_))));
+    }
+}
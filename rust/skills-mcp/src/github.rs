@@ -0,0 +1,491 @@
+//! Importing skills from GitHub repositories.
+//!
+//! A repository URL may name a branch (or tag, or commit) and a
+//! subdirectory, as copied from the browser:
+//! `https://github.com/owner/repo/tree/main/skills`. The archive of that
+//! ref is downloaded, and every skill below the subdirectory is imported,
+//! whether it has a `_meta.json` or only SKILL.md frontmatter. Each skill
+//! records the URL of its own directory in `source`, so importing that URL
+//! again in merge mode picks up upstream changes.
+//!
+//! Downloads go through [`crate::fetch`], so an archive over
+//! [`MAX_ARCHIVE_BYTES`] is cut off rather than held in memory.
+
+use std::fs::{self, File};
+use std::io::{self, Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+use crate::fetch::{self, FetchError};
+use crate::import::{self, ImportError, ImportMode, ImportReport};
+
+/// Largest archive downloaded.
+pub const MAX_ARCHIVE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Most bytes extracted from an archive.
+const MAX_EXTRACTED_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Seconds a download may take.
+const DOWNLOAD_TIMEOUT_SECS: u64 = 120;
+
+/// Directory levels below the subdirectory searched for skills.
+const MAX_SKILL_DEPTH: usize = 4;
+
+/// Longest ref accepted, as git allows for branch names.
+const MAX_REF_LEN: usize = 255;
+
+/// A GitHub repository, ref, and subdirectory to import from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubSource {
+    /// Repository owner.
+    pub owner: String,
+
+    /// Repository name.
+    pub repo: String,
+
+    /// Branch, tag, or commit; the default branch when None.
+    pub reference: Option<String>,
+
+    /// Subdirectory holding the skills; the whole repository when None.
+    pub path: Option<String>,
+}
+
+impl std::str::FromStr for GitHubSource {
+    type Err = ImportError;
+
+    /// Parse `https://github.com/<owner>/<repo>[/tree/<ref>[/<path>]]`.
+    ///
+    /// The ref is the segment after `tree`, so refs containing `/` can't be
+    /// named this way.
+    fn from_str(url: &str) -> Result<Self, Self::Err> {
+        let invalid = || ImportError::Invalid(format!("Not a GitHub repository URL: {}", url));
+        let rest = url
+            .trim()
+            .strip_prefix("https://")
+            .or_else(|| url.trim().strip_prefix("http://"))
+            .ok_or_else(invalid)?;
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let rest = rest
+            .strip_prefix("github.com/")
+            .or_else(|| rest.strip_prefix("www.github.com/"))
+            .ok_or_else(invalid)?;
+
+        let mut segments = rest.split('/').filter(|s| !s.is_empty());
+        let owner = segments.next().ok_or_else(invalid)?;
+        let repo = segments.next().ok_or_else(invalid)?;
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        let reference = match segments.next() {
+            None => None,
+            Some("tree") => Some(segments.next().ok_or_else(invalid)?),
+            Some(_) => return Err(invalid()),
+        };
+        let path: Vec<&str> = segments.collect();
+
+        let plain = |s: &str| {
+            !s.starts_with('.') && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        // Refs go into the archive URL, so only plain names pass
+        let plain_ref = |r: &str| plain(r) && !r.starts_with('-') && !r.contains("..") && r.len() <= MAX_REF_LEN;
+        if !plain(owner) || !plain(repo) || reference.is_some_and(|r| !plain_ref(r)) {
+            return Err(invalid());
+        }
+        if path.iter().any(|s| *s == "." || *s == "..") {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            reference: reference.map(str::to_string),
+            path: (!path.is_empty()).then(|| path.join("/")),
+        })
+    }
+}
+
+impl GitHubSource {
+    /// URL of the ZIP archive of the ref.
+    pub fn archive_url(&self) -> String {
+        format!(
+            "https://codeload.github.com/{}/{}/zip/{}",
+            self.owner,
+            self.repo,
+            self.reference.as_deref().unwrap_or("HEAD")
+        )
+    }
+
+    /// Browser URL of a directory in the repository, relative to its root.
+    pub fn tree_url(&self, path: &str) -> String {
+        let url = format!(
+            "https://github.com/{}/{}/tree/{}",
+            self.owner,
+            self.repo,
+            self.reference.as_deref().unwrap_or("HEAD")
+        );
+        if path.is_empty() {
+            url
+        } else {
+            format!("{}/{}", url, path)
+        }
+    }
+}
+
+/// A skill directory of the archive that wasn't imported.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedSkill {
+    /// Directory, relative to the repository root.
+    pub path: String,
+
+    /// Why it was skipped.
+    pub reason: String,
+}
+
+/// Outcome of importing from a repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlImportReport {
+    /// Archive downloaded.
+    pub archive_url: String,
+
+    /// Skills imported, with the files written for each.
+    pub skills: Vec<ImportReport>,
+
+    /// Skills found but not imported.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedSkill>,
+}
+
+/// Download the archive of a repository and import the skills in it.
+pub fn import_from_github(
+    skills_dir: &Path,
+    source: &GitHubSource,
+    mode: ImportMode,
+) -> Result<UrlImportReport, ImportError> {
+    let archive = download(&source.archive_url())?;
+    import_archive(skills_dir, &archive, source, mode)
+}
+
+/// Fetch a URL, up to [`MAX_ARCHIVE_BYTES`].
+pub fn download(url: &str) -> Result<Vec<u8>, ImportError> {
+    fetch::fetch(url, MAX_ARCHIVE_BYTES, Duration::from_secs(DOWNLOAD_TIMEOUT_SECS)).map_err(|e| match e {
+        FetchError::MissingCurl => ImportError::Download("curl is needed to download archives".to_string()),
+        FetchError::Io(e) => ImportError::Io(e),
+        e => ImportError::Download(e.to_string()),
+    })
+}
+
+/// Import the skills in a GitHub ZIP archive of `source`.
+///
+/// This is [`stage_archive`] followed by [`StagedArchive::install`] of
+/// every skill.
+pub fn import_archive(
+    skills_dir: &Path,
+    archive: &[u8],
+    source: &GitHubSource,
+    mode: ImportMode,
+) -> Result<UrlImportReport, ImportError> {
+    stage_archive(archive, source)?.install(skills_dir, mode, |_| Ok(()))
+}
+
+/// Skills extracted from an archive into a scratch directory, not yet
+/// imported.
+pub struct StagedArchive {
+    staging: Staging,
+    source: GitHubSource,
+    skill_dirs: Vec<PathBuf>,
+}
+
+/// Extract the skills in a GitHub ZIP archive of `source`, leaving the
+/// skills directory alone.
+///
+/// Archive entries sit below one top-level directory (`repo-main/`).
+/// Only entries below `source.path` are read. If that directory is itself
+/// a skill, it is the only one staged; otherwise every skill below it is.
+pub fn stage_archive(archive: &[u8], source: &GitHubSource) -> Result<StagedArchive, ImportError> {
+    let staging = Staging::create()?;
+    extract(archive, source.path.as_deref(), &staging.0)?;
+
+    let skill_dirs = find_skill_dirs(&staging.0);
+    if skill_dirs.is_empty() {
+        return Err(ImportError::NotFound(format!(
+            "No skills in {}",
+            source.tree_url(source.path.as_deref().unwrap_or_default())
+        )));
+    }
+    Ok(StagedArchive {
+        staging,
+        source: source.clone(),
+        skill_dirs,
+    })
+}
+
+impl StagedArchive {
+    /// Import the staged skills into `skills_dir`.
+    ///
+    /// `check` is given each skill's name before it is imported, and may
+    /// refuse it. A skill that is refused or fails to import is skipped and
+    /// reported, unless none imports, in which case the first failure is
+    /// returned.
+    pub fn install(
+        &self,
+        skills_dir: &Path,
+        mode: ImportMode,
+        check: impl Fn(&str) -> Result<(), ImportError>,
+    ) -> Result<UrlImportReport, ImportError> {
+        let source = &self.source;
+        let mut skills = Vec::new();
+        let mut skipped = Vec::new();
+        let mut first_error = None;
+        for dir in &self.skill_dirs {
+            let relative = dir.strip_prefix(&self.staging.0).unwrap_or(dir).to_string_lossy().replace('\\', "/");
+            let path = match (source.path.as_deref(), relative.as_str()) {
+                (Some(base), "") => base.to_string(),
+                (Some(base), rest) => format!("{}/{}", base, rest),
+                (None, rest) => rest.to_string(),
+            };
+            let result = import::skill_name(dir)
+                .and_then(|name| check(&name))
+                .and_then(|()| import::import_skill_dir(skills_dir, dir, mode, &source.tree_url(&path)));
+            match result {
+                Ok(report) => skills.push(report),
+                Err(e) => {
+                    skipped.push(SkippedSkill {
+                        path,
+                        reason: e.to_string(),
+                    });
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if skills.is_empty() => Err(e),
+            _ => Ok(UrlImportReport {
+                archive_url: source.archive_url(),
+                skills,
+                skipped,
+            }),
+        }
+    }
+}
+
+/// Write the archive entries below `path` into `target`, without the
+/// archive's top-level directory and `path` itself.
+fn extract(archive: &[u8], path: Option<&str>, target: &Path) -> Result<(), ImportError> {
+    let invalid = |e: zip::result::ZipError| ImportError::Invalid(format!("archive: {}", e));
+    let mut archive = ZipArchive::new(Cursor::new(archive)).map_err(invalid)?;
+    let prefix: PathBuf = path.map(PathBuf::from).unwrap_or_default();
+    let mut remaining = MAX_EXTRACTED_BYTES;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(invalid)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        // Drop the top-level directory, then keep what is below the prefix
+        let relative: PathBuf = name.components().skip(1).collect();
+        let Ok(relative) = relative.strip_prefix(&prefix) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+            continue;
+        }
+
+        let too_large = || ImportError::Invalid(format!("archive extracts to more than {} bytes", MAX_EXTRACTED_BYTES));
+        if entry.size() > remaining {
+            return Err(too_large());
+        }
+        let file = target.join(relative);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // The declared size may be wrong, so a byte past the limit is read
+        // to tell a full budget from an oversized entry
+        let written = io::copy(&mut (&mut entry).take(remaining + 1), &mut File::create(&file)?)?;
+        if written > remaining {
+            return Err(too_large());
+        }
+        remaining -= written;
+    }
+    Ok(())
+}
+
+/// Skill directories at or below `root`, in name order. Directories of a
+/// skill aren't searched further.
+fn find_skill_dirs(root: &Path) -> Vec<PathBuf> {
+    let is_skill = |dir: &Path| dir.join("_meta.json").is_file() || dir.join("SKILL.md").is_file();
+    if is_skill(root) {
+        return vec![root.to_path_buf()];
+    }
+
+    let mut dirs = Vec::new();
+    let mut entries = WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(MAX_SKILL_DEPTH)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.file_type().is_dir() && !name.starts_with('.') && !name.starts_with('_')
+        });
+    while let Some(entry) = entries.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if is_skill(entry.path()) {
+            dirs.push(entry.into_path());
+            entries.skip_current_dir();
+        }
+    }
+    dirs
+}
+
+/// A scratch directory, removed when dropped.
+struct Staging(PathBuf);
+
+impl Staging {
+    fn create() -> io::Result<Self> {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos());
+        let dir = std::env::temp_dir().join(format!("skills-mcp-import-{}-{}", std::process::id(), nanos));
+        fs::create_dir_all(&dir)?;
+        Ok(Self(dir))
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SkillMeta;
+    use std::io::Write;
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    fn read_meta(dir: &Path) -> SkillMeta {
+        serde_json::from_str(&fs::read_to_string(dir.join("_meta.json")).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_url() {
+        let source: GitHubSource = "https://github.com/acme/skills".parse().unwrap();
+        assert_eq!(source.reference, None);
+        assert_eq!(source.archive_url(), "https://codeload.github.com/acme/skills/zip/HEAD");
+
+        let source: GitHubSource = "https://github.com/acme/skills.git/tree/v2/library/pdf/?tab=readme"
+            .parse()
+            .unwrap();
+        assert_eq!(source.repo, "skills");
+        assert_eq!(source.reference.as_deref(), Some("v2"));
+        assert_eq!(source.path.as_deref(), Some("library/pdf"));
+        assert_eq!(source.tree_url("library/pdf"), "https://github.com/acme/skills/tree/v2/library/pdf");
+
+        for url in [
+            "https://gitlab.com/acme/skills",
+            "https://github.com/acme",
+            "https://github.com/acme/skills/blob/main/SKILL.md",
+            "https://github.com/acme/skills/tree/main/../secrets",
+            "https://github.com/acme/skills/tree/..main",
+            "https://github.com/acme/skills/tree/-main",
+            "https://github.com/acme/skills/tree/{main,dev}",
+            "https://github.com/acme/skills/tree/ma[in]",
+            "https://github.com/acme/skills/tree/main%2F..",
+            "github.com/acme/skills",
+        ] {
+            assert!(url.parse::<GitHubSource>().is_err(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_import_archive() {
+        let skills_dir = TempDir::new().unwrap();
+        let zip = archive(&[
+            ("skills-main/README.md", "# Skills\n"),
+            ("skills-main/library/pdf/SKILL.md", "---\nname: pdf\ndescription: PDF tools\n---\n# PDF\n"),
+            ("skills-main/library/pdf/FORMS.md", "# Forms\n"),
+            ("skills-main/library/forms/_meta.json", r#"{"name": "forms", "description": "Forms"}"#),
+            ("skills-main/library/forms/SKILL.md", "# Forms\n"),
+            ("skills-main/library/forms/references/guide.md", "# Guide\n"),
+            ("skills-main/other/tables/_meta.json", r#"{"name": "tables", "description": "Tables"}"#),
+        ]);
+        let source: GitHubSource = "https://github.com/acme/skills/tree/main/library".parse().unwrap();
+
+        let report = import_archive(skills_dir.path(), &zip, &source, ImportMode::Create).unwrap();
+        let names: Vec<&str> = report.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["forms", "pdf"]);
+        assert!(report.skipped.is_empty());
+        assert!(!skills_dir.path().join("tables").exists());
+
+        let forms = read_meta(&skills_dir.path().join("forms"));
+        assert_eq!(
+            forms.source.as_deref(),
+            Some("https://github.com/acme/skills/tree/main/library/forms")
+        );
+        assert!(skills_dir.path().join("forms/references/guide.md").is_file());
+        let pdf = read_meta(&skills_dir.path().join("pdf"));
+        assert_eq!(pdf.source.as_deref(), Some("https://github.com/acme/skills/tree/main/library/pdf"));
+        assert_eq!(pdf.find_sub_skill("forms").unwrap().file, "FORMS.md");
+
+        // Importing again fails unless merging
+        let result = import_archive(skills_dir.path(), &zip, &source, ImportMode::Create);
+        assert!(matches!(result, Err(ImportError::AlreadyExists(_))));
+        let report = import_archive(skills_dir.path(), &zip, &source, ImportMode::Merge).unwrap();
+        assert!(report.skills.iter().all(|s| s.merge.as_ref().is_some_and(|m| m.conflicts.is_empty())));
+
+        // A URL naming one skill imports only that one
+        let skills_dir = TempDir::new().unwrap();
+        let source: GitHubSource = "https://github.com/acme/skills/tree/main/other/tables".parse().unwrap();
+        let report = import_archive(skills_dir.path(), &zip, &source, ImportMode::Create).unwrap();
+        assert_eq!(report.skills[0].name, "tables");
+
+        let source: GitHubSource = "https://github.com/acme/skills/tree/main/docs".parse().unwrap();
+        let result = import_archive(skills_dir.path(), &zip, &source, ImportMode::Create);
+        assert!(matches!(result, Err(ImportError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_install_check() {
+        let skills_dir = TempDir::new().unwrap();
+        let zip = archive(&[
+            ("skills-main/pdf/SKILL.md", "---\nname: pdf\ndescription: PDF tools\n---\n# PDF\n"),
+            ("skills-main/forms/_meta.json", r#"{"name": "forms", "description": "Forms"}"#),
+            ("skills-main/forms/SKILL.md", "# Forms\n"),
+        ]);
+        let source: GitHubSource = "https://github.com/acme/skills".parse().unwrap();
+
+        // Staging leaves the skills directory alone
+        let staged = stage_archive(&zip, &source).unwrap();
+        assert_eq!(fs::read_dir(skills_dir.path()).unwrap().count(), 0);
+
+        let report = staged
+            .install(skills_dir.path(), ImportMode::Create, |name| match name {
+                "forms" => Err(ImportError::NotFound("Skill 'forms' not found".to_string())),
+                _ => Ok(()),
+            })
+            .unwrap();
+        let names: Vec<&str> = report.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["pdf"]);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].path, "forms");
+        assert!(!skills_dir.path().join("forms").exists());
+    }
+}
@@ -0,0 +1,449 @@
+//! Importers for skills authored in other directory layouts.
+//!
+//! The Claude skill layout keeps metadata in SKILL.md frontmatter, bundles
+//! supporting docs as extra top-level markdown files, and stores reference
+//! material under `resources/`. Importing writes a `_meta.json`, registers
+//! the extra markdown files as sub-skills, and moves `resources/` to
+//! `references/` so the indexer picks it up.
+//!
+//! Every import is recorded as the base of a three-way merge (see
+//! [`merge`](crate::merge)), so a skill that already exists can be imported
+//! again in [`ImportMode::Merge`] without losing local edits.
+//!
+//! Folders on the server are only imported from below the `[import]`
+//! root, so API callers can't read arbitrary directories.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use crate::cold::ColdStorage;
+use crate::markdown::{self, rewrite_links};
+use crate::merge::{self, Imports, MergeError, MergeReport, SkillFiles};
+use crate::models::{ErrorCode, SkillMeta, SubSkillMeta};
+use crate::validation::validate_meta;
+
+/// Supported import formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Claude skill directory: SKILL.md with frontmatter, `scripts/`,
+    /// `resources/`.
+    Claude,
+}
+
+impl std::str::FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "claude" => Ok(Self::Claude),
+            other => Err(format!("Unknown import format '{}' (expected claude)", other)),
+        }
+    }
+}
+
+/// What to do when the imported skill already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Fail with [`ImportError::AlreadyExists`].
+    #[default]
+    Create,
+    /// Merge the incoming files into the existing skill.
+    Merge,
+}
+
+/// Import settings from the `[import]` section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ImportConfig {
+    /// Directory that folders imported on the server must be inside,
+    /// relative to the skills directory unless absolute. Folder imports are
+    /// refused when unset.
+    pub root: Option<PathBuf>,
+}
+
+impl ImportConfig {
+    /// The canonical path of `folder` if it is inside the import root.
+    /// None when no root is set, or either doesn't exist.
+    pub fn resolve(&self, skills_dir: &Path, folder: &Path) -> Option<PathBuf> {
+        let root = skills_dir.join(self.root.as_ref()?).canonicalize().ok()?;
+        let folder = folder.canonicalize().ok()?;
+        folder.starts_with(&root).then_some(folder)
+    }
+}
+
+/// Outcome of importing a skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    /// Name of the imported skill.
+    pub name: String,
+
+    /// Files written, relative to the skill directory.
+    pub files: Vec<String>,
+
+    /// Sub-skills registered from extra top-level markdown files.
+    pub sub_skills: Vec<String>,
+
+    /// Links retargeted from `resources/` to `references/`.
+    pub links_rewritten: usize,
+
+    /// How the import merged into an existing skill, in merge mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge: Option<MergeReport>,
+}
+
+/// Import a Claude-format skill directory into the skills directory.
+///
+/// `name` overrides the frontmatter name. The frontmatter must provide a
+/// description. A skill that already exists is an error unless `mode`
+/// merges into it.
+pub fn import_claude_skill(
+    skills_dir: &Path,
+    source: &Path,
+    name: Option<&str>,
+    mode: ImportMode,
+) -> Result<ImportReport, ImportError> {
+    import_claude_dir(skills_dir, source, name, mode, "import:claude")
+}
+
+/// Import a skill directory in either layout, recording `origin` as the
+/// skill's `source`.
+///
+/// A directory with a `_meta.json` is copied as it is; one with only
+/// SKILL.md frontmatter is converted like [`import_claude_skill`] does.
+pub fn import_skill_dir(
+    skills_dir: &Path,
+    source: &Path,
+    mode: ImportMode,
+    origin: &str,
+) -> Result<ImportReport, ImportError> {
+    if !source.join("_meta.json").is_file() {
+        return import_claude_dir(skills_dir, source, None, mode, origin);
+    }
+
+    let mut meta: SkillMeta = serde_json::from_str(&fs::read_to_string(source.join("_meta.json"))?)
+        .map_err(|e| ImportError::Invalid(format!("_meta.json: {}", e)))?;
+    meta.source = Some(origin.to_string());
+    validate_meta(&meta).map_err(|errors| ImportError::Invalid(errors.join("; ")))?;
+    check_target(skills_dir, &meta.name, mode)?;
+
+    let mut files = SkillFiles::new();
+    for (relative, path) in source_files(source)? {
+        files.insert(relative, fs::read(path)?);
+    }
+    let meta_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| ImportError::Invalid(format!("Failed to serialize meta: {}", e)))?;
+    files.insert("_meta.json".to_string(), meta_json.into_bytes());
+
+    let (files, merge) = store(skills_dir, &meta.name, files)?;
+    Ok(ImportReport {
+        name: meta.name,
+        files,
+        sub_skills: Vec::new(),
+        links_rewritten: 0,
+        merge,
+    })
+}
+
+/// Name a skill directory in either layout is imported as, without
+/// importing it.
+pub fn skill_name(source: &Path) -> Result<String, ImportError> {
+    if source.join("_meta.json").is_file() {
+        let meta: SkillMeta = serde_json::from_str(&fs::read_to_string(source.join("_meta.json"))?)
+            .map_err(|e| ImportError::Invalid(format!("_meta.json: {}", e)))?;
+        return Ok(meta.name);
+    }
+    let content = fs::read_to_string(source.join("SKILL.md"))
+        .map_err(|_| ImportError::NotFound(format!("No SKILL.md in {}", source.display())))?;
+    markdown::parse_frontmatter(&content)
+        .ok_or_else(|| ImportError::Invalid("SKILL.md has no frontmatter".to_string()))?
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| ImportError::Invalid("frontmatter has no name".to_string()))
+}
+
+fn import_claude_dir(
+    skills_dir: &Path,
+    source: &Path,
+    name: Option<&str>,
+    mode: ImportMode,
+    origin: &str,
+) -> Result<ImportReport, ImportError> {
+    let skill_md = source.join("SKILL.md");
+    if !skill_md.is_file() {
+        return Err(ImportError::NotFound(format!(
+            "No SKILL.md in {}",
+            source.display()
+        )));
+    }
+
+    let frontmatter = markdown::parse_frontmatter(&fs::read_to_string(&skill_md)?)
+        .ok_or_else(|| ImportError::Invalid("SKILL.md has no frontmatter".to_string()))?;
+    let field = |key: &str| frontmatter.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let name = name
+        .map(str::to_string)
+        .or_else(|| field("name"))
+        .ok_or_else(|| ImportError::Invalid("frontmatter has no name".to_string()))?;
+    let tags = frontmatter
+        .get("tags")
+        .and_then(Value::as_array)
+        .map(|tags| tags.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default();
+
+    let mut meta = SkillMeta {
+        name: name.clone(),
+        description: field("description").unwrap_or_default(),
+        tags,
+        source: Some(origin.to_string()),
+        ..Default::default()
+    };
+    validate_meta(&meta).map_err(|errors| ImportError::Invalid(errors.join("; ")))?;
+    check_target(skills_dir, &name, mode)?;
+
+    let mut files = SkillFiles::new();
+    let mut sub_skills = Vec::new();
+    let mut links_rewritten = 0;
+
+    for (relative, path) in source_files(source)? {
+        let target = match relative.strip_prefix("resources/") {
+            Some(rest) => format!("references/{}", rest),
+            None => relative.clone(),
+        };
+
+        let is_markdown = target.ends_with(".md") || target.ends_with(".markdown");
+        if is_markdown {
+            let content = fs::read_to_string(&path)?;
+            let (rewritten, count) = rewrite_links(&content, retarget_resource_link);
+            files.insert(target.clone(), rewritten.into_bytes());
+            links_rewritten += count;
+
+            // Extra top-level markdown files become sub-skills
+            if !target.contains('/') && target != "SKILL.md" {
+                let sub_name = sub_skill_name(&target);
+                sub_skills.push(sub_name.clone());
+                meta.sub_skills.get_or_insert_with(Vec::new).push(SubSkillMeta {
+                    name: sub_name,
+                    file: target.clone(),
+                    triggers: vec![],
+                    language: None,
+                });
+            }
+        } else {
+            files.insert(target, fs::read(&path)?);
+        }
+    }
+
+    let meta_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| ImportError::Invalid(format!("Failed to serialize meta: {}", e)))?;
+    files.insert("_meta.json".to_string(), meta_json.into_bytes());
+
+    let (written, merge) = store(skills_dir, &name, files)?;
+    Ok(ImportReport {
+        name,
+        files: written,
+        sub_skills,
+        links_rewritten,
+        merge,
+    })
+}
+
+/// Fail if `name` is taken and the import can't merge into it.
+fn check_target(skills_dir: &Path, name: &str, mode: ImportMode) -> Result<(), ImportError> {
+    if ColdStorage::new(skills_dir).contains(name) || (skills_dir.join(name).exists() && mode == ImportMode::Create) {
+        return Err(ImportError::AlreadyExists(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Files of a source directory, skipping hidden ones, as (path relative
+/// to the directory, full path) pairs in name order.
+fn source_files(source: &Path) -> Result<Vec<(String, PathBuf)>, ImportError> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(source)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+    {
+        let entry = entry.map_err(|e| ImportError::Io(e.into()))?;
+        if entry.file_type().is_file() {
+            let relative = entry
+                .path()
+                .strip_prefix(source)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            files.push((relative, entry.into_path()));
+        }
+    }
+    Ok(files)
+}
+
+/// Write a new skill's files, or merge them into the existing skill,
+/// returning the files written and the merge outcome.
+fn store(
+    skills_dir: &Path,
+    name: &str,
+    files: SkillFiles,
+) -> Result<(Vec<String>, Option<MergeReport>), ImportError> {
+    let imports = Imports::new(skills_dir);
+    let skill_dir = skills_dir.join(name);
+    if skill_dir.exists() {
+        let report = imports.merge(name, &files)?;
+        let mut written: Vec<String> = report.added.iter().chain(&report.updated).cloned().collect();
+        written.sort();
+        Ok((written, Some(report)))
+    } else {
+        merge::write_files(&skill_dir, &files)?;
+        imports.record(name, &files)?;
+        Ok((files.into_keys().collect(), None))
+    }
+}
+
+/// Point links into `resources/` at `references/`.
+fn retarget_resource_link(target: &str) -> Option<String> {
+    let path = target.strip_prefix("./").unwrap_or(target);
+    path.strip_prefix("resources/")
+        .map(|rest| format!("references/{}", rest))
+}
+
+/// Derive a sub-skill name from a markdown file name (`FORMS.md` → `forms`).
+fn sub_skill_name(file: &str) -> String {
+    let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem);
+    stem.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Errors that can occur while importing a skill.
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    /// The source directory is not a skill in the expected format.
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// A skill with this name already exists.
+    #[error("Skill '{0}' already exists")]
+    AlreadyExists(String),
+
+    /// The source metadata is missing or invalid.
+    #[error("Invalid skill: {0}")]
+    Invalid(String),
+
+    /// Downloading the source failed.
+    #[error("Download failed: {0}")]
+    Download(String),
+
+    /// Filesystem error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl From<MergeError> for ImportError {
+    fn from(e: MergeError) -> Self {
+        match e {
+            MergeError::Io(e) => ImportError::Io(e),
+            other => ImportError::Invalid(other.to_string()),
+        }
+    }
+}
+
+impl ImportError {
+    /// Machine-readable code of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ImportError::NotFound(_) => ErrorCode::NotFound,
+            ImportError::AlreadyExists(_) => ErrorCode::AlreadyExists,
+            ImportError::Invalid(_) => ErrorCode::InvalidRequest,
+            ImportError::Download(_) => ErrorCode::InternalError,
+            ImportError::Io(_) => ErrorCode::IoError,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_claude_skill(dir: &Path) {
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::create_dir_all(dir.join("resources")).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: pdf\ndescription: PDF tools\nlicense: MIT\n---\n# PDF\n\nSee [forms](FORMS.md) and [spec](resources/spec.md).\n",
+        )
+        .unwrap();
+        fs::write(dir.join("FORMS.md"), "# Filling forms\n").unwrap();
+        fs::write(dir.join("resources/spec.md"), "# Spec\n").unwrap();
+        fs::write(dir.join("scripts/fill.py"), "print('fill')\n").unwrap();
+    }
+
+    #[test]
+    fn test_import_claude_skill() {
+        let source = TempDir::new().unwrap();
+        let skills_dir = TempDir::new().unwrap();
+        create_claude_skill(source.path());
+
+        let report = import_claude_skill(skills_dir.path(), source.path(), None, ImportMode::Create).unwrap();
+        assert_eq!(report.name, "pdf");
+        assert_eq!(report.sub_skills, vec!["forms"]);
+        assert_eq!(report.links_rewritten, 1);
+        assert_eq!(
+            report.files,
+            vec!["FORMS.md", "SKILL.md", "_meta.json", "references/spec.md", "scripts/fill.py"]
+        );
+
+        let meta: SkillMeta = serde_json::from_str(
+            &fs::read_to_string(skills_dir.path().join("pdf/_meta.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(meta.description, "PDF tools");
+        assert_eq!(meta.source.as_deref(), Some("import:claude"));
+        assert_eq!(meta.find_sub_skill("forms").unwrap().file, "FORMS.md");
+
+        let content = fs::read_to_string(skills_dir.path().join("pdf/SKILL.md")).unwrap();
+        assert!(content.contains("[spec](references/spec.md)"));
+
+        let result = import_claude_skill(skills_dir.path(), source.path(), None, ImportMode::Create);
+        assert!(matches!(result, Err(ImportError::AlreadyExists(_))));
+
+        let report = import_claude_skill(skills_dir.path(), source.path(), Some("pdf-copy"), ImportMode::Create).unwrap();
+        assert_eq!(report.name, "pdf-copy");
+    }
+
+    #[test]
+    fn test_import_merge() {
+        let source = TempDir::new().unwrap();
+        let skills_dir = TempDir::new().unwrap();
+        create_claude_skill(source.path());
+        import_claude_skill(skills_dir.path(), source.path(), None, ImportMode::Merge).unwrap();
+
+        // A local edit, then an upstream edit to another file and a new one
+        fs::write(skills_dir.path().join("pdf/FORMS.md"), "# Filling forms, locally\n").unwrap();
+        fs::write(source.path().join("scripts/fill.py"), "print('fill v2')\n").unwrap();
+        fs::write(source.path().join("resources/more.md"), "# More\n").unwrap();
+
+        let report = import_claude_skill(skills_dir.path(), source.path(), None, ImportMode::Merge).unwrap();
+        assert_eq!(report.files, vec!["references/more.md", "scripts/fill.py"]);
+        let merge = report.merge.unwrap();
+        assert_eq!(merge.kept, vec!["FORMS.md"]);
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(
+            fs::read_to_string(skills_dir.path().join("pdf/FORMS.md")).unwrap(),
+            "# Filling forms, locally\n"
+        );
+
+        // Both sides change the same file
+        fs::write(source.path().join("FORMS.md"), "# Filling forms v2\n").unwrap();
+        let report = import_claude_skill(skills_dir.path(), source.path(), None, ImportMode::Merge).unwrap();
+        assert_eq!(report.merge.unwrap().conflicts[0].file, "FORMS.md");
+    }
+}
@@ -0,0 +1,207 @@
+//! In-memory cache of skill file contents.
+//!
+//! SKILL.md and sub-skill files are read far more often than they change,
+//! so reads go through a least-recently-used cache. An entry is only served
+//! while the file's modification time and size match the ones it was read
+//! with; reloads and watcher updates also drop entries, covering edits that
+//! keep both.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use super::store::SkillStore;
+
+/// Hit and miss counts of the content cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ContentCacheStats {
+    /// Files the cache holds at most.
+    pub capacity: usize,
+    /// Files cached now.
+    pub entries: usize,
+    /// Reads served from memory.
+    pub hits: u64,
+    /// Reads that went to disk.
+    pub misses: u64,
+}
+
+/// LRU cache of file contents, keyed by path and checked against the
+/// file's modification time.
+#[derive(Debug)]
+pub struct ContentCache {
+    inner: Mutex<CacheInner>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    capacity: usize,
+    files: HashMap<PathBuf, CachedFile>,
+    /// Incremented on every access, for LRU ordering.
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct CachedFile {
+    modified: Option<SystemTime>,
+    len: u64,
+    content: String,
+    last_used: u64,
+}
+
+impl Default for ContentCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl ContentCache {
+    /// Files cached unless configured otherwise.
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// Create a cache holding up to `capacity` files; 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(CacheInner {
+                capacity,
+                ..Default::default()
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Change how many files are kept, dropping the least recently used
+    /// ones if there are more.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut inner = self.inner.lock();
+        inner.capacity = capacity;
+        inner.evict();
+    }
+
+    /// Read a UTF-8 file from a store, from memory if it hasn't changed
+    /// since it was cached.
+    pub fn read_to_string(&self, store: &dyn SkillStore, path: &Path) -> io::Result<String> {
+        let metadata = store.metadata(path)?;
+        let modified = metadata.modified;
+
+        {
+            let mut inner = self.inner.lock();
+            inner.tick += 1;
+            let tick = inner.tick;
+            if let Some(file) = inner.files.get_mut(path) {
+                if file.modified == modified && file.len == metadata.len {
+                    file.last_used = tick;
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return Ok(file.content.clone());
+                }
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let content = String::from_utf8(store.read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut inner = self.inner.lock();
+        if inner.capacity > 0 {
+            let last_used = inner.tick;
+            inner.files.insert(
+                path.to_path_buf(),
+                CachedFile {
+                    modified,
+                    len: metadata.len,
+                    content: content.clone(),
+                    last_used,
+                },
+            );
+            inner.evict();
+        }
+        Ok(content)
+    }
+
+    /// Drop cached files under `dir`.
+    pub fn invalidate(&self, dir: &Path) {
+        self.inner.lock().files.retain(|path, _| !path.starts_with(dir));
+    }
+
+    /// Drop every cached file. Counters are kept.
+    pub fn clear(&self) {
+        self.inner.lock().files.clear();
+    }
+
+    /// Current size and counters.
+    pub fn stats(&self) -> ContentCacheStats {
+        let inner = self.inner.lock();
+        ContentCacheStats {
+            capacity: inner.capacity,
+            entries: inner.files.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl CacheInner {
+    /// Drop least recently used files until within capacity.
+    fn evict(&mut self) {
+        while self.files.len() > self.capacity {
+            let Some(oldest) = self
+                .files
+                .iter()
+                .min_by_key(|(_, f)| f.last_used)
+                .map(|(path, _)| path.clone())
+            else {
+                break;
+            };
+            self.files.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::LocalStore;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_content_cache() {
+        let temp = TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = ["a.md", "b.md", "c.md"].iter().map(|n| temp.path().join(n)).collect();
+        for path in &paths {
+            fs::write(path, "# Content\n").unwrap();
+        }
+
+        let cache = ContentCache::new(2);
+        cache.read_to_string(&LocalStore, &paths[0]).unwrap();
+        cache.read_to_string(&LocalStore, &paths[1]).unwrap();
+        assert_eq!(cache.read_to_string(&LocalStore, &paths[0]).unwrap(), "# Content\n");
+        assert_eq!((cache.stats().hits, cache.stats().misses), (1, 2));
+
+        // b is least recently used
+        cache.read_to_string(&LocalStore, &paths[2]).unwrap();
+        assert_eq!(cache.stats().entries, 2);
+        cache.read_to_string(&LocalStore, &paths[1]).unwrap();
+        assert_eq!(cache.stats().misses, 4);
+
+        // A changed size is a miss
+        fs::write(&paths[1], "# Changed content\n").unwrap();
+        assert_eq!(cache.read_to_string(&LocalStore, &paths[1]).unwrap(), "# Changed content\n");
+        assert_eq!(cache.stats().misses, 5);
+
+        cache.invalidate(temp.path());
+        assert_eq!(cache.stats().entries, 0);
+
+        let disabled = ContentCache::new(0);
+        disabled.read_to_string(&LocalStore, &paths[0]).unwrap();
+        disabled.read_to_string(&LocalStore, &paths[0]).unwrap();
+        assert_eq!(disabled.stats(), ContentCacheStats { capacity: 0, entries: 0, hits: 0, misses: 2 });
+    }
+}
@@ -0,0 +1,204 @@
+//! Dependency graph built from `depends_on` in skill metadata.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+
+use crate::models::SkillIndex;
+
+/// Dependencies of a single skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillDependencies {
+    /// Skill name.
+    pub skill: String,
+
+    /// Dependencies declared directly in the skill's `_meta.json`.
+    pub direct: Vec<String>,
+
+    /// Transitive closure of dependencies, nearest first.
+    pub transitive: Vec<String>,
+
+    /// Declared dependencies that don't exist in the index.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+}
+
+/// Directed graph of skill dependencies.
+pub struct DependencyGraph {
+    edges: HashMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    /// Build the graph from a skill index.
+    pub fn new(index: &SkillIndex) -> Self {
+        let edges = index
+            .skills
+            .iter()
+            .map(|s| (s.name.clone(), s.depends_on.clone()))
+            .collect();
+
+        Self { edges }
+    }
+
+    /// Check whether a skill is in the graph.
+    pub fn contains(&self, name: &str) -> bool {
+        self.edges.contains_key(name)
+    }
+
+    /// Get direct dependencies of a skill.
+    pub fn direct(&self, name: &str) -> &[String] {
+        self.edges.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Resolve a skill's dependencies, or None if the skill is unknown.
+    ///
+    /// The transitive list is breadth-first so nearer dependencies come
+    /// first, and terminates on cycles.
+    pub fn dependencies(&self, name: &str) -> Option<SkillDependencies> {
+        let direct = self.edges.get(name)?.clone();
+
+        let mut seen: HashSet<&str> = HashSet::from([name]);
+        let mut queue: VecDeque<&str> = direct.iter().map(String::as_str).collect();
+        let mut transitive = Vec::new();
+        let mut missing = Vec::new();
+
+        while let Some(dep) = queue.pop_front() {
+            if !seen.insert(dep) {
+                continue;
+            }
+            if !self.contains(dep) {
+                missing.push(dep.to_string());
+                continue;
+            }
+            transitive.push(dep.to_string());
+            queue.extend(self.direct(dep).iter().map(String::as_str));
+        }
+
+        Some(SkillDependencies {
+            skill: name.to_string(),
+            direct,
+            transitive,
+            missing,
+        })
+    }
+
+    /// List `(skill, dependency)` pairs where the dependency doesn't exist.
+    pub fn missing(&self) -> Vec<(String, String)> {
+        let mut missing: Vec<(String, String)> = self
+            .edges
+            .iter()
+            .flat_map(|(skill, deps)| {
+                deps.iter()
+                    .filter(|d| !self.contains(d))
+                    .map(move |d| (skill.clone(), d.clone()))
+            })
+            .collect();
+        missing.sort();
+        missing
+    }
+
+    /// Find dependency cycles.
+    ///
+    /// Each cycle is returned once, as a path starting and ending at the
+    /// same skill (e.g. `["a", "b", "a"]`).
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut names: Vec<&str> = self.edges.keys().map(String::as_str).collect();
+        names.sort_unstable();
+
+        let mut done: HashSet<&str> = HashSet::new();
+        let mut cycles = Vec::new();
+
+        for start in names {
+            let mut stack = Vec::new();
+            self.visit(start, &mut stack, &mut done, &mut cycles);
+        }
+
+        cycles
+    }
+
+    fn visit<'a>(
+        &'a self,
+        node: &'a str,
+        stack: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if done.contains(node) {
+            return;
+        }
+        if let Some(pos) = stack.iter().position(|n| *n == node) {
+            let mut cycle: Vec<String> = stack[pos..].iter().map(|n| n.to_string()).collect();
+            cycle.push(node.to_string());
+            cycles.push(cycle);
+            return;
+        }
+
+        stack.push(node);
+        for dep in self.direct(node) {
+            if self.contains(dep) {
+                self.visit(dep, stack, done, cycles);
+            }
+        }
+        stack.pop();
+        done.insert(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SkillMeta;
+
+    fn skill(name: &str, deps: &[&str]) -> SkillMeta {
+        SkillMeta {
+            name: name.to_string(),
+            description: format!("{} skill", name),
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_transitive_dependencies() {
+        let index = SkillIndex::with_skills(
+            vec![
+                skill("forms", &["validation", "react"]),
+                skill("validation", &["zod"]),
+                skill("react", &[]),
+                skill("zod", &["ghost"]),
+            ],
+            vec![],
+        );
+        let graph = DependencyGraph::new(&index);
+
+        let deps = graph.dependencies("forms").unwrap();
+        assert_eq!(deps.direct, vec!["validation", "react"]);
+        assert_eq!(deps.transitive, vec!["validation", "react", "zod"]);
+        assert_eq!(deps.missing, vec!["ghost"]);
+        assert!(graph.dependencies("unknown").is_none());
+        assert_eq!(
+            graph.missing(),
+            vec![("zod".to_string(), "ghost".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_detects_cycles() {
+        let index = SkillIndex::with_skills(
+            vec![
+                skill("a", &["b"]),
+                skill("b", &["c"]),
+                skill("c", &["a"]),
+                skill("d", &["a"]),
+            ],
+            vec![],
+        );
+        let graph = DependencyGraph::new(&index);
+
+        assert_eq!(graph.cycles(), vec![vec!["a", "b", "c", "a"]]);
+
+        // Closure terminates despite the cycle
+        let deps = graph.dependencies("d").unwrap();
+        assert_eq!(deps.transitive, vec!["a", "b", "c"]);
+    }
+}
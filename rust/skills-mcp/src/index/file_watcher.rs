@@ -1,29 +1,52 @@
 //! File system watcher for skill directory changes.
-
-use std::path::Path;
+//!
+//! Events are debounced: changed paths accumulate until the debounce window
+//! passes without new events (or [`MAX_BATCH_WINDOWS`] windows at most,
+//! so constant churn still gets indexed), then the affected skills are
+//! reindexed as one batch and a single
+//! [`IndexUpdated`](crate::events::SkillEventKind::IndexUpdated) event is
+//! published.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
-use super::SkillIndexer;
+use crate::events::SkillEventKind;
+
+use super::{SkillIndexer, WatchMode};
+
+/// Default quiet time before a batch of changes is applied.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Longest a batch waits, in debounce windows, while events keep arriving.
+pub const MAX_BATCH_WINDOWS: u32 = 10;
 
 /// File watcher that monitors skill directory for changes.
+///
+/// Stops when dropped.
 pub struct FileWatcher {
     watcher: RecommendedWatcher,
-    /// Shutdown signal sender (reserved for future graceful shutdown).
-    #[allow(dead_code)]
-    shutdown_tx: Option<mpsc::Sender<()>>,
 }
 
 impl FileWatcher {
-    /// Create and start a new file watcher.
+    /// Create and start a new file watcher with the default debounce window.
     ///
     /// The watcher uses incremental updates when possible, only rebuilding
     /// the affected skill's entries instead of the entire index.
     pub fn new(indexer: Arc<SkillIndexer>) -> Result<Self, WatchError> {
-        let indexer_clone = Arc::clone(&indexer);
+        Self::with_debounce(indexer, DEFAULT_DEBOUNCE)
+    }
 
+    /// Create and start a file watcher that applies changes once `debounce`
+    /// has passed without new events.
+    pub fn with_debounce(indexer: Arc<SkillIndexer>, debounce: Duration) -> Result<Self, WatchError> {
+        let (tx, rx) = mpsc::channel::<()>();
+
+        let recorder = Arc::clone(&indexer);
         let watcher = notify::recommended_watcher(move |res: Result<notify::Event, _>| {
             match res {
                 Ok(event) => {
@@ -37,35 +60,8 @@ impl FileWatcher {
                         return;
                     }
 
-                    // Try to determine which skill(s) were affected
-                    let mut affected_skills = std::collections::HashSet::new();
-
-                    for path in &event.paths {
-                        if let Some(skill_name) = indexer_clone.skill_from_path(path) {
-                            affected_skills.insert(skill_name);
-                        }
-                    }
-
-                    if affected_skills.is_empty() {
-                        // Couldn't determine affected skills, do a full reload
-                        debug!("File change outside skill directories, doing full reload");
-                        if let Err(e) = indexer_clone.reload() {
-                            error!("Failed to reload index: {}", e);
-                        }
-                    } else {
-                        // Incremental update for each affected skill
-                        for skill_name in affected_skills {
-                            debug!("Incrementally updating skill: {}", skill_name);
-                            if let Err(e) = indexer_clone.update_skill(&skill_name) {
-                                warn!("Failed to update skill {}: {}", skill_name, e);
-                                // Fall back to full reload on error
-                                if let Err(e) = indexer_clone.reload() {
-                                    error!("Failed to reload index: {}", e);
-                                }
-                                break;
-                            }
-                        }
-                    }
+                    recorder.watch_state().record(&event.paths);
+                    let _ = tx.send(());
                 }
                 Err(e) => {
                     warn!("Watch error: {:?}", e);
@@ -74,10 +70,13 @@ impl FileWatcher {
         })
         .map_err(|e| WatchError::Setup(format!("Failed to create watcher: {}", e)))?;
 
-        Ok(Self {
-            watcher,
-            shutdown_tx: None,
-        })
+        indexer.watch_state().started(WatchMode::Events, debounce);
+        std::thread::Builder::new()
+            .name("skills-watch-debounce".to_string())
+            .spawn(move || debounce_loop(&indexer, &rx, debounce))
+            .map_err(|e| WatchError::Setup(format!("Failed to start debounce thread: {}", e)))?;
+
+        Ok(Self { watcher })
     }
 
     /// Start watching a directory.
@@ -100,6 +99,78 @@ impl FileWatcher {
     }
 }
 
+/// Apply batches of recorded changes until the watcher is dropped, which
+/// closes the channel.
+fn debounce_loop(indexer: &SkillIndexer, rx: &mpsc::Receiver<()>, debounce: Duration) {
+    let max_wait = debounce * MAX_BATCH_WINDOWS;
+    let mut open = true;
+
+    while open && rx.recv().is_ok() {
+        let first = Instant::now();
+        loop {
+            let remaining = max_wait.saturating_sub(first.elapsed());
+            if remaining.is_zero() {
+                break;
+            }
+            match rx.recv_timeout(debounce.min(remaining)) {
+                Ok(()) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    open = false;
+                    break;
+                }
+            }
+        }
+
+        let paths = indexer.watch_state().take_pending();
+        if !paths.is_empty() {
+            debug!("Applying {} changed paths", paths.len());
+            apply_changes(indexer, &paths);
+        }
+    }
+
+    indexer.watch_state().stopped();
+}
+
+/// Apply a batch of changed paths to the index.
+///
+/// Affected skills are updated incrementally, followed by one
+/// `IndexUpdated` event; changes outside any skill directory, or failed
+/// incremental updates, trigger a full reload instead. Shared by the
+/// event-based [`FileWatcher`] and the polling watcher.
+pub(crate) fn apply_changes(indexer: &SkillIndexer, paths: &[PathBuf]) {
+    // Try to determine which skill(s) were affected
+    let mut affected_skills: Vec<String> = paths
+        .iter()
+        .filter_map(|path| indexer.skill_from_path(path))
+        .collect();
+    affected_skills.sort();
+    affected_skills.dedup();
+
+    let incremental = !affected_skills.is_empty()
+        && affected_skills.iter().all(|skill_name| {
+            debug!("Incrementally updating skill: {}", skill_name);
+            indexer
+                .update_skill(skill_name)
+                .map_err(|e| warn!("Failed to update skill {}: {}", skill_name, e))
+                .is_ok()
+        });
+
+    if incremental {
+        indexer.watch_state().batch_applied(&affected_skills);
+        indexer.events().publish(SkillEventKind::IndexUpdated {
+            skills: affected_skills,
+        });
+    } else {
+        // Couldn't determine affected skills, or an update failed
+        debug!("Doing full reload");
+        if let Err(e) = indexer.reload() {
+            error!("Failed to reload index: {}", e);
+        }
+        indexer.watch_state().batch_applied(&[]);
+    }
+}
+
 /// Errors that can occur with file watching.
 #[derive(Debug, thiserror::Error)]
 pub enum WatchError {
@@ -134,7 +205,51 @@ mod tests {
         let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
         indexer.reload().unwrap();
 
-        let mut watcher = FileWatcher::new(indexer).unwrap();
+        let mut watcher = FileWatcher::new(Arc::clone(&indexer)).unwrap();
         watcher.watch(temp_dir.path()).unwrap();
+        assert!(indexer.watch_state().status().enabled);
+    }
+
+    #[test]
+    fn test_debounced_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["forms", "tables"] {
+            let skill_dir = temp_dir.path().join(name);
+            std::fs::create_dir_all(&skill_dir).unwrap();
+            std::fs::write(
+                skill_dir.join("_meta.json"),
+                format!(r#"{{"name": "{}", "description": "Test"}}"#, name),
+            )
+            .unwrap();
+        }
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let mut events = indexer.events().subscribe();
+
+        // A burst of events across two skills is applied as one batch
+        let (tx, rx) = mpsc::channel();
+        let state = indexer.watch_state();
+        for name in ["forms", "tables", "forms"] {
+            state.record(&[temp_dir.path().join(name).join("_meta.json")]);
+            tx.send(()).unwrap();
+        }
+        drop(tx);
+        debounce_loop(&indexer, &rx, Duration::from_millis(10));
+
+        let status = state.status();
+        assert_eq!((status.batches, status.pending), (1, 0));
+        assert_eq!(status.last_batch_skills, vec!["forms", "tables"]);
+        assert!(!status.enabled);
+
+        let kinds: Vec<SkillEventKind> = std::iter::from_fn(|| events.try_recv().ok())
+            .map(|e| e.kind)
+            .collect();
+        assert_eq!(
+            kinds.last(),
+            Some(&SkillEventKind::IndexUpdated {
+                skills: vec!["forms".to_string(), "tables".to_string()]
+            })
+        );
+        assert_eq!(kinds.iter().filter(|k| k.name() == "index_updated").count(), 1);
     }
 }
@@ -0,0 +1,513 @@
+//! Which reference files are indexed, and the type of each file.
+//!
+//! Reference directories can hold anything, including images and PDFs.
+//! Only files with a configured extension are indexed, and only when they
+//! are text; everything is still listed and served by the references API.
+//!
+//! Indexing never gives up on a file it can read: invalid UTF-8 is replaced
+//! with U+FFFD and files over the size cap are truncated with a marker, each
+//! leaving a warning for the validation report.
+//!
+//! Reference directories are walked with [`walk_files`], which follows
+//! symlinks only as the configured [`SymlinkPolicy`] allows, never enters a
+//! directory twice, and stops at a maximum depth, so a link cycle can't
+//! hang a reload.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::cache::ContentCache;
+use super::names::NameConfig;
+use super::store::SkillStore;
+use super::tags::TagConfig;
+
+/// Type of markdown files.
+pub const MARKDOWN: &str = "markdown";
+
+/// Bytes sniffed for NUL bytes to tell binary files from text.
+const SNIFF_BYTES: usize = 8192;
+
+/// Indexing settings from the `[index]` configuration section:
+///
+/// ```toml
+/// [index]
+/// extensions = ["md", "markdown", "py", "ts", "json", "yaml", "yml"]
+/// max_file_bytes = 1048576
+/// symlinks = "internal"
+/// max_depth = 16
+/// content_cache = 256
+/// register_orphans = false
+///
+/// [index.tags]
+/// lowercase = true
+///
+/// [index.names]
+/// case = "auto"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct IndexConfig {
+    /// Extensions of reference files to index, without the dot. Files that
+    /// aren't markdown are indexed as plain text.
+    pub extensions: Vec<String>,
+
+    /// Bytes of each file indexed; the rest of a longer file is cut off.
+    pub max_file_bytes: u64,
+
+    /// Which symlinks are followed in reference directories.
+    pub symlinks: SymlinkPolicy,
+
+    /// Deepest directory level walked below a references directory.
+    pub max_depth: usize,
+
+    /// Skill files whose contents are kept in memory between reads; 0
+    /// reads from disk every time.
+    pub content_cache: usize,
+
+    /// Load markdown files next to SKILL.md that no sub-skill declares as
+    /// sub-skills named after the file, instead of only reporting them.
+    pub register_orphans: bool,
+
+    /// Normalization rules for tags.
+    pub tags: TagConfig,
+
+    /// How skill names are compared and which are refused.
+    pub names: NameConfig,
+}
+
+/// Which symlinks are followed when walking reference directories.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymlinkPolicy {
+    /// Follow links whose target is inside the skills directory.
+    #[default]
+    Internal,
+    /// Follow every link.
+    Follow,
+    /// Ignore links.
+    Skip,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            extensions: vec!["md".to_string(), "markdown".to_string()],
+            max_file_bytes: 1024 * 1024,
+            symlinks: SymlinkPolicy::default(),
+            max_depth: 16,
+            content_cache: ContentCache::DEFAULT_CAPACITY,
+            register_orphans: false,
+            tags: TagConfig::default(),
+            names: NameConfig::default(),
+        }
+    }
+}
+
+impl IndexConfig {
+    /// Whether a reference file has an indexed extension.
+    pub fn indexes(&self, path: &Path) -> bool {
+        path.extension().and_then(|e| e.to_str()).is_some_and(|ext| {
+            self.extensions
+                .iter()
+                .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(ext))
+        })
+    }
+
+    /// Why a reference file is left out of the index, or None if it is
+    /// indexed.
+    pub fn skip_reason(&self, store: &dyn SkillStore, path: &Path) -> Option<SkipReason> {
+        if !self.indexes(path) {
+            Some(SkipReason::Extension)
+        } else if is_binary(store, path) {
+            Some(SkipReason::Binary)
+        } else {
+            None
+        }
+    }
+}
+
+/// Why a reference file isn't indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Its extension isn't configured for indexing.
+    Extension,
+    /// It contains binary data.
+    Binary,
+}
+
+/// A file in a skill's references directory.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferenceFile {
+    /// Path relative to the references directory.
+    pub path: String,
+
+    /// MIME type guessed from the extension.
+    pub mime_type: String,
+
+    /// Size in bytes.
+    pub size: u64,
+
+    /// Whether the file is in the search index.
+    pub indexed: bool,
+
+    /// Whether only the first `max_file_bytes` are indexed.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+
+    /// Why the file isn't indexed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<SkipReason>,
+}
+
+/// List every file under a references directory, sorted by path.
+///
+/// `boundary` is the skills directory that internal symlinks must stay in.
+pub fn list_references(
+    store: &dyn SkillStore,
+    refs_dir: &Path,
+    boundary: &Path,
+    config: &IndexConfig,
+) -> Vec<ReferenceFile> {
+    let mut files: Vec<ReferenceFile> = store
+        .walk(refs_dir, boundary, config)
+        .files
+        .into_iter()
+        .filter_map(|path| {
+            let size = store.metadata(&path).ok()?.len;
+            let relative = path.strip_prefix(refs_dir).ok()?;
+            let skipped = config.skip_reason(store, &path);
+            Some(ReferenceFile {
+                path: relative.to_string_lossy().replace('\\', "/"),
+                mime_type: mime_type(&path),
+                size,
+                indexed: skipped.is_none(),
+                truncated: skipped.is_none() && size > config.max_file_bytes,
+                skipped,
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+/// MIME type of a file, guessed from its extension.
+pub fn mime_type(path: &Path) -> String {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown") => {
+            "text/markdown".to_string()
+        }
+        _ => mime_guess::from_path(path)
+            .first_or_octet_stream()
+            .essence_str()
+            .to_string(),
+    }
+}
+
+/// Files found walking a directory, and the paths left out.
+#[derive(Debug, Default)]
+pub struct Walk {
+    /// Files, as paths under the walked directory (not symlink targets).
+    pub files: Vec<PathBuf>,
+
+    /// Symlinks and directories not followed, with the reason.
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+/// Identity of a directory, so one reached through several links is
+/// walked once: its device and inode.
+#[cfg(unix)]
+fn dir_id(_path: &Path, metadata: &fs::Metadata) -> (u64, u64) {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.dev(), metadata.ino())
+}
+
+/// Identity of a directory: its canonical path, where there are no inodes.
+#[cfg(not(unix))]
+fn dir_id(path: &Path, _metadata: &fs::Metadata) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Walk the files under a directory in path order.
+///
+/// Symlinks are followed as `config.symlinks` allows; internal links must
+/// resolve inside `boundary`. Each directory is entered at most once, so
+/// link cycles end, and nothing deeper than `config.max_depth` is walked.
+pub fn walk_files(dir: &Path, boundary: &Path, config: &IndexConfig) -> Walk {
+    let mut walk = Walk::default();
+    let boundary = fs::canonicalize(boundary).unwrap_or_else(|_| boundary.to_path_buf());
+    let mut visited = HashSet::new();
+    if let Ok(metadata) = fs::metadata(dir) {
+        visited.insert(dir_id(dir, &metadata));
+    }
+
+    let mut stack = vec![(dir.to_path_buf(), 0)];
+    while let Some((current, depth)) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        paths.sort();
+
+        for path in paths {
+            let Ok(link_meta) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if link_meta.file_type().is_symlink() {
+                match config.symlinks {
+                    SymlinkPolicy::Skip => continue,
+                    SymlinkPolicy::Follow => {}
+                    SymlinkPolicy::Internal => {
+                        let inside = fs::canonicalize(&path).is_ok_and(|t| t.starts_with(&boundary));
+                        if !inside {
+                            walk.skipped.push((path, "Symlink points outside the skills directory".to_string()));
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            // Follows the link, if it is one
+            let Ok(metadata) = fs::metadata(&path) else {
+                walk.skipped.push((path, "Broken symlink".to_string()));
+                continue;
+            };
+            if metadata.is_file() {
+                walk.files.push(path);
+            } else if metadata.is_dir() {
+                if depth >= config.max_depth {
+                    walk.skipped.push((path, format!("Deeper than max_depth ({})", config.max_depth)));
+                } else if !visited.insert(dir_id(&path, &metadata)) {
+                    walk.skipped.push((path, "Directory already walked (symlink cycle?)".to_string()));
+                } else {
+                    stack.push((path, depth + 1));
+                }
+            }
+        }
+    }
+
+    walk.files.sort();
+    walk
+}
+
+/// Text of a file as read for indexing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedText {
+    /// The decoded text, ending in a marker if it was truncated.
+    pub content: String,
+
+    /// What was lost decoding or truncating the file.
+    pub warnings: Vec<String>,
+}
+
+/// Read at most `max_bytes` of a file for indexing.
+///
+/// Invalid UTF-8 is replaced rather than failing the read, and a longer file
+/// is cut at a character boundary and marked as truncated. Only errors
+/// opening or reading the file are returned.
+pub fn read_indexed_text(store: &dyn SkillStore, path: &Path, max_bytes: u64) -> io::Result<IndexedText> {
+    let (mut bytes, size) = store.read_prefix(path, max_bytes)?;
+
+    let mut warnings = Vec::new();
+    let truncated = size > max_bytes;
+    if truncated {
+        bytes.truncate(char_boundary(&bytes));
+    }
+
+    let mut content = match String::from_utf8(bytes) {
+        Ok(content) => content,
+        Err(e) => {
+            warnings.push("Invalid UTF-8 replaced with U+FFFD".to_string());
+            String::from_utf8_lossy(e.as_bytes()).into_owned()
+        }
+    };
+    if truncated {
+        warnings.push(format!(
+            "File is {} bytes; only the first {} are indexed",
+            size, max_bytes
+        ));
+        content.push_str(&format!("\n\n[truncated: {} of {} bytes indexed]\n", max_bytes, size));
+    }
+
+    Ok(IndexedText { content, warnings })
+}
+
+/// Read a whole file as text, replacing invalid UTF-8.
+pub fn read_lossy(path: &Path) -> io::Result<String> {
+    let bytes = fs::read(path)?;
+    Ok(match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    })
+}
+
+/// Length of `bytes` without a character cut off at the end.
+fn char_boundary(bytes: &[u8]) -> usize {
+    // The last character starts within the final four bytes
+    let start = bytes
+        .iter()
+        .rposition(|b| b & 0xC0 != 0x80)
+        .filter(|&i| bytes.len() - i <= 4);
+    let Some(start) = start else {
+        return bytes.len();
+    };
+    let width = match bytes[start] {
+        b if b >= 0xF0 => 4,
+        b if b >= 0xE0 => 3,
+        b if b >= 0xC0 => 2,
+        _ => 1,
+    };
+    if start + width > bytes.len() {
+        start
+    } else {
+        bytes.len()
+    }
+}
+
+/// Whether a file looks binary: it has a NUL byte near the start.
+fn is_binary(store: &dyn SkillStore, path: &Path) -> bool {
+    store
+        .read_prefix(path, SNIFF_BYTES as u64)
+        .is_ok_and(|(bytes, _)| bytes.contains(&0))
+}
+
+/// Type of a file from its path: `markdown`, `text`, or the lowercased
+/// extension (`py`, `json`, `yaml`).
+pub fn file_type(path: &str) -> String {
+    let ext = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    normalize_file_type(ext)
+}
+
+/// Fold the spellings of a file type or extension into one name, so
+/// filters for `md` or `yml` find `markdown` and `yaml` files.
+pub fn normalize_file_type(file_type: &str) -> String {
+    let file_type = file_type.trim_start_matches('.').to_lowercase();
+    match file_type.as_str() {
+        "md" | "markdown" => MARKDOWN.to_string(),
+        "yml" => "yaml".to_string(),
+        "" | "txt" => "text".to_string(),
+        _ => file_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::LocalStore;
+
+    #[test]
+    fn test_file_types() {
+        assert_eq!(file_type("references/api.md"), "markdown");
+        assert_eq!(file_type("references/config.YML"), "yaml");
+        assert_eq!(file_type("references/example.py"), "py");
+        assert_eq!(file_type("references/NOTES"), "text");
+
+        let config = IndexConfig {
+            extensions: vec!["md".to_string(), ".py".to_string()],
+            ..Default::default()
+        };
+        assert!(config.indexes(Path::new("a/example.PY")));
+        assert!(!config.indexes(Path::new("a/data.json")));
+        assert!(!IndexConfig::default().indexes(Path::new("a/example.py")));
+    }
+
+    #[test]
+    fn test_list_references() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let refs = temp_dir.path();
+        fs::create_dir_all(refs.join("img")).unwrap();
+        fs::write(refs.join("api.md"), "# API\n").unwrap();
+        fs::write(refs.join("big.md"), "x".repeat(100)).unwrap();
+        fs::write(refs.join("fake.md"), b"\x89PNG\0\0").unwrap();
+        fs::write(refs.join("img/logo.png"), b"\x89PNG\0\0").unwrap();
+
+        let config = IndexConfig {
+            max_file_bytes: 50,
+            ..Default::default()
+        };
+        let files = list_references(&LocalStore, refs, refs, &config);
+        let summary: Vec<(&str, &str, Option<SkipReason>)> = files
+            .iter()
+            .map(|f| (f.path.as_str(), f.mime_type.as_str(), f.skipped))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("api.md", "text/markdown", None),
+                ("big.md", "text/markdown", None),
+                ("fake.md", "text/markdown", Some(SkipReason::Binary)),
+                ("img/logo.png", "image/png", Some(SkipReason::Extension)),
+            ]
+        );
+        assert_eq!(files[3].size, 6);
+        assert!(files[1].truncated && !files[0].truncated);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_files_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let skills = temp_dir.path().join("skills");
+        let refs = skills.join("forms/references");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir_all(refs.join("a/b")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(refs.join("top.md"), "top").unwrap();
+        fs::write(refs.join("a/b/deep.md"), "deep").unwrap();
+        fs::write(outside.join("secret.md"), "secret").unwrap();
+        symlink(&refs, refs.join("a/loop")).unwrap();
+        symlink(&outside, refs.join("outside")).unwrap();
+
+        let walk = |symlinks, max_depth| {
+            let config = IndexConfig {
+                symlinks,
+                max_depth,
+                ..Default::default()
+            };
+            let walk = walk_files(&refs, &skills, &config);
+            let files: Vec<String> = walk
+                .files
+                .iter()
+                .map(|p| p.strip_prefix(&refs).unwrap().to_string_lossy().into_owned())
+                .collect();
+            (files, walk.skipped.len())
+        };
+
+        // The cycle back to references/ is walked once; outside is refused
+        assert_eq!(walk(SymlinkPolicy::Internal, 16), (vec!["a/b/deep.md".into(), "top.md".into()], 2));
+        assert_eq!(
+            walk(SymlinkPolicy::Follow, 16),
+            (vec!["a/b/deep.md".into(), "outside/secret.md".into(), "top.md".into()], 1)
+        );
+        assert_eq!(walk(SymlinkPolicy::Skip, 16), (vec!["a/b/deep.md".into(), "top.md".into()], 0));
+        assert_eq!(walk(SymlinkPolicy::Skip, 1), (vec!["top.md".into()], 1));
+    }
+
+    #[test]
+    fn test_read_indexed_text() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("notes.md");
+
+        fs::write(&path, b"caf\xc3\xa9 ok \xff end").unwrap();
+        let text = read_indexed_text(&LocalStore, &path, 1024).unwrap();
+        assert_eq!(text.content, "café ok \u{FFFD} end");
+        assert_eq!(text.warnings, vec!["Invalid UTF-8 replaced with U+FFFD"]);
+
+        // The cut falls inside "é", which is dropped rather than mangled
+        fs::write(&path, "abcé tail").unwrap();
+        let text = read_indexed_text(&LocalStore, &path, 4).unwrap();
+        assert_eq!(text.content, "abc\n\n[truncated: 4 of 10 bytes indexed]\n");
+        assert_eq!(text.warnings, vec!["File is 10 bytes; only the first 4 are indexed"]);
+
+        assert!(read_indexed_text(&LocalStore, &temp_dir.path().join("missing.md"), 4).is_err());
+    }
+}
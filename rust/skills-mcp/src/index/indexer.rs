@@ -1,18 +1,31 @@
 //! Skill indexer implementation.
 
-use std::fs;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
-use tracing::{debug, error, info};
-use walkdir::WalkDir;
+use serde_json::{Map, Value};
+use tracing::{debug, info, warn};
 
+use crate::events::{EventBus, SkillEventKind};
 use crate::models::{
-    ContentIndex, ContentIndexEntry, SkillContent, SkillIndex, SkillMeta, SubSkillContent,
+    ContentIndex, ContentIndexEntry, ErrorCode, IndexWarning, ShadowedSkill, SkillContent, SkillIndex,
+    SkillMeta, SubSkillContent, SubSkillMeta,
 };
+use crate::markdown;
+use crate::scripts::SCRIPTS_DIR;
 use crate::validation::validate_meta;
 
+use super::reload::{ReloadPhase, ReloadProgress};
+use super::{
+    list_references, normalize_separators, read_indexed_text, reserved_segment, ContentCache, IndexConfig,
+    LocalStore, ReferenceFile, SkillStore, SkipReason, WatchState,
+};
+
 /// Combined index structure for atomic updates.
 ///
 /// This ensures that skill_index and content_index are always consistent
@@ -21,13 +34,40 @@ use crate::validation::validate_meta;
 struct CombinedIndex {
     skill_index: SkillIndex,
     content_index: ContentIndex,
+    /// Incremented on every change, so readers can tell the index moved on.
+    generation: u64,
 }
 
 impl CombinedIndex {
+    /// Hash of each skill's metadata and indexed content, keyed by name.
+    fn fingerprints(&self) -> HashMap<String, u64> {
+        let mut entries: Vec<(&String, &ContentIndexEntry)> = self.content_index.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut hashers: HashMap<&str, DefaultHasher> = HashMap::new();
+        for skill in &self.skill_index.skills {
+            let mut hasher = DefaultHasher::new();
+            serde_json::to_string(skill).unwrap_or_default().hash(&mut hasher);
+            hashers.insert(&skill.name, hasher);
+        }
+        for (key, entry) in entries {
+            if let Some(hasher) = hashers.get_mut(entry.domain.as_str()) {
+                key.hash(hasher);
+                entry.content.hash(hasher);
+            }
+        }
+
+        hashers
+            .into_iter()
+            .map(|(name, hasher)| (name.to_string(), hasher.finish()))
+            .collect()
+    }
+
     fn new() -> Self {
         Self {
             skill_index: SkillIndex::new(),
             content_index: ContentIndex::new(),
+            generation: 0,
         }
     }
 }
@@ -35,7 +75,7 @@ impl CombinedIndex {
 /// Validates that a file path from metadata doesn't escape the skill directory.
 ///
 /// Returns `Ok(canonical_path)` if the path is safe, `Err` otherwise.
-fn validate_sub_skill_path(skill_dir: &Path, file: &str) -> Result<PathBuf, IndexError> {
+fn validate_sub_skill_path(store: &dyn SkillStore, skill_dir: &Path, file: &str) -> Result<PathBuf, IndexError> {
     // Check for obvious path traversal sequences
     if file.contains("..") {
         return Err(IndexError::ValidationError(format!(
@@ -63,12 +103,12 @@ fn validate_sub_skill_path(skill_dir: &Path, file: &str) -> Result<PathBuf, Inde
     let file_path = skill_dir.join(file);
 
     // If the file exists, canonicalize and verify it's within skill_dir
-    if file_path.exists() {
-        let canonical_path = file_path.canonicalize().map_err(|e| {
+    if store.metadata(&file_path).is_ok() {
+        let canonical_path = store.canonicalize(&file_path).map_err(|e| {
             IndexError::ReadError(format!("Failed to resolve path {}: {}", file_path.display(), e))
         })?;
 
-        let canonical_skill_dir = skill_dir.canonicalize().map_err(|e| {
+        let canonical_skill_dir = store.canonicalize(skill_dir).map_err(|e| {
             IndexError::ReadError(format!(
                 "Failed to resolve skill directory {}: {}",
                 skill_dir.display(),
@@ -93,39 +133,283 @@ fn validate_sub_skill_path(skill_dir: &Path, file: &str) -> Result<PathBuf, Inde
     }
 }
 
+/// Which metadata source wins when a skill has both `_meta.json` and
+/// YAML frontmatter in SKILL.md.
+///
+/// Fields are merged key by key: the preferred source wins where both set a
+/// field, and the other source fills in the rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetaPrecedence {
+    /// `_meta.json` fields override frontmatter.
+    #[default]
+    MetaJson,
+    /// Frontmatter fields override `_meta.json`.
+    Frontmatter,
+}
+
+impl std::str::FromStr for MetaPrecedence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "meta-json" | "meta" | "json" => Ok(Self::MetaJson),
+            "frontmatter" => Ok(Self::Frontmatter),
+            other => Err(format!(
+                "Unknown metadata precedence '{}' (expected meta-json or frontmatter)",
+                other
+            )),
+        }
+    }
+}
+
+/// A directory of skills managed by the indexer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillRoot {
+    /// Directory containing one subdirectory per skill.
+    pub path: PathBuf,
+
+    /// Reject writes to skills loaded from this root.
+    pub read_only: bool,
+}
+
+impl SkillRoot {
+    /// A writable root.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            read_only: false,
+        }
+    }
+
+    /// A read-only root.
+    pub fn read_only(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            read_only: true,
+        }
+    }
+}
+
+impl std::str::FromStr for SkillRoot {
+    type Err = String;
+
+    /// Parse `PATH`, `PATH:ro`, or `PATH:rw`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("Skills root path cannot be empty".to_string());
+        }
+        Ok(match s.rsplit_once(':') {
+            Some((path, "ro")) => Self::read_only(path),
+            Some((path, "rw")) => Self::new(path),
+            _ => Self::new(s),
+        })
+    }
+}
+
 /// Skill indexer that manages metadata and content indexes.
+///
+/// Skills can come from several roots. Roots are searched in priority
+/// order, and a skill in a higher-priority root shadows one with the same
+/// name further down. The first root is the primary root, where new skills
+/// are created.
 pub struct SkillIndexer {
-    /// Path to the skills directory.
+    /// Path to the primary skills directory.
     skills_dir: PathBuf,
 
+    /// All roots in priority order, starting with the primary root.
+    roots: RwLock<Vec<SkillRoot>>,
+
+    /// Which metadata source wins when both are present.
+    meta_precedence: RwLock<MetaPrecedence>,
+
+    /// Which reference files are indexed.
+    index_config: RwLock<IndexConfig>,
+
     /// Combined index protected by a single lock for atomic updates.
     /// This ensures skill_index and content_index are always consistent.
     index: Arc<RwLock<CombinedIndex>>,
+
+    /// Change events published as the index is updated.
+    events: EventBus,
+
+    /// State of the watcher keeping the index up to date, if any.
+    watch: WatchState,
+
+    /// When the last full build completed; None until the first one does.
+    built_at: RwLock<Option<DateTime<Utc>>>,
+
+    /// Contents of SKILL.md and sub-skill files read recently.
+    content_cache: ContentCache,
+
+    /// Where skill files are read from.
+    store: Arc<dyn SkillStore>,
 }
 
 impl SkillIndexer {
     /// Create a new indexer for the given skills directory.
     pub fn new(skills_dir: impl AsRef<Path>) -> Self {
+        Self::with_roots(vec![SkillRoot::new(skills_dir)])
+    }
+
+    /// Create an indexer over several roots, highest priority first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `roots` is empty.
+    pub fn with_roots(roots: Vec<SkillRoot>) -> Self {
+        let primary = roots.first().expect("at least one skills root").path.clone();
         Self {
-            skills_dir: skills_dir.as_ref().to_path_buf(),
+            skills_dir: primary,
+            roots: RwLock::new(roots),
+            meta_precedence: RwLock::new(MetaPrecedence::default()),
+            index_config: RwLock::new(IndexConfig::default()),
             index: Arc::new(RwLock::new(CombinedIndex::new())),
+            events: EventBus::new(),
+            watch: WatchState::new(),
+            built_at: RwLock::new(None),
+            content_cache: ContentCache::default(),
+            store: Arc::new(LocalStore),
         }
     }
 
-    /// Get the skills directory path.
+    /// Read skills from `store` rather than the local filesystem. Roots
+    /// are paths within the store.
+    pub fn with_store(mut self, store: Arc<dyn SkillStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Backend skill files are read from.
+    pub fn store(&self) -> &dyn SkillStore {
+        self.store.as_ref()
+    }
+
+    /// Bus of skill change events published by this indexer.
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// State of the watcher keeping this index up to date.
+    pub fn watch_state(&self) -> &WatchState {
+        &self.watch
+    }
+
+    /// Cache of skill file contents.
+    pub fn content_cache(&self) -> &ContentCache {
+        &self.content_cache
+    }
+
+    /// When the index was last fully built, or None if no build has
+    /// completed yet.
+    pub fn built_at(&self) -> Option<DateTime<Utc>> {
+        *self.built_at.read()
+    }
+
+    /// Get the primary skills directory path.
     pub fn skills_dir(&self) -> &Path {
         &self.skills_dir
     }
 
+    /// Get all roots in priority order.
+    pub fn roots(&self) -> Vec<SkillRoot> {
+        self.roots.read().clone()
+    }
+
+    /// Add a root with lower priority than the existing ones. Takes effect
+    /// on the next reload.
+    pub fn add_root(&self, root: SkillRoot) {
+        self.roots.write().push(root);
+    }
+
+    /// Get the root an indexed skill was loaded from.
+    pub fn skill_root(&self, name: &str) -> Option<SkillRoot> {
+        let root = self.get_skill_meta(name)?.root?;
+        self.roots
+            .read()
+            .iter()
+            .find(|r| r.path.to_string_lossy() == root)
+            .cloned()
+    }
+
+    /// Get the skills directory an indexed skill was loaded from; other
+    /// names resolve to the primary root.
+    pub fn skill_root_dir(&self, name: &str) -> PathBuf {
+        self.skill_root(name)
+            .map_or_else(|| self.skills_dir.clone(), |root| root.path)
+    }
+
+    /// Get the directory of a skill.
+    ///
+    /// Indexed skills resolve to the root they were loaded from; other names
+    /// resolve to the first root containing them, or the primary root.
+    pub fn skill_dir(&self, name: &str) -> PathBuf {
+        if let Some(meta) = self.get_skill_meta(name) {
+            if let Some(root) = meta.root {
+                return PathBuf::from(root).join(meta.name);
+            }
+        }
+        self.find_skill_dir(name)
+            .map(|(_, dir)| dir)
+            .unwrap_or_else(|| self.skills_dir.join(name))
+    }
+
+    /// Whether writes to a skill are rejected because its root is read-only.
+    ///
+    /// Names that aren't indexed would be created in the primary root, so
+    /// they follow the primary root's flag.
+    pub fn is_read_only(&self, name: &str) -> bool {
+        match self.skill_root(name) {
+            Some(root) => root.read_only,
+            None => self.roots.read().first().is_some_and(|r| r.read_only),
+        }
+    }
+
+    /// Find the highest-priority root with a directory for `name`.
+    fn find_skill_dir(&self, name: &str) -> Option<(SkillRoot, PathBuf)> {
+        self.roots
+            .read()
+            .iter()
+            .map(|root| (root.clone(), root.path.join(name)))
+            .find(|(_, dir)| self.store.is_dir(dir))
+    }
+
+    /// Get the metadata precedence.
+    pub fn meta_precedence(&self) -> MetaPrecedence {
+        *self.meta_precedence.read()
+    }
+
+    /// Set the metadata precedence. Takes effect on the next reload.
+    pub fn set_meta_precedence(&self, precedence: MetaPrecedence) {
+        *self.meta_precedence.write() = precedence;
+    }
+
+    /// Get the indexing settings.
+    pub fn index_config(&self) -> IndexConfig {
+        self.index_config.read().clone()
+    }
+
+    /// Set the indexing settings. Takes effect on the next reload.
+    pub fn set_index_config(&self, config: IndexConfig) {
+        self.content_cache.set_capacity(config.content_cache);
+        *self.index_config.write() = config;
+    }
+
     /// Reload both indexes from disk.
     ///
     /// This performs an atomic update of both indexes to ensure consistency.
     /// Readers will see either the old state or the new state, never a mix.
     pub fn reload(&self) -> Result<(), IndexError> {
+        self.reload_with_progress(&ReloadProgress::new())
+    }
+
+    /// Reload both indexes, reporting progress as skills are scanned.
+    pub fn reload_with_progress(&self, progress: &ReloadProgress) -> Result<(), IndexError> {
         info!("Reloading skill indexes from {:?}", self.skills_dir);
 
         // Build new indexes outside the lock
-        let skill_index = self.build_skill_index()?;
+        progress.set_phase(ReloadPhase::Scanning);
+        let skill_index = self.build_skill_index(progress)?;
+        progress.set_phase(ReloadPhase::Indexing);
         let content_index = self.build_content_index(&skill_index)?;
 
         // Capture counts before moving into the combined index
@@ -133,20 +417,51 @@ impl SkillIndexer {
         let content_count = content_index.len();
 
         // Atomic update: replace both indexes in a single write operation
-        let combined = CombinedIndex {
+        let mut combined = CombinedIndex {
             skill_index,
             content_index,
+            generation: 0,
         };
-        *self.index.write() = combined;
+        let after = combined.fingerprints();
+        let before = {
+            let mut index = self.index.write();
+            combined.generation = index.generation + 1;
+            std::mem::replace(&mut *index, combined).fingerprints()
+        };
+        *self.built_at.write() = Some(Utc::now());
+        self.content_cache.clear();
 
         info!(
             "Index reload complete: {} skills, {} content entries",
             skill_count, content_count
         );
 
+        self.publish_changes(&before, &after);
+        self.events.publish(SkillEventKind::IndexReloaded {
+            skills: skill_count,
+        });
+
         Ok(())
     }
 
+    /// Publish created, updated, and deleted events between two snapshots.
+    fn publish_changes(&self, before: &HashMap<String, u64>, after: &HashMap<String, u64>) {
+        let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+        names.sort();
+        names.dedup();
+
+        for name in names {
+            let name = name.clone();
+            let change = match (before.get(&name), after.get(&name)) {
+                (None, Some(_)) => SkillEventKind::Created { name },
+                (Some(_), None) => SkillEventKind::Deleted { name },
+                (Some(old), Some(new)) if old != new => SkillEventKind::Updated { name },
+                _ => continue,
+            };
+            self.events.publish(change);
+        }
+    }
+
     /// Get the current skill index.
     pub fn get_skill_index(&self) -> SkillIndex {
         self.index.read().skill_index.clone()
@@ -157,6 +472,85 @@ impl SkillIndexer {
         self.index.read().content_index.clone()
     }
 
+    /// Index generation, incremented whenever the index changes. Anything
+    /// derived from the index is current while the generation is the same.
+    pub fn generation(&self) -> u64 {
+        self.index.read().generation
+    }
+
+    // ========================================================================
+    // Async API
+    // ========================================================================
+    //
+    // Indexing walks and reads whole skill trees with `std::fs`. These
+    // wrappers run that work on tokio's blocking thread pool so async
+    // handlers can await it without stalling the runtime's worker threads.
+
+    /// Run indexer work on the blocking thread pool.
+    pub async fn run_blocking<T, F>(self: &Arc<Self>, f: F) -> Result<T, IndexError>
+    where
+        F: FnOnce(&SkillIndexer) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let indexer = Arc::clone(self);
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            let _span = span.entered();
+            f(&indexer)
+        })
+            .await
+            .map_err(|e| IndexError::ReadError(format!("Indexing task failed: {}", e)))
+    }
+
+    /// Reload both indexes from disk without blocking the runtime.
+    pub async fn reload_async(self: &Arc<Self>) -> Result<(), IndexError> {
+        self.run_blocking(|indexer| indexer.reload()).await?
+    }
+
+    /// Update a single skill in the index without blocking the runtime.
+    pub async fn update_skill_async(self: &Arc<Self>, name: &str) -> Result<(), IndexError> {
+        let name = name.to_string();
+        self.run_blocking(move |indexer| indexer.update_skill(&name)).await?
+    }
+
+    /// Read main SKILL.md content without blocking the runtime.
+    pub async fn read_skill_content_async(
+        self: &Arc<Self>,
+        name: &str,
+    ) -> Result<SkillContent, IndexError> {
+        let name = name.to_string();
+        self.run_blocking(move |indexer| indexer.read_skill_content(&name)).await?
+    }
+
+    /// Read a skill's content in a language without blocking the runtime.
+    pub async fn read_skill_content_in_async(
+        self: &Arc<Self>,
+        name: &str,
+        lang: Option<&str>,
+    ) -> Result<SkillContent, IndexError> {
+        let (name, lang) = (name.to_string(), lang.map(str::to_string));
+        self.run_blocking(move |indexer| indexer.read_skill_content_in(&name, lang.as_deref())).await?
+    }
+
+    /// Read sub-skill content without blocking the runtime.
+    pub async fn read_sub_skill_content_async(
+        self: &Arc<Self>,
+        name: &str,
+        sub_skill: &str,
+    ) -> Result<SubSkillContent, IndexError> {
+        let (name, sub_skill) = (name.to_string(), sub_skill.to_string());
+        self.run_blocking(move |indexer| indexer.read_sub_skill_content(&name, &sub_skill))
+            .await?
+    }
+
+    /// List a skill's reference files without blocking the runtime.
+    pub async fn list_references_async(self: &Arc<Self>, name: &str) -> Vec<ReferenceFile> {
+        let name = name.to_string();
+        self.run_blocking(move |indexer| indexer.list_references(&name))
+            .await
+            .unwrap_or_default()
+    }
+
     // ========================================================================
     // Incremental Index Updates
     // ========================================================================
@@ -165,22 +559,30 @@ impl SkillIndexer {
     ///
     /// This is more efficient than `reload()` when only one skill has changed.
     pub fn update_skill(&self, name: &str) -> Result<(), IndexError> {
-        let skill_dir = self.skills_dir.join(name);
+        for root in self.roots.read().iter() {
+            self.content_cache.invalidate(&root.path.join(name));
+        }
+        if let Some(segment) = reserved_segment(name).filter(|_| self.index_config.read().names.reject_reserved) {
+            warn!(
+                "Skill '{}' uses '{}', a reserved name on Windows; leaving it out of the index (set [index.names] reject_reserved = false to keep it)",
+                name, segment
+            );
+            return self.remove_skill(name);
+        }
 
-        // Check if skill directory exists
-        if !skill_dir.is_dir() {
+        // Check if skill directory exists in any root
+        let Some((root, skill_dir)) = self.find_skill_dir(name) else {
             // Skill was deleted, remove it from index
             return self.remove_skill(name);
-        }
+        };
 
         // Load the skill metadata
-        let meta_path = skill_dir.join("_meta.json");
-        if !meta_path.exists() {
-            debug!("Skill {} missing _meta.json, removing from index", name);
+        let Some(mut meta) = self.load_meta(&skill_dir)? else {
+            debug!("Skill {} has no _meta.json or frontmatter, removing from index", name);
             return self.remove_skill(name);
-        }
-
-        let meta = self.load_meta(&meta_path)?;
+        };
+        meta.root = Some(root.path.to_string_lossy().into_owned());
+        self.enrich_meta(&mut meta, &skill_dir);
 
         // Validate metadata
         if let Err(validation_errors) = validate_meta(&meta) {
@@ -190,75 +592,16 @@ impl SkillIndexer {
         }
 
         // Build content entries for this skill
-        let mut content_entries = Vec::new();
-
-        // Index main SKILL.md
-        let skill_md = skill_dir.join("SKILL.md");
-        if skill_md.exists() {
-            if let Ok(content) = fs::read_to_string(&skill_md) {
-                content_entries.push(ContentIndexEntry::new(
-                    name.to_string(),
-                    None,
-                    "SKILL.md".to_string(),
-                    content,
-                ));
-            }
-        }
-
-        // Index sub-skills
-        if let Some(ref sub_skills) = meta.sub_skills {
-            for sub in sub_skills {
-                let sub_path = skill_dir.join(&sub.file);
-                if sub_path.exists() {
-                    if let Ok(content) = fs::read_to_string(&sub_path) {
-                        content_entries.push(ContentIndexEntry::new(
-                            name.to_string(),
-                            Some(sub.name.clone()),
-                            sub.file.clone(),
-                            content,
-                        ));
-                    }
-                }
-            }
-        }
-
-        // Index references directory if present
-        let refs_dir = skill_dir.join("references");
-        if refs_dir.is_dir() {
-            for entry in WalkDir::new(&refs_dir)
-                .follow_links(true)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let path = entry.path();
-                if !path.is_file() {
-                    continue;
-                }
-
-                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-                if ext != "md" && ext != "markdown" {
-                    continue;
-                }
-
-                if let Ok(content) = fs::read_to_string(path) {
-                    let relative = path.strip_prefix(&skill_dir).unwrap_or(path);
-                    content_entries.push(ContentIndexEntry::new(
-                        name.to_string(),
-                        None,
-                        relative.to_string_lossy().to_string(),
-                        content,
-                    ));
-                }
-            }
-        }
+        let (content_entries, warnings) = self.index_skill_files(&meta, &skill_dir);
 
         // Atomically update the index
-        {
+        let existed = {
             let mut index = self.index.write();
+            let existed = index.skill_index.find(name).is_some();
 
             // Remove old entries for this skill
             index.skill_index.skills.retain(|s| s.name != name);
-            index.content_index.entries.retain(|_key, entry| entry.domain != name);
+            index.content_index.remove_domain(name);
 
             // Add updated entries
             index.skill_index.skills.push(meta);
@@ -267,9 +610,18 @@ impl SkillIndexer {
             for entry in content_entries {
                 index.content_index.insert(entry);
             }
-        }
+            index.content_index.warnings.extend(warnings);
+            index.generation += 1;
+            existed
+        };
 
         debug!("Incrementally updated skill: {}", name);
+        let name = name.to_string();
+        self.events.publish(if existed {
+            SkillEventKind::Updated { name }
+        } else {
+            SkillEventKind::Created { name }
+        });
         Ok(())
     }
 
@@ -284,16 +636,27 @@ impl SkillIndexer {
         index.skill_index.skills.retain(|s| s.name != name);
 
         // Remove content entries
-        index.content_index.entries.retain(|_key, entry| entry.domain != name);
+        index.content_index.remove_domain(name);
 
         let removed_skills = before_skills - index.skill_index.skills.len();
         let removed_content = before_content - index.content_index.entries.len();
+        if removed_skills + removed_content > 0 {
+            index.generation += 1;
+        }
+
+        drop(index);
 
         debug!(
             "Removed skill {} from index ({} skills, {} content entries removed)",
             name, removed_skills, removed_content
         );
 
+        if removed_skills > 0 {
+            self.events.publish(SkillEventKind::Deleted {
+                name: name.to_string(),
+            });
+        }
+
         Ok(())
     }
 
@@ -301,23 +664,34 @@ impl SkillIndexer {
     ///
     /// Returns the skill name if the path is within a skill directory.
     pub fn skill_from_path(&self, path: &Path) -> Option<String> {
-        // Try to get the path relative to skills_dir
-        let relative = path.strip_prefix(&self.skills_dir).ok()?;
-
-        // The first component should be the skill name
-        let skill_name = relative.components().next()?;
-
-        match skill_name {
-            std::path::Component::Normal(name) => {
-                let name_str = name.to_str()?;
-                // Skip hidden directories
-                if name_str.starts_with('.') || name_str.starts_with('_') {
-                    return None;
+        // Try to get the path relative to one of the roots
+        let (root, relative) = self.roots.read().iter().find_map(|root| {
+            path.strip_prefix(&root.path)
+                .ok()
+                .map(|rel| (root.path.clone(), rel.to_path_buf()))
+        })?;
+
+        // The first component is the skill name, or its collection
+        let mut components = relative.components().map(|c| match c {
+            std::path::Component::Normal(name) => name.to_str(),
+            _ => None,
+        });
+        let first = components.next()??;
+
+        // Skip hidden directories
+        if first.starts_with('.') || first.starts_with('_') {
+            return None;
+        }
+
+        let first_dir = root.join(first);
+        if !is_skill_dir(self.store(), &first_dir) {
+            if let Some(Some(second)) = components.next() {
+                if self.store.is_dir(&first_dir.join(second)) && !second.starts_with('.') {
+                    return Some(format!("{}/{}", first, second));
                 }
-                Some(name_str.to_string())
             }
-            _ => None,
         }
+        Some(first.to_string())
     }
 
     /// Get metadata for a specific skill.
@@ -325,44 +699,70 @@ impl SkillIndexer {
         self.index.read().skill_index.find(name).cloned()
     }
 
-    /// Check if a skill exists.
+    /// Names of indexed skills close to `name`, for "did you mean" hints.
+    pub fn suggest_names(&self, name: &str, limit: usize) -> Vec<String> {
+        self.index.read().skill_index.suggestions(name, limit)
+    }
+
+    /// Check if a skill exists, by directory or alias.
     pub fn skill_exists(&self, name: &str) -> bool {
-        self.skills_dir.join(name).is_dir()
+        self.find_skill_dir(name).is_some() || self.index.read().skill_index.find(name).is_some()
     }
 
     /// Check if a skill has a references directory.
     pub fn has_references(&self, name: &str) -> bool {
-        self.skills_dir.join(name).join("references").is_dir()
+        self.store.is_dir(&self.skill_dir(name).join("references"))
     }
 
     /// Read main SKILL.md content for a skill.
     pub fn read_skill_content(&self, name: &str) -> Result<SkillContent, IndexError> {
-        let skill_dir = self.skills_dir.join(name);
-        let skill_md = skill_dir.join("SKILL.md");
+        self.read_skill_content_in(name, None)
+    }
+
+    /// Read a skill's content in a language, falling back to SKILL.md when
+    /// it has no translation in that language.
+    pub fn read_skill_content_in(&self, name: &str, lang: Option<&str>) -> Result<SkillContent, IndexError> {
+        let meta = self.get_skill_meta(name);
+        // Aliases read the skill under its real name
+        let name = meta.as_ref().map_or(name, |m| m.name.as_str());
+        let skill_dir = self.skill_dir(name);
+        let translation = meta
+            .as_ref()
+            .zip(lang)
+            .and_then(|(m, lang)| m.translation_for(lang))
+            .map(str::to_string);
+        let skill_md = match &translation {
+            Some(lang) => skill_dir.join(translation_file(lang)),
+            None => skill_dir.join("SKILL.md"),
+        };
 
-        if !skill_md.exists() {
+        if !self.store.is_file(&skill_md) {
             return Err(IndexError::NotFound(format!(
                 "SKILL.md not found for '{}'",
                 name
             )));
         }
 
-        let content = fs::read_to_string(&skill_md).map_err(|e| {
+        let content = self.content_cache.read_to_string(self.store(), &skill_md).map_err(|e| {
             IndexError::ReadError(format!("Failed to read {}: {}", skill_md.display(), e))
         })?;
 
-        let meta = self.get_skill_meta(name);
         let sub_skills = meta
             .as_ref()
             .and_then(|m| m.sub_skills.as_ref())
             .map(|subs| subs.iter().map(|s| s.name.clone()).collect())
             .unwrap_or_default();
+        let depends_on = meta.as_ref().map(|m| m.depends_on.clone()).unwrap_or_default();
 
         let has_references = self.has_references(name);
 
+        let language = translation.or_else(|| meta.as_ref().and_then(|m| m.language.clone()));
+
         Ok(SkillContent::new(name.to_string(), content)
+            .with_language(language)
             .with_sub_skills(sub_skills)
-            .with_references(has_references))
+            .with_references(has_references)
+            .with_depends_on(depends_on))
     }
 
     /// Read sub-skill content.
@@ -383,77 +783,115 @@ impl SkillIndexer {
         })?;
 
         // Validate that the sub-skill file path doesn't escape the skill directory
-        let skill_dir = self.skills_dir.join(domain);
-        let file_path = validate_sub_skill_path(&skill_dir, &sub_meta.file)?;
+        let skill_dir = self.skill_dir(domain);
+        let file_path = validate_sub_skill_path(self.store(), &skill_dir, &sub_meta.file)?;
 
-        let content = fs::read_to_string(&file_path).map_err(|e| {
+        let content = self.content_cache.read_to_string(self.store(), &file_path).map_err(|e| {
             IndexError::ReadError(format!("Failed to read {}: {}", file_path.display(), e))
         })?;
 
         Ok(SubSkillContent::new(
-            domain.to_string(),
+            meta.name.clone(),
             sub_skill.to_string(),
             content,
         ))
     }
 
-    /// Build the skill metadata index by scanning directories.
-    fn build_skill_index(&self) -> Result<SkillIndex, IndexError> {
-        let mut skills = Vec::new();
+    /// Build the skill metadata index by scanning every root.
+    fn build_skill_index(&self, progress: &ReloadProgress) -> Result<SkillIndex, IndexError> {
+        let mut skills: Vec<SkillMeta> = Vec::new();
         let mut errors = Vec::new();
+        let mut shadowed = Vec::new();
+        let names = self.index_config.read().names.clone();
 
-        if !self.skills_dir.exists() {
+        if self.store.metadata(&self.skills_dir).is_err() {
             return Err(IndexError::NotFound(format!(
                 "Skills directory not found: {:?}",
                 self.skills_dir
             )));
         }
 
-        // Read each subdirectory as a potential skill
-        let entries = fs::read_dir(&self.skills_dir).map_err(|e| {
-            IndexError::ReadError(format!(
-                "Failed to read skills directory {:?}: {}",
-                self.skills_dir, e
-            ))
-        })?;
-
-        for entry in entries.flatten() {
-            let path = entry.path();
+        for (i, root) in self.roots().iter().enumerate() {
+            let root_name = root.path.to_string_lossy().into_owned();
 
-            // Skip non-directories and hidden files
-            if !path.is_dir() {
-                continue;
-            }
-
-            let name = path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or_default();
-
-            if name.starts_with('.') || name.starts_with('_') {
-                continue;
-            }
+            // Read each subdirectory (or collection member) as a potential skill
+            let entries = match skill_dirs_in(self.store(), &root.path) {
+                Ok(entries) => entries,
+                // Only the primary root is required to be readable
+                Err(e) if i > 0 => {
+                    errors.push(format!("Skipping skills root {:?}: {}", root.path, e));
+                    progress.sync_errors(&errors);
+                    continue;
+                }
+                Err(e) => {
+                    return Err(IndexError::ReadError(format!(
+                        "Failed to read skills directory {:?}: {}",
+                        root.path, e
+                    )));
+                }
+            };
+            progress.add_total(entries.len());
+
+            for (name, path) in &entries {
+                let name = name.as_str();
+
+                if let Some(segment) = reserved_segment(name).filter(|_| names.reject_reserved) {
+                    warn!(
+                        "Skipping skill '{}': '{}' is a reserved name on Windows (set [index.names] reject_reserved = false to keep it)",
+                        name, segment
+                    );
+                    errors.push(format!("{}: '{}' is a reserved name on Windows, skipped", name, segment));
+                    progress.scanned(&errors);
+                    continue;
+                }
 
-            // Try to load _meta.json
-            let meta_path = path.join("_meta.json");
-            if !meta_path.exists() {
-                errors.push(format!("{}: Missing _meta.json", name));
-                continue;
-            }
+                // Higher-priority roots win name collisions; within a root,
+                // the first of two names differing only in case wins
+                if let Some(winner) = skills.iter().find(|s| names.same(&s.name, name)) {
+                    if winner.root.as_deref() == Some(root_name.as_str()) {
+                        errors.push(format!(
+                            "{}: Name collides with '{}' when case is ignored, skipped",
+                            name, winner.name
+                        ));
+                    } else {
+                        shadowed.push(ShadowedSkill {
+                            name: name.to_string(),
+                            root: root_name.clone(),
+                            shadowed_by: winner.root.clone().unwrap_or_default(),
+                        });
+                    }
+                    progress.scanned(&errors);
+                    continue;
+                }
 
-            match self.load_meta(&meta_path) {
-                Ok(meta) => {
-                    // Validate the metadata
-                    if let Err(validation_errors) = validate_meta(&meta) {
-                        for err in validation_errors {
-                            errors.push(format!("{}: {}", name, err));
+                // Load _meta.json and/or SKILL.md frontmatter
+                match self.load_meta(path) {
+                    Ok(None) => {
+                        errors.push(format!("{}: Missing _meta.json or SKILL.md frontmatter", name));
+                    }
+                    Ok(Some(mut meta)) => {
+                        // Skills inside a collection are named `collection/skill`
+                        if let Some((collection, _)) = name.split_once('/') {
+                            if !meta.name.contains('/') {
+                                meta.name = format!("{}/{}", collection, meta.name);
+                            }
+                        }
+                        meta.root = Some(root_name.clone());
+                        self.enrich_meta(&mut meta, path);
+
+                        // Validate the metadata
+                        if let Err(validation_errors) = validate_meta(&meta) {
+                            for err in validation_errors {
+                                errors.push(format!("{}: {}", name, err));
+                            }
                         }
+                        skills.push(meta);
+                    }
+                    Err(e) => {
+                        errors.push(format!("{}: {}", name, e));
                     }
-                    skills.push(meta);
-                }
-                Err(e) => {
-                    errors.push(format!("{}: {}", name, e));
                 }
+                progress.scanned(&errors);
             }
         }
 
@@ -462,7 +900,10 @@ impl SkillIndexer {
 
         debug!("Built skill index: {} skills, {} errors", skills.len(), errors.len());
 
-        Ok(SkillIndex::with_skills(skills, errors))
+        let mut index = SkillIndex::with_skills(skills, errors);
+        index.shadowed = shadowed;
+        index.case_insensitive = names.case.folds();
+        Ok(index)
     }
 
     /// Build the content index for full-text search.
@@ -470,92 +911,356 @@ impl SkillIndexer {
         let mut content_index = ContentIndex::new();
 
         for skill in &skill_index.skills {
-            // Index main SKILL.md
-            let skill_md = self.skills_dir.join(&skill.name).join("SKILL.md");
-            if skill_md.exists() {
-                if let Ok(content) = fs::read_to_string(&skill_md) {
-                    content_index.insert(ContentIndexEntry::new(
-                        skill.name.clone(),
-                        None,
-                        "SKILL.md".to_string(),
-                        content,
-                    ));
-                }
+            let skill_dir = match &skill.root {
+                Some(root) => Path::new(root).join(&skill.name),
+                None => self.skills_dir.join(&skill.name),
+            };
+
+            let (entries, warnings) = self.index_skill_files(skill, &skill_dir);
+            for entry in entries {
+                content_index.insert(entry);
             }
+            content_index.warnings.extend(warnings);
+        }
 
-            // Index sub-skills
-            if let Some(sub_skills) = &skill.sub_skills {
-                for sub in sub_skills {
-                    let sub_path = self.skills_dir.join(&skill.name).join(&sub.file);
-                    if sub_path.exists() {
-                        if let Ok(content) = fs::read_to_string(&sub_path) {
-                            content_index.insert(ContentIndexEntry::new(
-                                skill.name.clone(),
-                                Some(sub.name.clone()),
-                                sub.file.clone(),
-                                content,
-                            ));
-                        }
-                    }
-                }
+        debug!(
+            "Built content index: {} entries, {} warnings",
+            content_index.len(),
+            content_index.warnings.len()
+        );
+
+        Ok(content_index)
+    }
+
+    /// Content entries for a skill's SKILL.md, sub-skills, and references,
+    /// with a warning for each file indexed incompletely or not at all.
+    fn index_skill_files(
+        &self,
+        meta: &SkillMeta,
+        skill_dir: &Path,
+    ) -> (Vec<ContentIndexEntry>, Vec<IndexWarning>) {
+        let mut files = FileIndexer {
+            store: self.store(),
+            skill: &meta.name,
+            config: self.index_config(),
+            entries: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        // Index main SKILL.md
+        let skill_md = skill_dir.join("SKILL.md");
+        if self.store.is_file(&skill_md) {
+            files.index(&skill_md, None, "SKILL.md".to_string());
+        }
+
+        // Index translations of SKILL.md
+        for lang in &meta.translations {
+            let file = translation_file(lang);
+            files.index(&skill_dir.join(&file), None, file);
+        }
+
+        // Index sub-skills
+        for sub in meta.sub_skills.iter().flatten() {
+            let sub_path = skill_dir.join(&sub.file);
+            if self.store.metadata(&sub_path).is_ok() {
+                files.index(&sub_path, Some(sub.name.clone()), sub.file.clone());
             }
+        }
 
-            // Index references directory if present
-            let refs_dir = self.skills_dir.join(&skill.name).join("references");
-            if refs_dir.is_dir() {
-                self.index_directory(&mut content_index, &skill.name, &refs_dir);
+        // Index references directory if present
+        let refs_dir = skill_dir.join("references");
+        if self.store.is_dir(&refs_dir) {
+            let walk = self.store.walk(&refs_dir, &self.skill_boundary(meta), &files.config);
+            let relative = |path: &Path| path.strip_prefix(skill_dir).unwrap_or(path).to_string_lossy().to_string();
+            for (path, reason) in walk.skipped {
+                files.warn(relative(&path), reason);
+            }
+            for path in walk.files {
+                match files.config.skip_reason(self.store(), &path) {
+                    None => files.index(&path, None, relative(&path)),
+                    Some(SkipReason::Extension) => {}
+                    Some(SkipReason::Binary) => files.warn(relative(&path), "Binary file not indexed".to_string()),
+                }
             }
         }
 
-        debug!("Built content index: {} entries", content_index.len());
+        for file in orphaned_files(self.store(), meta, skill_dir) {
+            files.warn(file, "Orphaned: not SKILL.md, a sub-skill, or under references/".to_string());
+        }
 
-        Ok(content_index)
+        (files.entries, files.warnings)
     }
 
-    /// Index all markdown files in a directory.
-    fn index_directory(&self, index: &mut ContentIndex, domain: &str, dir: &Path) {
-        for entry in WalkDir::new(dir)
-            .follow_links(true)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
+    /// Files in a skill's references directory, or an empty list if it has
+    /// none.
+    pub fn list_references(&self, name: &str) -> Vec<ReferenceFile> {
+        let Some(meta) = self.get_skill_meta(name) else {
+            return Vec::new();
+        };
+        let refs_dir = self.skill_dir(&meta.name).join("references");
+        if !self.store.is_dir(&refs_dir) {
+            return Vec::new();
+        }
+        list_references(self.store(), &refs_dir, &self.skill_boundary(&meta), &self.index_config())
+    }
 
-            if !path.is_file() {
-                continue;
+    /// Directory that internal symlinks in a skill must stay inside: the
+    /// root it was loaded from.
+    fn skill_boundary(&self, meta: &SkillMeta) -> PathBuf {
+        meta.root
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.skills_dir.clone())
+    }
+
+    /// Fill derived metadata fields from the skill's files.
+    fn enrich_meta(&self, meta: &mut SkillMeta, skill_dir: &Path) {
+        self.index_config.read().tags.apply(&mut meta.tags);
+
+        // Metadata written on Windows may use `\` in file paths
+        for sub in meta.sub_skills.iter_mut().flatten() {
+            if sub.file.contains('\\') {
+                sub.file = normalize_separators(&sub.file);
             }
+        }
 
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            if ext != "md" && ext != "markdown" {
-                continue;
+        let times = super::times::skill_times(self.store(), skill_dir);
+        meta.created_at = times.map(|(created, _)| created);
+        meta.updated_at = times.map(|(_, updated)| updated);
+
+        if meta.quick_answer.is_none() {
+            if let Ok(content) = self.store.read_lossy(&skill_dir.join("SKILL.md")) {
+                meta.quick_answer = markdown::quick_answer(markdown::strip_frontmatter(&content));
             }
+        }
 
-            if let Ok(content) = fs::read_to_string(path) {
-                let relative = path
-                    .strip_prefix(&self.skills_dir.join(domain))
-                    .unwrap_or(path);
+        meta.translations = self
+            .store
+            .list_dir(skill_dir)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?;
+                let lang = name.strip_prefix("SKILL.")?.strip_suffix(".md")?;
+                (markdown::is_language_tag(lang) && self.store.is_file(path)).then(|| lang.to_string())
+            })
+            .collect();
+        meta.translations.sort();
+
+        if self.index_config.read().register_orphans {
+            let mut registered: Vec<SubSkillMeta> = Vec::new();
+            for file in orphaned_files(self.store(), meta, skill_dir) {
+                let Some(stem) = file.strip_suffix(".md").filter(|stem| !stem.contains('/')) else {
+                    continue;
+                };
+                let name = crate::lifecycle::normalize_name(stem);
+                let taken = meta.find_sub_skill(&name).is_some() || registered.iter().any(|s| s.name == name);
+                if !name.is_empty() && !taken {
+                    registered.push(SubSkillMeta {
+                        name,
+                        file,
+                        triggers: Vec::new(),
+                        language: None,
+                    });
+                }
+            }
+            if !registered.is_empty() {
+                meta.sub_skills.get_or_insert_with(Vec::new).extend(registered);
+            }
+        }
 
-                index.insert(ContentIndexEntry::new(
-                    domain.to_string(),
-                    None,
-                    relative.to_string_lossy().to_string(),
-                    content,
-                ));
+        if meta.language.is_none() {
+            if let Ok(content) = self.store.read_lossy(&skill_dir.join("SKILL.md")) {
+                meta.language = markdown::detect_language(&content);
+            }
+        }
+        for sub in meta.sub_skills.iter_mut().flatten() {
+            if sub.language.is_none() {
+                if let Ok(content) = self.store.read_lossy(&skill_dir.join(&sub.file)) {
+                    sub.language = markdown::detect_language(&content);
+                }
             }
         }
     }
 
-    /// Load and parse _meta.json file.
-    fn load_meta(&self, path: &Path) -> Result<SkillMeta, IndexError> {
-        let content = fs::read_to_string(path)
+    /// Load skill metadata from `_meta.json` and SKILL.md frontmatter.
+    ///
+    /// Returns None when the skill has neither. A missing `name` defaults to
+    /// the directory name.
+    fn load_meta(&self, skill_dir: &Path) -> Result<Option<SkillMeta>, IndexError> {
+        let json = self.load_meta_json(&skill_dir.join("_meta.json"))?;
+        let frontmatter = self
+            .store
+            .read_lossy(&skill_dir.join("SKILL.md"))
+            .ok()
+            .and_then(|content| markdown::parse_frontmatter(&content));
+
+        if json.is_none() && frontmatter.is_none() {
+            return Ok(None);
+        }
+
+        let (base, overlay) = match self.meta_precedence() {
+            MetaPrecedence::MetaJson => (frontmatter, json),
+            MetaPrecedence::Frontmatter => (json, frontmatter),
+        };
+        let mut fields = base.unwrap_or_default();
+        fields.extend(overlay.unwrap_or_default());
+
+        if !fields.contains_key("name") {
+            let dir_name = skill_dir.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            fields.insert("name".to_string(), Value::String(dir_name.to_string()));
+        }
+
+        serde_json::from_value(Value::Object(fields)).map(Some).map_err(|e| {
+            IndexError::ParseError(format!("Invalid metadata in {:?}: {}", skill_dir, e))
+        })
+    }
+
+    /// Load and parse a _meta.json file, if present.
+    fn load_meta_json(&self, path: &Path) -> Result<Option<Map<String, Value>>, IndexError> {
+        if !self.store.is_file(path) {
+            return Ok(None);
+        }
+
+        let content = self
+            .store
+            .read(path)
+            .and_then(|bytes| String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
             .map_err(|e| IndexError::ReadError(format!("Failed to read {:?}: {}", path, e)))?;
 
-        serde_json::from_str(&content).map_err(|e| {
+        serde_json::from_str(&content).map(Some).map_err(|e| {
             IndexError::ParseError(format!("Failed to parse {:?}: {}", path, e))
         })
     }
 }
 
+/// Whether a directory holds a skill (has `_meta.json` or `SKILL.md`).
+/// Collects one skill's content entries and indexing warnings.
+struct FileIndexer<'a> {
+    store: &'a dyn SkillStore,
+    skill: &'a str,
+    config: IndexConfig,
+    entries: Vec<ContentIndexEntry>,
+    warnings: Vec<IndexWarning>,
+}
+
+impl FileIndexer<'_> {
+    /// Index a file, noting anything lost reading it.
+    fn index(&mut self, path: &Path, sub_skill: Option<String>, file: String) {
+        match read_indexed_text(self.store, path, self.config.max_file_bytes) {
+            Ok(text) => {
+                for warning in text.warnings {
+                    self.warn(file.clone(), warning);
+                }
+                self.entries.push(ContentIndexEntry::new(
+                    self.skill.to_string(),
+                    sub_skill,
+                    file,
+                    text.content,
+                ));
+            }
+            Err(e) => self.warn(file, format!("Not indexed: {}", e)),
+        }
+    }
+
+    fn warn(&mut self, file: String, message: String) {
+        debug!("Indexing {}/{}: {}", self.skill, file, message);
+        self.warnings.push(IndexWarning {
+            skill: self.skill.to_string(),
+            file,
+            message,
+        });
+    }
+}
+
+/// Files in a skill directory that aren't SKILL.md, its metadata or
+/// translations, a declared sub-skill, or under `references/` or
+/// `scripts/`, relative to the directory with `/` separators.
+///
+/// Hidden files and directories are left out.
+pub fn orphaned_files(store: &dyn SkillStore, meta: &SkillMeta, skill_dir: &Path) -> Vec<String> {
+    let declared: Vec<&str> = meta
+        .sub_skills
+        .iter()
+        .flatten()
+        .map(|s| s.file.trim_start_matches("./"))
+        .collect();
+    store
+        .visible_files(skill_dir)
+        .iter()
+        .filter_map(|path| path.strip_prefix(skill_dir).ok())
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .filter(|file| {
+            !matches!(file.as_str(), "SKILL.md" | "_meta.json")
+                && !file.starts_with("references/")
+                && !file
+                    .strip_prefix(SCRIPTS_DIR)
+                    .is_some_and(|rest| rest.starts_with('/'))
+                && !declared.contains(&file.as_str())
+                && !file
+                    .strip_prefix("SKILL.")
+                    .and_then(|rest| rest.strip_suffix(".md"))
+                    .is_some_and(markdown::is_language_tag)
+        })
+        .collect()
+}
+
+/// File name of a skill's translation into `lang`.
+fn translation_file(lang: &str) -> String {
+    format!("SKILL.{}.md", lang)
+}
+
+fn is_skill_dir(store: &dyn SkillStore, path: &Path) -> bool {
+    store.is_file(&path.join("_meta.json")) || store.is_file(&path.join("SKILL.md"))
+}
+
+/// Visible subdirectories of `dir`, as (name, path) pairs.
+fn subdirectories(store: &dyn SkillStore, dir: &Path) -> std::io::Result<Vec<(String, PathBuf)>> {
+    let mut dirs = Vec::new();
+    for path in store.list_dir(dir)? {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+            continue;
+        };
+        if !name.starts_with('.') && !name.starts_with('_') && store.is_dir(&path) {
+            dirs.push((name, path));
+        }
+    }
+    // Directory order varies by platform; collisions are settled by name
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Candidate skill directories in a root, as (name, path) pairs.
+///
+/// A directory without skill files whose subdirectories hold skills is a
+/// collection; its skills are named `collection/skill`. Collections are one
+/// level deep.
+pub(crate) fn skill_dirs(root: &Path) -> std::io::Result<Vec<(String, PathBuf)>> {
+    skill_dirs_in(&LocalStore, root)
+}
+
+/// Candidate skill directories in a root of a store; see [`skill_dirs`].
+fn skill_dirs_in(store: &dyn SkillStore, root: &Path) -> std::io::Result<Vec<(String, PathBuf)>> {
+    let mut skills = Vec::new();
+    for (name, path) in subdirectories(store, root)? {
+        if !is_skill_dir(store, &path) {
+            let members: Vec<_> = subdirectories(store, &path)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, p)| is_skill_dir(store, p))
+                .collect();
+            if !members.is_empty() {
+                for (member, member_path) in members {
+                    skills.push((format!("{}/{}", name, member), member_path));
+                }
+                continue;
+            }
+        }
+        skills.push((name, path));
+    }
+    Ok(skills)
+}
+
 /// Errors that can occur during indexing.
 #[derive(Debug, thiserror::Error)]
 pub enum IndexError {
@@ -576,9 +1281,23 @@ pub enum IndexError {
     ValidationError(String),
 }
 
+impl IndexError {
+    /// Machine-readable code of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            IndexError::NotFound(_) => ErrorCode::NotFound,
+            IndexError::ReadError(_) => ErrorCode::IoError,
+            IndexError::ParseError(_) => ErrorCode::MetaParseError,
+            IndexError::ValidationError(_) => ErrorCode::ValidationFailed,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::index::{CaseFolding, NameConfig};
+    use crate::test_support::write_skill;
     use std::fs;
     use tempfile::TempDir;
 
@@ -604,7 +1323,9 @@ mod tests {
         create_test_skill(temp_dir.path(), "test-skill", "A test skill");
 
         let indexer = SkillIndexer::new(temp_dir.path());
+        assert!(indexer.built_at().is_none());
         indexer.reload().unwrap();
+        assert!(indexer.built_at().is_some());
 
         let index = indexer.get_skill_index();
         assert_eq!(index.len(), 1);
@@ -624,6 +1345,145 @@ mod tests {
         assert!(content.content.contains("Form handling patterns"));
     }
 
+    #[test]
+    fn test_content_cache_invalidation() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        indexer.read_skill_content("forms").unwrap();
+        indexer.read_skill_content("forms").unwrap();
+        let stats = indexer.content_cache().stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+
+        // Watcher updates and reloads drop cached contents
+        indexer.update_skill("forms").unwrap();
+        assert_eq!(indexer.content_cache().stats().entries, 0);
+        indexer.read_skill_content("forms").unwrap();
+        indexer.reload().unwrap();
+        assert_eq!(indexer.content_cache().stats().entries, 0);
+
+        indexer.set_index_config(IndexConfig {
+            content_cache: 0,
+            ..Default::default()
+        });
+        indexer.read_skill_content("forms").unwrap();
+        assert_eq!(indexer.content_cache().stats().entries, 0);
+    }
+
+    #[test]
+    fn test_case_insensitive_names() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+        create_test_skill(temp_dir.path(), "tables", "Table layouts");
+        // A second copy differing in case, as a Linux checkout can hold
+        write_skill(temp_dir.path(), "Tables", r#"{"name": "Tables", "description": "Table layouts"}"#, "# Tables\n");
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.set_index_config(IndexConfig {
+            names: NameConfig {
+                case: CaseFolding::Insensitive,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        indexer.reload().unwrap();
+        let index = indexer.get_skill_index();
+        let names: Vec<&str> = index.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Tables", "forms"]);
+        assert!(index
+            .validation_errors
+            .contains(&"tables: Name collides with 'Tables' when case is ignored, skipped".to_string()));
+        assert_eq!(indexer.get_skill_meta("FORMS").unwrap().name, "forms");
+        assert!(indexer.skill_dir("Forms").ends_with("forms"));
+
+        indexer.set_index_config(IndexConfig {
+            names: NameConfig {
+                case: CaseFolding::Sensitive,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        indexer.reload().unwrap();
+        assert_eq!(indexer.get_skill_index().skills.len(), 3);
+        assert!(indexer.get_skill_meta("FORMS").is_none());
+    }
+
+    #[test]
+    fn test_reserved_names() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+        create_test_skill(temp_dir.path(), "con", "Console tricks");
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        let index = indexer.get_skill_index();
+        assert!(index.find("con").is_none());
+        assert!(index
+            .validation_errors
+            .contains(&"con: 'con' is a reserved name on Windows, skipped".to_string()));
+        indexer.update_skill("con").unwrap();
+        assert!(indexer.get_skill_meta("con").is_none());
+
+        indexer.set_index_config(IndexConfig {
+            names: NameConfig {
+                reject_reserved: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        indexer.reload().unwrap();
+        let index = indexer.get_skill_index();
+        assert!(index.find("con").is_some());
+        assert!(index
+            .validation_errors
+            .contains(&"con: name: 'con' is a reserved name on Windows".to_string()));
+    }
+
+    #[test]
+    fn test_windows_separators() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("forms");
+        fs::create_dir_all(skill_dir.join("references")).unwrap();
+        fs::write(
+            skill_dir.join("_meta.json"),
+            r#"{"name": "forms", "description": "Forms", "sub_skills": [{"name": "guide", "file": "references\\guide.md"}]}"#,
+        )
+        .unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Forms\n").unwrap();
+        fs::write(skill_dir.join("references/guide.md"), "# Guide\n").unwrap();
+        let frontend = temp_dir.path().join("frontend/tables");
+        fs::create_dir_all(&frontend).unwrap();
+        fs::write(frontend.join("_meta.json"), r#"{"name": "tables", "description": "Tables"}"#).unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        let meta = indexer.get_skill_meta("forms").unwrap();
+        assert_eq!(meta.sub_skills.unwrap()[0].file, "references/guide.md");
+        assert!(indexer.read_sub_skill_content("forms", "guide").is_ok());
+        assert_eq!(indexer.get_skill_meta("frontend\\tables").unwrap().name, "frontend/tables");
+    }
+
+    #[test]
+    fn test_generation() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        assert_eq!(indexer.generation(), 0);
+        indexer.reload().unwrap();
+        assert_eq!(indexer.generation(), 1);
+        indexer.update_skill("forms").unwrap();
+        assert_eq!(indexer.generation(), 2);
+        indexer.remove_skill("forms").unwrap();
+        assert_eq!(indexer.generation(), 3);
+
+        // Removing a skill that isn't indexed changes nothing
+        indexer.remove_skill("forms").unwrap();
+        assert_eq!(indexer.generation(), 3);
+    }
+
     #[test]
     fn test_missing_skill() {
         let temp_dir = TempDir::new().unwrap();
@@ -633,4 +1493,305 @@ mod tests {
         let result = indexer.read_skill_content("nonexistent");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_frontmatter_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Frontmatter only
+        let pdf_dir = temp_dir.path().join("pdf");
+        fs::create_dir_all(&pdf_dir).unwrap();
+        fs::write(
+            pdf_dir.join("SKILL.md"),
+            "---\nname: pdf\ndescription: PDF tools\ntags: [pdf]\n---\n# PDF\n\n## TL;DR\n\nUse pypdf.\n",
+        )
+        .unwrap();
+
+        // Both sources, disagreeing on description
+        create_test_skill(temp_dir.path(), "forms", "From meta");
+        fs::write(
+            temp_dir.path().join("forms/SKILL.md"),
+            "---\ndescription: From frontmatter\ntags: [input]\n---\n# Forms\n",
+        )
+        .unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+
+        let pdf = indexer.get_skill_meta("pdf").unwrap();
+        assert_eq!(pdf.description, "PDF tools");
+        assert_eq!(pdf.tags, vec!["pdf"]);
+        assert_eq!(pdf.quick_answer.as_deref(), Some("Use pypdf."));
+
+        let forms = indexer.get_skill_meta("forms").unwrap();
+        assert_eq!(forms.description, "From meta");
+        assert_eq!(forms.tags, vec!["input"]);
+
+        indexer.set_meta_precedence(MetaPrecedence::Frontmatter);
+        indexer.reload().unwrap();
+        assert_eq!(indexer.get_skill_meta("forms").unwrap().description, "From frontmatter");
+    }
+
+    #[test]
+    fn test_multiple_roots() {
+        let primary = TempDir::new().unwrap();
+        let team = TempDir::new().unwrap();
+        create_test_skill(primary.path(), "forms", "Personal forms");
+        create_test_skill(team.path(), "forms", "Team forms");
+        create_test_skill(team.path(), "testing", "Team testing");
+
+        let indexer = SkillIndexer::with_roots(vec![
+            SkillRoot::new(primary.path()),
+            SkillRoot::read_only(team.path()),
+        ]);
+        indexer.reload().unwrap();
+
+        let index = indexer.get_skill_index();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.shadowed.len(), 1);
+        assert_eq!(index.shadowed[0].name, "forms");
+
+        let forms = indexer.get_skill_meta("forms").unwrap();
+        assert_eq!(forms.description, "Personal forms");
+        assert_eq!(forms.root.as_deref(), Some(primary.path().to_string_lossy().as_ref()));
+        assert!(!indexer.is_read_only("forms"));
+
+        assert!(indexer.is_read_only("testing"));
+        assert_eq!(indexer.skill_dir("testing"), team.path().join("testing"));
+        assert!(indexer.read_skill_content("testing").unwrap().content.contains("Team testing"));
+
+        let root: SkillRoot = "/srv/skills:ro".parse().unwrap();
+        assert_eq!(root.path, PathBuf::from("/srv/skills"));
+        assert!(root.read_only);
+    }
+
+    #[test]
+    fn test_collections() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "testing", "Test patterns");
+        create_test_skill(&temp_dir.path().join("frontend"), "forms", "Frontend forms");
+        create_test_skill(&temp_dir.path().join("backend"), "auth", "Backend auth");
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+
+        let index = indexer.get_skill_index();
+        let names: Vec<&str> = index.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["backend/auth", "frontend/forms", "testing"]);
+        assert!(!index.has_errors(), "{:?}", index.validation_errors);
+
+        let collections = index.collections();
+        assert_eq!(collections.len(), 2);
+        assert_eq!(collections[1].name, "frontend");
+        assert_eq!(collections[1].skills, vec!["frontend/forms"]);
+
+        let content = indexer.read_skill_content("frontend/forms").unwrap();
+        assert!(content.content.contains("Frontend forms"));
+        assert_eq!(
+            indexer.skill_from_path(&temp_dir.path().join("frontend/forms/SKILL.md")).as_deref(),
+            Some("frontend/forms")
+        );
+        assert_eq!(
+            indexer.skill_from_path(&temp_dir.path().join("testing/SKILL.md")).as_deref(),
+            Some("testing")
+        );
+    }
+
+    #[test]
+    fn test_language_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "formulare", "Formulare");
+        fs::write(
+            temp_dir.path().join("formulare/_meta.json"),
+            r#"{"name": "formulare", "description": "Formulare", "sub_skills": [{"name": "react", "file": "react.md"}]}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("formulare/SKILL.md"),
+            "# Formulare\n\nDiese Anleitung zeigt, wie man die Felder eines Formulars mit der Bibliothek prüft.",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("formulare/react.md"),
+            "# React\n\nUse this when the form is built with React and you need the hooks.",
+        )
+        .unwrap();
+
+        // A declared language wins over detection
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+        fs::write(
+            temp_dir.path().join("forms/_meta.json"),
+            r#"{"name": "forms", "description": "Form handling patterns", "language": "en-GB"}"#,
+        )
+        .unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+
+        let meta = indexer.get_skill_meta("formulare").unwrap();
+        assert_eq!(meta.language.as_deref(), Some("de"));
+        assert_eq!(meta.find_sub_skill("react").unwrap().language.as_deref(), Some("en"));
+        assert_eq!(indexer.get_skill_meta("forms").unwrap().language.as_deref(), Some("en-GB"));
+
+        let content = indexer.get_content_index();
+        assert_eq!(content.get("formulare").unwrap().language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn test_orphaned_files() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+        let skill_dir = temp_dir.path().join("forms");
+        fs::create_dir_all(skill_dir.join("references")).unwrap();
+        fs::create_dir_all(skill_dir.join("assets")).unwrap();
+        fs::write(skill_dir.join("references/api.md"), "# API").unwrap();
+        fs::write(skill_dir.join("SKILL.de.md"), "# Formulare").unwrap();
+        fs::write(skill_dir.join("Zod.md"), "# Zod\n\nSchemas.").unwrap();
+        fs::write(skill_dir.join("assets/logo.svg"), "<svg/>").unwrap();
+        fs::write(skill_dir.join(".notes.md"), "# Hidden").unwrap();
+        fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+        fs::write(skill_dir.join("scripts/check.py"), "print('ok')").unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        let meta = indexer.get_skill_meta("forms").unwrap();
+        assert_eq!(orphaned_files(&LocalStore, &meta, &skill_dir), vec!["Zod.md", "assets/logo.svg"]);
+        let warnings = indexer.get_content_index().warnings;
+        assert!(warnings.iter().any(|w| w.file == "Zod.md" && w.message.starts_with("Orphaned")));
+
+        // Registered, the markdown file is a sub-skill and no longer orphaned
+        indexer.set_index_config(IndexConfig {
+            register_orphans: true,
+            ..Default::default()
+        });
+        indexer.reload().unwrap();
+        let meta = indexer.get_skill_meta("forms").unwrap();
+        assert_eq!(meta.find_sub_skill("zod").unwrap().file, "Zod.md");
+        assert_eq!(indexer.read_sub_skill_content("forms", "zod").unwrap().content, "# Zod\n\nSchemas.");
+        let warnings = indexer.get_content_index().warnings;
+        assert_eq!(warnings.iter().filter(|w| w.message.starts_with("Orphaned")).count(), 1);
+    }
+
+    #[test]
+    fn test_translations() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+        let skill_dir = temp_dir.path().join("forms");
+        fs::write(skill_dir.join("SKILL.de.md"), "# Formulare\n\nFelder prüfen.").unwrap();
+        fs::write(skill_dir.join("SKILL.pt-BR.md"), "# Formulários").unwrap();
+        // Not language tags
+        fs::write(skill_dir.join("SKILL.draft.md"), "# Draft").unwrap();
+        fs::write(skill_dir.join("SKILL.EN.md"), "# Forms").unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+
+        let meta = indexer.get_skill_meta("forms").unwrap();
+        assert_eq!(meta.translations, vec!["de", "pt-BR"]);
+
+        let german = indexer.read_skill_content_in("forms", Some("de-AT")).unwrap();
+        assert_eq!(german.content, "# Formulare\n\nFelder prüfen.");
+        assert_eq!(german.language.as_deref(), Some("de"));
+        assert_eq!(indexer.read_skill_content_in("forms", Some("pt")).unwrap().content, "# Formulários");
+
+        // Without a matching translation, SKILL.md is served
+        let fallback = indexer.read_skill_content_in("forms", Some("fr")).unwrap();
+        assert_eq!(fallback.content, indexer.read_skill_content("forms").unwrap().content);
+        assert_eq!(fallback.language, meta.language);
+
+        let content = indexer.get_content_index();
+        assert!(content.get("forms/SKILL.de.md").is_some());
+        assert!(content.get("forms/SKILL.draft.md").is_none());
+    }
+
+    #[test]
+    fn test_malformed_and_large_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("forms");
+        fs::create_dir_all(skill_dir.join("references")).unwrap();
+        // Frontmatter-only skill whose body isn't valid UTF-8
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            b"---\nname: forms\ndescription: Form handling\n---\n# Forms \xff\n",
+        )
+        .unwrap();
+        fs::write(skill_dir.join("references/big.md"), "x".repeat(100)).unwrap();
+        fs::write(skill_dir.join("references/image.md"), b"\x89PNG\0\0").unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.set_index_config(IndexConfig {
+            max_file_bytes: 80,
+            ..Default::default()
+        });
+        indexer.reload().unwrap();
+
+        assert!(indexer.skill_exists("forms"));
+        let content = indexer.get_content_index();
+        assert!(content.get("forms").unwrap().content.contains("# forms \u{FFFD}"));
+        assert!(content
+            .get("forms/references/big.md")
+            .unwrap()
+            .content
+            .ends_with("[truncated: 80 of 100 bytes indexed]\n"));
+        assert!(content.get("forms/references/image.md").is_none());
+
+        let warnings: Vec<String> = content.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings.contains(&"forms: SKILL.md: Invalid UTF-8 replaced with U+FFFD".to_string()));
+        assert!(warnings.iter().any(|w| w.starts_with("forms: references/big.md: File is 100 bytes")));
+        assert!(warnings.iter().any(|w| w.ends_with("Binary file not indexed")));
+
+        // Fixing a file clears its warning on the next update
+        fs::remove_file(skill_dir.join("references/image.md")).unwrap();
+        indexer.update_skill("forms").unwrap();
+        assert_eq!(indexer.get_content_index().warnings.len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_reload_with_symlink_cycle() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+        let refs = temp_dir.path().join("forms/references");
+        fs::create_dir_all(&refs).unwrap();
+        fs::write(refs.join("api.md"), "# API").unwrap();
+        std::os::unix::fs::symlink(".", refs.join("self")).unwrap();
+        std::os::unix::fs::symlink("/", refs.join("root")).unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+
+        let content = indexer.get_content_index();
+        assert!(content.get("forms/references/api.md").is_some());
+        assert_eq!(content.len(), 2);
+        let warnings: Vec<String> = content.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "forms: references/root: Symlink points outside the skills directory",
+                "forms: references/self: Directory already walked (symlink cycle?)",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_api() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_skill(temp_dir.path(), "forms", "Form handling patterns");
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload_async().await.unwrap();
+        assert!(indexer.skill_exists("forms"));
+
+        let content = indexer.read_skill_content_async("forms").await.unwrap();
+        assert!(content.content.contains("Form handling patterns"));
+        assert!(matches!(
+            indexer.read_skill_content_async("missing").await,
+            Err(IndexError::NotFound(_))
+        ));
+
+        create_test_skill(temp_dir.path(), "tables", "Table layouts");
+        indexer.update_skill_async("tables").await.unwrap();
+        assert_eq!(indexer.get_skill_index().len(), 2);
+    }
 }
@@ -0,0 +1,283 @@
+//! Link graph built from markdown links in the content index.
+
+use std::collections::HashSet;
+use std::path::{Component, Path};
+
+use serde::Serialize;
+
+use crate::markdown::is_external_link;
+use crate::models::{ContentIndex, SkillIndex};
+
+/// An intra-library link from one skill file to another path.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillLink {
+    /// Skill containing the link.
+    pub source_skill: String,
+
+    /// File containing the link, relative to the source skill directory.
+    pub source_file: String,
+
+    /// Link target as written.
+    pub target: String,
+
+    /// Skill the target resolves into.
+    pub target_skill: String,
+
+    /// Target path relative to the target skill directory (empty for the
+    /// skill directory itself).
+    pub target_file: String,
+}
+
+/// Why a link is broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrokenLinkReason {
+    /// The target skill doesn't exist.
+    MissingSkill,
+    /// The target skill exists but the file doesn't.
+    MissingFile,
+    /// The target resolves outside the skills directory.
+    OutsideLibrary,
+}
+
+/// A link whose target doesn't exist.
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokenLink {
+    /// Skill containing the link.
+    pub source_skill: String,
+
+    /// File containing the link.
+    pub source_file: String,
+
+    /// Link target as written.
+    pub target: String,
+
+    /// Why the link is broken.
+    pub reason: BrokenLinkReason,
+}
+
+/// Intra-library links across all indexed skill files.
+pub struct LinkGraph {
+    links: Vec<SkillLink>,
+    broken: Vec<BrokenLink>,
+}
+
+impl LinkGraph {
+    /// Resolve links recorded in the content index.
+    ///
+    /// External links (URLs, email, same-page anchors) are ignored. Target
+    /// files are checked against the target skill's root, or `skills_dir`
+    /// when it has none.
+    pub fn new(skills_dir: &Path, skill_index: &SkillIndex, content_index: &ContentIndex) -> Self {
+        let skill_names: HashSet<&str> = skill_index.skills.iter().map(|s| s.name.as_str()).collect();
+
+        let mut entries: Vec<_> = content_index.entries.values().collect();
+        entries.sort_by(|a, b| (&a.domain, &a.file).cmp(&(&b.domain, &b.file)));
+
+        let mut links = Vec::new();
+        let mut broken = Vec::new();
+
+        for entry in entries {
+            for target in &entry.links {
+                if is_external_link(target) {
+                    continue;
+                }
+
+                let broken_link = |reason| BrokenLink {
+                    source_skill: entry.domain.clone(),
+                    source_file: entry.file.clone(),
+                    target: target.clone(),
+                    reason,
+                };
+
+                let Some(resolved) = resolve_link(&entry.domain, &entry.file, target) else {
+                    broken.push(broken_link(BrokenLinkReason::OutsideLibrary));
+                    continue;
+                };
+                let (target_skill, target_file) = split_target(&resolved, |name| skill_names.contains(name));
+
+                if !skill_names.contains(target_skill.as_str()) {
+                    broken.push(broken_link(BrokenLinkReason::MissingSkill));
+                    continue;
+                }
+                // Targets may live in a different skills root
+                let target_root = skill_index
+                    .find(&target_skill)
+                    .and_then(|s| s.root.as_deref())
+                    .map_or(skills_dir, Path::new);
+                if !target_root.join(&resolved).exists() {
+                    broken.push(broken_link(BrokenLinkReason::MissingFile));
+                    continue;
+                }
+
+                links.push(SkillLink {
+                    source_skill: entry.domain.clone(),
+                    source_file: entry.file.clone(),
+                    target: target.clone(),
+                    target_skill,
+                    target_file,
+                });
+            }
+        }
+
+        Self { links, broken }
+    }
+
+    /// All resolved intra-library links.
+    pub fn links(&self) -> &[SkillLink] {
+        &self.links
+    }
+
+    /// Links whose targets don't exist.
+    pub fn broken(&self) -> &[BrokenLink] {
+        &self.broken
+    }
+
+    /// Links from other skills into `skill`, optionally limited to one file.
+    pub fn backlinks(&self, skill: &str, file: Option<&str>) -> Vec<&SkillLink> {
+        self.links
+            .iter()
+            .filter(|l| l.target_skill == skill && l.source_skill != skill)
+            .filter(|l| file.is_none_or(|f| l.target_file == f))
+            .collect()
+    }
+}
+
+/// Resolve a relative link in `source_file` of skill `domain` to the skill
+/// it points into and the file within it (empty for the skill directory).
+///
+/// Returns None for external links, links that leave the skills directory,
+/// and links into skills that aren't indexed.
+pub fn resolve_skill_link(
+    skill_index: &SkillIndex,
+    domain: &str,
+    source_file: &str,
+    target: &str,
+) -> Option<(String, String)> {
+    if is_external_link(target) {
+        return None;
+    }
+    let resolved = resolve_link(domain, source_file, target)?;
+    let (skill, file) = split_target(&resolved, |name| skill_index.find(name).is_some());
+    skill_index.find(&skill).map(|meta| (meta.name.clone(), file))
+}
+
+/// Split a resolved path into skill name and file, recognizing skills
+/// nested in a collection (`frontend/forms/SKILL.md`).
+fn split_target(resolved: &str, is_skill: impl Fn(&str) -> bool) -> (String, String) {
+    let mut parts = resolved.splitn(3, '/');
+    let first = parts.next().unwrap_or_default();
+    let second = parts.next();
+    let rest = parts.next();
+
+    if let Some(second) = second {
+        let nested = format!("{}/{}", first, second);
+        if is_skill(&nested) {
+            return (nested, rest.unwrap_or_default().to_string());
+        }
+    }
+    match resolved.split_once('/') {
+        Some((skill, file)) => (skill.to_string(), file.to_string()),
+        None => (resolved.to_string(), String::new()),
+    }
+}
+
+/// Resolve a relative link target to a path relative to the skills directory.
+///
+/// Returns None if the link is absolute or climbs above the skills directory.
+fn resolve_link(domain: &str, source_file: &str, target: &str) -> Option<String> {
+    // Drop fragments and queries
+    let target = target.split(['#', '?']).next().unwrap_or_default();
+    if target.starts_with('/') || target.starts_with('\\') {
+        return None;
+    }
+
+    let source = Path::new(domain).join(source_file);
+    let base = source.parent().unwrap_or(Path::new(""));
+
+    let mut parts: Vec<String> = Vec::new();
+    for component in base.join(target).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    if parts.is_empty() {
+        return None;
+    }
+    Some(parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::SkillIndexer;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_link() {
+        assert_eq!(
+            resolve_link("forms", "SKILL.md", "references/api.md#setup").as_deref(),
+            Some("forms/references/api.md")
+        );
+        assert_eq!(
+            resolve_link("forms", "references/api.md", "../../testing/SKILL.md").as_deref(),
+            Some("testing/SKILL.md")
+        );
+        assert_eq!(resolve_link("forms", "SKILL.md", "../../etc/passwd"), None);
+        assert_eq!(resolve_link("forms", "SKILL.md", "/etc/passwd"), None);
+    }
+
+    #[test]
+    fn test_link_graph_backlinks_and_broken() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["forms", "testing"] {
+            let skill_dir = temp_dir.path().join(name);
+            fs::create_dir_all(skill_dir.join("references")).unwrap();
+            fs::write(
+                skill_dir.join("_meta.json"),
+                format!(r#"{{"name": "{}", "description": "{}"}}"#, name, name),
+            )
+            .unwrap();
+        }
+        fs::write(
+            temp_dir.path().join("forms/SKILL.md"),
+            "See [testing](../testing/SKILL.md), [api](references/api.md), \
+             [gone](../missing/SKILL.md) and [docs](https://example.com).",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("testing/SKILL.md"), "# Testing").unwrap();
+        fs::write(
+            temp_dir.path().join("testing/references/guide.md"),
+            "Back to [forms](../../forms/SKILL.md).",
+        )
+        .unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+        let graph = LinkGraph::new(
+            temp_dir.path(),
+            &indexer.get_skill_index(),
+            &indexer.get_content_index(),
+        );
+
+        let backlinks = graph.backlinks("testing", Some("SKILL.md"));
+        assert_eq!(backlinks.len(), 1);
+        assert_eq!(backlinks[0].source_skill, "forms");
+
+        // Links from reference files are recorded too
+        assert_eq!(graph.backlinks("forms", None)[0].source_file, "references/guide.md");
+
+        let reasons: Vec<_> = graph.broken().iter().map(|b| b.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![BrokenLinkReason::MissingFile, BrokenLinkReason::MissingSkill]
+        );
+    }
+}
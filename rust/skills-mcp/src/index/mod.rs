@@ -3,8 +3,35 @@
 //! Responsible for scanning skill directories, building metadata indexes,
 //! and creating content indexes for full-text search.
 
+mod cache;
+mod dependencies;
+mod files;
 mod indexer;
 mod file_watcher;
+mod links;
+mod names;
+mod poll_watcher;
+mod reload;
+mod store;
+mod tags;
+mod times;
+mod watch;
 
-pub use indexer::{IndexError, SkillIndexer};
-pub use file_watcher::{FileWatcher, WatchError};
+pub use cache::{ContentCache, ContentCacheStats};
+pub use dependencies::{DependencyGraph, SkillDependencies};
+pub use files::{
+    file_type, list_references, mime_type, normalize_file_type, read_indexed_text, read_lossy,
+    walk_files, IndexConfig, IndexedText, ReferenceFile, SkipReason, SymlinkPolicy, Walk, MARKDOWN,
+};
+pub use store::{LocalStore, SkillStore, StoreMetadata};
+pub use tags::TagConfig;
+pub use names::{is_reserved, normalize_separators, reserved_segment, CaseFolding, NameConfig};
+pub use indexer::{orphaned_files, IndexError, MetaPrecedence, SkillIndexer, SkillRoot};
+pub(crate) use indexer::skill_dirs;
+#[cfg(feature = "mcp")]
+pub(crate) use times::{file_times, FileTimes};
+pub use file_watcher::{FileWatcher, WatchError, DEFAULT_DEBOUNCE, MAX_BATCH_WINDOWS};
+pub use poll_watcher::{PollConfig, PollingHandle, PollingWatcher};
+pub use reload::{JobState, ReloadJob, ReloadJobs, ReloadPhase, ReloadProgress, ReloadStatus};
+pub use links::{resolve_skill_link, BrokenLink, BrokenLinkReason, LinkGraph, SkillLink};
+pub use watch::{WatchMode, WatchState, WatchStatus};
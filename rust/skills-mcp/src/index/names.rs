@@ -0,0 +1,142 @@
+//! Portable skill names.
+//!
+//! Skill names double as directory names, so a library has to work on
+//! filesystems that disagree about them. Windows and macOS ignore case,
+//! which makes `Forms` and `forms` the same directory there and two
+//! skills on Linux; Windows also refuses device names such as `CON` and
+//! writes paths with backslashes. Rules under `[index.names]` decide how
+//! the index treats names so lookups behave the same everywhere.
+
+use serde::Deserialize;
+
+/// Device names Windows reserves in every directory, with any extension.
+const RESERVED: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9",
+    "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Whether skill names that differ only in case name the same skill.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaseFolding {
+    /// Follow the platform: insensitive on Windows and macOS, sensitive
+    /// elsewhere.
+    #[default]
+    Auto,
+    /// `Forms` and `forms` are different skills.
+    Sensitive,
+    /// `Forms` and `forms` are the same skill.
+    Insensitive,
+}
+
+impl CaseFolding {
+    /// Whether names are compared ignoring case.
+    pub fn folds(self) -> bool {
+        match self {
+            Self::Auto => cfg!(any(windows, target_os = "macos")),
+            Self::Sensitive => false,
+            Self::Insensitive => true,
+        }
+    }
+}
+
+/// Name rules from the `[index.names]` configuration section:
+///
+/// ```toml
+/// [index.names]
+/// case = "insensitive"
+/// reject_reserved = true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct NameConfig {
+    /// How names are compared.
+    pub case: CaseFolding,
+
+    /// Leave skills whose directory has a name Windows reserves out of the
+    /// index, rather than only reporting them.
+    pub reject_reserved: bool,
+}
+
+impl Default for NameConfig {
+    fn default() -> Self {
+        Self {
+            case: CaseFolding::default(),
+            reject_reserved: true,
+        }
+    }
+}
+
+impl NameConfig {
+    /// Whether `a` and `b` name the same skill.
+    pub fn same(&self, a: &str, b: &str) -> bool {
+        let (a, b) = (normalize_separators(a), normalize_separators(b));
+        a == b || (self.case.folds() && a.to_lowercase() == b.to_lowercase())
+    }
+}
+
+/// Write `\` separators as `/`, the separator names and metadata paths use.
+pub fn normalize_separators(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// Whether Windows refuses a file or directory with this name.
+///
+/// Device names are reserved whatever their case or extension (`con`,
+/// `Aux.md`), and names can't end in a dot or space.
+pub fn is_reserved(segment: &str) -> bool {
+    let stem = segment.split('.').next().unwrap_or_default().trim_end();
+    RESERVED.iter().any(|r| r.eq_ignore_ascii_case(stem)) || segment.ends_with(['.', ' '])
+}
+
+/// The first segment of a skill or file path that Windows reserves.
+pub fn reserved_segment(path: &str) -> Option<&str> {
+    path.split(['/', '\\']).find(|segment| is_reserved(segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserved_names() {
+        for name in ["con", "CON", "Aux.md", "nul.tar.gz", "com1", "LPT9", "forms.", "forms "] {
+            assert!(is_reserved(name), "{}", name);
+        }
+        for name in ["console", "forms", "com10", "lpt", "auxiliary.md", ".hidden"] {
+            assert!(!is_reserved(name), "{}", name);
+        }
+        assert_eq!(reserved_segment("frontend/con"), Some("con"));
+        assert_eq!(reserved_segment("references\\PRN.md"), Some("PRN.md"));
+        assert_eq!(reserved_segment("frontend/forms"), None);
+    }
+
+    #[test]
+    fn test_same_name() {
+        let sensitive = NameConfig {
+            case: CaseFolding::Sensitive,
+            ..Default::default()
+        };
+        let insensitive = NameConfig {
+            case: CaseFolding::Insensitive,
+            ..Default::default()
+        };
+        assert!(!sensitive.same("Forms", "forms"));
+        assert!(insensitive.same("Forms", "forms"));
+        assert!(sensitive.same("frontend\\forms", "frontend/forms"));
+        assert!(!insensitive.same("forms", "tables"));
+        assert_eq!(normalize_separators("references\\forms.md"), "references/forms.md");
+    }
+
+    #[test]
+    #[cfg(any(windows, target_os = "macos"))]
+    fn test_auto_folds_case() {
+        assert!(NameConfig::default().same("Forms", "forms"));
+    }
+
+    #[test]
+    #[cfg(not(any(windows, target_os = "macos")))]
+    fn test_auto_keeps_case() {
+        assert!(!NameConfig::default().same("Forms", "forms"));
+    }
+}
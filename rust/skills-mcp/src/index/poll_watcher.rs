@@ -0,0 +1,233 @@
+//! Polling-based change detection for storage without filesystem events.
+//!
+//! Network mounts and object-store-backed filesystems often don't deliver
+//! inotify/FSEvents notifications. The polling watcher periodically
+//! snapshots the skills directory and feeds changed paths into the same
+//! incremental reload pipeline as [`FileWatcher`](super::FileWatcher).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+use sha2::{Digest, Sha256};
+use tracing::{debug, info};
+use walkdir::WalkDir;
+
+use super::file_watcher::apply_changes;
+use super::{SkillIndexer, WatchError, WatchMode};
+
+/// Polling watcher settings.
+#[derive(Debug, Clone)]
+pub struct PollConfig {
+    /// Time between scans.
+    pub interval: Duration,
+
+    /// Compare file content hashes as well as size and modification time.
+    ///
+    /// Needed where modification times are unreliable, at the cost of
+    /// reading every file on each scan.
+    pub hash_contents: bool,
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            hash_contents: false,
+        }
+    }
+}
+
+/// What a scan records about each file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Fingerprint {
+    len: u64,
+    modified: Option<SystemTime>,
+    hash: Option<[u8; 32]>,
+}
+
+/// Watcher that detects changes by periodically scanning the directory.
+pub struct PollingWatcher {
+    indexer: Arc<SkillIndexer>,
+    config: PollConfig,
+    snapshot: HashMap<PathBuf, Fingerprint>,
+}
+
+impl PollingWatcher {
+    /// Create a polling watcher, taking an initial snapshot.
+    pub fn new(indexer: Arc<SkillIndexer>, config: PollConfig) -> Self {
+        let mut watcher = Self {
+            indexer,
+            config,
+            snapshot: HashMap::new(),
+        };
+        watcher.snapshot = watcher.scan();
+        watcher
+    }
+
+    /// Scan once, apply any changes to the index, and return changed paths.
+    pub fn poll_once(&mut self) -> Vec<PathBuf> {
+        let current = self.scan();
+
+        let mut changed: Vec<PathBuf> = current
+            .iter()
+            .filter(|(path, fp)| self.snapshot.get(*path) != Some(fp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.snapshot
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+
+        self.snapshot = current;
+
+        if !changed.is_empty() {
+            debug!("Polling detected {} changed files", changed.len());
+            let state = self.indexer.watch_state();
+            state.record(&changed);
+            apply_changes(&self.indexer, &state.take_pending());
+        }
+
+        changed
+    }
+
+    /// Run the watcher on a background thread until the handle is dropped.
+    pub fn start(mut self) -> Result<PollingHandle, WatchError> {
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let interval = self.config.interval;
+        let roots: Vec<PathBuf> = self.indexer.roots().into_iter().map(|r| r.path).collect();
+        self.indexer.watch_state().started(WatchMode::Polling, interval);
+
+        let thread = std::thread::Builder::new()
+            .name("skills-poll-watcher".to_string())
+            .spawn(move || {
+                // Wake every interval until the stop channel fires or closes
+                while let Err(mpsc::RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+                    self.poll_once();
+                }
+                self.indexer.watch_state().stopped();
+            })
+            .map_err(|e| WatchError::Setup(format!("Failed to start polling thread: {}", e)))?;
+
+        info!("Started polling {:?} every {:?}", roots, interval);
+
+        Ok(PollingHandle {
+            stop_tx: Some(stop_tx),
+            thread: Some(thread),
+        })
+    }
+
+    /// Fingerprint every non-hidden file under each skills root.
+    fn scan(&self) -> HashMap<PathBuf, Fingerprint> {
+        self.indexer
+            .roots()
+            .into_iter()
+            .flat_map(|root| {
+                WalkDir::new(root.path)
+                    .min_depth(1)
+                    .into_iter()
+                    .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let meta = e.metadata().ok()?;
+                let hash = if self.config.hash_contents {
+                    Some(Sha256::digest(fs::read(e.path()).ok()?).into())
+                } else {
+                    None
+                };
+                let fingerprint = Fingerprint {
+                    len: meta.len(),
+                    modified: meta.modified().ok(),
+                    hash,
+                };
+                Some((e.into_path(), fingerprint))
+            })
+            .collect()
+    }
+}
+
+/// Handle to a running polling watcher; stops the watcher when dropped.
+pub struct PollingHandle {
+    stop_tx: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PollingHandle {
+    /// Stop polling and wait for the background thread to exit.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        if let Some(tx) = self.stop_tx.take() {
+            let _ = tx.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for PollingHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{meta, write_skill};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_poll_detects_changes_by_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "forms", &meta("forms", "Before"), "# forms");
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+
+        let config = PollConfig {
+            hash_contents: true,
+            ..Default::default()
+        };
+        let mut watcher = PollingWatcher::new(Arc::clone(&indexer), config);
+        assert!(watcher.poll_once().is_empty());
+
+        // Same length, so only the content hash reliably reveals the edit
+        write_skill(temp_dir.path(), "forms", &meta("forms", "Beforf"), "# forms");
+        write_skill(temp_dir.path(), "testing", &meta("testing", "New"), "# testing");
+
+        let changed = watcher.poll_once();
+        assert!(changed.contains(&temp_dir.path().join("forms/_meta.json")));
+        assert_eq!(indexer.get_skill_meta("forms").unwrap().description, "Beforf");
+        assert!(indexer.get_skill_meta("testing").is_some());
+
+        fs::remove_dir_all(temp_dir.path().join("testing")).unwrap();
+        watcher.poll_once();
+        assert!(indexer.get_skill_meta("testing").is_none());
+    }
+
+    #[test]
+    fn test_start_and_stop() {
+        let temp_dir = TempDir::new().unwrap();
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+
+        let config = PollConfig {
+            interval: Duration::from_millis(10),
+            hash_contents: false,
+        };
+        let handle = PollingWatcher::new(indexer, config).start().unwrap();
+        handle.stop();
+    }
+}
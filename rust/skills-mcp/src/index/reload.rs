@@ -0,0 +1,321 @@
+//! Background reloads with progress reporting.
+//!
+//! A reload of a large library takes seconds. [`ReloadJobs::start`] runs it
+//! on the blocking thread pool and returns a job whose progress can be
+//! polled while the old index keeps serving; the new index is swapped in
+//! atomically when the job completes. Starting a reload while one is running
+//! returns the running job instead of queueing another.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::Notify;
+use tracing::warn;
+
+use super::SkillIndexer;
+
+/// Finished jobs kept for polling.
+const MAX_FINISHED_JOBS: usize = 20;
+
+/// Counters a reload updates as it goes.
+#[derive(Debug, Default)]
+pub struct ReloadProgress {
+    phase: Mutex<ReloadPhase>,
+    skills_total: AtomicUsize,
+    skills_scanned: AtomicUsize,
+    errors: Mutex<Vec<String>>,
+}
+
+impl ReloadProgress {
+    /// Create progress counters for a reload that hasn't started.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn set_phase(&self, phase: ReloadPhase) {
+        *self.phase.lock() = phase;
+    }
+
+    pub(crate) fn add_total(&self, skills: usize) {
+        self.skills_total.fetch_add(skills, Ordering::Relaxed);
+    }
+
+    /// Count one skill directory as scanned and catch up on new errors.
+    pub(crate) fn scanned(&self, errors: &[String]) {
+        self.skills_scanned.fetch_add(1, Ordering::Relaxed);
+        self.sync_errors(errors);
+    }
+
+    /// Record errors not yet seen; `errors` only ever grows.
+    pub(crate) fn sync_errors(&self, errors: &[String]) {
+        let mut seen = self.errors.lock();
+        if errors.len() > seen.len() {
+            let new = &errors[seen.len()..];
+            seen.extend_from_slice(new);
+        }
+    }
+
+    /// The current phase.
+    pub fn phase(&self) -> ReloadPhase {
+        *self.phase.lock()
+    }
+
+    /// Skill directories found so far.
+    pub fn skills_total(&self) -> usize {
+        self.skills_total.load(Ordering::Relaxed)
+    }
+
+    /// Skill directories scanned so far.
+    pub fn skills_scanned(&self) -> usize {
+        self.skills_scanned.load(Ordering::Relaxed)
+    }
+
+    /// Errors found so far.
+    pub fn errors(&self) -> Vec<String> {
+        self.errors.lock().clone()
+    }
+}
+
+/// What a reload is doing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReloadPhase {
+    /// Waiting to start.
+    #[default]
+    Pending,
+    /// Reading skill metadata.
+    Scanning,
+    /// Reading skill content for search.
+    Indexing,
+    /// Finished, successfully or not.
+    Done,
+}
+
+/// Outcome of a reload job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    /// Still running; the old index is being served.
+    Running,
+    /// The new index is being served.
+    Completed,
+    /// The reload failed; the old index is still being served.
+    Failed,
+}
+
+/// When a job finished, and its skill count or error.
+type JobResult = (DateTime<Utc>, Result<usize, String>);
+
+/// A background reload.
+#[derive(Debug)]
+pub struct ReloadJob {
+    id: String,
+    started_at: DateTime<Utc>,
+    progress: ReloadProgress,
+    result: Mutex<Option<JobResult>>,
+    done: Notify,
+}
+
+impl ReloadJob {
+    /// Job ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Whether the job has finished.
+    pub fn is_finished(&self) -> bool {
+        self.result.lock().is_some()
+    }
+
+    /// Wait for the job to finish.
+    pub async fn wait(&self) {
+        loop {
+            let done = self.done.notified();
+            if self.is_finished() {
+                return;
+            }
+            done.await;
+        }
+    }
+
+    /// Snapshot of the job's progress.
+    pub fn status(&self) -> ReloadStatus {
+        let result = self.result.lock().clone();
+        let (state, finished_at, skill_count, error) = match result {
+            None => (JobState::Running, None, None, None),
+            Some((at, Ok(count))) => (JobState::Completed, Some(at), Some(count), None),
+            Some((at, Err(e))) => (JobState::Failed, Some(at), None, Some(e)),
+        };
+        ReloadStatus {
+            job_id: self.id.clone(),
+            state,
+            phase: self.progress.phase(),
+            started_at: self.started_at,
+            finished_at,
+            skills_total: self.progress.skills_total(),
+            skills_scanned: self.progress.skills_scanned(),
+            errors: self.progress.errors(),
+            skill_count,
+            error,
+        }
+    }
+
+    fn finish(&self, result: Result<usize, String>) {
+        self.progress.set_phase(ReloadPhase::Done);
+        *self.result.lock() = Some((Utc::now(), result));
+        self.done.notify_waiters();
+    }
+}
+
+/// Progress of a reload job, as served by the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReloadStatus {
+    /// Job ID.
+    pub job_id: String,
+
+    /// Whether the job is running, completed, or failed.
+    pub state: JobState,
+
+    /// What the reload is doing.
+    pub phase: ReloadPhase,
+
+    /// When the job started.
+    #[serde(with = "crate::models::timestamp")]
+    pub started_at: DateTime<Utc>,
+
+    /// When the job finished.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+
+    /// Skill directories found so far.
+    pub skills_total: usize,
+
+    /// Skill directories scanned so far.
+    pub skills_scanned: usize,
+
+    /// Skill errors found so far.
+    pub errors: Vec<String>,
+
+    /// Skills in the new index, once completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_count: Option<usize>,
+
+    /// Why the reload failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Running and recently finished reload jobs.
+#[derive(Debug, Default)]
+pub struct ReloadJobs {
+    jobs: Mutex<VecDeque<Arc<ReloadJob>>>,
+    counter: AtomicU64,
+}
+
+impl ReloadJobs {
+    /// Create an empty job list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a background reload, or return the one already running.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn start(&self, indexer: &Arc<SkillIndexer>) -> Arc<ReloadJob> {
+        let mut jobs = self.jobs.lock();
+        if let Some(running) = jobs.iter().find(|j| !j.is_finished()) {
+            return Arc::clone(running);
+        }
+
+        let id = format!("reload-{}", self.counter.fetch_add(1, Ordering::Relaxed) + 1);
+        let job = Arc::new(ReloadJob {
+            id,
+            started_at: Utc::now(),
+            progress: ReloadProgress::new(),
+            result: Mutex::new(None),
+            done: Notify::new(),
+        });
+
+        // Forget the oldest finished jobs
+        while jobs.len() >= MAX_FINISHED_JOBS {
+            jobs.pop_front();
+        }
+        jobs.push_back(Arc::clone(&job));
+        drop(jobs);
+
+        let indexer = Arc::clone(indexer);
+        let running = Arc::clone(&job);
+        // Keep logging under the span (and request ID) that started the job
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            let _span = span.entered();
+            let result = indexer
+                .reload_with_progress(&running.progress)
+                .map(|()| indexer.get_skill_index().len())
+                .map_err(|e| {
+                    warn!("Reload {} failed: {}", running.id, e);
+                    e.to_string()
+                });
+            running.finish(result);
+        });
+        job
+    }
+
+    /// Look up a job by ID.
+    pub fn get(&self, id: &str) -> Option<Arc<ReloadJob>> {
+        self.jobs.lock().iter().find(|j| j.id == id).cloned()
+    }
+
+    /// The job currently running, if any.
+    pub fn running(&self) -> Option<Arc<ReloadJob>> {
+        self.jobs.lock().iter().find(|j| !j.is_finished()).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_reload_job() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["forms", "tables"] {
+            let skill_dir = temp_dir.path().join(name);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join("_meta.json"),
+                format!(r#"{{"name": "{}", "description": "About {}"}}"#, name, name),
+            )
+            .unwrap();
+        }
+        fs::create_dir_all(temp_dir.path().join("broken")).unwrap();
+        fs::write(temp_dir.path().join("broken/_meta.json"), "{").unwrap();
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        let jobs = ReloadJobs::new();
+        let job = jobs.start(&indexer);
+        job.wait().await;
+
+        let status = job.status();
+        assert_eq!(status.state, JobState::Completed);
+        assert_eq!(status.phase, ReloadPhase::Done);
+        assert_eq!((status.skills_total, status.skills_scanned), (3, 3));
+        assert_eq!(status.skill_count, Some(2));
+        assert_eq!(status.errors.len(), 1);
+        assert!(status.errors[0].starts_with("broken:"));
+        assert!(jobs.get(job.id()).is_some());
+        assert!(jobs.running().is_none());
+
+        // A finished job doesn't block the next one
+        let next = jobs.start(&indexer);
+        assert_ne!(next.id(), job.id());
+        next.wait().await;
+        assert!(jobs.get("reload-99").is_none());
+    }
+}
@@ -0,0 +1,281 @@
+//! Storage backends the indexer reads skills from.
+//!
+//! The indexer never touches the filesystem directly: listing skill
+//! directories, reading metadata and content, and walking reference
+//! directories all go through a [`SkillStore`]. [`LocalStore`] reads a
+//! local directory tree; other backends, such as an object store, map
+//! paths under a root to keys and "directories" to key prefixes.
+//!
+//! Only reads go through the store. Writes made through the API and MCP
+//! tools, file watching, and backups work on a local skills directory.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use walkdir::WalkDir;
+
+use super::files::{walk_files, IndexConfig, Walk};
+
+/// What a store knows about a file or directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreMetadata {
+    /// Whether the path is a directory (or, in an object store, a prefix).
+    pub is_dir: bool,
+    /// Size in bytes; 0 for directories.
+    pub len: u64,
+    /// Last modification, if the backend records it.
+    pub modified: Option<SystemTime>,
+    /// Creation, if the backend records it.
+    pub created: Option<SystemTime>,
+}
+
+/// Read access to a tree of skill files.
+///
+/// Paths are the ones the indexer builds from its roots, so a backend is
+/// free to interpret them as it likes as long as it does so consistently.
+pub trait SkillStore: Send + Sync + fmt::Debug {
+    /// Metadata of a file or directory.
+    fn metadata(&self, path: &Path) -> io::Result<StoreMetadata>;
+
+    /// Paths of the entries directly in a directory, in no particular
+    /// order.
+    fn list_dir(&self, dir: &Path) -> io::Result<Vec<PathBuf>>;
+
+    /// Whole contents of a file.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// At most `max_bytes` from the start of a file, and its full size.
+    fn read_prefix(&self, path: &Path, max_bytes: u64) -> io::Result<(Vec<u8>, u64)> {
+        let mut bytes = self.read(path)?;
+        let size = bytes.len() as u64;
+        bytes.truncate(max_bytes.min(size) as usize);
+        Ok((bytes, size))
+    }
+
+    /// The path with links resolved, for checking that a path stays inside
+    /// a directory. Stores without links return it unchanged.
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        Ok(path.to_path_buf())
+    }
+
+    /// Files under a directory in path order, down to `config.max_depth`.
+    ///
+    /// `boundary` is the directory links must stay inside; the default
+    /// walk is for stores without links and ignores it.
+    fn walk(&self, dir: &Path, _boundary: &Path, config: &IndexConfig) -> Walk {
+        let mut walk = Walk::default();
+        let mut stack = vec![(dir.to_path_buf(), 0)];
+        while let Some((current, depth)) = stack.pop() {
+            for path in self.list_dir(&current).unwrap_or_default() {
+                match self.metadata(&path) {
+                    Ok(meta) if meta.is_dir && depth >= config.max_depth => {
+                        walk.skipped
+                            .push((path, format!("Deeper than max_depth ({})", config.max_depth)));
+                    }
+                    Ok(meta) if meta.is_dir => stack.push((path, depth + 1)),
+                    Ok(_) => walk.files.push(path),
+                    Err(_) => {}
+                }
+            }
+        }
+        walk.files.sort();
+        walk
+    }
+
+    /// Every file under a directory whose path has no hidden (`.`)
+    /// component below it.
+    fn visible_files(&self, dir: &Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        let mut stack = vec![dir.to_path_buf()];
+        while let Some(current) = stack.pop() {
+            for path in self.list_dir(&current).unwrap_or_default() {
+                if is_hidden(&path) {
+                    continue;
+                }
+                match self.metadata(&path) {
+                    Ok(meta) if meta.is_dir => stack.push(path),
+                    Ok(_) => files.push(path),
+                    Err(_) => {}
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    /// Whether the path is a file.
+    fn is_file(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok_and(|m| !m.is_dir)
+    }
+
+    /// Whether the path is a directory.
+    fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok_and(|m| m.is_dir)
+    }
+
+    /// A whole file as text, replacing invalid UTF-8.
+    fn read_lossy(&self, path: &Path) -> io::Result<String> {
+        let bytes = self.read(path)?;
+        Ok(match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        })
+    }
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+/// Skills on the local filesystem.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStore;
+
+impl SkillStore for LocalStore {
+    fn metadata(&self, path: &Path) -> io::Result<StoreMetadata> {
+        let meta = fs::metadata(path)?;
+        Ok(StoreMetadata {
+            is_dir: meta.is_dir(),
+            len: if meta.is_dir() { 0 } else { meta.len() },
+            modified: meta.modified().ok(),
+            created: meta.created().ok(),
+        })
+    }
+
+    fn list_dir(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        Ok(fs::read_dir(dir)?.flatten().map(|e| e.path()).collect())
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+
+    fn read_prefix(&self, path: &Path, max_bytes: u64) -> io::Result<(Vec<u8>, u64)> {
+        let mut file = fs::File::open(path)?;
+        let size = file.metadata()?.len();
+        let mut bytes = Vec::new();
+        file.by_ref().take(max_bytes).read_to_end(&mut bytes)?;
+        Ok((bytes, size))
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        fs::canonicalize(path)
+    }
+
+    fn walk(&self, dir: &Path, boundary: &Path, config: &IndexConfig) -> Walk {
+        walk_files(dir, boundary, config)
+    }
+
+    fn visible_files(&self, dir: &Path) -> Vec<PathBuf> {
+        WalkDir::new(dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !is_hidden(e.path()))
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    /// A store of in-memory files, standing in for an object store.
+    #[derive(Debug, Default)]
+    struct MemoryStore {
+        files: HashMap<PathBuf, Vec<u8>>,
+    }
+
+    impl SkillStore for MemoryStore {
+        fn metadata(&self, path: &Path) -> io::Result<StoreMetadata> {
+            if let Some(file) = self.files.get(path) {
+                return Ok(StoreMetadata { is_dir: false, len: file.len() as u64, modified: None, created: None });
+            }
+            if self.files.keys().any(|p| p.starts_with(path)) {
+                return Ok(StoreMetadata { is_dir: true, len: 0, modified: None, created: None });
+            }
+            Err(io::ErrorKind::NotFound.into())
+        }
+
+        fn list_dir(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+            let mut entries: Vec<PathBuf> = self
+                .files
+                .keys()
+                .filter_map(|p| p.strip_prefix(dir).ok()?.components().next())
+                .map(|c| dir.join(c))
+                .collect();
+            entries.sort();
+            entries.dedup();
+            Ok(entries)
+        }
+
+        fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+            self.files.get(path).cloned().ok_or_else(|| io::ErrorKind::NotFound.into())
+        }
+    }
+
+    #[test]
+    fn test_default_walk() {
+        let store = MemoryStore {
+            files: ["refs/a.md", "refs/.hidden/b.md", "refs/deep/er/c.md"]
+                .iter()
+                .map(|p| (PathBuf::from(p), b"x".to_vec()))
+                .collect(),
+        };
+        let config = IndexConfig {
+            max_depth: 1,
+            ..Default::default()
+        };
+
+        let walk = store.walk(Path::new("refs"), Path::new(""), &config);
+        assert_eq!(walk.files, vec![PathBuf::from("refs/.hidden/b.md"), PathBuf::from("refs/a.md")]);
+        assert_eq!(walk.skipped[0].0, PathBuf::from("refs/deep/er"));
+
+        assert_eq!(store.visible_files(Path::new("refs")).len(), 2);
+        assert_eq!(store.read_prefix(Path::new("refs/a.md"), 0).unwrap(), (vec![], 1));
+        assert!(store.is_dir(Path::new("refs/deep")));
+    }
+
+    #[test]
+    fn test_indexer_over_store() {
+        let files = [
+            ("skills/forms/_meta.json", r#"{"name": "forms", "description": "Forms"}"#),
+            ("skills/forms/SKILL.md", "# Forms\n\nValidate input."),
+            ("skills/forms/references/api.md", "# API"),
+            ("skills/team/review/SKILL.md", "---\nname: review\ndescription: Reviews\n---\n# Review"),
+        ];
+        let store = MemoryStore {
+            files: files.iter().map(|(p, c)| (PathBuf::from(p), c.as_bytes().to_vec())).collect(),
+        };
+        let indexer = crate::index::SkillIndexer::new("skills").with_store(std::sync::Arc::new(store));
+        indexer.reload().unwrap();
+
+        let names: Vec<String> = indexer.get_skill_index().skills.iter().map(|s| s.name.clone()).collect();
+        assert_eq!(names, vec!["forms", "team/review"]);
+        assert_eq!(indexer.read_skill_content("forms").unwrap().content, "# Forms\n\nValidate input.");
+        assert!(indexer.has_references("forms"));
+        assert_eq!(indexer.list_references("forms")[0].path, "api.md");
+        assert_eq!(indexer.get_content_index().get_domain_entries("forms").len(), 2);
+    }
+
+    #[test]
+    fn test_local_store() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".git")).unwrap();
+        fs::write(temp.path().join(".git/HEAD"), "ref").unwrap();
+        fs::write(temp.path().join("SKILL.md"), "# Skill").unwrap();
+
+        let store = LocalStore;
+        assert_eq!(store.visible_files(temp.path()), vec![temp.path().join("SKILL.md")]);
+        assert_eq!(store.read_prefix(&temp.path().join("SKILL.md"), 3).unwrap(), (b"# S".to_vec(), 7));
+        assert_eq!(store.metadata(temp.path()).unwrap().len, 0);
+    }
+}
@@ -0,0 +1,96 @@
+//! Tag normalization applied at index time.
+//!
+//! Tags are free-form in `_meta.json`, so the same idea turns up as
+//! `testing`, `Tests`, and `unit-test`. Rules under `[index.tags]` fold
+//! them into one tag in the index; the files keep what was written until a
+//! tag is renamed through the API.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// Tag rules from the `[index.tags]` configuration section:
+///
+/// ```toml
+/// [index.tags]
+/// lowercase = true
+///
+/// [index.tags.aliases]
+/// tests = "testing"
+/// unit-test = "testing"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TagConfig {
+    /// Lowercase every tag.
+    pub lowercase: bool,
+
+    /// Tags to replace, matched case-insensitively, and their replacements.
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl TagConfig {
+    /// Whether any rule is set.
+    pub fn is_empty(&self) -> bool {
+        !self.lowercase && self.aliases.is_empty()
+    }
+
+    /// The tag `tag` is indexed as.
+    pub fn normalize(&self, tag: &str) -> String {
+        let tag = tag.trim();
+        let tag = self
+            .aliases
+            .get(tag)
+            .or_else(|| {
+                self.aliases
+                    .iter()
+                    .find(|(alias, _)| alias.eq_ignore_ascii_case(tag))
+                    .map(|(_, target)| target)
+            })
+            .map_or(tag, String::as_str);
+        if self.lowercase {
+            tag.to_lowercase()
+        } else {
+            tag.to_string()
+        }
+    }
+
+    /// Normalize tags in place, dropping empty tags and later duplicates.
+    pub fn apply(&self, tags: &mut Vec<String>) {
+        let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags.drain(..) {
+            let tag = self.normalize(&tag);
+            if !tag.is_empty() && !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+        }
+        *tags = normalized;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let config = TagConfig {
+            lowercase: true,
+            aliases: [("tests", "testing"), ("unit-test", "Testing")]
+                .into_iter()
+                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .collect(),
+        };
+        assert_eq!(config.normalize(" Tests "), "testing");
+        assert_eq!(config.normalize("Unit-Test"), "testing");
+        assert_eq!(config.normalize("React"), "react");
+
+        let mut tags = vec!["testing".to_string(), "Tests".to_string(), " ".to_string(), "React".to_string()];
+        config.apply(&mut tags);
+        assert_eq!(tags, vec!["testing", "react"]);
+
+        let untouched = TagConfig::default();
+        assert!(untouched.is_empty());
+        assert_eq!(untouched.normalize("Tests"), "Tests");
+    }
+}
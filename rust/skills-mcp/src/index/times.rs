@@ -0,0 +1,66 @@
+//! File timestamps of skill directories.
+
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use walkdir::WalkDir;
+
+use super::store::SkillStore;
+
+/// Creation and modification times of a file in a skill.
+#[cfg_attr(not(feature = "mcp"), allow(dead_code))]
+pub(crate) struct FileTimes {
+    /// Path relative to the skill directory, with `/` separators.
+    pub path: String,
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+}
+
+/// Times of every visible file in a skill directory.
+///
+/// Falls back to the modification time where the filesystem doesn't
+/// record creation times.
+#[cfg_attr(not(feature = "mcp"), allow(dead_code))]
+pub(crate) fn file_times(skill_dir: &Path) -> Vec<FileTimes> {
+    WalkDir::new(skill_dir)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let meta = fs::metadata(e.path()).ok()?;
+            let modified: SystemTime = meta.modified().ok()?;
+            let created = meta.created().unwrap_or(modified);
+            Some(FileTimes {
+                path: e
+                    .path()
+                    .strip_prefix(skill_dir)
+                    .unwrap_or(e.path())
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                created: created.into(),
+                modified: modified.into(),
+            })
+        })
+        .collect()
+}
+
+/// When a skill was created and last changed: the earliest file creation
+/// and latest file modification. None for a directory without files.
+/// Files whose backend records no modification time are left out.
+pub(crate) fn skill_times(store: &dyn SkillStore, skill_dir: &Path) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let times: Vec<(SystemTime, SystemTime)> = store
+        .visible_files(skill_dir)
+        .iter()
+        .filter_map(|path| {
+            let meta = store.metadata(path).ok()?;
+            let modified = meta.modified?;
+            Some((meta.created.unwrap_or(modified), modified))
+        })
+        .collect();
+    let created = times.iter().map(|(c, m)| *c.min(m)).min()?;
+    let updated = times.iter().map(|(_, m)| *m).max()?;
+    Some((created.into(), updated.into()))
+}
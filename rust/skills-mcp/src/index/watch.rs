@@ -0,0 +1,160 @@
+//! Shared state of whichever watcher keeps the index up to date.
+//!
+//! Watchers record changed paths here as events arrive and take them as one
+//! batch once the debounce window has passed without new events, so a
+//! `git pull` touching hundreds of files is reindexed once. The state is
+//! served at `GET /api/watch/status`.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// How changes are detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchMode {
+    /// Filesystem events.
+    Events,
+    /// Periodic scans.
+    Polling,
+}
+
+/// Watcher state, shared between a watcher and whoever reports on it.
+#[derive(Debug, Default)]
+pub struct WatchState {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    mode: Option<WatchMode>,
+    debounce: Duration,
+    last_event_at: Option<DateTime<Utc>>,
+    pending: HashSet<PathBuf>,
+    batches: u64,
+    last_batch_at: Option<DateTime<Utc>>,
+    last_batch_skills: Vec<String>,
+}
+
+impl WatchState {
+    /// State with no watcher running.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a watcher started. `debounce` is the quiet time before a
+    /// batch is applied (the scan interval when polling).
+    pub(crate) fn started(&self, mode: WatchMode, debounce: Duration) {
+        let mut inner = self.inner.lock();
+        inner.mode = Some(mode);
+        inner.debounce = debounce;
+    }
+
+    /// Record that the watcher stopped.
+    pub(crate) fn stopped(&self) {
+        let mut inner = self.inner.lock();
+        inner.mode = None;
+        inner.pending.clear();
+    }
+
+    /// Record changed paths waiting to be applied.
+    pub(crate) fn record(&self, paths: &[PathBuf]) {
+        let mut inner = self.inner.lock();
+        inner.last_event_at = Some(Utc::now());
+        inner.pending.extend(paths.iter().cloned());
+    }
+
+    /// Take every pending path, sorted.
+    pub(crate) fn take_pending(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.inner.lock().pending.drain().collect();
+        paths.sort();
+        paths
+    }
+
+    /// Record a batch applied to the index; no skills means a full reload.
+    pub(crate) fn batch_applied(&self, skills: &[String]) {
+        let mut inner = self.inner.lock();
+        inner.batches += 1;
+        inner.last_batch_at = Some(Utc::now());
+        inner.last_batch_skills = skills.to_vec();
+    }
+
+    /// Snapshot of the state.
+    pub fn status(&self) -> WatchStatus {
+        let inner = self.inner.lock();
+        WatchStatus {
+            enabled: inner.mode.is_some(),
+            mode: inner.mode,
+            debounce_ms: inner.debounce.as_millis() as u64,
+            last_event_at: inner.last_event_at,
+            pending: inner.pending.len(),
+            batches: inner.batches,
+            last_batch_at: inner.last_batch_at,
+            last_batch_skills: inner.last_batch_skills.clone(),
+        }
+    }
+}
+
+/// Watcher state, as served by the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchStatus {
+    /// Whether a watcher is running.
+    pub enabled: bool,
+
+    /// How changes are detected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<WatchMode>,
+
+    /// Quiet time before changes are applied, or the polling interval.
+    pub debounce_ms: u64,
+
+    /// When the last change was seen.
+    #[serde(with = "crate::models::timestamp::option")]
+    pub last_event_at: Option<DateTime<Utc>>,
+
+    /// Changed paths waiting for the debounce window to pass.
+    pub pending: usize,
+
+    /// Batches applied since startup.
+    pub batches: u64,
+
+    /// When the last batch was applied.
+    #[serde(with = "crate::models::timestamp::option")]
+    pub last_batch_at: Option<DateTime<Utc>>,
+
+    /// Skills updated by the last batch; empty after a full reload.
+    pub last_batch_skills: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_state() {
+        let state = WatchState::new();
+        assert!(!state.status().enabled);
+
+        state.started(WatchMode::Events, Duration::from_millis(250));
+        state.record(&[PathBuf::from("b"), PathBuf::from("a")]);
+        state.record(&[PathBuf::from("a")]);
+        let status = state.status();
+        assert!(status.enabled);
+        assert_eq!(status.debounce_ms, 250);
+        assert_eq!(status.pending, 2);
+        assert!(status.last_event_at.is_some());
+
+        assert_eq!(state.take_pending(), vec![PathBuf::from("a"), PathBuf::from("b")]);
+        state.batch_applied(&["forms".to_string()]);
+        let status = state.status();
+        assert_eq!((status.pending, status.batches), (0, 1));
+        assert_eq!(status.last_batch_skills, vec!["forms"]);
+
+        state.stopped();
+        assert!(!state.status().enabled);
+    }
+}
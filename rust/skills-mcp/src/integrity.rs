@@ -0,0 +1,261 @@
+//! Integrity verification of the skills directory.
+//!
+//! A manifest of SHA-256 file hashes can be written as a snapshot of a
+//! known-good library and checked at startup. Locked-down deployments can
+//! refuse to serve a tampered library, or serve it with a warning status.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+/// Manifest file name, stored in the skills directory root.
+pub const MANIFEST_FILE: &str = ".skills-manifest.json";
+
+/// Expected file hashes for a skills directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Manifest format version.
+    pub version: u32,
+
+    /// When the manifest was generated.
+    #[serde(with = "crate::models::timestamp")]
+    pub created_at: DateTime<Utc>,
+
+    /// SHA-256 hex digest per file, keyed by path relative to the skills
+    /// directory using `/` separators.
+    pub files: BTreeMap<String, String>,
+}
+
+impl Manifest {
+    /// Current manifest format version.
+    pub const VERSION: u32 = 1;
+
+    /// Hash every file in a skills directory.
+    ///
+    /// Hidden files and directories (leading `.`) are skipped, which
+    /// excludes the manifest itself.
+    pub fn generate(skills_dir: &Path) -> Result<Self, IntegrityError> {
+        Ok(Self {
+            version: Self::VERSION,
+            created_at: Utc::now(),
+            files: hash_files(skills_dir)?,
+        })
+    }
+
+    /// Load the manifest from a skills directory, if one is present.
+    pub fn load(skills_dir: &Path) -> Result<Option<Self>, IntegrityError> {
+        let path = skills_dir.join(MANIFEST_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let manifest: Self = serde_json::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| IntegrityError::InvalidManifest(e.to_string()))?;
+
+        if manifest.version != Self::VERSION {
+            return Err(IntegrityError::InvalidManifest(format!(
+                "unsupported version {}",
+                manifest.version
+            )));
+        }
+
+        Ok(Some(manifest))
+    }
+
+    /// Write the manifest into a skills directory.
+    pub fn save(&self, skills_dir: &Path) -> Result<(), IntegrityError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| IntegrityError::InvalidManifest(e.to_string()))?;
+        fs::write(skills_dir.join(MANIFEST_FILE), json)?;
+        Ok(())
+    }
+
+    /// Compare the directory's current contents against this manifest.
+    pub fn verify(&self, skills_dir: &Path) -> Result<IntegrityReport, IntegrityError> {
+        let actual = hash_files(skills_dir)?;
+
+        let mut modified = Vec::new();
+        let mut missing = Vec::new();
+        for (path, expected) in &self.files {
+            match actual.get(path) {
+                Some(hash) if hash == expected => {}
+                Some(_) => modified.push(path.clone()),
+                None => missing.push(path.clone()),
+            }
+        }
+        let unexpected: Vec<String> = actual
+            .keys()
+            .filter(|p| !self.files.contains_key(*p))
+            .cloned()
+            .collect();
+
+        let status = if modified.is_empty() && missing.is_empty() && unexpected.is_empty() {
+            IntegrityStatus::Verified
+        } else {
+            IntegrityStatus::Tampered
+        };
+
+        Ok(IntegrityReport {
+            status,
+            manifest_created_at: self.created_at,
+            checked_at: Utc::now(),
+            files_checked: actual.len(),
+            modified,
+            missing,
+            unexpected,
+        })
+    }
+}
+
+/// Overall verification result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegrityStatus {
+    /// All files match the manifest.
+    Verified,
+    /// Files were modified, removed, or added since the manifest was made.
+    Tampered,
+}
+
+/// Result of verifying a skills directory against its manifest.
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    /// Overall status.
+    pub status: IntegrityStatus,
+
+    /// When the manifest was generated.
+    #[serde(with = "crate::models::timestamp")]
+    pub manifest_created_at: DateTime<Utc>,
+
+    /// When verification ran.
+    #[serde(with = "crate::models::timestamp")]
+    pub checked_at: DateTime<Utc>,
+
+    /// Number of files hashed on disk.
+    pub files_checked: usize,
+
+    /// Files whose contents differ from the manifest.
+    pub modified: Vec<String>,
+
+    /// Files in the manifest that no longer exist.
+    pub missing: Vec<String>,
+
+    /// Files on disk that the manifest doesn't list.
+    pub unexpected: Vec<String>,
+}
+
+impl IntegrityReport {
+    /// Whether verification found no differences.
+    pub fn is_verified(&self) -> bool {
+        self.status == IntegrityStatus::Verified
+    }
+}
+
+/// How integrity failures are handled at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntegrityMode {
+    /// Skip verification.
+    Off,
+    /// Verify if a manifest is present; serve with a warning status if not.
+    #[default]
+    Warn,
+    /// Verify if a manifest is present; refuse to serve if tampered.
+    Enforce,
+}
+
+impl std::str::FromStr for IntegrityMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "warn" => Ok(Self::Warn),
+            "enforce" => Ok(Self::Enforce),
+            other => Err(format!(
+                "Unknown integrity mode '{}' (expected off, warn, or enforce)",
+                other
+            )),
+        }
+    }
+}
+
+/// Hash all non-hidden files under a directory.
+fn hash_files(skills_dir: &Path) -> Result<BTreeMap<String, String>, IntegrityError> {
+    let mut files = BTreeMap::new();
+
+    for entry in WalkDir::new(skills_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+    {
+        let entry = entry.map_err(|e| IntegrityError::Io(e.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(skills_dir)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        let digest = Sha256::digest(fs::read(entry.path())?);
+        let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+
+        files.insert(relative, hex);
+    }
+
+    Ok(files)
+}
+
+/// Errors that can occur during integrity verification.
+#[derive(Debug, thiserror::Error)]
+pub enum IntegrityError {
+    /// The manifest could not be parsed or has an unsupported version.
+    #[error("Invalid manifest: {0}")]
+    InvalidManifest(String),
+
+    /// Filesystem error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_manifest_roundtrip_and_tamper_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("forms");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Forms").unwrap();
+        fs::write(skill_dir.join("_meta.json"), "{}").unwrap();
+
+        assert!(Manifest::load(temp_dir.path()).unwrap().is_none());
+
+        Manifest::generate(temp_dir.path())
+            .unwrap()
+            .save(temp_dir.path())
+            .unwrap();
+        let manifest = Manifest::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        assert!(manifest.verify(temp_dir.path()).unwrap().is_verified());
+
+        fs::write(skill_dir.join("SKILL.md"), "# Forms (edited)").unwrap();
+        fs::remove_file(skill_dir.join("_meta.json")).unwrap();
+        fs::write(skill_dir.join("extra.md"), "new").unwrap();
+
+        let report = manifest.verify(temp_dir.path()).unwrap();
+        assert_eq!(report.status, IntegrityStatus::Tampered);
+        assert_eq!(report.modified, vec!["forms/SKILL.md"]);
+        assert_eq!(report.missing, vec!["forms/_meta.json"]);
+        assert_eq!(report.unexpected, vec!["forms/extra.md"]);
+    }
+}
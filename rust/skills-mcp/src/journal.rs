@@ -0,0 +1,654 @@
+//! Journal of skill mutations for replay.
+//!
+//! Where the audit log says who changed which skill, the journal records
+//! what changed: every file written or removed by a mutation, with the full
+//! contents. Replaying a journal onto an empty directory rebuilds the skills
+//! directory file for file, which makes agent-driven edits reproducible.
+//!
+//! Each mutation is written ahead: an intent line naming the mutation is
+//! appended before its files are touched, and the entry with its changes
+//! once they are on disk, before the edit lock is released and the index
+//! reloaded. The entry compares the paths the mutation touched (a skill's
+//! directory, or the whole library for a rename, which rewrites links in
+//! other skills) against the state last recorded for them. Changes made
+//! outside the server (an editor, `git pull`) are recorded when journaling
+//! starts, as an entry without an action; if the journal ends with an
+//! intent, the server stopped mid-mutation and that entry is recorded in
+//! the mutation's name instead.
+//!
+//! Top-level dot-files and underscore-prefixed entries (server state, cold
+//! storage, backups) are not journaled, as they aren't part of the library.
+//! Empty directories aren't either; replay removes directories left empty.
+
+use std::collections::{btree_map, BTreeMap};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use crate::audit::{AuditAction, AuditEntry};
+
+/// Default journal file name, kept at the root of the skills directory.
+pub const JOURNAL_FILE: &str = ".skills-journal.jsonl";
+
+/// A file written or removed by a mutation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum FileChange {
+    /// A text file was created or changed.
+    Write {
+        /// Path relative to the skills directory, with `/` separators.
+        path: String,
+        /// New contents.
+        content: String,
+    },
+    /// A file that isn't UTF-8 was created or changed.
+    WriteBinary {
+        /// Path relative to the skills directory, with `/` separators.
+        path: String,
+        /// New contents, hex-encoded.
+        hex: String,
+    },
+    /// A file was removed.
+    Remove {
+        /// Path relative to the skills directory, with `/` separators.
+        path: String,
+    },
+}
+
+impl FileChange {
+    /// Path of the file changed.
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Write { path, .. } | Self::WriteBinary { path, .. } | Self::Remove { path } => path,
+        }
+    }
+}
+
+/// One journaled mutation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Position in the journal, from 1.
+    pub seq: u64,
+
+    /// When the changes were recorded.
+    #[serde(with = "crate::models::timestamp")]
+    pub at: DateTime<Utc>,
+
+    /// Who made the mutation, as in the audit log; `server` for changes
+    /// found on disk.
+    pub actor: String,
+
+    /// What was done; None for changes made outside the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<AuditAction>,
+
+    /// Skill the mutation was made to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+
+    /// Whether this announces a mutation about to be made rather than
+    /// recording one; the entry recording it follows with the same `seq`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub intent: bool,
+
+    /// Files written and removed, in path order; empty for intents.
+    #[serde(default)]
+    pub changes: Vec<FileChange>,
+}
+
+/// What a replay did.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReplayReport {
+    /// Entries applied.
+    pub entries: usize,
+    /// Files written.
+    pub written: usize,
+    /// Files removed.
+    pub removed: usize,
+    /// Sequence number of the last entry applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_seq: Option<u64>,
+}
+
+/// The mutation journal of a skills directory.
+#[derive(Debug, Default)]
+pub struct Journal {
+    state: Mutex<Option<JournalState>>,
+}
+
+/// An open journal and the directory state it has recorded.
+#[derive(Debug)]
+struct JournalState {
+    path: PathBuf,
+    skills_dir: PathBuf,
+    seq: u64,
+    /// SHA-256 of each recorded file, by relative path.
+    files: BTreeMap<String, [u8; 32]>,
+}
+
+impl Journal {
+    /// Create a journal that records nothing until [`Journal::write_to`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append mutations of `skills_dir` to the JSON Lines file at `path`
+    /// from now on.
+    ///
+    /// An existing journal is continued: its entries are folded to learn
+    /// what it last recorded, and anything that changed on disk since is
+    /// appended as an entry of its own, in the name of the mutation that
+    /// was interrupted if the journal ends with an intent.
+    pub fn write_to(&self, path: impl Into<PathBuf>, skills_dir: impl Into<PathBuf>) -> Result<(), JournalError> {
+        let path = path.into();
+        let entries = if path.exists() { read(&path)? } else { Vec::new() };
+        let (seq, files) = fold(&entries)?;
+        let mut state = JournalState {
+            skills_dir: skills_dir.into(),
+            seq,
+            files,
+            path,
+        };
+
+        let library = [PathBuf::new()];
+        match entries.last().filter(|e| e.intent) {
+            Some(e) => state.append(&e.actor, e.action, e.skill.as_deref(), &library)?,
+            None => state.append("server", None, None, &library)?,
+        }
+        *self.state.lock() = Some(state);
+        Ok(())
+    }
+
+    /// Path of the journal file, if journaling.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.state.lock().as_ref().map(|s| s.path.clone())
+    }
+
+    /// Announce the mutation `entry` describes, before any of its files
+    /// are written.
+    ///
+    /// Failures are logged, as for [`Journal::record`].
+    pub fn begin(&self, entry: &AuditEntry) {
+        if let Some(state) = self.state.lock().as_mut() {
+            if let Err(e) = state.announce(&entry.actor, entry.action, &entry.skill) {
+                tracing::warn!("Failed to write journal {:?}: {}", state.path, e);
+            }
+        }
+    }
+
+    /// Record the files under `paths` changed by the mutation `entry`
+    /// describes. Nothing is written when no file changed; paths outside
+    /// the skills directory are ignored.
+    ///
+    /// Failures are logged, never returned: a mutation that already
+    /// happened must not fail because journaling did.
+    pub fn record(&self, entry: &AuditEntry, paths: &[PathBuf]) {
+        if let Some(state) = self.state.lock().as_mut() {
+            let scope: Vec<PathBuf> = paths
+                .iter()
+                .filter_map(|p| p.strip_prefix(&state.skills_dir).ok().map(Path::to_path_buf))
+                .collect();
+            if scope.is_empty() {
+                return;
+            }
+            if let Err(e) = state.append(&entry.actor, Some(entry.action), Some(&entry.skill), &scope) {
+                tracing::warn!("Failed to write journal {:?}: {}", state.path, e);
+            }
+        }
+    }
+}
+
+impl JournalState {
+    /// Append an intent for a mutation about to be made.
+    fn announce(&mut self, actor: &str, action: AuditAction, skill: &str) -> Result<(), JournalError> {
+        self.write_line(&JournalEntry {
+            seq: self.seq + 1,
+            at: Utc::now(),
+            actor: actor.to_string(),
+            action: Some(action),
+            // Imports may only learn the skill's name once they've run
+            skill: Some(skill.to_string()).filter(|s| !s.is_empty()),
+            intent: true,
+            changes: Vec::new(),
+        })
+    }
+
+    /// Append an entry with the changes under `scope`, relative paths of
+    /// which an empty one is the whole library, since they were last
+    /// recorded, if any.
+    fn append(
+        &mut self,
+        actor: &str,
+        action: Option<AuditAction>,
+        skill: Option<&str>,
+        scope: &[PathBuf],
+    ) -> Result<(), JournalError> {
+        let in_scope = |path: &str| scope.iter().any(|s| Path::new(path).starts_with(s));
+        let mut current = BTreeMap::new();
+        let mut changes = Vec::new();
+        for (path, bytes) in library_files(&self.skills_dir, scope)? {
+            let hash: [u8; 32] = Sha256::digest(&bytes).into();
+            if self.files.get(&path) != Some(&hash) {
+                changes.push(match String::from_utf8(bytes) {
+                    Ok(content) => FileChange::Write { path: path.clone(), content },
+                    Err(e) => FileChange::WriteBinary {
+                        path: path.clone(),
+                        hex: encode_hex(e.as_bytes()),
+                    },
+                });
+            }
+            current.insert(path, hash);
+        }
+        changes.extend(
+            self.files
+                .keys()
+                .filter(|path| in_scope(path) && !current.contains_key(*path))
+                .map(|path| FileChange::Remove { path: path.clone() }),
+        );
+        if changes.is_empty() {
+            return Ok(());
+        }
+        changes.sort_by(|a, b| a.path().cmp(b.path()));
+
+        let entry = JournalEntry {
+            seq: self.seq + 1,
+            at: Utc::now(),
+            actor: actor.to_string(),
+            action,
+            skill: skill.map(str::to_string),
+            intent: false,
+            changes,
+        };
+        self.write_line(&entry)?;
+
+        self.seq = entry.seq;
+        self.files.retain(|path, _| !in_scope(path));
+        self.files.extend(current);
+        Ok(())
+    }
+
+    fn write_line(&self, entry: &JournalEntry) -> Result<(), JournalError> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Sequence number of the last entry and SHA-256 of each file the
+/// entries leave behind.
+fn fold(entries: &[JournalEntry]) -> Result<(u64, BTreeMap<String, [u8; 32]>), JournalError> {
+    let mut seq = 0;
+    let mut files = BTreeMap::new();
+    for entry in entries.iter().filter(|e| !e.intent) {
+        seq = entry.seq;
+        for change in &entry.changes {
+            match change {
+                FileChange::Write { path, content } => {
+                    files.insert(path.clone(), Sha256::digest(content).into());
+                }
+                FileChange::WriteBinary { path, hex } => {
+                    let bytes = decode_hex(hex).ok_or(JournalError::Corrupt(entry.seq))?;
+                    files.insert(path.clone(), Sha256::digest(bytes).into());
+                }
+                FileChange::Remove { path } => {
+                    files.remove(path);
+                }
+            }
+        }
+    }
+    Ok((seq, files))
+}
+
+/// How a journal compares with the skills directory it records.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct JournalCheck {
+    /// Entries in the journal, not counting intents.
+    pub entries: usize,
+    /// Sequence numbers that don't follow the entry before them.
+    pub out_of_sequence: Vec<u64>,
+    /// Library files that differ from what the journal last recorded,
+    /// including files it records that are gone. They are journaled the
+    /// next time journaling starts.
+    pub unrecorded: Vec<String>,
+}
+
+impl JournalCheck {
+    /// Whether replaying the journal would rebuild the directory as it is.
+    pub fn is_consistent(&self) -> bool {
+        self.out_of_sequence.is_empty() && self.unrecorded.is_empty()
+    }
+}
+
+/// Compare the journal at `journal` with the skills directory.
+pub fn check(journal: &Path, skills_dir: &Path) -> Result<JournalCheck, JournalError> {
+    let entries: Vec<JournalEntry> = read(journal)?.into_iter().filter(|e| !e.intent).collect();
+    let (_, mut recorded) = fold(&entries)?;
+
+    let out_of_sequence = entries
+        .iter()
+        .enumerate()
+        .filter(|(i, entry)| entry.seq != *i as u64 + 1)
+        .map(|(_, entry)| entry.seq)
+        .collect();
+
+    let mut unrecorded = Vec::new();
+    for (path, bytes) in library_files(skills_dir, &[PathBuf::new()])? {
+        let hash: [u8; 32] = Sha256::digest(&bytes).into();
+        if recorded.remove(&path) != Some(hash) {
+            unrecorded.push(path);
+        }
+    }
+    unrecorded.extend(recorded.into_keys());
+    unrecorded.sort();
+
+    Ok(JournalCheck {
+        entries: entries.len(),
+        out_of_sequence,
+        unrecorded,
+    })
+}
+
+/// Files of the library under `skills_dir` within `scope`, by relative
+/// path. An empty path in `scope` is the whole library.
+fn library_files(skills_dir: &Path, scope: &[PathBuf]) -> Result<Vec<(String, Vec<u8>)>, JournalError> {
+    let mut files = BTreeMap::new();
+    if !skills_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    for root in scope {
+        // Server state and dot-files at the top level aren't library files
+        let top = root.components().next().map(|c| c.as_os_str().to_string_lossy());
+        if top.is_some_and(|name| name.starts_with('.') || name.starts_with('_')) {
+            continue;
+        }
+        let walker = WalkDir::new(skills_dir.join(root))
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name().to_string_lossy();
+                e.path().parent() != Some(skills_dir) || !(name.starts_with('.') || name.starts_with('_'))
+            });
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                // A path the mutation removed
+                Err(e) if e.io_error().is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound) => continue,
+                Err(e) => return Err(JournalError::Io(e.into())),
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(skills_dir).unwrap_or(entry.path());
+            let path = relative.to_string_lossy().replace('\\', "/");
+            if let btree_map::Entry::Vacant(slot) = files.entry(path) {
+                slot.insert(fs::read(entry.path())?);
+            }
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// Read the entries of a journal file, in order.
+pub fn read(path: &Path) -> Result<Vec<JournalEntry>, JournalError> {
+    let mut entries = Vec::new();
+    for line in BufReader::new(fs::File::open(path)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line)?);
+    }
+    Ok(entries)
+}
+
+/// Apply the entries of the journal at `journal` recorded at or after
+/// `from` (all of them when None) to `target`, in order.
+///
+/// Replaying a whole journal onto an empty directory reproduces the skills
+/// directory it was recorded from. Replaying from a point in time needs a
+/// directory as it was at that time, e.g. a restored backup.
+pub fn replay(journal: &Path, target: &Path, from: Option<DateTime<Utc>>) -> Result<ReplayReport, JournalError> {
+    let mut report = ReplayReport::default();
+    fs::create_dir_all(target)?;
+
+    for entry in read(journal)? {
+        if entry.intent || from.is_some_and(|from| entry.at < from) {
+            continue;
+        }
+        for change in &entry.changes {
+            let path = target.join(safe_path(change.path()).ok_or_else(|| JournalError::InvalidPath(change.path().to_string()))?);
+            match change {
+                FileChange::Write { content, .. } => {
+                    write_file(&path, content.as_bytes())?;
+                    report.written += 1;
+                }
+                FileChange::WriteBinary { hex, .. } => {
+                    let bytes = decode_hex(hex).ok_or(JournalError::Corrupt(entry.seq))?;
+                    write_file(&path, &bytes)?;
+                    report.written += 1;
+                }
+                FileChange::Remove { .. } => {
+                    match fs::remove_file(&path) {
+                        Ok(()) => report.removed += 1,
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(e) => return Err(e.into()),
+                    }
+                    remove_empty_parents(&path, target);
+                }
+            }
+        }
+        report.entries += 1;
+        report.last_seq = Some(entry.seq);
+    }
+    Ok(report)
+}
+
+/// A journaled path as a relative path that stays inside the target.
+fn safe_path(path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then(|| path.to_path_buf())
+        .filter(|p| p.components().next().is_some())
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes)
+}
+
+/// Remove the directories above `path` that are left empty, up to `root`.
+fn remove_empty_parents(path: &Path, root: &Path) {
+    let mut dir = path.parent();
+    while let Some(d) = dir.filter(|d| *d != root && d.starts_with(root)) {
+        if fs::remove_dir(d).is_err() {
+            break;
+        }
+        dir = d.parent();
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Journal errors.
+#[derive(Debug, thiserror::Error)]
+pub enum JournalError {
+    /// The journal or a skills file couldn't be read or written.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// An entry couldn't be serialized or parsed.
+    #[error("Serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    /// A journaled path would leave the target directory.
+    #[error("Invalid path in journal: {0}")]
+    InvalidPath(String),
+
+    /// An entry's binary contents aren't valid hex.
+    #[error("Corrupt contents in journal entry {0}")]
+    Corrupt(u64),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn snapshot(dir: &Path) -> Vec<(String, Vec<u8>)> {
+        library_files(dir, &[PathBuf::new()]).unwrap()
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let skills = TempDir::new().unwrap();
+        fs::create_dir_all(skills.path().join("forms")).unwrap();
+        fs::write(skills.path().join("forms/SKILL.md"), "# Forms\n").unwrap();
+        fs::write(skills.path().join(".skills-audit.jsonl"), "{}\n").unwrap();
+
+        let path = skills.path().join(JOURNAL_FILE);
+        let journal = Journal::new();
+        journal.write_to(&path, skills.path()).unwrap();
+
+        fs::create_dir_all(skills.path().join("testing/assets")).unwrap();
+        fs::write(skills.path().join("testing/SKILL.md"), "# Testing\n").unwrap();
+        fs::write(skills.path().join("testing/assets/logo.bin"), [0xff, 0x00, 0x7f]).unwrap();
+        let testing = [skills.path().join("testing")];
+        journal.record(&AuditEntry::new("key:abc", AuditAction::Create, "testing"), &testing);
+
+        // No change, no entry
+        journal.record(&AuditEntry::new("mcp", AuditAction::Archive, "testing"), &testing);
+
+        fs::write(skills.path().join("forms/SKILL.md"), "# Forms\n\nUse zod.\n").unwrap();
+        fs::remove_dir_all(skills.path().join("testing")).unwrap();
+        let library = [skills.path().to_path_buf()];
+        journal.record(&AuditEntry::new("key:abc", AuditAction::Delete, "testing"), &library);
+
+        let entries = read(&path).unwrap();
+        assert_eq!(entries.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(entries[0].action, None);
+        assert_eq!(entries[0].changes.len(), 1);
+        assert_eq!(entries[1].skill.as_deref(), Some("testing"));
+        assert!(matches!(&entries[1].changes[1], FileChange::WriteBinary { hex, .. } if hex == "ff007f"));
+        assert_eq!(entries[2].changes.len(), 3);
+
+        let target = TempDir::new().unwrap();
+        let report = replay(&path, target.path(), None).unwrap();
+        assert_eq!((report.entries, report.written, report.removed), (3, 4, 2));
+        assert_eq!(snapshot(target.path()), snapshot(skills.path()));
+        assert!(!target.path().join("testing").exists());
+
+        // Continuing a journal picks up where it left off
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(check(&path, skills.path()).unwrap().is_consistent());
+        fs::write(skills.path().join("forms/extra.md"), "Extra\n").unwrap();
+        assert_eq!(check(&path, skills.path()).unwrap().unrecorded, vec!["forms/extra.md"]);
+        let journal = Journal::new();
+        journal.write_to(&path, skills.path()).unwrap();
+        let entries = read(&path).unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[3].changes, vec![FileChange::Write {
+            path: "forms/extra.md".to_string(),
+            content: "Extra\n".to_string(),
+        }]);
+
+        // Replaying from a point in time skips earlier entries
+        let target = TempDir::new().unwrap();
+        let report = replay(&path, target.path(), Some(entries[3].at)).unwrap();
+        assert_eq!(report.last_seq, Some(4));
+        assert!(target.path().join("forms/extra.md").is_file());
+        assert!(!target.path().join("forms/SKILL.md").exists());
+    }
+
+    #[test]
+    fn test_write_ahead_and_scope() {
+        let skills = TempDir::new().unwrap();
+        fs::create_dir_all(skills.path().join("forms")).unwrap();
+        fs::write(skills.path().join("forms/SKILL.md"), "# Forms\n").unwrap();
+
+        let path = skills.path().join(JOURNAL_FILE);
+        let journal = Journal::new();
+        journal.write_to(&path, skills.path()).unwrap();
+
+        // Only the paths a mutation touched are recorded with it
+        let entry = AuditEntry::new("key:abc", AuditAction::Update, "forms");
+        journal.begin(&entry);
+        fs::write(skills.path().join("forms/SKILL.md"), "# Forms v2\n").unwrap();
+        fs::write(skills.path().join("notes.md"), "Edited by hand\n").unwrap();
+        journal.record(&entry, &[skills.path().join("forms")]);
+
+        let entries = read(&path).unwrap();
+        assert_eq!(entries.iter().map(|e| (e.seq, e.intent)).collect::<Vec<_>>(), vec![
+            (1, false),
+            (2, true),
+            (2, false)
+        ]);
+        assert_eq!(entries[2].changes.len(), 1);
+        assert_eq!(entries[2].changes[0].path(), "forms/SKILL.md");
+        assert_eq!(check(&path, skills.path()).unwrap().unrecorded, vec!["notes.md"]);
+
+        // A mutation interrupted after its intent is recorded in its name
+        let entry = AuditEntry::new("key:abc", AuditAction::Delete, "forms");
+        journal.begin(&entry);
+        fs::remove_dir_all(skills.path().join("forms")).unwrap();
+        let journal = Journal::new();
+        journal.write_to(&path, skills.path()).unwrap();
+
+        let entries = read(&path).unwrap();
+        let last = entries.last().unwrap();
+        assert_eq!((last.seq, last.intent, last.action), (3, false, Some(AuditAction::Delete)));
+        assert_eq!(last.changes.len(), 2);
+        let report = check(&path, skills.path()).unwrap();
+        assert_eq!(report.entries, 3);
+        assert!(report.is_consistent());
+
+        let target = TempDir::new().unwrap();
+        let report = replay(&path, target.path(), None).unwrap();
+        assert_eq!(report.entries, 3);
+        assert_eq!(snapshot(target.path()), snapshot(skills.path()));
+    }
+
+    #[test]
+    fn test_replay_rejects_escaping_paths() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(JOURNAL_FILE);
+        let entry = JournalEntry {
+            seq: 1,
+            at: Utc::now(),
+            actor: "server".to_string(),
+            action: None,
+            skill: None,
+            intent: false,
+            changes: vec![FileChange::Remove { path: "../outside".to_string() }],
+        };
+        fs::write(&path, serde_json::to_string(&entry).unwrap()).unwrap();
+
+        let result = replay(&path, &temp.path().join("target"), None);
+        assert!(matches!(result, Err(JournalError::InvalidPath(_))));
+    }
+}
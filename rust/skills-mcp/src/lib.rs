@@ -48,30 +48,37 @@
 //! # Quick Start
 //!
 //! ```rust,no_run
-//! use std::sync::Arc;
-//! use skills_mcp::index::SkillIndexer;
-//! use skills_mcp::search::SearchService;
 //! use skills_mcp::models::SearchOptions;
+//! use skills_mcp::Library;
 //!
-//! // Create an indexer pointing to your skills directory
-//! let indexer = Arc::new(SkillIndexer::new("./skills"));
-//! indexer.reload().unwrap();
-//!
-//! // Create a search service
-//! let search = SearchService::new(indexer);
+//! // Index your skills directory
+//! let library = Library::open("./skills").unwrap();
 //!
 //! // Search for skills
-//! let results = search.search_skills("forms", SearchOptions::with_limit(5));
+//! let results = library.search("forms", SearchOptions::with_limit(5));
 //! for result in &results.results {
 //!     println!("{}: {:.2}", result.domain, result.score);
 //! }
 //! ```
 //!
+//! # Feature Flags
+//!
+//! - `mcp` (default): the MCP server over stdio ([`mcp`]) and weekly
+//!   reports ([`report`])
+//! - `api` (default): the HTTP API server ([`api`]), including MCP over
+//!   HTTP; implies `mcp`
+//! - `graphql`: a GraphQL endpoint at `/api/graphql` over skills,
+//!   sub-skills, references, stats, and search; implies `api`
+//!
+//! With `default-features = false` the crate is a library for indexing,
+//! search, and validation, without axum or the servers. See [`Library`].
+//!
 //! # Running the Servers
 //!
 //! ## MCP Server (for Claude Desktop)
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "mcp")] {
 //! use skills_mcp::mcp::McpServer;
 //!
 //! #[tokio::main]
@@ -79,11 +86,13 @@
 //!     let server = McpServer::new("./skills");
 //!     server.run().await.unwrap();
 //! }
+//! # }
 //! ```
 //!
 //! ## HTTP API Server
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "api")] {
 //! use skills_mcp::api::ApiServer;
 //!
 //! #[tokio::main]
@@ -91,22 +100,66 @@
 //!     let server = ApiServer::new("./skills");
 //!     server.run().await.unwrap();
 //! }
+//! # }
 //! ```
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod abuse;
+pub mod analysis;
+pub mod analytics;
+pub mod audit;
+pub mod backup;
+pub mod bundles;
+#[cfg(feature = "api")]
 pub mod api;
+pub mod cold;
+pub mod config;
+pub mod context;
+pub mod deprecation;
+pub mod diff;
+pub mod doctor;
+pub mod events;
+pub mod export;
+pub mod fetch;
+pub mod github;
+pub mod import;
 pub mod index;
+pub mod integrity;
+pub mod journal;
+pub mod lifecycle;
+pub mod lint;
+pub mod logging;
+pub mod maintenance;
+pub mod markdown;
+#[cfg(feature = "mcp")]
 pub mod mcp;
+pub mod merge;
 pub mod models;
+pub mod notify;
+pub mod patch;
+pub mod replace;
+#[cfg(feature = "mcp")]
+pub mod report;
+pub mod scaffold;
+pub mod schedule;
+pub mod scripts;
 pub mod search;
 pub mod validation;
 
+mod library;
+#[cfg(test)]
+mod test_support;
+
+pub use library::Library;
+
 /// Re-export commonly used types.
 pub mod prelude {
+    #[cfg(feature = "api")]
     pub use crate::api::ApiServer;
     pub use crate::index::SkillIndexer;
+    #[cfg(feature = "mcp")]
     pub use crate::mcp::McpServer;
     pub use crate::models::{
         MatchType, SearchOptions, SearchResult, SearchResults, SkillContent, SkillIndex,
@@ -114,6 +167,7 @@ pub mod prelude {
     };
     pub use crate::search::SearchService;
     pub use crate::validation::{validate_meta, validate_skills};
+    pub use crate::Library;
 }
 
 /// Crate version.
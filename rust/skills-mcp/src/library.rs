@@ -0,0 +1,186 @@
+//! Embeddable library facade.
+//!
+//! [`Library`] bundles an indexer and a search service behind one handle,
+//! for applications that want skill indexing and search without running
+//! either server. It needs none of the `api` or `mcp` features:
+//!
+//! ```toml
+//! skills-mcp = { version = "0.1", default-features = false }
+//! ```
+//!
+//! The underlying [`SkillIndexer`] and [`SearchService`] stay available
+//! through [`Library::indexer`] and [`Library::search_service`] for
+//! anything the facade doesn't cover.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::index::{FileWatcher, IndexConfig, IndexError, SkillIndexer, SkillRoot, WatchError};
+use crate::models::{
+    FallbackConfig, FieldBoosts, FusedSearchResults, SearchOptions, SearchResults, SkillContent, SkillMeta,
+    SubSkillContent, ValidationResult,
+};
+use crate::search::SearchService;
+use crate::validation::validate_skills;
+
+/// A skill library: indexed skills and search over them.
+///
+/// ```rust,no_run
+/// use skills_mcp::models::SearchOptions;
+/// use skills_mcp::Library;
+///
+/// let library = Library::open("./skills").unwrap();
+/// for result in library.search("form validation", SearchOptions::with_limit(5)).results {
+///     println!("{}: {:.2}", result.domain, result.score);
+/// }
+/// let skill = library.read_skill("forms").unwrap();
+/// println!("{}", skill.content);
+/// ```
+pub struct Library {
+    indexer: Arc<SkillIndexer>,
+    search: SearchService,
+}
+
+impl Library {
+    /// Create a library over a skills directory without indexing it yet;
+    /// call [`reload`](Self::reload) once configured.
+    pub fn new(skills_dir: impl AsRef<Path>) -> Self {
+        Self::from_indexer(Arc::new(SkillIndexer::new(skills_dir)))
+    }
+
+    /// Create a library over several roots, highest priority first,
+    /// without indexing them yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `roots` is empty.
+    pub fn with_roots(roots: Vec<SkillRoot>) -> Self {
+        Self::from_indexer(Arc::new(SkillIndexer::with_roots(roots)))
+    }
+
+    /// Create a library around an existing indexer.
+    pub fn from_indexer(indexer: Arc<SkillIndexer>) -> Self {
+        let search = SearchService::new(Arc::clone(&indexer));
+        Self { indexer, search }
+    }
+
+    /// Open a skills directory and index it.
+    pub fn open(skills_dir: impl AsRef<Path>) -> Result<Self, IndexError> {
+        let library = Self::new(skills_dir);
+        library.reload()?;
+        Ok(library)
+    }
+
+    /// Set which reference files are indexed. Takes effect on the next
+    /// reload.
+    pub fn with_index_config(self, config: IndexConfig) -> Self {
+        self.indexer.set_index_config(config);
+        self
+    }
+
+    /// Serve a fallback skill for queries nothing matches well.
+    pub fn with_fallback(self, config: FallbackConfig) -> Self {
+        self.search.set_fallback(Some(config));
+        self
+    }
+
+    /// Weight search matches by field; see [`FieldBoosts`].
+    pub fn with_boosts(self, boosts: FieldBoosts) -> Self {
+        self.search.set_boosts(boosts);
+        self
+    }
+
+    /// Rebuild the index from disk.
+    pub fn reload(&self) -> Result<(), IndexError> {
+        self.indexer.reload()
+    }
+
+    /// Watch the skill roots and update the index as files change. The
+    /// returned watcher stops when dropped.
+    pub fn watch(&self) -> Result<FileWatcher, WatchError> {
+        let mut watcher = FileWatcher::new(Arc::clone(&self.indexer))?;
+        for root in self.indexer.roots() {
+            watcher.watch(&root.path)?;
+        }
+        Ok(watcher)
+    }
+
+    /// Metadata of every indexed skill, sorted by name.
+    pub fn skills(&self) -> Vec<SkillMeta> {
+        self.indexer.get_skill_index().skills
+    }
+
+    /// Metadata of a skill, by name or alias.
+    pub fn skill(&self, name: &str) -> Option<SkillMeta> {
+        self.indexer.get_skill_meta(name)
+    }
+
+    /// Read a skill's SKILL.md.
+    pub fn read_skill(&self, name: &str) -> Result<SkillContent, IndexError> {
+        self.indexer.read_skill_content(name)
+    }
+
+    /// Read a sub-skill's file.
+    pub fn read_sub_skill(&self, skill: &str, sub_skill: &str) -> Result<SubSkillContent, IndexError> {
+        self.indexer.read_sub_skill_content(skill, sub_skill)
+    }
+
+    /// Search skill metadata and content. The query may use the
+    /// [query language](crate::search::Query).
+    pub fn search(&self, query: &str, options: SearchOptions) -> SearchResults {
+        let mut results = self.search.search_all(query, options);
+        self.search.apply_fallback(&mut results);
+        results
+    }
+
+    /// Search for skills relevant to a task description, fusing the
+    /// results of several query variants.
+    pub fn search_for_task(&self, task: &str, options: SearchOptions) -> FusedSearchResults {
+        let mut results = self.search.search_fused(task, options);
+        self.search.apply_fallback_if_empty(&mut results.results);
+        results
+    }
+
+    /// Validate every skill's metadata, files, and links.
+    pub fn validate(&self) -> ValidationResult {
+        validate_skills(Arc::clone(&self.indexer))
+    }
+
+    /// The underlying indexer.
+    pub fn indexer(&self) -> &Arc<SkillIndexer> {
+        &self.indexer
+    }
+
+    /// The underlying search service.
+    pub fn search_service(&self) -> &SearchService {
+        &self.search
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_skill;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_library() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Form validation patterns", "tags": ["react"]}"#,
+            "# Forms\n\nValidate with zod.\n",
+        );
+
+        let library = Library::open(temp_dir.path()).unwrap();
+        assert_eq!(library.skills().len(), 1);
+        assert_eq!(library.skill("forms").unwrap().tags, vec!["react"]);
+        assert!(library.read_skill("forms").unwrap().content.contains("zod"));
+        assert!(library.read_skill("tables").is_err());
+
+        let results = library.search("zod", SearchOptions::with_limit(5));
+        assert_eq!(results.results[0].domain, "forms");
+        assert!(library.validate().valid);
+    }
+}
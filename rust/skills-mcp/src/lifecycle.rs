@@ -0,0 +1,834 @@
+//! Skill lifecycle operations that restructure the skills directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use crate::cold::ColdStorage;
+use crate::index::TagConfig;
+use crate::markdown::rewrite_links;
+use crate::models::{ErrorCode, SkillMeta};
+use crate::validation::validate_meta;
+
+/// Outcome of renaming a skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct RenameReport {
+    /// Previous skill name.
+    pub old_name: String,
+
+    /// New skill name.
+    pub new_name: String,
+
+    /// Number of markdown link targets rewritten.
+    pub links_rewritten: usize,
+
+    /// Markdown files whose links were rewritten, relative to the skills
+    /// directory (after the rename).
+    pub files_updated: Vec<String>,
+
+    /// Skills whose `depends_on` or `see_also` referenced the old name.
+    pub dependents_updated: Vec<String>,
+}
+
+/// Rename a skill directory and update its `_meta.json`.
+///
+/// Other skills' `depends_on` and `see_also` entries are always updated. When
+/// `rewrite_links` is set, relative markdown links that point into the old
+/// skill directory are rewritten to the new name across the library.
+pub fn rename_skill(
+    skills_dir: &Path,
+    old_name: &str,
+    new_name: &str,
+    rewrite_links: bool,
+) -> Result<RenameReport, LifecycleError> {
+    // Old names may predate the naming rules, but must stay inside the root
+    if !is_relative_name(old_name) {
+        return Err(LifecycleError::Invalid(format!("'{}' is not a skill name", old_name)));
+    }
+    if !skills_dir.join(old_name).join("_meta.json").is_file() {
+        return Err(LifecycleError::NotFound(old_name.to_string()));
+    }
+    check_new_name(skills_dir, new_name)?;
+
+    move_skill(skills_dir, old_name, new_name, rewrite_links)
+}
+
+/// Move a skill directory and update everything that refers to it by name.
+///
+/// `_meta.json` is updated when present; SKILL.md frontmatter is left as is.
+fn move_skill(
+    skills_dir: &Path,
+    old_name: &str,
+    new_name: &str,
+    rewrite_links: bool,
+) -> Result<RenameReport, LifecycleError> {
+    let old_dir = skills_dir.join(old_name);
+    let new_dir = skills_dir.join(new_name);
+
+    // Moving into a collection may need its directory
+    if let Some(parent) = new_dir.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&old_dir, &new_dir)?;
+    if new_dir.join("_meta.json").is_file() {
+        update_meta(&new_dir, |meta| {
+            meta["name"] = Value::String(new_name.to_string());
+        })?;
+    }
+
+    let dependents_updated = rename_dependencies(skills_dir, old_name, new_name)?;
+
+    let (links_rewritten, files_updated) = if rewrite_links {
+        rewrite_skill_links(skills_dir, old_name, new_name)?
+    } else {
+        (0, Vec::new())
+    };
+
+    Ok(RenameReport {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        links_rewritten,
+        files_updated,
+        dependents_updated,
+    })
+}
+
+/// Outcome of duplicating a skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateReport {
+    /// Skill that was copied.
+    pub source_name: String,
+
+    /// Name of the new skill.
+    pub new_name: String,
+
+    /// Files copied, relative to the new skill directory.
+    pub files: Vec<String>,
+}
+
+/// Deep-copy a skill under a new name.
+///
+/// Sub-skills, references, and scripts are copied as-is. The copy's
+/// `_meta.json` gets the new name and `source: "fork:<source_name>"`, and
+/// links the skill makes to its own directory by name are retargeted.
+pub fn duplicate_skill(
+    skills_dir: &Path,
+    source_name: &str,
+    new_name: &str,
+) -> Result<DuplicateReport, LifecycleError> {
+    let source_dir = skills_dir.join(source_name);
+    let new_dir = skills_dir.join(new_name);
+
+    if !source_dir.join("_meta.json").is_file() {
+        return Err(LifecycleError::NotFound(source_name.to_string()));
+    }
+    check_new_name(skills_dir, new_name)?;
+
+    let mut files = Vec::new();
+    for entry in WalkDir::new(&source_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let relative = entry.path().strip_prefix(&source_dir).unwrap_or(entry.path());
+        let target = new_dir.join(relative);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &target)?;
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+
+    update_meta(&new_dir, |meta| {
+        meta["name"] = Value::String(new_name.to_string());
+        meta["source"] = Value::String(format!("fork:{}", source_name));
+    })?;
+
+    rewrite_links_in(skills_dir, &new_dir, source_name, new_name)?;
+
+    Ok(DuplicateReport {
+        source_name: source_name.to_string(),
+        new_name: new_name.to_string(),
+        files,
+    })
+}
+
+/// A skill directory whose name breaks the naming rules.
+#[derive(Debug, Clone, Serialize)]
+pub struct NameChange {
+    /// Current directory name (`collection/skill` for nested skills).
+    pub old_name: String,
+
+    /// Normalized name, or None when no valid name can be derived.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+
+    /// Why the rename can't be applied automatically.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<String>,
+}
+
+/// Outcome of normalizing skill directory names.
+#[derive(Debug, Clone, Serialize)]
+pub struct NormalizeReport {
+    /// Whether renames were applied, or only planned.
+    pub applied: bool,
+
+    /// Every directory with an invalid name, in name order.
+    pub changes: Vec<NameChange>,
+
+    /// Renames performed, with the links and dependents they updated.
+    pub renamed: Vec<RenameReport>,
+}
+
+impl NormalizeReport {
+    /// Changes that need a human: no derivable name, or a name clash.
+    pub fn conflicts(&self) -> impl Iterator<Item = &NameChange> {
+        self.changes.iter().filter(|c| c.conflict.is_some())
+    }
+}
+
+/// Plan renames for skill directories whose names break the naming rules
+/// (uppercase, spaces, punctuation, non-ASCII look-alikes).
+///
+/// Nothing is changed on disk. A change has a conflict when no valid name
+/// can be derived or when the normalized name is taken, including by
+/// another planned rename.
+pub fn plan_name_normalization(skills_dir: &Path) -> Result<Vec<NameChange>, LifecycleError> {
+    let mut names: Vec<String> = crate::index::skill_dirs(skills_dir)?
+        .into_iter()
+        .filter(|(_, path)| path.join("_meta.json").is_file() || path.join("SKILL.md").is_file())
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+
+    let mut claimed: Vec<String> = Vec::new();
+    let mut changes = Vec::new();
+    for old_name in names.into_iter().filter(|n| !is_valid_name(n)) {
+        let new_name = Some(normalize_name(&old_name)).filter(|n| is_valid_name(n));
+
+        let conflict = match &new_name {
+            None => Some("no valid name can be derived; rename by hand".to_string()),
+            Some(new_name) if claimed.contains(new_name) => {
+                Some(format!("'{}' is also the normalized name of another skill", new_name))
+            }
+            Some(new_name)
+                if is_taken(skills_dir, &old_name, new_name)
+                    || ColdStorage::new(skills_dir).contains(new_name) =>
+            {
+                Some(format!("'{}' already exists", new_name))
+            }
+            Some(_) => None,
+        };
+        if let Some(new_name) = &new_name {
+            claimed.push(new_name.clone());
+        }
+
+        changes.push(NameChange {
+            old_name,
+            new_name,
+            conflict,
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Rename every skill directory with an invalid name to its normalized name.
+///
+/// With `apply` unset this is a dry run that only reports the plan. When
+/// applying, conflicting changes are skipped; the others are renamed like
+/// [`rename_skill`], updating `depends_on`, `see_also`, and relative links
+/// across the library.
+pub fn normalize_names(skills_dir: &Path, apply: bool) -> Result<NormalizeReport, LifecycleError> {
+    let changes = plan_name_normalization(skills_dir)?;
+    let mut renamed = Vec::new();
+
+    if apply {
+        for change in changes.iter().filter(|c| c.conflict.is_none()) {
+            let Some(new_name) = &change.new_name else {
+                continue;
+            };
+            let mut report = move_skill(skills_dir, &change.old_name, new_name, true)?;
+
+            // Links can only reach names with spaces percent-encoded
+            let encoded = change.old_name.replace(' ', "%20");
+            if encoded != change.old_name {
+                let (count, files) = rewrite_skill_links(skills_dir, &encoded, new_name)?;
+                report.links_rewritten += count;
+                for file in files {
+                    if !report.files_updated.contains(&file) {
+                        report.files_updated.push(file);
+                    }
+                }
+            }
+            renamed.push(report);
+
+            // Don't leave a renamed collection behind empty
+            if let Some((collection, _)) = change.old_name.split_once('/') {
+                let _ = fs::remove_dir(skills_dir.join(collection));
+            }
+        }
+    }
+
+    Ok(NormalizeReport {
+        applied: apply,
+        changes,
+        renamed,
+    })
+}
+
+/// Derive a valid skill name from a legacy directory name.
+///
+/// Look-alike letters from other scripts and accented Latin letters are
+/// folded to ASCII, everything is lowercased, and runs of other characters
+/// become single hyphens. Collection and skill segments are normalized
+/// separately and each is cut to 50 characters.
+pub fn normalize_name(name: &str) -> String {
+    name.split('/')
+        .map(|segment| {
+            let mut out = String::new();
+            for c in segment.chars().flat_map(char::to_lowercase) {
+                match fold_confusable(c) {
+                    Some(ascii) => out.push_str(ascii),
+                    None if c.is_ascii_alphanumeric() => out.push(c),
+                    None if !out.ends_with('-') => out.push('-'),
+                    None => {}
+                }
+            }
+            let mut segment: String = out.trim_matches('-').chars().take(50).collect();
+            segment.truncate(segment.trim_end_matches('-').len());
+            segment
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// ASCII replacement for a lowercase look-alike or accented letter.
+fn fold_confusable(c: char) -> Option<&'static str> {
+    Some(match c {
+        // Cyrillic and Greek homoglyphs
+        'а' | 'α' => "a",
+        'в' | 'β' => "b",
+        'с' | 'ϲ' => "c",
+        'е' | 'ε' | 'ё' => "e",
+        'һ' => "h",
+        'і' | 'ι' | 'ї' => "i",
+        'ј' => "j",
+        'к' | 'κ' => "k",
+        'м' => "m",
+        'н' | 'η' => "n",
+        'о' | 'ο' | 'σ' => "o",
+        'р' | 'ρ' => "p",
+        'ѕ' => "s",
+        'т' | 'τ' => "t",
+        'у' | 'υ' => "y",
+        'х' | 'χ' => "x",
+        // Accented Latin
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => "a",
+        'ç' | 'ć' | 'č' => "c",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' => "e",
+        'ì' | 'í' | 'î' | 'ï' | 'ı' => "i",
+        'ñ' | 'ń' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => "o",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' => "u",
+        'ý' | 'ÿ' => "y",
+        'ž' | 'ź' | 'ż' => "z",
+        'š' | 'ś' => "s",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        // Fullwidth digits and letters
+        '０'..='９' | 'ａ'..='ｚ' => {
+            const ASCII: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+            let i = if c <= '９' {
+                c as usize - '０' as usize
+            } else {
+                10 + c as usize - 'ａ' as usize
+            };
+            &ASCII[i..i + 1]
+        }
+        _ => return None,
+    })
+}
+
+/// Set or clear the `archived` flag in a skill's `_meta.json`.
+///
+/// Returns whether the file changed; unarchiving removes the key rather
+/// than writing `false`.
+pub fn set_archived(skill_dir: &Path, archived: bool) -> Result<bool, LifecycleError> {
+    if !skill_dir.join("_meta.json").is_file() {
+        let name = skill_dir.file_name().unwrap_or_default().to_string_lossy();
+        return Err(LifecycleError::NotFound(name.into_owned()));
+    }
+
+    let mut changed = false;
+    update_meta(skill_dir, |meta| {
+        let Some(object) = meta.as_object_mut() else {
+            return;
+        };
+        let current = object.get("archived").and_then(Value::as_bool).unwrap_or(false);
+        changed = current != archived;
+        if archived {
+            object.insert("archived".to_string(), Value::Bool(true));
+        } else {
+            changed |= object.remove("archived").is_some();
+        }
+    })?;
+    Ok(changed)
+}
+
+/// Replace the tag `from` with `to` in a skill's `_meta.json`.
+///
+/// Tags equal to `from`, or indexed as the same tag under `rules`, are
+/// replaced, and a tag left twice is kept once. Returns whether the file
+/// changed.
+pub fn rename_tag(skill_dir: &Path, from: &str, to: &str, rules: &TagConfig) -> Result<bool, LifecycleError> {
+    if !skill_dir.join("_meta.json").is_file() {
+        let name = skill_dir.file_name().unwrap_or_default().to_string_lossy();
+        return Err(LifecycleError::NotFound(name.into_owned()));
+    }
+
+    let target = rules.normalize(from);
+    let mut changed = false;
+    update_meta(skill_dir, |meta| {
+        let Some(tags) = meta.get_mut("tags").and_then(Value::as_array_mut) else {
+            return;
+        };
+        let mut renamed: Vec<Value> = Vec::with_capacity(tags.len());
+        for tag in tags.drain(..) {
+            let tag = match tag.as_str() {
+                Some(t) if t == from || rules.normalize(t) == target => {
+                    changed |= t != to;
+                    Value::String(to.to_string())
+                }
+                _ => tag,
+            };
+            if renamed.contains(&tag) {
+                changed = true;
+            } else {
+                renamed.push(tag);
+            }
+        }
+        *tags = renamed;
+    })?;
+    Ok(changed)
+}
+
+/// Whether `new_name` exists as a different directory than `old_name`.
+///
+/// On case-insensitive filesystems a case-only rename finds itself.
+fn is_taken(skills_dir: &Path, old_name: &str, new_name: &str) -> bool {
+    let target = skills_dir.join(new_name);
+    target.exists() && fs::canonicalize(&target).ok() != fs::canonicalize(skills_dir.join(old_name)).ok()
+}
+
+/// Whether `name` passes the skill naming rules.
+fn is_valid_name(name: &str) -> bool {
+    let probe = SkillMeta {
+        name: name.to_string(),
+        description: "-".to_string(),
+        ..Default::default()
+    };
+    validate_meta(&probe).is_ok()
+}
+
+/// Whether `name` is a plain relative path (`name` or `collection/name`).
+fn is_relative_name(name: &str) -> bool {
+    let path = Path::new(name);
+    !name.is_empty()
+        && path.components().count() <= 2
+        && path.components().all(|c| matches!(c, std::path::Component::Normal(_)))
+}
+
+/// Check that `name` is valid and free in both the hot and cold tiers.
+fn check_new_name(skills_dir: &Path, name: &str) -> Result<(), LifecycleError> {
+    let probe = SkillMeta {
+        name: name.to_string(),
+        description: "-".to_string(),
+        ..Default::default()
+    };
+    validate_meta(&probe).map_err(|errors| LifecycleError::Invalid(errors.join("; ")))?;
+
+    if skills_dir.join(name).exists() || ColdStorage::new(skills_dir).contains(name) {
+        return Err(LifecycleError::AlreadyExists(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Edit a skill's `_meta.json` in place, preserving unknown fields.
+fn update_meta(skill_dir: &Path, edit: impl FnOnce(&mut Value)) -> Result<(), LifecycleError> {
+    let path = skill_dir.join("_meta.json");
+    let mut meta: Value = serde_json::from_str(&fs::read_to_string(&path)?)
+        .map_err(|e| LifecycleError::Invalid(format!("Failed to parse _meta.json: {}", e)))?;
+
+    edit(&mut meta);
+
+    let json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| LifecycleError::Invalid(format!("Failed to serialize _meta.json: {}", e)))?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+/// Skill directories, including skills nested in collections (excluding
+/// hidden and underscore-prefixed).
+fn skill_dirs(skills_dir: &Path) -> Result<Vec<PathBuf>, LifecycleError> {
+    let mut dirs: Vec<PathBuf> = crate::index::skill_dirs(skills_dir)?
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect();
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Replace `old_name` with `new_name` in every skill's `depends_on` and
+/// `see_also`.
+fn rename_dependencies(
+    skills_dir: &Path,
+    old_name: &str,
+    new_name: &str,
+) -> Result<Vec<String>, LifecycleError> {
+    let mut updated = Vec::new();
+
+    for dir in skill_dirs(skills_dir)? {
+        let Ok(content) = fs::read_to_string(dir.join("_meta.json")) else {
+            continue;
+        };
+        let Ok(meta) = serde_json::from_str::<SkillMeta>(&content) else {
+            continue;
+        };
+        if !meta.depends_on.iter().chain(&meta.see_also).any(|d| d == old_name) {
+            continue;
+        }
+
+        update_meta(&dir, |value| {
+            for field in ["depends_on", "see_also"] {
+                if let Some(names) = value.get_mut(field).and_then(Value::as_array_mut) {
+                    for name in names.iter_mut() {
+                        if name.as_str() == Some(old_name) {
+                            *name = Value::String(new_name.to_string());
+                        }
+                    }
+                }
+            }
+        })?;
+        updated.push(meta.name);
+    }
+
+    Ok(updated)
+}
+
+/// Rewrite relative links that climb to the skills root and enter `old_name`.
+fn rewrite_skill_links(
+    skills_dir: &Path,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(usize, Vec<String>), LifecycleError> {
+    let mut total = 0;
+    let mut files = Vec::new();
+
+    for dir in skill_dirs(skills_dir)? {
+        let (count, updated) = rewrite_links_in(skills_dir, &dir, old_name, new_name)?;
+        total += count;
+        files.extend(updated);
+    }
+
+    Ok((total, files))
+}
+
+/// Rewrite links into `old_name` in the markdown files under one directory.
+fn rewrite_links_in(
+    skills_dir: &Path,
+    dir: &Path,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(usize, Vec<String>), LifecycleError> {
+    let mut total = 0;
+    let mut files = Vec::new();
+
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if !entry.file_type().is_file() || (ext != "md" && ext != "markdown") {
+            continue;
+        }
+
+        // Number of `..` segments needed to reach the skills root
+        let relative = path.strip_prefix(skills_dir).unwrap_or(path);
+        let depth = relative.components().count() - 1;
+
+        let content = fs::read_to_string(path)?;
+        let (rewritten, count) = rewrite_links(&content, |target| {
+            retarget_link(target, depth, old_name, new_name)
+        });
+
+        if count > 0 {
+            fs::write(path, rewritten)?;
+            total += count;
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+
+    Ok((total, files))
+}
+
+/// Rewrite `target` if it climbs exactly `depth` levels and enters `old_name`.
+///
+/// Names may span two segments (`collection/skill`).
+fn retarget_link(target: &str, depth: usize, old_name: &str, new_name: &str) -> Option<String> {
+    let segments: Vec<&str> = target.split('/').collect();
+    let mut ups = 0;
+    for (i, segment) in segments.iter().enumerate() {
+        match *segment {
+            "." => {}
+            ".." => ups += 1,
+            _ => {
+                if ups != depth {
+                    return None;
+                }
+                // Match "old" as whole segments, allowing a #fragment or ?query
+                let rest = segments[i..].join("/");
+                let tail = rest.strip_prefix(old_name)?;
+                if !(tail.is_empty() || tail.starts_with(['/', '#', '?'])) {
+                    return None;
+                }
+                let mut out: Vec<&str> = segments[..i].to_vec();
+                let renamed = format!("{}{}", new_name, tail);
+                out.push(&renamed);
+                return Some(out.join("/"));
+            }
+        }
+    }
+    None
+}
+
+/// Errors that can occur during lifecycle operations.
+#[derive(Debug, thiserror::Error)]
+pub enum LifecycleError {
+    /// The source skill does not exist.
+    #[error("Skill '{0}' not found")]
+    NotFound(String),
+
+    /// The target name is already taken.
+    #[error("Skill '{0}' already exists")]
+    AlreadyExists(String),
+
+    /// A name or metadata file is invalid.
+    #[error("Invalid skill: {0}")]
+    Invalid(String),
+
+    /// Filesystem error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl LifecycleError {
+    /// Machine-readable code of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            LifecycleError::NotFound(_) => ErrorCode::SkillNotFound,
+            LifecycleError::AlreadyExists(_) => ErrorCode::AlreadyExists,
+            LifecycleError::Invalid(_) => ErrorCode::InvalidRequest,
+            LifecycleError::Io(_) => ErrorCode::IoError,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{meta, write_skill};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_retarget_link() {
+        assert_eq!(
+            retarget_link("../forms/SKILL.md#usage", 1, "forms", "web-forms").as_deref(),
+            Some("../web-forms/SKILL.md#usage")
+        );
+        assert_eq!(
+            retarget_link("../../forms", 2, "forms", "web-forms").as_deref(),
+            Some("../../web-forms")
+        );
+        // Wrong depth points somewhere else
+        assert_eq!(retarget_link("../forms/SKILL.md", 2, "forms", "x"), None);
+        assert_eq!(retarget_link("forms/SKILL.md", 1, "forms", "x"), None);
+        // Collection-qualified names span two segments
+        assert_eq!(
+            retarget_link("../../frontend/forms/SKILL.md", 2, "frontend/forms", "web/forms").as_deref(),
+            Some("../../web/forms/SKILL.md")
+        );
+        assert_eq!(retarget_link("../frontend/forms-v2", 1, "frontend/forms", "x"), None);
+    }
+
+    #[test]
+    fn test_rename_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "forms", r#"{"name": "forms", "description": "forms", "custom": 1}"#, "# Forms");
+        write_skill(
+            temp_dir.path(),
+            "testing",
+            r#"{"name": "testing", "description": "testing", "depends_on": ["forms"], "see_also": ["forms"]}"#,
+            "See [forms](../forms/SKILL.md).",
+        );
+
+        let report = rename_skill(temp_dir.path(), "forms", "web-forms", true).unwrap();
+        assert_eq!(report.links_rewritten, 1);
+        assert_eq!(report.files_updated, vec!["testing/SKILL.md"]);
+        assert_eq!(report.dependents_updated, vec!["testing"]);
+
+        let meta: Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join("web-forms/_meta.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(meta["name"], "web-forms");
+        assert_eq!(meta["custom"], 1);
+        assert!(!temp_dir.path().join("forms").exists());
+
+        let testing_meta: Value = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join("testing/_meta.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(testing_meta["see_also"][0], "web-forms");
+
+        let testing = fs::read_to_string(temp_dir.path().join("testing/SKILL.md")).unwrap();
+        assert_eq!(testing, "See [forms](../web-forms/SKILL.md).");
+
+        assert!(matches!(
+            rename_skill(temp_dir.path(), "../web-forms", "escaped", false),
+            Err(LifecycleError::Invalid(_))
+        ));
+
+        let result = rename_skill(temp_dir.path(), "web-forms", "testing", true);
+        assert!(matches!(result, Err(LifecycleError::AlreadyExists(_))));
+    }
+
+    #[test]
+    fn test_duplicate_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "forms", "tags": ["input"]}"#,
+            "See [notes](../forms/references/notes.md).",
+        );
+        fs::create_dir_all(temp_dir.path().join("forms/references")).unwrap();
+        fs::write(temp_dir.path().join("forms/references/notes.md"), "notes").unwrap();
+
+        let report = duplicate_skill(temp_dir.path(), "forms", "forms-v2").unwrap();
+        assert_eq!(report.files, vec!["SKILL.md", "_meta.json", "references/notes.md"]);
+
+        let meta: SkillMeta = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join("forms-v2/_meta.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(meta.name, "forms-v2");
+        assert_eq!(meta.source.as_deref(), Some("fork:forms"));
+        assert_eq!(meta.tags, vec!["input"]);
+
+        // The copy links to itself; the original is untouched
+        let copy = fs::read_to_string(temp_dir.path().join("forms-v2/SKILL.md")).unwrap();
+        assert_eq!(copy, "See [notes](../forms-v2/references/notes.md).");
+        let original = fs::read_to_string(temp_dir.path().join("forms/SKILL.md")).unwrap();
+        assert!(original.contains("../forms/"));
+    }
+
+    #[test]
+    fn test_set_archived() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "forms", r#"{"name": "forms", "description": "forms", "custom": 1}"#, "# Forms");
+        let skill_dir = temp_dir.path().join("forms");
+        let read = || -> Value {
+            serde_json::from_str(&fs::read_to_string(skill_dir.join("_meta.json")).unwrap()).unwrap()
+        };
+
+        assert!(set_archived(&skill_dir, true).unwrap());
+        assert_eq!(read()["archived"], true);
+        assert_eq!(read()["custom"], 1);
+        assert!(!set_archived(&skill_dir, true).unwrap());
+
+        assert!(set_archived(&skill_dir, false).unwrap());
+        assert!(read().get("archived").is_none());
+        assert!(!set_archived(&skill_dir, false).unwrap());
+
+        let missing = set_archived(&temp_dir.path().join("tables"), true);
+        assert!(matches!(missing, Err(LifecycleError::NotFound(name)) if name == "tables"));
+    }
+
+    #[test]
+    fn test_rename_tag() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "forms", "tags": ["Tests", "react", "testing"]}"#,
+            "# Forms",
+        );
+        let skill_dir = temp_dir.path().join("forms");
+        let tags = || -> Value {
+            let meta: Value = serde_json::from_str(&fs::read_to_string(skill_dir.join("_meta.json")).unwrap()).unwrap();
+            meta["tags"].clone()
+        };
+        let rules = TagConfig {
+            lowercase: true,
+            ..Default::default()
+        };
+
+        assert!(rename_tag(&skill_dir, "tests", "testing", &rules).unwrap());
+        assert_eq!(tags(), serde_json::json!(["testing", "react"]));
+        assert!(!rename_tag(&skill_dir, "vue", "frontend", &rules).unwrap());
+        assert!(rename_tag(&skill_dir, "react", "frontend", &TagConfig::default()).unwrap());
+        assert_eq!(tags(), serde_json::json!(["testing", "frontend"]));
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("My Forms"), "my-forms");
+        assert_eq!(normalize_name("API_Design  (v2)"), "api-design-v2");
+        // Cyrillic "а" and "о" look like Latin letters
+        assert_eq!(normalize_name("fоrmа"), "forma");
+        assert_eq!(normalize_name("Café/Crème Brûlée"), "cafe/creme-brulee");
+        assert_eq!(normalize_name("ｆｏｒｍｓ"), "forms");
+        assert_eq!(normalize_name("日本語"), "");
+    }
+
+    #[test]
+    fn test_normalize_names() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "My Forms", &meta("My Forms", "My Forms"), "# Forms");
+        write_skill(
+            temp_dir.path(),
+            "testing",
+            r#"{"name": "testing", "description": "testing", "depends_on": ["My Forms"]}"#,
+            "See [forms](../My%20Forms/SKILL.md).",
+        );
+        write_skill(temp_dir.path(), "Testing", &meta("Testing", "Testing"), "# Clash");
+        write_skill(temp_dir.path(), "日本語", &meta("日本語", "日本語"), "# Japanese");
+
+        // Dry run changes nothing
+        let report = normalize_names(temp_dir.path(), false).unwrap();
+        assert!(!report.applied);
+        assert_eq!(report.changes.len(), 3);
+        assert_eq!(report.conflicts().count(), 2);
+        assert!(temp_dir.path().join("My Forms").exists());
+
+        let report = normalize_names(temp_dir.path(), true).unwrap();
+        assert_eq!(report.renamed.len(), 1);
+        assert_eq!(report.renamed[0].new_name, "my-forms");
+        assert_eq!(report.renamed[0].dependents_updated, vec!["testing"]);
+        assert!(!temp_dir.path().join("My Forms").exists());
+
+        let testing = fs::read_to_string(temp_dir.path().join("testing/SKILL.md")).unwrap();
+        assert_eq!(testing, "See [forms](../my-forms/SKILL.md).");
+
+        // Only the conflicts are left
+        let remaining = plan_name_normalization(temp_dir.path()).unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|c| c.conflict.is_some()));
+    }
+}
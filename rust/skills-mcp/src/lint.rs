@@ -0,0 +1,325 @@
+//! Style and quality rules for skills.
+//!
+//! Validation rejects skills that can't be loaded; linting flags skills that
+//! load fine but could be better. Each rule has an ID and a default
+//! severity, both overridable in the `[lint]` section of `skills-mcp.toml`:
+//!
+//! ```toml
+//! [lint]
+//! max_description_chars = 160
+//!
+//! [lint.rules]
+//! no-todo = "error"
+//! h1-title = "off"
+//! ```
+
+use std::collections::HashMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use crate::index::SkillIndexer;
+use crate::markdown::{parse_heading, strip_frontmatter};
+use crate::models::SkillMeta;
+
+/// How seriously a finding is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The rule is disabled.
+    Off,
+    /// Reported for information only.
+    Info,
+    /// Reported, but doesn't fail a run.
+    Warning,
+    /// Fails a run.
+    Error,
+}
+
+/// A lint rule.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Rule {
+    /// Stable rule ID used in configuration.
+    pub id: &'static str,
+
+    /// Severity unless configured otherwise.
+    pub default_severity: Severity,
+
+    /// What the rule checks.
+    pub description: &'static str,
+}
+
+/// Every rule, in the order they run.
+pub const RULES: &[Rule] = &[
+    Rule {
+        id: "description-length",
+        default_severity: Severity::Warning,
+        description: "Description is at most max_description_chars characters",
+    },
+    Rule {
+        id: "h1-title",
+        default_severity: Severity::Warning,
+        description: "SKILL.md has a level-1 title",
+    },
+    Rule {
+        id: "no-todo",
+        default_severity: Severity::Warning,
+        description: "No TODO or FIXME markers in skill files",
+    },
+    Rule {
+        id: "sub-skill-triggers",
+        default_severity: Severity::Warning,
+        description: "Every sub-skill declares trigger keywords",
+    },
+];
+
+/// Lint settings from the `[lint]` configuration section.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LintConfig {
+    /// Longest description accepted by `description-length`.
+    pub max_description_chars: usize,
+
+    /// Severity overrides by rule ID.
+    pub rules: HashMap<String, Severity>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            max_description_chars: 200,
+            rules: HashMap::new(),
+        }
+    }
+}
+
+impl LintConfig {
+    /// Effective severity of a rule.
+    pub fn severity(&self, rule: &Rule) -> Severity {
+        self.rules.get(rule.id).copied().unwrap_or(rule.default_severity)
+    }
+
+    /// Configured rule IDs that don't name a rule.
+    pub fn unknown_rules(&self) -> Vec<&str> {
+        let mut unknown: Vec<&str> = self
+            .rules
+            .keys()
+            .map(String::as_str)
+            .filter(|id| !RULES.iter().any(|r| r.id == *id))
+            .collect();
+        unknown.sort_unstable();
+        unknown
+    }
+}
+
+/// One rule violation.
+#[derive(Debug, Clone, Serialize)]
+pub struct LintFinding {
+    /// Rule ID.
+    pub rule: &'static str,
+
+    /// Configured severity.
+    pub severity: Severity,
+
+    /// Skill the finding is in.
+    pub skill: String,
+
+    /// File relative to the skill directory, when the finding is in a file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    /// 1-based line number, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    /// What is wrong.
+    pub message: String,
+}
+
+/// Result of linting a library.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LintReport {
+    /// Number of skills linted.
+    pub skills_checked: usize,
+
+    /// Findings with error severity.
+    pub errors: usize,
+
+    /// Findings with warning severity.
+    pub warnings: usize,
+
+    /// Every finding, grouped by skill.
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// Whether no finding has error severity.
+    pub fn passed(&self) -> bool {
+        self.errors == 0
+    }
+}
+
+/// Lint every indexed skill.
+pub fn lint_skills(indexer: &SkillIndexer, config: &LintConfig) -> LintReport {
+    let index = indexer.get_skill_index();
+    let mut report = LintReport {
+        skills_checked: index.len(),
+        ..Default::default()
+    };
+
+    for meta in &index.skills {
+        let mut findings = Vec::new();
+        let skill_md = indexer
+            .read_skill_content(&meta.name)
+            .map(|c| c.content)
+            .unwrap_or_default();
+
+        let mut files = vec![("SKILL.md".to_string(), skill_md.clone())];
+        for sub in meta.sub_skills.iter().flatten() {
+            if let Ok(content) = fs::read_to_string(indexer.skill_dir(&meta.name).join(&sub.file)) {
+                files.push((sub.file.clone(), content));
+            }
+        }
+
+        check_description(meta, config, &mut findings);
+        check_h1_title(&skill_md, &mut findings);
+        for (file, content) in &files {
+            check_todo(file, content, &mut findings);
+        }
+        check_triggers(meta, &mut findings);
+
+        for (rule_id, file, line, message) in findings {
+            let rule = RULES.iter().find(|r| r.id == rule_id).expect("known rule");
+            let severity = config.severity(rule);
+            match severity {
+                Severity::Off => continue,
+                Severity::Error => report.errors += 1,
+                Severity::Warning => report.warnings += 1,
+                Severity::Info => {}
+            }
+            report.findings.push(LintFinding {
+                rule: rule.id,
+                severity,
+                skill: meta.name.clone(),
+                file,
+                line,
+                message,
+            });
+        }
+    }
+
+    report
+}
+
+/// A finding before severity is applied: rule, file, line, message.
+type RawFinding = (&'static str, Option<String>, Option<usize>, String);
+
+fn check_description(meta: &SkillMeta, config: &LintConfig, findings: &mut Vec<RawFinding>) {
+    let chars = meta.description.chars().count();
+    if chars > config.max_description_chars {
+        findings.push((
+            "description-length",
+            None,
+            None,
+            format!(
+                "Description is {} characters (max {})",
+                chars, config.max_description_chars
+            ),
+        ));
+    }
+}
+
+fn check_h1_title(content: &str, findings: &mut Vec<RawFinding>) {
+    let mut in_fence = false;
+    let has_title = strip_frontmatter(content).lines().any(|line| {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        !in_fence && parse_heading(line).is_some_and(|(level, _)| level == 1)
+    });
+    if !has_title {
+        findings.push((
+            "h1-title",
+            Some("SKILL.md".to_string()),
+            None,
+            "SKILL.md has no `# Title` heading".to_string(),
+        ));
+    }
+}
+
+fn check_todo(file: &str, content: &str, findings: &mut Vec<RawFinding>) {
+    for (i, line) in content.lines().enumerate() {
+        let marker = line
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .find(|word| *word == "TODO" || *word == "FIXME");
+        if let Some(marker) = marker {
+            findings.push((
+                "no-todo",
+                Some(file.to_string()),
+                Some(i + 1),
+                format!("{} marker", marker),
+            ));
+        }
+    }
+}
+
+fn check_triggers(meta: &SkillMeta, findings: &mut Vec<RawFinding>) {
+    for sub in meta.sub_skills.iter().flatten() {
+        if sub.triggers.iter().all(|t| t.trim().is_empty()) {
+            findings.push((
+                "sub-skill-triggers",
+                None,
+                None,
+                format!("Sub-skill '{}' has no triggers", sub.name),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_skill;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lint_skills() {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("forms");
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            &format!(
+                r#"{{"name": "forms", "description": "{}", "sub_skills": [{{"name": "react", "file": "react.md"}}]}}"#,
+                "x".repeat(250)
+            ),
+            "## Forms\n\nTODO: write this.\n",
+        );
+        fs::write(skill_dir.join("react.md"), "# React\n\n// FIXME later\n").unwrap();
+
+        let indexer = SkillIndexer::new(temp_dir.path());
+        indexer.reload().unwrap();
+
+        let report = lint_skills(&indexer, &LintConfig::default());
+        let rules: Vec<&str> = report.findings.iter().map(|f| f.rule).collect();
+        assert_eq!(
+            rules,
+            vec!["description-length", "h1-title", "no-todo", "no-todo", "sub-skill-triggers"]
+        );
+        assert_eq!(report.findings[3].file.as_deref(), Some("react.md"));
+        assert_eq!(report.findings[3].line, Some(3));
+        assert!(report.passed());
+
+        let config = LintConfig {
+            max_description_chars: 300,
+            rules: HashMap::from([
+                ("no-todo".to_string(), Severity::Error),
+                ("h1-title".to_string(), Severity::Off),
+            ]),
+        };
+        let report = lint_skills(&indexer, &config);
+        assert_eq!(report.findings.len(), 3);
+        assert_eq!(report.errors, 2);
+        assert!(!report.passed());
+    }
+}
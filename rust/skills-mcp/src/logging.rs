@@ -0,0 +1,208 @@
+//! Log output formats for the server binaries.
+//!
+//! `text` is tracing's usual human-readable output. `json` writes one
+//! object per line, for log pipelines:
+//!
+//! ```json
+//! {"timestamp":"2024-05-01T09:30:00.000Z","level":"DEBUG","target":"skills_mcp::search::service",
+//!  "message":"Skill search 'forms' found 0 results (2 more matched but were filtered out)",
+//!  "span":"search","request_id":"5f0c…","method":"GET","uri":"/api/search?q=forms","kind":"skills","query":"forms"}
+//! ```
+//!
+//! Fields of the enclosing spans are merged into each event, inner spans
+//! winning, so every line of a request carries its `request_id`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::Utc;
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::models::timestamp;
+
+/// How log lines are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!("unknown log format '{}' (expected text or json)", other)),
+        }
+    }
+}
+
+/// Formats events as JSON objects; use with [`JsonFields`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormat;
+
+/// Records span fields as JSON, so [`JsonFormat`] can merge them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFields;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let meta = event.metadata();
+        let mut object = Map::new();
+        object.insert("timestamp".to_string(), timestamp::format(&Utc::now()).into());
+        object.insert("level".to_string(), meta.level().as_str().into());
+        object.insert("target".to_string(), meta.target().into());
+        event.record(&mut JsonVisitor(&mut object));
+
+        if let Some(scope) = ctx.event_scope() {
+            let mut innermost = None;
+            for span in scope.from_root() {
+                innermost = Some(span.name());
+                let extensions = span.extensions();
+                let Some(fields) = extensions.get::<FormattedFields<N>>() else {
+                    continue;
+                };
+                if let Ok(Value::Object(fields)) = serde_json::from_str(&fields.fields) {
+                    for (name, value) in fields {
+                        // The event's own fields win over its spans'
+                        if !event.fields().any(|f| f.name() == name) {
+                            object.insert(name, value);
+                        }
+                    }
+                }
+            }
+            if let Some(name) = innermost {
+                object.insert("span".to_string(), name.into());
+            }
+        }
+
+        writeln!(writer, "{}", Value::Object(object))
+    }
+}
+
+impl<'writer> FormatFields<'writer> for JsonFields {
+    fn format_fields<R: RecordFields>(&self, mut writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut object = Map::new();
+        fields.record(&mut JsonVisitor(&mut object));
+        write!(writer, "{}", Value::Object(object))
+    }
+
+    fn add_fields(
+        &self,
+        current: &mut FormattedFields<Self>,
+        fields: &tracing::span::Record<'_>,
+    ) -> fmt::Result {
+        let mut object = match serde_json::from_str(&current.fields) {
+            Ok(Value::Object(object)) => object,
+            _ => Map::new(),
+        };
+        fields.record(&mut JsonVisitor(&mut object));
+        current.fields = Value::Object(object).to_string();
+        Ok(())
+    }
+}
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use tracing_subscriber::fmt::MakeWriter;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_json_format() {
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::fmt()
+            .event_format(JsonFormat)
+            .fmt_fields(JsonFields)
+            .with_writer(buffer.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = tracing::info_span!("request", request_id = "abc", method = "GET");
+            let _request = request.enter();
+            let search = tracing::info_span!("search", query = "forms", results = tracing::field::Empty);
+            search.record("results", 2);
+            let _search = search.enter();
+            tracing::info!(method = "override", "Search done");
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["message"], "Search done");
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["span"], "search");
+        assert_eq!(line["request_id"], "abc");
+        assert_eq!(line["query"], "forms");
+        assert_eq!(line["results"], 2);
+        assert_eq!(line["method"], "override");
+
+        assert_eq!("json".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! Maintenance mode for migrations and bulk restructurings.
+//!
+//! While enabled, writes through the API and MCP tools are rejected with
+//! the configured message, and read results carry a `maintenance` banner
+//! so agents and UIs know the library may be mid-change.
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Message shown when maintenance is enabled without one.
+pub const DEFAULT_MESSAGE: &str = "Skills library is under maintenance; writes are disabled";
+
+/// Banner attached to read results while maintenance is enabled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceBanner {
+    /// Message explaining the maintenance.
+    pub message: String,
+
+    /// When maintenance was enabled.
+    #[serde(with = "crate::models::timestamp")]
+    pub since: DateTime<Utc>,
+}
+
+/// Current maintenance state.
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceStatus {
+    /// Whether maintenance mode is enabled.
+    pub enabled: bool,
+
+    /// Banner served while enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banner: Option<MaintenanceBanner>,
+}
+
+/// Switch for maintenance mode.
+#[derive(Debug, Default)]
+pub struct Maintenance {
+    banner: RwLock<Option<MaintenanceBanner>>,
+}
+
+impl Maintenance {
+    /// Create a switch with maintenance disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable maintenance mode, replacing the message if already enabled.
+    pub fn enable(&self, message: Option<String>) -> MaintenanceBanner {
+        let mut banner = self.banner.write();
+        let since = banner.as_ref().map_or_else(Utc::now, |b| b.since);
+        let enabled = MaintenanceBanner {
+            message: message
+                .filter(|m| !m.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_MESSAGE.to_string()),
+            since,
+        };
+        *banner = Some(enabled.clone());
+        enabled
+    }
+
+    /// Disable maintenance mode.
+    pub fn disable(&self) {
+        *self.banner.write() = None;
+    }
+
+    /// Whether maintenance mode is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.banner.read().is_some()
+    }
+
+    /// The current banner, if maintenance is enabled.
+    pub fn banner(&self) -> Option<MaintenanceBanner> {
+        self.banner.read().clone()
+    }
+
+    /// Snapshot of the current state.
+    pub fn status(&self) -> MaintenanceStatus {
+        let banner = self.banner();
+        MaintenanceStatus {
+            enabled: banner.is_some(),
+            banner,
+        }
+    }
+
+    /// Add a `maintenance` field to a JSON object while enabled.
+    ///
+    /// Non-object values are left unchanged.
+    pub fn annotate(&self, value: &mut Value) {
+        let Some(banner) = self.banner() else {
+            return;
+        };
+        if let (Value::Object(map), Ok(banner)) = (value, serde_json::to_value(banner)) {
+            map.insert("maintenance".to_string(), banner);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maintenance_toggle_and_annotate() {
+        let maintenance = Maintenance::new();
+        let mut value = serde_json::json!({ "name": "forms" });
+        maintenance.annotate(&mut value);
+        assert!(value.get("maintenance").is_none());
+
+        let banner = maintenance.enable(None);
+        assert_eq!(banner.message, DEFAULT_MESSAGE);
+
+        // Re-enabling keeps the original start time
+        let updated = maintenance.enable(Some("Migrating to v2".to_string()));
+        assert_eq!(updated.since, banner.since);
+
+        maintenance.annotate(&mut value);
+        assert_eq!(value["maintenance"]["message"], "Migrating to v2");
+
+        maintenance.disable();
+        assert!(!maintenance.status().enabled);
+    }
+}
@@ -0,0 +1,136 @@
+//! Fenced code block extraction.
+
+use serde::{Deserialize, Serialize};
+
+use super::frontmatter::split_frontmatter;
+use super::sections::parse_heading;
+
+/// A fenced code block and where it sits in its file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeBlock {
+    /// Language of the fence info string, normalized with
+    /// [`normalize_code_language`]; None for untagged fences.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Nearest heading above the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    /// 1-based line of the opening fence.
+    pub line: usize,
+    /// Code between the fences.
+    pub code: String,
+}
+
+/// Canonical name of a code language: lowercased, with common aliases
+/// (`py`, `ts`, `sh`, `yml`) spelled out.
+pub fn normalize_code_language(tag: &str) -> String {
+    let tag = tag.trim().to_ascii_lowercase();
+    match tag.as_str() {
+        "py" | "python3" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "ts" => "typescript",
+        "rs" => "rust",
+        "sh" | "shell" | "zsh" | "console" => "bash",
+        "golang" => "go",
+        "yml" => "yaml",
+        "rb" => "ruby",
+        "md" => "markdown",
+        _ => return tag,
+    }
+    .to_string()
+}
+
+/// Extract the fenced code blocks of `content`.
+///
+/// Fences open with three or more backticks or tildes and close with at
+/// least as many of the same; an unclosed fence runs to the end of the file.
+/// Frontmatter is skipped, but lines are counted from the top of the file.
+pub fn extract_code_blocks(content: &str) -> Vec<CodeBlock> {
+    let (_, body) = split_frontmatter(content);
+    let first_line = content[..content.len() - body.len()].matches('\n').count() + 1;
+
+    let mut blocks = Vec::new();
+    let mut heading: Option<&str> = None;
+    // Fence character and length, with the block being collected
+    let mut open: Option<(char, usize, CodeBlock)> = None;
+
+    for (i, line) in body.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let fence = fence(trimmed);
+
+        let closes = match (&open, fence) {
+            (Some((c, len, _)), Some((fc, flen, info))) => fc == *c && flen >= *len && info.is_empty(),
+            _ => false,
+        };
+        if closes {
+            blocks.extend(open.take().map(|(_, _, block)| block));
+            continue;
+        }
+
+        match (&mut open, fence) {
+            (Some((_, _, block)), _) => {
+                block.code.push_str(line);
+                block.code.push('\n');
+            }
+            (None, Some((c, len, info))) => {
+                let language = info.split_whitespace().next().map(normalize_code_language);
+                let block = CodeBlock {
+                    language,
+                    heading: heading.map(str::to_string),
+                    line: first_line + i,
+                    code: String::new(),
+                };
+                open = Some((c, len, block));
+            }
+            (None, None) => {
+                if let Some((_, title)) = parse_heading(trimmed).filter(|_| line.len() - trimmed.len() < 4) {
+                    heading = Some(title);
+                }
+            }
+        }
+    }
+    blocks.extend(open.map(|(_, _, block)| block));
+
+    blocks
+}
+
+/// The fence character, its length, and the info string, if `line` is a
+/// code fence.
+fn fence(line: &str) -> Option<(char, usize, &str)> {
+    let c = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = line.chars().take_while(|x| *x == c).count();
+    (len >= 3).then(|| (c, len, line[len..].trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_code_blocks() {
+        let content = "---\nname: forms\n---\n# Forms\n\n```py title=x\nimport zod\n```\n\n## Shell\n\n````\n```sh\nnpm i\n```\n````\n\n~~~TS\nlet x = 1;\n";
+        let blocks = extract_code_blocks(content);
+        assert_eq!(blocks.len(), 3);
+
+        assert_eq!(blocks[0].language.as_deref(), Some("python"));
+        assert_eq!(blocks[0].heading.as_deref(), Some("Forms"));
+        assert_eq!(blocks[0].line, 6);
+        assert_eq!(blocks[0].code, "import zod\n");
+
+        // Shorter fences inside a longer one are code
+        assert_eq!(blocks[1].language, None);
+        assert_eq!(blocks[1].heading.as_deref(), Some("Shell"));
+        assert_eq!(blocks[1].code, "```sh\nnpm i\n```\n");
+
+        // Unclosed fences run to the end
+        assert_eq!(blocks[2].language.as_deref(), Some("typescript"));
+        assert_eq!(blocks[2].code, "let x = 1;\n");
+    }
+
+    #[test]
+    fn test_normalize_code_language() {
+        assert_eq!(normalize_code_language("Py"), "python");
+        assert_eq!(normalize_code_language("yml"), "yaml");
+        assert_eq!(normalize_code_language("elixir"), "elixir");
+    }
+}
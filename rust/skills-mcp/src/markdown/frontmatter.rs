@@ -0,0 +1,182 @@
+//! YAML frontmatter parsing.
+//!
+//! Supports the subset of YAML that skill frontmatter uses in practice:
+//! top-level `key: value` pairs, quoted and plain scalars, inline
+//! (`[a, b]`) and block (`- a`) lists of scalars, and `|` / `>` block
+//! scalars. Nested mappings are skipped. Keys are normalized from
+//! kebab-case to snake_case so they line up with `_meta.json` fields.
+
+use serde_json::{Map, Value};
+
+/// Split a document into its frontmatter block (without delimiters) and body.
+///
+/// Frontmatter must open with `---` on the first line and close with `---`
+/// or `...` on a line of its own.
+pub fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let text = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed == "..." {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+
+    (None, content)
+}
+
+/// Strip frontmatter, returning only the markdown body.
+pub fn strip_frontmatter(content: &str) -> &str {
+    split_frontmatter(content).1
+}
+
+/// Parse the frontmatter of a document into a JSON object.
+///
+/// Returns None when the document has no frontmatter.
+pub fn parse_frontmatter(content: &str) -> Option<Map<String, Value>> {
+    let (block, _) = split_frontmatter(content);
+    let lines: Vec<&str> = block?.lines().collect();
+
+    let mut map = Map::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+
+        if is_blank_or_comment(line) || line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let Some((key, raw)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(['"', '\'']).replace('-', "_");
+        let raw = raw.trim();
+
+        // Indented continuation lines belonging to this key
+        let start = i;
+        while i < lines.len() && (lines[i].starts_with([' ', '\t', '-']) || lines[i].trim().is_empty()) {
+            i += 1;
+        }
+        let block = &lines[start..i];
+
+        let value = match raw {
+            "|" | "|-" | "|+" => block_scalar(block, "\n"),
+            ">" | ">-" | ">+" => block_scalar(block, " "),
+            "" => block_list(block).unwrap_or(Value::Null),
+            _ => inline_value(raw),
+        };
+        map.insert(key, value);
+    }
+
+    Some(map)
+}
+
+fn is_blank_or_comment(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+/// Join indented lines of a block scalar.
+fn block_scalar(lines: &[&str], separator: &str) -> Value {
+    let text = lines
+        .iter()
+        .map(|l| l.trim())
+        .collect::<Vec<_>>()
+        .join(separator);
+    Value::String(text.trim().to_string())
+}
+
+/// Parse `- item` lines; None if the block is empty or a nested mapping.
+fn block_list(lines: &[&str]) -> Option<Value> {
+    let items: Vec<Value> = lines
+        .iter()
+        .filter(|l| !is_blank_or_comment(l))
+        .map(|l| l.trim().strip_prefix('-').map(|item| scalar(item.trim())))
+        .collect::<Option<_>>()?;
+
+    if items.is_empty() {
+        None
+    } else {
+        Some(Value::Array(items))
+    }
+}
+
+/// Parse a value written on the same line as its key.
+fn inline_value(raw: &str) -> Value {
+    match raw.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+        Some(inner) => Value::Array(
+            inner
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(scalar)
+                .collect(),
+        ),
+        None => scalar(raw),
+    }
+}
+
+/// Parse a scalar. Everything except `null`/`~` is kept as a string.
+fn scalar(raw: &str) -> Value {
+    if let Some(inner) = raw.strip_prefix('"').and_then(|r| r.strip_suffix('"')) {
+        return Value::String(inner.replace("\\\"", "\"").replace("\\n", "\n"));
+    }
+    if let Some(inner) = raw.strip_prefix('\'').and_then(|r| r.strip_suffix('\'')) {
+        return Value::String(inner.replace("''", "'"));
+    }
+
+    // Trailing comments on plain scalars
+    let plain = raw.split(" #").next().unwrap_or(raw).trim();
+    match plain {
+        "null" | "~" => Value::Null,
+        _ => Value::String(plain.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_frontmatter() {
+        let (front, body) = split_frontmatter("---\nname: forms\n---\n# Forms\n");
+        assert_eq!(front, Some("name: forms\n"));
+        assert_eq!(body, "# Forms\n");
+
+        let (front, body) = split_frontmatter("# Forms\n---\n");
+        assert!(front.is_none());
+        assert_eq!(body, "# Forms\n---\n");
+    }
+
+    #[test]
+    fn test_parse_frontmatter_values() {
+        let content = "---\n\
+name: pdf-tools  # comment\n\
+description: \"Work with PDFs: extract, merge\"\n\
+tags: [pdf, 'documents']\n\
+depends-on:\n  - forms\n  - testing\n\
+quick_answer: >\n  Use pypdf\n  for merging.\n\
+metadata:\n  version: 1\n\
+---\n# PDF";
+
+        let map = parse_frontmatter(content).unwrap();
+        assert_eq!(map["name"], "pdf-tools");
+        assert_eq!(map["description"], "Work with PDFs: extract, merge");
+        assert_eq!(map["tags"], serde_json::json!(["pdf", "documents"]));
+        assert_eq!(map["depends_on"], serde_json::json!(["forms", "testing"]));
+        assert_eq!(map["quick_answer"], "Use pypdf for merging.");
+        assert_eq!(map["metadata"], Value::Null);
+
+        assert!(parse_frontmatter("# No frontmatter").is_none());
+    }
+}
@@ -0,0 +1,249 @@
+//! Syntax highlighting for code blocks rendered to HTML.
+//!
+//! A small lexer, not a grammar: it picks out comments, strings, numbers,
+//! and keywords, which is what makes code in a skill readable. Tokens are
+//! wrapped in `<span class="hl-comment">` (and `hl-string`, `hl-number`,
+//! `hl-keyword`) for the page's stylesheet to color.
+
+/// Lexical rules of one language.
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static str,
+    /// `'` starts a char literal only when it closes right after (Rust
+    /// lifetimes).
+    char_literals: bool,
+    /// Keywords match regardless of case (SQL).
+    case_insensitive: bool,
+}
+
+const RUST: Syntax = Syntax {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "false",
+        "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+        "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+        "where", "while",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: "\"",
+    char_literals: true,
+    case_insensitive: false,
+};
+
+const JAVASCRIPT: Syntax = Syntax {
+    keywords: &[
+        "async", "await", "break", "case", "catch", "class", "const", "continue", "default", "delete",
+        "do", "else", "export", "extends", "false", "finally", "for", "from", "function", "if",
+        "import", "in", "instanceof", "interface", "let", "new", "null", "of", "return", "static",
+        "super", "switch", "this", "throw", "true", "try", "type", "typeof", "undefined", "var",
+        "void", "while", "yield",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: "\"'`",
+    char_literals: false,
+    case_insensitive: false,
+};
+
+const PYTHON: Syntax = Syntax {
+    keywords: &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+        "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if",
+        "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try",
+        "while", "with", "yield",
+    ],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: "\"'",
+    char_literals: false,
+    case_insensitive: false,
+};
+
+const SHELL: Syntax = Syntax {
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if", "in",
+        "local", "return", "then", "until", "while",
+    ],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: "\"'",
+    char_literals: false,
+    case_insensitive: false,
+};
+
+const GO: Syntax = Syntax {
+    keywords: &[
+        "break", "case", "chan", "const", "continue", "default", "defer", "else", "false", "for",
+        "func", "go", "if", "import", "interface", "map", "nil", "package", "range", "return",
+        "select", "struct", "switch", "true", "type", "var",
+    ],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: "\"'`",
+    char_literals: false,
+    case_insensitive: false,
+};
+
+const CSS: Syntax = Syntax {
+    keywords: &["!important", "@import", "@media", "@keyframes"],
+    line_comments: &[],
+    block_comment: Some(("/*", "*/")),
+    quotes: "\"'",
+    char_literals: false,
+    case_insensitive: false,
+};
+
+const SQL: Syntax = Syntax {
+    keywords: &[
+        "AND", "AS", "BY", "CREATE", "DELETE", "FROM", "GROUP", "INSERT", "INTO", "JOIN", "LEFT",
+        "LIMIT", "NOT", "NULL", "ON", "OR", "ORDER", "SELECT", "SET", "TABLE", "UPDATE", "VALUES",
+        "WHERE",
+    ],
+    line_comments: &["--"],
+    block_comment: Some(("/*", "*/")),
+    quotes: "'\"",
+    char_literals: false,
+    case_insensitive: true,
+};
+
+/// JSON, YAML, and TOML: strings, numbers, and literals.
+const DATA: Syntax = Syntax {
+    keywords: &["true", "false", "null"],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: "\"'",
+    char_literals: false,
+    case_insensitive: false,
+};
+
+fn syntax(language: &str) -> Option<&'static Syntax> {
+    match language.to_ascii_lowercase().as_str() {
+        "rust" | "rs" => Some(&RUST),
+        "javascript" | "js" | "jsx" | "mjs" | "typescript" | "ts" | "tsx" => Some(&JAVASCRIPT),
+        "python" | "py" => Some(&PYTHON),
+        "bash" | "sh" | "shell" | "zsh" => Some(&SHELL),
+        "go" | "golang" => Some(&GO),
+        "css" | "scss" => Some(&CSS),
+        "sql" => Some(&SQL),
+        "json" | "yaml" | "yml" | "toml" => Some(&DATA),
+        _ => None,
+    }
+}
+
+/// Highlight `code` written in `language` (a fence info string such as
+/// `rust` or `tsx`), returning escaped HTML. Unknown languages are only
+/// escaped.
+pub fn highlight(code: &str, language: &str) -> String {
+    let Some(syntax) = syntax(language) else {
+        return escape_html(code);
+    };
+
+    let mut out = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+    while let Some(c) = rest.chars().next() {
+        let (class, len) = if let Some(len) = comment_len(syntax, rest) {
+            ("hl-comment", len)
+        } else if syntax.quotes.contains(c) || (c == '\'' && syntax.char_literals) {
+            match string_len(rest, c, syntax.char_literals && c == '\'') {
+                Some(len) => ("hl-string", len),
+                None => ("", c.len_utf8()),
+            }
+        } else if c.is_ascii_digit() {
+            ("hl-number", word_len(rest))
+        } else if c.is_alphanumeric() || c == '_' || c == '@' || c == '!' {
+            let len = word_len(rest).max(c.len_utf8());
+            let word = &rest[..len];
+            let is_keyword = if syntax.case_insensitive {
+                syntax.keywords.iter().any(|k| k.eq_ignore_ascii_case(word))
+            } else {
+                syntax.keywords.contains(&word)
+            };
+            (if is_keyword { "hl-keyword" } else { "" }, len)
+        } else {
+            ("", c.len_utf8())
+        };
+
+        let token = escape_html(&rest[..len]);
+        if class.is_empty() {
+            out.push_str(&token);
+        } else {
+            out.push_str(&format!("<span class=\"{}\">{}</span>", class, token));
+        }
+        rest = &rest[len..];
+    }
+    out
+}
+
+/// Length of the comment starting `text`, if one does.
+fn comment_len(syntax: &Syntax, text: &str) -> Option<usize> {
+    if syntax.line_comments.iter().any(|start| text.starts_with(start)) {
+        return Some(text.find('\n').unwrap_or(text.len()));
+    }
+    let (start, end) = syntax.block_comment?;
+    let body = text.strip_prefix(start)?;
+    Some(body.find(end).map_or(text.len(), |i| start.len() + i + end.len()))
+}
+
+/// Length of the string literal opened by `quote` at the start of `text`.
+///
+/// Strings end at the closing quote or, except for backtick templates, at
+/// the end of the line. Char literals must close within one (possibly
+/// escaped) character, or None is returned.
+fn string_len(text: &str, quote: char, char_literal: bool) -> Option<usize> {
+    let mut escaped = false;
+    for (n, (i, c)) in text.char_indices().skip(1).enumerate() {
+        if char_literal && n > 1 && !text[1..].starts_with('\\') {
+            return None;
+        }
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\n' if quote != '`' => return if char_literal { None } else { Some(i) },
+            c if c == quote => return Some(i + c.len_utf8()),
+            _ => {}
+        }
+    }
+    (!char_literal).then_some(text.len())
+}
+
+/// Length of the identifier or number at the start of `text`.
+fn word_len(text: &str) -> usize {
+    text.char_indices()
+        .find(|(i, c)| !(c.is_alphanumeric() || *c == '_' || (*i == 0 && (*c == '@' || *c == '!'))))
+        .map_or(text.len(), |(i, _)| i)
+}
+
+/// Escape text for HTML element content and attribute values.
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(
+            highlight("let x = \"a<b\"; // done", "rust"),
+            "<span class=\"hl-keyword\">let</span> x = <span class=\"hl-string\">&quot;a&lt;b&quot;</span>; \
+             <span class=\"hl-comment\">// done</span>"
+        );
+        // Lifetimes aren't strings; char literals are
+        assert_eq!(
+            highlight("&'a str, '\\n'", "rs"),
+            "&amp;'a str, <span class=\"hl-string\">'\\n'</span>"
+        );
+        assert_eq!(
+            highlight("select 1 from t", "sql"),
+            "<span class=\"hl-keyword\">select</span> <span class=\"hl-number\">1</span> \
+             <span class=\"hl-keyword\">from</span> t"
+        );
+        assert_eq!(highlight("<div>", "html"), "&lt;div&gt;");
+    }
+}
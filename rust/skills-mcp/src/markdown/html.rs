@@ -0,0 +1,663 @@
+//! Markdown to HTML rendering.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use super::highlight::{escape_html, highlight};
+use super::sections::{parse_heading, Anchors};
+use super::tables::{is_delimiter_row, split_row};
+
+/// URL schemes kept in links and images; targets with any other scheme
+/// (`javascript:`, `data:`), or anything that might be read as one, are
+/// dropped.
+const SAFE_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Reference-style link definitions: `[label]: target`.
+fn reference_def_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\s{0,3}\[([^\]]+)\]:\s*<?([^\s>]+)>?").unwrap())
+}
+
+/// Render markdown to an HTML fragment.
+///
+/// Covers what skill files use: ATX headings with GitHub-style `id`
+/// anchors, paragraphs, emphasis, code spans, fenced code blocks with
+/// syntax highlighting, block quotes, nested and task lists, pipe tables,
+/// rules, links, and images. Raw HTML is escaped rather than passed
+/// through, and HTML comments are dropped.
+///
+/// `rewrite_link` is called for each link and image target; returning
+/// Some replaces it.
+pub fn render_html(content: &str, rewrite_link: impl Fn(&str) -> Option<String>) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let references = lines
+        .iter()
+        .filter_map(|line| reference_def_regex().captures(line))
+        .map(|caps| (caps[1].to_lowercase(), caps[2].to_string()))
+        .collect();
+    let mut renderer = Renderer {
+        rewrite_link: &rewrite_link,
+        references,
+        anchors: Anchors::default(),
+    };
+    renderer.blocks(&lines)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListKind {
+    Bullet,
+    Ordered,
+}
+
+impl ListKind {
+    fn tag(self) -> &'static str {
+        match self {
+            ListKind::Bullet => "ul",
+            ListKind::Ordered => "ol",
+        }
+    }
+}
+
+struct Renderer<'a> {
+    rewrite_link: &'a dyn Fn(&str) -> Option<String>,
+    /// Reference link targets by lowercased label.
+    references: HashMap<String, String>,
+    anchors: Anchors,
+}
+
+impl Renderer<'_> {
+    /// Render a sequence of block-level lines.
+    fn blocks(&mut self, lines: &[&str]) -> String {
+        let mut out = String::new();
+        let mut paragraph: Vec<&str> = Vec::new();
+        // Open lists with their marker indentation, innermost last, and the
+        // text of the innermost item not yet written
+        let mut lists: Vec<(ListKind, usize)> = Vec::new();
+        let mut item = String::new();
+        let mut after_blank = false;
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.trim();
+            let indent = line.len() - line.trim_start().len();
+
+            if let Some((kind, marker_len, start)) = list_marker(line).filter(|_| !is_rule(trimmed)) {
+                self.paragraph(&mut out, &mut paragraph);
+                self.item(&mut out, &mut item);
+                while lists.last().is_some_and(|(_, level)| *level > indent) {
+                    let (kind, _) = lists.pop().unwrap();
+                    out.push_str(&format!("</li>\n</{}>\n", kind.tag()));
+                }
+                let same_level = lists.last().filter(|(_, level)| *level == indent).map(|(open, _)| *open);
+                if same_level == Some(kind) {
+                    out.push_str("</li>\n<li>");
+                } else {
+                    if let Some(open) = same_level {
+                        lists.pop();
+                        out.push_str(&format!("</li>\n</{}>\n", open.tag()));
+                    } else if !lists.is_empty() {
+                        // Nested in the open item
+                        out.push('\n');
+                    }
+                    match start {
+                        Some(start) if start != 1 => out.push_str(&format!("<ol start=\"{}\">\n<li>", start)),
+                        _ => out.push_str(&format!("<{}>\n<li>", kind.tag())),
+                    }
+                    lists.push((kind, indent));
+                }
+                item = line[indent + marker_len..].trim().to_string();
+                after_blank = false;
+                i += 1;
+                continue;
+            }
+
+            if !lists.is_empty() {
+                if trimmed.is_empty() {
+                    after_blank = true;
+                    i += 1;
+                    continue;
+                }
+                if indent >= 2 && is_fence(trimmed) {
+                    // A code block belongs to the item it's indented under
+                    self.item(&mut out, &mut item);
+                    let (code, next) = code_block(lines, i);
+                    out.push('\n');
+                    out.push_str(&code);
+                    i = next;
+                    continue;
+                }
+                if indent >= 2 || (!after_blank && !starts_block(lines, i)) {
+                    if !item.is_empty() {
+                        item.push('\n');
+                    }
+                    item.push_str(trimmed);
+                    i += 1;
+                    continue;
+                }
+                self.item(&mut out, &mut item);
+                while let Some((kind, _)) = lists.pop() {
+                    out.push_str(&format!("</li>\n</{}>\n", kind.tag()));
+                }
+            }
+            after_blank = false;
+
+            if trimmed.is_empty() {
+                self.paragraph(&mut out, &mut paragraph);
+                i += 1;
+            } else if trimmed.starts_with("<!--") {
+                self.paragraph(&mut out, &mut paragraph);
+                while i < lines.len() && !lines[i].contains("-->") {
+                    i += 1;
+                }
+                i += 1;
+            } else if is_fence(trimmed) {
+                self.paragraph(&mut out, &mut paragraph);
+                let (code, next) = code_block(lines, i);
+                out.push_str(&code);
+                i = next;
+            } else if let Some((level, title)) = parse_heading(trimmed).filter(|_| indent < 4) {
+                self.paragraph(&mut out, &mut paragraph);
+                let anchor = self.anchors.anchor(title);
+                out.push_str(&format!("<h{0} id=\"{1}\">{2}</h{0}>\n", level, anchor, self.inline(title)));
+                i += 1;
+            } else if is_rule(trimmed) {
+                self.paragraph(&mut out, &mut paragraph);
+                out.push_str("<hr>\n");
+                i += 1;
+            } else if trimmed.starts_with('>') {
+                self.paragraph(&mut out, &mut paragraph);
+                let mut quoted = Vec::new();
+                while let Some(rest) = lines.get(i).and_then(|l| l.trim_start().strip_prefix('>')) {
+                    quoted.push(rest.strip_prefix(' ').unwrap_or(rest));
+                    i += 1;
+                }
+                out.push_str(&format!("<blockquote>\n{}</blockquote>\n", self.blocks(&quoted)));
+            } else if is_table(lines, i) {
+                self.paragraph(&mut out, &mut paragraph);
+                i = self.table(&mut out, lines, i);
+            } else if reference_def_regex().is_match(line) {
+                i += 1;
+            } else {
+                paragraph.push(line);
+                i += 1;
+            }
+        }
+
+        self.paragraph(&mut out, &mut paragraph);
+        self.item(&mut out, &mut item);
+        while let Some((kind, _)) = lists.pop() {
+            out.push_str(&format!("</li>\n</{}>\n", kind.tag()));
+        }
+        out
+    }
+
+    /// Write and clear a pending paragraph.
+    fn paragraph(&self, out: &mut String, lines: &mut Vec<&str>) {
+        if lines.is_empty() {
+            return;
+        }
+        out.push_str("<p>");
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                out.push_str(if lines[i - 1].ends_with("  ") { "<br>\n" } else { "\n" });
+            }
+            out.push_str(&self.inline(line.trim()));
+        }
+        out.push_str("</p>\n");
+        lines.clear();
+    }
+
+    /// Write and clear the text of the innermost list item.
+    fn item(&self, out: &mut String, text: &mut String) {
+        let task = [("[ ] ", false), ("[x] ", true), ("[X] ", true)]
+            .into_iter()
+            .find_map(|(prefix, done)| text.strip_prefix(prefix).map(|rest| (rest, done)));
+        match task {
+            Some((rest, done)) => out.push_str(&format!(
+                "<input type=\"checkbox\" disabled{}> {}",
+                if done { " checked" } else { "" },
+                self.inline(rest)
+            )),
+            None => out.push_str(&self.inline(text)),
+        }
+        text.clear();
+    }
+
+    /// Write the table starting at `lines[start]`, returning the index of
+    /// the line after it.
+    fn table(&self, out: &mut String, lines: &[&str], start: usize) -> usize {
+        let headers = split_row(lines[start]);
+        let aligns: Vec<&str> = split_row(lines[start + 1])
+            .iter()
+            .map(|cell| match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => " style=\"text-align: center\"",
+                (false, true) => " style=\"text-align: right\"",
+                (true, false) => " style=\"text-align: left\"",
+                (false, false) => "",
+            })
+            .collect();
+
+        out.push_str("<table>\n<thead>\n<tr>");
+        for (cell, align) in headers.iter().zip(&aligns) {
+            out.push_str(&format!("<th{}>{}</th>", align, self.inline(cell)));
+        }
+        out.push_str("</tr>\n</thead>\n<tbody>\n");
+
+        let mut i = start + 2;
+        while let Some(row) = lines.get(i).filter(|l| l.contains('|') && !l.trim().is_empty()) {
+            let mut cells = split_row(row);
+            cells.resize(headers.len(), String::new());
+            out.push_str("<tr>");
+            for (cell, align) in cells.iter().zip(&aligns) {
+                out.push_str(&format!("<td{}>{}</td>", align, self.inline(cell)));
+            }
+            out.push_str("</tr>\n");
+            i += 1;
+        }
+        out.push_str("</tbody>\n</table>\n");
+        i
+    }
+
+    /// Render inline markup: code spans, emphasis, links, images, and
+    /// autolinks. Everything else is escaped.
+    fn inline(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut prev = None;
+        let mut rest = text;
+
+        while let Some(c) = rest.chars().next() {
+            let (html, len) = match c {
+                '\\' => match rest[1..].chars().next().filter(|n| n.is_ascii_punctuation()) {
+                    Some(escaped) => (escape_html(&escaped.to_string()), 2),
+                    None => ("\\".to_string(), 1),
+                },
+                '`' => code_span(rest).unwrap_or_else(|| {
+                    let ticks = rest.len() - rest.trim_start_matches('`').len();
+                    (rest[..ticks].to_string(), ticks)
+                }),
+                '!' if rest[1..].starts_with('[') => match self.link(&rest[1..]) {
+                    Some((alt, target, len)) => {
+                        let alt = escape_html(alt);
+                        let html = match self.url(&target) {
+                            Some(src) => format!("<img src=\"{}\" alt=\"{}\">", src, alt),
+                            None => alt,
+                        };
+                        (html, 1 + len)
+                    }
+                    None => ("!".to_string(), 1),
+                },
+                '[' => match self.link(rest) {
+                    Some((label, target, len)) => {
+                        let html = match self.url(&target) {
+                            Some(href) => format!("<a href=\"{}\">{}</a>", href, self.inline(label)),
+                            None => self.inline(label),
+                        };
+                        (html, len)
+                    }
+                    None => ("[".to_string(), 1),
+                },
+                '<' => match autolink(rest) {
+                    Some((url, len)) => {
+                        let url = escape_html(url);
+                        (format!("<a href=\"{}\">{}</a>", url, url), len)
+                    }
+                    None => ("&lt;".to_string(), 1),
+                },
+                '*' | '_' | '~' => self.emphasis(rest, prev).unwrap_or_else(|| {
+                    let run = rest.len() - rest.trim_start_matches(c).len();
+                    (rest[..run].to_string(), run)
+                }),
+                _ => (escape_html(&c.to_string()), c.len_utf8()),
+            };
+            out.push_str(&html);
+            prev = rest[..len].chars().last();
+            rest = &rest[len..];
+        }
+        out
+    }
+
+    /// Emphasis, strong emphasis, or strikethrough opening at the start of
+    /// `text`, with the length it spans. `_` only counts at word boundaries.
+    fn emphasis(&self, text: &str, prev: Option<char>) -> Option<(String, usize)> {
+        let delimiter = text.chars().next()?;
+        let run = text.len() - text.trim_start_matches(delimiter).len();
+        let word_boundary = |c: Option<char>| !c.is_some_and(char::is_alphanumeric);
+        let (open, close) = match (delimiter, run) {
+            ('~', 2) => ("<del>", "</del>"),
+            ('*' | '_', 1) => ("<em>", "</em>"),
+            ('*' | '_', 2) => ("<strong>", "</strong>"),
+            ('*' | '_', 3) => ("<em><strong>", "</strong></em>"),
+            _ => return None,
+        };
+        if (delimiter == '_' && !word_boundary(prev)) || text[run..].starts_with(char::is_whitespace) {
+            return None;
+        }
+
+        let marker = &text[..run];
+        let body = &text[run..];
+        let mut from = 0;
+        while let Some(found) = body[from..].find(marker) {
+            let end = from + found;
+            let closes = end > 0
+                && !body[..end].ends_with(char::is_whitespace)
+                && !body[end + run..].starts_with(delimiter)
+                && (delimiter != '_' || word_boundary(body[end + run..].chars().next()));
+            if closes {
+                let inner = self.inline(&body[..end]);
+                return Some((format!("{}{}{}", open, inner, close), run + end + run));
+            }
+            from = end + run;
+        }
+        None
+    }
+
+    /// An inline (`[label](target)`), full (`[label][ref]`), collapsed
+    /// (`[label][]`), or shortcut (`[label]`) link at the start of `text`:
+    /// its label, target, and length.
+    fn link<'t>(&self, text: &'t str) -> Option<(&'t str, String, usize)> {
+        let label_end = matching(text, '[', ']')?;
+        let label = &text[1..label_end];
+        let after = &text[label_end + 1..];
+
+        if after.starts_with('(') {
+            let end = matching(after, '(', ')')?;
+            let inner = after[1..end].trim();
+            let target = match inner.strip_prefix('<') {
+                Some(rest) => rest.split('>').next().unwrap_or_default(),
+                None => inner.split_whitespace().next().unwrap_or_default(),
+            };
+            return Some((label, target.to_string(), label_end + 1 + end + 1));
+        }
+
+        let (reference, len) = match after.strip_prefix('[') {
+            Some(rest) => {
+                let end = rest.find(']')?;
+                let reference = if end == 0 { label } else { &rest[..end] };
+                (reference, label_end + 1 + end + 2)
+            }
+            None => (label, label_end + 1),
+        };
+        let target = self.references.get(&reference.to_lowercase())?;
+        Some((label, target.clone(), len))
+    }
+
+    /// The escaped URL for a link target after rewriting, or None if it
+    /// isn't relative or of an allowed scheme.
+    fn url(&self, target: &str) -> Option<String> {
+        let target = (self.rewrite_link)(target).unwrap_or_else(|| target.to_string());
+        // Browsers drop spaces and tabs before reading the scheme, so
+        // ` javascript:` and `java\tscript:` must be read the same way
+        let target = strip_whitespace(&target);
+        // Checked as a browser would read it if the entities were decoded
+        is_safe_url(&strip_whitespace(&decode_char_refs(&target))).then(|| escape_html(&target))
+    }
+}
+
+fn strip_whitespace(url: &str) -> String {
+    url.chars().filter(|c| !c.is_ascii_whitespace() && !c.is_ascii_control()).collect()
+}
+
+/// Decode numeric character references (`&#106;`, `&#x6A;`) and the named
+/// ones for `:` and whitespace; other text is kept as it is.
+fn decode_char_refs(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = rest.find(';').and_then(|end| {
+            let name = &rest[1..end];
+            let c = match name {
+                "colon" => Some(':'),
+                "Tab" | "NewLine" => Some(' '),
+                _ => match name.strip_prefix('#') {
+                    Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok().and_then(char::from_u32),
+                    Some(dec) => dec.parse().ok().and_then(char::from_u32),
+                    None => None,
+                },
+            };
+            c.map(|c| (c, end + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether a URL is relative or has an allowed scheme. A `:` before the
+/// first `/`, `?`, or `#` ends a scheme, whatever precedes it.
+fn is_safe_url(url: &str) -> bool {
+    match url.find([':', '/', '?', '#']) {
+        Some(i) if url[i..].starts_with(':') => SAFE_SCHEMES.contains(&url[..i].to_ascii_lowercase().as_str()),
+        _ => true,
+    }
+}
+
+/// The list marker starting `line`: the list kind, the marker's length
+/// including the following space, and an ordered list's first number.
+fn list_marker(line: &str) -> Option<(ListKind, usize, Option<u64>)> {
+    let text = line.trim_start();
+    if text.len() >= 2 && text.starts_with(['-', '*', '+']) && text[1..].starts_with(' ') {
+        return Some((ListKind::Bullet, 2, None));
+    }
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let rest = &text[digits..];
+    if (1..=9).contains(&digits) && (rest.starts_with(". ") || rest.starts_with(") ")) {
+        return Some((ListKind::Ordered, digits + 2, text[..digits].parse().ok()));
+    }
+    None
+}
+
+/// Whether a trimmed line is a thematic break: three or more `-`, `*`, or
+/// `_`, optionally spaced.
+fn is_rule(trimmed: &str) -> bool {
+    let chars: Vec<char> = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3 && ['-', '*', '_'].iter().any(|m| chars.iter().all(|c| c == m))
+}
+
+fn is_fence(trimmed: &str) -> bool {
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+fn is_table(lines: &[&str], i: usize) -> bool {
+    lines[i].contains('|')
+        && lines
+            .get(i + 1)
+            .is_some_and(|next| is_delimiter_row(next, split_row(lines[i]).len()))
+}
+
+/// Whether `lines[i]` starts a block other than a paragraph, and so can't
+/// continue a list item without indentation.
+fn starts_block(lines: &[&str], i: usize) -> bool {
+    let trimmed = lines[i].trim();
+    is_fence(trimmed)
+        || parse_heading(trimmed).is_some()
+        || trimmed.starts_with('>')
+        || trimmed.starts_with("<!--")
+        || is_rule(trimmed)
+        || is_table(lines, i)
+}
+
+/// Render the fenced code block starting at `lines[start]`, returning the
+/// HTML and the index of the line after the closing fence.
+fn code_block(lines: &[&str], start: usize) -> (String, usize) {
+    let opening = lines[start].trim_start();
+    let indent = lines[start].len() - opening.len();
+    let fence_char = opening.chars().next().unwrap_or('`');
+    let fence = &opening[..opening.len() - opening.trim_start_matches(fence_char).len()];
+    let language = opening[fence.len()..].split_whitespace().next().unwrap_or_default();
+
+    let mut code = String::new();
+    let mut i = start + 1;
+    while let Some(line) = lines.get(i) {
+        i += 1;
+        if line.trim_start().starts_with(fence) {
+            break;
+        }
+        // Drop the fence's own indentation from each line
+        let dedent = line.len() - line.trim_start().len();
+        code.push_str(&line[dedent.min(indent)..]);
+        code.push('\n');
+    }
+
+    let class = if language.is_empty() {
+        String::new()
+    } else {
+        format!(" class=\"language-{}\"", escape_html(language))
+    };
+    (format!("<pre><code{}>{}</code></pre>\n", class, highlight(&code, language)), i)
+}
+
+/// A code span at the start of `text`, with its length.
+fn code_span(text: &str) -> Option<(String, usize)> {
+    let ticks = text.len() - text.trim_start_matches('`').len();
+    let marker = &text[..ticks];
+    let body = &text[ticks..];
+    let end = body.find(marker)?;
+    let code = body[..end].trim();
+    Some((format!("<code>{}</code>", escape_html(code)), ticks + end + ticks))
+}
+
+/// An autolink (`<https://example.com>`) at the start of `text`: the URL
+/// and the length of the whole link.
+fn autolink(text: &str) -> Option<(&str, usize)> {
+    let end = text.find('>')?;
+    let url = &text[1..end];
+    let is_url = ["http://", "https://", "mailto:"].iter().any(|p| url.starts_with(p))
+        && !url.contains(char::is_whitespace);
+    is_url.then_some((url, end + 1))
+}
+
+/// Index of the bracket closing the one that opens `text`, counting nested
+/// pairs.
+fn matching(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == open => depth += 1,
+            c if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_blocks() {
+        let content = "\
+# Forms
+
+Intro with **bold**, _em_, `a<b`, and snake_case_name.
+Second line.
+
+## Setup
+
+1. Install
+   ```bash
+   npm install
+   ```
+2. Configure
+   - nested
+   - [x] done
+
+> Quoted *text*
+
+| Option | Default |
+|:-------|--------:|
+| `a|b`  | 1       |
+
+<!-- hidden -->
+---
+
+## Setup
+";
+        let html = render_html(content, |_| None);
+        assert_eq!(
+            html,
+            "<h1 id=\"forms\">Forms</h1>\n\
+             <p>Intro with <strong>bold</strong>, <em>em</em>, <code>a&lt;b</code>, and snake_case_name.\n\
+             Second line.</p>\n\
+             <h2 id=\"setup\">Setup</h2>\n\
+             <ol>\n<li>Install\n\
+             <pre><code class=\"language-bash\">npm install\n</code></pre>\n\
+             </li>\n<li>Configure\n\
+             <ul>\n<li>nested</li>\n<li><input type=\"checkbox\" disabled checked> done</li>\n</ul>\n\
+             </li>\n</ol>\n\
+             <blockquote>\n<p>Quoted <em>text</em></p>\n</blockquote>\n\
+             <table>\n<thead>\n<tr><th style=\"text-align: left\">Option</th><th style=\"text-align: right\">Default</th></tr>\n</thead>\n\
+             <tbody>\n<tr><td style=\"text-align: left\"><code>a|b</code></td><td style=\"text-align: right\">1</td></tr>\n</tbody>\n</table>\n\
+             <hr>\n\
+             <h2 id=\"setup-1\">Setup</h2>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_links() {
+        let content = "\
+See [API](references/api.md#auth \"API\"), [docs][d], <https://example.com>,
+![flow](img/flow.png), and [bad](javascript:alert(1)).
+
+[d]: https://docs.example.com
+";
+        let html = render_html(content, |target| {
+            target
+                .starts_with("references/")
+                .then(|| format!("/api/skills/forms/{}", target))
+        });
+        assert_eq!(
+            html,
+            "<p>See <a href=\"/api/skills/forms/references/api.md#auth\">API</a>, \
+             <a href=\"https://docs.example.com\">docs</a>, \
+             <a href=\"https://example.com\">https://example.com</a>,\n\
+             <img src=\"img/flow.png\" alt=\"flow\">, and bad.</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_unsafe_links() {
+        for target in [
+            "< javascript:alert(1)>",
+            "<java\tscript:alert(1)>",
+            "<\u{1}javascript:alert(1)>",
+            "JavaScript:alert(1)",
+            "javascript&#58;alert(1)",
+            "&#106;avascript:alert(1)",
+            "&#x6A;ava&Tab;script&colon;alert(1)",
+            "data:text/html,x",
+            "vbscript:x",
+        ] {
+            let html = render_html(&format!("[x]({})", target), |_| None);
+            assert_eq!(html, "<p>x</p>\n", "{:?}", target);
+        }
+
+        let html = render_html("[x](a&amp;b) [y](./c:d) [z](< https://example.com >)", |_| None);
+        assert_eq!(
+            html,
+            "<p><a href=\"a&amp;amp;b\">x</a> <a href=\"./c:d\">y</a> <a href=\"https://example.com\">z</a></p>\n"
+        );
+    }
+}
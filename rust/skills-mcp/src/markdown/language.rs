@@ -0,0 +1,230 @@
+//! Natural language detection for skill content.
+//!
+//! A deliberately small detector: non-Latin scripts are identified from
+//! their Unicode blocks, and Latin-script languages by counting common
+//! function words. Code blocks, inline code, and link targets are ignored
+//! so examples don't skew the result. Good enough to partition a library;
+//! declare `language` in `_meta.json` where it guesses wrong.
+
+/// Minimum number of prose words (or script characters) before guessing.
+const MIN_SAMPLE: usize = 8;
+
+/// Share of Latin-script words that must be stopwords of the winner.
+const MIN_STOPWORD_RATIO: f64 = 0.05;
+
+/// Common function words per Latin-script language (ISO 639-1 code).
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "of", "to", "is", "in", "that", "it", "for", "with", "as", "on", "this",
+            "be", "are", "you", "or", "an", "when", "use", "your", "from", "not", "by", "if",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "la", "de", "que", "y", "en", "los", "las", "del", "se", "por", "un", "una",
+            "para", "con", "es", "al", "lo", "como", "más", "pero", "su", "si", "cuando", "usa",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "de", "des", "et", "est", "un", "une", "du", "en", "que", "qui",
+            "pour", "dans", "pas", "sur", "au", "avec", "ce", "il", "vous", "ne", "se", "si",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "mit", "ein", "eine", "den", "zu", "von",
+            "für", "auf", "im", "sie", "es", "wenn", "auch", "sich", "des", "dem", "wird", "oder",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "a", "os", "as", "de", "que", "e", "do", "da", "em", "um", "uma", "para", "com",
+            "não", "no", "na", "se", "por", "mais", "dos", "das", "ao", "quando", "você",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "lo", "la", "gli", "le", "di", "che", "e", "è", "un", "una", "per", "non",
+            "con", "del", "della", "in", "si", "da", "sono", "come", "al", "quando", "anche",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "van", "is", "dat", "niet", "op", "te", "voor", "met",
+            "zijn", "die", "er", "aan", "ook", "als", "bij", "wordt", "je", "naar", "wanneer",
+        ],
+    ),
+];
+
+/// Detect the dominant natural language of markdown text.
+///
+/// Returns an ISO 639-1 code (e.g. `en`, `de`, `ja`), or None when the
+/// prose is too short or too mixed to call.
+pub fn detect_language(text: &str) -> Option<String> {
+    let prose = prose(text);
+
+    if let Some(lang) = detect_script(&prose) {
+        return Some(lang.to_string());
+    }
+
+    let words: Vec<String> = prose
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() < MIN_SAMPLE {
+        return None;
+    }
+
+    let (lang, hits) = STOPWORDS
+        .iter()
+        .map(|(lang, stopwords)| {
+            let hits = words.iter().filter(|w| stopwords.contains(&w.as_str())).count();
+            (*lang, hits)
+        })
+        .max_by_key(|(_, hits)| *hits)?;
+
+    (hits as f64 / words.len() as f64 >= MIN_STOPWORD_RATIO).then(|| lang.to_string())
+}
+
+/// Whether a language tag is well formed: a 2-3 letter lowercase primary
+/// subtag with an optional region or script subtag (`en`, `pt-BR`).
+pub fn is_language_tag(tag: &str) -> bool {
+    let mut parts = tag.split('-');
+    let primary = parts.next().unwrap_or_default();
+    let primary_ok = (2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_lowercase());
+    let rest: Vec<&str> = parts.collect();
+    primary_ok
+        && rest.len() <= 1
+        && rest
+            .iter()
+            .all(|s| (2..=4).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Whether `tag` matches a language filter, comparing primary subtags
+/// case-insensitively (a `pt` filter matches `pt-BR`).
+pub fn language_matches(tag: &str, filter: &str) -> bool {
+    let primary = |t: &str| t.split('-').next().unwrap_or_default().to_ascii_lowercase();
+    primary(tag) == primary(filter)
+}
+
+/// Identify languages with their own script from character counts.
+fn detect_script(text: &str) -> Option<&'static str> {
+    let mut counts = [0usize; 9];
+    let mut letters = 0;
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        let slot = match c as u32 {
+            0x3040..=0x30FF => 0,                   // Hiragana, Katakana
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => 1, // Hangul
+            0x4E00..=0x9FFF | 0x3400..=0x4DBF => 2, // CJK ideographs
+            0x0400..=0x04FF => 3,                   // Cyrillic
+            0x0600..=0x06FF => 4,                   // Arabic
+            0x0590..=0x05FF => 5,                   // Hebrew
+            0x0370..=0x03FF => 6,                   // Greek
+            0x0900..=0x097F => 7,                   // Devanagari
+            0x0E00..=0x0E7F => 8,                   // Thai
+            _ => continue,
+        };
+        counts[slot] += 1;
+    }
+
+    let script_chars: usize = counts.iter().sum();
+    if script_chars < MIN_SAMPLE || script_chars * 2 < letters {
+        return None;
+    }
+
+    // Japanese mixes kana with ideographs; any real share of kana decides it
+    if counts[0] * 10 >= script_chars {
+        return Some("ja");
+    }
+    let (slot, _) = counts.iter().enumerate().max_by_key(|(_, n)| **n)?;
+    Some(["ja", "ko", "zh", "ru", "ar", "he", "el", "hi", "th"][slot])
+}
+
+/// Strip frontmatter, code, and link targets, leaving prose.
+fn prose(text: &str) -> String {
+    let text = super::strip_frontmatter(text);
+    let mut out = String::with_capacity(text.len());
+    let mut in_fence = false;
+
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut in_code = false;
+        let mut in_target = false;
+        let mut prev = ' ';
+        for c in line.chars() {
+            match c {
+                '`' => in_code = !in_code,
+                '(' if prev == ']' => in_target = true,
+                ')' if in_target => in_target = false,
+                _ if in_code || in_target => {}
+                _ => out.push(c),
+            }
+            prev = c;
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_latin_languages() {
+        let en = "# Forms\n\nUse this skill when you need to validate the fields of a form in the browser.";
+        assert_eq!(detect_language(en).as_deref(), Some("en"));
+
+        let de = "# Formulare\n\nDiese Anleitung zeigt, wie man die Felder eines Formulars mit der Bibliothek prüft, wenn es nicht anders geht.";
+        assert_eq!(detect_language(de).as_deref(), Some("de"));
+
+        let es = "# Formularios\n\nUsa esta guía cuando necesites validar los campos de un formulario en el navegador, con la librería que prefieras.";
+        assert_eq!(detect_language(es).as_deref(), Some("es"));
+
+        // Code doesn't count as prose
+        let code = "```js\nconst the = and.of.to.is.in.that.it.for.with;\n```\n";
+        assert_eq!(detect_language(code), None);
+        assert_eq!(detect_language("Short."), None);
+    }
+
+    #[test]
+    fn test_detect_scripts() {
+        assert_eq!(
+            detect_language("フォームの検証にはこのスキルを使ってください。").as_deref(),
+            Some("ja")
+        );
+        assert_eq!(detect_language("使用此技能验证表单中的所有字段。").as_deref(), Some("zh"));
+        assert_eq!(
+            detect_language("Используйте этот навык для проверки форм.").as_deref(),
+            Some("ru")
+        );
+    }
+
+    #[test]
+    fn test_language_tags() {
+        assert!(is_language_tag("en"));
+        assert!(is_language_tag("pt-BR"));
+        assert!(!is_language_tag("EN"));
+        assert!(!is_language_tag("english"));
+        assert!(language_matches("pt-BR", "PT"));
+        assert!(!language_matches("en", "es"));
+    }
+}
@@ -0,0 +1,186 @@
+//! Link extraction.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Inline links and images: `[text](target "title")`.
+fn inline_link_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"!?\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap())
+}
+
+/// Reference-style link definitions: `[id]: target`.
+fn reference_def_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\s{0,3}\[[^\]]+\]:\s*<?([^\s>]+)>?").unwrap())
+}
+
+/// Extract link targets from markdown, in document order.
+///
+/// Covers inline links, images, and reference definitions. Links inside
+/// fenced code blocks and inline code spans are ignored.
+pub fn extract_links(content: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        if let Some(caps) = reference_def_regex().captures(line) {
+            links.push(caps[1].to_string());
+            continue;
+        }
+
+        let line = strip_code_spans(line);
+        links.extend(
+            inline_link_regex()
+                .captures_iter(&line)
+                .map(|caps| caps[1].to_string()),
+        );
+    }
+
+    links
+}
+
+/// Rewrite link targets in markdown, leaving everything else untouched.
+///
+/// `rewrite` is called for each inline link, image, and reference
+/// definition target outside code; returning Some replaces the target.
+/// Returns the new content and the number of targets replaced.
+pub fn rewrite_links(content: &str, rewrite: impl Fn(&str) -> Option<String>) -> (String, usize) {
+    let mut out = String::with_capacity(content.len());
+    let mut count = 0;
+    let mut in_fence = false;
+
+    for line in content.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            out.push_str(line);
+            continue;
+        }
+        if in_fence {
+            out.push_str(line);
+            continue;
+        }
+
+        if let Some(caps) = reference_def_regex().captures(line) {
+            let target = caps.get(1).unwrap();
+            if let Some(new_target) = rewrite(target.as_str()) {
+                out.push_str(&line[..target.start()]);
+                out.push_str(&new_target);
+                out.push_str(&line[target.end()..]);
+                count += 1;
+                continue;
+            }
+            out.push_str(line);
+            continue;
+        }
+
+        // Only rewrite outside inline code spans
+        for (i, segment) in line.split('`').enumerate() {
+            if i > 0 {
+                out.push('`');
+            }
+            if i % 2 == 1 {
+                out.push_str(segment);
+                continue;
+            }
+
+            let mut last = 0;
+            for caps in inline_link_regex().captures_iter(segment) {
+                let target = caps.get(1).unwrap();
+                if let Some(new_target) = rewrite(target.as_str()) {
+                    out.push_str(&segment[last..target.start()]);
+                    out.push_str(&new_target);
+                    last = target.end();
+                    count += 1;
+                }
+            }
+            out.push_str(&segment[last..]);
+        }
+    }
+
+    (out, count)
+}
+
+/// Check whether a link target points outside the library (URL, email, anchor).
+pub fn is_external_link(target: &str) -> bool {
+    target.starts_with('#')
+        || target.starts_with("//")
+        || target.starts_with("mailto:")
+        || target
+            .split_once(':')
+            .map(|(scheme, _)| {
+                scheme.len() > 1
+                    && scheme
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c))
+            })
+            .unwrap_or(false)
+}
+
+/// Remove `inline code` spans from a line.
+fn strip_code_spans(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut in_code = false;
+    for c in line.chars() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if !in_code {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links() {
+        let content = "\
+See [forms](../forms/SKILL.md) and ![diagram](refs/flow.png \"Flow\").
+Ignore `[code](not-a-link.md)` spans.
+
+```
+[also](ignored.md)
+```
+
+[ref]: references/api.md
+";
+        assert_eq!(
+            extract_links(content),
+            vec!["../forms/SKILL.md", "refs/flow.png", "references/api.md"]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links() {
+        let content = "[a](../old/SKILL.md) `[b](../old/x.md)`\n\n[ref]: ../old/api.md\n";
+        let (rewritten, count) =
+            rewrite_links(content, |t| Some(t.replace("../old/", "../new/")));
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            rewritten,
+            "[a](../new/SKILL.md) `[b](../old/x.md)`\n\n[ref]: ../new/api.md\n"
+        );
+    }
+
+    #[test]
+    fn test_is_external_link() {
+        assert!(is_external_link("https://example.com"));
+        assert!(is_external_link("mailto:team@example.com"));
+        assert!(is_external_link("#usage"));
+        assert!(!is_external_link("references/api.md"));
+        assert!(!is_external_link("../forms/SKILL.md#setup"));
+    }
+}
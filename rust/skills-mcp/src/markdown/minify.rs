@@ -0,0 +1,189 @@
+//! Token-reducing cleanup of skill content for agent delivery.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Badge images, optionally wrapped in a link: `[![ci](…/badge.svg)](…)`.
+fn badge_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?i)\[!\[[^\]]*\]\([^)]*\)\]\([^)]*\)|!\[[^\]]*\]\([^)]*(?:shields\.io|badge)[^)]*\)",
+        )
+        .unwrap()
+    })
+}
+
+/// Inline links, capturing the link text.
+fn link_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap())
+}
+
+/// Link texts that mark a line as navigation (compared case-insensitively).
+const NAV_WORDS: &[&str] = &[
+    "back", "top", "previous", "prev", "next", "home", "index", "contents", "up", "←", "→",
+];
+
+/// Strip markdown decorations that don't carry meaning for a model.
+///
+/// Removes badges, HTML comments, navigation-only lines (e.g.
+/// `[← Previous](a.md) | [Next →](b.md)`), trailing whitespace, and
+/// repeated blank lines. Fenced code blocks are left untouched.
+pub fn minify(content: &str) -> String {
+    let content = strip_html_comments(content);
+
+    let mut out: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            out.push(line.to_string());
+            continue;
+        }
+        if in_fence {
+            out.push(line.to_string());
+            continue;
+        }
+
+        let without_badges = badge_regex().replace_all(line, "");
+        let text = without_badges.trim_end();
+
+        // Skip badge-only and navigation lines, and collapse blank runs
+        let only_badges = text.trim().is_empty() && !line.trim().is_empty();
+        let repeated_blank = text.is_empty() && out.last().is_none_or(|l| l.is_empty());
+        if only_badges || repeated_blank || is_navigation(text) {
+            continue;
+        }
+        out.push(text.to_string());
+    }
+
+    // Drop blank lines and rules left dangling at the end
+    while out
+        .last()
+        .is_some_and(|l| l.is_empty() || matches!(l.trim(), "---" | "***" | "___"))
+    {
+        out.pop();
+    }
+
+    out.join("\n")
+}
+
+/// Remove `<!-- … -->` comments outside fenced code blocks.
+fn strip_html_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut in_fence = false;
+    let mut in_comment = false;
+
+    for line in content.split_inclusive('\n') {
+        if !in_comment && line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            out.push_str(line);
+            continue;
+        }
+
+        let touched = in_comment || line.contains("<!--");
+        let mut rest = line;
+        let mut kept = String::new();
+        loop {
+            if in_comment {
+                match rest.find("-->") {
+                    Some(end) => {
+                        rest = &rest[end + 3..];
+                        in_comment = false;
+                    }
+                    None => break,
+                }
+            } else {
+                match rest.find("<!--") {
+                    Some(start) => {
+                        kept.push_str(&rest[..start]);
+                        rest = &rest[start + 4..];
+                        in_comment = true;
+                    }
+                    None => {
+                        kept.push_str(rest);
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Drop lines that held nothing but a comment
+        if touched && kept.trim().is_empty() {
+            continue;
+        }
+        if touched && !kept.ends_with('\n') && line.ends_with('\n') {
+            kept.push('\n');
+        }
+        out.push_str(&kept);
+    }
+
+    out
+}
+
+/// Whether a line consists only of navigation links and separators.
+fn is_navigation(line: &str) -> bool {
+    let mut has_nav_link = false;
+    let remainder = link_regex().replace_all(line, |caps: &regex::Captures| {
+        let text = caps[1].to_lowercase();
+        let mut words = text.split(|c: char| c.is_whitespace() || c.is_ascii_punctuation());
+        if words.any(|w| NAV_WORDS.contains(&w)) {
+            has_nav_link = true;
+        }
+        ""
+    });
+
+    has_nav_link
+        && remainder
+            .chars()
+            .all(|c| c.is_whitespace() || "|·•-–—/<>←→↑".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minify_strips_decorations() {
+        let content = "\
+# Forms
+[![CI](https://ci.example/badge.svg)](https://ci.example) ![npm](https://img.shields.io/npm/v/x)
+
+<!-- internal note
+spanning lines -->
+Use zod.   <!-- inline -->
+
+
+
+## Usage
+
+```html
+<!-- kept in code -->
+
+
+```
+
+---
+[← Previous](intro.md) | [Next →](usage.md)
+[Back to top](#forms)
+";
+
+        let minified = minify(content);
+        assert_eq!(
+            minified,
+            "# Forms\n\nUse zod.\n\n## Usage\n\n```html\n<!-- kept in code -->\n\n\n```"
+        );
+        assert!(minified.len() < content.len());
+    }
+
+    #[test]
+    fn test_minify_keeps_content_links() {
+        let content = "See [the next section](usage.md) for details.\n[Next](usage.md)";
+        assert_eq!(minify(content), "See [the next section](usage.md) for details.");
+    }
+}
@@ -0,0 +1,33 @@
+//! Markdown parsing helpers for skill content.
+//!
+//! These are intentionally lightweight line-based parsers: skill files are
+//! conventional markdown and don't need a full CommonMark implementation.
+
+mod code;
+mod frontmatter;
+mod highlight;
+mod html;
+mod language;
+mod links;
+mod minify;
+mod outline;
+mod sections;
+mod stats;
+mod tables;
+mod tokens;
+mod variables;
+
+pub use code::{extract_code_blocks, normalize_code_language, CodeBlock};
+pub use frontmatter::{parse_frontmatter, split_frontmatter, strip_frontmatter};
+pub use highlight::highlight;
+pub use html::render_html;
+pub use language::{detect_language, is_language_tag, language_matches};
+pub use links::{extract_links, is_external_link, rewrite_links};
+pub use minify::minify;
+pub use outline::{outline, Outline, OutlineSection};
+pub(crate) use sections::parse_heading;
+pub use sections::{find_section, quick_answer};
+pub use stats::{text_stats, TextStats};
+pub use tables::{extract_tables, Table};
+pub use tokens::estimate_tokens;
+pub use variables::{find_variables, render_variables};
@@ -0,0 +1,151 @@
+//! Heading outline of a markdown document.
+
+use serde::Serialize;
+
+use super::frontmatter::split_frontmatter;
+use super::sections::{parse_heading, Anchors};
+use super::tokens::estimate_tokens;
+
+/// A heading and the section it opens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutlineSection {
+    /// Heading level, 1 through 6.
+    pub level: usize,
+    /// Heading text.
+    pub title: String,
+    /// Anchor id, matching the one in the rendered HTML.
+    pub anchor: String,
+    /// 1-based line of the heading in the file.
+    pub line: usize,
+    /// Estimated tokens from the heading up to its first subsection.
+    pub tokens: u64,
+    /// Estimated tokens of the whole section, subsections included.
+    pub total_tokens: u64,
+    /// Subsections, in document order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineSection>,
+}
+
+/// The heading outline of a markdown document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Outline {
+    /// Estimated tokens before the first heading, frontmatter excluded.
+    pub preamble_tokens: u64,
+    /// Top-level sections.
+    pub sections: Vec<OutlineSection>,
+}
+
+/// Build the heading hierarchy of `content` with token estimates per section.
+///
+/// Frontmatter is skipped, as are headings inside fenced code blocks. A
+/// section nests under the nearest preceding heading of a lower level, so
+/// skipped levels (`#` then `###`) still nest.
+pub fn outline(content: &str) -> Outline {
+    let (_, body) = split_frontmatter(content);
+    let first_line = content[..content.len() - body.len()].matches('\n').count() + 1;
+
+    // Flat list of headings with their own text, then nested by level.
+    let mut preamble = String::new();
+    let mut flat: Vec<(OutlineSection, String)> = Vec::new();
+    let mut anchors = Anchors::default();
+    let mut fence: Option<&str> = None;
+
+    for (i, line) in body.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+        match (fence, marker) {
+            (Some(open), Some(m)) if m == open => fence = None,
+            (None, Some(m)) => fence = Some(m),
+            _ => {}
+        }
+        let indent = line.len() - trimmed.len();
+        let heading = match fence.or(marker) {
+            None if indent < 4 => parse_heading(trimmed),
+            _ => None,
+        };
+
+        if let Some((level, title)) = heading {
+            let section = OutlineSection {
+                level,
+                title: title.to_string(),
+                anchor: anchors.anchor(title),
+                line: first_line + i,
+                tokens: 0,
+                total_tokens: 0,
+                children: Vec::new(),
+            };
+            flat.push((section, String::new()));
+        }
+
+        let text = flat.last_mut().map_or(&mut preamble, |(_, text)| text);
+        text.push_str(line);
+        text.push('\n');
+    }
+
+    let mut sections = Vec::new();
+    let mut open: Vec<OutlineSection> = Vec::new();
+    for (mut section, text) in flat {
+        section.tokens = estimate_tokens(text.trim_end());
+        section.total_tokens = section.tokens;
+        close_sections(&mut open, &mut sections, section.level);
+        open.push(section);
+    }
+    close_sections(&mut open, &mut sections, 0);
+
+    Outline {
+        preamble_tokens: estimate_tokens(preamble.trim()),
+        sections,
+    }
+}
+
+/// Pop sections at `level` or deeper off `open`, attaching each to its parent
+/// (or to `top` when it has none).
+fn close_sections(open: &mut Vec<OutlineSection>, top: &mut Vec<OutlineSection>, level: usize) {
+    while open.last().is_some_and(|s| s.level >= level) {
+        let done = open.pop().unwrap();
+        match open.last_mut() {
+            Some(parent) => {
+                parent.total_tokens += done.total_tokens;
+                parent.children.push(done);
+            }
+            None => top.push(done),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline() {
+        let content = "---\nname: forms\n---\nIntro text.\n\n# Forms\n\nUse zod.\n\n## Setup\n\n```sh\n# not a heading\n```\n\n### Install\n\nnpm i\n\n## Setup\n\nAgain.\n\n# Other\n";
+        let outline = outline(content);
+
+        assert_eq!(outline.preamble_tokens, estimate_tokens("Intro text."));
+        assert_eq!(outline.sections.len(), 2);
+
+        let forms = &outline.sections[0];
+        assert_eq!((forms.level, forms.title.as_str(), forms.line), (1, "Forms", 6));
+        assert_eq!(forms.tokens, estimate_tokens("# Forms\n\nUse zod."));
+        let titles: Vec<_> = forms.children.iter().map(|s| s.anchor.as_str()).collect();
+        assert_eq!(titles, vec!["setup", "setup-1"]);
+
+        let setup = &forms.children[0];
+        assert_eq!(setup.children[0].title, "Install");
+        assert_eq!(setup.total_tokens, setup.tokens + setup.children[0].tokens);
+        assert_eq!(
+            forms.total_tokens,
+            forms.tokens + forms.children.iter().map(|s| s.total_tokens).sum::<u64>()
+        );
+        assert!(outline.sections[1].children.is_empty());
+    }
+
+    #[test]
+    fn test_outline_skipped_levels() {
+        let outline = outline("### Deep\n\n# Top\n\n### Nested\n");
+        let titles: Vec<_> = outline.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Deep", "Top"]);
+        assert_eq!(outline.sections[1].children[0].title, "Nested");
+    }
+}
@@ -0,0 +1,140 @@
+//! Heading-delimited section extraction.
+
+use std::collections::HashMap;
+
+use crate::models::SkillMeta;
+
+/// Headings recognized as a quick-answer section (compared case-insensitively).
+const QUICK_ANSWER_HEADINGS: &[&str] = &["tl;dr", "tldr", "quick answer"];
+
+/// Parse a markdown heading line into its level and title.
+pub(crate) fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim()))
+}
+
+/// Heading anchors handed out so far in one document.
+#[derive(Debug, Default)]
+pub(crate) struct Anchors {
+    /// Times each slug was used, to keep anchors unique.
+    used: HashMap<String, usize>,
+}
+
+impl Anchors {
+    /// A unique anchor for a heading: lowercased, spaces as hyphens, other
+    /// punctuation dropped, and `-1`, `-2`, ... appended to repeats.
+    pub(crate) fn anchor(&mut self, title: &str) -> String {
+        let slug: String = title
+            .to_lowercase()
+            .chars()
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+                _ => None,
+            })
+            .collect();
+        let count = self.used.entry(slug.clone()).or_insert(0);
+        let anchor = match *count {
+            0 => slug,
+            n => format!("{}-{}", slug, n),
+        };
+        *count += 1;
+        anchor
+    }
+}
+
+/// Find the body of the first section whose heading matches one of `titles`.
+///
+/// Matching is case-insensitive. The section ends at the next heading of the
+/// same or higher level. Headings inside fenced code blocks are ignored.
+pub fn find_section(content: &str, titles: &[&str]) -> Option<String> {
+    let mut in_fence = false;
+    let mut section_level = None;
+    let mut body = Vec::new();
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+
+        let heading = if in_fence { None } else { parse_heading(line) };
+
+        match (section_level, heading) {
+            (None, Some((level, title))) => {
+                if titles.iter().any(|t| t.eq_ignore_ascii_case(title)) {
+                    section_level = Some(level);
+                }
+            }
+            (Some(current), Some((level, _))) if level <= current => break,
+            (Some(_), _) => body.push(line),
+            (None, None) => {}
+        }
+    }
+
+    section_level.map(|_| body.join("\n").trim().to_string())
+}
+
+/// Extract a quick answer from a `## TL;DR` or `## Quick Answer` section.
+///
+/// Returns the first paragraph of the section with whitespace collapsed,
+/// truncated to a bounded length.
+pub fn quick_answer(content: &str) -> Option<String> {
+    let section = find_section(content, QUICK_ANSWER_HEADINGS)?;
+
+    let paragraph = section
+        .split("\n\n")
+        .map(str::trim)
+        .find(|p| !p.is_empty())?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if paragraph.chars().count() > SkillMeta::MAX_QUICK_ANSWER_CHARS {
+        let truncated: String = paragraph
+            .chars()
+            .take(SkillMeta::MAX_QUICK_ANSWER_CHARS - 3)
+            .collect();
+        Some(format!("{}...", truncated.trim_end()))
+    } else {
+        Some(paragraph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_section_stops_at_sibling_heading() {
+        let content = "# Forms\n\n## Usage\n\nUse it.\n\n### Detail\n\nMore.\n\n## Other\n\nNo.";
+        let section = find_section(content, &["usage"]).unwrap();
+
+        assert!(section.contains("Use it."));
+        assert!(section.contains("More."));
+        assert!(!section.contains("No."));
+    }
+
+    #[test]
+    fn test_quick_answer_first_paragraph() {
+        let content = "# Forms\n\n## TL;DR\n\nUse react-hook-form\nwith zod.\n\nDetails follow.\n";
+
+        assert_eq!(
+            quick_answer(content).as_deref(),
+            Some("Use react-hook-form with zod.")
+        );
+        assert!(quick_answer("# Forms\n\nNo summary.").is_none());
+    }
+
+    #[test]
+    fn test_headings_in_code_fences_ignored() {
+        let content = "```bash\n# TL;DR\n```\n\nText";
+        assert!(quick_answer(content).is_none());
+    }
+}
@@ -0,0 +1,73 @@
+//! Size statistics for markdown text.
+
+use serde::Serialize;
+
+use super::sections::parse_heading;
+use super::tokens::estimate_tokens;
+
+/// Counts describing a markdown text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TextStats {
+    /// Whitespace-separated words containing a letter or digit, code
+    /// included; bare markup like `#` or `---` isn't counted.
+    pub words: usize,
+
+    /// Headings outside code blocks.
+    pub headings: usize,
+
+    /// Fenced code blocks.
+    pub code_blocks: usize,
+
+    /// Approximate model tokens; see [`estimate_tokens`].
+    pub tokens: u64,
+}
+
+impl TextStats {
+    /// Add another text's counts to these.
+    pub fn add(&mut self, other: &TextStats) {
+        self.words += other.words;
+        self.headings += other.headings;
+        self.code_blocks += other.code_blocks;
+        self.tokens += other.tokens;
+    }
+}
+
+/// Count words, headings, code blocks, and tokens in markdown.
+pub fn text_stats(content: &str) -> TextStats {
+    let mut stats = TextStats {
+        words: content
+            .split_whitespace()
+            .filter(|w| w.chars().any(char::is_alphanumeric))
+            .count(),
+        tokens: estimate_tokens(content),
+        ..Default::default()
+    };
+
+    let mut in_fence = false;
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            if !in_fence {
+                stats.code_blocks += 1;
+            }
+            in_fence = !in_fence;
+        } else if !in_fence && parse_heading(line).is_some() {
+            stats.headings += 1;
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_stats() {
+        let stats = text_stats("# Forms\n\nUse zod.\n\n```ts\n# not a heading\n```\n\n## Usage\n");
+        assert_eq!(stats.headings, 2);
+        assert_eq!(stats.code_blocks, 1);
+        assert_eq!(stats.words, 8);
+        assert_eq!(stats.tokens, estimate_tokens("# Forms\n\nUse zod.\n\n```ts\n# not a heading\n```\n\n## Usage\n"));
+    }
+}
@@ -0,0 +1,156 @@
+//! GitHub-flavored markdown table extraction.
+
+use serde::Serialize;
+
+use super::sections::parse_heading;
+
+/// A table parsed from markdown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Table {
+    /// Nearest heading above the table.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+
+    /// 1-based line number of the header row.
+    pub line: usize,
+
+    /// Column headers.
+    pub headers: Vec<String>,
+
+    /// Body rows, each with one cell per header.
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Extract every table from markdown, in document order.
+///
+/// A table is a header row followed by a delimiter row (`| --- | :-: |`)
+/// with the same number of cells. Body rows end at the first line without
+/// a pipe; short rows are padded and long rows truncated to the header
+/// width. Tables inside fenced code blocks are ignored.
+pub fn extract_tables(content: &str) -> Vec<Table> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut tables = Vec::new();
+    let mut heading = None;
+    let mut in_fence = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            i += 1;
+            continue;
+        }
+        if let Some((_, title)) = parse_heading(line) {
+            heading = Some(title.to_string());
+        }
+
+        let headers = split_row(line);
+        let is_table = line.contains('|')
+            && lines
+                .get(i + 1)
+                .is_some_and(|next| is_delimiter_row(next, headers.len()));
+        if !is_table {
+            i += 1;
+            continue;
+        }
+
+        let mut rows = Vec::new();
+        let mut end = i + 2;
+        while let Some(row) = lines.get(end).filter(|l| l.contains('|') && !l.trim().is_empty()) {
+            let mut cells = split_row(row);
+            cells.resize(headers.len(), String::new());
+            rows.push(cells);
+            end += 1;
+        }
+
+        tables.push(Table {
+            heading: heading.clone(),
+            line: i + 1,
+            headers,
+            rows,
+        });
+        i = end;
+    }
+
+    tables
+}
+
+/// Whether a line is a delimiter row with `columns` cells.
+pub(super) fn is_delimiter_row(line: &str, columns: usize) -> bool {
+    if !line.contains('-') {
+        return false;
+    }
+    let cells = split_row(line);
+    cells.len() == columns
+        && cells.iter().all(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+        })
+}
+
+/// Split a table row into trimmed cells.
+///
+/// Leading and trailing pipes are optional. Escaped pipes (`\|`) and pipes
+/// inside code spans don't split cells.
+pub(super) fn split_row(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = match trimmed.strip_suffix('|') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => trimmed,
+    };
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_code = false;
+    let mut chars = trimmed.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '`' => {
+                in_code = !in_code;
+                cell.push(c);
+            }
+            '|' if !in_code => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_tables() {
+        let content = "# Forms\n\n## Which library?\n\n| Need | Library | Notes |\n|:-----|:-------:|------:|\n| Simple | `useState` | a \\| b |\n| Complex | react-hook-form |\n\nText after.\n\n```\n| not | a |\n|---|---|\n```\n\nA | B\n--|--\n1 | 2 | 3\n";
+
+        let tables = extract_tables(content);
+        assert_eq!(tables.len(), 2);
+
+        let table = &tables[0];
+        assert_eq!(table.heading.as_deref(), Some("Which library?"));
+        assert_eq!(table.line, 5);
+        assert_eq!(table.headers, vec!["Need", "Library", "Notes"]);
+        assert_eq!(table.rows[0], vec!["Simple", "`useState`", "a | b"]);
+        assert_eq!(table.rows[1], vec!["Complex", "react-hook-form", ""]);
+
+        // Pipes without outer borders, and extra cells dropped
+        assert_eq!(tables[1].headers, vec!["A", "B"]);
+        assert_eq!(tables[1].rows, vec![vec!["1", "2"]]);
+    }
+
+    #[test]
+    fn test_requires_delimiter_row() {
+        assert!(extract_tables("a | b\nc | d\n").is_empty());
+        assert!(extract_tables("| a | b |\n| --- |\n").is_empty());
+    }
+}
@@ -0,0 +1,21 @@
+//! Approximate token counting.
+
+/// Estimate the number of model tokens in `text`.
+///
+/// Uses the common heuristic of about four characters per token, which is
+/// close enough for budgeting without shipping a tokenizer.
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abc"), 1);
+        assert_eq!(estimate_tokens("# Forms\n\nUse zod."), 5);
+    }
+}
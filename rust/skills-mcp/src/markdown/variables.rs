@@ -0,0 +1,51 @@
+//! `{{variable}}` placeholder substitution.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::{Captures, Regex};
+
+fn placeholder_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap())
+}
+
+/// Names of the `{{variable}}` placeholders in content, in first-use order.
+pub fn find_variables(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for caps in placeholder_regex().captures_iter(content) {
+        if !names.iter().any(|n| n == &caps[1]) {
+            names.push(caps[1].to_string());
+        }
+    }
+    names
+}
+
+/// Replace `{{variable}}` placeholders with values.
+///
+/// Placeholders without a value are left as written.
+pub fn render_variables(content: &str, values: &HashMap<String, String>) -> String {
+    placeholder_regex()
+        .replace_all(content, |caps: &Captures| match values.get(&caps[1]) {
+            Some(value) => value.clone(),
+            None => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_render_variables() {
+        let content = "Deploy to {{ env }}:\n\n```bash\nkubectl --context {{env}} -n {{namespace}}\n```";
+        assert_eq!(find_variables(content), vec!["env", "namespace"]);
+
+        let values = HashMap::from([("env".to_string(), "prod".to_string())]);
+        assert_eq!(
+            render_variables(content, &values),
+            "Deploy to prod:\n\n```bash\nkubectl --context prod -n {{namespace}}\n```"
+        );
+    }
+}
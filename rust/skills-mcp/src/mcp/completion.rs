@@ -0,0 +1,254 @@
+//! Argument completion (`completion/complete`).
+//!
+//! Clients ask for completions of one argument of a prompt or resource
+//! template as the user types. Arguments are completed by name, whatever
+//! they belong to:
+//!
+//! - `name`, `skill`, `domain`: skill names and aliases
+//! - `sub_skill`: sub-skill names of the skill given in the request's
+//!   context arguments, or of every skill without one
+//! - `tag`, `tags`: tags
+//!
+//! Candidates starting with the typed value come first, then those with a
+//! word starting with it, containing it, or containing its characters in
+//! order. Archived skills and skills hidden from the caller are never
+//! offered.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::resources::SCHEME;
+use super::tools::ServiceContext;
+use crate::models::{ErrorCode, ErrorResponse, SkillMeta};
+
+/// Most values returned in one completion, as the protocol allows.
+pub const MAX_COMPLETIONS: usize = 100;
+
+/// What is being completed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum CompletionRef {
+    /// An argument of a prompt.
+    #[serde(rename = "ref/prompt")]
+    Prompt {
+        /// Prompt name.
+        name: String,
+    },
+    /// A variable of a resource template.
+    #[serde(rename = "ref/resource")]
+    Resource {
+        /// Resource template URI.
+        uri: String,
+    },
+}
+
+/// The argument being typed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompletionArgument {
+    /// Argument name.
+    pub name: String,
+    /// What has been typed so far.
+    #[serde(default)]
+    pub value: String,
+}
+
+/// Arguments already filled in.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CompletionContext {
+    /// Values of the other arguments, by name.
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Params of `completion/complete`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompleteRequest {
+    /// Prompt or resource template the argument belongs to.
+    #[serde(rename = "ref")]
+    pub reference: CompletionRef,
+    /// The argument to complete.
+    pub argument: CompletionArgument,
+    /// Arguments already filled in.
+    #[serde(default)]
+    pub context: CompletionContext,
+}
+
+/// Completion values.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Completion {
+    /// Best matches first, at most [`MAX_COMPLETIONS`].
+    pub values: Vec<String>,
+    /// Matches in all.
+    pub total: usize,
+    /// Whether matches were left out.
+    pub has_more: bool,
+}
+
+/// Result of `completion/complete`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompleteResult {
+    /// Completion values.
+    pub completion: Completion,
+}
+
+/// Complete an argument from the skill index.
+pub fn complete(ctx: &ServiceContext, req: &CompleteRequest) -> Result<CompleteResult, ErrorResponse> {
+    if let CompletionRef::Resource { uri } = &req.reference {
+        if !uri.starts_with(SCHEME) {
+            return Err(ErrorResponse::new(
+                ErrorCode::InvalidRequest,
+                format!("Unknown resource template '{}'", uri),
+            ));
+        }
+    }
+
+    let index = ctx.indexer.get_skill_index();
+    let skills: Vec<&SkillMeta> = index
+        .skills
+        .iter()
+        .filter(|s| !s.archived && ctx.is_visible(&s.name))
+        .collect();
+
+    let candidates: BTreeSet<&str> = match req.argument.name.as_str() {
+        "name" | "skill" | "domain" => skills
+            .iter()
+            .flat_map(|s| std::iter::once(&s.name).chain(&s.aliases))
+            .map(String::as_str)
+            .collect(),
+        "sub_skill" => {
+            let skill = ["name", "skill", "domain"]
+                .iter()
+                .find_map(|key| req.context.arguments.get(*key))
+                .and_then(|name| index.find(name));
+            match skill {
+                Some(skill) if skills.iter().any(|s| s.name == skill.name) => {
+                    skill.sub_skill_names().into_iter().collect()
+                }
+                Some(_) => BTreeSet::new(),
+                None => skills.iter().flat_map(|s| s.sub_skill_names()).collect(),
+            }
+        }
+        "tag" | "tags" => skills.iter().flat_map(|s| &s.tags).map(String::as_str).collect(),
+        _ => BTreeSet::new(),
+    };
+
+    let query = req.argument.value.trim().to_lowercase();
+    let mut matches: Vec<(u8, &str)> = candidates
+        .into_iter()
+        .filter_map(|candidate| match_rank(&query, candidate).map(|rank| (rank, candidate)))
+        .collect();
+    matches.sort();
+
+    let total = matches.len();
+    Ok(CompleteResult {
+        completion: Completion {
+            values: matches
+                .into_iter()
+                .take(MAX_COMPLETIONS)
+                .map(|(_, candidate)| candidate.to_string())
+                .collect(),
+            total,
+            has_more: total > MAX_COMPLETIONS,
+        },
+    })
+}
+
+/// How well a candidate matches what was typed, lower being better, or
+/// None when it doesn't.
+fn match_rank(query: &str, candidate: &str) -> Option<u8> {
+    let candidate = candidate.to_lowercase();
+    if candidate.starts_with(query) {
+        return Some(0);
+    }
+    if candidate.split(['-', '_', '/', ' ']).any(|word| word.starts_with(query)) {
+        return Some(1);
+    }
+    if candidate.contains(query) {
+        return Some(2);
+    }
+    let mut chars = candidate.chars();
+    query.chars().all(|q| chars.any(|c| c == q)).then_some(3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::SkillIndexer;
+    use serde_json::json;
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn request(value: serde_json::Value) -> CompleteRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_complete() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, meta) in [
+            ("forms", r#"{"name": "forms", "description": "Forms", "tags": ["frontend", "validation"],
+                "aliases": ["form-handling"],
+                "sub_skills": [{"name": "react", "file": "react.md"}, {"name": "vue", "file": "vue.md"}]}"#),
+            ("react-forms", r#"{"name": "react-forms", "description": "React forms", "tags": ["react"]}"#),
+            ("old-forms", r#"{"name": "old-forms", "description": "Old", "archived": true}"#),
+            ("billing", r#"{"name": "billing", "description": "Billing", "visibility": "private"}"#),
+        ] {
+            fs::create_dir_all(temp_dir.path().join(name)).unwrap();
+            fs::write(temp_dir.path().join(name).join("_meta.json"), meta).unwrap();
+            fs::write(temp_dir.path().join(name).join("SKILL.md"), "# Skill").unwrap();
+        }
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let ctx = ServiceContext::new(indexer);
+
+        let names = |value: &str| {
+            complete(
+                &ctx,
+                &request(json!({
+                    "ref": {"type": "ref/resource", "uri": "skill://{name}"},
+                    "argument": {"name": "name", "value": value},
+                })),
+            )
+            .unwrap()
+            .completion
+        };
+        assert_eq!(names("form").values, vec!["form-handling", "forms", "react-forms"]);
+        assert_eq!(names("rfm").values, vec!["react-forms"]);
+        assert_eq!(names("").total, 3);
+        assert!(names("bill").values.is_empty());
+
+        let sub_skills = complete(
+            &ctx,
+            &request(json!({
+                "ref": {"type": "ref/prompt", "name": "load"},
+                "argument": {"name": "sub_skill", "value": "v"},
+                "context": {"arguments": {"name": "form-handling"}},
+            })),
+        )
+        .unwrap();
+        assert_eq!(sub_skills.completion.values, vec!["vue"]);
+
+        let tags = complete(
+            &ctx,
+            &request(json!({
+                "ref": {"type": "ref/prompt", "name": "load"},
+                "argument": {"name": "tag", "value": "re"},
+            })),
+        )
+        .unwrap();
+        assert_eq!(tags.completion.values, vec!["react", "frontend"]);
+
+        let error = complete(
+            &ctx,
+            &request(json!({
+                "ref": {"type": "ref/resource", "uri": "file:///{path}"},
+                "argument": {"name": "path", "value": ""},
+            })),
+        )
+        .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidRequest);
+    }
+}
@@ -0,0 +1,408 @@
+//! MCP over HTTP: the Streamable HTTP transport, with the older HTTP+SSE
+//! transport as a fallback for clients that predate it.
+//!
+//! Streamable HTTP (`/mcp`):
+//! - `POST` takes a JSON-RPC message or batch and answers with JSON, or
+//!   `202 Accepted` when it held only notifications. `initialize` starts a
+//!   session whose ID is returned in the `Mcp-Session-Id` header; every
+//!   later request must send it back.
+//! - `GET` opens an SSE stream of resource change notifications.
+//! - `DELETE` ends the session.
+//!
+//! HTTP+SSE fallback: `GET /sse` opens the stream and announces a
+//! `/messages?sessionId=...` endpoint in an `endpoint` event; requests
+//! POSTed there are answered on the stream.
+//!
+//! Streams send keep-alive comments so proxies don't drop idle connections.
+//! Sessions without an open stream expire after [`SESSION_TTL`] of
+//! inactivity.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post},
+    Json, Router,
+};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{debug, warn};
+
+use super::resources::ResourceTracker;
+use super::server::{dispatch, error_response, PARSE_ERROR};
+use super::tools::ServiceContext;
+
+/// Header carrying the session ID.
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Idle time after which a session without an open stream is dropped.
+pub const SESSION_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Interval between SSE keep-alive comments.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Messages buffered per stream before the sender waits.
+const STREAM_BUFFER: usize = 64;
+
+/// JSON-RPC error code for a request the transport can't accept.
+const INVALID_REQUEST: i64 = -32600;
+
+/// One connected client.
+#[derive(Debug)]
+struct Session {
+    resources: ResourceTracker,
+    stream: Mutex<Option<mpsc::Sender<Value>>>,
+    last_seen: Mutex<Instant>,
+}
+
+impl Session {
+    fn new(ctx: &ServiceContext) -> Self {
+        Self {
+            resources: ResourceTracker::new(ctx),
+            stream: Mutex::new(None),
+            last_seen: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// The open stream, if the client is listening.
+    fn stream(&self) -> Option<mpsc::Sender<Value>> {
+        self.stream.lock().clone().filter(|tx| !tx.is_closed())
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        self.stream().is_none() && self.last_seen.lock().elapsed() > ttl
+    }
+}
+
+/// Live MCP sessions on the HTTP transports.
+#[derive(Debug)]
+pub struct McpSessions {
+    sessions: Mutex<HashMap<String, Arc<Session>>>,
+    ttl: Duration,
+    ids: RandomState,
+    counter: AtomicU64,
+}
+
+impl Default for McpSessions {
+    fn default() -> Self {
+        Self::new(SESSION_TTL)
+    }
+}
+
+impl McpSessions {
+    /// Create an empty session table with the given idle timeout.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+            ttl,
+            ids: RandomState::new(),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Number of live sessions.
+    pub fn len(&self) -> usize {
+        self.sessions.lock().len()
+    }
+
+    /// Whether there are no live sessions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Start a session, returning its ID.
+    fn create(&self, ctx: &ServiceContext) -> (String, Arc<Session>) {
+        let id = self.next_id();
+        let session = Arc::new(Session::new(ctx));
+
+        let mut sessions = self.sessions.lock();
+        sessions.retain(|_, s| !s.is_expired(self.ttl));
+        sessions.insert(id.clone(), Arc::clone(&session));
+        (id, session)
+    }
+
+    /// Look up a live session and mark it active.
+    fn get(&self, id: &str) -> Option<Arc<Session>> {
+        let mut sessions = self.sessions.lock();
+        sessions.retain(|_, s| !s.is_expired(self.ttl));
+        let session = sessions.get(id)?;
+        *session.last_seen.lock() = Instant::now();
+        Some(Arc::clone(session))
+    }
+
+    /// End a session. Returns false if it didn't exist.
+    fn remove(&self, id: &str) -> bool {
+        self.sessions.lock().remove(id).is_some()
+    }
+
+    /// An unguessable session ID: 128 bits from randomly keyed hashers.
+    fn next_id(&self) -> String {
+        let count = self.counter.fetch_add(1, Ordering::Relaxed);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+
+        let mut high = self.ids.build_hasher();
+        high.write_u64(count);
+        high.write_u128(nanos);
+        let mut low = RandomState::new().build_hasher();
+        low.write_u64(high.finish());
+        low.write_u64(count);
+
+        format!("{:016x}{:016x}", high.finish(), low.finish())
+    }
+}
+
+/// State shared by the HTTP transport handlers.
+#[derive(Clone)]
+struct HttpState {
+    ctx: Arc<ServiceContext>,
+    sessions: Arc<McpSessions>,
+}
+
+/// Routes for both HTTP transports, to be merged into the API router.
+pub fn router(ctx: Arc<ServiceContext>, sessions: Arc<McpSessions>) -> Router {
+    Router::new()
+        .route(
+            "/mcp",
+            post(post_message).get(open_stream).delete(close_session),
+        )
+        .route("/sse", get(open_legacy_stream))
+        .route("/messages", post(post_legacy_message))
+        .with_state(HttpState { ctx, sessions })
+}
+
+// ============================================================================
+// Streamable HTTP
+// ============================================================================
+
+async fn post_message(
+    State(state): State<HttpState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let message: Value = match serde_json::from_str(&body) {
+        Ok(message) => message,
+        Err(e) => return jsonrpc_error(StatusCode::BAD_REQUEST, PARSE_ERROR, &e.to_string()),
+    };
+    let (messages, batch) = match message {
+        Value::Array(messages) if !messages.is_empty() => (messages, true),
+        Value::Array(_) => {
+            return jsonrpc_error(StatusCode::BAD_REQUEST, INVALID_REQUEST, "Empty batch");
+        }
+        message => (vec![message], false),
+    };
+
+    let initializing = messages
+        .iter()
+        .any(|m| m.get("method").and_then(Value::as_str) == Some("initialize"));
+
+    let (session_id, session) = if initializing {
+        let (id, session) = state.sessions.create(&state.ctx);
+        debug!("MCP session {} started", id);
+        (id, session)
+    } else {
+        match session_from_headers(&state, &headers) {
+            Ok(found) => found,
+            Err((status, message)) => return jsonrpc_error(status, INVALID_REQUEST, message),
+        }
+    };
+
+    let responses = dispatch_blocking(&state, &session, messages).await;
+
+    let mut response = match (responses.len(), batch) {
+        (0, _) => StatusCode::ACCEPTED.into_response(),
+        (_, true) => Json(Value::Array(responses)).into_response(),
+        (_, false) => Json(responses.into_iter().next().unwrap_or_default()).into_response(),
+    };
+    if let Ok(value) = HeaderValue::from_str(&session_id) {
+        response.headers_mut().insert(SESSION_HEADER, value);
+    }
+    response
+}
+
+async fn open_stream(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    let (_, session) = match session_from_headers(&state, &headers) {
+        Ok(found) => found,
+        Err((status, message)) => return jsonrpc_error(status, INVALID_REQUEST, message),
+    };
+
+    let events = attach_stream(&state, session).map(|m| Ok(message_event(&m)));
+    sse(events)
+}
+
+async fn close_session(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    let (id, _) = match session_from_headers(&state, &headers) {
+        Ok(found) => found,
+        Err((status, message)) => return jsonrpc_error(status, INVALID_REQUEST, message),
+    };
+
+    state.sessions.remove(&id);
+    debug!("MCP session {} closed", id);
+    StatusCode::NO_CONTENT.into_response()
+}
+
+// ============================================================================
+// HTTP+SSE fallback
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct LegacyQuery {
+    #[serde(rename = "sessionId")]
+    session_id: String,
+}
+
+async fn open_legacy_stream(State(state): State<HttpState>) -> Response {
+    let (id, session) = state.sessions.create(&state.ctx);
+    debug!("MCP SSE session {} started", id);
+
+    let endpoint = Event::default()
+        .event("endpoint")
+        .data(format!("/messages?sessionId={}", id));
+    let events = tokio_stream::once(endpoint)
+        .chain(attach_stream(&state, session).map(|m| message_event(&m)))
+        .map(Ok);
+    sse(events)
+}
+
+async fn post_legacy_message(
+    State(state): State<HttpState>,
+    Query(query): Query<LegacyQuery>,
+    body: String,
+) -> Response {
+    let Some(session) = state.sessions.get(&query.session_id) else {
+        return jsonrpc_error(StatusCode::NOT_FOUND, INVALID_REQUEST, "Unknown session");
+    };
+    let Some(stream) = session.stream() else {
+        return jsonrpc_error(StatusCode::GONE, INVALID_REQUEST, "Session stream is closed");
+    };
+
+    let response = match serde_json::from_str::<Value>(&body) {
+        Ok(message) => dispatch_blocking(&state, &session, vec![message]).await.pop(),
+        Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+    };
+    if let Some(response) = response {
+        if stream.send(response).await.is_err() {
+            return jsonrpc_error(StatusCode::GONE, INVALID_REQUEST, "Session stream is closed");
+        }
+    }
+    StatusCode::ACCEPTED.into_response()
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Dispatch messages on the blocking thread pool, since tools read skill
+/// files synchronously. Returns the responses that are due.
+async fn dispatch_blocking(
+    state: &HttpState,
+    session: &Arc<Session>,
+    messages: Vec<Value>,
+) -> Vec<Value> {
+    let ctx = Arc::clone(&state.ctx);
+    let session = Arc::clone(session);
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || {
+        let _span = span.entered();
+        messages
+            .iter()
+            .filter_map(|m| dispatch(&ctx, &session.resources, m))
+            .collect()
+    })
+    .await
+    .unwrap_or_else(|e| {
+        warn!("MCP dispatch failed: {}", e);
+        Vec::new()
+    })
+}
+
+/// Resolve the session named by the `Mcp-Session-Id` header.
+///
+/// A missing header is a 400; an unknown or expired session is a 404, which
+/// tells the client to initialize again.
+fn session_from_headers(
+    state: &HttpState,
+    headers: &HeaderMap,
+) -> Result<(String, Arc<Session>), (StatusCode, &'static str)> {
+    let Some(id) = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok()) else {
+        return Err((StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header"));
+    };
+    match state.sessions.get(id) {
+        Some(session) => Ok((id.to_string(), session)),
+        None => Err((StatusCode::NOT_FOUND, "Unknown session")),
+    }
+}
+
+/// Open a stream for a session, replacing any previous one, and forward
+/// resource change notifications to it until the client disconnects.
+fn attach_stream(state: &HttpState, session: Arc<Session>) -> ReceiverStream<Value> {
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+    *session.stream.lock() = Some(tx.clone());
+
+    let ctx = Arc::clone(&state.ctx);
+    let mut events = ctx.indexer.events().subscribe();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = events.recv() => {}
+                _ = tx.closed() => break,
+            }
+            for notification in session.resources.poll(&ctx) {
+                if tx.send(notification.to_message()).await.is_err() {
+                    return;
+                }
+            }
+            *session.last_seen.lock() = Instant::now();
+        }
+    });
+
+    ReceiverStream::new(rx)
+}
+
+fn message_event(message: &Value) -> Event {
+    Event::default().event("message").data(message.to_string())
+}
+
+fn sse<S>(events: S) -> Response
+where
+    S: Stream<Item = Result<Event, Infallible>> + Send + 'static,
+{
+    Sse::new(events)
+        .keep_alive(KeepAlive::new().interval(KEEP_ALIVE_INTERVAL))
+        .into_response()
+}
+
+fn jsonrpc_error(status: StatusCode, code: i64, message: &str) -> Response {
+    (status, Json(error_response(Value::Null, code, message))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_ids_are_unique() {
+        let sessions = McpSessions::default();
+        let a = sessions.next_id();
+        let b = sessions.next_id();
+        assert_eq!(a.len(), 32);
+        assert_ne!(a, b);
+    }
+}
@@ -1,21 +1,60 @@
 //! MCP Server implementation.
 //!
-//! This module will contain the MCP protocol handlers and tool registration
-//! once the Rust MCP SDK is integrated.
-//!
-//! Tools to implement:
+//! Tools are listed with their input schemas by `tools/list` and called
+//! with `tools/call`; see [`registry`]. Tools:
 //! - list_skills: Enumerate available skill domains
 //! - get_skill: Load main SKILL.md content
+//! - get_skill_arguments: JSON Schema of get_skill arguments, including
+//!   the skill's declared template variables
 //! - get_sub_skill: Retrieve specific sub-skill content
+//! - get_quick_answer: Return a skill's short answer without full content
+//! - get_skill_tables: Markdown tables of a skill as structured data
+//! - get_skill_outline: Heading hierarchy with token counts per section
+//! - find_examples: Search only within skills' fenced code blocks
 //! - get_skills_batch: Fetch multiple skills in one call
 //! - search_skills: Query by metadata (names, tags, triggers)
 //! - search_content: Full-text markdown search with snippets
+//! - search_for_task: Multi-query fusion search for task descriptions
+//! - get_bundle: Load a named bundle of skills as one document
+//! - build_context: Assemble the skills relevant to a task within a token
+//!   budget, reporting what was left out
+//! - rename_skill: Rename a skill and update references to it
+//! - archive_skill / unarchive_skill: Hide a skill from listings and
+//!   search without deleting it, or show it again
+//! - run_skill_script: Run an allow-listed script from a skill's
+//!   `scripts/` directory with a timeout, when enabled
 //! - reload_index: Refresh skill index from disk
 //! - get_stats: Return usage statistics
 //! - validate_skills: Check skill structure and metadata
+//!
+//! Skills that declare template variables are also MCP prompts whose
+//! arguments are the variables; see [`prompts`].
+//!
+//! Skills and sub-skills are also exposed as MCP resources
+//! (`skill://forms`, `skill://forms/validation`); see [`resources`].
+//! Clients can autocomplete skill names, sub-skill names, and tags with
+//! `completion/complete`; see [`completion`].
+//!
+//! Besides stdio, the API server speaks MCP over Streamable HTTP (`/mcp`)
+//! and the older HTTP+SSE transport (`/sse`); see [`http`].
+//!
+//! Listing tools page their results with a shared `cursor`/`page_size`
+//! contract and every result is kept under the configured
+//! `max_response_bytes`; see [`paging`].
+//!
+//! Tool results are serialized with `ServiceContext::tool_result`, which
+//! adds a `maintenance` banner while maintenance mode is enabled; write
+//! tools are rejected during maintenance.
 
+pub mod completion;
+#[cfg(feature = "api")]
+pub mod http;
+pub mod paging;
+pub mod prompts;
+pub mod registry;
+pub mod resources;
 pub mod tools;
 mod server;
 
-pub use server::McpServer;
+pub use server::{McpServer, PROTOCOL_VERSION};
 pub use tools::*;
@@ -0,0 +1,192 @@
+//! Pagination and size limits for MCP tool results.
+//!
+//! Listing tools (`list_skills`, `search_skills`, `search_content`,
+//! `search_for_task`, `get_skills_batch`) take the same `cursor` and
+//! `page_size` arguments and return `next_cursor` while items remain;
+//! content tools (`get_skill`, `get_sub_skill`) take a `cursor` and split
+//! long content the same way. `resources/list` follows the protocol's own
+//! `cursor`/`nextCursor` contract.
+//!
+//! Whatever the page size, a page stops growing before its items exceed
+//! the context's `max_response_bytes`, so a client gets a continuation
+//! instead of megabytes it can't pass on to the model. Cursors are opaque
+//! to clients.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ErrorCode, ErrorResponse};
+
+/// Prefix that marks a string as one of our cursors.
+const CURSOR_PREFIX: &str = "c";
+
+/// Pagination arguments shared by listing tools.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PageRequest {
+    /// `next_cursor` of the previous page; omit for the first page.
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Maximum items in the page; all remaining items when omitted.
+    #[serde(default)]
+    pub page_size: Option<usize>,
+}
+
+impl PageRequest {
+    /// The page starting at `cursor`.
+    pub fn at(cursor: impl Into<String>) -> Self {
+        Self {
+            cursor: Some(cursor.into()),
+            page_size: None,
+        }
+    }
+
+    /// Limit the page to `size` items.
+    pub fn page_size(mut self, size: usize) -> Self {
+        self.page_size = Some(size);
+        self
+    }
+}
+
+/// One page of items.
+#[derive(Debug)]
+pub struct Page<T> {
+    /// Items in the page.
+    pub items: Vec<T>,
+    /// Cursor of the next page, if items remain.
+    pub next_cursor: Option<String>,
+}
+
+/// Encode an offset as a cursor.
+pub fn encode_cursor(offset: usize) -> String {
+    format!("{}{:x}", CURSOR_PREFIX, offset)
+}
+
+/// Decode a cursor to the offset it continues from; no cursor is the start.
+pub fn decode_cursor(cursor: Option<&str>) -> Result<usize, ErrorResponse> {
+    let Some(cursor) = cursor else {
+        return Ok(0);
+    };
+    cursor
+        .strip_prefix(CURSOR_PREFIX)
+        .and_then(|hex| usize::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| ErrorResponse::new(ErrorCode::InvalidRequest, format!("Invalid cursor '{}'", cursor)))
+}
+
+/// Take the requested page of `items`, lazily so items past the page are
+/// never produced.
+///
+/// With `max_bytes`, the page ends before the serialized items would
+/// exceed it, though it always holds at least one item.
+pub fn paginate<T: Serialize>(
+    items: impl IntoIterator<Item = T>,
+    page: &PageRequest,
+    max_bytes: Option<usize>,
+) -> Result<Page<T>, ErrorResponse> {
+    let offset = decode_cursor(page.cursor.as_deref())?;
+    if page.page_size == Some(0) {
+        return Err(ErrorResponse::new(ErrorCode::InvalidRequest, "page_size must be at least 1"));
+    }
+    let page_size = page.page_size.unwrap_or(usize::MAX);
+
+    let mut items = items.into_iter().skip(offset).peekable();
+    let mut page_items = Vec::new();
+    let mut bytes = 0;
+    while page_items.len() < page_size {
+        let Some(item) = items.peek() else {
+            break;
+        };
+        if let Some(max) = max_bytes {
+            let size = serde_json::to_vec(item).map_or(0, |v| v.len());
+            if !page_items.is_empty() && bytes + size > max {
+                break;
+            }
+            bytes += size;
+        }
+        page_items.extend(items.next());
+    }
+
+    let next_cursor = items
+        .peek()
+        .is_some()
+        .then(|| encode_cursor(offset + page_items.len()));
+    Ok(Page {
+        items: page_items,
+        next_cursor,
+    })
+}
+
+/// Split `content` into the part starting at `cursor` that fits in
+/// `max_bytes`, and the cursor of the rest.
+///
+/// Parts end after a line break where one fits, otherwise at a character
+/// boundary.
+pub fn split_content(
+    content: &str,
+    cursor: Option<&str>,
+    max_bytes: Option<usize>,
+) -> Result<(String, Option<String>), ErrorResponse> {
+    let offset = decode_cursor(cursor)?;
+    let rest = content
+        .get(offset..)
+        .ok_or_else(|| ErrorResponse::new(ErrorCode::InvalidRequest, format!("Invalid cursor '{}'", cursor.unwrap_or_default())))?;
+
+    let Some(max) = max_bytes.filter(|max| rest.len() > *max) else {
+        return Ok((rest.to_string(), None));
+    };
+    let mut end = max;
+    while !rest.is_char_boundary(end) {
+        end -= 1;
+    }
+    if let Some(newline) = rest[..end].rfind('\n') {
+        end = newline + 1;
+    }
+    if end == 0 {
+        // A single character wider than the budget still makes progress
+        end = rest.chars().next().map_or(0, char::len_utf8);
+    }
+    Ok((rest[..end].to_string(), Some(encode_cursor(offset + end))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate() {
+        let items: Vec<u32> = (0..5).collect();
+
+        let first = paginate(items.clone(), &PageRequest::default().page_size(2), None).unwrap();
+        assert_eq!(first.items, vec![0, 1]);
+        let cursor = first.next_cursor.unwrap();
+
+        let second = paginate(items.clone(), &PageRequest::at(cursor), None).unwrap();
+        assert_eq!(second.items, vec![2, 3, 4]);
+        assert!(second.next_cursor.is_none());
+
+        // Each item serializes to two bytes, so the budget fits two
+        let items: Vec<u32> = (10..15).collect();
+        let page = paginate(items, &PageRequest::default(), Some(5)).unwrap();
+        assert_eq!(page.items, vec![10, 11]);
+        assert_eq!(page.next_cursor, Some(encode_cursor(2)));
+
+        assert!(paginate(vec![1], &PageRequest::at("nope"), None).is_err());
+        assert!(paginate(vec![1], &PageRequest::default().page_size(0), None).is_err());
+    }
+
+    #[test]
+    fn test_split_content() {
+        let content = "# Forms\n\nValidate — always.";
+
+        assert_eq!(split_content(content, None, None).unwrap(), (content.to_string(), None));
+
+        let (part, cursor) = split_content(content, None, Some(11)).unwrap();
+        assert_eq!(part, "# Forms\n\n");
+        let (part, cursor) = split_content(content, cursor.as_deref(), Some(11)).unwrap();
+        // Cut inside the dash, so the part ends at the last full character
+        assert_eq!(part, "Validate ");
+        let (part, cursor) = split_content(content, cursor.as_deref(), Some(11)).unwrap();
+        assert_eq!(part, "— always.");
+        assert!(cursor.is_none());
+
+        assert!(split_content(content, Some(&encode_cursor(100)), None).is_err());
+    }
+}
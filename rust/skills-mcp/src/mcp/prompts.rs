@@ -0,0 +1,155 @@
+//! Skills with template variables as MCP prompts (`prompts/list`,
+//! `prompts/get`).
+//!
+//! A skill that declares variables is listed as a prompt of the same name
+//! whose arguments are its variables, so clients can ask the user for
+//! required values before loading it; the same arguments are described as
+//! JSON Schema by the `get_skill_arguments` tool. Getting the prompt loads
+//! the skill with `get_skill` and returns its rendered content as one user
+//! message. Archived skills and skills hidden from the caller aren't
+//! listed.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::tools::{self, GetSkillRequest, ServiceContext};
+use crate::models::{ErrorCode, ErrorResponse, SkillMeta};
+
+/// A prompt as listed by `prompts/list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Prompt {
+    /// Skill name.
+    pub name: String,
+    /// Skill description.
+    pub description: String,
+    /// The skill's template variables.
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// One argument of a prompt.
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptArgument {
+    /// Variable name.
+    pub name: String,
+    /// What the value is for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the skill can't be loaded without a value.
+    pub required: bool,
+}
+
+/// Result of `prompts/list`.
+#[derive(Debug, Serialize)]
+pub struct ListPromptsResult {
+    /// One prompt per skill with variables, sorted by name.
+    pub prompts: Vec<Prompt>,
+}
+
+/// Parameters of `prompts/get`.
+#[derive(Debug, Deserialize)]
+pub struct GetPromptRequest {
+    /// Skill name or alias.
+    pub name: String,
+    /// Variable values, by name.
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Result of `prompts/get`.
+#[derive(Debug, Serialize)]
+pub struct GetPromptResult {
+    /// Skill description.
+    pub description: String,
+    /// The rendered skill content as one user message.
+    pub messages: Vec<PromptMessage>,
+}
+
+/// A message of a prompt.
+#[derive(Debug, Serialize)]
+pub struct PromptMessage {
+    /// Always `user`.
+    pub role: &'static str,
+    /// Message content.
+    pub content: TextContent,
+}
+
+/// Text content of a prompt message.
+#[derive(Debug, Serialize)]
+pub struct TextContent {
+    /// Always `text`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// The text.
+    pub text: String,
+}
+
+/// List the skills that declare template variables as prompts.
+pub fn list_prompts(ctx: &ServiceContext) -> ListPromptsResult {
+    let index = ctx.indexer.get_skill_index();
+    let mut prompts: Vec<Prompt> = index
+        .skills
+        .iter()
+        .filter(|s| !s.archived && !s.variables.is_empty())
+        .filter(|s| s.is_visible_to(ctx.mcp_key.read().as_deref()))
+        .map(prompt)
+        .collect();
+    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+    ListPromptsResult { prompts }
+}
+
+/// Load a skill with the given variable values.
+pub fn get_prompt(ctx: &ServiceContext, req: GetPromptRequest) -> Result<GetPromptResult, ErrorResponse> {
+    let meta = ctx
+        .indexer
+        .get_skill_meta(&req.name)
+        .filter(|_| ctx.is_visible(&req.name))
+        .ok_or_else(|| ctx.skill_not_found(&req.name))?;
+    if meta.variables.is_empty() {
+        return Err(ErrorResponse::new(
+            ErrorCode::InvalidRequest,
+            format!("Skill '{}' declares no variables and is not a prompt", meta.name),
+        ));
+    }
+
+    let content = tools::get_skill(
+        ctx,
+        GetSkillRequest {
+            name: meta.name,
+            minify: false,
+            cursor: None,
+            lang: None,
+            variables: req
+                .arguments
+                .into_iter()
+                .map(|(name, value)| (name, value.into()))
+                .collect(),
+        },
+    )?;
+    Ok(GetPromptResult {
+        description: meta.description,
+        messages: vec![PromptMessage {
+            role: "user",
+            content: TextContent {
+                kind: "text",
+                text: content.content,
+            },
+        }],
+    })
+}
+
+fn prompt(meta: &SkillMeta) -> Prompt {
+    Prompt {
+        name: meta.name.clone(),
+        description: meta.description.clone(),
+        arguments: meta
+            .variables
+            .iter()
+            .map(|var| PromptArgument {
+                name: var.name.clone(),
+                description: var.description.clone(),
+                required: var.required && var.default.is_none(),
+            })
+            .collect(),
+    }
+}
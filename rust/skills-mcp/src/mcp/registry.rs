@@ -0,0 +1,373 @@
+//! Tool registry: the tools advertised by `tools/list`, with their input
+//! schemas, and the routing of `tools/call` to the handlers in [`tools`].
+//!
+//! A call's result is the handler's response serialized with
+//! `ServiceContext::tool_result` as one JSON text content item. Errors a
+//! handler reports come back as a result with `isError` set and the
+//! error's JSON as text, so the model sees why the call failed; unknown
+//! tools and arguments that don't match the schema are protocol errors.
+//!
+//! Listing and content tools page their own results (see [`super::paging`]),
+//! so `cursor`, `page_size`, and the `max_response_bytes` limit apply to
+//! calls made here exactly as to direct calls.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::models::{ErrorCode, ErrorResponse};
+
+use super::tools::{self, ServiceContext};
+
+/// A tool as listed by `tools/list`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolDefinition {
+    /// Tool name, as passed to `tools/call`.
+    pub name: &'static str,
+    /// What the tool does, for the model.
+    pub description: &'static str,
+    /// JSON Schema of the tool's arguments.
+    pub input_schema: Value,
+}
+
+/// Parameters of `tools/call`.
+#[derive(Debug, Deserialize)]
+pub struct CallToolRequest {
+    /// Name of the tool to call.
+    pub name: String,
+    /// Tool arguments; an empty object when omitted.
+    #[serde(default)]
+    pub arguments: Option<Value>,
+}
+
+/// Result of `tools/call`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallToolResult {
+    /// The tool's response, as one text item holding JSON.
+    pub content: Vec<ToolContent>,
+    /// Whether the tool reported an error.
+    pub is_error: bool,
+}
+
+/// A content item of a tool result.
+#[derive(Debug, Serialize)]
+pub struct ToolContent {
+    /// Always `text`.
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// JSON of the tool's response or error.
+    pub text: String,
+}
+
+impl CallToolResult {
+    fn text(value: &Value, is_error: bool) -> Self {
+        Self {
+            content: vec![ToolContent {
+                kind: "text",
+                text: value.to_string(),
+            }],
+            is_error,
+        }
+    }
+}
+
+/// Definitions of every tool, in the order `tools/list` returns them.
+pub fn list_tools() -> Vec<ToolDefinition> {
+    let name = json!({ "type": "string", "description": "Skill name or alias" });
+    let sub_skill = json!({ "type": "string", "description": "Sub-skill name" });
+    let minify = json!({ "type": "boolean", "default": false, "description": "Strip decorations that don't matter to the model" });
+    let cursor = json!({ "type": "string", "description": "next_cursor of a previous response, to continue" });
+    let limit = json!({ "type": "integer", "minimum": 1, "description": "Maximum number of results" });
+    let include_archived = json!({ "type": "boolean", "default": false, "description": "Include archived skills" });
+    let collection = json!({ "type": "string", "description": "Only return skills in this collection" });
+    let lang = json!({ "type": "string", "description": "Natural language, e.g. en" });
+    let query = json!({ "type": "string", "description": "Search query; supports phrases, tag:/domain: filters, -excluded terms, and AND/OR grouping" });
+
+    vec![
+        ToolDefinition {
+            name: "list_skills",
+            description: "List available skill domains; archived skills only on request.",
+            input_schema: paged(object(&[("include_archived", &include_archived)], &[])),
+        },
+        ToolDefinition {
+            name: "get_skill",
+            description: "Get the main SKILL.md content of a skill. Skills that declare template \
+                          variables take their values as extra arguments; get_skill_arguments \
+                          returns a skill's full argument schema.",
+            input_schema: {
+                let mut schema = object(
+                    &[("name", &name), ("minify", &minify), ("cursor", &cursor), ("lang", &lang)],
+                    &["name"],
+                );
+                schema["additionalProperties"] = json!({ "type": "string" });
+                schema
+            },
+        },
+        ToolDefinition {
+            name: "get_skill_arguments",
+            description: "Get the JSON Schema of get_skill arguments for a skill, including its \
+                          declared template variables.",
+            input_schema: object(&[("name", &name)], &["name"]),
+        },
+        ToolDefinition {
+            name: "get_sub_skill",
+            description: "Get the content of a sub-skill.",
+            input_schema: object(
+                &[
+                    ("domain", &name),
+                    ("sub_skill", &sub_skill),
+                    ("minify", &minify),
+                    ("cursor", &cursor),
+                ],
+                &["domain", "sub_skill"],
+            ),
+        },
+        ToolDefinition {
+            name: "get_quick_answer",
+            description: "Get a skill's quick answer without loading its full content.",
+            input_schema: object(&[("name", &name)], &["name"]),
+        },
+        ToolDefinition {
+            name: "get_skill_tables",
+            description: "Get the markdown tables of a skill as structured data.",
+            input_schema: object(&[("name", &name), ("sub_skill", &sub_skill)], &["name"]),
+        },
+        ToolDefinition {
+            name: "get_skill_outline",
+            description: "Get the heading outline of a skill file with token counts per section.",
+            input_schema: object(&[("name", &name), ("sub_skill", &sub_skill)], &["name"]),
+        },
+        ToolDefinition {
+            name: "find_examples",
+            description: "Search only within skills' fenced code blocks.",
+            input_schema: paged(object(
+                &[
+                    ("query", &json!({ "type": "string", "description": "Query matched against code; empty lists every block" })),
+                    ("lang", &json!({ "type": "string", "description": "Code language, e.g. python" })),
+                    ("skill", &name),
+                    ("limit", &limit),
+                    ("include_archived", &include_archived),
+                ],
+                &[],
+            )),
+        },
+        ToolDefinition {
+            name: "get_skills_batch",
+            description: "Load several skills and sub-skills in one call.",
+            input_schema: paged(object(
+                &[
+                    (
+                        "requests",
+                        &json!({
+                            "type": "array",
+                            "items": object(&[("domain", &name), ("sub_skill", &sub_skill)], &["domain"]),
+                        }),
+                    ),
+                    ("lang", &lang),
+                ],
+                &["requests"],
+            )),
+        },
+        ToolDefinition {
+            name: "search_skills",
+            description: "Search skills by metadata: names, descriptions, tags, and triggers.",
+            input_schema: paged(object(
+                &[
+                    ("query", &query),
+                    ("limit", &limit),
+                    ("collection", &collection),
+                    ("lang", &lang),
+                    ("include_archived", &include_archived),
+                ],
+                &["query"],
+            )),
+        },
+        ToolDefinition {
+            name: "search_content",
+            description: "Full-text search of skill content, with snippets.",
+            input_schema: paged(object(
+                &[
+                    ("query", &query),
+                    ("limit", &limit),
+                    ("collection", &collection),
+                    ("lang", &lang),
+                    (
+                        "file_types",
+                        &json!({ "type": "array", "items": { "type": "string" }, "description": "File types, e.g. markdown, py" }),
+                    ),
+                    ("include_archived", &include_archived),
+                ],
+                &["query"],
+            )),
+        },
+        ToolDefinition {
+            name: "search_for_task",
+            description: "Find skills for a long or vague task description.",
+            input_schema: paged(object(
+                &[
+                    ("task", &json!({ "type": "string", "description": "Task description" })),
+                    ("limit", &limit),
+                ],
+                &["task"],
+            )),
+        },
+        ToolDefinition {
+            name: "get_bundle",
+            description: "Load a named bundle of skills as one document within its token budget.",
+            input_schema: object(
+                &[
+                    ("name", &json!({ "type": "string", "description": "Bundle name" })),
+                    ("max_tokens", &json!({ "type": "integer", "minimum": 1 })),
+                ],
+                &["name"],
+            ),
+        },
+        ToolDefinition {
+            name: "build_context",
+            description: "Assemble the skills and sub-skills most relevant to a task into one \
+                          document within a token budget, reporting what was left out and why.",
+            input_schema: object(
+                &[
+                    ("task", &json!({ "type": "string", "description": "Task description" })),
+                    ("max_tokens", &json!({ "type": "integer", "minimum": 1, "description": "Token budget" })),
+                    ("max_skills", &json!({ "type": "integer", "minimum": 1 })),
+                    ("include_deprecated", &json!({ "type": "boolean", "default": false })),
+                ],
+                &["task", "max_tokens"],
+            ),
+        },
+        ToolDefinition {
+            name: "rename_skill",
+            description: "Rename a skill, updating dependents and links to it.",
+            input_schema: object(
+                &[
+                    ("name", &name),
+                    ("new_name", &json!({ "type": "string" })),
+                    ("rewrite_links", &json!({ "type": "boolean", "default": true })),
+                ],
+                &["name", "new_name"],
+            ),
+        },
+        ToolDefinition {
+            name: "archive_skill",
+            description: "Hide a skill from listings and search without deleting it.",
+            input_schema: object(&[("name", &name)], &["name"]),
+        },
+        ToolDefinition {
+            name: "unarchive_skill",
+            description: "Make an archived skill visible again.",
+            input_schema: object(&[("name", &name)], &["name"]),
+        },
+        ToolDefinition {
+            name: "run_skill_script",
+            description: "Run an allow-listed script from a skill's scripts/ directory and \
+                          capture its output. Disabled unless enabled in the server's settings.",
+            input_schema: object(
+                &[
+                    ("name", &name),
+                    ("script", &json!({ "type": "string", "description": "Script path relative to scripts/" })),
+                    ("args", &json!({ "type": "array", "items": { "type": "string" } })),
+                ],
+                &["name", "script"],
+            ),
+        },
+        ToolDefinition {
+            name: "reload_index",
+            description: "Reload the skill index from disk.",
+            input_schema: object(&[], &[]),
+        },
+        ToolDefinition {
+            name: "get_stats",
+            description: "Get usage statistics.",
+            input_schema: object(&[], &[]),
+        },
+        ToolDefinition {
+            name: "validate_skills",
+            description: "Check skill structure and metadata.",
+            input_schema: object(&[], &[]),
+        },
+    ]
+}
+
+/// Call a tool by name.
+///
+/// Errors are protocol errors: an unknown tool or arguments the tool
+/// can't take. Errors the tool itself reports are part of the result.
+pub fn call_tool(ctx: &ServiceContext, req: CallToolRequest) -> Result<CallToolResult, ErrorResponse> {
+    let args = req.arguments.unwrap_or_else(|| json!({}));
+    let response = match req.name.as_str() {
+        "list_skills" => call(args, |r| tools::list_skills(ctx, r)),
+        "get_skill" => call(args, |r| tools::get_skill(ctx, r)),
+        "get_skill_arguments" => call(args, |r| tools::get_skill_arguments(ctx, r)),
+        "get_sub_skill" => call(args, |r| tools::get_sub_skill(ctx, r)),
+        "get_quick_answer" => call(args, |r| tools::get_quick_answer(ctx, r)),
+        "get_skill_tables" => call(args, |r| tools::get_skill_tables(ctx, r)),
+        "get_skill_outline" => call(args, |r| tools::get_skill_outline(ctx, r)),
+        "find_examples" => call(args, |r| tools::find_examples(ctx, r)),
+        "get_skills_batch" => call(args, |r| tools::get_skills_batch(ctx, r)),
+        "search_skills" => call(args, |r| tools::search_skills(ctx, r)),
+        "search_content" => call(args, |r| tools::search_content(ctx, r)),
+        "search_for_task" => call(args, |r| tools::search_for_task(ctx, r)),
+        "get_bundle" => call(args, |r| tools::get_bundle(ctx, r)),
+        "build_context" => call(args, |r| tools::build_context(ctx, r)),
+        "rename_skill" => call(args, |r| tools::rename_skill(ctx, r)),
+        "archive_skill" => call(args, |r| tools::archive_skill(ctx, r)),
+        "unarchive_skill" => call(args, |r| tools::unarchive_skill(ctx, r)),
+        "run_skill_script" => call(args, |r| tools::run_skill_script(ctx, r)),
+        "reload_index" => call(args, |NoArguments {}| Ok(tools::reload_index(ctx))),
+        "get_stats" => call(args, |NoArguments {}| Ok(tools::get_stats(ctx))),
+        "validate_skills" => call(args, |NoArguments {}| Ok(tools::validate_skills_tool(ctx))),
+        name => {
+            return Err(ErrorResponse::new(
+                ErrorCode::InvalidRequest,
+                format!("Unknown tool: {}", name),
+            ));
+        }
+    }?;
+
+    Ok(match response {
+        Ok(value) => CallToolResult::text(&ctx.tool_result(&value), false),
+        Err(e) => CallToolResult::text(&json!(e), true),
+    })
+}
+
+/// Arguments of tools that take none; unknown arguments are ignored.
+#[derive(Debug, Deserialize)]
+struct NoArguments {}
+
+/// Deserialize a tool's arguments and run it.
+///
+/// The outer error is for arguments that don't fit the tool's request;
+/// the inner result is the tool's own.
+fn call<R, T>(
+    args: Value,
+    tool: impl FnOnce(R) -> Result<T, ErrorResponse>,
+) -> Result<Result<Value, ErrorResponse>, ErrorResponse>
+where
+    R: DeserializeOwned,
+    T: Serialize,
+{
+    let req = serde_json::from_value(args)
+        .map_err(|e| ErrorResponse::new(ErrorCode::InvalidRequest, format!("Invalid arguments: {}", e)))?;
+    Ok(tool(req).map(|response| serde_json::to_value(response).unwrap_or(Value::Null)))
+}
+
+/// Schema of an object with the given properties.
+fn object(properties: &[(&str, &Value)], required: &[&str]) -> Value {
+    let properties: Map<String, Value> = properties
+        .iter()
+        .map(|(name, schema)| (name.to_string(), (*schema).clone()))
+        .collect();
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// Add the `cursor` and `page_size` arguments listing tools share.
+fn paged(mut schema: Value) -> Value {
+    schema["properties"]["cursor"] =
+        json!({ "type": "string", "description": "next_cursor of the previous page" });
+    schema["properties"]["page_size"] =
+        json!({ "type": "integer", "minimum": 1, "description": "Maximum items in the page" });
+    schema
+}
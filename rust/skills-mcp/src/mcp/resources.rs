@@ -0,0 +1,350 @@
+//! MCP resources: skills and sub-skills as browsable documents.
+//!
+//! Each skill is exposed as `skill://<name>` and each sub-skill as
+//! `skill://<name>/<sub-skill>`. Skills nested in a collection keep their
+//! qualified name (`skill://frontend/forms`).
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::paging::{paginate, PageRequest};
+use super::tools::{ServiceContext, MCP_READER};
+use crate::models::{ErrorCode, ErrorResponse};
+
+/// URI scheme for skill resources.
+pub const SCHEME: &str = "skill://";
+
+/// MIME type of skill resources.
+pub const MIME_TYPE: &str = "text/markdown";
+
+/// A resource entry in `resources/list`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    /// Resource URI.
+    pub uri: String,
+    /// Display name.
+    pub name: String,
+    /// What the resource covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Content type.
+    pub mime_type: String,
+}
+
+/// Result of `resources/list`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResourcesResult {
+    /// Skill and sub-skill resources in this page.
+    pub resources: Vec<Resource>,
+    /// Cursor of the next page, if resources remain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Text contents of a resource.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    /// Resource URI.
+    pub uri: String,
+    /// Content type.
+    pub mime_type: String,
+    /// Markdown content.
+    pub text: String,
+}
+
+/// Result of `resources/read`.
+#[derive(Debug, Serialize)]
+pub struct ReadResourceResult {
+    /// Contents of the requested resource.
+    pub contents: Vec<ResourceContents>,
+}
+
+/// URI of a skill, or of one of its sub-skills.
+pub fn resource_uri(skill: &str, sub_skill: Option<&str>) -> String {
+    match sub_skill {
+        Some(sub) => format!("{}{}/{}", SCHEME, skill, sub),
+        None => format!("{}{}", SCHEME, skill),
+    }
+}
+
+/// List every skill and sub-skill as a resource.
+///
+/// Archived skills are left out but can still be read by URI; private
+/// skills are left out unless the context's key is allowed.
+pub fn list_resources(ctx: &ServiceContext, cursor: Option<&str>) -> Result<ListResourcesResult, ErrorResponse> {
+    let index = ctx.indexer.get_skill_index();
+    let key = ctx.mcp_key.read().clone();
+
+    let mut resources = Vec::new();
+    for skill in index
+        .skills
+        .iter()
+        .filter(|s| !s.archived && s.is_visible_to(key.as_deref()))
+    {
+        resources.push(Resource {
+            uri: resource_uri(&skill.name, None),
+            name: skill.name.clone(),
+            description: Some(skill.description.clone()),
+            mime_type: MIME_TYPE.to_string(),
+        });
+        for sub in skill.sub_skills.iter().flatten() {
+            resources.push(Resource {
+                uri: resource_uri(&skill.name, Some(&sub.name)),
+                name: format!("{}/{}", skill.name, sub.name),
+                description: Some(format!("Sub-skill of {}", skill.name)),
+                mime_type: MIME_TYPE.to_string(),
+            });
+        }
+    }
+
+    let page = PageRequest {
+        cursor: cursor.map(str::to_string),
+        page_size: None,
+    };
+    let page = paginate(resources, &page, *ctx.max_response_bytes.read())?;
+    Ok(ListResourcesResult {
+        resources: page.items,
+        next_cursor: page.next_cursor,
+    })
+}
+
+/// Resolve a resource URI to a skill and optional sub-skill.
+///
+/// A full path naming a skill wins over a skill/sub-skill split, so
+/// `skill://frontend/forms` resolves to the collection skill.
+pub fn parse_resource_uri(ctx: &ServiceContext, uri: &str) -> Option<(String, Option<String>)> {
+    let path = uri.strip_prefix(SCHEME)?.trim_end_matches('/');
+    if path.is_empty() {
+        return None;
+    }
+
+    if ctx.indexer.skill_exists(path) {
+        return Some((ctx.canonical_name(path), None));
+    }
+    let (skill, sub) = path.rsplit_once('/')?;
+    let meta = ctx.indexer.get_skill_meta(skill)?;
+    meta.find_sub_skill(sub)?;
+    Some((meta.name, Some(sub.to_string())))
+}
+
+/// Read a skill or sub-skill resource.
+pub fn read_resource(ctx: &ServiceContext, uri: &str) -> Result<ReadResourceResult, ErrorResponse> {
+    let (skill, sub_skill) = parse_resource_uri(ctx, uri)
+        .filter(|(skill, _)| ctx.is_visible(skill))
+        .ok_or_else(|| ErrorResponse::new(ErrorCode::NotFound, format!("Resource not found: {}", uri)))?;
+
+    ctx.track_skill_load(&skill);
+    ctx.track_skill_read(&skill, MCP_READER);
+
+    let mut text = match &sub_skill {
+        Some(sub) => ctx.indexer.read_sub_skill_content(&skill, sub).map(|c| c.content),
+        None => ctx.indexer.read_skill_content(&skill).map(|c| c.content),
+    }
+    .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))?;
+    ctx.append_deprecation_notice(&skill, &mut text);
+
+    let tracked = match &sub_skill {
+        Some(sub) => format!("{}:{}", skill, sub),
+        None => skill.clone(),
+    };
+    ctx.track_tokens_served(&tracked, MCP_READER, &text);
+
+    Ok(ReadResourceResult {
+        contents: vec![ResourceContents {
+            uri: uri.to_string(),
+            mime_type: MIME_TYPE.to_string(),
+            text,
+        }],
+    })
+}
+
+/// A resource change to announce to the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceNotification {
+    /// Resources were added or removed.
+    ListChanged,
+    /// A subscribed resource changed.
+    Updated(String),
+}
+
+impl ResourceNotification {
+    /// The JSON-RPC notification message.
+    pub fn to_message(&self) -> Value {
+        match self {
+            Self::ListChanged => json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/resources/list_changed",
+            }),
+            Self::Updated(uri) => json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/resources/updated",
+                "params": { "uri": uri },
+            }),
+        }
+    }
+}
+
+/// Tracks resource subscriptions and detects changes between index snapshots.
+#[derive(Debug, Default)]
+pub struct ResourceTracker {
+    fingerprints: Mutex<HashMap<String, u64>>,
+    subscriptions: Mutex<HashSet<String>>,
+}
+
+impl ResourceTracker {
+    /// Create a tracker using the current index as its baseline.
+    pub fn new(ctx: &ServiceContext) -> Self {
+        Self {
+            fingerprints: Mutex::new(fingerprints(ctx)),
+            subscriptions: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Subscribe to updates for a resource.
+    pub fn subscribe(&self, uri: &str) {
+        self.subscriptions.lock().insert(uri.to_string());
+    }
+
+    /// Stop receiving updates for a resource.
+    pub fn unsubscribe(&self, uri: &str) {
+        self.subscriptions.lock().remove(uri);
+    }
+
+    /// Compare the index against the last snapshot and return the
+    /// notifications to send.
+    pub fn poll(&self, ctx: &ServiceContext) -> Vec<ResourceNotification> {
+        let current = fingerprints(ctx);
+        let previous = std::mem::replace(&mut *self.fingerprints.lock(), current.clone());
+
+        let mut notifications = Vec::new();
+        let same_uris =
+            previous.len() == current.len() && previous.keys().all(|k| current.contains_key(k));
+        if !same_uris {
+            notifications.push(ResourceNotification::ListChanged);
+        }
+
+        let mut updated: Vec<String> = self
+            .subscriptions
+            .lock()
+            .iter()
+            .filter(|uri| previous.get(*uri) != current.get(*uri))
+            .cloned()
+            .collect();
+        updated.sort();
+        notifications.extend(updated.into_iter().map(ResourceNotification::Updated));
+        notifications
+    }
+}
+
+/// Hash of each resource's metadata and indexed content, keyed by URI.
+fn fingerprints(ctx: &ServiceContext) -> HashMap<String, u64> {
+    let index = ctx.indexer.get_skill_index();
+    let content = ctx.indexer.get_content_index();
+
+    let mut hashes: HashMap<String, u64> = HashMap::new();
+    for skill in &index.skills {
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(skill).unwrap_or_default().hash(&mut hasher);
+        hashes.insert(resource_uri(&skill.name, None), hasher.finish());
+        for sub in skill.sub_skills.iter().flatten() {
+            hashes.insert(resource_uri(&skill.name, Some(&sub.name)), 0);
+        }
+    }
+
+    for entry in content.entries.values() {
+        let uri = resource_uri(&entry.domain, entry.sub_skill.as_deref());
+        if entry.sub_skill.is_none() && entry.file != "SKILL.md" {
+            continue;
+        }
+        if let Some(hash) = hashes.get_mut(&uri) {
+            let mut hasher = DefaultHasher::new();
+            hash.hash(&mut hasher);
+            entry.content.hash(&mut hasher);
+            *hash = hasher.finish();
+        }
+    }
+
+    hashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::SkillIndexer;
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn create_context() -> (TempDir, ServiceContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let skill_dir = temp_dir.path().join("forms");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("_meta.json"),
+            r#"{"name": "forms", "description": "Form handling",
+                "sub_skills": [{"name": "validation", "file": "validation.md"}]}"#,
+        )
+        .unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "# Forms").unwrap();
+        fs::write(skill_dir.join("validation.md"), "# Validation").unwrap();
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        (temp_dir, ServiceContext::new(indexer))
+    }
+
+    #[test]
+    fn test_list_and_read_resources() {
+        let (_temp, ctx) = create_context();
+
+        let uris: Vec<String> = list_resources(&ctx, None).unwrap().resources.into_iter().map(|r| r.uri).collect();
+        assert_eq!(uris, vec!["skill://forms", "skill://forms/validation"]);
+
+        // Under a size limit the list continues with a cursor
+        *ctx.max_response_bytes.write() = Some(1);
+        let first = list_resources(&ctx, None).unwrap();
+        assert_eq!(first.resources[0].uri, "skill://forms");
+        let second = list_resources(&ctx, first.next_cursor.as_deref()).unwrap();
+        assert_eq!(second.resources[0].uri, "skill://forms/validation");
+        assert!(second.next_cursor.is_none());
+        *ctx.max_response_bytes.write() = None;
+
+        let result = read_resource(&ctx, "skill://forms/validation").unwrap();
+        assert_eq!(result.contents[0].text, "# Validation");
+        assert!(read_resource(&ctx, "skill://forms/missing").is_err());
+        assert!(read_resource(&ctx, "file:///etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_resource_notifications() {
+        let (temp, ctx) = create_context();
+        let tracker = ResourceTracker::new(&ctx);
+        tracker.subscribe("skill://forms/validation");
+        assert!(tracker.poll(&ctx).is_empty());
+
+        fs::write(temp.path().join("forms/validation.md"), "# Validation v2").unwrap();
+        ctx.indexer.reload().unwrap();
+        assert_eq!(
+            tracker.poll(&ctx),
+            vec![ResourceNotification::Updated("skill://forms/validation".to_string())]
+        );
+
+        let skill_dir = temp.path().join("testing");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(
+            skill_dir.join("_meta.json"),
+            r#"{"name": "testing", "description": "Tests"}"#,
+        )
+        .unwrap();
+        ctx.indexer.reload().unwrap();
+        assert_eq!(tracker.poll(&ctx), vec![ResourceNotification::ListChanged]);
+    }
+}
@@ -1,20 +1,44 @@
 //! MCP Server implementation.
 //!
-//! This is a placeholder that will be fully implemented once the
-//! Rust MCP SDK is available and integrated.
+//! Speaks JSON-RPC over stdio; the same dispatch serves the HTTP transport
+//! in [`super::http`]. Tools (`tools/list`, `tools/call`), prompts
+//! (`prompts/list`, `prompts/get`), resources (`resources/list`,
+//! `resources/read`, subscriptions, and change notifications), and
+//! argument completion (`completion/complete`) are handled here.
 
 use std::sync::Arc;
 
-use tracing::info;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tracing::{debug, info};
 
+use crate::config::Config;
 use crate::index::SkillIndexer;
+use crate::models::{ErrorCode, ErrorResponse};
+use super::completion::{self, CompleteRequest};
+use super::prompts::{self, GetPromptRequest};
+use super::registry::{self, CallToolRequest};
+use super::resources::{self, ResourceTracker};
 use super::tools::ServiceContext;
 
+/// Latest MCP protocol version implemented by the server.
+pub const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// Protocol versions a client may negotiate, newest first.
+const SUPPORTED_VERSIONS: &[&str] = &[PROTOCOL_VERSION, "2024-11-05"];
+
+/// JSON-RPC error codes.
+pub(crate) const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
 /// MCP Server for the Skills service.
 ///
-/// Handles MCP protocol communication and routes tool calls to handlers.
+/// Handles MCP protocol communication and routes requests to handlers.
 pub struct McpServer {
-    ctx: ServiceContext,
+    ctx: Arc<ServiceContext>,
+    resources: Arc<ResourceTracker>,
 }
 
 impl McpServer {
@@ -27,9 +51,21 @@ impl McpServer {
             tracing::error!("Failed to load initial index: {}", e);
         }
 
-        let ctx = ServiceContext::new(indexer);
+        let ctx = Arc::new(ServiceContext::new(indexer));
+        let resources = Arc::new(ResourceTracker::new(&ctx));
 
-        Self { ctx }
+        Self { ctx, resources }
+    }
+
+    /// Apply settings from a `skills-mcp.toml` configuration, reloading the
+    /// index if the indexing settings changed.
+    pub fn with_config(self, config: Config) -> Self {
+        if self.ctx.apply_config(config) {
+            if let Err(e) = self.reload() {
+                tracing::error!("Failed to reload index: {}", e);
+            }
+        }
+        self
     }
 
     /// Get the service context.
@@ -37,37 +73,211 @@ impl McpServer {
         &self.ctx
     }
 
-    /// Start the MCP server.
+    /// Start the MCP server on stdio.
     ///
-    /// This will be implemented to handle stdio transport and MCP protocol
-    /// once the Rust MCP SDK is integrated.
+    /// Reads one JSON-RPC message per line from stdin and writes responses
+    /// to stdout until stdin closes or a shutdown signal arrives. Messages
+    /// are handled on the blocking thread pool, since tools read skill files
+    /// and run scripts synchronously. Resource change notifications are
+    /// sent as the indexer publishes change events.
     pub async fn run(&self) -> Result<(), McpError> {
         info!("Starting MCP server...");
 
-        // TODO: Implement MCP protocol handling
-        // 1. Set up stdio transport
-        // 2. Register tools with MCP runtime
-        // 3. Handle incoming requests
-        // 4. Route to appropriate tool handlers
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut stdout = tokio::io::stdout();
+        let mut events = self.ctx.indexer.events().subscribe();
 
-        // Placeholder - will be replaced with actual MCP runtime
-        info!("MCP server running (placeholder implementation)");
+        loop {
+            let outgoing: Vec<Value> = tokio::select! {
+                line = lines.next_line() => {
+                    match line.map_err(|e| McpError::Runtime(e.to_string()))? {
+                        Some(line) if line.trim().is_empty() => continue,
+                        Some(line) => self.handle_line_blocking(line).await.into_iter().collect(),
+                        None => break,
+                    }
+                }
+                // Lagging behind still means something changed
+                _ = events.recv() => {
+                    self.resources.poll(&self.ctx).iter().map(|n| n.to_message()).collect()
+                }
+                _ = tokio::signal::ctrl_c() => break,
+            };
 
-        // Keep running until shutdown signal
-        tokio::signal::ctrl_c()
-            .await
-            .map_err(|e| McpError::Runtime(e.to_string()))?;
+            for message in outgoing {
+                let mut line = message.to_string();
+                line.push('\n');
+                stdout
+                    .write_all(line.as_bytes())
+                    .await
+                    .map_err(|e| McpError::Runtime(e.to_string()))?;
+            }
+            stdout.flush().await.map_err(|e| McpError::Runtime(e.to_string()))?;
+        }
 
         info!("Shutting down MCP server...");
         Ok(())
     }
 
+    /// Handle one line of input on the blocking thread pool.
+    async fn handle_line_blocking(&self, line: String) -> Option<Value> {
+        let ctx = Arc::clone(&self.ctx);
+        let resources = Arc::clone(&self.resources);
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            let _span = span.entered();
+            handle_line(&ctx, &resources, &line)
+        })
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("MCP dispatch failed: {}", e);
+            None
+        })
+    }
+
+    /// Handle one line of input, returning the response if one is due.
+    pub fn handle_line(&self, line: &str) -> Option<Value> {
+        handle_line(&self.ctx, &self.resources, line)
+    }
+
+    /// Handle a JSON-RPC message.
+    ///
+    /// Returns None for notifications, which get no response.
+    pub fn handle_message(&self, message: &Value) -> Option<Value> {
+        dispatch(&self.ctx, &self.resources, message)
+    }
+
     /// Reload the skill index.
     pub fn reload(&self) -> Result<(), crate::index::IndexError> {
         self.ctx.indexer.reload()
     }
 }
 
+/// Parse and handle one line of JSON-RPC input.
+fn handle_line(ctx: &ServiceContext, resources: &ResourceTracker, line: &str) -> Option<Value> {
+    match serde_json::from_str::<Value>(line) {
+        Ok(message) => dispatch(ctx, resources, &message),
+        Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+    }
+}
+
+/// Handle a JSON-RPC message for a client whose subscriptions are tracked
+/// by `resources`.
+///
+/// Returns None for notifications and responses, which get no response.
+pub(crate) fn dispatch(
+    ctx: &ServiceContext,
+    resources: &ResourceTracker,
+    message: &Value,
+) -> Option<Value> {
+    let method = message.get("method").and_then(Value::as_str)?;
+    let id = message.get("id")?.clone();
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let uri = params.get("uri").and_then(Value::as_str);
+
+    let _span = tracing::debug_span!("mcp", method, id = %id).entered();
+    debug!("MCP request: {}", method);
+
+    let result = match (method, uri) {
+        ("initialize", _) => {
+            let requested = params.get("protocolVersion").and_then(Value::as_str);
+            let version = SUPPORTED_VERSIONS
+                .iter()
+                .find(|v| Some(**v) == requested)
+                .unwrap_or(&PROTOCOL_VERSION);
+            json!({
+                "protocolVersion": version,
+                "capabilities": {
+                    "tools": { "listChanged": false },
+                    "prompts": { "listChanged": false },
+                    "resources": { "subscribe": true, "listChanged": true },
+                    "completions": {},
+                },
+                "serverInfo": { "name": crate::NAME, "version": crate::VERSION },
+            })
+        }
+        ("ping", _) => json!({}),
+        ("tools/list", _) => json!({ "tools": registry::list_tools() }),
+        ("tools/call", _) => {
+            let called = serde_json::from_value::<CallToolRequest>(params)
+                .map_err(|e| ErrorResponse::new(ErrorCode::InvalidRequest, e.to_string()))
+                .and_then(|req| registry::call_tool(ctx, req));
+            match called {
+                Ok(result) => serde_json::to_value(result).unwrap_or_default(),
+                Err(e) => return Some(service_error_response(id, INVALID_PARAMS, &e)),
+            }
+        }
+        ("prompts/list", _) => serde_json::to_value(prompts::list_prompts(ctx)).unwrap_or_default(),
+        ("prompts/get", _) => {
+            let prompt = serde_json::from_value::<GetPromptRequest>(params)
+                .map_err(|e| ErrorResponse::new(ErrorCode::InvalidRequest, e.to_string()))
+                .and_then(|req| prompts::get_prompt(ctx, req));
+            match prompt {
+                Ok(result) => serde_json::to_value(result).unwrap_or_default(),
+                Err(e) => return Some(service_error_response(id, INVALID_PARAMS, &e)),
+            }
+        }
+        ("resources/list", _) => {
+            let cursor = params.get("cursor").and_then(Value::as_str);
+            match resources::list_resources(ctx, cursor) {
+                Ok(result) => serde_json::to_value(result).unwrap_or_default(),
+                Err(e) => return Some(service_error_response(id, INVALID_PARAMS, &e)),
+            }
+        }
+        ("resources/read", Some(uri)) => match resources::read_resource(ctx, uri) {
+            Ok(result) => serde_json::to_value(result).unwrap_or_default(),
+            Err(e) => return Some(service_error_response(id, RESOURCE_NOT_FOUND, &e)),
+        },
+        ("resources/subscribe", Some(uri)) => {
+            resources.subscribe(uri);
+            json!({})
+        }
+        ("resources/unsubscribe", Some(uri)) => {
+            resources.unsubscribe(uri);
+            json!({})
+        }
+        ("completion/complete", _) => {
+            let completed = serde_json::from_value::<CompleteRequest>(params)
+                .map_err(|e| ErrorResponse::new(ErrorCode::InvalidRequest, e.to_string()))
+                .and_then(|req| completion::complete(ctx, &req));
+            match completed {
+                Ok(result) => serde_json::to_value(result).unwrap_or_default(),
+                Err(e) => return Some(service_error_response(id, INVALID_PARAMS, &e)),
+            }
+        }
+        ("resources/read" | "resources/subscribe" | "resources/unsubscribe", None) => {
+            return Some(error_response(id, INVALID_PARAMS, "Missing 'uri' parameter"));
+        }
+        _ => {
+            return Some(error_response(
+                id,
+                METHOD_NOT_FOUND,
+                &format!("Method not found: {}", method),
+            ));
+        }
+    };
+
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+/// Build a JSON-RPC error response.
+pub(crate) fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Build a JSON-RPC error response for a service error, carrying its
+/// machine-readable code, field details, and suggestions as `data`.
+pub(crate) fn service_error_response(id: Value, code: i64, error: &ErrorResponse) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": error.error, "data": error },
+    })
+}
+
 /// MCP server errors.
 #[derive(Debug, thiserror::Error)]
 pub enum McpError {
@@ -84,6 +294,7 @@ pub enum McpError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::write_skill;
     use std::fs;
     use tempfile::TempDir;
 
@@ -92,14 +303,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         // Create a test skill
-        let skill_dir = temp_dir.path().join("test-skill");
-        fs::create_dir_all(&skill_dir).unwrap();
-        fs::write(
-            skill_dir.join("_meta.json"),
-            r#"{"name": "test-skill", "description": "Test"}"#,
-        )
-        .unwrap();
-        fs::write(skill_dir.join("SKILL.md"), "# Test").unwrap();
+        write_skill(temp_dir.path(), "test-skill", r#"{"name": "test-skill", "description": "Test"}"#, "# Test");
 
         let server = McpServer::new(temp_dir.path());
         let ctx = server.context();
@@ -108,4 +312,295 @@ mod tests {
         let index = ctx.indexer.get_skill_index();
         assert_eq!(index.len(), 1);
     }
+
+    #[test]
+    fn test_resources_over_jsonrpc() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "forms", r#"{"name": "forms", "description": "Forms"}"#, "# Forms");
+
+        let server = McpServer::new(temp_dir.path());
+
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#)
+            .unwrap();
+        assert_eq!(response["result"]["capabilities"]["resources"]["listChanged"], true);
+
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 2, "method": "resources/list"}"#)
+            .unwrap();
+        assert_eq!(response["result"]["resources"][0]["uri"], "skill://forms");
+        assert_eq!(response["result"]["resources"][0]["mimeType"], "text/markdown");
+
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 3, "method": "resources/read", "params": {"uri": "skill://forms"}}"#,
+            )
+            .unwrap();
+        assert_eq!(response["id"], 3);
+        assert_eq!(response["result"]["contents"][0]["text"], "# Forms");
+
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 4, "method": "resources/read", "params": {"uri": "skill://nope"}}"#,
+            )
+            .unwrap();
+        assert_eq!(response["error"]["code"], RESOURCE_NOT_FOUND);
+        assert_eq!(response["error"]["data"]["code"], "NOT_FOUND");
+
+        // Notifications get no response
+        assert!(server
+            .handle_line(r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#)
+            .is_none());
+        assert_eq!(server.handle_line("{").unwrap()["error"]["code"], PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_completion_over_jsonrpc() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "forms", r#"{"name": "forms", "description": "Forms"}"#, "# Forms");
+
+        let server = McpServer::new(temp_dir.path());
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#)
+            .unwrap();
+        assert!(response["result"]["capabilities"]["completions"].is_object());
+
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 2, "method": "completion/complete", "params": {
+                    "ref": {"type": "ref/resource", "uri": "skill://{name}"},
+                    "argument": {"name": "name", "value": "fo"}}}"#,
+            )
+            .unwrap();
+        assert_eq!(response["result"]["completion"]["values"], json!(["forms"]));
+        assert_eq!(response["result"]["completion"]["hasMore"], false);
+
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 3, "method": "completion/complete", "params": {}}"#)
+            .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_tools_over_jsonrpc() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "forms", r#"{"name": "forms", "description": "Forms"}"#, "# Forms");
+
+        let server = McpServer::new(temp_dir.path());
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#)
+            .unwrap();
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 2, "method": "tools/list"}"#)
+            .unwrap();
+        let tools = response["result"]["tools"].as_array().unwrap();
+        let get_skill = tools.iter().find(|t| t["name"] == "get_skill").unwrap();
+        assert_eq!(get_skill["inputSchema"]["required"], json!(["name"]));
+        assert!(tools.iter().all(|t| t["inputSchema"]["type"] == "object"));
+
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "get_skill", "arguments": {"name": "forms"}}}"#,
+            )
+            .unwrap();
+        assert_eq!(response["result"]["isError"], false);
+        let content: Value =
+            serde_json::from_str(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(content["content"], "# Forms");
+
+        // Errors the tool reports are results; bad calls are protocol errors
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 4, "method": "tools/call", "params": {"name": "get_skill", "arguments": {"name": "nope"}}}"#,
+            )
+            .unwrap();
+        assert_eq!(response["result"]["isError"], true);
+        assert!(response["result"]["content"][0]["text"].as_str().unwrap().contains("not found"));
+
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 5, "method": "tools/call", "params": {"name": "nope"}}"#)
+            .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 6, "method": "tools/call", "params": {"name": "get_skill", "arguments": {}}}"#,
+            )
+            .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scripts_enabled_by_config() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(temp_dir.path(), "test-skill", r#"{"name": "test-skill", "description": "Test"}"#, "# Test");
+        fs::create_dir_all(temp_dir.path().join("test-skill/scripts")).unwrap();
+        fs::write(temp_dir.path().join("test-skill/scripts/hello.sh"), "echo \"hello $1\"").unwrap();
+        let call = r#"{"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {"name": "run_skill_script", "arguments": {"name": "test-skill", "script": "hello.sh", "args": ["there"]}}}"#;
+
+        let server = McpServer::new(temp_dir.path());
+        let response = server.handle_line(call).unwrap();
+        assert_eq!(response["result"]["isError"], true);
+
+        let config = Config::parse("[scripts]\nenabled = true\nallow = [\"test-skill/*.sh\"]\n").unwrap();
+        let server = McpServer::new(temp_dir.path()).with_config(config);
+        let response = server.handle_line(call).unwrap();
+        assert_eq!(response["result"]["isError"], false);
+        let output: Value =
+            serde_json::from_str(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(output["stdout"], "hello there\n");
+    }
+
+    #[test]
+    fn test_prompts_over_jsonrpc() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "greeting",
+            r#"{"name": "greeting", "description": "Greets", "variables": [
+                {"name": "who", "description": "Who to greet", "required": true},
+                {"name": "tone", "default": "warm"}]}"#,
+            "Hello {{who}}, {{tone}}ly",
+        );
+        write_skill(temp_dir.path(), "forms", r#"{"name": "forms", "description": "Forms"}"#, "# Forms");
+
+        let server = McpServer::new(temp_dir.path());
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#)
+            .unwrap();
+        assert!(response["result"]["capabilities"]["prompts"].is_object());
+
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 2, "method": "prompts/list"}"#)
+            .unwrap();
+        let prompts = response["result"]["prompts"].as_array().unwrap();
+        assert_eq!(prompts.len(), 1);
+        assert_eq!(prompts[0]["name"], "greeting");
+        assert_eq!(
+            prompts[0]["arguments"],
+            json!([
+                {"name": "who", "description": "Who to greet", "required": true},
+                {"name": "tone", "required": false},
+            ])
+        );
+
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 3, "method": "prompts/get", "params": {"name": "greeting", "arguments": {"who": "Ada"}}}"#,
+            )
+            .unwrap();
+        assert_eq!(response["result"]["messages"][0]["role"], "user");
+        assert_eq!(response["result"]["messages"][0]["content"]["text"], "Hello Ada, warmly");
+
+        // Missing required values and skills without variables are errors
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 4, "method": "prompts/get", "params": {"name": "greeting"}}"#)
+            .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 5, "method": "prompts/get", "params": {"name": "forms"}}"#)
+            .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        // The argument schema is also a tool
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 6, "method": "tools/call", "params": {"name": "get_skill_arguments", "arguments": {"name": "greeting"}}}"#,
+            )
+            .unwrap();
+        let schema: Value =
+            serde_json::from_str(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(schema["required"], json!(["name", "who"]));
+    }
+
+    #[test]
+    fn test_tool_calls_are_paged() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["alpha", "beta", "gamma"] {
+            write_skill(
+                temp_dir.path(),
+                name,
+                &format!(r#"{{"name": "{}", "description": "Skill {}"}}"#, name, name),
+                &format!("# {}\n\n{}\n", name, "line\n".repeat(40)),
+            );
+        }
+        let server = McpServer::new(temp_dir.path());
+        *server.context().max_response_bytes.write() = Some(120);
+
+        let call = |name: &str, arguments: Value| {
+            let request = json!({
+                "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                "params": {"name": name, "arguments": arguments},
+            });
+            let response = server.handle_line(&request.to_string()).unwrap();
+            assert_eq!(response["result"]["isError"], false, "{}", response);
+            serde_json::from_str::<Value>(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap()
+        };
+
+        // Listings stop before the size limit and continue with the cursor
+        let mut names = Vec::new();
+        let mut arguments = json!({});
+        loop {
+            let page = call("list_skills", arguments);
+            assert_eq!(page["total"], 3);
+            names.extend(page["skills"].as_array().unwrap().iter().map(|s| s["name"].clone()));
+            match page.get("next_cursor") {
+                Some(cursor) => arguments = json!({ "cursor": cursor }),
+                None => break,
+            }
+        }
+        assert_eq!(names, vec![json!("alpha"), json!("beta"), json!("gamma")]);
+
+        let page = call("list_skills", json!({ "page_size": 1 }));
+        assert_eq!(page["skills"].as_array().unwrap().len(), 1);
+        assert!(page["next_cursor"].is_string());
+
+        // Content is split the same way
+        let mut content = String::new();
+        let mut arguments = json!({ "name": "beta" });
+        loop {
+            let part = call("get_skill", arguments.clone());
+            let text = part["content"].as_str().unwrap();
+            assert!(text.len() <= 120);
+            content.push_str(text);
+            match part.get("next_cursor") {
+                Some(cursor) => arguments["cursor"] = cursor.clone(),
+                None => break,
+            }
+        }
+        assert_eq!(content, format!("# beta\n\n{}\n", "line\n".repeat(40)));
+    }
+
+    #[test]
+    fn test_build_context_over_jsonrpc() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Form validation patterns"}"#,
+            "# Forms\n\nValidate on blur.",
+        );
+
+        let server = McpServer::new(temp_dir.path());
+        let response = server
+            .handle_line(r#"{"jsonrpc": "2.0", "id": 1, "method": "tools/list"}"#)
+            .unwrap();
+        let tools = response["result"]["tools"].as_array().unwrap();
+        let tool = tools.iter().find(|t| t["name"] == "build_context").unwrap();
+        assert_eq!(tool["inputSchema"]["required"], json!(["task", "max_tokens"]));
+
+        let response = server
+            .handle_line(
+                r#"{"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "build_context",
+                    "arguments": {"task": "form validation", "max_tokens": 1000}}}"#,
+            )
+            .unwrap();
+        assert_eq!(response["result"]["isError"], false);
+        let pack: Value =
+            serde_json::from_str(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(pack["parts"][0]["source"], "forms/SKILL.md");
+        assert!(pack["content"].as_str().unwrap().contains("Validate on blur."));
+    }
 }
@@ -3,15 +3,36 @@
 //! Each function here corresponds to an MCP tool that will be registered
 //! with the MCP server.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
-use crate::index::SkillIndexer;
+use crate::abuse::{AbuseConfig, AbuseDetector};
+use crate::analytics::Analytics;
+use crate::audit::{AuditAction, AuditEntry, AuditLog};
+use crate::analysis::QualityConfig;
+use crate::backup::BackupConfig;
+use crate::bundles::{BundleContent, Bundles};
+use crate::config::Config;
+use crate::context::{self, ContextOptions, ContextPack, DEFAULT_MAX_SKILLS};
+use crate::import::ImportConfig;
+use crate::index::{IndexError, ReloadJobs, SkillIndexer};
+use crate::integrity::IntegrityReport;
+use crate::journal::Journal;
+use crate::lifecycle::RenameReport;
+use crate::maintenance::Maintenance;
+use crate::markdown;
 use crate::models::*;
+use crate::notify::Notifiers;
+use crate::scripts::{self, ScriptError, ScriptOutput, ScriptsConfig};
 use crate::search::SearchService;
+use crate::lint::LintConfig;
 use crate::validation::validate_skills;
 
+use super::paging::{paginate, split_content, PageRequest};
+
 /// Service context shared across all tool handlers.
 pub struct ServiceContext {
     /// The skill indexer for loading skill metadata and content.
@@ -20,43 +41,276 @@ pub struct ServiceContext {
     pub search: SearchService,
     /// Usage statistics tracker.
     pub stats: Arc<parking_lot::RwLock<UsageStats>>,
+    /// Per-skill read receipts.
+    pub receipts: Arc<parking_lot::RwLock<ReadReceipts>>,
+    /// Tokens of skill content served, per session and per day.
+    pub tokens: Arc<parking_lot::RwLock<TokenMetrics>>,
+    /// Alert delivery for operational events.
+    pub notifiers: Arc<Notifiers>,
+    /// Detector for anomalous access patterns.
+    pub abuse: AbuseDetector,
+    /// Result of the last integrity verification, if a manifest was checked.
+    pub integrity: parking_lot::RwLock<Option<IntegrityReport>>,
+    /// Maintenance mode switch.
+    pub maintenance: Maintenance,
+    /// Usage analytics, optionally persisted across restarts.
+    pub analytics: Analytics,
+    /// Log of skill mutations.
+    pub audit: AuditLog,
+    /// Files changed by each mutation, for replay.
+    pub journal: Journal,
+    /// Serializes read-modify-write edits of skill files.
+    pub edits: parking_lot::Mutex<()>,
+    /// Lint rule settings.
+    pub lint: parking_lot::RwLock<LintConfig>,
+    /// Background reloads started through the API.
+    pub reloads: ReloadJobs,
+    /// Fingerprint of the API key MCP tools and resources act for; private
+    /// skills are hidden from them unless they allow it.
+    pub mcp_key: parking_lot::RwLock<Option<String>>,
+    /// Size in bytes that listed items and content in a tool result are
+    /// kept under, continuing with a cursor; unlimited when None.
+    pub max_response_bytes: parking_lot::RwLock<Option<usize>>,
+    /// Backup directory, schedule, and retention.
+    pub backup: parking_lot::RwLock<BackupConfig>,
+    /// Quality report thresholds.
+    pub quality: parking_lot::RwLock<QualityConfig>,
+    /// Whether and which skill scripts may run.
+    pub scripts: parking_lot::RwLock<ScriptsConfig>,
+    /// Where folders may be imported from.
+    pub import: parking_lot::RwLock<ImportConfig>,
 }
 
+/// Reader identity recorded for reads made over the MCP transport.
+pub const MCP_READER: &str = "mcp";
+
+/// Computed related-skill suggestions returned alongside curated links.
+const RELATED_LIMIT: usize = 5;
+
+/// "Did you mean" suggestions offered for an unknown skill name.
+const MAX_NAME_SUGGESTIONS: usize = 3;
+
 impl ServiceContext {
     /// Create a new service context.
     pub fn new(indexer: Arc<SkillIndexer>) -> Self {
         let search = SearchService::new(Arc::clone(&indexer));
         let stats = Arc::new(parking_lot::RwLock::new(UsageStats::new()));
+        let receipts = Arc::new(parking_lot::RwLock::new(ReadReceipts::new()));
+        let tokens = Arc::new(parking_lot::RwLock::new(TokenMetrics::new()));
+        let notifiers = Arc::new(Notifiers::new());
+        let abuse = AbuseDetector::new(AbuseConfig::default(), Arc::clone(&notifiers));
 
         Self {
             indexer,
             search,
             stats,
+            receipts,
+            tokens,
+            notifiers,
+            abuse,
+            integrity: parking_lot::RwLock::new(None),
+            maintenance: Maintenance::new(),
+            analytics: Analytics::new(),
+            audit: AuditLog::new(),
+            journal: Journal::new(),
+            edits: parking_lot::Mutex::new(()),
+            lint: parking_lot::RwLock::new(LintConfig::default()),
+            reloads: ReloadJobs::new(),
+            mcp_key: parking_lot::RwLock::new(None),
+            max_response_bytes: parking_lot::RwLock::new(None),
+            backup: parking_lot::RwLock::new(BackupConfig::default()),
+            quality: parking_lot::RwLock::new(QualityConfig::default()),
+            scripts: parking_lot::RwLock::new(ScriptsConfig::default()),
+            import: parking_lot::RwLock::new(ImportConfig::default()),
+        }
+    }
+
+    /// Apply `skills-mcp.toml` settings, returning whether the indexing
+    /// settings changed so the index needs a reload.
+    pub fn apply_config(&self, config: Config) -> bool {
+        *self.lint.write() = config.lint;
+        self.search.set_boosts(config.search.boosts);
+        self.search.set_cache_capacity(config.search.cache);
+        *self.max_response_bytes.write() = config.mcp.max_response_bytes;
+        *self.backup.write() = config.backup;
+        *self.quality.write() = config.quality;
+        *self.scripts.write() = config.scripts;
+        *self.import.write() = config.import;
+        if self.indexer.index_config() == config.index {
+            return false;
+        }
+        self.indexer.set_index_config(config.index);
+        true
+    }
+
+    /// Announce a mutation in the journal. Call with the edit lock held,
+    /// before any of the mutation's files are written.
+    pub fn begin_mutation(&self, entry: &AuditEntry) {
+        self.journal.begin(entry);
+    }
+
+    /// Record a mutation of one skill's directory in the audit log and the
+    /// journal. Call once its files are written, before releasing the edit
+    /// lock.
+    pub fn record_mutation(&self, entry: AuditEntry) {
+        let dir = self.indexer.skill_dir(&entry.skill);
+        self.record_mutation_in(entry, &[dir]);
+    }
+
+    /// Record a mutation that touched `paths`, files or directories, as
+    /// [`ServiceContext::record_mutation`] does.
+    pub fn record_mutation_in(&self, entry: AuditEntry, paths: &[PathBuf]) {
+        self.journal.record(&entry, paths);
+        self.audit.record(entry);
+    }
+
+    /// Act for an API key in MCP tools and resources, so private skills
+    /// that allow it are visible.
+    pub fn set_mcp_key(&self, key: &str) {
+        *self.mcp_key.write() = Some(key_fingerprint(key));
+    }
+
+    /// Whether MCP tools and resources can see a skill. Unknown names are
+    /// visible, so callers report them as usual.
+    pub fn is_visible(&self, name: &str) -> bool {
+        self.indexer
+            .get_skill_meta(name)
+            .is_none_or(|meta| meta.is_visible_to(self.mcp_key.read().as_deref()))
+    }
+
+    /// The real name of a skill looked up by name or alias; unknown names
+    /// are returned as given.
+    pub fn canonical_name(&self, name: &str) -> String {
+        self.indexer
+            .get_skill_meta(name)
+            .map_or_else(|| name.to_string(), |meta| meta.name)
+    }
+
+    /// Error for a skill that doesn't exist, suggesting close names.
+    pub fn skill_not_found(&self, name: &str) -> ErrorResponse {
+        // Private skills are never suggested, whoever is asking
+        let suggestions: Vec<String> = self
+            .indexer
+            .suggest_names(name, MAX_NAME_SUGGESTIONS)
+            .into_iter()
+            .filter(|s| self.indexer.get_skill_meta(s).is_some_and(|m| m.visibility.is_public()))
+            .collect();
+        let mut message = format!("Skill '{}' not found", name);
+        if !suggestions.is_empty() {
+            message.push_str(&format!("; did you mean {}?", suggestions.join(", ")));
         }
+        ErrorResponse::new(ErrorCode::SkillNotFound, message).with_suggestions(suggestions)
     }
 
     /// Record a tool call for statistics.
     pub fn track_tool_call(&self, tool_name: &str) {
         self.stats.write().record_tool_call(tool_name);
+        self.analytics.record_tool_call(tool_name);
     }
 
     /// Record a skill load for statistics.
     pub fn track_skill_load(&self, skill_name: &str) {
         self.stats.write().record_skill_load(skill_name);
     }
+
+    /// Record a read receipt for a skill.
+    pub fn track_skill_read(&self, skill_name: &str, reader: &str) {
+        self.receipts.write().record(skill_name, reader);
+        self.analytics.record_read(skill_name);
+    }
+
+    /// Record a search query and its number of matches.
+    pub fn track_search(&self, query: &str, total_matches: usize) {
+        self.stats.write().record_search(query.to_string(), total_matches);
+        self.analytics.record_search(query, total_matches);
+    }
+
+    /// Append the deprecation notice of `skill`, if it is deprecated, to
+    /// content read from it.
+    pub fn append_deprecation_notice(&self, skill: &str, content: &mut String) {
+        if let Some(meta) = self.indexer.get_skill_meta(skill) {
+            meta.append_deprecation_notice(content);
+        }
+    }
+
+    /// Record the approximate tokens of skill content served to a session.
+    pub fn track_tokens_served(&self, skill_name: &str, session: &str, content: &str) {
+        self.tokens
+            .write()
+            .record(session, skill_name, markdown::estimate_tokens(content));
+    }
+
+    /// Record reads and tokens served for each item of a loaded bundle.
+    pub fn track_bundle_served(&self, bundle: &BundleContent, session: &str) {
+        for part in &bundle.parts {
+            self.track_part_served(&part.skill, part.sub_skill.as_deref(), part.tokens, session);
+        }
+    }
+
+    /// Record each part of a served context pack as a load and a read.
+    pub fn track_context_served(&self, pack: &ContextPack, session: &str) {
+        for part in &pack.parts {
+            self.track_part_served(&part.skill, part.sub_skill.as_deref(), part.tokens, session);
+        }
+    }
+
+    fn track_part_served(&self, skill: &str, sub_skill: Option<&str>, tokens: u64, session: &str) {
+        let tracked = match sub_skill {
+            Some(sub) => format!("{}:{}", skill, sub),
+            None => skill.to_string(),
+        };
+        self.track_skill_load(&tracked);
+        self.track_skill_read(skill, session);
+        self.tokens.write().record(session, &tracked, tokens);
+    }
+
+    /// Narrow search results to the requested page. `total_matches`
+    /// still counts every match.
+    pub fn page_results(
+        &self,
+        mut results: SearchResults,
+        page: &PageRequest,
+    ) -> Result<SearchResults, ErrorResponse> {
+        let page = paginate(results.results, page, *self.max_response_bytes.read())?;
+        results.results = page.items;
+        results.next_cursor = page.next_cursor;
+        Ok(results)
+    }
+
+    /// Serialize a tool result, adding the maintenance banner while
+    /// maintenance mode is enabled.
+    pub fn tool_result<T: Serialize>(&self, result: &T) -> serde_json::Value {
+        let mut value = serde_json::to_value(result).unwrap_or(serde_json::Value::Null);
+        self.maintenance.annotate(&mut value);
+        value
+    }
 }
 
 // ============================================================================
 // Tool: list_skills
 // ============================================================================
 
+/// Request for list_skills tool.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListSkillsRequest {
+    /// Include archived skills.
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Page of skills to return.
+    #[serde(flatten)]
+    pub page: PageRequest,
+}
+
 /// Response for list_skills tool.
 #[derive(Debug, Serialize)]
 pub struct ListSkillsResponse {
     /// List of skill summaries.
     pub skills: Vec<SkillSummary>,
-    /// Total number of skills.
+    /// Total number of skills, across all pages.
     pub total: usize,
+    /// Cursor of the next page, if skills remain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Summary info for a skill.
@@ -70,10 +324,13 @@ pub struct SkillSummary {
     pub tags: Vec<String>,
     /// Names of sub-skills within this skill.
     pub sub_skills: Vec<String>,
+    /// Whether the skill is archived.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
 }
 
-/// List all available skill domains.
-pub fn list_skills(ctx: &ServiceContext) -> ListSkillsResponse {
+/// List available skill domains; archived skills only on request.
+pub fn list_skills(ctx: &ServiceContext, req: ListSkillsRequest) -> Result<ListSkillsResponse, ErrorResponse> {
     ctx.track_tool_call("list_skills");
 
     let index = ctx.indexer.get_skill_index();
@@ -81,17 +338,25 @@ pub fn list_skills(ctx: &ServiceContext) -> ListSkillsResponse {
     let skills: Vec<SkillSummary> = index
         .skills
         .iter()
+        .filter(|s| req.include_archived || !s.archived)
+        .filter(|s| s.is_visible_to(ctx.mcp_key.read().as_deref()))
         .map(|s| SkillSummary {
             name: s.name.clone(),
             description: s.description.clone(),
             tags: s.tags.clone(),
             sub_skills: s.sub_skill_names().iter().map(|n| n.to_string()).collect(),
+            archived: s.archived,
         })
         .collect();
 
     let total = skills.len();
+    let page = paginate(skills, &req.page, *ctx.max_response_bytes.read())?;
 
-    ListSkillsResponse { skills, total }
+    Ok(ListSkillsResponse {
+        skills: page.items,
+        total,
+        next_cursor: page.next_cursor,
+    })
 }
 
 // ============================================================================
@@ -103,16 +368,190 @@ pub fn list_skills(ctx: &ServiceContext) -> ListSkillsResponse {
 pub struct GetSkillRequest {
     /// Name of the skill to retrieve.
     pub name: String,
+    /// Strip decorations that don't matter to the model.
+    #[serde(default)]
+    pub minify: bool,
+    /// `next_cursor` of a previous response, to continue split content.
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Serve the translation in this language, if the skill has one.
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Values for the skill's declared template variables.
+    #[serde(flatten)]
+    pub variables: HashMap<String, serde_json::Value>,
 }
 
 /// Get the main SKILL.md content for a skill.
+///
+/// Declared template variables are filled from the request's extra
+/// arguments, falling back to their defaults.
 pub fn get_skill(ctx: &ServiceContext, req: GetSkillRequest) -> Result<SkillContent, ErrorResponse> {
     ctx.track_tool_call("get_skill");
-    ctx.track_skill_load(&req.name);
+    if !ctx.indexer.skill_exists(&req.name) || !ctx.is_visible(&req.name) {
+        return Err(ctx.skill_not_found(&req.name));
+    }
+    let name = ctx.canonical_name(&req.name);
+    ctx.track_skill_load(&name);
+    ctx.track_skill_read(&name, MCP_READER);
+
+    let mut content = ctx
+        .indexer
+        .read_skill_content_in(&name, req.lang.as_deref())
+        .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))?;
+
+    if let Some(meta) = ctx.indexer.get_skill_meta(&name) {
+        if !meta.variables.is_empty() {
+            let provided: HashMap<String, String> = req
+                .variables
+                .into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(s) => (key, s),
+                    other => (key, other.to_string()),
+                })
+                .collect();
+            let values = meta.resolve_variables(&provided).map_err(|errors| {
+                ErrorResponse::new(ErrorCode::ValidationFailed, format!(
+                    "Invalid variables for '{}': {}",
+                    name,
+                    errors.join("; ")
+                ))
+            })?;
+            content.content = markdown::render_variables(&content.content, &values);
+        }
+    }
 
-    ctx.indexer
-        .read_skill_content(&req.name)
-        .map_err(|e| ErrorResponse::new(e.to_string()))
+    if req.minify {
+        content.content = markdown::minify(&content.content);
+    }
+    ctx.append_deprecation_notice(&name, &mut content.content);
+    (content.content, content.next_cursor) =
+        split_content(&content.content, req.cursor.as_deref(), *ctx.max_response_bytes.read())?;
+    ctx.track_tokens_served(&name, MCP_READER, &content.content);
+    let index = ctx.indexer.get_skill_index();
+    let related = index
+        .related(&name, RELATED_LIMIT)
+        .into_iter()
+        .filter(|r| ctx.is_visible(&r.name))
+        .collect();
+    Ok(content.with_related(related))
+}
+
+// ============================================================================
+// Tool: get_skill_arguments
+// ============================================================================
+
+/// Request for get_skill_arguments tool.
+#[derive(Debug, Deserialize)]
+pub struct GetSkillArgumentsRequest {
+    /// Name of the skill.
+    pub name: String,
+}
+
+/// Get the JSON Schema of `get_skill` arguments for a specific skill.
+///
+/// Declared template variables appear as string properties, so clients
+/// can prompt for required values instead of leaving agents to guess.
+pub fn get_skill_arguments(
+    ctx: &ServiceContext,
+    req: GetSkillArgumentsRequest,
+) -> Result<serde_json::Value, ErrorResponse> {
+    ctx.track_tool_call("get_skill_arguments");
+
+    let meta = ctx
+        .indexer
+        .get_skill_meta(&req.name)
+        .filter(|_| ctx.is_visible(&req.name))
+        .ok_or_else(|| ctx.skill_not_found(&req.name))?;
+
+    let mut properties = serde_json::Map::new();
+    properties.insert(
+        "name".to_string(),
+        serde_json::json!({ "type": "string", "const": meta.name }),
+    );
+    properties.insert(
+        "minify".to_string(),
+        serde_json::json!({ "type": "boolean", "default": false }),
+    );
+    if !meta.translations.is_empty() {
+        properties.insert(
+            "lang".to_string(),
+            serde_json::json!({
+                "type": "string",
+                "description": "Language of the content; SKILL.md is served without a matching translation",
+                "examples": meta.translations,
+            }),
+        );
+    }
+
+    let mut required = vec!["name".to_string()];
+    for var in &meta.variables {
+        let mut property = serde_json::json!({ "type": "string" });
+        if let Some(description) = &var.description {
+            property["description"] = description.clone().into();
+        }
+        if let Some(default) = &var.default {
+            property["default"] = default.clone().into();
+        }
+        if !var.options.is_empty() {
+            property["enum"] = var.options.clone().into();
+        }
+        properties.insert(var.name.clone(), property);
+
+        if var.required && var.default.is_none() {
+            required.push(var.name.clone());
+        }
+    }
+
+    Ok(serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    }))
+}
+
+// ============================================================================
+// Tool: get_quick_answer
+// ============================================================================
+
+/// Request for get_quick_answer tool.
+#[derive(Debug, Deserialize)]
+pub struct GetQuickAnswerRequest {
+    /// Name of the skill.
+    pub name: String,
+}
+
+/// Response for get_quick_answer tool.
+#[derive(Debug, Serialize)]
+pub struct QuickAnswerResponse {
+    /// Skill name.
+    pub name: String,
+    /// Short answer, if the skill defines one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quick_answer: Option<String>,
+    /// Skill description, as a fallback when there is no quick answer.
+    pub description: String,
+}
+
+/// Get a skill's quick answer without loading its full content.
+pub fn get_quick_answer(
+    ctx: &ServiceContext,
+    req: GetQuickAnswerRequest,
+) -> Result<QuickAnswerResponse, ErrorResponse> {
+    ctx.track_tool_call("get_quick_answer");
+
+    let meta = ctx
+        .indexer
+        .get_skill_meta(&req.name)
+        .filter(|_| ctx.is_visible(&req.name))
+        .ok_or_else(|| ctx.skill_not_found(&req.name))?;
+
+    Ok(QuickAnswerResponse {
+        name: meta.name,
+        quick_answer: meta.quick_answer,
+        description: meta.description,
+    })
 }
 
 // ============================================================================
@@ -126,6 +565,12 @@ pub struct GetSubSkillRequest {
     pub domain: String,
     /// Name of the sub-skill to retrieve.
     pub sub_skill: String,
+    /// Strip decorations that don't matter to the model.
+    #[serde(default)]
+    pub minify: bool,
+    /// `next_cursor` of a previous response, to continue split content.
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 /// Get sub-skill content.
@@ -134,11 +579,415 @@ pub fn get_sub_skill(
     req: GetSubSkillRequest,
 ) -> Result<SubSkillContent, ErrorResponse> {
     ctx.track_tool_call("get_sub_skill");
-    ctx.track_skill_load(&format!("{}:{}", req.domain, req.sub_skill));
+    if ctx.indexer.get_skill_meta(&req.domain).is_none() || !ctx.is_visible(&req.domain) {
+        return Err(ctx.skill_not_found(&req.domain));
+    }
+    let domain = ctx.canonical_name(&req.domain);
+    ctx.track_skill_load(&format!("{}:{}", domain, req.sub_skill));
+    ctx.track_skill_read(&domain, MCP_READER);
+
+    let mut content = ctx
+        .indexer
+        .read_sub_skill_content(&domain, &req.sub_skill)
+        .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))?;
+    if req.minify {
+        content.content = markdown::minify(&content.content);
+    }
+    ctx.append_deprecation_notice(&domain, &mut content.content);
+    (content.content, content.next_cursor) =
+        split_content(&content.content, req.cursor.as_deref(), *ctx.max_response_bytes.read())?;
+    ctx.track_tokens_served(
+        &format!("{}:{}", domain, req.sub_skill),
+        MCP_READER,
+        &content.content,
+    );
+    Ok(content)
+}
 
-    ctx.indexer
-        .read_sub_skill_content(&req.domain, &req.sub_skill)
-        .map_err(|e| ErrorResponse::new(e.to_string()))
+// ============================================================================
+// Tool: get_skill_tables
+// ============================================================================
+
+/// Request for get_skill_tables tool.
+#[derive(Debug, Deserialize)]
+pub struct GetSkillTablesRequest {
+    /// Name of the skill.
+    pub name: String,
+    /// Only return tables from this sub-skill.
+    #[serde(default)]
+    pub sub_skill: Option<String>,
+}
+
+/// A table found in one of a skill's files.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillTable {
+    /// File containing the table, relative to the skill directory.
+    pub file: String,
+    /// Sub-skill owning the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+    /// The parsed table.
+    #[serde(flatten)]
+    pub table: markdown::Table,
+}
+
+/// Tables in a skill's SKILL.md, sub-skills, and references.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillTables {
+    /// Skill name.
+    pub skill: String,
+    /// Tables, SKILL.md first, then other files by path.
+    pub tables: Vec<SkillTable>,
+}
+
+/// Extract the markdown tables of a skill, or None if it isn't indexed.
+pub fn skill_tables(ctx: &ServiceContext, name: &str, sub_skill: Option<&str>) -> Option<SkillTables> {
+    if !ctx.indexer.skill_exists(name) {
+        return None;
+    }
+    let name = &ctx.canonical_name(name);
+
+    // The content index stores lowercased text, so read the files themselves
+    let skill_dir = ctx.indexer.skill_dir(name);
+    let content_index = ctx.indexer.get_content_index();
+    let mut entries: Vec<&ContentIndexEntry> = content_index
+        .get_domain_entries(name)
+        .into_iter()
+        .filter(|e| sub_skill.is_none_or(|sub| e.sub_skill.as_deref() == Some(sub)))
+        .collect();
+    entries.sort_by_key(|e| (e.file != "SKILL.md", e.file.clone()));
+
+    let tables = entries
+        .into_iter()
+        .flat_map(|entry| {
+            let content = std::fs::read_to_string(skill_dir.join(&entry.file)).unwrap_or_default();
+            markdown::extract_tables(&content)
+                .into_iter()
+                .map(|table| SkillTable {
+                    file: entry.file.clone(),
+                    sub_skill: entry.sub_skill.clone(),
+                    table,
+                })
+        })
+        .collect();
+
+    Some(SkillTables {
+        skill: name.to_string(),
+        tables,
+    })
+}
+
+/// Get a skill's markdown tables as structured data.
+pub fn get_skill_tables(
+    ctx: &ServiceContext,
+    req: GetSkillTablesRequest,
+) -> Result<SkillTables, ErrorResponse> {
+    ctx.track_tool_call("get_skill_tables");
+    if !ctx.is_visible(&req.name) {
+        return Err(ctx.skill_not_found(&req.name));
+    }
+    ctx.track_skill_read(&ctx.canonical_name(&req.name), MCP_READER);
+
+    skill_tables(ctx, &req.name, req.sub_skill.as_deref()).ok_or_else(|| ctx.skill_not_found(&req.name))
+}
+
+// ============================================================================
+// Tool: get_skill_outline
+// ============================================================================
+
+/// Request for get_skill_outline tool.
+#[derive(Debug, Deserialize)]
+pub struct GetSkillOutlineRequest {
+    /// Name of the skill.
+    pub name: String,
+    /// Outline this sub-skill's file instead of SKILL.md.
+    #[serde(default)]
+    pub sub_skill: Option<String>,
+}
+
+/// Heading outline of a skill file.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillOutline {
+    /// Skill name.
+    pub skill: String,
+    /// Sub-skill outlined, if not SKILL.md.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+    /// Estimated tokens of the whole file.
+    pub total_tokens: u64,
+    /// Preamble tokens and the section tree.
+    #[serde(flatten)]
+    pub outline: markdown::Outline,
+}
+
+/// Outline a skill's SKILL.md, or one of its sub-skills.
+pub fn skill_outline(
+    ctx: &ServiceContext,
+    name: &str,
+    sub_skill: Option<&str>,
+) -> Result<SkillOutline, IndexError> {
+    let (skill, content) = match sub_skill {
+        Some(sub) => {
+            let content = ctx.indexer.read_sub_skill_content(name, sub)?;
+            (content.domain, content.content)
+        }
+        None => {
+            let content = ctx.indexer.read_skill_content(name)?;
+            (content.name, content.content)
+        }
+    };
+    let body = markdown::strip_frontmatter(&content);
+
+    Ok(SkillOutline {
+        skill,
+        sub_skill: sub_skill.map(str::to_string),
+        total_tokens: markdown::estimate_tokens(body.trim()),
+        outline: markdown::outline(&content),
+    })
+}
+
+/// Get the heading hierarchy of a skill with token counts per section, to
+/// pick sections before fetching full content.
+pub fn get_skill_outline(
+    ctx: &ServiceContext,
+    req: GetSkillOutlineRequest,
+) -> Result<SkillOutline, ErrorResponse> {
+    ctx.track_tool_call("get_skill_outline");
+    if !ctx.indexer.skill_exists(&req.name) || !ctx.is_visible(&req.name) {
+        return Err(ctx.skill_not_found(&req.name));
+    }
+    ctx.track_skill_read(&ctx.canonical_name(&req.name), MCP_READER);
+
+    skill_outline(ctx, &req.name, req.sub_skill.as_deref())
+        .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))
+}
+
+// ============================================================================
+// Tool: find_examples
+// ============================================================================
+
+/// A code block in one of a skill's files.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillExample {
+    /// File containing the block, relative to the skill directory.
+    pub file: String,
+    /// Sub-skill owning the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+    /// The block, with its language and nearest heading.
+    #[serde(flatten)]
+    pub block: markdown::CodeBlock,
+}
+
+/// Code examples of a skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillExamples {
+    /// Skill name.
+    pub skill: String,
+    /// Examples, SKILL.md first, then other files by path, each in file
+    /// order.
+    pub examples: Vec<SkillExample>,
+}
+
+/// List the indexed code blocks of a skill, optionally only those in one
+/// code language, or None if the skill isn't indexed.
+pub fn skill_examples(ctx: &ServiceContext, name: &str, lang: Option<&str>) -> Option<SkillExamples> {
+    if !ctx.indexer.skill_exists(name) {
+        return None;
+    }
+    let name = &ctx.canonical_name(name);
+    let lang = lang.map(markdown::normalize_code_language);
+
+    let content_index = ctx.indexer.get_content_index();
+    let mut entries = content_index.get_domain_entries(name);
+    entries.sort_by_key(|e| (e.file != "SKILL.md", e.file.clone()));
+
+    let examples = entries
+        .into_iter()
+        .flat_map(|entry| {
+            entry
+                .code_blocks
+                .iter()
+                .filter(|block| lang.is_none() || block.language == lang)
+                .map(|block| SkillExample {
+                    file: entry.file.clone(),
+                    sub_skill: entry.sub_skill.clone(),
+                    block: block.clone(),
+                })
+        })
+        .collect();
+
+    Some(SkillExamples {
+        skill: name.to_string(),
+        examples,
+    })
+}
+
+/// Request for find_examples tool.
+#[derive(Debug, Deserialize)]
+pub struct FindExamplesRequest {
+    /// Query matched against code only, in the same language as
+    /// `search_content`. Empty lists every block.
+    #[serde(default)]
+    pub query: String,
+    /// Only return blocks in this code language (e.g. `python`, `ts`).
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Only search this skill.
+    #[serde(default)]
+    pub skill: Option<String>,
+    /// Maximum number of results to return.
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Include examples of archived skills.
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Page of the results to return.
+    #[serde(flatten)]
+    pub page: PageRequest,
+}
+
+/// Response from find_examples tool.
+#[derive(Debug, Serialize)]
+pub struct FindExamplesResponse {
+    /// Matching code blocks, best first.
+    pub examples: Vec<ExampleMatch>,
+    /// Number of matching blocks, before the limit.
+    pub total: usize,
+    /// Cursor of the next page, if results remain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Search only within skills' fenced code blocks.
+pub fn find_examples(ctx: &ServiceContext, req: FindExamplesRequest) -> Result<FindExamplesResponse, ErrorResponse> {
+    ctx.track_tool_call("find_examples");
+    let domains = match &req.skill {
+        Some(skill) if !ctx.indexer.skill_exists(skill) || !ctx.is_visible(skill) => {
+            return Err(ctx.skill_not_found(skill));
+        }
+        Some(skill) => Some(vec![ctx.canonical_name(skill)]),
+        None => None,
+    };
+
+    let options = SearchOptions {
+        domains,
+        include_archived: req.include_archived,
+        key: ctx.mcp_key.read().clone(),
+        ..Default::default()
+    };
+    let mut examples = ctx.search.search_examples(&req.query, req.lang.as_deref(), &options);
+    let total = examples.len();
+    examples.truncate(req.limit.unwrap_or(10));
+    if !req.query.trim().is_empty() {
+        ctx.track_search(&req.query, total);
+    }
+
+    let page = paginate(examples, &req.page, *ctx.max_response_bytes.read())?;
+    Ok(FindExamplesResponse {
+        examples: page.items,
+        total,
+        next_cursor: page.next_cursor,
+    })
+}
+
+// ============================================================================
+// Skill content statistics
+// ============================================================================
+
+/// Largest files listed in skill statistics.
+const LARGEST_FILES: usize = 5;
+
+/// Statistics for one markdown file of a skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileStats {
+    /// File path, relative to the skill directory.
+    pub file: String,
+    /// Sub-skill owning the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+    /// Word, heading, code block, and token counts.
+    #[serde(flatten)]
+    pub stats: markdown::TextStats,
+}
+
+/// Size of one file of a skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileSize {
+    /// File path, relative to the skill directory.
+    pub file: String,
+    /// Size in bytes.
+    pub bytes: u64,
+}
+
+/// Content statistics for a skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillStats {
+    /// Skill name.
+    pub skill: String,
+    /// Totals across every markdown file.
+    pub total: markdown::TextStats,
+    /// Per-file counts, SKILL.md first, then other files by path.
+    pub files: Vec<FileStats>,
+    /// Number of files under `references/`.
+    pub references: usize,
+    /// Largest files of any kind, biggest first.
+    pub largest_files: Vec<FileSize>,
+}
+
+/// Compute content statistics for a skill, or None if it isn't indexed.
+pub fn skill_stats(ctx: &ServiceContext, name: &str) -> Option<SkillStats> {
+    if !ctx.indexer.skill_exists(name) {
+        return None;
+    }
+    let name = &ctx.canonical_name(name);
+
+    let skill_dir = ctx.indexer.skill_dir(name);
+    let content_index = ctx.indexer.get_content_index();
+    let mut entries = content_index.get_domain_entries(name);
+    entries.sort_by_key(|e| (e.file != "SKILL.md", e.file.clone()));
+
+    let mut total = markdown::TextStats::default();
+    let files: Vec<FileStats> = entries
+        .into_iter()
+        .map(|entry| {
+            let content = std::fs::read_to_string(skill_dir.join(&entry.file)).unwrap_or_default();
+            let stats = markdown::text_stats(&content);
+            total.add(&stats);
+            FileStats {
+                file: entry.file.clone(),
+                sub_skill: entry.sub_skill.clone(),
+                stats,
+            }
+        })
+        .collect();
+
+    let mut sizes: Vec<FileSize> = walkdir::WalkDir::new(&skill_dir)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| FileSize {
+            file: e
+                .path()
+                .strip_prefix(&skill_dir)
+                .unwrap_or(e.path())
+                .to_string_lossy()
+                .replace('\\', "/"),
+            bytes: e.metadata().map(|m| m.len()).unwrap_or(0),
+        })
+        .collect();
+    let references = sizes.iter().filter(|f| f.file.starts_with("references/")).count();
+    sizes.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.file.cmp(&b.file)));
+    sizes.truncate(LARGEST_FILES);
+
+    Some(SkillStats {
+        skill: name.to_string(),
+        total,
+        files,
+        references,
+        largest_files: sizes,
+    })
 }
 
 // ============================================================================
@@ -150,42 +999,77 @@ pub fn get_sub_skill(
 pub struct GetSkillsBatchRequest {
     /// List of skill/sub-skill requests to process.
     pub requests: Vec<BatchRequest>,
+    /// Serve skills in this language where they have a translation.
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Page of the requests to process.
+    #[serde(flatten)]
+    pub page: PageRequest,
 }
 
 /// Response for get_skills_batch tool.
 #[derive(Debug, Serialize)]
 pub struct GetSkillsBatchResponse {
-    /// Results for each requested skill.
+    /// Results for each requested skill in the page.
     pub results: Vec<BatchResponseItem>,
+    /// Cursor of the next page, if requests remain; repeat the same
+    /// requests with it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Load multiple skills in a single request.
-pub fn get_skills_batch(ctx: &ServiceContext, req: GetSkillsBatchRequest) -> GetSkillsBatchResponse {
+pub fn get_skills_batch(
+    ctx: &ServiceContext,
+    req: GetSkillsBatchRequest,
+) -> Result<GetSkillsBatchResponse, ErrorResponse> {
     ctx.track_tool_call("get_skills_batch");
 
-    let results: Vec<BatchResponseItem> = req
+    // Requests past the page are never loaded
+    let results = req
         .requests
         .into_iter()
-        .map(|r| {
+        .map(|mut r| {
+            if !ctx.is_visible(&r.domain) {
+                return BatchResponseItem::error(r.domain.clone(), ctx.skill_not_found(&r.domain).error);
+            }
+            r.domain = ctx.canonical_name(&r.domain);
             if let Some(sub_skill) = r.sub_skill {
                 ctx.track_skill_load(&format!("{}:{}", r.domain, sub_skill));
+                ctx.track_skill_read(&r.domain, MCP_READER);
 
                 match ctx.indexer.read_sub_skill_content(&r.domain, &sub_skill) {
-                    Ok(content) => BatchResponseItem::SubSkill(content),
+                    Ok(mut content) => {
+                        ctx.append_deprecation_notice(&r.domain, &mut content.content);
+                        ctx.track_tokens_served(
+                            &format!("{}:{}", r.domain, sub_skill),
+                            MCP_READER,
+                            &content.content,
+                        );
+                        BatchResponseItem::SubSkill(content)
+                    }
                     Err(e) => BatchResponseItem::error(r.domain, e.to_string()),
                 }
             } else {
                 ctx.track_skill_load(&r.domain);
-
-                match ctx.indexer.read_skill_content(&r.domain) {
-                    Ok(content) => BatchResponseItem::Skill(content),
+                ctx.track_skill_read(&r.domain, MCP_READER);
+
+                match ctx.indexer.read_skill_content_in(&r.domain, req.lang.as_deref()) {
+                    Ok(mut content) => {
+                        ctx.append_deprecation_notice(&r.domain, &mut content.content);
+                        ctx.track_tokens_served(&r.domain, MCP_READER, &content.content);
+                        BatchResponseItem::Skill(content)
+                    }
                     Err(e) => BatchResponseItem::error(r.domain, e.to_string()),
                 }
             }
-        })
-        .collect();
+        });
+    let page = paginate(results, &req.page, *ctx.max_response_bytes.read())?;
 
-    GetSkillsBatchResponse { results }
+    Ok(GetSkillsBatchResponse {
+        results: page.items,
+        next_cursor: page.next_cursor,
+    })
 }
 
 // ============================================================================
@@ -195,29 +1079,45 @@ pub fn get_skills_batch(ctx: &ServiceContext, req: GetSkillsBatchRequest) -> Get
 /// Request for search_skills tool.
 #[derive(Debug, Deserialize)]
 pub struct SearchSkillsRequest {
-    /// Search query string.
+    /// Search query; supports phrases, `tag:`/`domain:` filters,
+    /// `-excluded` terms, and `AND`/`OR` grouping.
     pub query: String,
     /// Maximum number of results to return.
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Only return skills in this collection.
+    #[serde(default)]
+    pub collection: Option<String>,
+    /// Only return skills in this natural language (e.g. `en`).
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Include archived skills.
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Page of the results to return.
+    #[serde(flatten)]
+    pub page: PageRequest,
 }
 
 /// Search skills by metadata.
-pub fn search_skills(ctx: &ServiceContext, req: SearchSkillsRequest) -> SearchResults {
+pub fn search_skills(ctx: &ServiceContext, req: SearchSkillsRequest) -> Result<SearchResults, ErrorResponse> {
     ctx.track_tool_call("search_skills");
 
     let options = SearchOptions {
         limit: req.limit.or(Some(10)),
+        collection: req.collection,
+        language: req.lang,
+        include_archived: req.include_archived,
+        key: ctx.mcp_key.read().clone(),
         ..Default::default()
     };
 
-    let results = ctx.search.search_skills(&req.query, options);
+    let mut results = ctx.search.search_skills(&req.query, options);
+    ctx.search.apply_fallback(&mut results);
 
-    ctx.stats
-        .write()
-        .record_search(req.query, results.total_matches);
+    ctx.track_search(&req.query, results.total_matches);
 
-    results
+    ctx.page_results(results, &req.page)
 }
 
 // ============================================================================
@@ -227,29 +1127,336 @@ pub fn search_skills(ctx: &ServiceContext, req: SearchSkillsRequest) -> SearchRe
 /// Request for search_content tool.
 #[derive(Debug, Deserialize)]
 pub struct SearchContentRequest {
-    /// Search query string for full-text search.
+    /// Full-text search query, in the same language as `search_skills`.
     pub query: String,
     /// Maximum number of results to return.
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Only return content from skills in this collection.
+    #[serde(default)]
+    pub collection: Option<String>,
+    /// Only return content in this natural language (e.g. `en`).
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Only return content from these file types (e.g. `markdown`, `py`).
+    #[serde(default)]
+    pub file_types: Option<Vec<String>>,
+    /// Include content of archived skills.
+    #[serde(default)]
+    pub include_archived: bool,
+    /// Page of the results to return.
+    #[serde(flatten)]
+    pub page: PageRequest,
 }
 
 /// Search content by full-text matching.
-pub fn search_content(ctx: &ServiceContext, req: SearchContentRequest) -> SearchResults {
+pub fn search_content(ctx: &ServiceContext, req: SearchContentRequest) -> Result<SearchResults, ErrorResponse> {
     ctx.track_tool_call("search_content");
 
     let options = SearchOptions {
         limit: req.limit.or(Some(10)),
+        collection: req.collection,
+        language: req.lang,
+        file_types: req.file_types,
+        include_archived: req.include_archived,
+        key: ctx.mcp_key.read().clone(),
         ..Default::default()
     };
 
-    let results = ctx.search.search_content(&req.query, options);
+    let mut results = ctx.search.search_content(&req.query, options);
+    ctx.search.apply_fallback(&mut results);
+
+    ctx.track_search(&req.query, results.total_matches);
+
+    ctx.page_results(results, &req.page)
+}
+
+// ============================================================================
+// Tool: search_for_task
+// ============================================================================
+
+/// Request for search_for_task tool.
+#[derive(Debug, Deserialize)]
+pub struct SearchForTaskRequest {
+    /// Free-form task description, e.g. the agent's current goal.
+    pub task: String,
+    /// Maximum number of results to return.
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Page of the results to return.
+    #[serde(flatten)]
+    pub page: PageRequest,
+}
 
-    ctx.stats
-        .write()
-        .record_search(req.query, results.total_matches);
+/// Find skills for a long or vague task description using multi-query fusion.
+pub fn search_for_task(
+    ctx: &ServiceContext,
+    req: SearchForTaskRequest,
+) -> Result<FusedSearchResults, ErrorResponse> {
+    ctx.track_tool_call("search_for_task");
 
-    results
+    let options = SearchOptions {
+        limit: req.limit.or(Some(10)),
+        key: ctx.mcp_key.read().clone(),
+        ..Default::default()
+    };
+
+    let mut results = ctx.search.search_fused(&req.task, options);
+    ctx.search.apply_fallback_if_empty(&mut results.results);
+
+    ctx.track_search(&req.task, results.results.total_matches);
+
+    results.results = ctx.page_results(results.results, &req.page)?;
+    Ok(results)
+}
+
+// ============================================================================
+// Tool: get_bundle
+// ============================================================================
+
+/// Request for get_bundle tool.
+#[derive(Debug, Deserialize)]
+pub struct GetBundleRequest {
+    /// Bundle name, as defined in `_bundles.json`.
+    pub name: String,
+    /// Token budget overriding the bundle's own.
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+}
+
+/// Load a bundle's skills and sub-skills as one document, dependencies
+/// first, within its token budget.
+pub fn get_bundle(ctx: &ServiceContext, req: GetBundleRequest) -> Result<BundleContent, ErrorResponse> {
+    ctx.track_tool_call("get_bundle");
+
+    let bundle = Bundles::new(ctx.indexer.skills_dir())
+        .get(&req.name)
+        .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))?;
+    let content = bundle.load(&ctx.indexer, req.max_tokens, |skill| ctx.is_visible(skill));
+    ctx.track_bundle_served(&content, MCP_READER);
+    Ok(content)
+}
+
+// ============================================================================
+// Tool: build_context
+// ============================================================================
+
+/// Request for build_context tool.
+#[derive(Debug, Deserialize)]
+pub struct BuildContextRequest {
+    /// Free-form task description the context is for.
+    pub task: String,
+    /// Token budget of the assembled document.
+    pub max_tokens: u64,
+    /// Maximum number of skills to include.
+    #[serde(default)]
+    pub max_skills: Option<usize>,
+    /// Consider deprecated skills too.
+    #[serde(default)]
+    pub include_deprecated: bool,
+}
+
+/// Assemble the skills and sub-skills most relevant to a task into one
+/// document with source headers, reporting what was left out and why.
+pub fn build_context(ctx: &ServiceContext, req: BuildContextRequest) -> Result<ContextPack, ErrorResponse> {
+    ctx.track_tool_call("build_context");
+
+    if req.task.trim().is_empty() {
+        return Err(ErrorResponse::new(ErrorCode::InvalidRequest, "Task must not be empty"));
+    }
+    let options = ContextOptions {
+        max_tokens: req.max_tokens,
+        max_skills: req.max_skills.unwrap_or(DEFAULT_MAX_SKILLS),
+        include_deprecated: req.include_deprecated,
+    };
+    let search = SearchOptions {
+        limit: Some(options.max_skills * 2),
+        key: ctx.mcp_key.read().clone(),
+        ..Default::default()
+    };
+    let results = ctx.search.search_fused(&req.task, search).results;
+    ctx.track_search(&req.task, results.total_matches);
+
+    let pack = context::build(&ctx.indexer, &req.task, &results.results, &options, |skill| {
+        ctx.is_visible(skill)
+    });
+    ctx.track_context_served(&pack, MCP_READER);
+    Ok(pack)
+}
+
+// ============================================================================
+// Tool: rename_skill
+// ============================================================================
+
+/// Request for rename_skill tool.
+#[derive(Debug, Deserialize)]
+pub struct RenameSkillRequest {
+    /// Current skill name.
+    pub name: String,
+    /// New skill name.
+    pub new_name: String,
+    /// Rewrite links in other skills that point at the old name.
+    #[serde(default = "default_rewrite_links")]
+    pub rewrite_links: bool,
+}
+
+fn default_rewrite_links() -> bool {
+    true
+}
+
+/// Rename a skill, updating dependents and (optionally) links, then reload.
+pub fn rename_skill(
+    ctx: &ServiceContext,
+    req: RenameSkillRequest,
+) -> Result<RenameReport, ErrorResponse> {
+    ctx.track_tool_call("rename_skill");
+
+    // Only indexed skills the caller can see; their names are safe paths
+    let meta = ctx
+        .indexer
+        .get_skill_meta(&req.name)
+        .filter(|_| ctx.is_visible(&req.name))
+        .ok_or_else(|| ctx.skill_not_found(&req.name))?;
+    let req = RenameSkillRequest { name: meta.name, ..req };
+
+    if let Some(banner) = ctx.maintenance.banner() {
+        return Err(ErrorResponse::new(ErrorCode::Maintenance, banner.message));
+    }
+    if ctx.indexer.is_read_only(&req.name) {
+        return Err(ErrorResponse::new(ErrorCode::ReadOnly, format!(
+            "Skill '{}' is in a read-only skills root",
+            req.name
+        )));
+    }
+
+    // Renames rewrite references across the root, so it's journaled whole
+    let root = ctx.indexer.skill_root_dir(&req.name);
+    let entry = AuditEntry::new(MCP_READER, AuditAction::Rename, &req.name);
+    let report = {
+        let _guard = ctx.edits.lock();
+        ctx.begin_mutation(&entry);
+        let report = crate::lifecycle::rename_skill(&root, &req.name, &req.new_name, req.rewrite_links)
+            .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))?;
+        ctx.record_mutation_in(
+            entry.with_summary(format!(
+                "renamed to {}; {} links rewritten",
+                report.new_name, report.links_rewritten
+            )),
+            &[root],
+        );
+        report
+    };
+
+    ctx.indexer
+        .reload()
+        .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))?;
+    ctx.receipts.write().forget(&req.name);
+
+    Ok(report)
+}
+
+// ============================================================================
+// Tools: archive_skill, unarchive_skill
+// ============================================================================
+
+/// Request for archive_skill and unarchive_skill tools.
+#[derive(Debug, Deserialize)]
+pub struct ArchiveSkillRequest {
+    /// Skill name or alias.
+    pub name: String,
+}
+
+/// Hide a skill from listings and search without deleting it.
+pub fn archive_skill(ctx: &ServiceContext, req: ArchiveSkillRequest) -> Result<SkillMeta, ErrorResponse> {
+    ctx.track_tool_call("archive_skill");
+    set_archived(ctx, &req.name, true)
+}
+
+/// Make an archived skill visible again.
+pub fn unarchive_skill(ctx: &ServiceContext, req: ArchiveSkillRequest) -> Result<SkillMeta, ErrorResponse> {
+    ctx.track_tool_call("unarchive_skill");
+    set_archived(ctx, &req.name, false)
+}
+
+fn set_archived(ctx: &ServiceContext, name: &str, archived: bool) -> Result<SkillMeta, ErrorResponse> {
+    if let Some(banner) = ctx.maintenance.banner() {
+        return Err(ErrorResponse::new(ErrorCode::Maintenance, banner.message));
+    }
+    let name = ctx
+        .indexer
+        .get_skill_meta(name)
+        .map(|meta| meta.name)
+        .ok_or_else(|| ctx.skill_not_found(name))?;
+    if ctx.indexer.is_read_only(&name) {
+        return Err(ErrorResponse::new(ErrorCode::ReadOnly, format!(
+            "Skill '{}' is in a read-only skills root",
+            name
+        )));
+    }
+
+    let action = if archived {
+        AuditAction::Archive
+    } else {
+        AuditAction::Unarchive
+    };
+    let entry = AuditEntry::new(MCP_READER, action, &name);
+    let changed = {
+        let _guard = ctx.edits.lock();
+        ctx.begin_mutation(&entry);
+        let changed = crate::lifecycle::set_archived(&ctx.indexer.skill_dir(&name), archived)
+            .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))?;
+        if changed {
+            ctx.record_mutation(entry);
+        }
+        changed
+    };
+    if changed {
+        ctx.indexer
+            .update_skill(&name)
+            .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))?;
+    }
+
+    ctx.indexer
+        .get_skill_meta(&name)
+        .ok_or_else(|| ctx.skill_not_found(&name))
+}
+
+// ============================================================================
+// Tool: run_skill_script
+// ============================================================================
+
+/// Request for run_skill_script tool.
+#[derive(Debug, Deserialize)]
+pub struct RunSkillScriptRequest {
+    /// Skill name or alias.
+    pub name: String,
+    /// Script path relative to the skill's `scripts/` directory.
+    pub script: String,
+    /// Arguments passed to the script.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Run an allow-listed script from a skill's `scripts/` directory,
+/// capturing its output. Off unless enabled under `[scripts]`.
+pub fn run_skill_script(ctx: &ServiceContext, req: RunSkillScriptRequest) -> Result<ScriptOutput, ErrorResponse> {
+    ctx.track_tool_call("run_skill_script");
+
+    let config = ctx.scripts.read().clone();
+    if !config.enabled {
+        let e = ScriptError::Disabled;
+        return Err(ErrorResponse::new(e.code(), e.to_string()));
+    }
+    let name = ctx
+        .indexer
+        .get_skill_meta(&req.name)
+        .filter(|meta| ctx.is_visible(&meta.name))
+        .map(|meta| meta.name)
+        .ok_or_else(|| ctx.skill_not_found(&req.name))?;
+
+    tracing::info!(skill = %name, script = %req.script, "Running skill script");
+    scripts::run_script(&config, &name, &ctx.indexer.skill_dir(&name), &req.script, &req.args)
+        .map_err(|e| ErrorResponse::new(e.code(), e.to_string()))
 }
 
 // ============================================================================
@@ -318,6 +1525,7 @@ pub fn validate_skills_tool(ctx: &ServiceContext) -> ValidationResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::write_skill;
     use std::fs;
     use tempfile::TempDir;
 
@@ -325,14 +1533,12 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         // Create a test skill
-        let skill_dir = temp_dir.path().join("test-skill");
-        fs::create_dir_all(&skill_dir).unwrap();
-        fs::write(
-            skill_dir.join("_meta.json"),
+        write_skill(
+            temp_dir.path(),
+            "test-skill",
             r#"{"name": "test-skill", "description": "A test skill"}"#,
-        )
-        .unwrap();
-        fs::write(skill_dir.join("SKILL.md"), "# Test Skill\n\nContent here.").unwrap();
+            "# Test Skill\n\nContent here.\n\n## TL;DR\n\nUse the test skill.",
+        );
 
         let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
         indexer.reload().unwrap();
@@ -346,7 +1552,7 @@ mod tests {
     fn test_list_skills() {
         let (_temp, ctx) = create_test_context();
 
-        let response = list_skills(&ctx);
+        let response = list_skills(&ctx, ListSkillsRequest::default()).unwrap();
         assert_eq!(response.total, 1);
         assert_eq!(response.skills[0].name, "test-skill");
     }
@@ -357,6 +1563,10 @@ mod tests {
 
         let req = GetSkillRequest {
             name: "test-skill".to_string(),
+            minify: false,
+            cursor: None,
+            lang: None,
+            variables: HashMap::new(),
         };
 
         let response = get_skill(&ctx, req).unwrap();
@@ -364,6 +1574,32 @@ mod tests {
         assert!(response.content.contains("Test Skill"));
     }
 
+    #[test]
+    fn test_get_skill_includes_dependencies() {
+        let (temp, ctx) = create_test_context();
+
+        write_skill(
+            temp.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Forms", "depends_on": ["test-skill"]}"#,
+            "# Forms",
+        );
+        ctx.indexer.reload().unwrap();
+
+        let response = get_skill(
+            &ctx,
+            GetSkillRequest {
+                name: "forms".to_string(),
+                minify: false,
+                cursor: None,
+                lang: None,
+                variables: HashMap::new(),
+            },
+        )
+        .unwrap();
+        assert_eq!(response.depends_on, vec!["test-skill"]);
+    }
+
     #[test]
     fn test_search_skills() {
         let (_temp, ctx) = create_test_context();
@@ -371,9 +1607,13 @@ mod tests {
         let req = SearchSkillsRequest {
             query: "test".to_string(),
             limit: None,
+            collection: None,
+            lang: None,
+            include_archived: false,
+            page: PageRequest::default(),
         };
 
-        let response = search_skills(&ctx, req);
+        let response = search_skills(&ctx, req).unwrap();
         assert!(!response.is_empty());
     }
 
@@ -382,12 +1622,16 @@ mod tests {
         let (_temp, ctx) = create_test_context();
 
         // Make some calls
-        list_skills(&ctx);
-        list_skills(&ctx);
+        list_skills(&ctx, ListSkillsRequest::default()).unwrap();
+        list_skills(&ctx, ListSkillsRequest::default()).unwrap();
         get_skill(
             &ctx,
             GetSkillRequest {
                 name: "test-skill".to_string(),
+                minify: false,
+                cursor: None,
+                lang: None,
+                variables: HashMap::new(),
             },
         )
         .unwrap();
@@ -397,4 +1641,330 @@ mod tests {
         assert_eq!(*stats.tool_calls.get("get_skill").unwrap(), 1);
         assert_eq!(*stats.skill_loads.get("test-skill").unwrap(), 1);
     }
+
+    #[test]
+    fn test_get_quick_answer() {
+        let (_temp, ctx) = create_test_context();
+
+        let response = get_quick_answer(
+            &ctx,
+            GetQuickAnswerRequest {
+                name: "test-skill".to_string(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(response.quick_answer.as_deref(), Some("Use the test skill."));
+        assert!(get_quick_answer(
+            &ctx,
+            GetQuickAnswerRequest {
+                name: "missing".to_string(),
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_find_examples() {
+        let (temp, ctx) = create_test_context();
+        fs::write(
+            temp.path().join("test-skill/SKILL.md"),
+            "# Test Skill\n\nRun the tests.\n\n## Running\n\n```sh\ncargo test\n```\n",
+        )
+        .unwrap();
+        ctx.indexer.reload().unwrap();
+        let request = |query: &str, skill: Option<&str>| FindExamplesRequest {
+            query: query.to_string(),
+            lang: Some("bash".to_string()),
+            skill: skill.map(str::to_string),
+            limit: None,
+            include_archived: false,
+            page: PageRequest::default(),
+        };
+
+        let response = find_examples(&ctx, request("test", None)).unwrap();
+        assert_eq!(response.total, 1);
+        assert_eq!(response.examples[0].domain, "test-skill");
+        assert_eq!(response.examples[0].block.heading.as_deref(), Some("Running"));
+
+        assert_eq!(find_examples(&ctx, request("tests", None)).unwrap().total, 0);
+        assert!(find_examples(&ctx, request("test", Some("missing"))).is_err());
+    }
+
+    #[test]
+    fn test_archive_skill() {
+        let (_temp, ctx) = create_test_context();
+        let request = || ArchiveSkillRequest {
+            name: "test-skill".to_string(),
+        };
+
+        assert!(archive_skill(&ctx, request()).unwrap().archived);
+        assert_eq!(list_skills(&ctx, ListSkillsRequest::default()).unwrap().total, 0);
+        let all = list_skills(&ctx, ListSkillsRequest { include_archived: true, ..Default::default() }).unwrap();
+        assert!(all.skills[0].archived);
+
+        let search = |include_archived| SearchContentRequest {
+            query: "content".to_string(),
+            limit: None,
+            collection: None,
+            lang: None,
+            file_types: None,
+            include_archived,
+            page: PageRequest::default(),
+        };
+        assert_eq!(search_content(&ctx, search(false)).unwrap().total_matches, 0);
+        assert_eq!(search_content(&ctx, search(true)).unwrap().total_matches, 1);
+
+        assert!(!unarchive_skill(&ctx, request()).unwrap().archived);
+        assert_eq!(list_skills(&ctx, ListSkillsRequest::default()).unwrap().total, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_skill_script() {
+        let (temp, ctx) = create_test_context();
+        fs::create_dir_all(temp.path().join("test-skill/scripts")).unwrap();
+        fs::write(temp.path().join("test-skill/scripts/hello.sh"), "echo \"hello $1\"").unwrap();
+        let request = |name: &str| RunSkillScriptRequest {
+            name: name.to_string(),
+            script: "hello.sh".to_string(),
+            args: vec!["there".to_string()],
+        };
+
+        let error = run_skill_script(&ctx, request("test-skill")).unwrap_err();
+        assert_eq!(error.code, ErrorCode::Disabled);
+
+        *ctx.scripts.write() = ScriptsConfig {
+            enabled: true,
+            allow: vec!["test-skill/*.sh".to_string()],
+            ..Default::default()
+        };
+        let output = run_skill_script(&ctx, request("test-skill")).unwrap();
+        assert_eq!(output.stdout, "hello there\n");
+        assert_eq!(output.exit_code, Some(0));
+        assert_eq!(run_skill_script(&ctx, request("nope")).unwrap_err().code, ErrorCode::SkillNotFound);
+    }
+
+    #[test]
+    fn test_get_bundle() {
+        let (temp, ctx) = create_test_context();
+        fs::write(
+            temp.path().join(crate::bundles::BUNDLES_FILE),
+            r#"{"testing": {"items": ["test-skill", "missing"]}}"#,
+        )
+        .unwrap();
+
+        let request = |name: &str| GetBundleRequest {
+            name: name.to_string(),
+            max_tokens: None,
+        };
+        let bundle = get_bundle(&ctx, request("testing")).unwrap();
+        assert!(bundle.content.starts_with("# Test Skill"));
+        assert_eq!(bundle.missing, vec!["missing"]);
+        assert_eq!(ctx.receipts.read().readers("test-skill").total_reads, 1);
+        assert!(get_bundle(&ctx, request("nope")).is_err());
+    }
+
+    #[test]
+    fn test_build_context() {
+        let (_temp, ctx) = create_test_context();
+        let request = |task: &str, max_tokens: u64| BuildContextRequest {
+            task: task.to_string(),
+            max_tokens,
+            max_skills: None,
+            include_deprecated: false,
+        };
+
+        let pack = build_context(&ctx, request("write a test skill", 1000)).unwrap();
+        assert_eq!(pack.parts[0].source, "test-skill/SKILL.md");
+        assert!(pack.content.starts_with("<!-- source: test-skill/SKILL.md"));
+        assert!(pack.content.contains("# Test Skill"));
+        assert_eq!(ctx.receipts.read().readers("test-skill").total_reads, 1);
+
+        let pack = build_context(&ctx, request("write a test skill", 1)).unwrap();
+        assert!(pack.parts.is_empty());
+        assert_eq!(pack.excluded[0].reason, crate::context::ExclusionReason::OverBudget);
+
+        assert!(build_context(&ctx, request(" ", 1000)).is_err());
+    }
+
+    #[test]
+    fn test_paginated_tools() {
+        let (temp, ctx) = create_test_context();
+        write_skill(temp.path(), "forms", r#"{"name": "forms", "description": "Form test patterns"}"#, "# Forms\n");
+        ctx.indexer.reload().unwrap();
+
+        let page = |cursor: Option<String>| ListSkillsRequest {
+            page: PageRequest { cursor, page_size: Some(1) },
+            ..Default::default()
+        };
+        let first = list_skills(&ctx, page(None)).unwrap();
+        assert_eq!((first.skills[0].name.as_str(), first.total), ("forms", 2));
+        let second = list_skills(&ctx, page(first.next_cursor)).unwrap();
+        assert_eq!(second.skills[0].name, "test-skill");
+        assert!(second.next_cursor.is_none());
+        assert!(list_skills(&ctx, page(Some("bogus".to_string()))).is_err());
+
+        // Over the size limit, content continues where the last part ended
+        *ctx.max_response_bytes.write() = Some(20);
+        let request = |cursor| GetSkillRequest {
+            name: "test-skill".to_string(),
+            minify: false,
+            cursor,
+            lang: None,
+            variables: HashMap::new(),
+        };
+        let mut content = String::new();
+        let mut cursor = None;
+        loop {
+            let part = get_skill(&ctx, request(cursor)).unwrap();
+            assert!(part.content.len() <= 20);
+            content.push_str(&part.content);
+            cursor = part.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(content, "# Test Skill\n\nContent here.\n\n## TL;DR\n\nUse the test skill.");
+
+        let search = search_skills(
+            &ctx,
+            SearchSkillsRequest {
+                query: "test".to_string(),
+                limit: None,
+                collection: None,
+                lang: None,
+                include_archived: false,
+                page: PageRequest::default(),
+            },
+        )
+        .unwrap();
+        assert_eq!((search.results.len(), search.total_matches), (1, 2));
+        assert!(search.next_cursor.is_some());
+    }
+
+    #[test]
+    fn test_private_skill() {
+        let (temp, ctx) = create_test_context();
+        write_skill(
+            temp.path(),
+            "billing",
+            &format!(
+                r#"{{"name": "billing", "description": "Billing", "visibility": "private",
+                    "allowed_keys": ["key:{}"]}}"#,
+                key_fingerprint("team-a-key")
+            ),
+            "# Billing",
+        );
+        ctx.indexer.reload().unwrap();
+        let request = || GetQuickAnswerRequest {
+            name: "billing".to_string(),
+        };
+
+        assert_eq!(list_skills(&ctx, ListSkillsRequest::default()).unwrap().total, 1);
+        assert!(get_quick_answer(&ctx, request()).is_err());
+        assert!(crate::mcp::resources::read_resource(&ctx, "skill://billing").is_err());
+
+        ctx.set_mcp_key("team-a-key");
+        assert_eq!(list_skills(&ctx, ListSkillsRequest::default()).unwrap().total, 2);
+        assert_eq!(get_quick_answer(&ctx, request()).unwrap().name, "billing");
+    }
+
+    #[test]
+    fn test_rename_skill_checks_names() {
+        let (temp, ctx) = create_test_context();
+        let outside_name = format!("../{}-outside", temp.path().file_name().unwrap().to_string_lossy());
+        let outside = temp.path().join(&outside_name);
+        let request = |name: &str, new_name: &str| RenameSkillRequest {
+            name: name.to_string(),
+            new_name: new_name.to_string(),
+            rewrite_links: false,
+        };
+
+        // A skill outside the root is not found, even if it exists on disk
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("_meta.json"), r#"{"name": "outside", "description": "x"}"#).unwrap();
+        let error = rename_skill(&ctx, request(&outside_name, "moved")).unwrap_err();
+        assert_eq!(error.code, ErrorCode::SkillNotFound);
+        fs::remove_dir_all(&outside).unwrap();
+
+        let error = rename_skill(&ctx, request("test-skill", "../escaped")).unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidRequest);
+        assert!(temp.path().join("test-skill").is_dir());
+
+        // Private skills can't be renamed by callers who can't see them
+        write_skill(
+            temp.path(),
+            "billing",
+            &format!(
+                r#"{{"name": "billing", "description": "Billing", "visibility": "private",
+                    "allowed_keys": ["key:{}"]}}"#,
+                key_fingerprint("team-a-key")
+            ),
+            "# Billing",
+        );
+        ctx.indexer.reload().unwrap();
+        let error = rename_skill(&ctx, request("billing", "payments")).unwrap_err();
+        assert_eq!(error.code, ErrorCode::SkillNotFound);
+        assert!(temp.path().join("billing").is_dir());
+
+        ctx.set_mcp_key("team-a-key");
+        assert_eq!(rename_skill(&ctx, request("billing", "payments")).unwrap().new_name, "payments");
+    }
+
+    #[test]
+    fn test_get_skill_with_variables() {
+        let (temp, ctx) = create_test_context();
+
+        write_skill(
+            temp.path(),
+            "deploy",
+            r#"{"name": "deploy", "description": "Deploy", "variables": [
+                {"name": "env", "required": true, "options": ["staging", "prod"]}
+            ]}"#,
+            "Deploy to {{env}}.",
+        );
+        ctx.indexer.reload().unwrap();
+
+        let schema = get_skill_arguments(
+            &ctx,
+            GetSkillArgumentsRequest {
+                name: "deploy".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["name", "env"]));
+        assert_eq!(
+            schema["properties"]["env"]["enum"],
+            serde_json::json!(["staging", "prod"])
+        );
+
+        let req: GetSkillRequest =
+            serde_json::from_value(serde_json::json!({ "name": "deploy", "env": "prod" })).unwrap();
+        assert_eq!(get_skill(&ctx, req).unwrap().content, "Deploy to prod.");
+
+        let req: GetSkillRequest =
+            serde_json::from_value(serde_json::json!({ "name": "deploy" })).unwrap();
+        assert!(get_skill(&ctx, req).is_err());
+    }
+
+    #[test]
+    fn test_maintenance_mode() {
+        let (temp, ctx) = create_test_context();
+        ctx.maintenance.enable(Some("Restructuring".to_string()));
+
+        let result = ctx.tool_result(&list_skills(&ctx, ListSkillsRequest::default()).unwrap());
+        assert_eq!(result["maintenance"]["message"], "Restructuring");
+
+        let req = RenameSkillRequest {
+            name: "test-skill".to_string(),
+            new_name: "renamed".to_string(),
+            rewrite_links: true,
+        };
+        assert_eq!(rename_skill(&ctx, req).unwrap_err().error, "Restructuring");
+        assert!(temp.path().join("test-skill").exists());
+
+        ctx.maintenance.disable();
+        assert!(ctx.tool_result(&list_skills(&ctx, ListSkillsRequest::default()).unwrap()).get("maintenance").is_none());
+    }
 }
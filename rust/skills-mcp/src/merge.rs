@@ -0,0 +1,467 @@
+//! Three-way merge of skills imported again over local edits.
+//!
+//! Each import records the files it wrote as the skill's base under
+//! `_imports/<skill>/base`. Importing the skill again in merge mode compares,
+//! file by file, the local copy, the incoming copy, and that base:
+//!
+//! - unchanged locally: the incoming file is taken (added, updated, or
+//!   removed)
+//! - unchanged upstream: the local edit is kept
+//! - the same on both sides: nothing to do
+//! - changed differently on both sides: a conflict
+//!
+//! Conflicting files keep their local content. The incoming side is held
+//! under `_imports/<skill>/incoming` until each conflict is resolved with
+//! [`Imports::resolve`], which takes the local file, the incoming file, or
+//! merged content written by hand. Without a recorded base, every file on
+//! both sides that differs is a conflict.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::models::ErrorCode;
+
+/// Files of a skill by path relative to its directory, with `/` separators.
+pub type SkillFiles = BTreeMap<String, Vec<u8>>;
+
+/// How the two sides of a conflicting file differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// Changed, or added, differently on both sides.
+    Modified,
+    /// Removed locally but changed upstream.
+    DeletedLocally,
+    /// Changed locally but removed upstream.
+    DeletedIncoming,
+}
+
+/// A file changed on both sides.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeConflict {
+    /// Path relative to the skill directory.
+    pub file: String,
+
+    /// How the sides differ.
+    pub kind: ConflictKind,
+}
+
+/// Outcome of merging an incoming skill into a local one.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergeReport {
+    /// Files added from the incoming skill.
+    pub added: Vec<String>,
+
+    /// Local files replaced by the incoming version.
+    pub updated: Vec<String>,
+
+    /// Local files removed because the incoming skill dropped them.
+    pub removed: Vec<String>,
+
+    /// Local edits kept because the file didn't change upstream.
+    pub kept: Vec<String>,
+
+    /// Files left for manual resolution, with their local content.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Pending conflicts of one skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillConflicts {
+    /// Skill name.
+    pub skill: String,
+
+    /// Unresolved files.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// How to resolve a conflicting file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep the local file as it is.
+    Local,
+    /// Take the incoming file, or remove the file if it was removed upstream.
+    Incoming,
+    /// Write this content.
+    Content(String),
+}
+
+/// Import records of a skills directory.
+pub struct Imports {
+    skills_dir: PathBuf,
+}
+
+impl Imports {
+    /// Directory (inside the skills directory) holding import records.
+    pub const DIR_NAME: &'static str = "_imports";
+
+    /// Import records of the given skills directory.
+    pub fn new(skills_dir: impl AsRef<Path>) -> Self {
+        Self {
+            skills_dir: skills_dir.as_ref().to_path_buf(),
+        }
+    }
+
+    fn record_dir(&self, name: &str) -> PathBuf {
+        self.skills_dir.join(Self::DIR_NAME).join(name)
+    }
+
+    fn conflicts_path(&self, name: &str) -> PathBuf {
+        self.record_dir(name).join("conflicts.json")
+    }
+
+    /// Record `files` as the base of future merges of `name`.
+    pub fn record(&self, name: &str, files: &SkillFiles) -> Result<(), MergeError> {
+        let dir = self.record_dir(name);
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        write_files(&dir.join("base"), files)
+    }
+
+    /// Merge `incoming` into the skill `name`, writing every file that
+    /// merges cleanly and recording the conflicts.
+    pub fn merge(&self, name: &str, incoming: &SkillFiles) -> Result<MergeReport, MergeError> {
+        let dir = self.record_dir(name);
+        let base = read_files(&dir.join("base"))?;
+        let skill_dir = self.skills_dir.join(name);
+        let local = read_files(&skill_dir)?;
+
+        let mut report = MergeReport::default();
+        let mut next_base = SkillFiles::new();
+        let mut held = SkillFiles::new();
+        let paths: BTreeSet<&String> = base.keys().chain(local.keys()).chain(incoming.keys()).collect();
+        for path in paths {
+            let (b, l, i) = (base.get(path), local.get(path), incoming.get(path));
+            if l == i || i == b {
+                if l != i {
+                    report.kept.push(path.clone());
+                }
+            } else if l == b {
+                match i {
+                    Some(bytes) => {
+                        write_file(&skill_dir.join(path), bytes)?;
+                        let list = if l.is_some() { &mut report.updated } else { &mut report.added };
+                        list.push(path.clone());
+                    }
+                    None => {
+                        fs::remove_file(skill_dir.join(path))?;
+                        report.removed.push(path.clone());
+                    }
+                }
+            } else {
+                let kind = match (l, i) {
+                    (None, _) => ConflictKind::DeletedLocally,
+                    (_, None) => ConflictKind::DeletedIncoming,
+                    _ => ConflictKind::Modified,
+                };
+                report.conflicts.push(MergeConflict { file: path.clone(), kind });
+                if let Some(bytes) = i {
+                    held.insert(path.clone(), bytes.clone());
+                }
+                // The base stays until the conflict is resolved
+                if let Some(bytes) = b {
+                    next_base.insert(path.clone(), bytes.clone());
+                }
+                continue;
+            }
+            if let Some(bytes) = i {
+                next_base.insert(path.clone(), bytes.clone());
+            }
+        }
+
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        write_files(&dir.join("base"), &next_base)?;
+        if !report.conflicts.is_empty() {
+            write_files(&dir.join("incoming"), &held)?;
+            self.write_conflicts(name, &report.conflicts)?;
+        }
+        Ok(report)
+    }
+
+    /// Pending conflicts of `name`.
+    pub fn conflicts(&self, name: &str) -> Result<Vec<MergeConflict>, MergeError> {
+        match fs::read_to_string(self.conflicts_path(name)) {
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Pending conflicts of every skill, sorted by skill name.
+    pub fn all_conflicts(&self) -> Result<Vec<SkillConflicts>, MergeError> {
+        let root = self.skills_dir.join(Self::DIR_NAME);
+        if !root.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut all = Vec::new();
+        // Depth 2 covers skills nested in a collection
+        for entry in WalkDir::new(&root).min_depth(1).max_depth(2).sort_by_file_name() {
+            let entry = entry.map_err(|e| MergeError::Io(e.into()))?;
+            if !entry.path().join("conflicts.json").is_file() {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(&root) else {
+                continue;
+            };
+            let skill = relative.to_string_lossy().replace('\\', "/");
+            let conflicts = self.conflicts(&skill)?;
+            if !conflicts.is_empty() {
+                all.push(SkillConflicts { skill, conflicts });
+            }
+        }
+        Ok(all)
+    }
+
+    /// Resolve the conflict on `file` of `name`, returning the conflicts
+    /// still pending.
+    pub fn resolve(&self, name: &str, file: &str, resolution: Resolution) -> Result<Vec<MergeConflict>, MergeError> {
+        let mut conflicts = self.conflicts(name)?;
+        let Some(position) = conflicts.iter().position(|c| c.file == file) else {
+            return Err(MergeError::NoConflict(format!("{}/{}", name, file)));
+        };
+
+        let dir = self.record_dir(name);
+        let held = dir.join("incoming").join(file);
+        let incoming = match fs::read(&held) {
+            Ok(bytes) => Some(bytes),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let target = self.skills_dir.join(name).join(file);
+        match resolution {
+            Resolution::Local => {}
+            Resolution::Incoming => match &incoming {
+                Some(bytes) => write_file(&target, bytes)?,
+                None if target.exists() => fs::remove_file(&target)?,
+                None => {}
+            },
+            Resolution::Content(content) => write_file(&target, content.as_bytes())?,
+        }
+
+        // The incoming side is now accounted for, so it becomes the base
+        let base = dir.join("base").join(file);
+        match &incoming {
+            Some(bytes) => write_file(&base, bytes)?,
+            None if base.exists() => fs::remove_file(&base)?,
+            None => {}
+        }
+        if incoming.is_some() {
+            fs::remove_file(&held)?;
+        }
+
+        conflicts.remove(position);
+        if conflicts.is_empty() {
+            fs::remove_file(self.conflicts_path(name))?;
+            let incoming_dir = dir.join("incoming");
+            if incoming_dir.exists() {
+                fs::remove_dir_all(incoming_dir)?;
+            }
+        } else {
+            self.write_conflicts(name, &conflicts)?;
+        }
+        Ok(conflicts)
+    }
+
+    fn write_conflicts(&self, name: &str, conflicts: &[MergeConflict]) -> Result<(), MergeError> {
+        let json = serde_json::to_string_pretty(conflicts)?;
+        fs::write(self.conflicts_path(name), json)?;
+        Ok(())
+    }
+}
+
+/// Read the files under `dir`, skipping hidden ones. A missing directory
+/// has no files.
+pub fn read_files(dir: &Path) -> Result<SkillFiles, MergeError> {
+    let mut files = SkillFiles::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    let entries = WalkDir::new(dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_string_lossy().starts_with('.'));
+    for entry in entries {
+        let entry = entry.map_err(|e| MergeError::Io(e.into()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        files.insert(relative.to_string_lossy().replace('\\', "/"), fs::read(entry.path())?);
+    }
+    Ok(files)
+}
+
+/// Write `files` under `dir`.
+pub fn write_files(dir: &Path, files: &SkillFiles) -> Result<(), MergeError> {
+    fs::create_dir_all(dir)?;
+    for (path, bytes) in files {
+        write_file(&dir.join(path), bytes)?;
+    }
+    Ok(())
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), MergeError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Whether `file` is a plain relative path that stays inside a skill.
+pub fn is_skill_file(file: &str) -> bool {
+    !file.is_empty() && Path::new(file).components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Errors that can occur while merging or resolving conflicts.
+#[derive(Debug, thiserror::Error)]
+pub enum MergeError {
+    /// The file has no pending conflict.
+    #[error("No merge conflict on {0}")]
+    NoConflict(String),
+
+    /// The conflict record is not valid.
+    #[error("Invalid conflict record: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    /// Filesystem error.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl MergeError {
+    /// Machine-readable code of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            MergeError::NoConflict(_) => ErrorCode::NotFound,
+            MergeError::Parse(_) => ErrorCode::InternalError,
+            MergeError::Io(_) => ErrorCode::IoError,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn files(entries: &[(&str, &str)]) -> SkillFiles {
+        entries.iter().map(|(p, c)| (p.to_string(), c.as_bytes().to_vec())).collect()
+    }
+
+    fn read(dir: &TempDir, path: &str) -> Option<String> {
+        fs::read_to_string(dir.path().join("forms").join(path)).ok()
+    }
+
+    /// Import v1, then edit locally.
+    fn setup() -> (TempDir, Imports) {
+        let temp_dir = TempDir::new().unwrap();
+        let v1 = files(&[
+            ("SKILL.md", "# Forms v1\n"),
+            ("guide.md", "guide v1\n"),
+            ("local-only.md", "edit me\n"),
+            ("dropped.md", "old\n"),
+            ("notes.md", "notes\n"),
+        ]);
+        write_files(&temp_dir.path().join("forms"), &v1).unwrap();
+        let imports = Imports::new(temp_dir.path());
+        imports.record("forms", &v1).unwrap();
+
+        fs::write(temp_dir.path().join("forms/local-only.md"), "edited\n").unwrap();
+        fs::write(temp_dir.path().join("forms/guide.md"), "guide local\n").unwrap();
+        fs::remove_file(temp_dir.path().join("forms/notes.md")).unwrap();
+        (temp_dir, imports)
+    }
+
+    fn v2() -> SkillFiles {
+        files(&[
+            ("SKILL.md", "# Forms v2\n"),
+            ("guide.md", "guide v2\n"),
+            ("local-only.md", "edit me\n"),
+            ("notes.md", "notes v2\n"),
+            ("new.md", "new\n"),
+        ])
+    }
+
+    #[test]
+    fn test_merge() {
+        let (temp_dir, imports) = setup();
+        let report = imports.merge("forms", &v2()).unwrap();
+
+        assert_eq!(report.added, vec!["new.md"]);
+        assert_eq!(report.updated, vec!["SKILL.md"]);
+        assert_eq!(report.removed, vec!["dropped.md"]);
+        assert_eq!(report.kept, vec!["local-only.md"]);
+        assert_eq!(
+            report.conflicts,
+            vec![
+                MergeConflict { file: "guide.md".to_string(), kind: ConflictKind::Modified },
+                MergeConflict { file: "notes.md".to_string(), kind: ConflictKind::DeletedLocally },
+            ]
+        );
+        assert_eq!(read(&temp_dir, "SKILL.md").as_deref(), Some("# Forms v2\n"));
+        assert_eq!(read(&temp_dir, "guide.md").as_deref(), Some("guide local\n"));
+        assert_eq!(read(&temp_dir, "dropped.md"), None);
+        assert_eq!(imports.all_conflicts().unwrap()[0].conflicts.len(), 2);
+
+        // Merging the same version again changes nothing else
+        let again = imports.merge("forms", &v2()).unwrap();
+        assert!(again.added.is_empty() && again.updated.is_empty() && again.removed.is_empty());
+        assert_eq!(again.conflicts.len(), 2);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let (temp_dir, imports) = setup();
+        imports.merge("forms", &v2()).unwrap();
+
+        assert!(matches!(
+            imports.resolve("forms", "SKILL.md", Resolution::Incoming),
+            Err(MergeError::NoConflict(_))
+        ));
+        let pending = imports.resolve("forms", "notes.md", Resolution::Incoming).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(read(&temp_dir, "notes.md").as_deref(), Some("notes v2\n"));
+
+        let pending = imports
+            .resolve("forms", "guide.md", Resolution::Content("guide merged\n".to_string()))
+            .unwrap();
+        assert!(pending.is_empty());
+        assert_eq!(read(&temp_dir, "guide.md").as_deref(), Some("guide merged\n"));
+        assert!(imports.all_conflicts().unwrap().is_empty());
+
+        // Resolved files count as based on v2, so the merge keeps them
+        let report = imports.merge("forms", &v2()).unwrap();
+        assert_eq!(report.kept, vec!["guide.md", "local-only.md"]);
+        assert!(report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_without_base() {
+        let temp_dir = TempDir::new().unwrap();
+        let local = files(&[("SKILL.md", "# Mine\n"), ("same.md", "x"), ("mine.md", "m")]);
+        write_files(&temp_dir.path().join("forms"), &local).unwrap();
+        let imports = Imports::new(temp_dir.path());
+
+        let report = imports
+            .merge("forms", &files(&[("SKILL.md", "# Theirs\n"), ("same.md", "x"), ("new.md", "n")]))
+            .unwrap();
+        assert_eq!(report.added, vec!["new.md"]);
+        assert_eq!(report.kept, vec!["mine.md"]);
+        assert_eq!(
+            report.conflicts,
+            vec![MergeConflict { file: "SKILL.md".to_string(), kind: ConflictKind::Modified }]
+        );
+        assert!(is_skill_file("references/a.md"));
+        assert!(!is_skill_file("../a.md"));
+    }
+}
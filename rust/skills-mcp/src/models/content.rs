@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::RelatedSkill;
+
 /// Full skill content response.
 ///
 /// Corresponds to `SkillContent` in TypeScript.
@@ -10,15 +12,32 @@ pub struct SkillContent {
     /// Skill name/identifier.
     pub name: String,
 
-    /// SKILL.md content.
+    /// SKILL.md content, or a translation of it.
     pub content: String,
 
+    /// Language of the content, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
     /// Available sub-skill names.
     #[serde(default)]
     pub sub_skills: Vec<String>,
 
     /// Whether this skill has a references directory.
     pub has_references: bool,
+
+    /// Skills this skill directly depends on, for preloading.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
+    /// Curated and suggested related skills.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedSkill>,
+
+    /// Cursor of the rest of the content, when it was split to fit a size
+    /// limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl SkillContent {
@@ -27,8 +46,12 @@ impl SkillContent {
         Self {
             name,
             content,
+            language: None,
             sub_skills: Vec::new(),
             has_references: false,
+            depends_on: Vec::new(),
+            related: Vec::new(),
+            next_cursor: None,
         }
     }
 
@@ -43,6 +66,24 @@ impl SkillContent {
         self.has_references = has_references;
         self
     }
+
+    /// Set direct dependencies.
+    pub fn with_depends_on(mut self, depends_on: Vec<String>) -> Self {
+        self.depends_on = depends_on;
+        self
+    }
+
+    /// Set the language of the content.
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Set related skills.
+    pub fn with_related(mut self, related: Vec<RelatedSkill>) -> Self {
+        self.related = related;
+        self
+    }
 }
 
 /// Sub-skill content response.
@@ -58,6 +99,11 @@ pub struct SubSkillContent {
 
     /// Sub-skill markdown content.
     pub content: String,
+
+    /// Cursor of the rest of the content, when it was split to fit a size
+    /// limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl SubSkillContent {
@@ -67,6 +113,7 @@ impl SubSkillContent {
             domain,
             sub_skill,
             content,
+            next_cursor: None,
         }
     }
 }
@@ -155,31 +202,126 @@ pub enum SkillTemplate {
     WithSubSkills,
 }
 
+impl std::str::FromStr for SkillTemplate {
+    type Err = String;
+
+    /// Parse a template name, accepting both `with-sub-skills` and `with_sub_skills`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "minimal" => Ok(Self::Minimal),
+            "standard" => Ok(Self::Standard),
+            "with-sub-skills" => Ok(Self::WithSubSkills),
+            other => Err(format!(
+                "Unknown template '{}' (expected minimal, standard, or with-sub-skills)",
+                other
+            )),
+        }
+    }
+}
+
+/// Machine-readable kind of an error, so clients can tell errors apart
+/// without matching on messages. Serialized in `SCREAMING_SNAKE_CASE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// No skill has the name or alias, or the caller can't see it.
+    SkillNotFound,
+    /// A sub-skill, reference, bundle, job, or other resource doesn't exist.
+    NotFound,
+    /// A skill name is empty, too long, or uses forbidden characters.
+    InvalidName,
+    /// A path is malformed or leaves the skills directory.
+    InvalidPath,
+    /// Request fields failed validation; `details` lists them.
+    ValidationFailed,
+    /// A malformed parameter, query, cursor, or request body.
+    InvalidRequest,
+    /// A skill's `_meta.json` or frontmatter couldn't be parsed.
+    MetaParseError,
+    /// The name is already taken.
+    AlreadyExists,
+    /// The request conflicts with the skill's current state.
+    Conflict,
+    /// The skill is served from a read-only skills root.
+    ReadOnly,
+    /// Writes are paused for maintenance.
+    Maintenance,
+    /// The caller is sending requests too fast.
+    Throttled,
+    /// The feature is turned off on this server.
+    Disabled,
+    /// A file couldn't be read or written.
+    IoError,
+    /// Any other server-side failure.
+    InternalError,
+}
+
+/// A validation failure of one request field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Field name, e.g. `description` or `tags[2]`.
+    pub field: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+impl FieldError {
+    /// Create a field error.
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
 /// Standard error response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
+    /// Kind of error.
+    pub code: ErrorCode,
+
     /// Error message.
     pub error: String,
+
+    /// Field-level failures, for `VALIDATION_FAILED`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+
+    /// Close matches for a name that wasn't found.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
 }
 
 impl ErrorResponse {
     /// Create a new error response.
-    pub fn new(error: impl Into<String>) -> Self {
+    pub fn new(code: ErrorCode, error: impl Into<String>) -> Self {
         Self {
+            code,
             error: error.into(),
+            details: Vec::new(),
+            suggestions: Vec::new(),
         }
     }
-}
 
-impl From<String> for ErrorResponse {
-    fn from(error: String) -> Self {
-        Self::new(error)
+    /// A validation failure listing each invalid field; the message
+    /// summarizes the first.
+    pub fn validation(details: Vec<FieldError>) -> Self {
+        let error = match details.as_slice() {
+            [] => "Validation failed".to_string(),
+            [only] => only.message.clone(),
+            [first, rest @ ..] => format!("{} (and {} more)", first.message, rest.len()),
+        };
+        Self {
+            details,
+            ..Self::new(ErrorCode::ValidationFailed, error)
+        }
     }
-}
 
-impl From<&str> for ErrorResponse {
-    fn from(error: &str) -> Self {
-        Self::new(error)
+    /// Add "did you mean" suggestions.
+    pub fn with_suggestions(mut self, suggestions: Vec<String>) -> Self {
+        self.suggestions = suggestions;
+        self
     }
 }
 
@@ -218,4 +360,21 @@ mod tests {
         ));
         assert!(!skill.is_error());
     }
+
+    #[test]
+    fn test_error_response() {
+        let error = ErrorResponse::new(ErrorCode::SkillNotFound, "Skill 'x' not found");
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "SKILL_NOT_FOUND");
+        assert!(json.get("details").is_none());
+
+        let error = ErrorResponse::validation(vec![
+            FieldError::new("description", "Description too long"),
+            FieldError::new("tags[0]", "Tags cannot be empty"),
+        ]);
+        assert_eq!(error.code, ErrorCode::ValidationFailed);
+        assert_eq!(error.error, "Description too long (and 1 more)");
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["details"][1]["field"], "tags[0]");
+    }
 }
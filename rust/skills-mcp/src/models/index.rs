@@ -2,9 +2,25 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use super::SkillMeta;
+use crate::markdown::CodeBlock;
+
+/// A skill that lost a name collision between skills roots.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShadowedSkill {
+    /// Skill name.
+    pub name: String,
+
+    /// Root containing the hidden copy.
+    pub root: String,
+
+    /// Root whose copy is served.
+    pub shadowed_by: String,
+}
 
 /// Aggregated skill metadata index.
 ///
@@ -18,8 +34,17 @@ pub struct SkillIndex {
     #[serde(default)]
     pub validation_errors: Vec<String>,
 
+    /// Skills hidden by a same-named skill in a higher-priority root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shadowed: Vec<ShadowedSkill>,
+
     /// ISO timestamp of last index update.
+    #[serde(with = "crate::models::timestamp")]
     pub last_updated: DateTime<Utc>,
+
+    /// Whether [`find`](Self::find) ignores case.
+    #[serde(skip)]
+    pub case_insensitive: bool,
 }
 
 impl SkillIndex {
@@ -28,7 +53,9 @@ impl SkillIndex {
         Self {
             skills: Vec::new(),
             validation_errors: Vec::new(),
+            shadowed: Vec::new(),
             last_updated: Utc::now(),
+            case_insensitive: false,
         }
     }
 
@@ -37,13 +64,61 @@ impl SkillIndex {
         Self {
             skills,
             validation_errors: errors,
+            shadowed: Vec::new(),
             last_updated: Utc::now(),
+            case_insensitive: false,
         }
     }
 
-    /// Find a skill by name.
+    /// Find a skill by name, or failing that by alias.
+    ///
+    /// A `\` collection separator is read as `/`. When the index is case
+    /// insensitive, exact matches are still preferred.
     pub fn find(&self, name: &str) -> Option<&SkillMeta> {
-        self.skills.iter().find(|s| s.name == name)
+        let name = &*crate::index::normalize_separators(name);
+        let exact = self
+            .skills
+            .iter()
+            .find(|s| s.name == name)
+            .or_else(|| self.skills.iter().find(|s| s.aliases.iter().any(|a| a == name)));
+        if exact.is_some() || !self.case_insensitive {
+            return exact;
+        }
+        let folded = name.to_lowercase();
+        self.skills
+            .iter()
+            .find(|s| s.name.to_lowercase() == folded)
+            .or_else(|| {
+                self.skills
+                    .iter()
+                    .find(|s| s.aliases.iter().any(|a| a.to_lowercase() == folded))
+            })
+    }
+
+    /// Names of skills close to `name`, best match first.
+    ///
+    /// A skill matches when its name or one of its aliases is a few edits
+    /// away from `name`, one is a prefix of the other, or they share a
+    /// hyphen-separated word. Used for "did you mean" hints.
+    pub fn suggestions(&self, name: &str, limit: usize) -> Vec<String> {
+        let query = name.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scored: Vec<(usize, &str)> = self
+            .skills
+            .iter()
+            .filter_map(|skill| {
+                std::iter::once(&skill.name)
+                    .chain(&skill.aliases)
+                    .filter_map(|candidate| name_similarity(&query, candidate))
+                    .min()
+                    .map(|score| (score, skill.name.as_str()))
+            })
+            .collect();
+        scored.sort();
+        scored.into_iter().take(limit).map(|(_, name)| name.to_string()).collect()
     }
 
     /// Get skill count.
@@ -60,6 +135,215 @@ impl SkillIndex {
     pub fn has_errors(&self) -> bool {
         !self.validation_errors.is_empty()
     }
+
+    /// Collections with their member skills, sorted by name.
+    pub fn collections(&self) -> Vec<Collection> {
+        let mut collections: Vec<Collection> = Vec::new();
+        for skill in &self.skills {
+            let Some(name) = skill.collection() else {
+                continue;
+            };
+            match collections.iter_mut().find(|c| c.name == name) {
+                Some(collection) => collection.skills.push(skill.name.clone()),
+                None => collections.push(Collection {
+                    name: name.to_string(),
+                    skills: vec![skill.name.clone()],
+                }),
+            }
+        }
+        collections.sort_by(|a, b| a.name.cmp(&b.name));
+        collections
+    }
+
+    /// Tags with the skills that have them, most used first.
+    pub fn tags(&self) -> Vec<TagCount> {
+        let mut tags: Vec<TagCount> = Vec::new();
+        for skill in &self.skills {
+            for tag in &skill.tags {
+                match tags.iter_mut().find(|t| &t.tag == tag) {
+                    Some(entry) => entry.skills.push(skill.name.clone()),
+                    None => tags.push(TagCount {
+                        tag: tag.clone(),
+                        count: 0,
+                        skills: vec![skill.name.clone()],
+                    }),
+                }
+            }
+        }
+        for entry in &mut tags {
+            entry.count = entry.skills.len();
+        }
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        tags
+    }
+
+    /// Skills related to `name`: its curated `see_also` links first, then
+    /// up to `limit` computed suggestions from dependencies and shared tags.
+    ///
+    /// Curated links to skills missing from the index are skipped, and
+    /// deprecated skills are never suggested.
+    pub fn related(&self, name: &str, limit: usize) -> Vec<RelatedSkill> {
+        let Some(skill) = self.find(name) else {
+            return Vec::new();
+        };
+
+        let mut related: Vec<RelatedSkill> = skill
+            .see_also
+            .iter()
+            .filter(|link| self.find(link).is_some())
+            .map(|link| RelatedSkill {
+                name: link.clone(),
+                reason: RelatedReason::SeeAlso,
+                shared_tags: Vec::new(),
+            })
+            .collect();
+
+        let mut suggestions: Vec<(f64, RelatedSkill)> = Vec::new();
+        for other in &self.skills {
+            if other.name == skill.name || other.is_deprecated() || related.iter().any(|r| r.name == other.name) {
+                continue;
+            }
+
+            let shared_tags: Vec<String> = skill
+                .tags
+                .iter()
+                .filter(|t| other.tags.iter().any(|o| o.eq_ignore_ascii_case(t)))
+                .cloned()
+                .collect();
+            let dependency =
+                skill.depends_on.contains(&other.name) || other.depends_on.contains(&skill.name);
+
+            let reason = if dependency {
+                RelatedReason::Dependency
+            } else if !shared_tags.is_empty() {
+                RelatedReason::SharedTags
+            } else {
+                continue;
+            };
+
+            // Jaccard similarity of tags, with dependencies ranked first
+            let union = skill.tags.len() + other.tags.len() - shared_tags.len();
+            let mut score = shared_tags.len() as f64 / union.max(1) as f64;
+            if dependency {
+                score += 1.0;
+            }
+            suggestions.push((
+                score,
+                RelatedSkill {
+                    name: other.name.clone(),
+                    reason,
+                    shared_tags,
+                },
+            ));
+        }
+
+        suggestions.sort_by(|a, b| {
+            b.0.partial_cmp(&a.0)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.1.name.cmp(&b.1.name))
+        });
+        related.extend(suggestions.into_iter().take(limit).map(|(_, r)| r));
+        related
+    }
+}
+
+/// How close a looked-up name is to a skill name or alias, lower being
+/// closer, or `None` when they look unrelated.
+fn name_similarity(query: &str, candidate: &str) -> Option<usize> {
+    let candidate = candidate.to_lowercase();
+    // Also compare the unqualified name: `forms` for `frontend/forms`
+    let short = candidate.rsplit('/').next().unwrap_or(&candidate);
+    let words = |s: &str| -> Vec<String> {
+        s.split(['-', '_', '/'])
+            .filter(|w| w.len() >= 3)
+            .map(str::to_string)
+            .collect()
+    };
+    let max_edits = (query.chars().count() / 4).clamp(1, 3);
+
+    [candidate.as_str(), short]
+        .into_iter()
+        .filter_map(|c| {
+            if c == query {
+                return Some(0);
+            }
+            if c.len().min(query.len()) >= 3 && (c.starts_with(query) || query.starts_with(c)) {
+                return Some(1);
+            }
+            let distance = edit_distance(query, c);
+            if distance <= max_edits {
+                return Some(1 + distance);
+            }
+            let shared = words(query)
+                .iter()
+                .any(|q| words(c).iter().any(|w| w.starts_with(q.as_str()) || q.starts_with(w.as_str())));
+            shared.then_some(max_edits + 2)
+        })
+        .min()
+}
+
+/// Levenshtein distance between two strings, by character.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            row.push(substitute.min(prev[j + 1] + 1).min(row[j] + 1));
+        }
+        prev = row;
+    }
+    prev[b.len()]
+}
+
+/// Why a skill is suggested as related to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelatedReason {
+    /// Curated in the skill's `see_also`.
+    SeeAlso,
+    /// One skill depends on the other.
+    Dependency,
+    /// The skills share tags.
+    SharedTags,
+}
+
+/// A skill related to another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedSkill {
+    /// Related skill name.
+    pub name: String,
+
+    /// Why it is related.
+    pub reason: RelatedReason,
+
+    /// Tags the skills have in common.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_tags: Vec<String>,
+}
+
+/// A namespace grouping skills (`frontend` in `frontend/forms`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collection {
+    /// Collection name.
+    pub name: String,
+
+    /// Qualified names of the skills in the collection.
+    pub skills: Vec<String>,
+}
+
+/// A tag and the skills that have it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    /// The tag, as indexed.
+    pub tag: String,
+
+    /// Number of skills with the tag.
+    pub count: usize,
+
+    /// Names of the skills with the tag.
+    pub skills: Vec<String>,
 }
 
 impl Default for SkillIndex {
@@ -83,8 +367,13 @@ pub struct ContentIndexEntry {
     /// Relative file path.
     pub file: String,
 
-    /// Lowercase searchable content.
-    pub content: String,
+    /// Lowercase searchable content, shared between entries for identical
+    /// files.
+    pub content: Arc<str>,
+
+    /// Hash of the file's text; identical files have the same hash.
+    #[serde(default)]
+    pub content_hash: String,
 
     /// Word count for TF-IDF calculations.
     pub word_count: usize,
@@ -92,27 +381,75 @@ pub struct ContentIndexEntry {
     /// Extracted markdown headings.
     #[serde(default)]
     pub headings: Vec<String>,
+
+    /// Link targets found in the markdown, as written.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+
+    /// Detected natural language of the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Fenced code blocks, with their original case.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub code_blocks: Vec<CodeBlock>,
+
+    /// File type: `markdown`, `text`, or the extension of other files
+    /// indexed as plain text (`py`, `json`, `yaml`).
+    #[serde(default = "markdown_file_type")]
+    pub file_type: String,
+}
+
+fn markdown_file_type() -> String {
+    crate::index::MARKDOWN.to_string()
 }
 
 impl ContentIndexEntry {
     /// Create a new content index entry.
+    ///
+    /// Headings, links, and code blocks are only extracted from markdown
+    /// files, and the natural language only detected in markdown and text
+    /// files.
     pub fn new(
         domain: String,
         sub_skill: Option<String>,
         file: String,
         content: String,
     ) -> Self {
+        let file_type = crate::index::file_type(&file);
+        let markdown = file_type == crate::index::MARKDOWN;
+
         let word_count = content.split_whitespace().count();
-        let headings = Self::extract_headings(&content);
+        let (headings, links, code_blocks) = if markdown {
+            (
+                Self::extract_headings(&content),
+                crate::markdown::extract_links(&content),
+                crate::markdown::extract_code_blocks(&content),
+            )
+        } else {
+            (Vec::new(), Vec::new(), Vec::new())
+        };
+        let language = (markdown || file_type == "text")
+            .then(|| crate::markdown::detect_language(&content))
+            .flatten();
+        let content_hash = Sha256::digest(content.as_bytes())[..16]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
         let content_lower = content.to_lowercase();
 
         Self {
             domain,
             sub_skill,
             file,
-            content: content_lower,
+            content: content_lower.into(),
+            content_hash,
             word_count,
             headings,
+            links,
+            language,
+            code_blocks,
+            file_type,
         }
     }
 
@@ -138,10 +475,14 @@ impl ContentIndexEntry {
     }
 
     /// Generate a unique key for this entry.
+    ///
+    /// Main SKILL.md content is keyed by domain and sub-skills by
+    /// `domain:sub_skill`; other files (e.g. references) by `domain/file`.
     pub fn key(&self) -> String {
         match &self.sub_skill {
             Some(sub) => format!("{}:{}", self.domain, sub),
-            None => self.domain.clone(),
+            None if self.file == "SKILL.md" => self.domain.clone(),
+            None => format!("{}/{}", self.domain, self.file),
         }
     }
 }
@@ -154,7 +495,17 @@ pub struct ContentIndex {
     /// Map of unique keys to content entries.
     pub entries: HashMap<String, ContentIndexEntry>,
 
+    /// Each distinct document's content, by hash. Entries for identical
+    /// files share one copy.
+    #[serde(skip)]
+    documents: HashMap<String, Arc<str>>,
+
+    /// Files indexed incompletely or not at all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<IndexWarning>,
+
     /// ISO timestamp of last index update.
+    #[serde(with = "crate::models::timestamp")]
     pub last_updated: DateTime<Utc>,
 }
 
@@ -163,17 +514,79 @@ impl ContentIndex {
     pub fn new() -> Self {
         Self {
             entries: HashMap::new(),
+            documents: HashMap::new(),
+            warnings: Vec::new(),
             last_updated: Utc::now(),
         }
     }
 
-    /// Add an entry to the index.
-    pub fn insert(&mut self, entry: ContentIndexEntry) {
+    /// Add an entry to the index, sharing the content of an identical
+    /// document already indexed.
+    pub fn insert(&mut self, mut entry: ContentIndexEntry) {
+        if !entry.content_hash.is_empty() {
+            let document = self
+                .documents
+                .entry(entry.content_hash.clone())
+                .or_insert_with(|| Arc::clone(&entry.content));
+            entry.content = Arc::clone(document);
+        }
         let key = entry.key();
-        self.entries.insert(key, entry);
+        if self.entries.insert(key, entry).is_some() {
+            self.prune_documents();
+        }
         self.last_updated = Utc::now();
     }
 
+    /// Storage used by the indexed content, and the files that are
+    /// identical to each other.
+    pub fn storage_report(&self) -> StorageReport {
+        // Entries loaded without a hash count as distinct
+        let mut groups: HashMap<String, Vec<&ContentIndexEntry>> = HashMap::new();
+        for (key, entry) in &self.entries {
+            let id = if entry.content_hash.is_empty() {
+                format!("key:{}", key)
+            } else {
+                entry.content_hash.clone()
+            };
+            groups.entry(id).or_default().push(entry);
+        }
+
+        let total_bytes = self.entries.values().map(|e| e.content.len()).sum();
+        let unique_bytes = groups.values().map(|g| g[0].content.len()).sum();
+        let documents = groups.len();
+        let mut duplicates: Vec<DuplicateDocument> = groups
+            .into_iter()
+            .filter(|(_, group)| group.len() > 1)
+            .map(|(hash, group)| {
+                let bytes = group[0].content.len();
+                let mut copies: Vec<String> =
+                    group.iter().map(|e| format!("{}/{}", e.domain, e.file)).collect();
+                copies.sort();
+                DuplicateDocument {
+                    hash,
+                    bytes,
+                    savings_bytes: bytes * (copies.len() - 1),
+                    copies,
+                }
+            })
+            .collect();
+        duplicates.sort_by(|a, b| b.savings_bytes.cmp(&a.savings_bytes).then(a.copies.cmp(&b.copies)));
+
+        StorageReport {
+            files: self.entries.len(),
+            documents,
+            total_bytes,
+            unique_bytes,
+            savings_bytes: total_bytes - unique_bytes,
+            duplicates,
+        }
+    }
+
+    /// Drop documents no entry refers to any more.
+    fn prune_documents(&mut self) {
+        self.documents.retain(|_, document| Arc::strong_count(document) > 1);
+    }
+
     /// Get an entry by key.
     pub fn get(&self, key: &str) -> Option<&ContentIndexEntry> {
         self.entries.get(key)
@@ -201,6 +614,70 @@ impl ContentIndex {
     pub fn iter(&self) -> impl Iterator<Item = (&String, &ContentIndexEntry)> {
         self.entries.iter()
     }
+
+    /// Remove a skill's entries and warnings.
+    pub fn remove_domain(&mut self, domain: &str) {
+        self.entries.retain(|_key, entry| entry.domain != domain);
+        self.warnings.retain(|w| w.skill != domain);
+        self.prune_documents();
+    }
+}
+
+/// Storage used by the content index.
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageReport {
+    /// Indexed files.
+    pub files: usize,
+
+    /// Distinct documents among them.
+    pub documents: usize,
+
+    /// Indexed text, counting every file.
+    pub total_bytes: usize,
+
+    /// Indexed text, counting each distinct document once.
+    pub unique_bytes: usize,
+
+    /// Bytes spent on duplicate copies: what removing them would save.
+    pub savings_bytes: usize,
+
+    /// Groups of identical files, largest savings first.
+    pub duplicates: Vec<DuplicateDocument>,
+}
+
+/// Files with identical content.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateDocument {
+    /// Content hash shared by the files.
+    pub hash: String,
+
+    /// Size of one copy.
+    pub bytes: usize,
+
+    /// Copies, as `skill/file`, sorted.
+    pub copies: Vec<String>,
+
+    /// Bytes saved by keeping a single copy.
+    pub savings_bytes: usize,
+}
+
+/// A problem indexing one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexWarning {
+    /// Skill the file belongs to.
+    pub skill: String,
+
+    /// File relative to the skill directory.
+    pub file: String,
+
+    /// What went wrong.
+    pub message: String,
+}
+
+impl std::fmt::Display for IndexWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.skill, self.file, self.message)
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +692,7 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
 
         let index = SkillIndex::with_skills(vec![meta.clone()], vec![]);
@@ -224,6 +702,90 @@ mod tests {
         assert!(index.find("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_tags() {
+        let skill = |name: &str, tags: &[&str]| SkillMeta {
+            name: name.to_string(),
+            description: format!("{} skill", name),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        let index = SkillIndex::with_skills(
+            vec![skill("forms", &["react", "testing"]), skill("jest", &["testing"]), skill("zod", &["api"])],
+            vec![],
+        );
+        let tags = index.tags();
+        let counts: Vec<(&str, usize)> = tags.iter().map(|t| (t.tag.as_str(), t.count)).collect();
+        assert_eq!(counts, vec![("testing", 2), ("api", 1), ("react", 1)]);
+        assert_eq!(tags[0].skills, vec!["forms", "jest"]);
+    }
+
+    #[test]
+    fn test_related_skills() {
+        let skill = |name: &str, tags: &[&str], see_also: &[&str]| SkillMeta {
+            name: name.to_string(),
+            description: format!("{} skill", name),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            see_also: see_also.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+        let mut forms = skill("forms", &["react", "validation"], &["testing", "ghost"]);
+        forms.depends_on = vec!["zod".to_string()];
+        let index = SkillIndex::with_skills(
+            vec![
+                forms,
+                skill("testing", &[], &[]),
+                skill("zod", &["schemas"], &[]),
+                skill("hooks", &["react"], &[]),
+                skill("styling", &["css"], &[]),
+                SkillMeta {
+                    deprecated: true,
+                    ..skill("old-forms", &["react", "validation"], &[])
+                },
+            ],
+            vec![],
+        );
+
+        // old-forms shares the most tags, but is deprecated
+        let related = index.related("forms", 5);
+        let names: Vec<&str> = related.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["testing", "zod", "hooks"]);
+        assert_eq!(related[0].reason, RelatedReason::SeeAlso);
+        assert_eq!(related[1].reason, RelatedReason::Dependency);
+        assert_eq!(related[2].shared_tags, vec!["react"]);
+
+        // The limit applies to computed suggestions only
+        assert_eq!(index.related("forms", 0).len(), 1);
+        assert!(index.related("unknown", 5).is_empty());
+    }
+
+    #[test]
+    fn test_aliases_and_suggestions() {
+        let skill = |name: &str, aliases: &[&str]| SkillMeta {
+            name: name.to_string(),
+            description: format!("{} skill", name),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        };
+        let index = SkillIndex::with_skills(
+            vec![
+                skill("forms", &["form-validation"]),
+                skill("frontend/testing", &[]),
+                skill("kubernetes", &["k8s"]),
+            ],
+            vec![],
+        );
+
+        assert_eq!(index.find("form-validation").unwrap().name, "forms");
+        assert_eq!(index.find("k8s").unwrap().name, "kubernetes");
+
+        assert_eq!(index.suggestions("form-handling", 3), vec!["forms"]);
+        assert_eq!(index.suggestions("kubernets", 3), vec!["kubernetes"]);
+        assert_eq!(index.suggestions("testng", 3), vec!["frontend/testing"]);
+        assert_eq!(index.suggestions("form-validaton", 3), vec!["forms"]);
+        assert!(index.suggestions("docker", 3).is_empty());
+    }
+
     #[test]
     fn test_content_index_entry() {
         let entry = ContentIndexEntry::new(
@@ -266,4 +828,40 @@ mod tests {
         assert!(index.get("forms:react").is_some());
         assert_eq!(index.get_domain_entries("forms").len(), 2);
     }
+
+    #[test]
+    fn test_content_index_deduplication() {
+        let mut index = ContentIndex::new();
+        let reference = |skill: &str| {
+            ContentIndexEntry::new(
+                skill.to_string(),
+                None,
+                "references/http.md".to_string(),
+                "HTTP status codes".to_string(),
+            )
+        };
+        index.insert(reference("forms"));
+        index.insert(reference("tables"));
+        index.insert(ContentIndexEntry::new(
+            "forms".to_string(),
+            None,
+            "SKILL.md".to_string(),
+            "Forms".to_string(),
+        ));
+
+        let shared = |key: &str| Arc::clone(&index.get(key).unwrap().content);
+        assert!(Arc::ptr_eq(&shared("forms/references/http.md"), &shared("tables/references/http.md")));
+
+        let report = index.storage_report();
+        assert_eq!((report.files, report.documents), (3, 2));
+        assert_eq!(report.savings_bytes, "http status codes".len());
+        assert_eq!(
+            report.duplicates[0].copies,
+            vec!["forms/references/http.md", "tables/references/http.md"]
+        );
+
+        index.remove_domain("forms");
+        index.remove_domain("tables");
+        assert!(index.documents.is_empty());
+    }
 }
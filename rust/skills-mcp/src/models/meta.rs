@@ -1,6 +1,10 @@
 //! Skill metadata types matching `_meta.json` schema.
 
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// Sub-skill reference within a parent skill.
 ///
@@ -16,12 +20,80 @@ pub struct SubSkillMeta {
     /// Optional keywords for search discovery
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub triggers: Vec<String>,
+
+    /// Natural language of the sub-skill file. Detected at index time when
+    /// not declared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// A template variable declared by a skill.
+///
+/// SKILL.md refers to variables as `{{name}}`; they are filled in from
+/// arguments when the skill is loaded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SkillVariable {
+    /// Variable name, as used in `{{name}}` placeholders.
+    pub name: String,
+
+    /// What the value is for, shown to whoever supplies it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Whether loading the skill fails without a value.
+    #[serde(default)]
+    pub required: bool,
+
+    /// Value used when none is supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// Allowed values; any value is accepted when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
+
+/// Who can list, search, and read a skill.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// Every caller.
+    #[default]
+    Public,
+    /// Only callers whose API key is in `allowed_keys`.
+    Private,
+}
+
+impl Visibility {
+    /// Whether this is [`Visibility::Public`].
+    pub fn is_public(&self) -> bool {
+        *self == Visibility::Public
+    }
+}
+
+/// Hex digits of a fingerprint shown to people.
+const SHORT_FINGERPRINT_LEN: usize = 12;
+
+/// Non-reversible fingerprint of an API key: its full SHA-256 digest.
+///
+/// `allowed_keys` lists fingerprints rather than keys, so `_meta.json`
+/// never holds a secret. Visibility compares whole digests, so no other
+/// key can be found to match one.
+pub fn key_fingerprint(key: &str) -> String {
+    let digest = Sha256::digest(key.as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The start of a fingerprint, naming a key in reader lists and audit
+/// entries. It's short enough to brute-force, so it never grants access.
+pub fn short_fingerprint(fingerprint: &str) -> &str {
+    fingerprint.get(..SHORT_FINGERPRINT_LEN).unwrap_or(fingerprint)
 }
 
 /// Primary skill metadata from `_meta.json`.
 ///
 /// Corresponds to `SkillMeta` in TypeScript and validates against `MetaSchema`.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SkillMeta {
     /// Skill identifier - must match directory name.
     /// Lowercase alphanumeric with hyphens only.
@@ -34,16 +106,109 @@ pub struct SkillMeta {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 
+    /// Alternative names the skill can be looked up by.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
     /// Optional nested sub-skills for domain/router skills.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sub_skills: Option<Vec<SubSkillMeta>>,
 
-    /// Optional origin indicator (e.g., "community", "official").
+    /// Optional origin indicator (e.g., "community", "official"), or the
+    /// repository URL a skill was imported from.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
+
+    /// Short machine-readable answer ("tl;dr") for simple questions.
+    ///
+    /// When absent from `_meta.json`, the indexer fills it from a
+    /// `## TL;DR` or `## Quick Answer` section in SKILL.md.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quick_answer: Option<String>,
+
+    /// Names of other skills this skill builds on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
+    /// Curated links to related skills, listed before computed suggestions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub see_also: Vec<String>,
+
+    /// Template variables used in SKILL.md.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<SkillVariable>,
+
+    /// Natural language of the skill content, as an ISO 639-1 code with an
+    /// optional region (`en`, `pt-BR`).
+    ///
+    /// When absent from `_meta.json`, the indexer detects it from SKILL.md.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Languages with a translated `SKILL.<lang>.md` next to SKILL.md,
+    /// sorted. Set by the indexer; not read from `_meta.json`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub translations: Vec<String>,
+
+    /// Archived skills stay on disk and readable by name but are left out
+    /// of listings and search unless asked for.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+
+    /// Deprecated skills stay listed and readable, with a notice appended
+    /// to their content, but aren't recommended.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+
+    /// Skill replacing this one. Implies `deprecated`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+
+    /// Who can list, search, and read the skill.
+    #[serde(default, skip_serializing_if = "Visibility::is_public")]
+    pub visibility: Visibility,
+
+    /// Fingerprints of the API keys that can see a private skill, with or
+    /// without the `key:` prefix used in audit logs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_keys: Vec<String>,
+
+    /// Skills root the skill was loaded from. Set by the indexer; not read
+    /// from `_meta.json`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root: Option<String>,
+
+    /// Earliest creation time of the skill's files. Set by the indexer.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::timestamp::option"
+    )]
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// Latest modification time of the skill's files. Set by the indexer.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::models::timestamp::option"
+    )]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Collection part of a skill name (`frontend` for `frontend/forms`).
+pub fn collection_of(name: &str) -> Option<&str> {
+    name.split_once('/').map(|(collection, _)| collection)
 }
 
 impl SkillMeta {
+    /// Maximum length of `quick_answer`, in characters.
+    pub const MAX_QUICK_ANSWER_CHARS: usize = 500;
+
+    /// Collection (namespace) of the skill, if it is nested in one.
+    pub fn collection(&self) -> Option<&str> {
+        collection_of(&self.name)
+    }
+
     /// Check if this skill has sub-skills (is a router/domain skill).
     pub fn has_sub_skills(&self) -> bool {
         self.sub_skills
@@ -60,6 +225,74 @@ impl SkillMeta {
             .unwrap_or_default()
     }
 
+    /// Whether a caller can see the skill, given the fingerprint of their
+    /// API key, if any. Private skills are hidden from callers without a
+    /// key.
+    pub fn is_visible_to(&self, key: Option<&str>) -> bool {
+        match self.visibility {
+            Visibility::Public => true,
+            Visibility::Private => key.is_some_and(|key| {
+                self.allowed_keys
+                    .iter()
+                    .any(|allowed| allowed.strip_prefix("key:").unwrap_or(allowed) == key)
+            }),
+        }
+    }
+
+    /// The translation to serve for a requested language, or None to
+    /// serve SKILL.md.
+    ///
+    /// An exact translation wins, then SKILL.md if its language shares the
+    /// primary subtag (`en` for `en-GB`), then any translation that does.
+    pub fn translation_for(&self, lang: &str) -> Option<&str> {
+        if let Some(exact) = self.translations.iter().find(|t| t.eq_ignore_ascii_case(lang)) {
+            return Some(exact);
+        }
+        if self
+            .language
+            .as_deref()
+            .is_some_and(|default| crate::markdown::language_matches(default, lang))
+        {
+            return None;
+        }
+        self.translations
+            .iter()
+            .find(|t| crate::markdown::language_matches(t, lang))
+            .map(String::as_str)
+    }
+
+    /// Whether the skill is deprecated, or superseded by another.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated || self.superseded_by.is_some()
+    }
+
+    /// Notice appended to content read from a deprecated skill.
+    pub fn deprecation_notice(&self) -> Option<String> {
+        if !self.is_deprecated() {
+            return None;
+        }
+        Some(match &self.superseded_by {
+            Some(successor) => format!(
+                "> **Deprecated:** `{}` is superseded by `{}`; use that skill instead.",
+                self.name, successor
+            ),
+            None => format!("> **Deprecated:** `{}` is no longer maintained.", self.name),
+        })
+    }
+
+    /// Append the deprecation notice, if any, to content read from the skill.
+    pub fn append_deprecation_notice(&self, content: &mut String) {
+        if let Some(notice) = self.deprecation_notice() {
+            let trimmed = content.trim_end().len();
+            content.truncate(trimmed);
+            if !content.is_empty() {
+                content.push_str("\n\n");
+            }
+            content.push_str(&notice);
+            content.push('\n');
+        }
+    }
+
     /// Find a sub-skill by name.
     pub fn find_sub_skill(&self, name: &str) -> Option<&SubSkillMeta> {
         self.sub_skills
@@ -67,6 +300,45 @@ impl SkillMeta {
             .and_then(|subs| subs.iter().find(|s| s.name == name))
     }
 
+    /// Resolve declared variables against supplied values.
+    ///
+    /// Applies defaults and checks required variables and allowed options.
+    /// Values for undeclared variables are ignored.
+    pub fn resolve_variables(
+        &self,
+        provided: &HashMap<String, String>,
+    ) -> Result<HashMap<String, String>, Vec<String>> {
+        let mut values = HashMap::new();
+        let mut errors = Vec::new();
+
+        for var in &self.variables {
+            let value = provided.get(&var.name).or(var.default.as_ref());
+            match value {
+                Some(value) if !var.options.is_empty() && !var.options.contains(value) => {
+                    errors.push(format!(
+                        "{}: must be one of {}, got '{}'",
+                        var.name,
+                        var.options.join(", "),
+                        value
+                    ));
+                }
+                Some(value) => {
+                    values.insert(var.name.clone(), value.clone());
+                }
+                None if var.required => {
+                    errors.push(format!("{}: required", var.name));
+                }
+                None => {}
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Get all trigger words (skill-level tags + sub-skill triggers).
     pub fn all_triggers(&self) -> Vec<&str> {
         let mut triggers: Vec<&str> = self.tags.iter().map(|s| s.as_str()).collect();
@@ -128,6 +400,63 @@ mod tests {
         assert_eq!(react_sub.triggers, vec!["useForm", "react-hook-form"]);
     }
 
+    #[test]
+    fn test_is_visible_to() {
+        let fingerprint = key_fingerprint("team-a-key");
+        let mut meta: SkillMeta = serde_json::from_str(&format!(
+            r#"{{"name": "billing", "description": "Billing", "visibility": "private",
+                "allowed_keys": ["key:{}"]}}"#,
+            fingerprint
+        ))
+        .unwrap();
+        assert!(meta.is_visible_to(Some(&fingerprint)));
+        assert!(!meta.is_visible_to(Some(&key_fingerprint("team-b-key"))));
+        assert!(!meta.is_visible_to(None));
+
+        // The short form names a key but never grants access
+        meta.allowed_keys = vec![short_fingerprint(&fingerprint).to_string()];
+        assert!(!meta.is_visible_to(Some(&fingerprint)));
+
+        meta.visibility = Visibility::Public;
+        assert!(meta.is_visible_to(None));
+    }
+
+    #[test]
+    fn test_deprecation_notice() {
+        let mut meta: SkillMeta =
+            serde_json::from_str(r#"{"name": "forms", "description": "Forms", "superseded_by": "forms-v2"}"#).unwrap();
+        assert!(meta.is_deprecated());
+        let mut content = "# Forms\n\n".to_string();
+        meta.append_deprecation_notice(&mut content);
+        assert_eq!(
+            content,
+            "# Forms\n\n> **Deprecated:** `forms` is superseded by `forms-v2`; use that skill instead.\n"
+        );
+
+        meta.superseded_by = None;
+        meta.deprecated = true;
+        assert!(meta.deprecation_notice().unwrap().contains("no longer maintained"));
+
+        meta.deprecated = false;
+        let mut content = "# Forms\n".to_string();
+        meta.append_deprecation_notice(&mut content);
+        assert_eq!(content, "# Forms\n");
+    }
+
+    #[test]
+    fn test_translation_for() {
+        let meta = SkillMeta {
+            language: Some("en".to_string()),
+            translations: vec!["de".to_string(), "en-GB".to_string(), "pt-BR".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(meta.translation_for("en-gb"), Some("en-GB"));
+        assert_eq!(meta.translation_for("en-US"), None);
+        assert_eq!(meta.translation_for("de-AT"), Some("de"));
+        assert_eq!(meta.translation_for("pt"), Some("pt-BR"));
+        assert_eq!(meta.translation_for("fr"), None);
+    }
+
     #[test]
     fn test_all_triggers() {
         let meta = SkillMeta {
@@ -138,8 +467,10 @@ mod tests {
                 name: "react".to_string(),
                 file: "react/SKILL.md".to_string(),
                 triggers: vec!["useForm".to_string()],
+                language: None,
             }]),
             source: None,
+            ..Default::default()
         };
 
         let triggers = meta.all_triggers();
@@ -147,4 +478,28 @@ mod tests {
         assert!(triggers.contains(&"input"));
         assert!(triggers.contains(&"useForm"));
     }
+
+    #[test]
+    fn test_resolve_variables() {
+        let meta: SkillMeta = serde_json::from_str(
+            r#"{
+                "name": "deploy",
+                "description": "Deployment",
+                "variables": [
+                    {"name": "env", "required": true, "options": ["staging", "prod"]},
+                    {"name": "region", "default": "us-east-1"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let provided = HashMap::from([("env".to_string(), "prod".to_string())]);
+        let values = meta.resolve_variables(&provided).unwrap();
+        assert_eq!(values["env"], "prod");
+        assert_eq!(values["region"], "us-east-1");
+
+        assert!(meta.resolve_variables(&HashMap::new()).is_err());
+        let provided = HashMap::from([("env".to_string(), "dev".to_string())]);
+        assert!(meta.resolve_variables(&provided).is_err());
+    }
 }
@@ -8,9 +8,14 @@ mod index;
 mod search;
 mod stats;
 mod content;
+mod receipts;
+mod tokens;
+pub mod timestamp;
 
 pub use meta::*;
 pub use index::*;
 pub use search::*;
 pub use stats::*;
 pub use content::*;
+pub use receipts::*;
+pub use tokens::*;
@@ -0,0 +1,145 @@
+//! Read receipts recording who recently read each skill.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A single reader's activity on a skill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReaderEntry {
+    /// Reader identity (session ID, API key fingerprint, or transport name).
+    pub reader: String,
+
+    /// Number of reads by this reader.
+    pub read_count: u64,
+
+    /// When this reader last read the skill.
+    #[serde(with = "crate::models::timestamp")]
+    pub last_read: DateTime<Utc>,
+}
+
+/// Read activity for a single skill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillReaders {
+    /// Skill name.
+    pub skill: String,
+
+    /// Total reads across all readers.
+    pub total_reads: u64,
+
+    /// Most recent read, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "crate::models::timestamp::option")]
+    pub last_read: Option<DateTime<Utc>>,
+
+    /// Readers sorted by most recent read first.
+    pub readers: Vec<ReaderEntry>,
+}
+
+/// In-memory read receipts keyed by skill name.
+///
+/// Reading the receipts back out is privacy-gated: callers must check
+/// [`ReadReceipts::is_enabled`] before exposing reader identities.
+#[derive(Debug, Clone, Default)]
+pub struct ReadReceipts {
+    enabled: bool,
+    skills: HashMap<String, HashMap<String, ReaderEntry>>,
+}
+
+impl ReadReceipts {
+    /// Maximum number of distinct readers retained per skill.
+    const MAX_READERS_PER_SKILL: usize = 100;
+
+    /// Create an empty, disabled receipt store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether reader identities may be exposed.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable exposing reader identities.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Record a read of `skill` by `reader`.
+    pub fn record(&mut self, skill: &str, reader: &str) {
+        let now = Utc::now();
+        let readers = self.skills.entry(skill.to_string()).or_default();
+
+        readers
+            .entry(reader.to_string())
+            .and_modify(|e| {
+                e.read_count += 1;
+                e.last_read = now;
+            })
+            .or_insert_with(|| ReaderEntry {
+                reader: reader.to_string(),
+                read_count: 1,
+                last_read: now,
+            });
+
+        // Evict the least recently seen reader when over capacity
+        if readers.len() > Self::MAX_READERS_PER_SKILL {
+            if let Some(oldest) = readers
+                .values()
+                .min_by_key(|e| e.last_read)
+                .map(|e| e.reader.clone())
+            {
+                readers.remove(&oldest);
+            }
+        }
+    }
+
+    /// When a skill was last read by anyone.
+    pub fn last_read(&self, skill: &str) -> Option<DateTime<Utc>> {
+        self.skills
+            .get(skill)
+            .and_then(|readers| readers.values().map(|e| e.last_read).max())
+    }
+
+    /// Get read activity for a skill.
+    pub fn readers(&self, skill: &str) -> SkillReaders {
+        let mut readers: Vec<ReaderEntry> = self
+            .skills
+            .get(skill)
+            .map(|r| r.values().cloned().collect())
+            .unwrap_or_default();
+        readers.sort_by_key(|e| std::cmp::Reverse(e.last_read));
+
+        SkillReaders {
+            skill: skill.to_string(),
+            total_reads: readers.iter().map(|e| e.read_count).sum(),
+            last_read: readers.first().map(|e| e.last_read),
+            readers,
+        }
+    }
+
+    /// Drop all receipts for a skill (e.g., after deletion).
+    pub fn forget(&mut self, skill: &str) {
+        self.skills.remove(skill);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_query_readers() {
+        let mut receipts = ReadReceipts::new();
+        assert!(!receipts.is_enabled());
+
+        receipts.record("forms", "session:a");
+        receipts.record("forms", "session:a");
+        receipts.record("forms", "session:b");
+
+        let readers = receipts.readers("forms");
+        assert_eq!(readers.total_reads, 3);
+        assert_eq!(readers.readers.len(), 2);
+        assert!(receipts.last_read("forms").is_some());
+        assert!(receipts.last_read("testing").is_none());
+    }
+}
@@ -14,6 +14,8 @@ pub enum MatchType {
     Tags,
     /// Matched trigger words.
     Triggers,
+    /// Matched markdown headings.
+    Headings,
     /// Matched content body.
     Content,
 }
@@ -27,9 +29,164 @@ impl MatchType {
             MatchType::Triggers => 2.5,
             MatchType::Tags => 2.0,
             MatchType::Description => 1.5,
+            MatchType::Headings => 1.25,
             MatchType::Content => 1.0,
         }
     }
+
+    /// Parse a field name as used in boost settings; `body` and `content`
+    /// both name the content body.
+    pub fn from_field(field: &str) -> Option<Self> {
+        match field {
+            "name" => Some(MatchType::Name),
+            "description" => Some(MatchType::Description),
+            "tags" => Some(MatchType::Tags),
+            "triggers" => Some(MatchType::Triggers),
+            "headings" => Some(MatchType::Headings),
+            "body" | "content" => Some(MatchType::Content),
+            _ => None,
+        }
+    }
+}
+
+/// Weight of each searchable field, multiplied into match scores.
+///
+/// Defaults to [`MatchType::weight`]. Configured under `[search.boosts]`
+/// in `skills-mcp.toml`, and overridden per query with
+/// [`SearchOptions::boost`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FieldBoosts {
+    /// Skill name.
+    pub name: f64,
+    /// Description.
+    pub description: f64,
+    /// Tags.
+    pub tags: f64,
+    /// Sub-skill triggers.
+    pub triggers: f64,
+    /// Markdown headings of indexed files.
+    pub headings: f64,
+    /// Body text of indexed files.
+    pub body: f64,
+}
+
+impl Default for FieldBoosts {
+    fn default() -> Self {
+        Self {
+            name: MatchType::Name.weight(),
+            description: MatchType::Description.weight(),
+            tags: MatchType::Tags.weight(),
+            triggers: MatchType::Triggers.weight(),
+            headings: MatchType::Headings.weight(),
+            body: MatchType::Content.weight(),
+        }
+    }
+}
+
+impl FieldBoosts {
+    /// The boost of the field a match type matched.
+    pub fn get(&self, field: MatchType) -> f64 {
+        match field {
+            MatchType::Name => self.name,
+            MatchType::Description => self.description,
+            MatchType::Tags => self.tags,
+            MatchType::Triggers => self.triggers,
+            MatchType::Headings => self.headings,
+            MatchType::Content => self.body,
+        }
+    }
+
+    /// Set the boost of one field.
+    pub fn set(&mut self, field: MatchType, boost: f64) {
+        *match field {
+            MatchType::Name => &mut self.name,
+            MatchType::Description => &mut self.description,
+            MatchType::Tags => &mut self.tags,
+            MatchType::Triggers => &mut self.triggers,
+            MatchType::Headings => &mut self.headings,
+            MatchType::Content => &mut self.body,
+        } = boost;
+    }
+
+    /// These boosts with per-field overrides applied.
+    pub fn with_overrides(mut self, overrides: &[(MatchType, f64)]) -> Self {
+        for &(field, boost) in overrides {
+            self.set(field, boost);
+        }
+        self
+    }
+
+    /// Parse overrides written as `field:boost` pairs separated by commas,
+    /// e.g. `tags:4,body:0.5`.
+    pub fn parse_overrides(text: &str) -> Result<Vec<(MatchType, f64)>, String> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (field, boost) = pair
+                    .split_once(':')
+                    .ok_or_else(|| format!("expected field:boost, got '{}'", pair))?;
+                let field = MatchType::from_field(field.trim())
+                    .ok_or_else(|| format!("unknown field '{}'", field.trim()))?;
+                let boost: f64 = boost
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|b: &f64| b.is_finite() && *b >= 0.0)
+                    .ok_or_else(|| format!("invalid boost '{}'", boost.trim()))?;
+                Ok((field, boost))
+            })
+            .collect()
+    }
+}
+
+/// Search settings from the `[search]` section of `skills-mcp.toml`.
+///
+/// ```toml
+/// [search]
+/// cache = 256
+///
+/// [search.boosts]
+/// tags = 3.0
+/// body = 0.5
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Per-field score boosts.
+    pub boosts: FieldBoosts,
+
+    /// Result sets kept in the search cache; 0 disables it.
+    pub cache: usize,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            boosts: FieldBoosts::default(),
+            cache: crate::search::SearchCache::DEFAULT_CAPACITY,
+        }
+    }
+}
+
+/// Position of a matched term within a result's snippet.
+///
+/// Byte offsets index the UTF-8 snippet; char offsets count Unicode scalar
+/// values, for clients (like JavaScript) that don't slice by bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Highlight {
+    /// Byte offset of the match start.
+    pub start: usize,
+
+    /// Byte offset just past the match.
+    pub end: usize,
+
+    /// Char offset of the match start.
+    pub char_start: usize,
+
+    /// Char offset just past the match.
+    pub char_end: usize,
 }
 
 /// A single search result.
@@ -54,9 +211,30 @@ pub struct SearchResult {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub snippet: Option<String>,
 
+    /// Matched terms within the snippet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<Highlight>,
+
+    /// 1-based line of the file the snippet was taken from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
     /// Optional file path for content matches.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub file: Option<String>,
+
+    /// The skill's quick answer, so simple questions can skip a full load.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quick_answer: Option<String>,
+
+    /// Whether the match came from cold storage.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cold: bool,
+
+    /// Other files with identical content, as `skill/file`, folded into
+    /// this result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<String>,
 }
 
 impl SearchResult {
@@ -68,7 +246,12 @@ impl SearchResult {
             score,
             match_type,
             snippet: None,
+            highlights: Vec::new(),
+            line: None,
             file: None,
+            quick_answer: None,
+            cold: false,
+            duplicates: Vec::new(),
         }
     }
 
@@ -84,12 +267,36 @@ impl SearchResult {
         self
     }
 
+    /// Set highlight ranges within the snippet.
+    pub fn with_highlights(mut self, highlights: Vec<Highlight>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
+    /// Set the line the snippet was taken from.
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
     /// Set file path.
     pub fn with_file(mut self, file: String) -> Self {
         self.file = Some(file);
         self
     }
 
+    /// Set quick answer.
+    pub fn with_quick_answer(mut self, quick_answer: String) -> Self {
+        self.quick_answer = Some(quick_answer);
+        self
+    }
+
+    /// Mark as a cold-storage match.
+    pub fn into_cold(mut self) -> Self {
+        self.cold = true;
+        self
+    }
+
     /// Get a display-friendly identifier.
     pub fn display_id(&self) -> String {
         match &self.sub_skill {
@@ -123,6 +330,27 @@ impl Ord for SearchResult {
     }
 }
 
+/// A code block matching an example search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleMatch {
+    /// Skill domain name.
+    pub domain: String,
+
+    /// Sub-skill owning the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+
+    /// File containing the block, relative to the skill directory.
+    pub file: String,
+
+    /// Relevance score; term occurrences per word of code.
+    pub score: f64,
+
+    /// The block itself.
+    #[serde(flatten)]
+    pub block: crate::markdown::CodeBlock,
+}
+
 /// Search query options.
 #[derive(Debug, Clone, Default)]
 pub struct SearchOptions {
@@ -137,6 +365,25 @@ pub struct SearchOptions {
 
     /// Filter to specific domains.
     pub domains: Option<Vec<String>>,
+
+    /// Filter to skills in one collection.
+    pub collection: Option<String>,
+
+    /// Filter to content in one natural language (`en`, `de`).
+    pub language: Option<String>,
+
+    /// Filter content to these file types (`markdown`, `py`, `yaml`).
+    pub file_types: Option<Vec<String>>,
+
+    /// Include archived skills.
+    pub include_archived: bool,
+
+    /// Fingerprint of the caller's API key; private skills are only
+    /// searched for keys they allow.
+    pub key: Option<String>,
+
+    /// Field boosts overriding the service's for this search.
+    pub boosts: Vec<(MatchType, f64)>,
 }
 
 impl SearchOptions {
@@ -159,6 +406,76 @@ impl SearchOptions {
         self.domains = Some(domains);
         self
     }
+
+    /// Filter to skills in one collection.
+    pub fn collection(mut self, collection: impl Into<String>) -> Self {
+        self.collection = Some(collection.into());
+        self
+    }
+
+    /// Filter to content in one natural language.
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Filter content to these file types.
+    pub fn file_types(mut self, file_types: Vec<String>) -> Self {
+        self.file_types = Some(file_types);
+        self
+    }
+
+    /// Include archived skills.
+    pub fn include_archived(mut self, include: bool) -> Self {
+        self.include_archived = include;
+        self
+    }
+
+    /// Search as the caller with this API key fingerprint.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Weight matches in `field` by `boost` for this search.
+    pub fn boost(mut self, field: MatchType, boost: f64) -> Self {
+        self.boosts.push((field, boost));
+        self
+    }
+
+    /// Whether a skill passes the archive and visibility filters.
+    pub fn allows_skill(&self, skill: &super::SkillMeta) -> bool {
+        (self.include_archived || !skill.archived) && skill.is_visible_to(self.key.as_deref())
+    }
+
+    /// Whether content of a file type passes the file type filter.
+    ///
+    /// Filters may use extensions (`md`, `yml`) for their file types.
+    pub fn allows_file_type(&self, file_type: &str) -> bool {
+        self.file_types.as_ref().is_none_or(|types| {
+            types
+                .iter()
+                .any(|t| crate::index::normalize_file_type(t) == file_type)
+        })
+    }
+
+    /// Whether content in `language` passes the language filter.
+    ///
+    /// Content whose language is unknown is excluded while filtering.
+    pub fn allows_language(&self, language: Option<&str>) -> bool {
+        self.language.as_deref().is_none_or(|filter| {
+            language.is_some_and(|l| crate::markdown::language_matches(l, filter))
+        })
+    }
+
+    /// Whether a skill passes the domain and collection filters.
+    pub fn allows_domain(&self, domain: &str) -> bool {
+        self.domains.as_ref().is_none_or(|d| d.iter().any(|d| d == domain))
+            && self
+                .collection
+                .as_deref()
+                .is_none_or(|c| super::collection_of(domain) == Some(c))
+    }
 }
 
 /// Results from a search operation.
@@ -175,6 +492,14 @@ pub struct SearchResults {
 
     /// Whether results were truncated.
     pub truncated: bool,
+
+    /// Fallback skill served when nothing matched with enough confidence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<FallbackSkill>,
+
+    /// Cursor of the next page, when results are paginated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl SearchResults {
@@ -195,6 +520,8 @@ impl SearchResults {
             query,
             total_matches,
             truncated,
+            fallback: None,
+            next_cursor: None,
         }
     }
 
@@ -214,6 +541,217 @@ impl SearchResults {
     }
 }
 
+/// Fallback skill configuration for unmatched queries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FallbackConfig {
+    /// Skill served when a search has no confident match.
+    pub skill: String,
+
+    /// Score the best result must reach to count as a confident match.
+    pub min_score: f64,
+}
+
+impl FallbackConfig {
+    /// Serve `skill` only when a search returns nothing.
+    pub fn new(skill: impl Into<String>) -> Self {
+        Self {
+            skill: skill.into(),
+            min_score: 0.0,
+        }
+    }
+
+    /// Also serve the fallback when the best score is below `score`.
+    pub fn min_score(mut self, score: f64) -> Self {
+        self.min_score = score;
+        self
+    }
+}
+
+/// Why a search produced no confident match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZeroResultDiagnostics {
+    /// Keywords extracted from the query.
+    pub keywords: Vec<String>,
+
+    /// Keywords found in no skill metadata or content.
+    pub unknown_keywords: Vec<String>,
+
+    /// Best result score, if anything matched at all.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_score: Option<f64>,
+
+    /// Score a result needed to count as a confident match.
+    pub min_score: f64,
+}
+
+/// Skill returned in place of an empty or low-confidence result set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackSkill {
+    /// Fallback skill name.
+    pub skill: String,
+
+    /// Fallback skill description.
+    pub description: String,
+
+    /// The fallback skill's quick answer, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quick_answer: Option<String>,
+
+    /// Why the search fell back.
+    pub diagnostics: ZeroResultDiagnostics,
+}
+
+/// Results from a multi-query fusion search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FusedSearchResults {
+    /// Query variants generated from the task description.
+    pub variants: Vec<String>,
+
+    /// Fused results; scores are Reciprocal Rank Fusion scores.
+    #[serde(flatten)]
+    pub results: SearchResults,
+}
+
+/// A task keyword that hit a skill metadata field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TermHit {
+    /// Keyword from the task description.
+    pub term: String,
+
+    /// Metadata field the keyword matched.
+    pub field: MatchType,
+
+    /// Field value that contained the keyword (tag, trigger, name, ...).
+    pub value: String,
+
+    /// Sub-skill owning the trigger, for trigger hits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub_skill: Option<String>,
+}
+
+/// Explanation of whether and why a skill matches a task description.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchExplanation {
+    /// Skill being explained.
+    pub skill: String,
+
+    /// Task description that was analyzed.
+    pub task: String,
+
+    /// Whether the skill is returned for the task by any search mode.
+    pub matched: bool,
+
+    /// Result of a literal metadata search for the whole task string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub literal_match: Option<SearchResult>,
+
+    /// 1-based rank of the skill in a fusion search for the task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fused_rank: Option<usize>,
+
+    /// Keywords extracted from the task.
+    pub keywords: Vec<String>,
+
+    /// Keywords that hit a metadata field.
+    pub hits: Vec<TermHit>,
+
+    /// Keywords that hit no metadata field.
+    pub missed_keywords: Vec<String>,
+
+    /// Tags and triggers that no keyword touched.
+    pub unmatched_triggers: Vec<String>,
+}
+
+/// A sample task phrase for trigger testing, with the sub-skills it should
+/// select.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriggerCase {
+    /// Task phrase.
+    pub phrase: String,
+
+    /// Sub-skills the phrase is expected to select, in any order; None to
+    /// only report the selection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<Vec<String>>,
+}
+
+/// A trigger that matched a task phrase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerMatch {
+    /// The trigger as declared.
+    pub trigger: String,
+
+    /// 1.0 when the phrase contains the whole trigger, less for some of
+    /// its words.
+    pub score: f64,
+
+    /// Trigger words found in the phrase.
+    pub matched_terms: Vec<String>,
+}
+
+/// How a sub-skill scored against a task phrase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubSkillScore {
+    /// Sub-skill name.
+    pub sub_skill: String,
+
+    /// Score of its best trigger.
+    pub score: f64,
+
+    /// Whether the score reaches the selection threshold.
+    pub selected: bool,
+
+    /// Triggers that matched, best first.
+    pub matches: Vec<TriggerMatch>,
+}
+
+/// Sub-skills a task phrase selects, and why.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerTest {
+    /// Task phrase.
+    pub phrase: String,
+
+    /// Selected sub-skills, best first.
+    pub selected: Vec<String>,
+
+    /// Every sub-skill with a matching trigger, best first.
+    pub sub_skills: Vec<SubSkillScore>,
+
+    /// Expected selection, if one was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Vec<String>>,
+
+    /// Whether the selection matched the expectation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passed: Option<bool>,
+}
+
+/// Trigger test results for a skill's sub-skills.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TriggerTestReport {
+    /// Skill tested.
+    pub skill: String,
+
+    /// Score a sub-skill needs to be selected.
+    pub min_score: f64,
+
+    /// Result per phrase, in request order.
+    pub results: Vec<TriggerTest>,
+
+    /// Phrases whose selection matched the expectation.
+    pub passed: usize,
+
+    /// Phrases whose selection didn't.
+    pub failed: usize,
+}
+
+impl TriggerTestReport {
+    /// Whether no expectation failed.
+    pub fn all_passed(&self) -> bool {
+        self.failed == 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,12 +759,25 @@ mod tests {
     #[test]
     fn test_match_type_weights() {
         assert!(MatchType::Name.weight() > MatchType::Content.weight());
+        assert!(MatchType::Headings.weight() > MatchType::Content.weight());
         assert!(MatchType::Triggers.weight() > MatchType::Tags.weight());
     }
 
+    #[test]
+    fn test_field_boost_overrides() {
+        let overrides = FieldBoosts::parse_overrides("tags:4, body:0.5").unwrap();
+        assert_eq!(overrides, vec![(MatchType::Tags, 4.0), (MatchType::Content, 0.5)]);
+        let boosts = FieldBoosts::default().with_overrides(&overrides);
+        assert_eq!((boosts.tags, boosts.body, boosts.name), (4.0, 0.5, 3.0));
+
+        assert!(FieldBoosts::parse_overrides("tags").is_err());
+        assert!(FieldBoosts::parse_overrides("title:2").is_err());
+        assert!(FieldBoosts::parse_overrides("tags:-1").is_err());
+    }
+
     #[test]
     fn test_search_result_ordering() {
-        let mut results = vec![
+        let mut results = [
             SearchResult::new("low".to_string(), 0.3, MatchType::Content),
             SearchResult::new("high".to_string(), 0.9, MatchType::Name),
             SearchResult::new("mid".to_string(), 0.6, MatchType::Tags),
@@ -11,6 +11,7 @@ pub struct SearchEntry {
     pub query: String,
 
     /// When the search was performed.
+    #[serde(with = "crate::models::timestamp")]
     pub timestamp: DateTime<Utc>,
 
     /// Number of results returned.
@@ -43,6 +44,7 @@ pub struct UsageStats {
     pub searches: Vec<SearchEntry>,
 
     /// Server start time.
+    #[serde(with = "crate::models::timestamp")]
     pub start_time: DateTime<Utc>,
 }
 
@@ -0,0 +1,87 @@
+//! Timestamp format shared by every response.
+//!
+//! All timestamps are serialized as RFC 3339 in UTC with millisecond
+//! precision and a `Z` suffix, e.g. `2024-05-01T09:30:00.000Z`, so clients
+//! can parse and compare them as strings. Any RFC 3339 offset is accepted
+//! on input and converted to UTC.
+//!
+//! Use with `#[serde(with = "crate::models::timestamp")]`, or
+//! `timestamp::option` for `Option<DateTime<Utc>>` fields.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// Format a timestamp as RFC 3339 UTC with milliseconds.
+pub fn format(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Parse an RFC 3339 timestamp with any offset into UTC.
+pub fn parse(text: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
+    DateTime::parse_from_rfc3339(text).map(|at| at.with_timezone(&Utc))
+}
+
+/// Serialize a timestamp.
+pub fn serialize<S: Serializer>(at: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format(at))
+}
+
+/// Deserialize a timestamp.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    parse(&text).map_err(serde::de::Error::custom)
+}
+
+/// The same format for optional timestamps.
+pub mod option {
+    use super::*;
+
+    /// Serialize an optional timestamp.
+    pub fn serialize<S: Serializer>(
+        at: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match at {
+            Some(at) => super::serialize(at, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional timestamp.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| parse(&text).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde::Serialize;
+
+    #[derive(Serialize, Deserialize)]
+    struct Stamped {
+        #[serde(with = "super")]
+        at: DateTime<Utc>,
+        #[serde(default, with = "option")]
+        seen: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let at = Utc.with_ymd_and_hms(2024, 5, 1, 9, 30, 0).unwrap();
+        let json = serde_json::to_string(&Stamped { at, seen: None }).unwrap();
+        assert_eq!(json, r#"{"at":"2024-05-01T09:30:00.000Z","seen":null}"#);
+
+        let parsed: Stamped =
+            serde_json::from_str(r#"{"at": "2024-05-01T11:30:00+02:00", "seen": "2024-05-01T09:30:00.123456Z"}"#)
+                .unwrap();
+        assert_eq!(parsed.at, at);
+        assert!(parsed.seen.is_some());
+        assert!(serde_json::from_str::<Stamped>(r#"{"at": "yesterday"}"#).is_err());
+    }
+}
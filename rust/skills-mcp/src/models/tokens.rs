@@ -0,0 +1,226 @@
+//! Token totals of skill content served, per session and per day.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Tokens served for one skill (or `skill:sub-skill`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillTokens {
+    /// Skill name, or `skill:sub-skill` for sub-skill content.
+    pub skill: String,
+
+    /// Approximate tokens served.
+    pub tokens: u64,
+
+    /// Number of times content was served.
+    pub reads: u64,
+}
+
+/// Token totals broken down by skill.
+#[derive(Debug, Clone, Default)]
+pub struct TokenUsage {
+    tokens: u64,
+    reads: u64,
+    by_skill: HashMap<String, (u64, u64)>,
+}
+
+impl TokenUsage {
+    fn add(&mut self, skill: &str, tokens: u64) {
+        self.tokens += tokens;
+        self.reads += 1;
+        let entry = self.by_skill.entry(skill.to_string()).or_default();
+        entry.0 += tokens;
+        entry.1 += 1;
+    }
+
+    /// Total approximate tokens served.
+    pub fn tokens(&self) -> u64 {
+        self.tokens
+    }
+
+    /// Total number of reads.
+    pub fn reads(&self) -> u64 {
+        self.reads
+    }
+
+    /// Skills that cost the most tokens, largest first.
+    pub fn top_skills(&self, limit: usize) -> Vec<SkillTokens> {
+        let mut skills: Vec<SkillTokens> = self
+            .by_skill
+            .iter()
+            .map(|(skill, (tokens, reads))| SkillTokens {
+                skill: skill.clone(),
+                tokens: *tokens,
+                reads: *reads,
+            })
+            .collect();
+        skills.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.skill.cmp(&b.skill)));
+        skills.truncate(limit);
+        skills
+    }
+}
+
+/// Token totals for one day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyTokens {
+    /// UTC date.
+    pub date: NaiveDate,
+
+    /// Approximate tokens served.
+    pub tokens: u64,
+
+    /// Number of reads.
+    pub reads: u64,
+
+    /// Most expensive skills that day.
+    pub top_skills: Vec<SkillTokens>,
+}
+
+/// Token totals for one session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionTokens {
+    /// Session identity (session ID, API key fingerprint, or transport name).
+    pub session: String,
+
+    /// Approximate tokens served.
+    pub tokens: u64,
+
+    /// Number of reads.
+    pub reads: u64,
+
+    /// When content was last served to the session.
+    #[serde(with = "crate::models::timestamp")]
+    pub last_seen: DateTime<Utc>,
+
+    /// Most expensive skills in the session.
+    pub top_skills: Vec<SkillTokens>,
+}
+
+/// Overall token report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenSummary {
+    /// Approximate tokens served since startup.
+    pub total_tokens: u64,
+
+    /// Reads since startup.
+    pub total_reads: u64,
+
+    /// Most expensive skills overall.
+    pub top_skills: Vec<SkillTokens>,
+
+    /// Per-day totals, most recent first.
+    pub days: Vec<DailyTokens>,
+}
+
+/// In-memory token metrics.
+#[derive(Debug, Clone, Default)]
+pub struct TokenMetrics {
+    total: TokenUsage,
+    days: BTreeMap<NaiveDate, TokenUsage>,
+    sessions: HashMap<String, (TokenUsage, DateTime<Utc>)>,
+}
+
+impl TokenMetrics {
+    /// Number of days of history retained.
+    const MAX_DAYS: usize = 90;
+
+    /// Number of sessions above which the least recently seen are dropped.
+    const MAX_SESSIONS: usize = 10_000;
+
+    /// Create empty metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `tokens` of `skill` content served to `session`.
+    pub fn record(&mut self, session: &str, skill: &str, tokens: u64) {
+        let now = Utc::now();
+
+        self.total.add(skill, tokens);
+        self.days.entry(now.date_naive()).or_default().add(skill, tokens);
+        while self.days.len() > Self::MAX_DAYS {
+            self.days.pop_first();
+        }
+
+        let entry = self
+            .sessions
+            .entry(session.to_string())
+            .or_insert_with(|| (TokenUsage::default(), now));
+        entry.0.add(skill, tokens);
+        entry.1 = now;
+
+        if self.sessions.len() > Self::MAX_SESSIONS {
+            if let Some(oldest) = self
+                .sessions
+                .iter()
+                .min_by_key(|(_, (_, last_seen))| *last_seen)
+                .map(|(session, _)| session.clone())
+            {
+                self.sessions.remove(&oldest);
+            }
+        }
+    }
+
+    /// Totals overall and per day, with the top `limit` skills for each.
+    pub fn summary(&self, limit: usize) -> TokenSummary {
+        TokenSummary {
+            total_tokens: self.total.tokens(),
+            total_reads: self.total.reads(),
+            top_skills: self.total.top_skills(limit),
+            days: self
+                .days
+                .iter()
+                .rev()
+                .map(|(date, usage)| DailyTokens {
+                    date: *date,
+                    tokens: usage.tokens(),
+                    reads: usage.reads(),
+                    top_skills: usage.top_skills(limit),
+                })
+                .collect(),
+        }
+    }
+
+    /// Per-session totals, largest first, with the top `limit` skills each.
+    pub fn sessions(&self, limit: usize) -> Vec<SessionTokens> {
+        let mut sessions: Vec<SessionTokens> = self
+            .sessions
+            .iter()
+            .map(|(session, (usage, last_seen))| SessionTokens {
+                session: session.clone(),
+                tokens: usage.tokens(),
+                reads: usage.reads(),
+                last_seen: *last_seen,
+                top_skills: usage.top_skills(limit),
+            })
+            .collect();
+        sessions.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.session.cmp(&b.session)));
+        sessions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_metrics() {
+        let mut metrics = TokenMetrics::new();
+        metrics.record("session:a", "forms", 300);
+        metrics.record("session:a", "forms:validation", 50);
+        metrics.record("session:b", "testing", 500);
+
+        let summary = metrics.summary(1);
+        assert_eq!(summary.total_tokens, 850);
+        assert_eq!(summary.total_reads, 3);
+        assert_eq!(summary.top_skills[0].skill, "testing");
+        assert_eq!(summary.days.len(), 1);
+        assert_eq!(summary.days[0].tokens, 850);
+
+        let sessions = metrics.sessions(5);
+        assert_eq!(sessions[0].session, "session:b");
+        assert_eq!(sessions[1].tokens, 350);
+        assert_eq!(sessions[1].top_skills.len(), 2);
+    }
+}
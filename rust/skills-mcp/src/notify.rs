@@ -0,0 +1,106 @@
+//! Notification adapters for operational alerts.
+//!
+//! Subsystems raise [`Alert`]s through a [`Notifiers`] fan-out; each
+//! registered [`Notifier`] decides how to deliver them. The built-in
+//! [`LogNotifier`] writes alerts to the tracing log.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Serialize;
+
+/// Alert severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Informational.
+    Info,
+    /// Needs attention.
+    Warning,
+    /// Needs immediate attention.
+    Critical,
+}
+
+/// An operational alert.
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    /// Machine-readable alert kind (e.g. `abuse.skill_hammering`).
+    pub kind: String,
+
+    /// Alert severity.
+    pub severity: Severity,
+
+    /// Human-readable message.
+    pub message: String,
+
+    /// When the alert was raised.
+    #[serde(with = "crate::models::timestamp")]
+    pub raised_at: DateTime<Utc>,
+}
+
+impl Alert {
+    /// Create a new alert raised now.
+    pub fn new(kind: impl Into<String>, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            severity,
+            message: message.into(),
+            raised_at: Utc::now(),
+        }
+    }
+}
+
+/// A destination for alerts.
+pub trait Notifier: Send + Sync {
+    /// Deliver an alert. Implementations must not block for long.
+    fn notify(&self, alert: &Alert);
+}
+
+/// Notifier that writes alerts to the tracing log.
+#[derive(Debug, Default)]
+pub struct LogNotifier;
+
+impl Notifier for LogNotifier {
+    fn notify(&self, alert: &Alert) {
+        match alert.severity {
+            Severity::Info => tracing::info!("[{}] {}", alert.kind, alert.message),
+            Severity::Warning => tracing::warn!("[{}] {}", alert.kind, alert.message),
+            Severity::Critical => tracing::error!("[{}] {}", alert.kind, alert.message),
+        }
+    }
+}
+
+/// Fan-out to all registered notifiers.
+///
+/// Starts with a [`LogNotifier`] registered.
+pub struct Notifiers {
+    notifiers: RwLock<Vec<Arc<dyn Notifier>>>,
+}
+
+impl Notifiers {
+    /// Create a fan-out with only the log notifier registered.
+    pub fn new() -> Self {
+        Self {
+            notifiers: RwLock::new(vec![Arc::new(LogNotifier)]),
+        }
+    }
+
+    /// Register an additional notifier.
+    pub fn register(&self, notifier: Arc<dyn Notifier>) {
+        self.notifiers.write().push(notifier);
+    }
+
+    /// Send an alert to every registered notifier.
+    pub fn send(&self, alert: &Alert) {
+        for notifier in self.notifiers.read().iter() {
+            notifier.notify(alert);
+        }
+    }
+}
+
+impl Default for Notifiers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,324 @@
+//! Partial edits to skill content.
+//!
+//! A patch is either a unified diff (as produced by
+//! [`unified_diff`](crate::diff::unified_diff)) or a list of operations
+//! targeting sections by heading. Patches apply all-or-nothing: if any hunk's
+//! context no longer matches, or a target heading is missing, nothing is
+//! changed and the caller gets a conflict to resolve against fresh content.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::markdown::parse_heading;
+
+/// Lines a hunk may drift from its stated position and still apply.
+const MAX_HUNK_OFFSET: usize = 50;
+
+/// What a section operation does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionEdit {
+    /// Replace the section body, keeping its heading.
+    Replace,
+    /// Add to the end of the section body.
+    Append,
+    /// Add to the start of the section body, right under the heading.
+    Insert,
+}
+
+/// An edit to the section under a heading.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionOp {
+    /// What to do.
+    pub op: SectionEdit,
+
+    /// Heading title, matched case-insensitively. Prefix with `#`s
+    /// (`## Usage`) to also require the heading level.
+    pub heading: String,
+
+    /// Markdown to write.
+    pub content: String,
+}
+
+/// SHA-256 of content as lowercase hex, used to detect concurrent edits.
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Apply a unified diff.
+///
+/// File header lines (`---`/`+++`) are ignored. Each hunk's context and
+/// removed lines must match the content exactly; a hunk that moved is
+/// found again within a few lines of its stated position.
+pub fn apply_unified_diff(content: &str, diff: &str) -> Result<String, PatchError> {
+    let hunks = parse_hunks(diff)?;
+    if hunks.is_empty() {
+        return Err(PatchError::Malformed("diff contains no hunks".to_string()));
+    }
+
+    let old: Vec<&str> = content.lines().collect();
+    let mut out: Vec<&str> = Vec::with_capacity(old.len());
+    let mut cursor = 0;
+
+    for hunk in &hunks {
+        let expected: Vec<&str> = hunk.lines.iter().filter_map(HunkLine::before).collect();
+        let at = find_hunk(&old, &expected, hunk.old_start.saturating_sub(1), cursor)
+            .ok_or_else(|| PatchError::Conflict(format!("hunk at line {} does not match", hunk.old_start)))?;
+
+        out.extend_from_slice(&old[cursor..at]);
+        out.extend(hunk.lines.iter().filter_map(HunkLine::after));
+        cursor = at + expected.len();
+    }
+    out.extend_from_slice(&old[cursor..]);
+
+    Ok(join_lines(&out, content))
+}
+
+/// Apply section operations in order.
+pub fn apply_section_ops(content: &str, ops: &[SectionOp]) -> Result<String, PatchError> {
+    if ops.is_empty() {
+        return Err(PatchError::Malformed("no operations".to_string()));
+    }
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    for op in ops {
+        let (heading, end) = find_section(&lines, &op.heading)?;
+        let content = op.content.trim_matches('\n').lines().map(str::to_string);
+
+        // Body without the blank lines around it
+        let body: Vec<String> = lines[heading + 1..end]
+            .iter()
+            .skip_while(|l| l.trim().is_empty())
+            .cloned()
+            .collect();
+        let blank_tail = body.iter().rev().take_while(|l| l.trim().is_empty()).count();
+        let body = &body[..body.len() - blank_tail];
+
+        let separator = (!body.is_empty()).then(String::new);
+        let new_body: Vec<String> = match op.op {
+            SectionEdit::Replace => content.collect(),
+            SectionEdit::Append => body.iter().cloned().chain(separator).chain(content).collect(),
+            SectionEdit::Insert => content.chain(separator).chain(body.iter().cloned()).collect(),
+        };
+
+        let mut replacement = vec![String::new()];
+        replacement.extend(new_body);
+        if end < lines.len() {
+            replacement.push(String::new());
+        }
+        lines.splice(heading + 1..end, replacement);
+    }
+
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    Ok(join_lines(&lines, content))
+}
+
+/// One parsed hunk.
+struct Hunk<'a> {
+    old_start: usize,
+    lines: Vec<HunkLine<'a>>,
+}
+
+enum HunkLine<'a> {
+    Context(&'a str),
+    Remove(&'a str),
+    Add(&'a str),
+}
+
+impl<'a> HunkLine<'a> {
+    /// The line as it appears in the original content.
+    fn before(&self) -> Option<&'a str> {
+        match self {
+            Self::Context(l) | Self::Remove(l) => Some(l),
+            Self::Add(_) => None,
+        }
+    }
+
+    /// The line as it appears in the patched content.
+    fn after(&self) -> Option<&'a str> {
+        match self {
+            Self::Context(l) | Self::Add(l) => Some(l),
+            Self::Remove(_) => None,
+        }
+    }
+}
+
+fn parse_hunks(diff: &str) -> Result<Vec<Hunk<'_>>, PatchError> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("@@ ") {
+            let old_start = header
+                .strip_prefix('-')
+                .and_then(|r| r.split([',', ' ']).next())
+                .and_then(|n| n.parse().ok())
+                .ok_or_else(|| PatchError::Malformed(format!("bad hunk header: {}", line)))?;
+            hunks.push(Hunk {
+                old_start,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        // File headers come before the first hunk; `\ No newline` notes anywhere
+        let Some(hunk) = hunks.last_mut() else {
+            continue;
+        };
+        if line.starts_with('\\') {
+            continue;
+        }
+        let parsed = match line.chars().next() {
+            Some(' ') => HunkLine::Context(&line[1..]),
+            Some('-') => HunkLine::Remove(&line[1..]),
+            Some('+') => HunkLine::Add(&line[1..]),
+            // Some tools drop the space of empty context lines
+            None => HunkLine::Context(""),
+            Some(_) => return Err(PatchError::Malformed(format!("bad diff line: {}", line))),
+        };
+        hunk.lines.push(parsed);
+    }
+    Ok(hunks)
+}
+
+/// Find where a hunk's old lines occur, preferring its stated position.
+fn find_hunk(old: &[&str], expected: &[&str], stated: usize, cursor: usize) -> Option<usize> {
+    let matches_at = |at: usize| at >= cursor && old.get(at..at + expected.len()) == Some(expected);
+    if matches_at(stated) {
+        return Some(stated);
+    }
+    (1..=MAX_HUNK_OFFSET).find_map(|offset| {
+        [stated.checked_sub(offset), Some(stated + offset)]
+            .into_iter()
+            .flatten()
+            .find(|&at| matches_at(at))
+    })
+}
+
+/// Find the unique section for a heading: its heading line and end line.
+fn find_section(lines: &[String], target: &str) -> Result<(usize, usize), PatchError> {
+    let (level, title) = match parse_heading(target.trim()) {
+        Some((level, title)) => (Some(level), title),
+        None => (None, target.trim()),
+    };
+
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if let Some(heading) = parse_heading(line).filter(|_| !in_fence) {
+            headings.push((i, heading.0, heading.1.to_string()));
+        }
+    }
+
+    let mut found = headings
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, l, t))| t.eq_ignore_ascii_case(title) && level.is_none_or(|level| level == *l));
+    let (index, &(line, section_level, _)) = found
+        .next()
+        .ok_or_else(|| PatchError::SectionNotFound(target.to_string()))?;
+    if found.next().is_some() {
+        return Err(PatchError::AmbiguousSection(target.to_string()));
+    }
+
+    let end = headings[index + 1..]
+        .iter()
+        .find(|(_, l, _)| *l <= section_level)
+        .map_or(lines.len(), |(i, _, _)| *i);
+    Ok((line, end))
+}
+
+/// Join lines, keeping the original's trailing newline.
+fn join_lines(lines: &[&str], original: &str) -> String {
+    let mut out = lines.join("\n");
+    if original.ends_with('\n') && !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+/// Errors applying a patch.
+#[derive(Debug, thiserror::Error)]
+pub enum PatchError {
+    /// The patch itself is invalid.
+    #[error("Malformed patch: {0}")]
+    Malformed(String),
+
+    /// The content no longer matches what the patch expects.
+    #[error("Patch conflict: {0}")]
+    Conflict(String),
+
+    /// No heading matches a section operation.
+    #[error("Section not found: {0}")]
+    SectionNotFound(String),
+
+    /// More than one heading matches a section operation.
+    #[error("Section heading is ambiguous: {0}")]
+    AmbiguousSection(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::unified_diff;
+
+    const SKILL: &str = "# Forms\n\n## Usage\n\nUse it.\n\n### Detail\n\nMore.\n\n## Notes\n\nOld note.\n";
+
+    fn op(op: SectionEdit, heading: &str, content: &str) -> SectionOp {
+        SectionOp {
+            op,
+            heading: heading.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_unified_diff() {
+        let new = SKILL.replace("Use it.", "Use it well.").replace("Old note.", "New note.");
+        let diff = unified_diff(SKILL, &new);
+        assert_eq!(apply_unified_diff(SKILL, &diff).unwrap(), new);
+
+        // A hunk still applies after lines were added above it
+        let shifted = format!("Intro\n\n{}", SKILL);
+        let patched = apply_unified_diff(&shifted, &diff).unwrap();
+        assert!(patched.contains("Use it well.") && patched.starts_with("Intro"));
+
+        let edited = SKILL.replace("Old note.", "Someone else's note.");
+        assert!(matches!(apply_unified_diff(&edited, &diff), Err(PatchError::Conflict(_))));
+        assert!(matches!(apply_unified_diff(SKILL, "nonsense"), Err(PatchError::Malformed(_))));
+    }
+
+    #[test]
+    fn test_apply_section_ops() {
+        let patched = apply_section_ops(
+            SKILL,
+            &[
+                op(SectionEdit::Replace, "notes", "New note."),
+                op(SectionEdit::Append, "## Usage", "Appended."),
+                op(SectionEdit::Insert, "Detail", "First."),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            patched,
+            "# Forms\n\n## Usage\n\nUse it.\n\n### Detail\n\nFirst.\n\nMore.\n\nAppended.\n\n## Notes\n\nNew note.\n"
+        );
+
+        assert!(matches!(
+            apply_section_ops(SKILL, &[op(SectionEdit::Replace, "Missing", "x")]),
+            Err(PatchError::SectionNotFound(_))
+        ));
+        assert!(matches!(
+            apply_section_ops(SKILL, &[op(SectionEdit::Replace, "### Usage", "x")]),
+            Err(PatchError::SectionNotFound(_))
+        ));
+        let twice = format!("{}\n## Usage\n\nAgain.\n", SKILL);
+        assert!(matches!(
+            apply_section_ops(&twice, &[op(SectionEdit::Append, "Usage", "x")]),
+            Err(PatchError::AmbiguousSection(_))
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+//! Search and replace across skill files.
+//!
+//! A [`Replacement`] is planned against a set of skill directories with
+//! [`plan`], giving one [`FileEdit`] per file it changes, and written with
+//! [`apply`]. Applying stages every file before renaming any into place
+//! and puts back the files already renamed if one fails, so the library
+//! ends up with all of the edits or none.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobMatcher};
+use regex::{NoExpand, Regex, RegexBuilder};
+use walkdir::WalkDir;
+
+use crate::models::ErrorCode;
+
+/// Files searched when no glob is given.
+pub const DEFAULT_FILES: &str = "*.md";
+
+/// A search pattern and what to replace its matches with.
+#[derive(Debug, Clone)]
+pub struct Replacement {
+    pattern: Regex,
+    replacement: String,
+    literal: bool,
+}
+
+impl Replacement {
+    /// Replace the text `find` with `replace`, as is.
+    pub fn literal(find: &str, replace: &str, case_insensitive: bool) -> Result<Self, ReplaceError> {
+        let mut replacement = Self::regex(&regex::escape(find), replace, case_insensitive)?;
+        replacement.literal = true;
+        Ok(replacement)
+    }
+
+    /// Replace matches of the regex `pattern` with `replace`, in which `$1`
+    /// or `${name}` stand for capture groups.
+    pub fn regex(pattern: &str, replace: &str, case_insensitive: bool) -> Result<Self, ReplaceError> {
+        if pattern.is_empty() {
+            return Err(ReplaceError::EmptyPattern);
+        }
+        let pattern = RegexBuilder::new(pattern)
+            .case_insensitive(case_insensitive)
+            .multi_line(true)
+            .build()
+            .map_err(|e| ReplaceError::InvalidPattern(e.to_string()))?;
+        Ok(Self {
+            pattern,
+            replacement: replace.to_string(),
+            literal: false,
+        })
+    }
+
+    /// Text with every match replaced, and the number of matches.
+    pub fn apply(&self, text: &str) -> (String, usize) {
+        let count = self.pattern.find_iter(text).count();
+        if count == 0 {
+            return (text.to_string(), 0);
+        }
+        let replaced = if self.literal {
+            self.pattern.replace_all(text, NoExpand(&self.replacement))
+        } else {
+            self.pattern.replace_all(text, self.replacement.as_str())
+        };
+        (replaced.into_owned(), count)
+    }
+}
+
+/// A change to one file.
+#[derive(Debug, Clone)]
+pub struct FileEdit {
+    /// Skill the file belongs to.
+    pub skill: String,
+
+    /// Path relative to the skill directory, with `/` separators.
+    pub path: String,
+
+    /// Absolute path of the file.
+    pub full_path: PathBuf,
+
+    /// Content before the edit.
+    pub old: String,
+
+    /// Content after the edit.
+    pub new: String,
+
+    /// Matches replaced.
+    pub replacements: usize,
+}
+
+/// Plan a replacement across skills, given as `(name, directory)`.
+///
+/// Files whose path relative to the skill directory matches `files` are
+/// searched; a glob without `/` matches file names at any depth. Hidden
+/// files and files that aren't UTF-8 text are skipped.
+pub fn plan(
+    skills: &[(String, PathBuf)],
+    replacement: &Replacement,
+    files: &str,
+) -> Result<Vec<FileEdit>, ReplaceError> {
+    let matcher = file_matcher(files)?;
+    let mut edits = Vec::new();
+    for (skill, dir) in skills {
+        let entries = WalkDir::new(dir)
+            .follow_links(false)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'));
+        for entry in entries.filter_map(Result::ok) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(dir) else {
+                continue;
+            };
+            let path = relative.to_string_lossy().replace('\\', "/");
+            if !matcher.is_match(&path) {
+                continue;
+            }
+            let Ok(old) = fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            let (new, replacements) = replacement.apply(&old);
+            if replacements > 0 && new != old {
+                edits.push(FileEdit {
+                    skill: skill.clone(),
+                    path,
+                    full_path: entry.path().to_path_buf(),
+                    old,
+                    new,
+                    replacements,
+                });
+            }
+        }
+    }
+    Ok(edits)
+}
+
+fn file_matcher(files: &str) -> Result<GlobMatcher, ReplaceError> {
+    let glob = if files.contains('/') {
+        files.to_string()
+    } else {
+        format!("**/{}", files)
+    };
+    Glob::new(&glob)
+        .map(|g| g.compile_matcher())
+        .map_err(|e| ReplaceError::InvalidPattern(e.to_string()))
+}
+
+/// Write planned edits, all or none.
+pub fn apply(edits: &[FileEdit]) -> Result<(), ReplaceError> {
+    let staged: Vec<PathBuf> = edits.iter().map(|edit| staging_path(&edit.full_path)).collect();
+    let discard = |staged: &[PathBuf]| {
+        for tmp in staged {
+            let _ = fs::remove_file(tmp);
+        }
+    };
+
+    for (edit, tmp) in edits.iter().zip(&staged) {
+        if let Err(e) = fs::write(tmp, &edit.new) {
+            discard(&staged);
+            return Err(e.into());
+        }
+    }
+
+    for (i, (edit, tmp)) in edits.iter().zip(&staged).enumerate() {
+        if let Err(e) = fs::rename(tmp, &edit.full_path) {
+            for done in &edits[..i] {
+                let _ = fs::write(&done.full_path, &done.old);
+            }
+            discard(&staged[i..]);
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+/// Hidden sibling a file's new content is staged in.
+fn staging_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    path.with_file_name(format!(".{}.replace.tmp", name))
+}
+
+/// Errors that can occur while replacing.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplaceError {
+    /// The search pattern is empty.
+    #[error("Search pattern is empty")]
+    EmptyPattern,
+
+    /// The search pattern or file glob doesn't parse.
+    #[error("Invalid pattern: {0}")]
+    InvalidPattern(String),
+
+    /// Filesystem error.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl ReplaceError {
+    /// Machine-readable code of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ReplaceError::EmptyPattern | ReplaceError::InvalidPattern(_) => ErrorCode::InvalidRequest,
+            ReplaceError::Io(_) => ErrorCode::IoError,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn library(temp: &TempDir) -> Vec<(String, PathBuf)> {
+        for (path, content) in [
+            ("forms/SKILL.md", "# Forms\n\nRun `oldtool lint` first.\n"),
+            ("forms/references/cli.md", "OldTool flags\n"),
+            ("forms/_meta.json", r#"{"name": "forms", "description": "Uses oldtool"}"#),
+            ("billing/SKILL.md", "# Billing\n\nNothing here.\n"),
+        ] {
+            let path = temp.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        ["billing", "forms"].iter().map(|n| (n.to_string(), temp.path().join(n))).collect()
+    }
+
+    #[test]
+    fn test_replacement() {
+        let literal = Replacement::literal("a.b", "$1", false).unwrap();
+        assert_eq!(literal.apply("a.b axb a.b"), ("$1 axb $1".to_string(), 2));
+
+        let regex = Replacement::regex(r"v(\d+)", "version $1", false).unwrap();
+        assert_eq!(regex.apply("v1 and v22"), ("version 1 and version 22".to_string(), 2));
+
+        let anchored = Replacement::regex("^- ", "* ", false).unwrap();
+        assert_eq!(anchored.apply("- a\n- b").1, 2);
+
+        assert!(matches!(Replacement::regex("", "x", false), Err(ReplaceError::EmptyPattern)));
+        assert!(matches!(Replacement::regex("(", "x", false), Err(ReplaceError::InvalidPattern(_))));
+    }
+
+    #[test]
+    fn test_plan_and_apply() {
+        let temp = TempDir::new().unwrap();
+        let skills = library(&temp);
+
+        let replacement = Replacement::literal("oldtool", "newtool", true).unwrap();
+        let edits = plan(&skills, &replacement, DEFAULT_FILES).unwrap();
+        let paths: Vec<&str> = edits.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "references/cli.md"]);
+        assert_eq!(edits[1].new, "newtool flags\n");
+
+        let edits_all = plan(&skills, &replacement, "*").unwrap();
+        assert_eq!(edits_all.len(), 3);
+        let references = plan(&skills, &replacement, "references/*.md").unwrap();
+        assert_eq!(references.len(), 1);
+
+        // Planning writes nothing
+        assert!(fs::read_to_string(temp.path().join("forms/SKILL.md")).unwrap().contains("oldtool"));
+
+        apply(&edits).unwrap();
+        assert_eq!(
+            fs::read_to_string(temp.path().join("forms/SKILL.md")).unwrap(),
+            "# Forms\n\nRun `newtool lint` first.\n"
+        );
+        assert_eq!(fs::read_to_string(temp.path().join("forms/references/cli.md")).unwrap(), "newtool flags\n");
+        let leftovers: Vec<_> = WalkDir::new(temp.path())
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn test_apply_is_all_or_nothing() {
+        let temp = TempDir::new().unwrap();
+        let skills = library(&temp);
+        let replacement = Replacement::literal("oldtool", "newtool", true).unwrap();
+        let mut edits = plan(&skills, &replacement, DEFAULT_FILES).unwrap();
+
+        // A file whose directory is gone can't be staged
+        edits.push(FileEdit {
+            skill: "gone".to_string(),
+            path: "SKILL.md".to_string(),
+            full_path: temp.path().join("gone/SKILL.md"),
+            old: String::new(),
+            new: "x".to_string(),
+            replacements: 1,
+        });
+        assert!(apply(&edits).is_err());
+        assert!(fs::read_to_string(temp.path().join("forms/SKILL.md")).unwrap().contains("oldtool"));
+        assert!(!temp.path().join("forms/.SKILL.md.replace.tmp").exists());
+    }
+}
@@ -0,0 +1,275 @@
+//! Periodic digest reports on the skills library.
+//!
+//! The weekly digest summarizes new skills, recently updated skills, the
+//! most used skills, and open flags (validation errors, broken links,
+//! shadowed skills, integrity and maintenance state). It renders as
+//! markdown or HTML and can be delivered through the notification adapters.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::index::{file_times, FileTimes, LinkGraph};
+use crate::integrity::IntegrityStatus;
+use crate::mcp::tools::ServiceContext;
+use crate::notify::{Alert, Severity};
+
+/// Number of top-used skills listed in a digest.
+const TOP_SKILLS: usize = 5;
+
+/// A skill updated during the report period.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillUpdate {
+    /// Skill name.
+    pub name: String,
+
+    /// Files modified during the period, relative to the skill directory.
+    pub files: Vec<String>,
+
+    /// Most recent modification.
+    #[serde(with = "crate::models::timestamp")]
+    pub last_modified: DateTime<Utc>,
+}
+
+/// A heavily used skill.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillUsage {
+    /// Skill name (or `skill:sub-skill`).
+    pub name: String,
+
+    /// Loads since the server started.
+    pub loads: u64,
+}
+
+/// Digest of library activity over a period.
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    /// Start of the period.
+    #[serde(with = "crate::models::timestamp")]
+    pub period_start: DateTime<Utc>,
+
+    /// End of the period.
+    #[serde(with = "crate::models::timestamp")]
+    pub period_end: DateTime<Utc>,
+
+    /// Skills created during the period.
+    pub new_skills: Vec<String>,
+
+    /// Existing skills modified during the period.
+    pub updated_skills: Vec<SkillUpdate>,
+
+    /// Most loaded skills since the server started.
+    pub top_skills: Vec<SkillUsage>,
+
+    /// Open problems needing a human.
+    pub flags: Vec<String>,
+}
+
+impl Digest {
+    /// Build the digest for the week ending now.
+    pub fn weekly(ctx: &ServiceContext) -> Self {
+        let end = Utc::now();
+        Self::build(ctx, end - Duration::days(7), end)
+    }
+
+    /// Build the digest for `[start, end]`.
+    pub fn build(ctx: &ServiceContext, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        let index = ctx.indexer.get_skill_index();
+        let in_period = |t: DateTime<Utc>| t >= start && t <= end;
+
+        let mut new_skills = Vec::new();
+        let mut updated_skills = Vec::new();
+        for skill in &index.skills {
+            let files = file_times(&ctx.indexer.skill_dir(&skill.name));
+            let Some(created) = files.iter().map(|f| f.created).min() else {
+                continue;
+            };
+
+            if in_period(created) {
+                new_skills.push(skill.name.clone());
+                continue;
+            }
+
+            let changed: Vec<&FileTimes> = files.iter().filter(|f| in_period(f.modified)).collect();
+            if let Some(last_modified) = changed.iter().map(|f| f.modified).max() {
+                updated_skills.push(SkillUpdate {
+                    name: skill.name.clone(),
+                    files: changed.iter().map(|f| f.path.clone()).collect(),
+                    last_modified,
+                });
+            }
+        }
+        updated_skills.sort_by_key(|u| std::cmp::Reverse(u.last_modified));
+
+        let top_skills = ctx
+            .stats
+            .read()
+            .top_skills(TOP_SKILLS)
+            .into_iter()
+            .map(|(name, loads)| SkillUsage {
+                name: name.clone(),
+                loads: *loads,
+            })
+            .collect();
+
+        Self {
+            period_start: start,
+            period_end: end,
+            new_skills,
+            updated_skills,
+            top_skills,
+            flags: open_flags(ctx),
+        }
+    }
+
+    /// Render as markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Skills weekly digest\n\n{} – {}\n",
+            self.period_start.format("%Y-%m-%d"),
+            self.period_end.format("%Y-%m-%d")
+        );
+
+        for (title, items) in self.sections() {
+            out.push_str(&format!("\n## {}\n\n", title));
+            if items.is_empty() {
+                out.push_str("_None_\n");
+            }
+            for item in items {
+                out.push_str(&format!("- {}\n", item));
+            }
+        }
+        out
+    }
+
+    /// Render as a standalone HTML page.
+    pub fn to_html(&self) -> String {
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Skills weekly digest</title></head>\n<body>\n<h1>Skills weekly digest</h1>\n<p>{} – {}</p>\n",
+            self.period_start.format("%Y-%m-%d"),
+            self.period_end.format("%Y-%m-%d")
+        );
+
+        for (title, items) in self.sections() {
+            out.push_str(&format!("<h2>{}</h2>\n", escape_html(title)));
+            if items.is_empty() {
+                out.push_str("<p><em>None</em></p>\n");
+                continue;
+            }
+            out.push_str("<ul>\n");
+            for item in items {
+                out.push_str(&format!("<li>{}</li>\n", escape_html(&item)));
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Send the digest through the notification adapters.
+    pub fn deliver(&self, ctx: &ServiceContext) {
+        ctx.notifiers
+            .send(&Alert::new("report.weekly", Severity::Info, self.to_markdown()));
+    }
+
+    /// Section titles with their list items, shared by both renderers.
+    fn sections(&self) -> Vec<(&'static str, Vec<String>)> {
+        vec![
+            ("New skills", self.new_skills.clone()),
+            (
+                "Updated skills",
+                self.updated_skills
+                    .iter()
+                    .map(|u| format!("{} ({} files)", u.name, u.files.len()))
+                    .collect(),
+            ),
+            (
+                "Top used skills",
+                self.top_skills
+                    .iter()
+                    .map(|s| format!("{}: {} loads", s.name, s.loads))
+                    .collect(),
+            ),
+            ("Open flags", self.flags.clone()),
+        ]
+    }
+}
+
+/// Problems currently open in the library.
+fn open_flags(ctx: &ServiceContext) -> Vec<String> {
+    let index = ctx.indexer.get_skill_index();
+    let mut flags: Vec<String> = index.validation_errors.clone();
+
+    let links = LinkGraph::new(ctx.indexer.skills_dir(), &index, &ctx.indexer.get_content_index());
+    for broken in links.broken() {
+        flags.push(format!(
+            "{}/{}: broken link to {}",
+            broken.source_skill, broken.source_file, broken.target
+        ));
+    }
+
+    for shadowed in &index.shadowed {
+        flags.push(format!(
+            "{}: copy in {} is shadowed by {}",
+            shadowed.name, shadowed.root, shadowed.shadowed_by
+        ));
+    }
+
+    if let Some(report) = ctx.integrity.read().as_ref() {
+        if report.status == IntegrityStatus::Tampered {
+            flags.push("Skills directory differs from its integrity manifest".to_string());
+        }
+    }
+    if let Some(banner) = ctx.maintenance.banner() {
+        flags.push(format!("Maintenance mode is enabled: {}", banner.message));
+    }
+
+    flags
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::SkillIndexer;
+    use crate::test_support::write_skill;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_weekly_digest() {
+        let temp_dir = TempDir::new().unwrap();
+        write_skill(
+            temp_dir.path(),
+            "forms",
+            r#"{"name": "forms", "description": "Forms <html>"}"#,
+            "See [gone](../gone/SKILL.md).",
+        );
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let ctx = ServiceContext::new(indexer);
+        ctx.track_skill_load("forms");
+
+        let digest = Digest::weekly(&ctx);
+        assert_eq!(digest.new_skills, vec!["forms"]);
+        assert!(digest.updated_skills.is_empty());
+        assert_eq!(digest.top_skills[0].loads, 1);
+        assert!(digest.flags[0].contains("broken link to ../gone/SKILL.md"));
+
+        let markdown = digest.to_markdown();
+        assert!(markdown.contains("## New skills\n\n- forms\n"));
+        assert!(markdown.contains("## Updated skills\n\n_None_\n"));
+
+        // Nothing happened in a later period
+        let digest = Digest::build(&ctx, Utc::now() + Duration::days(1), Utc::now() + Duration::days(8));
+        assert!(digest.new_skills.is_empty());
+
+        assert!(Digest::weekly(&ctx).to_html().contains("<li>forms: 1 loads</li>"));
+    }
+}
@@ -0,0 +1,205 @@
+//! Skill scaffolding from templates.
+//!
+//! Mirrors `generateSkillContent` and the `skills_create` tool in
+//! `skills-mcp-server/src/tools/crud.ts`.
+
+use std::fs;
+use std::path::Path;
+
+use crate::models::{SkillMeta, SkillTemplate, SubSkillMeta};
+use crate::validation::validate_meta;
+
+/// Render SKILL.md content for a new skill.
+pub fn render_skill_content(name: &str, description: &str, template: SkillTemplate) -> String {
+    let title = name
+        .split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut lines: Vec<String> = vec![
+        "---".to_string(),
+        format!("name: {}", name),
+        format!("description: {}", description),
+        "---".to_string(),
+        String::new(),
+        format!("# {}", title),
+        String::new(),
+        "## Overview".to_string(),
+        String::new(),
+        description.to_string(),
+        String::new(),
+        "## When to Use".to_string(),
+        String::new(),
+        "- [Add trigger conditions here]".to_string(),
+        String::new(),
+    ];
+
+    if template != SkillTemplate::Minimal {
+        lines.extend(
+            [
+                "## Quick Start",
+                "",
+                "```",
+                "// Add example code here",
+                "```",
+                "",
+                "## Best Practices",
+                "",
+                "- [Add best practices here]",
+                "",
+                "## Examples",
+                "",
+                "[Add practical examples here]",
+                "",
+            ]
+            .iter()
+            .map(|s| s.to_string()),
+        );
+    }
+
+    if template == SkillTemplate::WithSubSkills {
+        lines.extend(
+            [
+                "## Sub-Skills",
+                "",
+                "This skill has the following sub-skills:",
+                "",
+                "| Sub-skill | Description |",
+                "|-----------|-------------|",
+                "| example | Example sub-skill |",
+                "",
+                "Use `get_sub_skill` to load specific sub-skill content.",
+                "",
+            ]
+            .iter()
+            .map(|s| s.to_string()),
+        );
+    }
+
+    lines.join("\n")
+}
+
+/// Create a new skill directory from a template.
+///
+/// Returns the list of created files, relative to the skill directory.
+pub fn create_skill(
+    skills_dir: &Path,
+    name: &str,
+    description: &str,
+    tags: Vec<String>,
+    template: SkillTemplate,
+) -> Result<Vec<String>, ScaffoldError> {
+    let sub_skills = (template == SkillTemplate::WithSubSkills).then(|| {
+        vec![SubSkillMeta {
+            name: "example".to_string(),
+            file: "references/example.md".to_string(),
+            triggers: vec![],
+            language: None,
+        }]
+    });
+
+    let meta = SkillMeta {
+        name: name.to_string(),
+        description: description.to_string(),
+        tags,
+        sub_skills,
+        source: Some("created".to_string()),
+        ..Default::default()
+    };
+
+    validate_meta(&meta).map_err(|errors| ScaffoldError::Invalid(errors.join("; ")))?;
+
+    let skill_dir = skills_dir.join(name);
+    if skill_dir.exists() {
+        return Err(ScaffoldError::AlreadyExists(name.to_string()));
+    }
+
+    fs::create_dir_all(&skill_dir)?;
+    let mut created = Vec::new();
+
+    let meta_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| ScaffoldError::Invalid(format!("Failed to serialize meta: {}", e)))?;
+    fs::write(skill_dir.join("_meta.json"), meta_json)?;
+    created.push("_meta.json".to_string());
+
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        render_skill_content(name, description, template),
+    )?;
+    created.push("SKILL.md".to_string());
+
+    if template == SkillTemplate::WithSubSkills {
+        let refs_dir = skill_dir.join("references");
+        fs::create_dir_all(&refs_dir)?;
+        fs::write(
+            refs_dir.join("example.md"),
+            "# Example Sub-Skill\n\nAdd content here.\n",
+        )?;
+        created.push("references/example.md".to_string());
+    }
+
+    Ok(created)
+}
+
+/// Errors that can occur while scaffolding a skill.
+#[derive(Debug, thiserror::Error)]
+pub enum ScaffoldError {
+    /// A skill with this name already exists.
+    #[error("Skill '{0}' already exists")]
+    AlreadyExists(String),
+
+    /// The requested metadata failed validation.
+    #[error("Invalid skill: {0}")]
+    Invalid(String),
+
+    /// Failed to write skill files.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_minimal_template() {
+        let content = render_skill_content("form-handling", "Forms", SkillTemplate::Minimal);
+
+        assert!(content.contains("# Form Handling"));
+        assert!(!content.contains("## Quick Start"));
+    }
+
+    #[test]
+    fn test_create_skill_with_sub_skills() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let created = create_skill(
+            temp_dir.path(),
+            "testing",
+            "Testing patterns",
+            vec!["jest".to_string()],
+            SkillTemplate::WithSubSkills,
+        )
+        .unwrap();
+
+        assert_eq!(created[0], "_meta.json");
+        assert!(temp_dir.path().join("testing/references/example.md").exists());
+
+        let result = create_skill(
+            temp_dir.path(),
+            "testing",
+            "Testing patterns",
+            vec![],
+            SkillTemplate::Minimal,
+        );
+        assert!(matches!(result, Err(ScaffoldError::AlreadyExists(_))));
+    }
+}
@@ -0,0 +1,223 @@
+//! Cron-style schedules for recurring jobs.
+//!
+//! A schedule is the five standard cron fields, evaluated in UTC:
+//!
+//! ```text
+//! minute  hour  day-of-month  month  day-of-week
+//! 0       3     *             *      *            every day at 03:00
+//! */15    *     *             *      1-5          every 15 minutes on weekdays
+//! ```
+//!
+//! Fields take `*`, numbers, ranges (`1-5`), lists (`1,15`), and steps
+//! (`*/15`, `0-30/10`). Days of the week run from 0 (Sunday) to 7 (Sunday
+//! again). As in cron, when both day fields are restricted a day matching
+//! either one fires.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Timelike, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    expr: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the day-of-month and day-of-week fields were `*`.
+    any_day: bool,
+    any_weekday: bool,
+}
+
+/// A cron expression that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid schedule '{expr}': {message}")]
+pub struct ScheduleError {
+    /// The expression as given.
+    pub expr: String,
+    /// What was wrong.
+    pub message: String,
+}
+
+impl Schedule {
+    /// The first time strictly after `after` that the schedule fires, or
+    /// None if it never does (e.g. `0 0 31 2 *`).
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut date = start.date_naive();
+
+        // Every combination of day of month and weekday recurs within 28 years
+        for _ in 0..366 * 28 {
+            if self.matches_day(date.day(), date.month(), date.weekday().num_days_from_sunday()) {
+                let from = if date == start.date_naive() {
+                    (start.hour(), start.minute())
+                } else {
+                    (0, 0)
+                };
+                if let Some((hour, minute)) = self.first_time(from) {
+                    let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+                    return Some(date.and_time(time).and_utc());
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    fn matches_day(&self, day: u32, month: u32, weekday: u32) -> bool {
+        if !bit(self.months, month) {
+            return false;
+        }
+        let day_match = bit(self.days, day);
+        let weekday_match = bit(self.weekdays, weekday);
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (false, true) => day_match,
+            (true, false) => weekday_match,
+            (false, false) => day_match || weekday_match,
+        }
+    }
+
+    /// The first matching hour and minute at or after `from` in a day.
+    fn first_time(&self, from: (u32, u32)) -> Option<(u32, u32)> {
+        (from.0..24).filter(|h| bit(self.hours, *h)).find_map(|hour| {
+            let first_minute = if hour == from.0 { from.1 } else { 0 };
+            (first_minute..60)
+                .find(|m| bit(self.minutes, *m))
+                .map(|minute| (hour, minute))
+        })
+    }
+}
+
+fn bit(set: u64, n: u32) -> bool {
+    set & (1 << n) != 0
+}
+
+impl FromStr for Schedule {
+    type Err = ScheduleError;
+
+    fn from_str(expr: &str) -> Result<Self, Self::Err> {
+        let error = |message: String| ScheduleError {
+            expr: expr.to_string(),
+            message,
+        };
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(error(format!("expected 5 fields, found {}", fields.len())));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7).map_err(|m| error(format!("day of week: {}", m)))?;
+        // 7 is Sunday too
+        if bit(weekdays, 7) {
+            weekdays |= 1;
+        }
+
+        Ok(Self {
+            expr: expr.split_whitespace().collect::<Vec<_>>().join(" "),
+            minutes: parse_field(minute, 0, 59).map_err(|m| error(format!("minute: {}", m)))?,
+            hours: parse_field(hour, 0, 23).map_err(|m| error(format!("hour: {}", m)))?,
+            days: parse_field(day, 1, 31).map_err(|m| error(format!("day of month: {}", m)))?,
+            months: parse_field(month, 1, 12).map_err(|m| error(format!("month: {}", m)))?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+}
+
+/// Parse one cron field into a bit set of the values it allows.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("invalid step '{}'", step))?;
+                if step == 0 {
+                    return Err("step must be at least 1".to_string());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let value = |text: &str| -> Result<u32, String> {
+            match text.parse() {
+                Ok(n) if (min..=max).contains(&n) => Ok(n),
+                _ => Err(format!("'{}' is not a number from {} to {}", text, min, max)),
+            }
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start)?, value(end)?),
+            None if step > 1 => (value(range)?, max),
+            None => (value(range)?, value(range)?),
+        };
+        if start > end {
+            return Err(format!("range '{}' is backwards", range));
+        }
+
+        for n in (start..=end).step_by(step as usize) {
+            set |= 1 << n;
+        }
+    }
+    Ok(set)
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expr)
+    }
+}
+
+impl<'de> Deserialize<'de> for Schedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_next_after() {
+        let daily: Schedule = "0 3 * * *".parse().unwrap();
+        assert_eq!(daily.next_after(at("2026-10-15T02:59:30Z")), Some(at("2026-10-15T03:00:00Z")));
+        assert_eq!(daily.next_after(at("2026-10-15T03:00:00Z")), Some(at("2026-10-16T03:00:00Z")));
+
+        // 2026-10-16 is a Friday
+        let weekdays: Schedule = "*/15 9-17 * * 1-5".parse().unwrap();
+        assert_eq!(weekdays.next_after(at("2026-10-16T17:50:00Z")), Some(at("2026-10-19T09:00:00Z")));
+
+        // Either day field matches when both are restricted
+        let either: Schedule = "0 0 1 * 0".parse().unwrap();
+        assert_eq!(either.next_after(at("2026-10-15T00:00:00Z")), Some(at("2026-10-18T00:00:00Z")));
+
+        let sunday: Schedule = "30 12 * * 7".parse().unwrap();
+        assert_eq!(sunday.next_after(at("2026-10-15T00:00:00Z")), Some(at("2026-10-18T12:30:00Z")));
+
+        let leap: Schedule = "0 0 29 2 *".parse().unwrap();
+        assert_eq!(leap.next_after(at("2026-10-15T00:00:00Z")), Some(at("2028-02-29T00:00:00Z")));
+        let never: Schedule = "0 0 31 2 *".parse().unwrap();
+        assert_eq!(never.next_after(at("2026-10-15T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("0 3 * *".parse::<Schedule>().is_err());
+        assert!("60 * * * *".parse::<Schedule>().is_err());
+        assert!("*/0 * * * *".parse::<Schedule>().is_err());
+        assert!("5-1 * * * *".parse::<Schedule>().is_err());
+        assert_eq!("0  3 * * *".parse::<Schedule>().unwrap().to_string(), "0 3 * * *");
+    }
+}
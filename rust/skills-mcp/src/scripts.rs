@@ -0,0 +1,470 @@
+//! Helper scripts bundled with skills.
+//!
+//! Scripts live in a skill's `scripts/` directory. They can always be
+//! listed; running one is off unless the `[scripts]` section enables it,
+//! and even then only scripts matching the allow list run. Allow-list
+//! globs match `<skill>/<script>`, e.g. `pdf/fill_form.py` or `pdf/*`;
+//! `*` stops at `/`, so scripts in subdirectories need `pdf/**`.
+//!
+//! A script runs in its skill directory with stdin closed and an emptied
+//! environment, apart from the variables passed through. Scripts are run
+//! with the interpreter configured for their extension, or directly when
+//! there is none. Stdout and stderr are captured up to a size limit, and a
+//! script still running at the timeout is killed. Processes a script leaves
+//! running in the background are not tracked.
+//!
+//! ```toml
+//! [scripts]
+//! enabled = true
+//! allow = ["pdf/*.py", "forms/validate.sh"]
+//! timeout_secs = 30
+//! max_output_bytes = 65536
+//! env = ["PATH", "HOME", "LANG"]
+//!
+//! [scripts.interpreters]
+//! py = "python3"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use globset::{GlobBuilder, GlobSetBuilder};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::models::ErrorCode;
+
+/// Directory of a skill holding its scripts.
+pub const SCRIPTS_DIR: &str = "scripts";
+
+/// How often a running script is checked for having exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long output is still read after the script exits.
+const OUTPUT_GRACE: Duration = Duration::from_millis(200);
+
+/// Deepest directory level listed below `scripts/`.
+const MAX_DEPTH: usize = 4;
+
+/// Script settings from the `[scripts]` configuration section.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ScriptsConfig {
+    /// Whether scripts can be run at all.
+    pub enabled: bool,
+
+    /// Globs of `<skill>/<script>` paths that may run.
+    pub allow: Vec<String>,
+
+    /// Seconds a script may run before it is killed.
+    pub timeout_secs: u64,
+
+    /// Bytes of stdout and of stderr kept; the rest is dropped.
+    pub max_output_bytes: usize,
+
+    /// Environment variables passed through to scripts.
+    pub env: Vec<String>,
+
+    /// Interpreter command by file extension, without the dot.
+    pub interpreters: BTreeMap<String, String>,
+}
+
+impl Default for ScriptsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allow: Vec::new(),
+            timeout_secs: 30,
+            max_output_bytes: 64 * 1024,
+            env: vec!["PATH".to_string()],
+            interpreters: [("py", "python3"), ("sh", "sh"), ("js", "node"), ("rb", "ruby")]
+                .into_iter()
+                .map(|(ext, command)| (ext.to_string(), command.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl ScriptsConfig {
+    /// Whether the allow list admits a skill's script.
+    pub fn allows(&self, skill: &str, script: &str) -> bool {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.allow {
+            if let Ok(glob) = GlobBuilder::new(pattern).literal_separator(true).build() {
+                builder.add(glob);
+            }
+        }
+        builder
+            .build()
+            .is_ok_and(|set| set.is_match(format!("{}/{}", skill, script)))
+    }
+
+    /// Whether a skill's script can be run now.
+    pub fn can_run(&self, skill: &str, script: &str) -> bool {
+        self.enabled && self.allows(skill, script)
+    }
+
+    /// Interpreter for a script, from its extension.
+    pub fn interpreter(&self, script: &str) -> Option<&str> {
+        let ext = Path::new(script).extension()?.to_str()?;
+        self.interpreters
+            .iter()
+            .find(|(e, _)| e.eq_ignore_ascii_case(ext))
+            .map(|(_, command)| command.as_str())
+    }
+}
+
+/// A script in a skill's `scripts/` directory.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillScript {
+    /// Path relative to `scripts/`, with `/` separators.
+    pub name: String,
+
+    /// Size in bytes.
+    pub size: u64,
+
+    /// Interpreter it would run with; run directly when None.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interpreter: Option<String>,
+
+    /// Whether it can be run with the current settings.
+    pub runnable: bool,
+}
+
+/// Scripts of a skill, sorted by name. Hidden files and symlinks are left
+/// out.
+pub fn list_scripts(config: &ScriptsConfig, skill: &str, skill_dir: &Path) -> Vec<SkillScript> {
+    let dir = skill_dir.join(SCRIPTS_DIR);
+    WalkDir::new(&dir)
+        .max_depth(MAX_DEPTH)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|entry| {
+            let name = entry.path().strip_prefix(&dir).ok()?.to_string_lossy().replace('\\', "/");
+            Some(SkillScript {
+                size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                interpreter: config.interpreter(&name).map(String::from),
+                runnable: config.can_run(skill, &name),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Captured result of running a script.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScriptOutput {
+    /// Skill the script belongs to.
+    pub skill: String,
+
+    /// Script path relative to `scripts/`.
+    pub script: String,
+
+    /// Exit code; None when killed, by the timeout or a signal.
+    pub exit_code: Option<i32>,
+
+    /// Captured stdout, lossily decoded.
+    pub stdout: String,
+
+    /// Captured stderr, lossily decoded.
+    pub stderr: String,
+
+    /// Whether the script was killed at the timeout.
+    pub timed_out: bool,
+
+    /// Whether stdout or stderr went over `max_output_bytes` and was cut.
+    pub truncated: bool,
+
+    /// Wall-clock run time in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Run a skill's script with arguments.
+pub fn run_script(
+    config: &ScriptsConfig,
+    skill: &str,
+    skill_dir: &Path,
+    script: &str,
+    args: &[String],
+) -> Result<ScriptOutput, ScriptError> {
+    if !config.enabled {
+        return Err(ScriptError::Disabled);
+    }
+    let path = resolve_script(skill_dir, script)?;
+    if !config.allows(skill, script) {
+        return Err(ScriptError::NotAllowed(format!("{}/{}", skill, script)));
+    }
+
+    let mut command = match config.interpreter(script) {
+        Some(interpreter) => {
+            let mut parts = interpreter.split_whitespace();
+            let mut command = Command::new(parts.next().unwrap_or(interpreter));
+            command.args(parts).arg(&path);
+            command
+        }
+        None => Command::new(&path),
+    };
+    command
+        .args(args)
+        .current_dir(skill_dir)
+        .env_clear()
+        .envs(config.env.iter().filter_map(|name| Some((name, std::env::var_os(name)?))))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let started = Instant::now();
+    let mut child = command.spawn()?;
+    let limit = config.max_output_bytes;
+    let stdout = child.stdout.take().map(|pipe| capture(pipe, limit));
+    let stderr = child.stderr.take().map(|pipe| capture(pipe, limit));
+
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            timed_out = true;
+            let _ = child.kill();
+            break child.wait()?;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    // A process the script left running can hold the pipes open; don't
+    // wait on it for long
+    let drained = Instant::now() + OUTPUT_GRACE;
+    let collect = |captured: Option<Captured>| {
+        let Some(captured) = captured else {
+            return (Vec::new(), false);
+        };
+        while !captured.done() && Instant::now() < drained {
+            thread::sleep(POLL_INTERVAL);
+        }
+        captured.take()
+    };
+    let (stdout, stdout_cut) = collect(stdout);
+    let (stderr, stderr_cut) = collect(stderr);
+
+    Ok(ScriptOutput {
+        skill: skill.to_string(),
+        script: script.to_string(),
+        exit_code: if timed_out { None } else { status.code() },
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        timed_out,
+        truncated: stdout_cut || stderr_cut,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// Path of a script, checked to be a file inside the skill's `scripts/`
+/// directory, symlinks resolved.
+fn resolve_script(skill_dir: &Path, script: &str) -> Result<PathBuf, ScriptError> {
+    let relative = Path::new(script);
+    let plain = relative.components().all(|c| matches!(c, Component::Normal(_)));
+    if script.is_empty() || !plain {
+        return Err(ScriptError::Invalid(format!("Invalid script path '{}'", script)));
+    }
+
+    let dir = skill_dir.join(SCRIPTS_DIR);
+    let not_found = || ScriptError::NotFound(script.to_string());
+    let path = fs::canonicalize(dir.join(relative)).map_err(|_| not_found())?;
+    let dir = fs::canonicalize(&dir).map_err(|_| not_found())?;
+    if !path.starts_with(&dir) {
+        return Err(ScriptError::Invalid(format!("Script '{}' is outside scripts/", script)));
+    }
+    if !path.is_file() {
+        return Err(not_found());
+    }
+    Ok(path)
+}
+
+/// Output read from a pipe so far, by a reader thread.
+#[derive(Default)]
+struct CapturedOutput {
+    kept: Vec<u8>,
+    truncated: bool,
+    done: bool,
+}
+
+/// Handle to a pipe being read in the background.
+struct Captured(Arc<Mutex<CapturedOutput>>);
+
+impl Captured {
+    fn done(&self) -> bool {
+        self.0.lock().done
+    }
+
+    fn take(&self) -> (Vec<u8>, bool) {
+        let mut output = self.0.lock();
+        (std::mem::take(&mut output.kept), output.truncated)
+    }
+}
+
+/// Read a pipe to the end in the background, keeping the first `limit`
+/// bytes. Reading on past the limit keeps the script from blocking on a
+/// full pipe.
+fn capture(mut pipe: impl Read + Send + 'static, limit: usize) -> Captured {
+    let output = Arc::new(Mutex::new(CapturedOutput::default()));
+    let shared = Arc::clone(&output);
+    thread::spawn(move || {
+        let mut buf = [0u8; 8192];
+        loop {
+            let read = pipe.read(&mut buf);
+            let mut output = shared.lock();
+            match read {
+                Ok(0) | Err(_) => {
+                    output.done = true;
+                    break;
+                }
+                Ok(n) => {
+                    let room = limit.saturating_sub(output.kept.len());
+                    output.kept.extend_from_slice(&buf[..n.min(room)]);
+                    output.truncated |= n > room;
+                }
+            }
+        }
+    });
+    Captured(output)
+}
+
+/// Errors that can occur while running a script.
+#[derive(Debug, thiserror::Error)]
+pub enum ScriptError {
+    /// Running scripts is turned off.
+    #[error("Running skill scripts is disabled")]
+    Disabled,
+
+    /// The script isn't on the allow list.
+    #[error("Script '{0}' is not allowed to run")]
+    NotAllowed(String),
+
+    /// The script doesn't exist.
+    #[error("Script '{0}' not found")]
+    NotFound(String),
+
+    /// The script path is malformed or leaves `scripts/`.
+    #[error("{0}")]
+    Invalid(String),
+
+    /// The script couldn't be started or waited for.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl ScriptError {
+    /// Machine-readable code of the error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ScriptError::Disabled | ScriptError::NotAllowed(_) => ErrorCode::Disabled,
+            ScriptError::NotFound(_) => ErrorCode::NotFound,
+            ScriptError::Invalid(_) => ErrorCode::InvalidPath,
+            ScriptError::Io(_) => ErrorCode::IoError,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn skill_with_scripts() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let scripts = temp.path().join("pdf").join(SCRIPTS_DIR);
+        fs::create_dir_all(scripts.join("lib")).unwrap();
+        fs::write(scripts.join("echo.sh"), "echo \"out:$1\"\necho err >&2\n").unwrap();
+        fs::write(scripts.join("loud.sh"), "i=0\nwhile [ $i -lt 2000 ]; do echo 0123456789; i=$((i+1)); done\n").unwrap();
+        fs::write(scripts.join("slow.sh"), "sleep 5\n").unwrap();
+        fs::write(scripts.join("lib/util.py"), "").unwrap();
+        fs::write(scripts.join(".cache"), "").unwrap();
+        temp
+    }
+
+    fn config(allow: &[&str]) -> ScriptsConfig {
+        ScriptsConfig {
+            enabled: true,
+            allow: allow.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_list_scripts() {
+        let temp = skill_with_scripts();
+        let scripts = list_scripts(&config(&["pdf/*.sh"]), "pdf", &temp.path().join("pdf"));
+        let names: Vec<&str> = scripts.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["echo.sh", "lib/util.py", "loud.sh", "slow.sh"]);
+        assert_eq!(scripts[1].interpreter.as_deref(), Some("python3"));
+        assert!(scripts[0].runnable);
+        assert!(!scripts[1].runnable);
+
+        let disabled = list_scripts(&ScriptsConfig::default(), "pdf", &temp.path().join("pdf"));
+        assert!(disabled.iter().all(|s| !s.runnable));
+        assert!(list_scripts(&ScriptsConfig::default(), "forms", &temp.path().join("forms")).is_empty());
+    }
+
+    #[test]
+    fn test_allow_list_separators() {
+        assert!(config(&["pdf/*"]).allows("pdf", "echo.sh"));
+        assert!(!config(&["pdf/*"]).allows("pdf", "lib/util.py"));
+        assert!(!config(&["*/echo.sh"]).allows("pdf", "lib/echo.sh"));
+        assert!(config(&["pdf/**"]).allows("pdf", "lib/util.py"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_script() {
+        let temp = skill_with_scripts();
+        let dir = temp.path().join("pdf");
+        let config = config(&["pdf/*"]);
+
+        let output = run_script(&config, "pdf", &dir, "echo.sh", &["a b".to_string()]).unwrap();
+        assert_eq!(output.exit_code, Some(0));
+        assert_eq!(output.stdout, "out:a b\n");
+        assert_eq!(output.stderr, "err\n");
+        assert!(!output.timed_out && !output.truncated);
+
+        let small = ScriptsConfig {
+            max_output_bytes: 100,
+            ..config.clone()
+        };
+        let output = run_script(&small, "pdf", &dir, "loud.sh", &[]).unwrap();
+        assert_eq!(output.stdout.len(), 100);
+        assert!(output.truncated);
+
+        let quick = ScriptsConfig {
+            timeout_secs: 0,
+            ..config.clone()
+        };
+        let output = run_script(&quick, "pdf", &dir, "slow.sh", &[]).unwrap();
+        assert!(output.timed_out);
+        assert_eq!(output.exit_code, None);
+    }
+
+    #[test]
+    fn test_run_script_refusals() {
+        let temp = skill_with_scripts();
+        let dir = temp.path().join("pdf");
+        let run = |config: &ScriptsConfig, script: &str| run_script(config, "pdf", &dir, script, &[]);
+
+        assert!(matches!(run(&ScriptsConfig::default(), "echo.sh"), Err(ScriptError::Disabled)));
+        assert!(matches!(run(&config(&["pdf/*.py"]), "echo.sh"), Err(ScriptError::NotAllowed(_))));
+        assert!(matches!(run(&config(&["**"]), "../pdf/scripts/echo.sh"), Err(ScriptError::Invalid(_))));
+        assert!(matches!(run(&config(&["**"]), "/bin/sh"), Err(ScriptError::Invalid(_))));
+        assert!(matches!(run(&config(&["**"]), "missing.sh"), Err(ScriptError::NotFound(_))));
+    }
+}
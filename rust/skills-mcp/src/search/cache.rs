@@ -0,0 +1,226 @@
+//! Cache of search results.
+//!
+//! Agents tend to repeat the same queries, and every search scans the whole
+//! index. Results are cached by query and options for one index generation;
+//! the first lookup after the index changes drops everything cached before.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::models::{SearchOptions, SearchResults};
+
+/// Hit and miss counts of the search cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SearchCacheStats {
+    /// Result sets the cache holds at most.
+    pub capacity: usize,
+    /// Result sets cached now.
+    pub entries: usize,
+    /// Index generation the cached results belong to.
+    pub generation: u64,
+    /// Searches answered from the cache.
+    pub hits: u64,
+    /// Searches that ran against the index.
+    pub misses: u64,
+    /// Share of searches answered from the cache, from 0 to 1.
+    pub hit_rate: f64,
+}
+
+/// LRU cache of search results for the current index generation.
+#[derive(Debug)]
+pub struct SearchCache {
+    inner: Mutex<CacheInner>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    capacity: usize,
+    generation: u64,
+    results: HashMap<String, CachedResults>,
+    /// Incremented on every access, for LRU ordering.
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct CachedResults {
+    results: SearchResults,
+    last_used: u64,
+}
+
+impl Default for SearchCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl SearchCache {
+    /// Result sets cached unless configured otherwise.
+    pub const DEFAULT_CAPACITY: usize = 256;
+
+    /// Create a cache holding up to `capacity` result sets; 0 disables
+    /// caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(CacheInner {
+                capacity,
+                ..Default::default()
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Change how many result sets are kept, dropping the least recently
+    /// used ones if there are more.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut inner = self.inner.lock();
+        inner.capacity = capacity;
+        inner.evict();
+    }
+
+    /// The cached results of a search, or those `search` computes.
+    ///
+    /// `kind` names the search (`skills`, `content`), so different searches
+    /// with the same query don't share results. Results cached for another
+    /// index generation are dropped first.
+    pub fn get_or_insert_with(
+        &self,
+        kind: &str,
+        query: &str,
+        options: &SearchOptions,
+        generation: u64,
+        search: impl FnOnce() -> SearchResults,
+    ) -> SearchResults {
+        // Options hold floats, so they are keyed by their debug form
+        let key = format!("{}\0{}\0{:?}", kind, query, options);
+
+        {
+            let mut inner = self.inner.lock();
+            inner.start_generation(generation);
+            inner.tick += 1;
+            let tick = inner.tick;
+            if let Some(cached) = inner.results.get_mut(&key) {
+                cached.last_used = tick;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return cached.results.clone();
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let results = search();
+
+        let mut inner = self.inner.lock();
+        if inner.capacity > 0 && inner.generation == generation {
+            let last_used = inner.tick;
+            inner.results.insert(
+                key,
+                CachedResults {
+                    results: results.clone(),
+                    last_used,
+                },
+            );
+            inner.evict();
+        }
+        results
+    }
+
+    /// Drop every cached result set. Counters are kept.
+    pub fn clear(&self) {
+        self.inner.lock().results.clear();
+    }
+
+    /// Current size and counters.
+    pub fn stats(&self) -> SearchCacheStats {
+        let inner = self.inner.lock();
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        SearchCacheStats {
+            capacity: inner.capacity,
+            entries: inner.results.len(),
+            generation: inner.generation,
+            hits,
+            misses,
+            hit_rate: if hits + misses == 0 {
+                0.0
+            } else {
+                hits as f64 / (hits + misses) as f64
+            },
+        }
+    }
+}
+
+impl CacheInner {
+    /// Drop results of earlier generations when the index has moved on.
+    fn start_generation(&mut self, generation: u64) {
+        if generation > self.generation {
+            self.results.clear();
+            self.generation = generation;
+        }
+    }
+
+    /// Drop least recently used result sets until within capacity.
+    fn evict(&mut self) {
+        while self.results.len() > self.capacity {
+            let Some(oldest) = self
+                .results
+                .iter()
+                .min_by_key(|(_, r)| r.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.results.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(query: &str) -> SearchResults {
+        SearchResults::new(query.to_string(), Vec::new(), None)
+    }
+
+    #[test]
+    fn test_search_cache() {
+        let cache = SearchCache::new(2);
+        let options = SearchOptions::default();
+
+        cache.get_or_insert_with("skills", "forms", &options, 1, || results("forms"));
+        cache.get_or_insert_with("skills", "forms", &options, 1, || panic!("cached"));
+        assert_eq!((cache.stats().hits, cache.stats().misses), (1, 1));
+        assert_eq!(cache.stats().hit_rate, 0.5);
+
+        // Kind and options are part of the key
+        cache.get_or_insert_with("content", "forms", &options, 1, || results("forms"));
+        cache.get_or_insert_with("skills", "forms", &SearchOptions::with_limit(1), 1, || results("forms"));
+        assert_eq!(cache.stats().misses, 3);
+        assert_eq!(cache.stats().entries, 2);
+
+        // A new generation starts empty
+        let mut searched = false;
+        cache.get_or_insert_with("skills", "forms", &SearchOptions::with_limit(1), 2, || {
+            searched = true;
+            results("forms")
+        });
+        assert!(searched);
+        assert_eq!(cache.stats().entries, 1);
+        assert_eq!(cache.stats().generation, 2);
+
+        // Results computed against an older generation aren't kept
+        cache.get_or_insert_with("skills", "tables", &options, 1, || results("tables"));
+        assert_eq!(cache.stats().entries, 1);
+
+        let disabled = SearchCache::new(0);
+        disabled.get_or_insert_with("skills", "forms", &options, 1, || results("forms"));
+        disabled.get_or_insert_with("skills", "forms", &options, 1, || results("forms"));
+        assert_eq!(disabled.stats().entries, 0);
+        assert_eq!(disabled.stats().misses, 2);
+    }
+}
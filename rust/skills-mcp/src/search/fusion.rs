@@ -0,0 +1,108 @@
+//! Multi-query fusion for long or vague task descriptions.
+//!
+//! A task description is expanded into several rule-based query variants,
+//! each variant is searched independently, and the ranked lists are merged
+//! with Reciprocal Rank Fusion (RRF).
+
+use std::collections::HashMap;
+
+use crate::models::SearchResult;
+
+use super::stopwords::keywords;
+
+/// RRF damping constant; 60 is the value from the original RRF paper.
+const RRF_K: f64 = 60.0;
+
+/// Maximum number of query variants generated for one task.
+pub const MAX_VARIANTS: usize = 12;
+
+/// Generate query variants for a task description.
+///
+/// Variants, in order: all keywords together, adjacent keyword pairs (both
+/// space- and hyphen-joined, to hit multi-word tags), then single keywords.
+pub fn generate_variants(task: &str) -> Vec<String> {
+    let mut terms = keywords(task);
+    let mut seen = std::collections::HashSet::new();
+    terms.retain(|t| seen.insert(t.clone()));
+
+    let mut variants = Vec::new();
+    let mut push = |v: String| {
+        if !v.is_empty() && !variants.contains(&v) && variants.len() < MAX_VARIANTS {
+            variants.push(v);
+        }
+    };
+
+    push(terms.join(" "));
+
+    for pair in terms.windows(2) {
+        push(format!("{} {}", pair[0], pair[1]));
+        push(format!("{}-{}", pair[0], pair[1]));
+    }
+
+    for term in &terms {
+        push(term.clone());
+    }
+
+    variants
+}
+
+/// Fuse several ranked result lists with Reciprocal Rank Fusion.
+///
+/// Results are keyed by domain and sub-skill; the first-seen result keeps
+/// its match type and snippet, and its score becomes the fused score.
+pub fn reciprocal_rank_fusion(lists: Vec<Vec<SearchResult>>) -> Vec<SearchResult> {
+    let mut fused: HashMap<String, SearchResult> = HashMap::new();
+    let mut scores: HashMap<String, f64> = HashMap::new();
+
+    for list in lists {
+        for (rank, result) in list.into_iter().enumerate() {
+            let key = result.display_id();
+            *scores.entry(key.clone()).or_insert(0.0) += 1.0 / (RRF_K + rank as f64 + 1.0);
+            fused.entry(key).or_insert(result);
+        }
+    }
+
+    fused
+        .into_iter()
+        .map(|(key, mut result)| {
+            result.score = scores[&key];
+            result
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MatchType;
+
+    #[test]
+    fn test_generate_variants() {
+        let variants = generate_variants("I need to build a form with validation");
+
+        assert_eq!(variants[0], "build form validation");
+        assert!(variants.contains(&"form-validation".to_string()));
+        assert!(variants.contains(&"validation".to_string()));
+        assert!(variants.len() <= MAX_VARIANTS);
+    }
+
+    #[test]
+    fn test_fusion_rewards_consensus() {
+        let a = vec![
+            SearchResult::new("forms".to_string(), 1.0, MatchType::Name),
+            SearchResult::new("testing".to_string(), 0.9, MatchType::Name),
+        ];
+        let b = vec![
+            SearchResult::new("testing".to_string(), 1.0, MatchType::Tags),
+            SearchResult::new("forms".to_string(), 0.5, MatchType::Tags),
+        ];
+        let c = vec![SearchResult::new("forms".to_string(), 0.2, MatchType::Content)];
+
+        let mut fused = reciprocal_rank_fusion(vec![a, b, c]);
+        fused.sort();
+
+        assert_eq!(fused[0].domain, "forms");
+        assert_eq!(fused[0].match_type, MatchType::Name);
+        assert_eq!(fused.len(), 2);
+    }
+}
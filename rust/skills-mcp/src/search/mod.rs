@@ -1,7 +1,17 @@
 //! Search services for skills and content.
 
+mod cache;
+mod fusion;
+mod query;
 mod service;
 mod snippet;
+mod stopwords;
+mod triggers;
 
+pub use cache::{SearchCache, SearchCacheStats};
+pub use fusion::{generate_variants, reciprocal_rank_fusion};
+pub use query::{Expr, Query, QueryTarget};
 pub use service::SearchService;
-pub use snippet::extract_snippet;
+pub use snippet::{extract_snippet, highlight_snippet, highlight_terms, Snippet};
+pub use stopwords::{is_stop_word, keywords};
+pub use triggers::{parse_trigger_cases, test_triggers, DEFAULT_TRIGGER_MIN_SCORE, PARTIAL_WEIGHT};
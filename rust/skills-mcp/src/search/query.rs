@@ -0,0 +1,405 @@
+//! Search query language.
+//!
+//! Queries are words matched anywhere in a skill, plus:
+//!
+//! - `"quoted phrases"`, matched as a whole
+//! - `tag:testing` and `domain:forms` field filters (`tag:"two words"` too)
+//! - `-excluded` or `NOT excluded` terms
+//! - `AND` / `OR` (uppercase) and parentheses for grouping; words next to
+//!   each other are ANDed
+//!
+//! Stop words like "how" or "the" are dropped unless the query has nothing
+//! else. Parsing never fails: an unclosed quote runs to the end of the
+//! query, unbalanced parentheses are closed or ignored, and dangling
+//! operators are skipped, so anything typed into a search box searches.
+
+use crate::models::collection_of;
+
+use super::is_stop_word;
+
+/// A parsed query expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    /// A lowercase word.
+    Term(String),
+    /// A lowercase phrase, whitespace collapsed.
+    Phrase(String),
+    /// `tag:value`: the skill has this tag.
+    Tag(String),
+    /// `domain:value`: the skill, or its collection, has this name.
+    Domain(String),
+    /// The expression doesn't match.
+    Not(Box<Expr>),
+    /// Every expression matches.
+    And(Vec<Expr>),
+    /// Any expression matches.
+    Or(Vec<Expr>),
+}
+
+/// Something a query can be evaluated against: a skill or a content entry.
+pub trait QueryTarget {
+    /// Whether the target's text contains a lowercase term or phrase.
+    fn contains(&self, text: &str) -> bool;
+
+    /// Whether the target's skill has a tag, ignoring case.
+    fn has_tag(&self, tag: &str) -> bool;
+
+    /// The name of the target's skill.
+    fn skill_name(&self) -> &str;
+}
+
+impl Expr {
+    fn matches(&self, target: &impl QueryTarget) -> bool {
+        match self {
+            Self::Term(text) | Self::Phrase(text) => target.contains(text),
+            Self::Tag(tag) => target.has_tag(tag),
+            Self::Domain(domain) => {
+                let name = target.skill_name();
+                name == domain || collection_of(name) == Some(domain.as_str())
+            }
+            Self::Not(expr) => !expr.matches(target),
+            Self::And(exprs) => exprs.iter().all(|e| e.matches(target)),
+            Self::Or(exprs) => exprs.iter().any(|e| e.matches(target)),
+        }
+    }
+
+    /// Add the terms and phrases that aren't negated.
+    fn collect_terms<'a>(&'a self, terms: &mut Vec<&'a str>) {
+        match self {
+            Self::Term(text) | Self::Phrase(text) => {
+                if !terms.contains(&text.as_str()) {
+                    terms.push(text);
+                }
+            }
+            Self::And(exprs) | Self::Or(exprs) => {
+                exprs.iter().for_each(|e| e.collect_terms(terms));
+            }
+            Self::Tag(_) | Self::Domain(_) | Self::Not(_) => {}
+        }
+    }
+
+    fn has_tag_filter(&self) -> bool {
+        match self {
+            Self::Tag(_) => true,
+            Self::And(exprs) | Self::Or(exprs) => exprs.iter().any(Self::has_tag_filter),
+            _ => false,
+        }
+    }
+}
+
+/// A parsed search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    expr: Option<Expr>,
+}
+
+impl Query {
+    /// Parse a query.
+    pub fn parse(input: &str) -> Self {
+        let expr = Parser::new(input, true)
+            .parse()
+            .or_else(|| Parser::new(input, false).parse());
+        Self { expr }
+    }
+
+    /// The parsed expression, or None for an empty query.
+    pub fn expr(&self) -> Option<&Expr> {
+        self.expr.as_ref()
+    }
+
+    /// Whether the query is only words, with no phrases, filters, or
+    /// operators. Plain queries keep the ranking of ordinary keyword search,
+    /// where matching any word is enough.
+    pub fn is_plain(&self) -> bool {
+        match &self.expr {
+            None | Some(Expr::Term(_)) => true,
+            Some(Expr::And(exprs)) => exprs.iter().all(|e| matches!(e, Expr::Term(_))),
+            Some(_) => false,
+        }
+    }
+
+    /// Words and phrases the results should contain, for scoring and
+    /// highlighting. Negated terms are left out.
+    pub fn terms(&self) -> Vec<&str> {
+        let mut terms = Vec::new();
+        if let Some(expr) = &self.expr {
+            expr.collect_terms(&mut terms);
+        }
+        terms
+    }
+
+    /// Whether the query filters on tags.
+    pub fn has_tag_filter(&self) -> bool {
+        self.expr.as_ref().is_some_and(Expr::has_tag_filter)
+    }
+
+    /// Whether a target matches. Empty queries match everything.
+    pub fn matches(&self, target: &impl QueryTarget) -> bool {
+        self.expr.as_ref().is_none_or(|e| e.matches(target))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Leaf(Expr),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(input: &str, drop_stop_words: bool) -> Self {
+        Self {
+            tokens: tokenize(input, drop_stop_words),
+            pos: 0,
+        }
+    }
+
+    fn parse(mut self) -> Option<Expr> {
+        let mut items = Vec::new();
+        while self.pos < self.tokens.len() {
+            items.extend(self.parse_or());
+            // A stray closing parenthesis ends nothing; skip it
+            self.eat(&Token::Close);
+        }
+        combine(items, Expr::And)
+    }
+
+    fn parse_or(&mut self) -> Option<Expr> {
+        let mut items = Vec::new();
+        loop {
+            items.extend(self.parse_and());
+            if !self.eat(&Token::Or) {
+                break;
+            }
+        }
+        combine(items, Expr::Or)
+    }
+
+    fn parse_and(&mut self) -> Option<Expr> {
+        let mut items = Vec::new();
+        while let Some(token) = self.tokens.get(self.pos) {
+            match token {
+                Token::Or | Token::Close => break,
+                Token::And => self.pos += 1,
+                _ => items.extend(self.parse_unary()),
+            }
+        }
+        combine(items, Expr::And)
+    }
+
+    fn parse_unary(&mut self) -> Option<Expr> {
+        let token = self.tokens.get(self.pos)?.clone();
+        match token {
+            Token::Or | Token::Close | Token::And => None,
+            Token::Not => {
+                self.pos += 1;
+                self.parse_unary().map(|e| Expr::Not(Box::new(e)))
+            }
+            Token::Open => {
+                self.pos += 1;
+                let expr = self.parse_or();
+                self.eat(&Token::Close);
+                expr
+            }
+            Token::Leaf(expr) => {
+                self.pos += 1;
+                Some(expr)
+            }
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.tokens.get(self.pos) == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+}
+
+/// Wrap several expressions with an operator; one needs no wrapping.
+fn combine(mut items: Vec<Expr>, op: fn(Vec<Expr>) -> Expr) -> Option<Expr> {
+    match items.len() {
+        0 => None,
+        1 => items.pop(),
+        _ => Some(op(items)),
+    }
+}
+
+fn tokenize(input: &str, drop_stop_words: bool) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '-' => {
+                chars.next();
+                if chars.peek().is_some_and(|c| !c.is_whitespace()) {
+                    tokens.push(Token::Not);
+                }
+            }
+            '"' => {
+                chars.next();
+                let phrase = read_quoted(&mut chars);
+                if !phrase.is_empty() {
+                    tokens.push(Token::Leaf(Expr::Phrase(phrase)));
+                }
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+
+                match word.as_str() {
+                    "AND" => tokens.push(Token::And),
+                    "OR" => tokens.push(Token::Or),
+                    "NOT" => tokens.push(Token::Not),
+                    _ => {
+                        if let Some((field, value)) = word.split_once(':') {
+                            let field = field.to_lowercase();
+                            if field == "tag" || field == "domain" {
+                                let value = if value.is_empty() && chars.peek() == Some(&'"') {
+                                    chars.next();
+                                    read_quoted(&mut chars)
+                                } else {
+                                    value.to_lowercase()
+                                };
+                                if !value.is_empty() {
+                                    tokens.push(Token::Leaf(if field == "tag" {
+                                        Expr::Tag(value)
+                                    } else {
+                                        Expr::Domain(value)
+                                    }));
+                                }
+                                continue;
+                            }
+                        }
+
+                        let term = word
+                            .trim_matches(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+                            .to_lowercase();
+                        let dropped = drop_stop_words && is_stop_word(&term);
+                        if !term.is_empty() && !dropped {
+                            tokens.push(Token::Leaf(Expr::Term(term)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    tokens
+}
+
+/// Read up to a closing quote (or the end), lowercased with whitespace
+/// collapsed.
+fn read_quoted(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut text = String::new();
+    for c in chars.by_ref() {
+        if c == '"' {
+            break;
+        }
+        text.push(c);
+    }
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(t: &str) -> Expr {
+        Expr::Term(t.to_string())
+    }
+
+    struct Doc {
+        name: &'static str,
+        text: &'static str,
+        tags: &'static [&'static str],
+    }
+
+    impl QueryTarget for Doc {
+        fn contains(&self, text: &str) -> bool {
+            self.text.contains(text)
+        }
+        fn has_tag(&self, tag: &str) -> bool {
+            self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+        }
+        fn skill_name(&self) -> &str {
+            self.name
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let query = Query::parse(r#"how to validate "react forms" tag:Testing -(zod OR yup) domain:frontend"#);
+        assert_eq!(
+            query.expr(),
+            Some(&Expr::And(vec![
+                term("validate"),
+                Expr::Phrase("react forms".to_string()),
+                Expr::Tag("testing".to_string()),
+                Expr::Not(Box::new(Expr::Or(vec![term("zod"), term("yup")]))),
+                Expr::Domain("frontend".to_string()),
+            ]))
+        );
+        assert_eq!(query.terms(), vec!["validate", "react forms"]);
+        assert!(!query.is_plain());
+        assert!(query.has_tag_filter());
+
+        // Lowercase operators are ordinary (stop) words
+        assert!(Query::parse("forms and validation").is_plain());
+        assert_eq!(Query::parse("how to").terms(), vec!["how", "to"]);
+
+        // Malformed input still parses
+        assert_eq!(Query::parse(r#"(forms OR "react"#).terms(), vec!["forms", "react"]);
+        assert_eq!(Query::parse("forms) OR - AND").expr(), Some(&term("forms")));
+        assert_eq!(Query::parse("").expr(), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        let docs = [
+            Doc { name: "forms", text: "react forms with zod", tags: &["react"] },
+            Doc { name: "frontend/testing", text: "testing react forms", tags: &["testing"] },
+            Doc { name: "backend", text: "rust services", tags: &[] },
+        ];
+        let hits = |q: &str| -> Vec<&str> {
+            let query = Query::parse(q);
+            docs.iter().filter(|d| query.matches(*d)).map(|d| d.name).collect()
+        };
+
+        assert_eq!(hits("react forms"), vec!["forms", "frontend/testing"]);
+        assert_eq!(hits("react -zod"), vec!["frontend/testing"]);
+        assert_eq!(hits("tag:REACT OR rust"), vec!["forms", "backend"]);
+        assert_eq!(hits("domain:frontend"), vec!["frontend/testing"]);
+        assert_eq!(hits(r#""forms with""#), vec!["forms"]);
+        assert_eq!(hits("NOT (react AND zod)"), vec!["frontend/testing", "backend"]);
+    }
+}
@@ -1,17 +1,30 @@
 //! Search service implementation.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use parking_lot::RwLock;
 use tracing::debug;
 
 use crate::index::SkillIndexer;
-use crate::models::{MatchType, SearchOptions, SearchResult, SearchResults, SkillMeta};
-
-use super::extract_snippet;
+use crate::markdown::normalize_code_language;
+use crate::models::{
+    ContentIndexEntry, ExampleMatch, FallbackConfig, FallbackSkill, FieldBoosts, FusedSearchResults, MatchExplanation,
+    MatchType, SearchOptions, SearchResult, SearchResults, SkillMeta, TermHit,
+    ZeroResultDiagnostics,
+};
+
+use super::{
+    generate_variants, highlight_snippet, highlight_terms, keywords, reciprocal_rank_fusion, Query,
+    QueryTarget, SearchCache, SearchCacheStats,
+};
 
 /// Search service for querying skills and content.
 pub struct SearchService {
     indexer: Arc<SkillIndexer>,
+    fallback: RwLock<Option<FallbackConfig>>,
+    boosts: RwLock<FieldBoosts>,
+    cache: SearchCache,
 }
 
 impl SearchService {
@@ -20,29 +33,138 @@ impl SearchService {
 
     /// Create a new search service.
     pub fn new(indexer: Arc<SkillIndexer>) -> Self {
-        Self { indexer }
+        Self {
+            indexer,
+            fallback: RwLock::new(None),
+            boosts: RwLock::new(FieldBoosts::default()),
+            cache: SearchCache::default(),
+        }
+    }
+
+    /// Set the field boosts searches use unless their options override them.
+    pub fn set_boosts(&self, boosts: FieldBoosts) {
+        let mut current = self.boosts.write();
+        if *current != boosts {
+            *current = boosts;
+            // Cached scores were weighted with the old boosts
+            self.cache.clear();
+        }
+    }
+
+    /// The configured field boosts.
+    pub fn boosts(&self) -> FieldBoosts {
+        *self.boosts.read()
+    }
+
+    /// Configure the skill served for unmatched queries, or None to disable.
+    pub fn set_fallback(&self, config: Option<FallbackConfig>) {
+        *self.fallback.write() = config;
+    }
+
+    /// Set how many result sets the search cache keeps; 0 disables it.
+    pub fn set_cache_capacity(&self, capacity: usize) {
+        self.cache.set_capacity(capacity);
+    }
+
+    /// Size and hit counts of the search cache.
+    pub fn cache_stats(&self) -> SearchCacheStats {
+        self.cache.stats()
+    }
+
+    /// The configured fallback, if any.
+    pub fn fallback(&self) -> Option<FallbackConfig> {
+        self.fallback.read().clone()
+    }
+
+    /// Attach the fallback skill when no result reaches the configured
+    /// confidence threshold.
+    pub fn apply_fallback(&self, results: &mut SearchResults) {
+        if let Some(config) = self.fallback() {
+            results.fallback = self.fallback_for(&config, results, config.min_score);
+        }
+    }
+
+    /// Attach the fallback skill only when there are no results at all.
+    ///
+    /// Used for fusion results, whose rank-based scores aren't comparable
+    /// with the confidence threshold.
+    pub fn apply_fallback_if_empty(&self, results: &mut SearchResults) {
+        if let Some(config) = self.fallback() {
+            results.fallback = self.fallback_for(&config, results, 0.0);
+        }
     }
 
     /// Search skills by metadata (name, description, tags, triggers).
+    ///
+    /// The query may use the [query language](super::Query). Results are
+    /// cached until the index changes.
     pub fn search_skills(&self, query: &str, options: SearchOptions) -> SearchResults {
-        let skill_index = self.indexer.get_skill_index();
-        let query_lower = query.to_lowercase();
-        let terms: Vec<&str> = query_lower.split_whitespace().collect();
+        let generation = self.indexer.generation();
+        self.cache.get_or_insert_with("skills", query, &options, generation, || {
+            let skill_index = self.indexer.get_skill_index();
+            self.search_skill_list(&skill_index.skills, query, options.clone())
+        })
+    }
+
+    /// Search an arbitrary list of skill metadata (e.g., cold-storage skills).
+    pub fn search_skill_list(
+        &self,
+        skills: &[SkillMeta],
+        query: &str,
+        options: SearchOptions,
+    ) -> SearchResults {
+        let _span = tracing::debug_span!("search", kind = "skills", query).entered();
+        let parsed = Query::parse(query);
+        let terms = parsed.terms();
+        let boosts = self.boosts().with_overrides(&options.boosts);
+        // Plain queries match on the whole text too, as typed
+        let text = if parsed.is_plain() {
+            query.to_lowercase()
+        } else {
+            terms.join(" ")
+        };
 
         let mut results = Vec::new();
+        let mut filtered = 0;
+
+        for skill in skills {
+            let matched = if parsed.is_plain() {
+                self.match_skill(skill, &text, &terms, &boosts)
+            } else if parsed.matches(skill) {
+                // Filters alone still select the skill, at a lower score
+                let by_terms = (!terms.is_empty())
+                    .then(|| self.match_skill(skill, &text, &terms, &boosts))
+                    .flatten();
+                Some(by_terms.unwrap_or_else(|| {
+                    let match_type = if parsed.has_tag_filter() {
+                        MatchType::Tags
+                    } else {
+                        MatchType::Name
+                    };
+                    SearchResult::new(skill.name.clone(), 0.5 * boosts.get(match_type), match_type)
+                }))
+            } else {
+                None
+            };
+
+            if let Some(mut result) = matched {
+                if let Some(quick_answer) = &skill.quick_answer {
+                    result = result.with_quick_answer(quick_answer.clone());
+                }
 
-        for skill in &skill_index.skills {
-            if let Some(result) = self.match_skill(skill, &query_lower, &terms) {
-                // Apply domain filter if set
-                if let Some(ref domains) = options.domains {
-                    if !domains.contains(&skill.name) {
-                        continue;
-                    }
+                // Apply domain, collection, and language filters if set
+                if !options.allows_domain(&skill.name)
+                    || !options.allows_language(skill.language.as_deref())
+                    || !options.allows_skill(skill)
+                {
+                    filtered += 1;
+                    continue;
                 }
 
                 // Apply match type filter if set
                 if let Some(ref match_types) = options.match_types {
                     if !match_types.contains(&result.match_type) {
+                        filtered += 1;
                         continue;
                     }
                 }
@@ -50,6 +172,7 @@ impl SearchService {
                 // Apply min score filter
                 if let Some(min_score) = options.min_score {
                     if result.score < min_score {
+                        filtered += 1;
                         continue;
                     }
                 }
@@ -59,40 +182,75 @@ impl SearchService {
         }
 
         debug!(
-            "Skill search '{}' found {} results",
+            "Skill search '{}' found {} results ({} more matched but were filtered out)",
             query,
-            results.len()
+            results.len(),
+            filtered
         );
 
         SearchResults::new(query.to_string(), results, options.limit)
     }
 
     /// Search content by full-text matching.
+    ///
+    /// The query may use the [query language](super::Query); `tag:` filters
+    /// apply the owning skill's tags. Results are cached until the index
+    /// changes.
     pub fn search_content(&self, query: &str, options: SearchOptions) -> SearchResults {
+        let generation = self.indexer.generation();
+        self.cache.get_or_insert_with("content", query, &options, generation, || {
+            self.search_content_uncached(query, options.clone())
+        })
+    }
+
+    fn search_content_uncached(&self, query: &str, options: SearchOptions) -> SearchResults {
+        let _span = tracing::debug_span!("search", kind = "content", query).entered();
         let content_index = self.indexer.get_content_index();
-        let query_lower = query.to_lowercase();
-        let terms: Vec<&str> = query_lower.split_whitespace().collect();
+        let skill_index = self.indexer.get_skill_index();
+        let parsed = Query::parse(query);
+        let terms = parsed.terms();
+        let boosts = self.boosts().with_overrides(&options.boosts);
 
         let mut results = Vec::new();
 
         for (_, entry) in content_index.iter() {
-            // Apply domain filter
-            if let Some(ref domains) = options.domains {
-                if !domains.contains(&entry.domain) {
-                    continue;
-                }
+            // Apply domain, collection, language, archive, and visibility
+            // filters
+            if !options.allows_domain(&entry.domain)
+                || !options.allows_language(entry.language.as_deref())
+                || !options.allows_file_type(&entry.file_type)
+                || skill_index.find(&entry.domain).is_some_and(|s| !options.allows_skill(s))
+            {
+                continue;
             }
 
-            // Check for matches
-            let match_count: usize = terms.iter().map(|t| entry.count_matches(t)).sum();
-
-            if match_count == 0 {
-                continue;
+            // Plain queries match any term; others must satisfy the query
+            let mut match_count: usize = terms.iter().map(|t| entry.count_matches(t)).sum();
+            if parsed.is_plain() {
+                if match_count == 0 {
+                    continue;
+                }
+            } else {
+                let target = EntryTarget {
+                    entry,
+                    skill: skill_index.find(&entry.domain),
+                };
+                if !parsed.matches(&target) {
+                    continue;
+                }
+                match_count = match_count.max(1);
             }
 
-            // Calculate TF-IDF-like score
+            // TF-like body score, plus the share of terms found in headings
             let tf = match_count as f64 / entry.word_count.max(1) as f64;
-            let score = tf * MatchType::Content.weight();
+            let body_score = tf * boosts.body;
+            let heading_score = heading_share(entry, &terms) * boosts.headings;
+            let score = heading_score + body_score;
+            let match_type = if heading_score > body_score {
+                MatchType::Headings
+            } else {
+                MatchType::Content
+            };
 
             // Apply min score filter
             if let Some(min_score) = options.min_score {
@@ -101,22 +259,16 @@ impl SearchService {
                 }
             }
 
-            // Extract snippet
-            let snippet = extract_snippet(&entry.content, &query_lower, Self::DEFAULT_SNIPPET_CONTEXT);
-
-            let mut result = SearchResult::new(entry.domain.clone(), score, MatchType::Content)
+            let mut result = SearchResult::new(entry.domain.clone(), score, match_type)
                 .with_file(entry.file.clone());
 
             if let Some(sub) = &entry.sub_skill {
                 result = result.with_sub_skill(sub.clone());
             }
 
-            if let Some(snippet) = snippet {
-                result = result.with_snippet(snippet);
-            }
-
-            results.push(result);
+            results.push((result, entry.content_hash.as_str()));
         }
+        let results = fold_duplicates(results);
 
         debug!(
             "Content search '{}' found {} results",
@@ -124,7 +276,93 @@ impl SearchService {
             results.len()
         );
 
-        SearchResults::new(query.to_string(), results, options.limit)
+        // Snippets come from the original files, so only read the hits returned
+        let mut results = SearchResults::new(query.to_string(), results, options.limit);
+        for result in &mut results.results {
+            let Some(file) = result.file.as_deref() else {
+                continue;
+            };
+            let original = std::fs::read_to_string(self.indexer.skill_dir(&result.domain).join(file));
+            let content = match &original {
+                Ok(text) => text.as_str(),
+                Err(_) => match content_index.get_domain_entries(&result.domain).into_iter().find(|e| e.file == file) {
+                    Some(entry) => &*entry.content,
+                    None => continue,
+                },
+            };
+            if let Some(snippet) = highlight_snippet(content, &terms, Self::DEFAULT_SNIPPET_CONTEXT) {
+                result.snippet = Some(snippet.text);
+                result.highlights = snippet.highlights;
+                result.line = Some(snippet.line);
+            }
+        }
+        results
+    }
+
+    /// Search only within fenced code blocks.
+    ///
+    /// Blocks match like content entries do, but on their code alone; an
+    /// empty query matches every block. `language` keeps blocks of one code
+    /// language (aliases such as `py` allowed). Domain, collection, archive,
+    /// and visibility filters of `options` apply; results are sorted by
+    /// score and not truncated.
+    pub fn search_examples(
+        &self,
+        query: &str,
+        language: Option<&str>,
+        options: &SearchOptions,
+    ) -> Vec<ExampleMatch> {
+        let _span = tracing::debug_span!("search", kind = "examples", query).entered();
+        let content_index = self.indexer.get_content_index();
+        let skill_index = self.indexer.get_skill_index();
+        let parsed = Query::parse(query);
+        let terms = parsed.terms();
+        let language = language.map(normalize_code_language);
+
+        let mut results = Vec::new();
+        for (_, entry) in content_index.iter() {
+            let skill = skill_index.find(&entry.domain);
+            if !options.allows_domain(&entry.domain) || skill.is_some_and(|s| !options.allows_skill(s)) {
+                continue;
+            }
+
+            for block in &entry.code_blocks {
+                if language.is_some() && block.language != language {
+                    continue;
+                }
+                let code = block.code.to_lowercase();
+                let match_count: usize = terms.iter().map(|t| code.matches(t).count()).sum();
+                let target = CodeTarget {
+                    code: &code,
+                    domain: &entry.domain,
+                    skill,
+                };
+                let matched = if parsed.is_plain() {
+                    terms.is_empty() || match_count > 0
+                } else {
+                    parsed.matches(&target)
+                };
+                if !matched {
+                    continue;
+                }
+
+                results.push(ExampleMatch {
+                    domain: entry.domain.clone(),
+                    sub_skill: entry.sub_skill.clone(),
+                    file: entry.file.clone(),
+                    score: match_count as f64 / code.split_whitespace().count().max(1) as f64,
+                    block: block.clone(),
+                });
+            }
+        }
+
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| (&a.domain, &a.file, a.block.line).cmp(&(&b.domain, &b.file, b.block.line)))
+        });
+        debug!("Example search '{}' found {} blocks", query, results.len());
+        results
     }
 
     /// Combined search across both skills and content.
@@ -149,83 +387,326 @@ impl SearchService {
         SearchResults::new(query.to_string(), all_results, options.limit)
     }
 
+    /// Search with a long task description using multi-query fusion.
+    ///
+    /// Each generated variant is run through [`search_all`](Self::search_all)
+    /// and the ranked lists are merged with Reciprocal Rank Fusion, which
+    /// favors skills that several variants agree on.
+    pub fn search_fused(&self, task: &str, options: SearchOptions) -> FusedSearchResults {
+        let variants = generate_variants(task);
+
+        let per_variant = SearchOptions {
+            limit: None,
+            ..options.clone()
+        };
+        let lists: Vec<Vec<SearchResult>> = variants
+            .iter()
+            .map(|v| self.search_all(v, per_variant.clone()).results)
+            .collect();
+
+        let fused = reciprocal_rank_fusion(lists);
+
+        debug!(
+            "Fused search over {} variants found {} results",
+            variants.len(),
+            fused.len()
+        );
+
+        FusedSearchResults {
+            variants,
+            results: SearchResults::new(task.to_string(), fused, options.limit),
+        }
+    }
+
+    /// Explain whether and why a skill matches a task description.
+    ///
+    /// Reports the literal and fusion search outcomes, plus a per-keyword
+    /// breakdown of which metadata fields (name, tags, triggers,
+    /// description) were hit. `options` filter the fusion search the
+    /// skill is ranked in. Returns None if the skill isn't indexed.
+    pub fn explain_match(
+        &self,
+        name: &str,
+        task: &str,
+        options: SearchOptions,
+    ) -> Option<MatchExplanation> {
+        let skill = self.indexer.get_skill_meta(name)?;
+
+        let task_lower = task.to_lowercase();
+        let terms: Vec<&str> = task_lower.split_whitespace().collect();
+        let boosts = self.boosts().with_overrides(&options.boosts);
+        let literal_match = self.match_skill(&skill, &task_lower, &terms, &boosts);
+
+        let fused_rank = self
+            .search_fused(task, options)
+            .results
+            .results
+            .iter()
+            .position(|r| r.domain == skill.name)
+            .map(|i| i + 1);
+
+        // Every searchable value, labelled with the field it came from
+        let mut fields: Vec<(MatchType, String, Option<String>)> = vec![
+            (MatchType::Name, skill.name.clone(), None),
+            (MatchType::Description, skill.description.clone(), None),
+        ];
+        fields.extend(skill.tags.iter().map(|t| (MatchType::Tags, t.clone(), None)));
+        for sub in skill.sub_skills.iter().flatten() {
+            fields.extend(
+                sub.triggers
+                    .iter()
+                    .map(|t| (MatchType::Triggers, t.clone(), Some(sub.name.clone()))),
+            );
+        }
+
+        let keywords = keywords(task);
+        let mut hits = Vec::new();
+        let mut missed_keywords = Vec::new();
+        for term in &keywords {
+            let before = hits.len();
+            for (field, value, sub_skill) in &fields {
+                if value.to_lowercase().contains(term.as_str()) {
+                    hits.push(TermHit {
+                        term: term.clone(),
+                        field: *field,
+                        value: value.clone(),
+                        sub_skill: sub_skill.clone(),
+                    });
+                }
+            }
+            if hits.len() == before {
+                missed_keywords.push(term.clone());
+            }
+        }
+
+        let unmatched_triggers = fields
+            .iter()
+            .filter(|(field, _, _)| matches!(field, MatchType::Tags | MatchType::Triggers))
+            .filter(|(_, value, _)| !hits.iter().any(|h| &h.value == value))
+            .map(|(_, value, _)| value.clone())
+            .collect();
+
+        Some(MatchExplanation {
+            skill: skill.name,
+            task: task.to_string(),
+            matched: literal_match.is_some() || fused_rank.is_some(),
+            literal_match,
+            fused_rank,
+            keywords,
+            hits,
+            missed_keywords,
+            unmatched_triggers,
+        })
+    }
+
+    /// The fallback for `results`, if none of them reach `min_score`.
+    fn fallback_for(
+        &self,
+        config: &FallbackConfig,
+        results: &SearchResults,
+        min_score: f64,
+    ) -> Option<FallbackSkill> {
+        let best_score = results.top().map(|r| r.score);
+        if best_score.is_some_and(|score| score >= min_score) {
+            return None;
+        }
+
+        let Some(skill) = self.indexer.get_skill_meta(&config.skill) else {
+            debug!("Fallback skill '{}' is not indexed", config.skill);
+            return None;
+        };
+
+        let keywords = keywords(&results.query);
+        let skill_index = self.indexer.get_skill_index();
+        let content_index = self.indexer.get_content_index();
+        let unknown_keywords = keywords
+            .iter()
+            .filter(|term| {
+                !skill_index.skills.iter().any(|s| mentions(s, term))
+                    && !content_index.iter().any(|(_, e)| e.count_matches(term) > 0)
+            })
+            .cloned()
+            .collect();
+
+        Some(FallbackSkill {
+            skill: skill.name,
+            description: skill.description,
+            quick_answer: skill.quick_answer,
+            diagnostics: ZeroResultDiagnostics {
+                keywords,
+                unknown_keywords,
+                best_score,
+                min_score,
+            },
+        })
+    }
+
     /// Match a skill against search terms.
+    ///
+    /// Each field is scored on its own and weighted by its boost; the best
+    /// scoring field is the match, earlier fields winning ties.
     fn match_skill(
         &self,
         skill: &SkillMeta,
         query: &str,
         terms: &[&str],
+        boosts: &FieldBoosts,
     ) -> Option<SearchResult> {
         let name_lower = skill.name.to_lowercase();
         let desc_lower = skill.description.to_lowercase();
+        let mut best: Option<(f64, MatchType)> = None;
+        let mut consider = |score: f64, match_type: MatchType| {
+            let score = score * boosts.get(match_type);
+            if best.is_none_or(|(b, _)| score > b) {
+                best = Some((score, match_type));
+            }
+        };
 
-        // Exact name match (highest priority)
+        // Exact name match scores highest, then the name containing the query
         if name_lower == query {
-            return Some(SearchResult::new(
-                skill.name.clone(),
-                1.0 * MatchType::Name.weight(),
-                MatchType::Name,
-            ));
+            consider(1.0, MatchType::Name);
+        } else if name_lower.contains(query) {
+            consider(0.8, MatchType::Name);
         }
 
-        // Name contains query
-        if name_lower.contains(query) {
-            return Some(SearchResult::new(
-                skill.name.clone(),
-                0.8 * MatchType::Name.weight(),
-                MatchType::Name,
-            ));
+        if skill.tags.iter().any(|tag| tag.to_lowercase().contains(query)) {
+            consider(0.9, MatchType::Tags);
         }
 
-        // Check tags first (before triggers, since all_triggers includes tags)
-        let tags: Vec<String> = skill.tags.iter().map(|s| s.to_lowercase()).collect();
-        for tag in &tags {
-            if tag == query || tag.contains(query) {
-                return Some(SearchResult::new(
-                    skill.name.clone(),
-                    0.9 * MatchType::Tags.weight(),
-                    MatchType::Tags,
-                ));
-            }
+        // Only the sub-skills' own triggers; all_triggers includes tags
+        let triggered = skill
+            .sub_skills
+            .iter()
+            .flatten()
+            .flat_map(|sub| &sub.triggers)
+            .any(|trigger| trigger.to_lowercase().contains(query));
+        if triggered {
+            consider(0.9, MatchType::Triggers);
         }
 
-        // Check sub-skill triggers (only the actual triggers, not tags)
-        if let Some(subs) = &skill.sub_skills {
-            for sub in subs {
-                for trigger in &sub.triggers {
-                    let trigger_lower = trigger.to_lowercase();
-                    if trigger_lower == query || trigger_lower.contains(query) {
-                        return Some(SearchResult::new(
-                            skill.name.clone(),
-                            0.9 * MatchType::Triggers.weight(),
-                            MatchType::Triggers,
-                        ));
-                    }
-                }
-            }
+        let term_matches = terms.iter().filter(|t| desc_lower.contains(*t)).count();
+        if term_matches > 0 {
+            consider(term_matches as f64 / terms.len() as f64, MatchType::Description);
         }
 
-        // Description match
-        let term_matches: usize = terms
+        let (score, match_type) = best.filter(|(score, _)| *score > 0.0)?;
+        let result = SearchResult::new(skill.name.clone(), score, match_type);
+        Some(if match_type == MatchType::Description {
+            result
+                .with_snippet(skill.description.clone())
+                .with_highlights(highlight_terms(&skill.description, terms))
+        } else {
+            result
+        })
+    }
+}
+
+/// Share of `terms` found in an entry's headings.
+fn heading_share(entry: &ContentIndexEntry, terms: &[&str]) -> f64 {
+    if terms.is_empty() || entry.headings.is_empty() {
+        return 0.0;
+    }
+    let headings = entry.headings.join("\n").to_lowercase();
+    let found = terms.iter().filter(|t| headings.contains(*t)).count();
+    found as f64 / terms.len() as f64
+}
+
+impl QueryTarget for SkillMeta {
+    fn contains(&self, text: &str) -> bool {
+        mentions(self, text)
+    }
+
+    fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    fn skill_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A content entry with the metadata of its skill.
+struct EntryTarget<'a> {
+    entry: &'a ContentIndexEntry,
+    skill: Option<&'a SkillMeta>,
+}
+
+impl QueryTarget for EntryTarget<'_> {
+    fn contains(&self, text: &str) -> bool {
+        self.entry.content.contains(text)
+    }
+
+    fn has_tag(&self, tag: &str) -> bool {
+        self.skill.is_some_and(|s| s.has_tag(tag))
+    }
+
+    fn skill_name(&self) -> &str {
+        &self.entry.domain
+    }
+}
+
+/// A code block with the metadata of its skill.
+struct CodeTarget<'a> {
+    /// Lowercased code.
+    code: &'a str,
+    domain: &'a str,
+    skill: Option<&'a SkillMeta>,
+}
+
+impl QueryTarget for CodeTarget<'_> {
+    fn contains(&self, text: &str) -> bool {
+        self.code.contains(text)
+    }
+
+    fn has_tag(&self, tag: &str) -> bool {
+        self.skill.is_some_and(|s| s.has_tag(tag))
+    }
+
+    fn skill_name(&self) -> &str {
+        self.domain
+    }
+}
+
+/// Whether any searchable metadata of a skill contains `term`.
+fn mentions(skill: &SkillMeta, term: &str) -> bool {
+    skill.name.to_lowercase().contains(term)
+        || skill.description.to_lowercase().contains(term)
+        || skill.tags.iter().any(|t| t.to_lowercase().contains(term))
+        || skill
+            .sub_skills
             .iter()
-            .filter(|t| desc_lower.contains(*t))
-            .count();
+            .flatten()
+            .any(|sub| sub.triggers.iter().any(|t| t.to_lowercase().contains(term)))
+}
 
-        if term_matches > 0 {
-            let score = (term_matches as f64 / terms.len() as f64) * MatchType::Description.weight();
-            return Some(
-                SearchResult::new(skill.name.clone(), score, MatchType::Description)
-                    .with_snippet(skill.description.clone()),
-            );
+/// Report identical files (e.g. vendored references) once, under the
+/// first by skill and path, listing the others as its duplicates.
+fn fold_duplicates(mut hits: Vec<(SearchResult, &str)>) -> Vec<SearchResult> {
+    hits.sort_by(|a, b| (&a.0.domain, &a.0.file).cmp(&(&b.0.domain, &b.0.file)));
+
+    let mut results: Vec<SearchResult> = Vec::new();
+    let mut first: HashMap<&str, usize> = HashMap::new();
+    for (result, hash) in hits {
+        match first.get(hash) {
+            Some(&i) => {
+                let file = result.file.as_deref().unwrap_or("SKILL.md");
+                results[i].duplicates.push(format!("{}/{}", result.domain, file));
+            }
+            None => {
+                if !hash.is_empty() {
+                    first.insert(hash, results.len());
+                }
+                results.push(result);
+            }
         }
-
-        None
     }
+    results
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::index::IndexConfig;
     use crate::models::SubSkillMeta;
     use std::fs;
     use tempfile::TempDir;
@@ -251,6 +732,7 @@ mod tests {
             tags: vec!["validation".to_string()],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
         create_test_skill(temp_dir.path(), &meta);
 
@@ -265,6 +747,35 @@ mod tests {
         assert_eq!(results.top().unwrap().match_type, MatchType::Name);
     }
 
+    #[test]
+    fn test_content_snippet_highlights() {
+        let temp_dir = TempDir::new().unwrap();
+        let meta = SkillMeta {
+            name: "forms".to_string(),
+            description: "Form handling patterns".to_string(),
+            ..Default::default()
+        };
+        create_test_skill(temp_dir.path(), &meta);
+        fs::write(
+            temp_dir.path().join("forms/SKILL.md"),
+            "# Forms\n\nIntro.\n\nValidate inputs with Zod before submit.\n",
+        )
+        .unwrap();
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+
+        let service = SearchService::new(indexer);
+        let results = service.search_content("zod", SearchOptions::default());
+        let hit = results.top().unwrap();
+
+        // Snippets keep the original casing, and highlights point into them
+        let snippet = hit.snippet.as_deref().unwrap();
+        assert!(snippet.contains("Validate inputs with Zod"));
+        assert_eq!(hit.line, Some(5));
+        assert_eq!(&snippet[hit.highlights[0].start..hit.highlights[0].end], "Zod");
+    }
+
     #[test]
     fn test_search_by_tag() {
         let temp_dir = TempDir::new().unwrap();
@@ -275,6 +786,7 @@ mod tests {
             tags: vec!["schema-validation".to_string(), "input".to_string()],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
         create_test_skill(temp_dir.path(), &meta);
 
@@ -289,6 +801,213 @@ mod tests {
         assert_eq!(results.top().unwrap().match_type, MatchType::Tags);
     }
 
+    #[test]
+    fn test_search_query_language() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, description, tags) in [
+            ("forms", "Form validation with zod", vec!["react"]),
+            ("testing", "Testing react form validation", vec!["testing"]),
+            ("styling", "CSS for forms", vec!["react"]),
+        ] {
+            let meta = SkillMeta {
+                name: name.to_string(),
+                description: description.to_string(),
+                tags: tags.into_iter().map(String::from).collect(),
+                ..Default::default()
+            };
+            create_test_skill(temp_dir.path(), &meta);
+        }
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let service = SearchService::new(indexer);
+        let domains = |results: SearchResults| -> Vec<String> {
+            let mut domains: Vec<String> = results.results.into_iter().map(|r| r.domain).collect();
+            domains.sort();
+            domains
+        };
+
+        let results = service.search_skills("validation -zod", SearchOptions::default());
+        assert_eq!(domains(results), vec!["testing"]);
+
+        let results = service.search_skills("tag:react", SearchOptions::default());
+        assert_eq!(domains(results), vec!["forms", "styling"]);
+
+        let results = service.search_skills("\"form validation\" OR css", SearchOptions::default());
+        assert_eq!(domains(results), vec!["forms", "styling", "testing"]);
+
+        let results = service.search_content("validation domain:testing", SearchOptions::default());
+        assert_eq!(domains(results), vec!["testing"]);
+    }
+
+    #[test]
+    fn test_search_examples() {
+        let temp_dir = TempDir::new().unwrap();
+        let meta = SkillMeta {
+            name: "forms".to_string(),
+            description: "Form handling".to_string(),
+            ..Default::default()
+        };
+        create_test_skill(temp_dir.path(), &meta);
+        fs::write(
+            temp_dir.path().join("forms/SKILL.md"),
+            "# Forms\n\nValidate with zod.\n\n## Python\n\n```py\nvalidate(form)\n```\n\n## TypeScript\n\n```ts\nschema.parse(form)\n```\n",
+        )
+        .unwrap();
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let service = SearchService::new(indexer);
+
+        // Prose mentions don't count, only code
+        let results = service.search_examples("validate", None, &SearchOptions::default());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].block.heading.as_deref(), Some("Python"));
+
+        let results = service.search_examples("form", Some("typescript"), &SearchOptions::default());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].block.code, "schema.parse(form)\n");
+
+        assert_eq!(service.search_examples("", Some("python"), &SearchOptions::default()).len(), 1);
+        assert_eq!(service.search_examples("", None, &SearchOptions::default()).len(), 2);
+    }
+
+    #[test]
+    fn test_search_content_file_types() {
+        let temp_dir = TempDir::new().unwrap();
+        let meta = SkillMeta {
+            name: "forms".to_string(),
+            description: "Form handling".to_string(),
+            ..Default::default()
+        };
+        create_test_skill(temp_dir.path(), &meta);
+        let refs = temp_dir.path().join("forms/references");
+        fs::create_dir_all(&refs).unwrap();
+        fs::write(refs.join("guide.md"), "# Guide\n\nValidate the schema.\n").unwrap();
+        fs::write(refs.join("example.py"), "# Validate the schema\ndef validate(form):\n    pass\n").unwrap();
+        fs::write(refs.join("schema.yml"), "validate: true\n").unwrap();
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let service = SearchService::new(indexer.clone());
+        let files = |options: SearchOptions| -> Vec<String> {
+            let mut files: Vec<String> = service
+                .search_content("validate", options)
+                .results
+                .into_iter()
+                .filter_map(|r| r.file)
+                .collect();
+            files.sort();
+            files
+        };
+        assert_eq!(files(SearchOptions::default()), vec!["references/guide.md"]);
+
+        indexer.set_index_config(IndexConfig {
+            extensions: vec!["md".to_string(), "py".to_string(), "yml".to_string()],
+            ..Default::default()
+        });
+        indexer.reload().unwrap();
+        let entry = indexer.get_content_index().get("forms/references/example.py").cloned().unwrap();
+        assert_eq!(entry.file_type, "py");
+        assert!(entry.headings.is_empty());
+
+        assert_eq!(files(SearchOptions::default()).len(), 3);
+        assert_eq!(
+            files(SearchOptions::default().file_types(vec!["py".to_string(), "yaml".to_string()])),
+            vec!["references/example.py", "references/schema.yml"]
+        );
+        assert_eq!(
+            files(SearchOptions::default().file_types(vec!["md".to_string()])),
+            vec!["references/guide.md"]
+        );
+    }
+
+    #[test]
+    fn test_search_field_boosts() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, tags) in [("ci", vec!["testing".to_string()]), ("forms", vec![]), ("tables", vec![])] {
+            let meta = SkillMeta {
+                name: name.to_string(),
+                description: format!("{} patterns", name),
+                tags,
+                ..Default::default()
+            };
+            create_test_skill(temp_dir.path(), &meta);
+        }
+        for (name, text) in [
+            ("forms", "# Notes\n\nRun the testing suite before a release.\n"),
+            ("tables", "# Testing tables\n\nSnapshot the rendered rows.\n"),
+        ] {
+            let refs = temp_dir.path().join(name).join("references");
+            fs::create_dir_all(&refs).unwrap();
+            fs::write(refs.join("notes.md"), text).unwrap();
+        }
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let service = SearchService::new(indexer);
+        let ranking = |options: SearchOptions| -> Vec<(String, MatchType)> {
+            service
+                .search_all("testing", options)
+                .results
+                .into_iter()
+                .map(|r| (r.domain, r.match_type))
+                .collect()
+        };
+
+        // A tag outranks a heading, which outranks a mention in the body
+        assert_eq!(
+            ranking(SearchOptions::default()),
+            vec![
+                ("ci".to_string(), MatchType::Tags),
+                ("tables".to_string(), MatchType::Headings),
+                ("forms".to_string(), MatchType::Content),
+            ]
+        );
+
+        let options = SearchOptions::default().boost(MatchType::Tags, 0.1);
+        assert_eq!(ranking(options)[0].0, "tables");
+
+        // A field boosted to zero no longer matches
+        service.set_boosts(FieldBoosts {
+            tags: 0.0,
+            headings: 0.0,
+            ..Default::default()
+        });
+        assert_eq!(
+            ranking(SearchOptions::default()),
+            vec![
+                ("tables".to_string(), MatchType::Content),
+                ("forms".to_string(), MatchType::Content),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_content_folds_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["forms", "tables"] {
+            let meta = SkillMeta {
+                name: name.to_string(),
+                description: name.to_string(),
+                ..Default::default()
+            };
+            create_test_skill(temp_dir.path(), &meta);
+            let refs = temp_dir.path().join(name).join("references");
+            fs::create_dir_all(&refs).unwrap();
+            fs::write(refs.join("http.md"), "# HTTP\n\nStatus codes.\n").unwrap();
+        }
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let service = SearchService::new(indexer);
+
+        let results = service.search_content("status", SearchOptions::default());
+        assert_eq!(results.results.len(), 1);
+        assert_eq!(results.results[0].domain, "forms");
+        assert_eq!(results.results[0].duplicates, vec!["tables/references/http.md"]);
+    }
+
     #[test]
     fn test_search_by_trigger() {
         let temp_dir = TempDir::new().unwrap();
@@ -301,8 +1020,10 @@ mod tests {
                 name: "react".to_string(),
                 file: "react/SKILL.md".to_string(),
                 triggers: vec!["useForm".to_string(), "react-hook-form".to_string()],
+                language: None,
             }]),
             source: None,
+            ..Default::default()
         };
         create_test_skill(temp_dir.path(), &meta);
 
@@ -326,6 +1047,7 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
         create_test_skill(temp_dir.path(), &meta);
 
@@ -337,4 +1059,148 @@ mod tests {
 
         assert!(results.is_empty());
     }
+
+    #[test]
+    fn test_search_fused_long_task() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let meta = SkillMeta {
+            name: "forms".to_string(),
+            description: "Form handling patterns".to_string(),
+            tags: vec!["validation".to_string()],
+            sub_skills: None,
+            source: None,
+            ..Default::default()
+        };
+        create_test_skill(temp_dir.path(), &meta);
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+
+        let service = SearchService::new(indexer);
+        let task = "I need to add client-side validation to a signup page";
+
+        let fused = service.search_fused(task, SearchOptions::with_limit(5));
+        assert_eq!(fused.results.top().unwrap().domain, "forms");
+        assert!(fused.variants.contains(&"validation".to_string()));
+    }
+
+    #[test]
+    fn test_explain_match() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let meta = SkillMeta {
+            name: "forms".to_string(),
+            description: "Form handling patterns".to_string(),
+            tags: vec!["validation".to_string(), "input".to_string()],
+            sub_skills: Some(vec![SubSkillMeta {
+                name: "react".to_string(),
+                file: "react/SKILL.md".to_string(),
+                triggers: vec!["useForm".to_string()],
+                language: None,
+            }]),
+            source: None,
+            ..Default::default()
+        };
+        create_test_skill(temp_dir.path(), &meta);
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let service = SearchService::new(indexer);
+
+        let explanation = service
+            .explain_match("forms", "add validation with useform hooks", SearchOptions::default())
+            .unwrap();
+        assert!(explanation.matched);
+        assert_eq!(explanation.fused_rank, Some(1));
+
+        let fields: Vec<_> = explanation.hits.iter().map(|h| h.field).collect();
+        assert_eq!(fields, vec![MatchType::Tags, MatchType::Triggers]);
+        assert_eq!(explanation.hits[1].sub_skill.as_deref(), Some("react"));
+        assert_eq!(explanation.missed_keywords, vec!["add", "hooks"]);
+        assert_eq!(explanation.unmatched_triggers, vec!["input"]);
+
+        assert!(service.explain_match("missing", "anything", SearchOptions::default()).is_none());
+    }
+
+    #[test]
+    fn test_fallback_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, description) in [
+            ("forms", "Form handling patterns"),
+            ("new-skill", "How to write and request a new skill"),
+        ] {
+            let meta = SkillMeta {
+                name: name.to_string(),
+                description: description.to_string(),
+                ..Default::default()
+            };
+            create_test_skill(temp_dir.path(), &meta);
+        }
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let service = SearchService::new(indexer);
+
+        // Disabled by default
+        let mut results = service.search_skills("kubernetes", SearchOptions::default());
+        service.apply_fallback(&mut results);
+        assert!(results.fallback.is_none());
+
+        service.set_fallback(Some(FallbackConfig::new("new-skill").min_score(2.0)));
+        service.apply_fallback(&mut results);
+        let fallback = results.fallback.unwrap();
+        assert_eq!(fallback.skill, "new-skill");
+        assert_eq!(fallback.diagnostics.unknown_keywords, vec!["kubernetes"]);
+        assert!(fallback.diagnostics.best_score.is_none());
+
+        // A weak description match is below the threshold
+        let mut results = service.search_skills("handling", SearchOptions::default());
+        service.apply_fallback(&mut results);
+        assert!(results.fallback.unwrap().diagnostics.best_score.is_some());
+
+        // A confident name match needs no fallback
+        let mut results = service.search_skills("forms", SearchOptions::default());
+        service.apply_fallback(&mut results);
+        assert!(results.fallback.is_none());
+    }
+
+    #[test]
+    fn test_search_cache_invalidation() {
+        let temp_dir = TempDir::new().unwrap();
+        let meta = SkillMeta {
+            name: "forms".to_string(),
+            description: "Form handling patterns".to_string(),
+            ..Default::default()
+        };
+        create_test_skill(temp_dir.path(), &meta);
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+        let service = SearchService::new(Arc::clone(&indexer));
+
+        assert_eq!(service.search_all("validation", SearchOptions::default()).results.len(), 0);
+        assert_eq!(service.search_all("validation", SearchOptions::default()).results.len(), 0);
+        let stats = service.cache_stats();
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+
+        // Index changes are searched straight away
+        let meta = SkillMeta {
+            description: "Form validation patterns".to_string(),
+            ..meta
+        };
+        create_test_skill(temp_dir.path(), &meta);
+        indexer.update_skill("forms").unwrap();
+        assert_eq!(service.search_all("validation", SearchOptions::default()).results.len(), 1);
+        assert_eq!(service.cache_stats().misses, 4);
+
+        // So are new boosts
+        service.search_skills("validation", SearchOptions::default());
+        service.set_boosts(FieldBoosts {
+            description: 5.0,
+            ..FieldBoosts::default()
+        });
+        service.search_skills("validation", SearchOptions::default());
+        assert_eq!(service.cache_stats().misses, 5);
+    }
 }
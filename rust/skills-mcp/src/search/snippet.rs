@@ -1,5 +1,208 @@
 //! Snippet extraction for search results.
 
+use crate::models::Highlight;
+
+/// A snippet of original content with its matched terms located.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    /// Excerpt with whitespace collapsed and `...` marking truncation.
+    pub text: String,
+
+    /// 1-based line of the content the excerpt is centered on.
+    pub line: usize,
+
+    /// Matched terms within `text`, in order, non-overlapping.
+    pub highlights: Vec<Highlight>,
+}
+
+/// Extract a snippet around the best match of `terms` in `content`.
+///
+/// Matching is case-insensitive. The snippet is centered on the line where
+/// the whole query (terms joined by spaces) occurs, or else the line that
+/// matches the most distinct terms, earliest first. Every term occurrence
+/// inside the snippet is highlighted.
+pub fn highlight_snippet(content: &str, terms: &[&str], context_chars: usize) -> Option<Snippet> {
+    let phrase = terms.join(" ");
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+
+    // Rank lines by (has phrase, distinct terms matched), earliest wins ties
+    let mut best_rank = (false, 0);
+    let mut best: Option<(usize, usize, usize)> = None;
+    for (line_index, &line_start) in line_starts.iter().enumerate() {
+        let line_end = line_starts
+            .get(line_index + 1)
+            .map_or(content.len(), |&next| next - 1);
+        let line = &content[line_start..line_end];
+
+        let phrase_match = if terms.len() > 1 {
+            find_matches(line, &phrase)
+        } else {
+            Vec::new()
+        };
+        let term_matches: Vec<Vec<(usize, usize)>> = terms.iter().map(|t| find_matches(line, t)).collect();
+        let distinct = term_matches.iter().filter(|m| !m.is_empty()).count();
+        if distinct == 0 {
+            continue;
+        }
+
+        let rank = (!phrase_match.is_empty(), distinct);
+        if rank <= best_rank {
+            continue;
+        }
+        let anchor = phrase_match
+            .first()
+            .copied()
+            .or_else(|| term_matches.iter().flatten().min().copied())?;
+        best_rank = rank;
+        best = Some((line_index, line_start + anchor.0, line_start + anchor.1));
+    }
+    let (line_index, match_start, match_end) = best?;
+
+    let start = find_word_start(content, floor_char_boundary(content, match_start.saturating_sub(context_chars)));
+    let end = find_word_end(content, floor_char_boundary(content, (match_end + context_chars).min(content.len())));
+
+    let mut matches: Vec<(usize, usize)> = terms
+        .iter()
+        .flat_map(|t| find_matches(&content[start..end], t))
+        .collect();
+    matches.sort();
+
+    let (text, highlights) = collapse(&content[start..end], &matches, start > 0, end < content.len());
+
+    Some(Snippet {
+        text,
+        line: line_index + 1,
+        highlights,
+    })
+}
+
+/// Highlight every case-insensitive occurrence of `terms` in `text`.
+pub fn highlight_terms(text: &str, terms: &[&str]) -> Vec<Highlight> {
+    let mut matches: Vec<(usize, usize)> = terms.iter().flat_map(|t| find_matches(text, t)).collect();
+    matches.sort();
+    merge(&matches)
+        .into_iter()
+        .map(|(start, end)| to_highlight(text, start, end))
+        .collect()
+}
+
+/// Byte ranges of case-insensitive occurrences of `term` in `text`.
+fn find_matches(text: &str, term: &str) -> Vec<(usize, usize)> {
+    let needle: Vec<char> = term.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    let mut pos = 0;
+    while pos < text.len() {
+        match match_at(text, pos, &needle) {
+            Some(end) => {
+                matches.push((pos, end));
+                pos = end;
+            }
+            None => pos += text[pos..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    matches
+}
+
+/// If `needle` (lowercased) matches `text` at `pos`, the byte just past it.
+fn match_at(text: &str, pos: usize, needle: &[char]) -> Option<usize> {
+    let mut remaining = needle.iter();
+    for (offset, c) in text[pos..].char_indices() {
+        for lower in c.to_lowercase() {
+            if remaining.next() != Some(&lower) {
+                return None;
+            }
+        }
+        if remaining.len() == 0 {
+            return Some(pos + offset + c.len_utf8());
+        }
+    }
+    None
+}
+
+/// Merge overlapping or touching ranges from a sorted list.
+fn merge(ranges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for &(start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Collapse whitespace runs to single spaces, add ellipses, and map match
+/// ranges into the result.
+fn collapse(
+    excerpt: &str,
+    matches: &[(usize, usize)],
+    leading: bool,
+    trailing: bool,
+) -> (String, Vec<Highlight>) {
+    let mut text = String::with_capacity(excerpt.len() + 6);
+    if leading {
+        text.push_str("...");
+    }
+    let body_start = text.len();
+
+    // Output offset for each input byte offset (char boundaries only)
+    let mut offsets = vec![0; excerpt.len() + 1];
+    let mut pending_space = false;
+    for (i, c) in excerpt.char_indices() {
+        if c.is_whitespace() {
+            pending_space = text.len() > body_start;
+        } else {
+            if pending_space {
+                text.push(' ');
+                pending_space = false;
+            }
+            offsets[i] = text.len();
+            text.push(c);
+            continue;
+        }
+        offsets[i] = text.len();
+    }
+    offsets[excerpt.len()] = text.len();
+
+    let highlights = merge(matches)
+        .into_iter()
+        .map(|(start, end)| {
+            // A match ends on a non-space char, so map its last char
+            let last = excerpt[..end].chars().next_back().map_or(0, char::len_utf8);
+            to_highlight(&text, offsets[start], offsets[end - last] + last)
+        })
+        .collect();
+
+    if trailing {
+        text.push_str("...");
+    }
+    (text, highlights)
+}
+
+fn to_highlight(text: &str, start: usize, end: usize) -> Highlight {
+    let char_start = text[..start].chars().count();
+    Highlight {
+        start,
+        end,
+        char_start,
+        char_end: char_start + text[start..end].chars().count(),
+    }
+}
+
+/// Largest char boundary at or before `pos`.
+fn floor_char_boundary(text: &str, mut pos: usize) -> usize {
+    while !text.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    pos
+}
+
 /// Extract a snippet around a search term match.
 ///
 /// Returns a portion of the content centered around the first match,
@@ -125,6 +328,36 @@ mod tests {
         assert!(snippet.is_none());
     }
 
+    #[test]
+    fn test_highlight_snippet() {
+        let content = "# Forms\n\nIntro text.\n\nValidate the form   with Zod schemas.\nMore zod below.\n";
+        let snippet = highlight_snippet(content, &["zod", "form"], 12).unwrap();
+
+        assert_eq!(snippet.line, 5);
+        assert_eq!(snippet.text, "...Validate the form with Zod schemas....");
+        let marked: Vec<&str> = snippet
+            .highlights
+            .iter()
+            .map(|h| &snippet.text[h.start..h.end])
+            .collect();
+        assert_eq!(marked, vec!["form", "Zod"]);
+
+        // The whole phrase beats a line with more scattered terms
+        let snippet = highlight_snippet("zod then form\nform zod\n", &["form", "zod"], 0).unwrap();
+        assert_eq!(snippet.line, 2);
+        assert!(highlight_snippet(content, &["missing"], 10).is_none());
+    }
+
+    #[test]
+    fn test_highlight_char_offsets() {
+        let highlights = highlight_terms("Größe der Straße", &["straße"]);
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0].start, 12);
+        assert_eq!(highlights[0].end, 19);
+        assert_eq!(highlights[0].char_start, 10);
+        assert_eq!(highlights[0].char_end, 16);
+    }
+
     #[test]
     fn test_extract_snippet_case_insensitive() {
         let content = "This has a TERM in it";
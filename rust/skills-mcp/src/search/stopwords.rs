@@ -0,0 +1,46 @@
+//! English stop words ignored when extracting query keywords.
+
+/// Common English words that carry no retrieval signal.
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "all", "an", "and", "any", "are", "as", "at", "be", "been", "but", "by",
+    "can", "could", "do", "does", "for", "from", "get", "has", "have", "help", "how", "i",
+    "if", "in", "into", "is", "it", "its", "me", "my", "need", "of", "on", "or", "our",
+    "please", "should", "so", "some", "that", "the", "their", "them", "then", "there",
+    "these", "this", "to", "up", "use", "using", "want", "we", "what", "when", "where",
+    "which", "while", "who", "why", "will", "with", "would", "you", "your",
+];
+
+/// Check whether a lowercase term is a stop word.
+pub fn is_stop_word(term: &str) -> bool {
+    STOP_WORDS.binary_search(&term).is_ok()
+}
+
+/// Split text into lowercase keywords, dropping stop words and punctuation.
+pub fn keywords(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        .map(|t| t.trim_matches(|c| c == '-' || c == '_'))
+        .filter(|t| t.len() > 1 && !is_stop_word(t))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_words_sorted() {
+        let mut sorted = STOP_WORDS.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(sorted, STOP_WORDS);
+    }
+
+    #[test]
+    fn test_keywords() {
+        assert_eq!(
+            keywords("How do I add validation to my React forms?"),
+            vec!["add", "validation", "react", "forms"]
+        );
+    }
+}
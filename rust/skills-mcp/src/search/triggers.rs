@@ -0,0 +1,209 @@
+//! Sub-skill selection by trigger, for testing trigger sets.
+//!
+//! A trigger matches a task phrase fully when the phrase contains all of
+//! its words in order (`react-hook-form` matches "validate with react hook
+//! form"), scoring 1.0. Otherwise it matches partly by the share of its
+//! keywords found in the phrase, scaled by [`PARTIAL_WEIGHT`] so a partial
+//! match never outranks a full one. A sub-skill scores as its best trigger
+//! and is selected when that reaches the threshold.
+//!
+//! Sample phrases can be kept in a plain text file, one per line, with the
+//! expected sub-skills after `=>`:
+//!
+//! ```text
+//! # comments and blank lines are skipped
+//! validate a signup form with zod => validation
+//! style the submit button =>
+//! what is a form
+//! ```
+//!
+//! An empty list after `=>` expects no selection; a line without `=>` has
+//! no expectation and is only reported.
+
+use crate::models::{SkillMeta, SubSkillScore, TriggerCase, TriggerMatch, TriggerTest, TriggerTestReport};
+
+use super::keywords;
+
+/// Score a sub-skill needs to be selected unless told otherwise.
+pub const DEFAULT_TRIGGER_MIN_SCORE: f64 = 0.5;
+
+/// Weight of a trigger matched by only some of its words.
+pub const PARTIAL_WEIGHT: f64 = 0.8;
+
+/// Test `cases` against the triggers of `skill`'s sub-skills.
+pub fn test_triggers(skill: &SkillMeta, cases: &[TriggerCase], min_score: f64) -> TriggerTestReport {
+    let results: Vec<TriggerTest> = cases.iter().map(|case| test_case(skill, case, min_score)).collect();
+    let passed = results.iter().filter(|r| r.passed == Some(true)).count();
+    let failed = results.iter().filter(|r| r.passed == Some(false)).count();
+    TriggerTestReport {
+        skill: skill.name.clone(),
+        min_score,
+        results,
+        passed,
+        failed,
+    }
+}
+
+fn test_case(skill: &SkillMeta, case: &TriggerCase, min_score: f64) -> TriggerTest {
+    let words = words(&case.phrase);
+
+    let mut sub_skills: Vec<SubSkillScore> = skill
+        .sub_skills
+        .iter()
+        .flatten()
+        .filter_map(|sub| {
+            let mut matches: Vec<TriggerMatch> =
+                sub.triggers.iter().filter_map(|t| match_trigger(&words, t)).collect();
+            matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+            let score = matches.first()?.score;
+            Some(SubSkillScore {
+                sub_skill: sub.name.clone(),
+                score,
+                selected: score >= min_score,
+                matches,
+            })
+        })
+        .collect();
+    // Stable, so declaration order breaks ties
+    sub_skills.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let selected: Vec<String> = sub_skills
+        .iter()
+        .filter(|s| s.selected)
+        .map(|s| s.sub_skill.clone())
+        .collect();
+    let passed = case.expect.as_ref().map(|expect| {
+        let mut expect = expect.clone();
+        let mut actual = selected.clone();
+        expect.sort();
+        actual.sort();
+        expect == actual
+    });
+
+    TriggerTest {
+        phrase: case.phrase.clone(),
+        selected,
+        sub_skills,
+        expected: case.expect.clone(),
+        passed,
+    }
+}
+
+/// Lowercase words of `text`, split on anything but letters and digits.
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// How well a trigger matches a phrase's words, if at all.
+fn match_trigger(phrase: &[String], trigger: &str) -> Option<TriggerMatch> {
+    let trigger_words = words(trigger);
+    if trigger_words.is_empty() {
+        return None;
+    }
+
+    let contiguous = phrase.windows(trigger_words.len()).any(|w| w == trigger_words.as_slice());
+    if contiguous {
+        return Some(TriggerMatch {
+            trigger: trigger.to_string(),
+            score: 1.0,
+            matched_terms: trigger_words,
+        });
+    }
+
+    // Stop words alone don't make a match
+    let terms: Vec<String> = keywords(&trigger_words.join(" "));
+    let matched_terms: Vec<String> = terms.iter().filter(|t| phrase.contains(t)).cloned().collect();
+    if matched_terms.is_empty() {
+        return None;
+    }
+    Some(TriggerMatch {
+        trigger: trigger.to_string(),
+        score: PARTIAL_WEIGHT * matched_terms.len() as f64 / terms.len() as f64,
+        matched_terms,
+    })
+}
+
+/// Parse a file of sample phrases (see the module docs).
+pub fn parse_trigger_cases(text: &str) -> Vec<TriggerCase> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split_once("=>") {
+            Some((phrase, expect)) => TriggerCase {
+                phrase: phrase.trim().to_string(),
+                expect: Some(
+                    expect
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|s| !s.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                ),
+            },
+            None => TriggerCase {
+                phrase: line.to_string(),
+                expect: None,
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SubSkillMeta;
+
+    fn skill() -> SkillMeta {
+        let sub = |name: &str, triggers: &[&str]| SubSkillMeta {
+            name: name.to_string(),
+            file: format!("{}.md", name),
+            triggers: triggers.iter().map(|t| t.to_string()).collect(),
+            language: None,
+        };
+        SkillMeta {
+            name: "forms".to_string(),
+            description: "Form handling".to_string(),
+            sub_skills: Some(vec![
+                sub("validation", &["zod", "schema validation"]),
+                sub("react", &["react-hook-form", "useForm"]),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_trigger_selection() {
+        let cases = parse_trigger_cases(
+            "# samples\nValidate a form with React Hook Form => react\nadd schema checks => \nuseForm and zod => validation, react\nstyle the button\n",
+        );
+        assert_eq!(cases.len(), 4);
+        assert_eq!(cases[1].expect, Some(vec![]));
+        assert_eq!(cases[3].expect, None);
+
+        let report = test_triggers(&skill(), &cases, DEFAULT_TRIGGER_MIN_SCORE);
+        assert_eq!((report.passed, report.failed), (3, 0));
+
+        let react = &report.results[0];
+        assert_eq!(react.selected, vec!["react"]);
+        assert_eq!(react.sub_skills[0].matches[0].trigger, "react-hook-form");
+        assert_eq!(react.sub_skills[0].score, 1.0);
+
+        // One of two keywords is a partial match below the threshold
+        let partial = &report.results[1];
+        assert_eq!(partial.sub_skills[0].sub_skill, "validation");
+        assert_eq!(partial.sub_skills[0].score, PARTIAL_WEIGHT / 2.0);
+        assert!(partial.selected.is_empty());
+        assert_eq!(partial.passed, Some(true));
+
+        assert_eq!(report.results[2].passed, Some(true));
+        assert!(report.results[3].sub_skills.is_empty());
+        assert_eq!(report.results[3].passed, None);
+
+        let failing = test_triggers(&skill(), &parse_trigger_cases("zod => react"), 0.5);
+        assert!(!failing.all_passed());
+    }
+}
@@ -0,0 +1,17 @@
+//! Fixtures shared by unit tests.
+
+use std::fs;
+use std::path::Path;
+
+/// Write a skill directory with a `_meta.json` and SKILL.md.
+pub(crate) fn write_skill(dir: &Path, name: &str, meta: &str, content: &str) {
+    let skill_dir = dir.join(name);
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(skill_dir.join("_meta.json"), meta).unwrap();
+    fs::write(skill_dir.join("SKILL.md"), content).unwrap();
+}
+
+/// `_meta.json` contents with only a name and description.
+pub(crate) fn meta(name: &str, description: &str) -> String {
+    format!(r#"{{"name": "{}", "description": "{}"}}"#, name, description)
+}
@@ -1,67 +1,74 @@
 //! Metadata validation.
 
-use regex::Regex;
+use std::collections::HashSet;
 
+use crate::index::reserved_segment;
 use crate::models::SkillMeta;
 
+use super::schema::{self, meta_schema};
+
 /// Validate skill metadata.
 ///
-/// Returns a list of validation errors, or empty if valid.
+/// Checks the metadata against the [`meta_schema`], then the rules that
+/// relate fields to each other. Returns a list of validation errors, or
+/// empty if valid.
 pub fn validate_meta(meta: &SkillMeta) -> Result<(), Vec<String>> {
-    let mut errors = Vec::new();
-
-    // Validate name format: lowercase alphanumeric with hyphens
-    let name_regex = Regex::new(r"^[a-z0-9][a-z0-9-]*[a-z0-9]$|^[a-z0-9]$").unwrap();
-    if !name_regex.is_match(&meta.name) {
-        errors.push(format!(
-            "name: must be lowercase alphanumeric with hyphens, got '{}'",
-            meta.name
-        ));
-    }
+    let value = serde_json::to_value(meta).expect("SkillMeta serializes");
+    let mut errors = schema::check(meta_schema(), &value);
 
-    // Validate name length
-    if meta.name.is_empty() {
-        errors.push("name: cannot be empty".to_string());
-    } else if meta.name.len() > 50 {
+    // Name length, per collection and skill segment
+    if let Some(segment) = meta.name.split('/').find(|s| s.len() > 50) {
         errors.push(format!(
             "name: must be 50 characters or less, got {}",
-            meta.name.len()
+            segment.len()
         ));
     }
+    if let Some(segment) = reserved_segment(&meta.name) {
+        errors.push(format!("name: '{}' is a reserved name on Windows", segment));
+    }
 
-    // Validate description
-    if meta.description.is_empty() {
-        errors.push("description: cannot be empty".to_string());
+    // Existence of linked skills, and dependency cycles, are checked
+    // index-wide
+    if meta.depends_on.contains(&meta.name) {
+        errors.push("depends_on: a skill cannot depend on itself".to_string());
+    }
+    if meta.see_also.contains(&meta.name) {
+        errors.push("see_also: a skill cannot link to itself".to_string());
+    }
+    if meta.superseded_by.as_ref() == Some(&meta.name) {
+        errors.push("superseded_by: a skill cannot supersede itself".to_string());
+    }
+    if meta.aliases.contains(&meta.name) {
+        errors.push("aliases: an alias cannot repeat the skill name".to_string());
+    }
+    if meta.visibility.is_public() && !meta.allowed_keys.is_empty() {
+        errors.push("allowed_keys: only applies to private skills".to_string());
     }
 
-    // Validate sub-skills if present
-    if let Some(sub_skills) = &meta.sub_skills {
-        for (i, sub) in sub_skills.iter().enumerate() {
-            // Validate sub-skill name
-            if sub.name.is_empty() {
-                errors.push(format!("sub_skills[{}].name: cannot be empty", i));
-            }
+    let mut seen_vars = HashSet::new();
+    for (i, var) in meta.variables.iter().enumerate() {
+        if !seen_vars.insert(&var.name) {
+            errors.push(format!("variables: duplicate name '{}'", var.name));
+        }
 
-            // Validate sub-skill file
-            if sub.file.is_empty() {
-                errors.push(format!("sub_skills[{}].file: cannot be empty", i));
-            } else if !sub.file.ends_with(".md") {
+        if let Some(default) = &var.default {
+            if !var.options.is_empty() && !var.options.contains(default) {
                 errors.push(format!(
-                    "sub_skills[{}].file: must end with .md, got '{}'",
-                    i, sub.file
+                    "variables[{}].default: '{}' is not one of the options",
+                    i, default
                 ));
             }
         }
+    }
 
-        // Check for duplicate sub-skill names
-        let mut seen_names = std::collections::HashSet::new();
-        for sub in sub_skills {
-            if !seen_names.insert(&sub.name) {
-                errors.push(format!(
-                    "sub_skills: duplicate name '{}'",
-                    sub.name
-                ));
-            }
+    // Check for duplicate sub-skill names
+    let mut seen_names = HashSet::new();
+    for sub in meta.sub_skills.iter().flatten() {
+        if !seen_names.insert(&sub.name) {
+            errors.push(format!(
+                "sub_skills: duplicate name '{}'",
+                sub.name
+            ));
         }
     }
 
@@ -91,7 +98,7 @@ impl std::fmt::Display for ValidationError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::SubSkillMeta;
+    use crate::models::{SkillVariable, SubSkillMeta};
 
     #[test]
     fn test_valid_minimal_meta() {
@@ -101,6 +108,7 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
 
         assert!(validate_meta(&meta).is_ok());
@@ -117,9 +125,11 @@ mod tests {
                     name: "buttons".to_string(),
                     file: "buttons/SKILL.md".to_string(),
                     triggers: vec!["Button".to_string()],
+                    language: None,
                 },
             ]),
             source: Some("official".to_string()),
+            ..Default::default()
         };
 
         assert!(validate_meta(&meta).is_ok());
@@ -133,6 +143,7 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
 
         let result = validate_meta(&meta);
@@ -148,6 +159,7 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
 
         let result = validate_meta(&meta);
@@ -162,6 +174,7 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
 
         let result = validate_meta(&meta);
@@ -179,8 +192,10 @@ mod tests {
                 name: "react".to_string(),
                 file: "react/SKILL.txt".to_string(), // Wrong extension
                 triggers: vec![],
+                language: None,
             }]),
             source: None,
+            ..Default::default()
         };
 
         let result = validate_meta(&meta);
@@ -199,14 +214,17 @@ mod tests {
                     name: "react".to_string(),
                     file: "react/SKILL.md".to_string(),
                     triggers: vec![],
+                    language: None,
                 },
                 SubSkillMeta {
                     name: "react".to_string(), // Duplicate
                     file: "react2/SKILL.md".to_string(),
                     triggers: vec![],
+                    language: None,
                 },
             ]),
             source: None,
+            ..Default::default()
         };
 
         let result = validate_meta(&meta);
@@ -222,8 +240,74 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
 
         assert!(validate_meta(&meta).is_ok());
     }
+
+    #[test]
+    fn test_collection_name() {
+        let meta = |name: &str| SkillMeta {
+            name: name.to_string(),
+            description: "Namespaced".to_string(),
+            ..Default::default()
+        };
+
+        assert!(validate_meta(&meta("frontend/forms")).is_ok());
+        assert!(validate_meta(&meta("a/b/c")).is_err());
+        assert!(validate_meta(&meta("/forms")).is_err());
+        assert!(validate_meta(&meta("frontend/")).is_err());
+    }
+
+    #[test]
+    fn test_reserved_name() {
+        let meta = |name: &str| SkillMeta {
+            name: name.to_string(),
+            description: "Reserved".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(validate_meta(&meta("con")).unwrap_err(), vec!["name: 'con' is a reserved name on Windows"]);
+        assert!(validate_meta(&meta("frontend/aux")).is_err());
+        assert!(validate_meta(&meta("console")).is_ok());
+    }
+
+    #[test]
+    fn test_cross_field_rules() {
+        let mut meta = SkillMeta {
+            name: "forms".to_string(),
+            description: "Forms".to_string(),
+            depends_on: vec!["forms".to_string()],
+            see_also: vec!["tables".to_string(), "tables".to_string()],
+            variables: vec![SkillVariable {
+                name: "framework".to_string(),
+                description: None,
+                required: false,
+                default: Some("vue".to_string()),
+                options: vec!["react".to_string()],
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_meta(&meta).unwrap_err(),
+            vec![
+                "see_also: duplicate 'tables'",
+                "depends_on: a skill cannot depend on itself",
+                "variables[0].default: 'vue' is not one of the options",
+            ]
+        );
+
+        meta.name = format!("{}/forms", "a".repeat(51));
+        let errors = validate_meta(&meta).unwrap_err();
+        assert!(errors.iter().any(|e| e.contains("50 characters or less")));
+
+        meta.allowed_keys = vec!["0123456789ab".to_string()];
+        let errors = validate_meta(&meta).unwrap_err();
+        assert!(errors.contains(&"allowed_keys: only applies to private skills".to_string()));
+
+        meta.superseded_by = Some(meta.name.clone());
+        let errors = validate_meta(&meta).unwrap_err();
+        assert!(errors.contains(&"superseded_by: a skill cannot supersede itself".to_string()));
+    }
 }
@@ -4,7 +4,9 @@
 //! matching the Zod validation in the TypeScript implementation.
 
 mod meta;
+mod schema;
 mod skills;
 
 pub use meta::validate_meta;
+pub use schema::meta_schema;
 pub use skills::{validate_skills, SkillValidator};
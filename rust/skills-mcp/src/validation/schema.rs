@@ -0,0 +1,378 @@
+//! JSON Schema for `_meta.json`.
+//!
+//! The schema is served at `GET /api/schema/meta` so editors can validate
+//! and complete `_meta.json` files (point `"$schema"` at it), and
+//! [`validate_meta`](super::validate_meta) checks metadata against the
+//! same document before applying the rules a schema can't express.
+//!
+//! Only the keywords the schema uses are implemented: `type`,
+//! `properties`, `required`, `items`, `minLength`, `maxLength`,
+//! `pattern`, `uniqueItems`, `enum`, `not`, and local `$ref`s into
+//! `definitions`. A failed `pattern` is reported with the schema's
+//! `x-pattern-message`. Each value reports at most one error.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+use crate::models::SkillMeta;
+
+/// Variable names that would clash with `get_skill` arguments.
+const RESERVED_VARIABLES: &[&str] = &["name", "minify", "cursor", "lang"];
+
+/// The JSON Schema (draft-07) for `_meta.json`.
+pub fn meta_schema() -> &'static Value {
+    static SCHEMA: OnceLock<Value> = OnceLock::new();
+    SCHEMA.get_or_init(build_meta_schema)
+}
+
+fn build_meta_schema() -> Value {
+    let names = json!({
+        "type": "array",
+        "items": { "$ref": "#/definitions/skillName" },
+        "uniqueItems": true,
+    });
+    let language = json!({
+        "type": "string",
+        "description": "ISO 639-1 language code with an optional region.",
+        "pattern": "^[a-z]{2,3}(-[A-Za-z0-9]{2,4})?$",
+        "x-pattern-message": "must be a language code like 'en' or 'pt-BR'",
+    });
+    let timestamp = json!({
+        "type": "string",
+        "format": "date-time",
+        "description": "Set by the indexer; not read from _meta.json.",
+    });
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Skill metadata (_meta.json)",
+        "type": "object",
+        "required": ["name", "description"],
+        "properties": {
+            "$schema": { "type": "string" },
+            "name": {
+                "description": "Skill identifier; must match the directory name, optionally prefixed by one collection (frontend/forms).",
+                "$ref": "#/definitions/skillName",
+            },
+            "description": {
+                "type": "string",
+                "description": "What the skill provides.",
+                "minLength": 1,
+            },
+            "tags": {
+                "type": "array",
+                "description": "Search tags.",
+                "items": { "type": "string" },
+            },
+            "aliases": names.clone(),
+            "sub_skills": {
+                "type": "array",
+                "description": "Nested sub-skills of a domain skill.",
+                "items": { "$ref": "#/definitions/subSkill" },
+            },
+            "source": {
+                "type": "string",
+                "description": "Origin of the skill, e.g. community or official.",
+            },
+            "quick_answer": {
+                "type": "string",
+                "description": "Short answer for simple questions. Filled from a TL;DR section of SKILL.md when absent.",
+                "maxLength": SkillMeta::MAX_QUICK_ANSWER_CHARS,
+                "pattern": "\\S",
+                "x-pattern-message": "cannot be blank",
+            },
+            "depends_on": names.clone(),
+            "see_also": names,
+            "variables": {
+                "type": "array",
+                "description": "Template variables used as {{name}} in SKILL.md.",
+                "items": { "$ref": "#/definitions/variable" },
+            },
+            "language": language.clone(),
+            "archived": {
+                "type": "boolean",
+                "description": "Hide the skill from listings and search without deleting it.",
+            },
+            "deprecated": {
+                "type": "boolean",
+                "description": "Keep the skill readable but stop recommending it.",
+            },
+            "superseded_by": {
+                "description": "Skill replacing this one; implies deprecated.",
+                "$ref": "#/definitions/skillName",
+            },
+            "visibility": {
+                "type": "string",
+                "description": "Who can list, search, and read the skill; private skills only for allowed_keys.",
+                "enum": ["public", "private"],
+            },
+            "allowed_keys": {
+                "type": "array",
+                "description": "Fingerprints of the API keys that can see a private skill.",
+                "items": {
+                    "type": "string",
+                    "pattern": "^(key:)?[0-9a-f]{64}$",
+                    "x-pattern-message": "must be an API key fingerprint",
+                },
+                "uniqueItems": true,
+            },
+            "root": { "type": "string", "description": "Set by the indexer; not read from _meta.json." },
+            "created_at": timestamp.clone(),
+            "updated_at": timestamp,
+        },
+        "definitions": {
+            "skillName": {
+                "type": "string",
+                "minLength": 1,
+                "pattern": "^(?:(?:[a-z0-9][a-z0-9-]*[a-z0-9]|[a-z0-9])/)?(?:[a-z0-9][a-z0-9-]*[a-z0-9]|[a-z0-9])$",
+                "x-pattern-message": "must be lowercase alphanumeric with hyphens",
+            },
+            "subSkill": {
+                "type": "object",
+                "required": ["name", "file"],
+                "properties": {
+                    "name": { "type": "string", "minLength": 1 },
+                    "file": {
+                        "type": "string",
+                        "description": "Markdown file, relative to the skill directory.",
+                        "minLength": 1,
+                        "pattern": "\\.md$",
+                        "x-pattern-message": "must end with .md",
+                    },
+                    "triggers": {
+                        "type": "array",
+                        "description": "Keywords for search discovery.",
+                        "items": { "type": "string" },
+                    },
+                    "language": language,
+                },
+            },
+            "variable": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "pattern": "^[A-Za-z_][A-Za-z0-9_]*$",
+                        "x-pattern-message": "must be an identifier",
+                        "not": { "enum": RESERVED_VARIABLES },
+                    },
+                    "description": { "type": "string" },
+                    "required": { "type": "boolean" },
+                    "default": { "type": "string" },
+                    "options": { "type": "array", "items": { "type": "string" } },
+                },
+            },
+        },
+    })
+}
+
+/// Check a value against a schema, returning `path: message` errors.
+///
+/// `$ref`s resolve against `schema` itself.
+pub fn check(schema: &Value, value: &Value) -> Vec<String> {
+    let mut checker = Checker {
+        root: schema,
+        regexes: HashMap::new(),
+        errors: Vec::new(),
+    };
+    checker.check(schema, value, "");
+    checker.errors
+}
+
+struct Checker<'a> {
+    root: &'a Value,
+    regexes: HashMap<&'a str, Regex>,
+    errors: Vec<String>,
+}
+
+impl<'a> Checker<'a> {
+    fn check(&mut self, schema: &'a Value, value: &Value, path: &str) {
+        if let Some(message) = self.first_error(schema, value) {
+            self.errors.push(if path.is_empty() {
+                message
+            } else {
+                format!("{}: {}", path, message)
+            });
+            return;
+        }
+
+        let schema = self.resolve(schema);
+        if let (Some(properties), Some(object)) = (schema["properties"].as_object(), value.as_object()) {
+            for (key, property) in properties {
+                if let Some(field) = object.get(key) {
+                    self.check(property, field, &join(path, key));
+                }
+            }
+        }
+        if let Some(items) = value.as_array() {
+            if schema.get("items").is_some() {
+                for (i, item) in items.iter().enumerate() {
+                    self.check(&schema["items"], item, &format!("{}[{}]", path, i));
+                }
+            }
+        }
+    }
+
+    /// The first failed keyword on `value` itself, ignoring nested values.
+    fn first_error(&mut self, schema: &'a Value, value: &Value) -> Option<String> {
+        let schema = self.resolve(schema);
+
+        if let Some(kind) = schema["type"].as_str() {
+            if !has_type(value, kind) {
+                return Some(format!("must be {} {}", article(kind), kind));
+            }
+        }
+
+        if let Some(required) = schema["required"].as_array() {
+            let missing: Vec<&str> = required
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|key| value.get(key).is_none())
+                .collect();
+            if !missing.is_empty() {
+                return Some(format!("missing {}", missing.join(", ")));
+            }
+        }
+
+        if let Some(text) = value.as_str() {
+            let length = text.chars().count() as u64;
+            if schema["minLength"].as_u64().is_some_and(|min| length < min) {
+                return Some(if length == 0 {
+                    "cannot be empty".to_string()
+                } else {
+                    format!("must be at least {} characters", schema["minLength"])
+                });
+            }
+            if let Some(max) = schema["maxLength"].as_u64().filter(|max| length > *max) {
+                return Some(format!("must be {} characters or less, got {}", max, length));
+            }
+            if let Some(pattern) = schema["pattern"].as_str() {
+                if !self.regex(pattern).is_match(text) {
+                    let message = schema["x-pattern-message"]
+                        .as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("must match {}", pattern));
+                    return Some(format!("{}, got '{}'", message, text));
+                }
+            }
+        }
+
+        if schema["uniqueItems"].as_bool() == Some(true) {
+            if let Some(items) = value.as_array() {
+                let duplicate = items.iter().enumerate().find(|(i, item)| items[..*i].contains(item));
+                if let Some((_, item)) = duplicate {
+                    return Some(format!("duplicate {}", display(item)));
+                }
+            }
+        }
+
+        if let Some(allowed) = schema["enum"].as_array() {
+            if !allowed.contains(value) {
+                let allowed: Vec<String> = allowed.iter().map(display).collect();
+                return Some(format!("must be one of {}", allowed.join(", ")));
+            }
+        }
+
+        if let Some(not) = schema.get("not") {
+            if self.first_error(not, value).is_none() {
+                return Some(format!("{} is reserved", display(value)));
+            }
+        }
+
+        None
+    }
+
+    fn resolve(&self, schema: &'a Value) -> &'a Value {
+        match schema["$ref"].as_str().and_then(|r| r.strip_prefix("#/definitions/")) {
+            Some(name) => &self.root["definitions"][name],
+            None => schema,
+        }
+    }
+
+    fn regex(&mut self, pattern: &'a str) -> &Regex {
+        self.regexes
+            .entry(pattern)
+            .or_insert_with(|| Regex::new(pattern).expect("schema patterns are valid"))
+    }
+}
+
+fn has_type(value: &Value, kind: &str) -> bool {
+    match kind {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn article(kind: &str) -> &'static str {
+    if kind.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Strings in quotes, anything else as JSON.
+fn display(value: &Value) -> String {
+    match value.as_str() {
+        Some(s) => format!("'{}'", s),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_meta_schema() {
+        let schema = meta_schema();
+        let errors = check(
+            schema,
+            &json!({
+                "$schema": "http://localhost:5050/api/schema/meta",
+                "name": "Forms",
+                "description": "",
+                "tags": "forms",
+                "see_also": ["tables", "tables"],
+                "sub_skills": [{ "name": "react", "file": "react.txt" }, { "file": "" }],
+                "variables": [{ "name": "minify" }, { "name": "2fa" }],
+                "language": "english",
+            }),
+        );
+        assert_eq!(
+            errors,
+            vec![
+                "description: cannot be empty",
+                "language: must be a language code like 'en' or 'pt-BR', got 'english'",
+                "name: must be lowercase alphanumeric with hyphens, got 'Forms'",
+                "see_also: duplicate 'tables'",
+                "sub_skills[0].file: must end with .md, got 'react.txt'",
+                "sub_skills[1]: missing name",
+                "tags: must be an array",
+                "variables[0].name: 'minify' is reserved",
+                "variables[1].name: must be an identifier, got '2fa'",
+            ]
+        );
+
+        assert!(check(schema, &json!({ "name": "frontend/forms", "description": "Forms" })).is_empty());
+        assert_eq!(check(schema, &json!({ "name": "forms" })), vec!["missing description"]);
+    }
+}
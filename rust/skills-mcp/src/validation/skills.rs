@@ -5,7 +5,8 @@ use std::sync::Arc;
 
 use tracing::debug;
 
-use crate::index::SkillIndexer;
+use crate::markdown;
+use crate::index::{BrokenLinkReason, DependencyGraph, LinkGraph, SkillIndexer};
 use crate::models::{SkillMeta, ValidationResult};
 
 use super::validate_meta;
@@ -31,11 +32,80 @@ impl SkillValidator {
             result.add_error(error.clone());
         }
 
+        // Note skills hidden by higher-priority roots
+        for shadowed in &index.shadowed {
+            result.add_warning(format!(
+                "{}: Copy in {} is shadowed by {}",
+                shadowed.name, shadowed.root, shadowed.shadowed_by
+            ));
+        }
+
+        // Note files that couldn't be indexed in full
+        for warning in &self.indexer.get_content_index().warnings {
+            result.add_warning(warning.to_string());
+        }
+
         // Validate each skill
         for skill in &index.skills {
             self.validate_skill(skill, &mut result);
         }
 
+        // Validate the dependency graph
+        let graph = DependencyGraph::new(&index);
+        for (skill, dep) in graph.missing() {
+            result.add_error(format!("{}: Depends on unknown skill '{}'", skill, dep));
+        }
+        for cycle in graph.cycles() {
+            result.add_error(format!("Dependency cycle: {}", cycle.join(" -> ")));
+        }
+
+        // Flag curated links to skills that no longer exist
+        for skill in &index.skills {
+            for link in skill.see_also.iter().filter(|l| index.find(l).is_none()) {
+                result.add_warning(format!("{}: See-also link to unknown skill '{}'", skill.name, link));
+            }
+        }
+
+        // Successors of deprecated skills must exist
+        for skill in &index.skills {
+            if let Some(successor) = skill.superseded_by.as_ref().filter(|s| index.find(s).is_none()) {
+                result.add_error(format!("{}: Superseded by unknown skill '{}'", skill.name, successor));
+            }
+        }
+
+        // Aliases must resolve to exactly one skill
+        for skill in &index.skills {
+            for alias in &skill.aliases {
+                if index.skills.iter().any(|s| s.name == *alias) {
+                    result.add_error(format!("{}: Alias '{}' is the name of another skill", skill.name, alias));
+                }
+                for other in index.skills.iter().filter(|s| s.name > skill.name && s.aliases.contains(alias)) {
+                    result.add_error(format!(
+                        "{}: Alias '{}' is also an alias of '{}'",
+                        skill.name, alias, other.name
+                    ));
+                }
+            }
+        }
+
+        // Flag broken intra-library links
+        let links = LinkGraph::new(
+            self.indexer.skills_dir(),
+            &index,
+            &self.indexer.get_content_index(),
+        );
+        for link in links.broken() {
+            let reason = match link.reason {
+                BrokenLinkReason::MissingSkill => "missing skill",
+                BrokenLinkReason::MissingFile => "missing file",
+                BrokenLinkReason::OutsideLibrary => "outside skills directory",
+            };
+            result.add_warning(format!(
+                "{}: Broken link in {} to '{}' ({})",
+                link.source_skill, link.source_file, link.target, reason
+            ));
+        }
+
         debug!(
             "Validated {} skills: {} errors, {} warnings",
             result.skills_checked,
@@ -48,7 +118,7 @@ impl SkillValidator {
 
     /// Validate a single skill.
     fn validate_skill(&self, skill: &SkillMeta, result: &mut ValidationResult) {
-        let skill_dir = self.indexer.skills_dir().join(&skill.name);
+        let skill_dir = self.indexer.skill_dir(&skill.name);
 
         // Validate metadata
         if let Err(errors) = validate_meta(skill) {
@@ -63,6 +133,17 @@ impl SkillValidator {
             result.add_error(format!("{}: Missing SKILL.md", skill.name));
         } else if std::fs::metadata(&skill_md).map(|m| m.len()).unwrap_or(0) == 0 {
             result.add_warning(format!("{}: SKILL.md is empty", skill.name));
+        } else if !skill.variables.is_empty() {
+            // Skills that declare variables should declare all of them
+            let content = std::fs::read_to_string(&skill_md).unwrap_or_default();
+            for name in markdown::find_variables(&content) {
+                if !skill.variables.iter().any(|v| v.name == name) {
+                    result.add_warning(format!(
+                        "{}: SKILL.md uses undeclared variable '{{{{{}}}}}'",
+                        skill.name, name
+                    ));
+                }
+            }
         }
 
         // Validate sub-skills
@@ -178,6 +259,7 @@ mod tests {
             tags: vec!["validation".to_string()],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
         create_skill(temp_dir.path(), &meta, true);
 
@@ -199,6 +281,7 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
         create_skill(temp_dir.path(), &meta, false);
 
@@ -222,8 +305,10 @@ mod tests {
                 name: "react".to_string(),
                 file: "react/SKILL.md".to_string(),
                 triggers: vec![],
+                language: None,
             }]),
             source: None,
+            ..Default::default()
         };
 
         // Create skill but don't create sub-skill file
@@ -244,6 +329,83 @@ mod tests {
         assert!(result.errors.iter().any(|e| e.contains("Sub-skill file not found")));
     }
 
+    #[test]
+    fn test_validate_dependencies() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for (name, deps) in [("forms", vec!["validation", "ghost"]), ("validation", vec!["forms"])] {
+            let meta = SkillMeta {
+                name: name.to_string(),
+                description: format!("{} patterns", name),
+                tags: vec![name.to_string()],
+                depends_on: deps.into_iter().map(String::from).collect(),
+                ..Default::default()
+            };
+            create_skill(temp_dir.path(), &meta, true);
+        }
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+
+        let result = validate_skills(indexer);
+        assert!(!result.valid);
+        assert!(result
+            .errors
+            .contains(&"forms: Depends on unknown skill 'ghost'".to_string()));
+        assert!(result
+            .errors
+            .contains(&"Dependency cycle: forms -> validation -> forms".to_string()));
+    }
+
+    #[test]
+    fn test_validate_superseded_by() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for (name, successor) in [("forms", Some("forms-v2")), ("tables", Some("ghost")), ("forms-v2", None)] {
+            let meta = SkillMeta {
+                name: name.to_string(),
+                description: format!("{} patterns", name),
+                tags: vec![name.to_string()],
+                superseded_by: successor.map(String::from),
+                ..Default::default()
+            };
+            create_skill(temp_dir.path(), &meta, true);
+        }
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+
+        let result = validate_skills(indexer);
+        assert_eq!(result.errors, vec!["tables: Superseded by unknown skill 'ghost'"]);
+    }
+
+    #[test]
+    fn test_validate_alias_collisions() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for (name, aliases) in [("forms", vec!["validation", "inputs"]), ("validation", vec!["inputs"])] {
+            let meta = SkillMeta {
+                name: name.to_string(),
+                description: format!("{} patterns", name),
+                tags: vec![name.to_string()],
+                aliases: aliases.into_iter().map(String::from).collect(),
+                ..Default::default()
+            };
+            create_skill(temp_dir.path(), &meta, true);
+        }
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+
+        let result = validate_skills(indexer);
+        assert!(result
+            .errors
+            .contains(&"forms: Alias 'validation' is the name of another skill".to_string()));
+        assert!(result
+            .errors
+            .contains(&"forms: Alias 'inputs' is also an alias of 'validation'".to_string()));
+    }
+
     #[test]
     fn test_validate_no_tags_warning() {
         let temp_dir = TempDir::new().unwrap();
@@ -254,6 +416,7 @@ mod tests {
             tags: vec![],
             sub_skills: None,
             source: None,
+            ..Default::default()
         };
         create_skill(temp_dir.path(), &meta, true);
 
@@ -264,4 +427,26 @@ mod tests {
         assert!(result.valid); // Warnings don't make it invalid
         assert!(result.warnings.iter().any(|w| w.contains("No tags")));
     }
+
+    #[test]
+    fn test_validate_reports_indexing_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let meta = SkillMeta {
+            name: "forms".to_string(),
+            description: "Form handling".to_string(),
+            tags: vec!["forms".to_string()],
+            ..Default::default()
+        };
+        create_skill(temp_dir.path(), &meta, true);
+        fs::write(temp_dir.path().join("forms/SKILL.md"), b"# Forms \xfe\n").unwrap();
+
+        let indexer = Arc::new(SkillIndexer::new(temp_dir.path()));
+        indexer.reload().unwrap();
+
+        let result = validate_skills(indexer);
+        assert!(result.valid);
+        assert!(result
+            .warnings
+            .contains(&"forms: SKILL.md: Invalid UTF-8 replaced with U+FFFD".to_string()));
+    }
 }